@@ -0,0 +1,310 @@
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::abort_call_site;
+use quote::quote;
+use syn::{
+    parse_macro_input, token::Comma, DeriveInput, Expr, GenericParam, Ident, Lit, Type, TypeArray,
+};
+
+use crate::utils::*;
+
+// Single encoded member, that is either a field or an element of the array field.
+// Access is relative to the struct or its witness
+struct Item {
+    access: TokenStream,
+    ty: Type,
+}
+
+struct Members {
+    items: Vec<Item>,
+    // how to initialize every field from the decoded items
+    initializations: TokenStream,
+}
+
+fn parse_integer_literal(expr: &Expr) -> usize {
+    match expr {
+        Expr::Lit(ref lit) => match lit.lit {
+            Lit::Int(ref int) => int
+                .base10_parse::<usize>()
+                .expect("must be a valid integer"),
+            _ => abort_call_site!("only integer literals are allowed"),
+        },
+        _ => abort_call_site!("only integer literals are allowed as array lengths"),
+    }
+}
+
+fn collect_members(data: &syn::Data) -> Members {
+    let mut items = vec![];
+    let mut initializations = TokenStream::new();
+
+    match data {
+        syn::Data::Struct(ref struct_data) => match struct_data.fields {
+            syn::Fields::Named(ref named_fields) => {
+                for field in named_fields.named.iter() {
+                    let field_ident = field.ident.clone().expect("should have a field elem ident");
+                    match field.ty {
+                        Type::Path(_) => {
+                            let item_ident = get_item_ident(items.len());
+                            initializations.extend(quote! {
+                                #field_ident: #item_ident,
+                            });
+                            items.push(Item {
+                                access: quote! { #field_ident },
+                                ty: field.ty.clone(),
+                            });
+                        }
+                        Type::Array(TypeArray {
+                            ref elem, ref len, ..
+                        }) => {
+                            match **elem {
+                                Type::Path(_) => {}
+                                _ => abort_call_site!("only arrays of path types are allowed"),
+                            };
+                            let len = parse_integer_literal(len);
+                            let mut item_idents = TokenStream::new();
+                            for idx in 0..len {
+                                let item_ident = get_item_ident(items.len());
+                                item_idents.extend(quote! { #item_ident, });
+                                items.push(Item {
+                                    access: quote! { #field_ident[#idx] },
+                                    ty: (**elem).clone(),
+                                });
+                            }
+                            initializations.extend(quote! {
+                                #field_ident: [#item_idents],
+                            });
+                        }
+                        _ => abort_call_site!("only array and path types are allowed"),
+                    };
+                }
+            }
+            _ => abort_call_site!("only named fields are allowed!"),
+        },
+        _ => abort_call_site!("only struct types are allowed!"),
+    }
+
+    Members {
+        items,
+        initializations,
+    }
+}
+
+fn get_item_ident(idx: usize) -> Ident {
+    Ident::new(&format!("__item_{}", idx), Span::call_site())
+}
+
+// (packed width, encoding length) of every item as expected by `derived_encoding_length`
+fn get_layout(items: &[Item]) -> TokenStream {
+    let mut layout = TokenStream::new();
+    for Item { ty, .. } in items.iter() {
+        layout.extend(quote! {
+            (<#ty as CircuitEncodingLayout<F>>::PACKED_WIDTH, <#ty as CircuitEncodingLayout<F>>::ENCODING_LENGTH),
+        });
+    }
+
+    quote! {
+        [#layout]
+    }
+}
+
+fn get_where_clause(generics: &syn::Generics) -> TokenStream {
+    if let Some(clause) = generics.where_clause.as_ref() {
+        quote! {
+            #clause
+        }
+    } else {
+        quote! {}
+    }
+}
+
+// encodings of the members are only usable if their lengths are known to be well formed
+fn get_where_clause_with_member_lengths(generics: &syn::Generics, items: &[Item]) -> TokenStream {
+    let mut predicates = TokenStream::new();
+    if let Some(clause) = generics.where_clause.as_ref() {
+        for predicate in clause.predicates.iter() {
+            predicates.extend(quote! { #predicate, });
+        }
+    }
+
+    let mut member_types: Vec<&Type> = vec![];
+    for Item { ty, .. } in items.iter() {
+        if member_types.iter().any(|el| *el == ty) {
+            continue;
+        }
+        member_types.push(ty);
+        predicates.extend(quote! {
+            [(); <#ty as CircuitEncodingLayout<F>>::ENCODING_LENGTH]:,
+        });
+    }
+
+    quote! {
+        where #predicates
+    }
+}
+
+fn check_generics(generics: &syn::Generics) {
+    let field_generic_param = syn::parse_str::<GenericParam>(&"F: SmallField").unwrap();
+    let has_engine_param = has_proper_small_field_parameter(&generics.params, &field_generic_param);
+    if has_engine_param == false {
+        panic!("Expected to have `F: SmallField` somewhere in bounds");
+    }
+}
+
+pub(crate) fn derive_encodable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let derived_input = parse_macro_input!(input as DeriveInput);
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        ..
+    } = derived_input.clone();
+
+    check_generics(&generics);
+
+    let Members {
+        items,
+        initializations,
+    } = collect_members(&data);
+    let layout = get_layout(&items);
+
+    let mut encoding_impls = TokenStream::new();
+    let mut decoding_impls = TokenStream::new();
+    for (idx, Item { access, ty }) in items.iter().enumerate() {
+        encoding_impls.extend(quote! {
+            encode_member(cs, &self.#access, &mut members);
+        });
+
+        let item_ident = get_item_ident(idx);
+        decoding_impls.extend(quote! {
+            let #item_ident: #ty = decode_member(cs, &mut members);
+        });
+    }
+
+    let comma = Comma(Span::call_site());
+    let type_params_of_allocated_struct = get_type_params_from_generics(&generics, &comma);
+
+    let where_clause = get_where_clause(&generics);
+    let where_clause_with_member_lengths = get_where_clause_with_member_lengths(&generics, &items);
+    let encoding_length = quote! {
+        <#ident<#type_params_of_allocated_struct> as CircuitEncodingLayout<F>>::ENCODING_LENGTH
+    };
+
+    let expanded = quote! {
+        impl #generics CircuitEncodingLayout<F> for #ident<#type_params_of_allocated_struct> #where_clause {
+            const ENCODING_LENGTH: usize = derived_encoding_length(&#layout);
+        }
+
+        impl #generics CircuitEncodable<F, { #encoding_length }> for #ident<#type_params_of_allocated_struct> #where_clause_with_member_lengths {
+            fn encode<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> [Variable; #encoding_length] {
+                let mut members = vec![];
+                #encoding_impls
+
+                encode_members(cs, &#layout, &members)
+            }
+        }
+
+        impl #generics CircuitDecodable<F, { #encoding_length }> for #ident<#type_params_of_allocated_struct> #where_clause_with_member_lengths {
+            fn decode<CS: ConstraintSystem<F>>(cs: &mut CS, encoding: [Variable; #encoding_length]) -> Self {
+                let members = decode_members(cs, &#layout, &encoding);
+                let mut members = &members[..];
+                #decoding_impls
+
+                Self {
+                    #initializations
+                }
+            }
+        }
+    };
+
+    proc_macro::TokenStream::from(expanded)
+}
+
+pub(crate) fn derive_encodable_ext(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let derived_input = parse_macro_input!(input as DeriveInput);
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        ..
+    } = derived_input.clone();
+
+    check_generics(&generics);
+
+    let Members { items, .. } = collect_members(&data);
+
+    let comma = Comma(Span::call_site());
+    let type_params_of_allocated_struct = get_type_params_from_generics(&generics, &comma);
+
+    let where_clause_with_member_lengths = get_where_clause_with_member_lengths(&generics, &items);
+    let encoding_length = quote! {
+        <#ident<#type_params_of_allocated_struct> as CircuitEncodingLayout<F>>::ENCODING_LENGTH
+    };
+
+    let expanded = quote! {
+        impl #generics CircuitEncodableExt<F, { #encoding_length }> for #ident<#type_params_of_allocated_struct> #where_clause_with_member_lengths {}
+    };
+
+    proc_macro::TokenStream::from(expanded)
+}
+
+pub(crate) fn derive_public_input(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let derived_input = parse_macro_input!(input as DeriveInput);
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        ..
+    } = derived_input.clone();
+
+    check_generics(&generics);
+
+    let Members {
+        items,
+        initializations,
+    } = collect_members(&data);
+    let layout = get_layout(&items);
+
+    // same layout as for `CSEncodable`, but over the witness values
+    let mut encoding_impls = TokenStream::new();
+    let mut decoding_impls = TokenStream::new();
+    for (idx, Item { access, ty }) in items.iter().enumerate() {
+        encoding_impls.extend(quote! {
+            encode_member_witness(std::marker::PhantomData::<#ty>, &witness.#access, &mut members);
+        });
+
+        let item_ident = get_item_ident(idx);
+        decoding_impls.extend(quote! {
+            let #item_ident = decode_member_witness(std::marker::PhantomData::<#ty>, &mut members)?;
+        });
+    }
+
+    let comma = Comma(Span::call_site());
+    let type_params_of_allocated_struct = get_type_params_from_generics(&generics, &comma);
+    let where_clause_with_member_lengths = get_where_clause_with_member_lengths(&generics, &items);
+    let witness_ident = get_witness_ident(&ident);
+    let encoding_length = quote! {
+        <#ident<#type_params_of_allocated_struct> as CircuitEncodingLayout<F>>::ENCODING_LENGTH
+    };
+
+    let expanded = quote! {
+        impl #generics CSPublicInput<F, { #encoding_length }> for #ident<#type_params_of_allocated_struct> #where_clause_with_member_lengths {
+            fn encode_witness(witness: &Self::Witness) -> [F; #encoding_length] {
+                let mut members = vec![];
+                #encoding_impls
+
+                encode_member_witnesses(&#layout, &members)
+            }
+
+            fn decode_witness(encoding: [F; #encoding_length]) -> Option<Self::Witness> {
+                let members = decode_member_witnesses(&#layout, &encoding)?;
+                let mut members = &members[..];
+                #decoding_impls
+
+                Some(#witness_ident {
+                    #initializations
+                })
+            }
+        }
+    };
+
+    proc_macro::TokenStream::from(expanded)
+}
@@ -4,6 +4,7 @@
 use proc_macro::TokenStream;
 
 mod allocatable;
+mod encodable;
 mod selectable;
 pub(crate) mod utils;
 mod var_length_encodable;
@@ -33,6 +34,24 @@ pub fn derive_var_length_encodable(input: TokenStream) -> TokenStream {
     self::var_length_encodable::derive_var_length_encodable(input)
 }
 
+#[proc_macro_derive(CSEncodable)]
+#[proc_macro_error::proc_macro_error]
+pub fn derive_encodable(input: TokenStream) -> TokenStream {
+    self::encodable::derive_encodable(input)
+}
+
+#[proc_macro_derive(CSEncodableExt)]
+#[proc_macro_error::proc_macro_error]
+pub fn derive_encodable_ext(input: TokenStream) -> TokenStream {
+    self::encodable::derive_encodable_ext(input)
+}
+
+#[proc_macro_derive(CSPublicInput)]
+#[proc_macro_error::proc_macro_error]
+pub fn derive_public_input(input: TokenStream) -> TokenStream {
+    self::encodable::derive_public_input(input)
+}
+
 // #[proc_macro_derive(CSOrdering)]
 // pub fn derive_ord(input: TokenStream) -> TokenStream{
 //     self::ord::derive_ord(input)
@@ -357,3 +357,9 @@ use crate::implementations::poseidon2::Poseidon2Goldilocks;
 
 pub type GoldilocksPoseidon2Sponge<M> =
     SimpleAlgebraicSponge<GoldilocksField, 8, 12, 4, Poseidon2Goldilocks, M>;
+
+use crate::field::baby_bear::BabyBearField;
+use crate::implementations::poseidon2_baby_bear::Poseidon2BabyBear;
+
+pub type BabyBearPoseidon2Sponge<M> =
+    SimpleAlgebraicSponge<BabyBearField, 8, 16, 8, Poseidon2BabyBear, M>;
@@ -3,19 +3,22 @@ use crate::field::{ExtensionField, FieldExtension};
 
 use super::*;
 
-// A simple gate of c0 * A * B + c1 * C -> D in the extension field
+// A simple gate of c0 * A * B + c1 * C -> D in the extension field. Only quadratic extensions are supported,
+// but the degree is a parameter, so code that is generic over the extension degree can still name the gate
 
 #[derive(Derivative)]
 #[derivative(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct FmaGateInExtensionWithoutConstantConstraintEvaluator<
     F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+    const DEGREE: usize = 2,
 > {
     _marker: std::marker::PhantomData<(F, EXT)>,
 }
 
-impl<F: PrimeField, EXT: FieldExtension<2, BaseField = F>> GateConstraintEvaluator<F>
-    for FmaGateInExtensionWithoutConstantConstraintEvaluator<F, EXT>
+impl<F: PrimeField, EXT: FieldExtension<DEGREE, BaseField = F>, const DEGREE: usize>
+    GateConstraintEvaluator<F>
+    for FmaGateInExtensionWithoutConstantConstraintEvaluator<F, EXT, DEGREE>
 {
     type UniqueParameterizationParams = ();
 
@@ -206,16 +209,21 @@ impl<F: PrimeField, EXT: FieldExtension<2, BaseField = F>> GateConstraintEvaluat
 #[derivative(Clone, Copy, Debug, PartialEq(bound = ""), Eq(bound = ""), Hash)]
 pub struct FmaGateInExtensionWithoutConstantParams<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+    const DEGREE: usize = 2,
 > {
-    pub coeff_for_quadtaric_part: ExtensionField<F, 2, EXT>,
-    pub linear_term_coeff: ExtensionField<F, 2, EXT>,
+    pub coeff_for_quadtaric_part: ExtensionField<F, DEGREE, EXT>,
+    pub linear_term_coeff: ExtensionField<F, DEGREE, EXT>,
 }
 
 #[derive(Derivative)]
 #[derivative(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct FmaGateInExtensionWithoutConstant<F: SmallField, EXT: FieldExtension<2, BaseField = F>> {
-    pub params: FmaGateInExtensionWithoutConstantParams<F, EXT>,
+pub struct FmaGateInExtensionWithoutConstant<
+    F: SmallField,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+    const DEGREE: usize = 2,
+> {
+    pub params: FmaGateInExtensionWithoutConstantParams<F, EXT, DEGREE>,
     pub quadratic_part: ([Variable; 2], [Variable; 2]),
     pub linear_part: [Variable; 2],
     pub rhs_part: [Variable; 2],
@@ -225,23 +233,24 @@ const UNIQUE_IDENTIFIER: &'static str = "c0 * A * B + c1 * C -> D in quadratic e
 const PRINCIPAL_WIDTH: usize = 8;
 
 // HashMap coefficients into row index to know vacant places
-type FmaInExtensionGateTooling<F, EXT> = (
+type FmaInExtensionGateTooling<F, EXT, const DEGREE: usize> = (
     usize,
-    HashMap<FmaGateInExtensionWithoutConstantParams<F, EXT>, (usize, usize)>,
+    HashMap<FmaGateInExtensionWithoutConstantParams<F, EXT, DEGREE>, (usize, usize)>,
 );
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Gate<F>
-    for FmaGateInExtensionWithoutConstant<F, EXT>
+impl<F: SmallField, EXT: FieldExtension<DEGREE, BaseField = F>, const DEGREE: usize> Gate<F>
+    for FmaGateInExtensionWithoutConstant<F, EXT, DEGREE>
 {
     #[inline(always)]
     fn check_compatible_with_cs<CS: ConstraintSystem<F>>(&self, cs: &CS) -> bool {
         let geometry = cs.get_params();
-        geometry.max_allowed_constraint_degree >= 3
+        DEGREE == 2
+            && geometry.max_allowed_constraint_degree >= 3
             && geometry.num_columns_under_copy_permutation >= 8
             && geometry.num_constant_columns >= 4
     }
 
-    type Evaluator = FmaGateInExtensionWithoutConstantConstraintEvaluator<F, EXT>;
+    type Evaluator = FmaGateInExtensionWithoutConstantConstraintEvaluator<F, EXT, DEGREE>;
 
     #[inline]
     fn evaluator(&self) -> Self::Evaluator {
@@ -251,8 +260,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Gate<F>
     }
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
-    FmaGateInExtensionWithoutConstant<F, EXT>
+impl<F: SmallField, EXT: FieldExtension<DEGREE, BaseField = F>, const DEGREE: usize>
+    FmaGateInExtensionWithoutConstant<F, EXT, DEGREE>
 {
     pub fn configure_builder<
         GC: GateConfigurationHolder<F>,
@@ -266,11 +275,13 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
         TImpl,
         F,
         (
-            GateTypeEntry<F, Self, FmaInExtensionGateTooling<F, EXT>>,
+            GateTypeEntry<F, Self, FmaInExtensionGateTooling<F, EXT, DEGREE>>,
             GC,
         ),
         TB,
     > {
+        assert_eq!(DEGREE, 2, "gate only supports quadratic extensions");
+
         builder.allow_gate(placement_strategy, (), (0, HashMap::with_capacity(16)))
     }
 
@@ -297,11 +308,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
                 let offered_row_idx = cs.next_available_row();
                 let capacity_per_row = self.capacity_per_row(&*cs);
                 let tooling: &mut HashMap<
-                    FmaGateInExtensionWithoutConstantParams<F, EXT>,
+                    FmaGateInExtensionWithoutConstantParams<F, EXT, DEGREE>,
                     (usize, usize),
                 > = &mut cs
                     .get_gates_config_mut()
-                    .get_aux_data_mut::<Self, FmaInExtensionGateTooling<F, EXT>>()
+                    .get_aux_data_mut::<Self, FmaInExtensionGateTooling<F, EXT, DEGREE>>()
                     .expect("gate must be allowed")
                     .1;
                 let (row, num_instances_already_placed) =
@@ -332,9 +343,9 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
             } => {
                 // gate knows how to place itself
                 let capacity_per_row = num_repetitions;
-                let t: &mut FmaInExtensionGateTooling<F, EXT> = cs
+                let t: &mut FmaInExtensionGateTooling<F, EXT, DEGREE> = cs
                     .get_gates_config_mut()
-                    .get_aux_data_mut::<Self, FmaInExtensionGateTooling<F, EXT>>()
+                    .get_aux_data_mut::<Self, FmaInExtensionGateTooling<F, EXT, DEGREE>>()
                     .expect("gate must be allowed");
 
                 let (next_available_row, tooling) = (&mut t.0, &mut t.1);
@@ -369,9 +380,9 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
 
     pub fn compute_fma_in_extension<CS: ConstraintSystem<F>>(
         cs: &mut CS,
-        coeff_for_quadtaric_part: ExtensionField<F, 2, EXT>,
+        coeff_for_quadtaric_part: ExtensionField<F, DEGREE, EXT>,
         ab: ([Variable; 2], [Variable; 2]),
-        linear_term_coeff: ExtensionField<F, 2, EXT>,
+        linear_term_coeff: ExtensionField<F, DEGREE, EXT>,
         c: [Variable; 2],
     ) -> [Variable; 2] {
         debug_assert!(cs.gate_is_allowed::<Self>());
@@ -386,9 +397,9 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
         if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
             let value_fn = move |inputs: [F; 6]| {
                 let [a_c0, a_c1, b_c0, b_c1, c_c0, c_c1] = inputs;
-                let a = ExtensionField::<F, 2, EXT>::from_coeff_in_base([a_c0, a_c1]);
-                let b = ExtensionField::<F, 2, EXT>::from_coeff_in_base([b_c0, b_c1]);
-                let c = ExtensionField::<F, 2, EXT>::from_coeff_in_base([c_c0, c_c1]);
+                let a = from_quadratic_coeffs::<F, EXT, DEGREE>([a_c0, a_c1]);
+                let b = from_quadratic_coeffs::<F, EXT, DEGREE>([b_c0, b_c1]);
+                let c = from_quadratic_coeffs::<F, EXT, DEGREE>([c_c0, c_c1]);
 
                 let mut result = params.coeff_for_quadtaric_part;
                 use crate::field::traits::field::Field;
@@ -399,7 +410,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
 
                 result.add_assign(&tmp);
 
-                let result = result.into_coeffs_in_base();
+                let result = into_quadratic_coeffs(result);
 
                 result
             };
@@ -451,10 +462,10 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
 
         if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
             let value_fn = move |inputs: [F; 2]| {
-                let value = ExtensionField::<F, 2, EXT>::from_coeff_in_base(inputs);
+                let value = from_quadratic_coeffs::<F, EXT, DEGREE>(inputs);
                 let inverse = value.inverse().unwrap();
 
-                inverse.into_coeffs_in_base()
+                into_quadratic_coeffs(inverse)
             };
 
             let dependencies = Place::from_variables(variable_to_inverse);
@@ -480,6 +491,33 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
     }
 }
 
+#[inline(always)]
+fn from_quadratic_coeffs<
+    F: SmallField,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+    const DEGREE: usize,
+>(
+    coeffs: [F; 2],
+) -> ExtensionField<F, DEGREE, EXT> {
+    debug_assert_eq!(DEGREE, 2);
+
+    ExtensionField::from_coeff_in_base(std::array::from_fn(|i| coeffs[i]))
+}
+
+#[inline(always)]
+fn into_quadratic_coeffs<
+    F: SmallField,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+    const DEGREE: usize,
+>(
+    value: ExtensionField<F, DEGREE, EXT>,
+) -> [F; 2] {
+    debug_assert_eq!(DEGREE, 2);
+    let coeffs = value.into_coeffs_in_base();
+
+    [coeffs[0], coeffs[1]]
+}
+
 #[cfg(test)]
 mod test {
     use crate::field::Field;
@@ -138,7 +138,7 @@ pub struct BufferedGateEvaluationReducingDestinationChunk<
     pub current_selector_idx: usize,
     pub selectors: Vec<ArcGenericLdeStorage<F, P>>,
     pub destination: GateEvaluationReducingDestination<F, P>,
-    pub work_buffer: [P; 2],
+    pub work_buffer: Vec<P>,
     base_challenge_offset: usize,
     current_challenge_offset: usize,
 }
@@ -198,24 +198,20 @@ impl<F: PrimeField, P: field::traits::field_like::PrimeFieldLikeVectorized<Base
             }
         }
 
-        // mul by selector once
         let zero = P::zero(ctx);
-        let [mut c0, mut c1] = std::mem::replace(&mut self.work_buffer, [zero; 2]);
-        // mul by selector once
-        c0.mul_assign(&gate_selector_value, ctx);
-        c1.mul_assign(&gate_selector_value, ctx);
-
-        // actually place it. When we created chunks those were non-overlapping, so we can
-        // transitively move statement over non-overlapping to the outer vector too
-        unsafe {
-            std::sync::Arc::get_mut_unchecked(&mut self.destination.quotient_buffers)[0][outer]
-                [inner]
-                .add_assign(&c0, ctx);
-
-            std::sync::Arc::get_mut_unchecked(&mut self.destination.quotient_buffers)[1][outer]
-                [inner]
-                .add_assign(&c1, ctx);
-        };
+        for (idx, el) in self.work_buffer.iter_mut().enumerate() {
+            // mul by selector once
+            let mut value = std::mem::replace(el, zero);
+            value.mul_assign(&gate_selector_value, ctx);
+
+            // actually place it. When we created chunks those were non-overlapping, so we can
+            // transitively move statement over non-overlapping to the outer vector too
+            unsafe {
+                std::sync::Arc::get_mut_unchecked(&mut self.destination.quotient_buffers)[idx]
+                    [outer][inner]
+                    .add_assign(&value, ctx);
+            };
+        }
 
         // proceed to next selector for next set of terms.
         // Note that it's ok to set it 1 beyond the bound
@@ -248,7 +244,7 @@ impl<F: PrimeField, P: field::traits::field_like::PrimeFieldLikeVectorized<Base
             let chunk = BufferedGateEvaluationReducingDestinationChunk {
                 destination: self.clone(),
                 chunks_iterator: subiterator,
-                work_buffer: [zero; 2],
+                work_buffer: vec![zero; self.extension_degree],
                 selectors: ordered_selectors.clone(),
                 base_challenge_offset: 0,
                 current_challenge_offset: 0,
@@ -281,7 +277,7 @@ impl<F: PrimeField, P: field::traits::field_like::PrimeFieldLikeVectorized<Base
             let chunk = BufferedGateEvaluationReducingDestinationChunk {
                 destination: self.clone(),
                 chunks_iterator: subiterator,
-                work_buffer: [zero; 2],
+                work_buffer: vec![zero; self.extension_degree],
                 selectors: vec![],
                 base_challenge_offset: 0,
                 current_challenge_offset: 0,
@@ -346,19 +342,14 @@ impl<F: PrimeField, P: field::traits::field_like::PrimeFieldLikeVectorized<Base
         // make something like alpha * term0 + alpha^2 * term1 + ...
         // Later of if necessary those terms will be multiplied by the corresponding selector
         let ctx = &mut self.destination.ctx;
-        let mut c0 = value;
-        c0.mul_all_by_base(
-            &self.destination.challenges_powers[self.current_challenge_offset][0],
-            ctx,
-        );
-        let mut c1 = value;
-        c1.mul_all_by_base(
-            &self.destination.challenges_powers[self.current_challenge_offset][1],
-            ctx,
-        );
-
-        self.work_buffer[0].add_assign(&c0, ctx);
-        self.work_buffer[1].add_assign(&c1, ctx);
+        let degree = self.destination.extension_degree;
+        let offset = self.current_challenge_offset * degree;
+        let challenge = &self.destination.challenges_powers[offset..(offset + degree)];
+        for (dst, challenge_coeff) in self.work_buffer.iter_mut().zip(challenge.iter()) {
+            let mut tmp = value;
+            tmp.mul_all_by_base(challenge_coeff, ctx);
+            dst.add_assign(&tmp, ctx);
+        }
 
         self.current_challenge_offset += 1;
     }
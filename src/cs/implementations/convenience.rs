@@ -30,7 +30,8 @@ impl<
     > CSReferenceAssembly<F, P, CFG>
 {
     pub fn prove_one_shot<
-        EXT: FieldExtension<2, BaseField = F>,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
         TR: Transcript<F>,
         H: TreeHasher<F, Output = TR::CompatibleCap>,
         POW: PoWRunner,
@@ -39,7 +40,7 @@ impl<
         worker: &Worker,
         proof_config: ProofConfig,
         transcript_params: TR::TransciptParameters,
-    ) -> (Proof<F, H, EXT>, VerificationKey<F, H>) {
+    ) -> (Proof<F, H, DEGREE, EXT>, VerificationKey<F, H>) {
         assert!(
             CFG::SetupConfig::KEEP_SETUP,
             "CS is not configured to keep setup to know variables placement"
@@ -50,7 +51,11 @@ impl<
             "CS is not configured to have witness available"
         );
 
-        assert!(proof_config.fri_lde_factor.is_power_of_two());
+        assert!(
+            proof_config.fri_lde_factor.is_power_of_two(),
+            "FRI requires power of two LDE factor, but {} is given. Other factors are only supported by `FftPlan::lde`",
+            proof_config.fri_lde_factor
+        );
 
         let mut ctx = P::Context::placeholder();
 
@@ -63,7 +68,7 @@ impl<
         );
         let witness_set = self.take_witness(&worker);
 
-        let proof = self.prove_cpu_basic::<EXT, TR, H, POW>(
+        let proof = self.prove_cpu_basic::<DEGREE, EXT, TR, H, POW>(
             worker,
             witness_set,
             &setup_base,
@@ -97,7 +102,11 @@ impl<
             "CS is not configured to keep setup to know variables placement"
         );
 
-        assert!(proof_config.fri_lde_factor.is_power_of_two());
+        assert!(
+            proof_config.fri_lde_factor.is_power_of_two(),
+            "FRI requires power of two LDE factor, but {} is given. Other factors are only supported by `FftPlan::lde`",
+            proof_config.fri_lde_factor
+        );
 
         let mut ctx = P::Context::placeholder();
 
@@ -115,7 +124,8 @@ impl<
     }
 
     pub fn prove_from_precomputations<
-        EXT: FieldExtension<2, BaseField = F>,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
         TR: Transcript<F>,
         H: TreeHasher<F, Output = TR::CompatibleCap>,
         POW: PoWRunner,
@@ -130,17 +140,21 @@ impl<
         wits_hint: &DenseWitnessCopyHint,
         transcript_params: TR::TransciptParameters,
         worker: &Worker,
-    ) -> Proof<F, H, EXT> {
+    ) -> Proof<F, H, DEGREE, EXT> {
         assert!(
             CFG::WitnessConfig::EVALUATE_WITNESS,
             "CS is not configured to have witness available"
         );
 
-        assert!(proof_config.fri_lde_factor.is_power_of_two());
+        assert!(
+            proof_config.fri_lde_factor.is_power_of_two(),
+            "FRI requires power of two LDE factor, but {} is given. Other factors are only supported by `FftPlan::lde`",
+            proof_config.fri_lde_factor
+        );
 
         let witness_set = self.take_witness_using_hints(&worker, vars_hint, wits_hint);
 
-        let proof = self.prove_cpu_basic::<EXT, TR, H, POW>(
+        let proof = self.prove_cpu_basic::<DEGREE, EXT, TR, H, POW>(
             worker,
             witness_set,
             &setup_base,
@@ -156,7 +170,8 @@ impl<
 
     /// Intended to be used mainly with assembly produced by `into_assembly_for_repeated_proving`
     pub fn prove_from_witness_vec_and_precomputations<
-        EXT: FieldExtension<2, BaseField = F>,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
         TR: Transcript<F>,
         H: TreeHasher<F, Output = TR::CompatibleCap>,
         POW: PoWRunner,
@@ -172,13 +187,17 @@ impl<
         wits_hint: &DenseWitnessCopyHint,
         transcript_params: TR::TransciptParameters,
         worker: &Worker,
-    ) -> Proof<F, H, EXT> {
-        assert!(proof_config.fri_lde_factor.is_power_of_two());
+    ) -> Proof<F, H, DEGREE, EXT> {
+        assert!(
+            proof_config.fri_lde_factor.is_power_of_two(),
+            "FRI requires power of two LDE factor, but {} is given. Other factors are only supported by `FftPlan::lde`",
+            proof_config.fri_lde_factor
+        );
 
         let witness_set =
             self.witness_set_from_witness_vec(witness_vector, vars_hint, wits_hint, &worker);
 
-        let proof = self.prove_cpu_basic::<EXT, TR, H, POW>(
+        let proof = self.prove_cpu_basic::<DEGREE, EXT, TR, H, POW>(
             worker,
             witness_set,
             &setup_base,
@@ -196,18 +215,19 @@ impl<
 pub fn verify_circuit<
     F: SmallField,
     C: Circuit<F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     TR: Transcript<F>,
     H: TreeHasher<F, Output = TR::CompatibleCap>,
     POW: PoWRunner,
 >(
     circuit: &C,
-    proof: &Proof<F, H, EXT>,
+    proof: &Proof<F, H, DEGREE, EXT>,
     vk: &VerificationKey<F, H>,
     transcript_params: TR::TransciptParameters,
 ) -> bool {
     let builder_impl =
-        CsVerifierBuilder::<F, EXT>::new_from_parameters(vk.fixed_parameters.parameters);
+        CsVerifierBuilder::<F, DEGREE, EXT>::new_from_parameters(vk.fixed_parameters.parameters);
     use crate::cs::cs_builder::new_builder;
 
     let builder = new_builder::<_, F>(builder_impl);
@@ -4,6 +4,10 @@ use super::{
     *,
 };
 use crate::cs::implementations::utils::*;
+use crate::field::batch_inversion::{
+    batch_inverse_vectorized_inplace, batch_inverse_vectorized_inplace_in_extension,
+    chunk_columns_mut,
+};
 use crate::utils::*;
 use crate::{
     cs::implementations::polynomial::lde::GenericLdeStorage,
@@ -68,8 +72,6 @@ pub(crate) fn pointwise_rational<
                 let mut ctx = *ctx;
                 scope.spawn(move |_| {
                     let mut buffer_den = Vec::with_capacity_in(chunk_size, A::default());
-                    let buffer_for_inverses = Vec::with_capacity_in(chunk_size, A::default());
-                    let mut buffer_for_inverses = P::vec_into_base_vec(buffer_for_inverses);
 
                     for (((w, sigma), x_poly), dst) in w
                         .iter()
@@ -97,12 +99,10 @@ pub(crate) fn pointwise_rational<
                         buffer_den.push(denominator);
                     }
 
-                    let buffer_den = P::slice_into_base_slice(&buffer_den[..]);
-                    batch_inverse(&buffer_den, &mut buffer_for_inverses);
-                    let buffer_for_inverses = P::vec_from_base_vec(buffer_for_inverses);
-                    assert_eq!(dst.len(), buffer_for_inverses.len());
+                    batch_inverse_vectorized_inplace::<P, A>(&mut buffer_den, &mut ctx);
+                    assert_eq!(dst.len(), buffer_den.len());
 
-                    for (dst, src) in dst.iter_mut().zip(buffer_for_inverses.iter()) {
+                    for (dst, src) in dst.iter_mut().zip(buffer_den.iter()) {
                         dst.mul_assign(src, &mut ctx);
                     }
                 });
@@ -112,9 +112,10 @@ pub(crate) fn pointwise_rational<
 }
 
 pub(crate) fn pointwise_rational_in_extension<
-    F: PrimeField,
+    F: SmallField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     A: GoodAllocator,
     B: GoodAllocator,
 >(
@@ -122,10 +123,9 @@ pub(crate) fn pointwise_rational_in_extension<
     precomputed_x_poly: std::sync::Arc<GenericPolynomial<F, LagrangeForm, P, A>>,
     sigma_polys: Vec<std::sync::Arc<GenericPolynomial<F, LagrangeForm, P, A>>, B>,
     non_residues: Vec<F, B>,
-    beta: ExtensionField<F, 2, EXT>,
-    gamma: ExtensionField<F, 2, EXT>,
-    basis_c0: &mut GenericPolynomial<F, LagrangeForm, P, A>,
-    basis_c1: &mut GenericPolynomial<F, LagrangeForm, P, A>,
+    beta: ExtensionField<F, DEGREE, EXT>,
+    gamma: ExtensionField<F, DEGREE, EXT>,
+    basis: &mut [GenericPolynomial<F, LagrangeForm, P, A>; DEGREE],
     worker: &Worker,
     ctx: &mut P::Context,
 ) {
@@ -134,13 +134,12 @@ pub(crate) fn pointwise_rational_in_extension<
     assert_eq!(copy_permutation_columns.len(), sigma_polys.len());
     assert_eq!(copy_permutation_columns.len(), non_residues.len());
     let typical_size = copy_permutation_columns[0].storage.len();
-    assert_eq!(basis_c0.storage.len(), typical_size);
-    assert_eq!(basis_c1.storage.len(), typical_size);
+    for el in basis.iter() {
+        assert_eq!(el.storage.len(), typical_size);
+    }
 
-    let beta_c0 = P::constant(beta.coeffs[0], ctx);
-    let beta_c1 = P::constant(beta.coeffs[1], ctx);
-    let gamma_c0 = P::constant(gamma.coeffs[0], ctx);
-    let gamma_c1 = P::constant(gamma.coeffs[1], ctx);
+    let beta = beta.coeffs.map(|el| P::constant(el, ctx));
+    let gamma = gamma.coeffs.map(|el| P::constant(el, ctx));
 
     for ((witness_poly, sigma_poly), non_residue) in copy_permutation_columns
         .iter()
@@ -148,27 +147,23 @@ pub(crate) fn pointwise_rational_in_extension<
         .zip(non_residues.iter())
     {
         let non_residue = P::constant(*non_residue, ctx);
+        let mut basis_it = basis.iter_mut();
+        let basis_columns: [&mut [P]; DEGREE] =
+            std::array::from_fn(|_| &mut basis_it.next().unwrap().storage[..]);
         worker.scope(typical_size, |scope, chunk_size| {
-            for ((((w, sigma), x_poly), dst_c0), dst_c1) in
-                (witness_poly.storage.chunks(chunk_size))
-                    .zip(sigma_poly.storage.chunks(chunk_size))
-                    .zip(precomputed_x_poly.storage.chunks(chunk_size))
-                    .zip(basis_c0.storage.chunks_mut(chunk_size))
-                    .zip(basis_c1.storage.chunks_mut(chunk_size))
+            for (((w, sigma), x_poly), dst) in (witness_poly.storage.chunks(chunk_size))
+                .zip(sigma_poly.storage.chunks(chunk_size))
+                .zip(precomputed_x_poly.storage.chunks(chunk_size))
+                .zip(chunk_columns_mut(basis_columns, chunk_size))
             {
                 let mut ctx = *ctx;
                 scope.spawn(move |_| {
-                    let mut buffer_den =
-                        Vec::with_capacity_in(chunk_size * P::SIZE_FACTOR, A::default());
-                    let mut buffer_for_inverses =
-                        Vec::with_capacity_in(chunk_size * P::SIZE_FACTOR, A::default());
+                    let mut dst = dst;
+                    let mut buffer_den: [_; DEGREE] =
+                        std::array::from_fn(|_| Vec::with_capacity_in(chunk_size, A::default()));
 
-                    for ((((w, sigma), x_poly), dst_c0), dst_c1) in w
-                        .iter()
-                        .zip(sigma.iter())
-                        .zip(x_poly.iter())
-                        .zip(dst_c0.iter_mut())
-                        .zip(dst_c1.iter_mut())
+                    for (idx, ((w, sigma), x_poly)) in
+                        w.iter().zip(sigma.iter()).zip(x_poly.iter()).enumerate()
                     {
                         // numerator is w + beta * non_res * x + gamma
 
@@ -177,69 +172,60 @@ pub(crate) fn pointwise_rational_in_extension<
                         let mut numerator_common = non_residue;
                         numerator_common.mul_assign(&x_poly, &mut ctx);
 
-                        // c0 and c1 parts
-                        let mut numerator_c0 = numerator_common;
-                        numerator_c0.mul_assign(&beta_c0, &mut ctx);
-                        numerator_c0
-                            .add_assign(w, &mut ctx)
-                            .add_assign(&gamma_c0, &mut ctx);
+                        // w only contributes to the c0 part
+                        let numerator: [P; DEGREE] = std::array::from_fn(|i| {
+                            let mut numerator = numerator_common;
+                            numerator.mul_assign(&beta[i], &mut ctx);
+                            if i == 0 {
+                                numerator.add_assign(w, &mut ctx);
+                            }
+                            numerator.add_assign(&gamma[i], &mut ctx);
 
-                        let mut numerator_c1 = numerator_common;
-                        numerator_c1.mul_assign(&beta_c1, &mut ctx);
-                        numerator_c1.add_assign(&gamma_c1, &mut ctx);
+                            numerator
+                        });
 
-                        mul_assign_vectorized_in_extension::<F, P, EXT>(
-                            dst_c0,
-                            dst_c1,
-                            &numerator_c0,
-                            &numerator_c1,
-                            &mut ctx,
+                        let mut value: [P; DEGREE] = std::array::from_fn(|i| dst[i][idx]);
+                        mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(
+                            &mut value, &numerator, &mut ctx,
                         );
+                        for (dst, value) in dst.iter_mut().zip(value.into_iter()) {
+                            dst[idx] = value;
+                        }
 
                         // denominator is w + beta * sigma(x) + gamma
                         // we do the same
+                        for (i, buffer_den) in buffer_den.iter_mut().enumerate() {
+                            let mut denominator = *sigma;
+                            denominator.mul_assign(&beta[i], &mut ctx);
+                            if i == 0 {
+                                denominator.add_assign(w, &mut ctx);
+                            }
+                            denominator.add_assign(&gamma[i], &mut ctx);
 
-                        // c0 and c1 parts
-                        let mut denominator_c0 = *sigma;
-                        denominator_c0.mul_assign(&beta_c0, &mut ctx);
-                        denominator_c0
-                            .add_assign(w, &mut ctx)
-                            .add_assign(&gamma_c0, &mut ctx);
-
-                        let mut denominator_c1 = *sigma;
-                        denominator_c1.mul_assign(&beta_c1, &mut ctx);
-                        denominator_c1.add_assign(&gamma_c1, &mut ctx);
-
-                        for (c0, c1) in denominator_c0
-                            .as_base_elements()
-                            .iter()
-                            .zip(denominator_c1.as_base_elements().iter())
-                        {
-                            let denominator =
-                                ExtensionField::<F, 2, EXT>::from_coeff_in_base([*c0, *c1]);
                             buffer_den.push(denominator);
                         }
                     }
 
-                    batch_inverse(&buffer_den, &mut buffer_for_inverses);
-                    assert_eq!(dst_c0.len() * P::SIZE_FACTOR, buffer_for_inverses.len());
-                    assert_eq!(dst_c1.len() * P::SIZE_FACTOR, buffer_for_inverses.len());
-
-                    // mul as scalars
-                    let dst_c0_base = P::slice_into_base_slice_mut(dst_c0);
-                    let dst_c1_base = P::slice_into_base_slice_mut(dst_c1);
+                    let mut buffer_den_it = buffer_den.iter_mut();
+                    batch_inverse_vectorized_inplace_in_extension::<F, P, DEGREE, EXT, A>(
+                        std::array::from_fn(|_| &mut buffer_den_it.next().unwrap()[..]),
+                        &mut ctx,
+                    );
+                    for (dst, buffer_den) in dst.iter().zip(buffer_den.iter()) {
+                        assert_eq!(dst.len(), buffer_den.len());
+                    }
 
-                    for ((dst_c0, dst_c1), src) in dst_c0_base
-                        .iter_mut()
-                        .zip(dst_c1_base.iter_mut())
-                        .zip(buffer_for_inverses.drain(..))
-                    {
-                        let mut dst =
-                            ExtensionField::<F, 2, EXT>::from_coeff_in_base([*dst_c0, *dst_c1]);
-                        crate::field::Field::mul_assign(&mut dst, &src);
-                        let [c0, c1] = dst.into_coeffs_in_base();
-                        *dst_c0 = c0;
-                        *dst_c1 = c1;
+                    for idx in 0..dst[0].len() {
+                        let mut value: [P; DEGREE] = std::array::from_fn(|i| dst[i][idx]);
+                        let denominator: [P; DEGREE] = std::array::from_fn(|i| buffer_den[i][idx]);
+                        mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(
+                            &mut value,
+                            &denominator,
+                            &mut ctx,
+                        );
+                        for (dst, value) in dst.iter_mut().zip(value.into_iter()) {
+                            dst[idx] = value;
+                        }
                     }
                 });
             }
@@ -298,65 +284,67 @@ pub(crate) fn pointwise_product_into<
 pub(crate) fn pointwise_product_in_extension<
     F: PrimeField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     A: GoodAllocator,
 >(
-    inputs: &[[GenericPolynomial<F, LagrangeForm, P, A>; 2]],
+    inputs: &[[GenericPolynomial<F, LagrangeForm, P, A>; DEGREE]],
     worker: &Worker,
     ctx: &mut P::Context,
-) -> [GenericPolynomial<F, LagrangeForm, P, A>; 2] {
+) -> [GenericPolynomial<F, LagrangeForm, P, A>; DEGREE] {
     if inputs.len() == 1 {
         return inputs[0].clone();
     }
 
-    let [mut result_c0, mut result_c1] = inputs[0].clone();
+    let mut result = inputs[0].clone();
 
-    pointwise_product_in_extension_into::<F, P, EXT, A>(
+    pointwise_product_in_extension_into::<F, P, DEGREE, EXT, A>(
         &inputs[1..],
-        &mut result_c0,
-        &mut result_c1,
+        &mut result,
         worker,
         ctx,
     );
 
-    [result_c0, result_c1]
+    result
 }
 
 pub(crate) fn pointwise_product_in_extension_into<
     F: PrimeField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     A: GoodAllocator,
 >(
-    inputs: &[[GenericPolynomial<F, LagrangeForm, P, A>; 2]],
-    into_c0: &mut GenericPolynomial<F, LagrangeForm, P, A>,
-    into_c1: &mut GenericPolynomial<F, LagrangeForm, P, A>,
+    inputs: &[[GenericPolynomial<F, LagrangeForm, P, A>; DEGREE]],
+    into: &mut [GenericPolynomial<F, LagrangeForm, P, A>; DEGREE],
     worker: &Worker,
     ctx: &mut P::Context,
 ) {
-    let typical_size = into_c0.storage.len(); // we need raw length in counts of P
+    let typical_size = into[0].storage.len(); // we need raw length in counts of P
 
     for source in inputs.iter() {
-        let [src_c0, src_c1] = source;
+        let mut into_it = into.iter_mut();
+        let into_columns: [&mut [P]; DEGREE] =
+            std::array::from_fn(|_| &mut into_it.next().unwrap().storage[..]);
         worker.scope(typical_size, |scope, chunk_size| {
-            for (((dst_c0, dst_c1), src_c0), src_c1) in into_c0
-                .storage
-                .chunks_mut(chunk_size)
-                .zip(into_c1.storage.chunks_mut(chunk_size))
-                .zip(src_c0.storage.chunks(chunk_size))
-                .zip(src_c1.storage.chunks(chunk_size))
+            for (chunk_idx, dst) in chunk_columns_mut(into_columns, chunk_size)
+                .into_iter()
+                .enumerate()
             {
                 let mut ctx = *ctx;
                 scope.spawn(move |_| {
-                    for (((dst_c0, dst_c1), src_c0), src_c1) in dst_c0
-                        .iter_mut()
-                        .zip(dst_c1.iter_mut())
-                        .zip(src_c0.iter())
-                        .zip(src_c1.iter())
-                    {
-                        mul_assign_vectorized_in_extension::<F, P, EXT>(
-                            dst_c0, dst_c1, src_c0, src_c1, &mut ctx,
+                    let mut dst = dst;
+                    let start = chunk_idx * chunk_size;
+                    for idx in 0..dst[0].len() {
+                        let mut value: [P; DEGREE] = std::array::from_fn(|i| dst[i][idx]);
+                        let src: [P; DEGREE] =
+                            std::array::from_fn(|i| source[i].storage[start + idx]);
+                        mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(
+                            &mut value, &src, &mut ctx,
                         );
+                        for (dst, value) in dst.iter_mut().zip(value.into_iter()) {
+                            dst[idx] = value;
+                        }
                     }
                 });
             }
@@ -424,41 +412,45 @@ pub(crate) fn shifted_grand_product<F: PrimeField, A: GoodAllocator>(
 
 pub(crate) fn shifted_grand_product_in_extension<
     F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     A: GoodAllocator,
 >(
-    input_c0: Vec<F, A>,
-    input_c1: Vec<F, A>,
+    input: [Vec<F, A>; DEGREE],
     worker: &Worker,
-) -> [Vec<F, A>; 2] {
-    let mut input_c0 = input_c0;
-    let mut input_c1 = input_c1;
-    let work_size = input_c0.len();
+) -> [Vec<F, A>; DEGREE] {
+    let mut input = input;
+    let work_size = input[0].len();
     assert!(work_size.is_power_of_two());
     let chunk_size = worker.get_chunk_size(work_size);
     let num_chunks = Worker::compute_num_chunks(work_size, chunk_size);
 
     let mut partial_subproducts = {
         use crate::field::traits::field::Field;
-        vec![ExtensionField::<F, 2, EXT>::ZERO; num_chunks]
+        vec![ExtensionField::<F, DEGREE, EXT>::ZERO; num_chunks]
     };
 
+    let mut input_it = input.iter_mut();
+    let columns: [&mut [F]; DEGREE] = std::array::from_fn(|_| &mut input_it.next().unwrap()[..]);
     worker.scope(work_size, |scope, chunk_size| {
-        for ((chunk_c0, chunk_c1), acc) in input_c0
-            .chunks_mut(chunk_size)
-            .zip(input_c1.chunks_mut(chunk_size))
+        for (chunk, acc) in chunk_columns_mut(columns, chunk_size)
+            .into_iter()
             .zip(partial_subproducts.iter_mut())
         {
             scope.spawn(move |_| {
+                let mut chunk = chunk;
                 let mut product = {
                     use crate::field::Field;
-                    ExtensionField::<F, 2, EXT>::ONE
+                    ExtensionField::<F, DEGREE, EXT>::ONE
                 };
-                for (dst_c0, dst_c1) in chunk_c0.iter_mut().zip(chunk_c1.iter_mut()) {
-                    let tmp = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*dst_c0, *dst_c1]);
+                for idx in 0..chunk[0].len() {
+                    let tmp = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(
+                        std::array::from_fn(|i| chunk[i][idx]),
+                    );
 
-                    *dst_c0 = product.coeffs[0];
-                    *dst_c1 = product.coeffs[1];
+                    for (dst, el) in chunk.iter_mut().zip(product.coeffs.iter()) {
+                        dst[idx] = *el;
+                    }
 
                     crate::field::Field::mul_assign(&mut product, &tmp);
                 }
@@ -471,7 +463,7 @@ pub(crate) fn shifted_grand_product_in_extension<
     // multiply partial products
     let one = {
         use crate::field::Field;
-        ExtensionField::<F, 2, EXT>::ONE
+        ExtensionField::<F, DEGREE, EXT>::ONE
     };
     let mut current = one;
 
@@ -485,28 +477,31 @@ pub(crate) fn shifted_grand_product_in_extension<
     assert_eq!(current, one);
 
     // second pass
+    let mut input_it = input.iter_mut();
+    let columns: [&mut [F]; DEGREE] = std::array::from_fn(|_| &mut input_it.next().unwrap()[..]);
     worker.scope(work_size, |scope, chunk_size| {
         // no multiplication for 1st chunk
-        for ((chunk_c0, chunk_c1), acc) in input_c0
-            .chunks_mut(chunk_size)
+        for (chunk, acc) in chunk_columns_mut(columns, chunk_size)
+            .into_iter()
             .skip(1)
-            .zip(input_c1.chunks_mut(chunk_size).skip(1))
             .zip(partial_subproducts.iter())
         {
             scope.spawn(move |_| {
-                for (dst_c0, dst_c1) in chunk_c0.iter_mut().zip(chunk_c1.iter_mut()) {
-                    let mut dst =
-                        ExtensionField::<F, 2, EXT>::from_coeff_in_base([*dst_c0, *dst_c1]);
-                    crate::field::Field::mul_assign(&mut dst, &acc);
-                    let [c0, c1] = dst.into_coeffs_in_base();
-                    *dst_c0 = c0;
-                    *dst_c1 = c1;
+                let mut chunk = chunk;
+                for idx in 0..chunk[0].len() {
+                    let mut value = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(
+                        std::array::from_fn(|i| chunk[i][idx]),
+                    );
+                    crate::field::Field::mul_assign(&mut value, &acc);
+                    for (dst, el) in chunk.iter_mut().zip(value.coeffs.iter()) {
+                        dst[idx] = *el;
+                    }
                 }
             });
         }
     });
 
-    [input_c0, input_c1]
+    input
 }
 
 pub fn non_residues_for_copy_permutation<F: PrimeField, B: GoodAllocator>(
@@ -647,23 +642,24 @@ use crate::field::FieldExtension;
 
 // Evaluated over main domain (no coset, no LDE)
 pub(crate) fn compute_partial_products_in_extension<
-    F: PrimeField,
+    F: SmallField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     A: GoodAllocator,
     B: GoodAllocator,
 >(
     all_copy_permutation_columns: Vec<std::sync::Arc<GenericPolynomial<F, LagrangeForm, P, A>>, B>,
     precomputed_x_poly: std::sync::Arc<GenericPolynomial<F, LagrangeForm, P, A>>,
     all_sigma_polys: Vec<std::sync::Arc<GenericPolynomial<F, LagrangeForm, P, A>>, B>,
-    beta: ExtensionField<F, 2, EXT>,
-    gamma: ExtensionField<F, 2, EXT>,
+    beta: ExtensionField<F, DEGREE, EXT>,
+    gamma: ExtensionField<F, DEGREE, EXT>,
     worker: &Worker,
     max_degree: usize,
     ctx: &mut P::Context,
 ) -> (
-    [GenericPolynomial<F, LagrangeForm, P, A>; 2], // Z(x)
-    Vec<[GenericPolynomial<F, LagrangeForm, P, A>; 2], B>, // partial products
+    [GenericPolynomial<F, LagrangeForm, P, A>; DEGREE], // Z(x)
+    Vec<[GenericPolynomial<F, LagrangeForm, P, A>; DEGREE], B>, // partial products
 ) {
     assert!(all_copy_permutation_columns.len() > 0);
     assert_eq!(all_copy_permutation_columns.len(), all_sigma_polys.len());
@@ -692,15 +688,15 @@ pub(crate) fn compute_partial_products_in_extension<
         let sigma_polys_chunk = sigma_polys_chunk.to_vec_in(B::default());
         let non_residues_chunk = non_residues_chunk.to_vec_in(B::default());
 
-        let basis_c0 =
-            initialize_in_with_alignment_of::<F, P, _>(F::ONE, domain_size, A::default());
-        let basis_c1 =
-            initialize_in_with_alignment_of::<F, P, _>(F::ZERO, domain_size, A::default());
-        let basis_c0 = P::vec_from_base_vec(basis_c0);
-        let basis_c1 = P::vec_from_base_vec(basis_c1);
+        // start from 1 in the extension
+        let mut partial_elementwise_product: [_; DEGREE] = std::array::from_fn(|i| {
+            let value = if i == 0 { F::ONE } else { F::ZERO };
+            let basis =
+                initialize_in_with_alignment_of::<F, P, _>(value, domain_size, A::default());
+            let basis = P::vec_from_base_vec(basis);
 
-        let mut partial_elementwise_product_c0 = GenericPolynomial::from_storage(basis_c0);
-        let mut partial_elementwise_product_c1 = GenericPolynomial::from_storage(basis_c1);
+            GenericPolynomial::from_storage(basis)
+        });
 
         pointwise_rational_in_extension(
             copy_permutation_columns_chunk.clone(),
@@ -709,33 +705,26 @@ pub(crate) fn compute_partial_products_in_extension<
             non_residues_chunk.clone(),
             beta,
             gamma,
-            &mut partial_elementwise_product_c0,
-            &mut partial_elementwise_product_c1,
+            &mut partial_elementwise_product,
             worker,
             ctx,
         );
 
-        partial_elementwise_products.push([
-            partial_elementwise_product_c0,
-            partial_elementwise_product_c1,
-        ]);
+        partial_elementwise_products.push(partial_elementwise_product);
     }
 
-    let [almost_z_poly_c0, almost_z_poly_c1] =
-        pointwise_product_in_extension::<F, P, EXT, A>(&partial_elementwise_products, worker, ctx);
+    let almost_z_poly = pointwise_product_in_extension::<F, P, DEGREE, EXT, A>(
+        &partial_elementwise_products,
+        worker,
+        ctx,
+    );
 
     // now we can compute almost z poly, by performing elementwise multiplication
     // that should be completed in base field
 
-    let almost_z_poly_c0 = P::vec_into_base_vec(almost_z_poly_c0.into_storage());
-    let almost_z_poly_c1 = P::vec_into_base_vec(almost_z_poly_c1.into_storage());
-    let [z_poly_c0, z_poly_c1] =
-        shifted_grand_product_in_extension::<F, EXT, A>(almost_z_poly_c0, almost_z_poly_c1, worker);
-    let z_poly_c0 = P::vec_from_base_vec(z_poly_c0);
-    let z_poly_c0 = GenericPolynomial::from_storage(z_poly_c0);
-
-    let z_poly_c1 = P::vec_from_base_vec(z_poly_c1);
-    let z_poly_c1 = GenericPolynomial::from_storage(z_poly_c1);
+    let almost_z_poly = almost_z_poly.map(|el| P::vec_into_base_vec(el.into_storage()));
+    let z_poly = shifted_grand_product_in_extension::<F, DEGREE, EXT, A>(almost_z_poly, worker);
+    let z_poly = z_poly.map(|el| GenericPolynomial::from_storage(P::vec_from_base_vec(el)));
 
     // and now we need to re-materialize partial elementwise products like
     // partial_0 = z(x) * partial_elementwise_products[0]
@@ -746,10 +735,10 @@ pub(crate) fn compute_partial_products_in_extension<
 
     if partial_elementwise_products.len() == 1 {
         // there are no intermediate products in practice, we can go directly from z(x) to z(x * omega)
-        ([z_poly_c0, z_poly_c1], partial_elementwise_products)
+        (z_poly, partial_elementwise_products)
     } else {
         // we have to compute intermediates
-        let mut previous = [[z_poly_c0.clone(), z_poly_c1.clone()]];
+        let mut previous = [z_poly.clone()];
         let mut full_elementwise_products = Vec::new_in(B::default());
 
         let _ = partial_elementwise_products.pop().unwrap();
@@ -757,19 +746,18 @@ pub(crate) fn compute_partial_products_in_extension<
         // we have to apply pointwise products on top of Z(x)
 
         for el in partial_elementwise_products.into_iter() {
-            let [mut el_c0, mut el_c1] = el;
-            pointwise_product_in_extension_into::<F, P, EXT, A>(
-                &previous, &mut el_c0, &mut el_c1, worker, ctx,
+            let mut el = el;
+            pointwise_product_in_extension_into::<F, P, DEGREE, EXT, A>(
+                &previous, &mut el, worker, ctx,
             );
 
             // we have new pointwise in el, and untouched previous, so we can reuse the storage
 
-            Clone::clone_from(&mut previous[0][0], &el_c0);
-            Clone::clone_from(&mut previous[0][1], &el_c1);
-            full_elementwise_products.push([el_c0, el_c1]);
+            Clone::clone_from(&mut previous[0], &el);
+            full_elementwise_products.push(el);
         }
 
-        ([z_poly_c0, z_poly_c1], full_elementwise_products)
+        (z_poly, full_elementwise_products)
     }
 }
 
@@ -999,7 +987,8 @@ pub(crate) fn compute_partial_products_in_extension<
 
 pub(crate) fn compute_quotient_terms_in_extension<
     F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
     A: GoodAllocator,
     B: GoodAllocator,
@@ -1007,15 +996,14 @@ pub(crate) fn compute_quotient_terms_in_extension<
     domain_size: usize,
     degree: usize,
     witness: &WitnessStorage<F, P, A, B>,
-    grand_products: &SecondStageProductsStorage<F, P, A, B>,
+    grand_products: &SecondStageProductsStorage<F, DEGREE, P, A, B>,
     setup: &SetupStorage<F, P, A, B>,
     num_intermediate_products: usize,
-    beta: ExtensionField<F, 2, EXT>,
-    gamma: ExtensionField<F, 2, EXT>,
-    alphas: Vec<ExtensionField<F, 2, EXT>>,
+    beta: ExtensionField<F, DEGREE, EXT>,
+    gamma: ExtensionField<F, DEGREE, EXT>,
+    alphas: Vec<ExtensionField<F, DEGREE, EXT>>,
     x_poly: &ArcGenericLdeStorage<F, P, A, B>,
-    dst_c0: &mut ArcGenericLdeStorage<F, P, A, B>,
-    dst_c1: &mut ArcGenericLdeStorage<F, P, A, B>,
+    dst: &mut [ArcGenericLdeStorage<F, P, A, B>; DEGREE],
     worker: &Worker,
     ctx: &mut P::Context,
 ) {
@@ -1038,7 +1026,7 @@ pub(crate) fn compute_quotient_terms_in_extension<
 
     assert_eq!(alphas.len(), num_intermediate_products + 1);
 
-    let z_poly_shifted: [_; 2] = grand_products.z_poly.clone().map(|el| {
+    let z_poly_shifted: [_; DEGREE] = grand_products.z_poly.clone().map(|el| {
         let subset = el.subset_for_degree(degree);
         let subset_len = subset.storage.len();
         let mut owned_set = Vec::with_capacity_in(subset_len, B::default());
@@ -1105,11 +1093,8 @@ pub(crate) fn compute_quotient_terms_in_extension<
 
     // lhs * denom - rhs * num == 0, and make it over coset
 
-    let beta_c0 = P::constant(beta.coeffs[0], ctx);
-    let beta_c1 = P::constant(beta.coeffs[1], ctx);
-
-    let gamma_c0 = P::constant(gamma.coeffs[0], ctx);
-    let gamma_c1 = P::constant(gamma.coeffs[1], ctx);
+    let beta = beta.coeffs.map(|el| P::constant(el, ctx));
+    let gamma = gamma.coeffs.map(|el| P::constant(el, ctx));
 
     for (_relation_idx, (((((lhs, rhs), alpha), non_residues), variables), sigmas)) in lhs
         .zip(rhs)
@@ -1119,8 +1104,7 @@ pub(crate) fn compute_quotient_terms_in_extension<
         .zip(sigmas_chunks.chunks(degree))
         .enumerate()
     {
-        let alpha_c0 = P::constant(alpha.coeffs[0], ctx);
-        let alpha_c1 = P::constant(alpha.coeffs[1], ctx);
+        let alpha = alpha.coeffs.map(|el| P::constant(el, ctx));
 
         assert_eq!(variables.len(), sigmas.len());
         assert_eq!(variables.len(), non_residues.len());
@@ -1130,8 +1114,7 @@ pub(crate) fn compute_quotient_terms_in_extension<
 
         worker.scope(0, |scope, _| {
             for iterator in iterators.into_iter() {
-                let mut dst_c0 = dst_c0.clone();
-                let mut dst_c1 = dst_c1.clone();
+                let mut dst = dst.clone();
                 let lhs = lhs.clone();
                 let rhs = rhs.clone();
                 let x_poly = x_poly.clone();
@@ -1142,72 +1125,73 @@ pub(crate) fn compute_quotient_terms_in_extension<
                     for _ in 0..num_iterations {
                         let (outer, inner) = iterator.current();
 
-                        let mut lhs_contribution_c0 = lhs[0].storage[outer].storage[inner];
-                        let mut lhs_contribution_c1 = lhs[1].storage[outer].storage[inner];
+                        let mut lhs_contribution: [P; DEGREE] =
+                            std::array::from_fn(|i| lhs[i].storage[outer].storage[inner]);
                         for (variables, sigma) in variables.iter().zip(sigmas.iter()) {
-                            // denominator is w + beta * sigma(x) + gamma
-                            let mut subres_c0 = sigma.storage[outer].storage[inner];
-                            subres_c0.mul_assign(&beta_c0, &mut ctx);
-                            subres_c0
-                                .add_assign(&variables.storage[outer].storage[inner], &mut ctx);
-                            subres_c0.add_assign(&gamma_c0, &mut ctx);
-
-                            let mut subres_c1 = sigma.storage[outer].storage[inner];
-                            subres_c1.mul_assign(&beta_c1, &mut ctx);
-                            subres_c1.add_assign(&gamma_c1, &mut ctx);
-
-                            mul_assign_vectorized_in_extension::<F, P, EXT>(
-                                &mut lhs_contribution_c0,
-                                &mut lhs_contribution_c1,
-                                &subres_c0,
-                                &subres_c1,
+                            // denominator is w + beta * sigma(x) + gamma, and w only contributes to c0
+                            let subres: [P; DEGREE] = std::array::from_fn(|i| {
+                                let mut subres = sigma.storage[outer].storage[inner];
+                                subres.mul_assign(&beta[i], &mut ctx);
+                                if i == 0 {
+                                    subres.add_assign(
+                                        &variables.storage[outer].storage[inner],
+                                        &mut ctx,
+                                    );
+                                }
+                                subres.add_assign(&gamma[i], &mut ctx);
+
+                                subres
+                            });
+
+                            mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(
+                                &mut lhs_contribution,
+                                &subres,
                                 &mut ctx,
                             );
                         }
 
-                        let mut rhs_contribution_c0 = rhs[0].storage[outer].storage[inner];
-                        let mut rhs_contribution_c1 = rhs[1].storage[outer].storage[inner];
+                        let mut rhs_contribution: [P; DEGREE] =
+                            std::array::from_fn(|i| rhs[i].storage[outer].storage[inner]);
                         let x_poly_value = x_poly.storage[outer].storage[inner];
                         for (non_res, variables) in non_residues.iter().zip(variables.iter()) {
                             // numerator is w + beta * non_res * x + gamma
-                            let mut subres_c0 = x_poly_value;
-                            subres_c0.mul_assign(&non_res, &mut ctx);
-                            subres_c0.mul_assign(&beta_c0, &mut ctx);
-                            subres_c0
-                                .add_assign(&variables.storage[outer].storage[inner], &mut ctx);
-                            subres_c0.add_assign(&gamma_c0, &mut ctx);
-
-                            let mut subres_c1 = x_poly_value;
-                            subres_c1.mul_assign(&non_res, &mut ctx);
-                            subres_c1.mul_assign(&beta_c1, &mut ctx);
-                            subres_c1.add_assign(&gamma_c1, &mut ctx);
-
-                            mul_assign_vectorized_in_extension::<F, P, EXT>(
-                                &mut rhs_contribution_c0,
-                                &mut rhs_contribution_c1,
-                                &subres_c0,
-                                &subres_c1,
+                            let subres: [P; DEGREE] = std::array::from_fn(|i| {
+                                let mut subres = x_poly_value;
+                                subres.mul_assign(&non_res, &mut ctx);
+                                subres.mul_assign(&beta[i], &mut ctx);
+                                if i == 0 {
+                                    subres.add_assign(
+                                        &variables.storage[outer].storage[inner],
+                                        &mut ctx,
+                                    );
+                                }
+                                subres.add_assign(&gamma[i], &mut ctx);
+
+                                subres
+                            });
+
+                            mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(
+                                &mut rhs_contribution,
+                                &subres,
                                 &mut ctx,
                             );
                         }
 
-                        let mut contribution_c0 = lhs_contribution_c0;
-                        contribution_c0.sub_assign(&rhs_contribution_c0, &mut ctx);
-                        let mut contribution_c1 = lhs_contribution_c1;
-                        contribution_c1.sub_assign(&rhs_contribution_c1, &mut ctx);
+                        let mut contribution = lhs_contribution;
+                        for (dst, src) in contribution.iter_mut().zip(rhs_contribution.iter()) {
+                            dst.sub_assign(src, &mut ctx);
+                        }
 
-                        mul_assign_vectorized_in_extension::<F, P, EXT>(
-                            &mut contribution_c0,
-                            &mut contribution_c1,
-                            &alpha_c0,
-                            &alpha_c1,
+                        mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(
+                            &mut contribution,
+                            &alpha,
                             &mut ctx,
                         );
 
                         if crate::config::DEBUG_SATISFIABLE == true {
                             if outer == 0 {
                                 // only on base coset
-                                let t = [contribution_c0];
+                                let t = [contribution[0]];
                                 let as_base = P::slice_into_base_slice(&t);
                                 for el in as_base.iter() {
                                     if el.is_zero() == false {
@@ -1234,13 +1218,11 @@ pub(crate) fn compute_quotient_terms_in_extension<
                             }
                         }
 
-                        unsafe { std::sync::Arc::get_mut_unchecked(&mut dst_c0.storage[outer]) }
-                            .storage[inner]
-                            .add_assign(&contribution_c0, &mut ctx);
-
-                        unsafe { std::sync::Arc::get_mut_unchecked(&mut dst_c1.storage[outer]) }
-                            .storage[inner]
-                            .add_assign(&contribution_c1, &mut ctx);
+                        for (dst, contribution) in dst.iter_mut().zip(contribution.iter()) {
+                            unsafe { std::sync::Arc::get_mut_unchecked(&mut dst.storage[outer]) }
+                                .storage[inner]
+                                .add_assign(contribution, &mut ctx);
+                        }
 
                         iterator.advance();
                     }
@@ -1172,13 +1172,14 @@ mod test {
         proof_config.pow_bits = 0;
 
         let (proof, vk) = cs.prove_one_shot::<
+            2,
             GoldilocksExt2,
             GoldilocksPoisedonTranscript,
             GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
             NoPow,
         >(&worker, proof_config, ());
 
-        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder_impl = CsVerifierBuilder::<F, 2, GoldilocksExt2>::new_from_parameters(geometry);
         let builder = new_builder::<_, F>(builder_impl);
 
         let builder = configure(builder);
@@ -1197,6 +1198,329 @@ mod test {
         assert!(is_valid);
     }
 
+    #[test]
+    fn prove_simple_with_salted_leaves() {
+        type P = GoldilocksField;
+
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 8,
+        };
+
+        fn configure<
+            T: CsBuilderImpl<F, T>,
+            GC: GateConfigurationHolder<F>,
+            TB: StaticToolboxHolder,
+        >(
+            builder: CsBuilder<T, F, GC, TB>,
+        ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+            let builder = ConstantsAllocatorGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = ZeroCheckGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+                false,
+            );
+            let builder = NopGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+
+            builder
+        }
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, P, DevCSConfig>::new(geometry, 512, 128);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = configure(builder);
+        let mut cs = builder.build(());
+
+        let mut previous = None;
+        for _ in 0..36 {
+            let a = if let Some(previous) = previous.take() {
+                previous
+            } else {
+                cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(1))
+            };
+            let b = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(2));
+            let c = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(3));
+
+            let d = FmaGateInBaseFieldWithoutConstant::compute_fma(
+                &mut cs,
+                GoldilocksField::TWO,
+                (a, b),
+                GoldilocksField::MINUS_ONE,
+                c,
+            );
+
+            let e = ZeroCheckGate::check_if_zero(&mut cs, d);
+            previous = Some(e);
+        }
+
+        use super::gates::constant_allocator::*;
+
+        cs.allocate_constant(GoldilocksField::from_u64_unchecked(3));
+
+        cs.pad_and_shrink();
+
+        let worker = Worker::new_with_num_threads(1);
+        let cs = cs.into_assembly();
+
+        let mut proof_config = ProofConfig::default();
+        proof_config.fri_lde_factor = 16;
+        proof_config.security_level = 80;
+        proof_config.pow_bits = 0;
+        proof_config.salt_oracle_leaves = true;
+
+        let (proof, vk) = cs.prove_one_shot::<
+            2,
+            GoldilocksExt2,
+            GoldilocksPoisedonTranscript,
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            NoPow,
+        >(&worker, proof_config, ());
+
+        use crate::cs::implementations::prover::ORACLE_LEAF_SALT_SIZE;
+        assert_eq!(
+            proof.queries_per_fri_repetition[0]
+                .witness_query
+                .leaf_elements
+                .len(),
+            geometry.num_columns_under_copy_permutation + ORACLE_LEAF_SALT_SIZE
+        );
+
+        let builder_impl = CsVerifierBuilder::<F, 2, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = configure(builder);
+        let verifier = builder.build(());
+
+        let is_valid = verifier.verify::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >(
+            (),
+            &vk,
+            &proof,
+        );
+
+        assert!(is_valid);
+
+        // salt is committed, so proof without it is not valid
+        let mut proof = proof;
+        proof.proof_config.salt_oracle_leaves = false;
+        let is_valid = verifier.verify::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >(
+            (),
+            &vk,
+            &proof,
+        );
+
+        assert!(!is_valid);
+    }
+
+    // Proves a simple circuit and returns the result of verification of the proof after `modify`
+    fn try_verify_modified_proof(
+        modify: impl FnOnce(
+            &mut crate::cs::implementations::proof::Proof<
+                F,
+                GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+                2,
+                GoldilocksExt2,
+            >,
+        ),
+    ) -> Result<(), crate::cs::implementations::verifier::VerificationError> {
+        type P = GoldilocksField;
+
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 8,
+        };
+
+        fn configure<
+            T: CsBuilderImpl<F, T>,
+            GC: GateConfigurationHolder<F>,
+            TB: StaticToolboxHolder,
+        >(
+            builder: CsBuilder<T, F, GC, TB>,
+        ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+            let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = NopGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+
+            builder
+        }
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, P, DevCSConfig>::new(geometry, 512, 128);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = configure(builder);
+        let mut cs = builder.build(());
+
+        let mut previous = None;
+        for _ in 0..64 {
+            let a = if let Some(previous) = previous.take() {
+                previous
+            } else {
+                cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(1))
+            };
+            let b = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(2));
+            let c = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(3));
+
+            let d = FmaGateInBaseFieldWithoutConstant::compute_fma(
+                &mut cs,
+                GoldilocksField::TWO,
+                (a, b),
+                GoldilocksField::MINUS_ONE,
+                c,
+            );
+            previous = Some(d);
+        }
+
+        cs.pad_and_shrink();
+
+        let worker = Worker::new_with_num_threads(1);
+        let cs = cs.into_assembly();
+
+        let mut proof_config = ProofConfig::default();
+        proof_config.fri_lde_factor = 16;
+        proof_config.security_level = 80;
+        proof_config.pow_bits = 0;
+
+        let (mut proof, vk) = cs.prove_one_shot::<
+            2,
+            GoldilocksExt2,
+            GoldilocksPoisedonTranscript,
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            NoPow,
+        >(&worker, proof_config, ());
+
+        let builder_impl = CsVerifierBuilder::<F, 2, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = configure(builder);
+        let verifier = builder.build(());
+
+        let result = verifier.try_verify::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >(
+            (),
+            &vk,
+            &proof,
+        );
+        assert_eq!(result, Ok(()));
+
+        modify(&mut proof);
+
+        verifier.try_verify::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >(
+            (),
+            &vk,
+            &proof,
+        )
+    }
+
+    #[test]
+    fn try_verify_truncated_leaves() {
+        use crate::cs::implementations::verifier::VerificationError;
+
+        let result = try_verify_modified_proof(|proof| {
+            proof.queries_per_fri_repetition[1]
+                .witness_query
+                .leaf_elements
+                .pop();
+        });
+
+        assert_eq!(
+            result,
+            Err(VerificationError::MalformedProof(
+                "invalid leaf size for witness oracle at query number 1".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn try_verify_wrong_fri_monomials_count() {
+        use crate::cs::implementations::verifier::VerificationError;
+
+        let result = try_verify_modified_proof(|proof| {
+            for monomials in proof.final_fri_monomials.iter_mut() {
+                monomials.push(GoldilocksField::ZERO);
+            }
+        });
+
+        assert_eq!(
+            result,
+            Err(VerificationError::MalformedProof(
+                "unexpected number of monomials in FRI".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn try_verify_bad_folding_schedule() {
+        use crate::cs::implementations::verifier::VerificationError;
+
+        // one more intermediate oracle than the schedule for this config and domain size gives
+        let result = try_verify_modified_proof(|proof| {
+            let cap = proof.fri_base_oracle_cap.clone();
+            proof.fri_intermediate_oracles_caps.push(cap);
+        });
+
+        assert_eq!(
+            result,
+            Err(VerificationError::MalformedProof(
+                "unexpected number of intermediate FRI oracles".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn try_verify_wrong_queries_count() {
+        use crate::cs::implementations::verifier::VerificationError;
+
+        let mut num_queries = 0;
+        let result = try_verify_modified_proof(|proof| {
+            proof.queries_per_fri_repetition.pop();
+            num_queries = proof.queries_per_fri_repetition.len();
+        });
+
+        assert_eq!(
+            result,
+            Err(VerificationError::MalformedProof(format!(
+                "FRI queries number is invalid: expecting {}, prover provided {}",
+                num_queries + 1,
+                num_queries
+            )))
+        );
+    }
+
     #[test]
     #[ignore = "Computation of poly pairs for lookups unimplemented"]
     fn prove_simple_with_lookups() {
@@ -1329,13 +1653,14 @@ mod test {
         proof_config.pow_bits = 0;
 
         let (proof, vk) = cs.prove_one_shot::<
+            2,
             GoldilocksExt2,
             GoldilocksPoisedonTranscript,
             GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
             NoPow,
         >(&worker, proof_config, ());
 
-        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder_impl = CsVerifierBuilder::<F, 2, GoldilocksExt2>::new_from_parameters(geometry);
         let builder = new_builder::<_, F>(builder_impl);
 
         let builder = configure(builder);
@@ -1430,13 +1755,14 @@ mod test {
         proof_config.pow_bits = 0;
 
         let (proof, vk) = cs.prove_one_shot::<
+            2,
             GoldilocksExt2,
             GoldilocksPoisedonTranscript,
             GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
             NoPow,
         >(&worker, proof_config, ());
 
-        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder_impl = CsVerifierBuilder::<F, 2, GoldilocksExt2>::new_from_parameters(geometry);
         let builder = new_builder::<_, F>(builder_impl);
 
         let builder = configure(builder);
@@ -1660,13 +1986,14 @@ mod test {
         // panic!();
 
         let (proof, vk) = cs.prove_one_shot::<
+            2,
             GoldilocksExt2,
             GoldilocksPoisedonTranscript,
             GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
             NoPow,
         >(&worker, proof_config, ());
 
-        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder_impl = CsVerifierBuilder::<F, 2, GoldilocksExt2>::new_from_parameters(geometry);
         let builder = new_builder::<_, F>(builder_impl);
 
         let builder = configure(builder);
@@ -3,7 +3,7 @@ use super::{
     *,
 };
 
-use crate::cs::implementations::utils::*;
+use crate::field::batch_inversion::batch_inverse_vectorized_inplace_parallel;
 
 use crate::cs::traits::GoodAllocator;
 
@@ -213,11 +213,11 @@ pub(crate) fn compute_lookup_poly_pairs_over_general_purpose_columns<
         });
 
         unsafe { aggregated_lookup_columns.set_len(domain_size / P::SIZE_FACTOR) };
-        let mut aggregated_lookup_columns_inversed =
-            P::vec_into_base_vec(aggregated_lookup_columns);
-        batch_inverse_inplace_parallel::<F, A>(&mut aggregated_lookup_columns_inversed, worker);
-        let aggregated_lookup_columns_inversed =
-            P::vec_from_base_vec(aggregated_lookup_columns_inversed);
+        let mut aggregated_lookup_columns_inversed = aggregated_lookup_columns;
+        batch_inverse_vectorized_inplace_parallel::<P, A>(
+            &mut aggregated_lookup_columns_inversed,
+            worker,
+        );
 
         // we follow the same aproach as above - first prepare chunks, and then work over them
 
@@ -301,9 +301,7 @@ pub(crate) fn compute_lookup_poly_pairs_over_general_purpose_columns<
             });
 
             unsafe { a_poly.set_len(domain_size / P::SIZE_FACTOR) };
-            let mut a_poly = P::vec_into_base_vec(a_poly);
-            batch_inverse_inplace_parallel::<F, A>(&mut a_poly, worker);
-            let mut a_poly = P::vec_from_base_vec(a_poly);
+            batch_inverse_vectorized_inplace_parallel::<P, A>(&mut a_poly, worker);
 
             // A poly's denominator is ready, now we have simple elementwise pass
 
@@ -522,11 +520,11 @@ pub(crate) fn compute_lookup_poly_pairs_specialized<
         });
 
         unsafe { aggregated_lookup_columns.set_len(domain_size / P::SIZE_FACTOR) };
-        let mut aggregated_lookup_columns_inversed =
-            P::vec_into_base_vec(aggregated_lookup_columns);
-        batch_inverse_inplace_parallel::<F, A>(&mut aggregated_lookup_columns_inversed, worker);
-        let aggregated_lookup_columns_inversed =
-            P::vec_from_base_vec(aggregated_lookup_columns_inversed);
+        let mut aggregated_lookup_columns_inversed = aggregated_lookup_columns;
+        batch_inverse_vectorized_inplace_parallel::<P, A>(
+            &mut aggregated_lookup_columns_inversed,
+            worker,
+        );
 
         // we follow the same aproach as above - first prepare chunks, and then work over them
         for witness_columns in
@@ -605,9 +603,7 @@ pub(crate) fn compute_lookup_poly_pairs_specialized<
             });
 
             unsafe { witness_encoding_poly.set_len(domain_size / P::SIZE_FACTOR) };
-            let mut witness_encoding_poly = P::vec_into_base_vec(witness_encoding_poly);
-            batch_inverse_inplace_parallel::<F, A>(&mut witness_encoding_poly, worker);
-            let witness_encoding_poly = P::vec_from_base_vec(witness_encoding_poly);
+            batch_inverse_vectorized_inplace_parallel::<P, A>(&mut witness_encoding_poly, worker);
 
             // push the results
             let witness_encoding_poly = GenericPolynomial::from_storage(witness_encoding_poly);
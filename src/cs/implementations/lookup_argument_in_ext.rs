@@ -3,7 +3,9 @@ use super::{
     polynomial_storage::{SecondStageProductsStorage, SetupStorage, WitnessStorage},
     *,
 };
-use crate::cs::implementations::utils::*;
+use crate::field::batch_inversion::{
+    batch_inverse_vectorized_inplace_parallel_in_extension, chunk_columns_mut,
+};
 use crate::field::traits::field_like::mul_assign_vectorized_in_extension;
 
 use crate::field::ExtensionField;
@@ -320,7 +322,8 @@ use crate::{cs::traits::GoodAllocator, field::PrimeField};
 pub(crate) fn compute_lookup_poly_pairs_specialized<
     F: SmallField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     A: GoodAllocator,
     B: GoodAllocator,
 >(
@@ -329,15 +332,15 @@ pub(crate) fn compute_lookup_poly_pairs_specialized<
     constant_polys: Vec<std::sync::Arc<GenericPolynomial<F, LagrangeForm, P, A>>, B>,
     lookup_tables_columns: Vec<std::sync::Arc<GenericPolynomial<F, LagrangeForm, P, A>>, B>,
     table_id_column_idxes: Vec<usize>,
-    beta: ExtensionField<F, 2, EXT>,  // for denominator
-    gamma: ExtensionField<F, 2, EXT>, // to aggregate columns
+    beta: ExtensionField<F, DEGREE, EXT>,  // for denominator
+    gamma: ExtensionField<F, DEGREE, EXT>, // to aggregate columns
     variables_offset: usize,
     lookup_parameters: LookupParameters,
     worker: &Worker,
     ctx: &mut P::Context,
 ) -> (
-    Vec<[GenericPolynomial<F, LagrangeForm, P, A>; 2], B>, // involves witness
-    Vec<[GenericPolynomial<F, LagrangeForm, P, A>; 2], B>, // involve multiplicities
+    Vec<[GenericPolynomial<F, LagrangeForm, P, A>; DEGREE], B>, // involves witness
+    Vec<[GenericPolynomial<F, LagrangeForm, P, A>; DEGREE], B>, // involve multiplicities
 ) {
     assert!(variables_columns.len() > 0);
 
@@ -390,49 +393,42 @@ pub(crate) fn compute_lookup_poly_pairs_specialized<
     // we no longer need full witness
     drop(variables_columns);
 
-    let beta_c0 = P::constant(beta.coeffs[0], ctx);
-    let beta_c1 = P::constant(beta.coeffs[1], ctx);
-    let _gamma_c0 = P::constant(gamma.coeffs[0], ctx);
-    let _gamma_c1 = P::constant(gamma.coeffs[1], ctx);
+    let beta = beta.coeffs.map(|el| P::constant(el, ctx));
 
     let mut subarguments_witness_encoding_polys =
         Vec::with_capacity_in(num_subarguments, B::default());
     let mut subarguments_multiplicities_encoding_polys = Vec::with_capacity_in(1, B::default());
 
-    let mut powers_of_gamma_c0 =
-        Vec::with_capacity_in(total_num_columns_per_subargument, B::default());
-    let mut powers_of_gamma_c1 =
+    let mut powers_of_gamma =
         Vec::with_capacity_in(total_num_columns_per_subargument, B::default());
     let mut tmp = {
         use crate::field::traits::field::Field;
 
-        ExtensionField::<F, 2, EXT>::ONE
+        ExtensionField::<F, DEGREE, EXT>::ONE
     };
-    powers_of_gamma_c0.push(P::constant(tmp.coeffs[0], ctx));
-    powers_of_gamma_c1.push(P::constant(tmp.coeffs[1], ctx));
+    powers_of_gamma.push(tmp.coeffs.map(|el| P::constant(el, ctx)));
     for _ in 1..total_num_columns_per_subargument {
         crate::field::Field::mul_assign(&mut tmp, &gamma);
 
-        powers_of_gamma_c0.push(P::constant(tmp.coeffs[0], ctx));
-        powers_of_gamma_c1.push(P::constant(tmp.coeffs[1], ctx));
+        powers_of_gamma.push(tmp.coeffs.map(|el| P::constant(el, ctx)));
     }
 
     // precomputed columns contribution
 
     // form denominator's part of t_0 + gamma * t_1 + ... + beta
-    let mut aggregated_lookup_columns_c0 =
-        Vec::with_capacity_in(domain_size / P::SIZE_FACTOR, A::default());
-    let mut aggregated_lookup_columns_c1 =
-        Vec::with_capacity_in(domain_size / P::SIZE_FACTOR, A::default());
+    let mut aggregated_lookup_columns: [_; DEGREE] =
+        std::array::from_fn(|_| Vec::with_capacity_in(domain_size / P::SIZE_FACTOR, A::default()));
+
+    let mut dst_it = aggregated_lookup_columns.iter_mut();
+    let dst_columns: [_; DEGREE] = std::array::from_fn(|_| {
+        &mut dst_it.next().unwrap().spare_capacity_mut()[..domain_size / P::SIZE_FACTOR]
+    });
 
     worker.scope(domain_size / P::SIZE_FACTOR, |scope, chunk_size| {
+        let dst_chunks = chunk_columns_mut(dst_columns, chunk_size);
         let mut subiterators = Vec::new_in(B::default());
 
-        for (idx, _) in aggregated_lookup_columns_c0.spare_capacity_mut()
-            [..domain_size / P::SIZE_FACTOR]
-            .chunks_mut(chunk_size)
-            .enumerate()
-        {
+        for idx in 0..dst_chunks.len() {
             let mut tmp = Vec::with_capacity_in(lookup_tables_columns.len(), B::default());
             for src in lookup_tables_columns.iter() {
                 let chunk = src
@@ -444,88 +440,60 @@ pub(crate) fn compute_lookup_poly_pairs_specialized<
                     .iter();
                 tmp.push(chunk);
             }
-            assert_eq!(tmp.len(), powers_of_gamma_c0.len());
-            assert_eq!(tmp.len(), powers_of_gamma_c1.len());
+            assert_eq!(tmp.len(), powers_of_gamma.len());
             subiterators.push(tmp);
         }
 
-        assert_eq!(
-            aggregated_lookup_columns_c0.spare_capacity_mut()[..domain_size / P::SIZE_FACTOR]
-                .chunks_mut(chunk_size)
-                .len(),
-            subiterators.len()
-        );
-        assert_eq!(
-            aggregated_lookup_columns_c1.spare_capacity_mut()[..domain_size / P::SIZE_FACTOR]
-                .chunks_mut(chunk_size)
-                .len(),
-            subiterators.len()
-        );
-
-        for ((dst_c0, dst_c1), src) in aggregated_lookup_columns_c0.spare_capacity_mut()
-            [..domain_size / P::SIZE_FACTOR]
-            .chunks_mut(chunk_size)
-            .zip(
-                aggregated_lookup_columns_c1.spare_capacity_mut()[..domain_size / P::SIZE_FACTOR]
-                    .chunks_mut(chunk_size),
-            )
-            .zip(subiterators.into_iter())
-        {
+        for (dst, src) in dst_chunks.into_iter().zip(subiterators.into_iter()) {
             let mut ctx = *ctx;
-            let powers_of_gamma_c0 = &powers_of_gamma_c0;
-            let powers_of_gamma_c1 = &powers_of_gamma_c1;
+            let powers_of_gamma = &powers_of_gamma;
 
-            assert_eq!(src.len(), powers_of_gamma_c0.len());
-            assert_eq!(src.len(), powers_of_gamma_c1.len());
+            assert_eq!(src.len(), powers_of_gamma.len());
 
             scope.spawn(move |_| {
                 let mut src = src;
-                for (dst_c0, dst_c1) in dst_c0.iter_mut().zip(dst_c1.iter_mut()) {
-                    let mut acc_c0 = beta_c0;
-                    let mut acc_c1 = beta_c1;
-                    for ((src, gamma_c0), gamma_c1) in src
-                        .iter_mut()
-                        .zip(powers_of_gamma_c0.iter())
-                        .zip(powers_of_gamma_c1.iter())
-                    {
+                let mut dst = dst;
+                for idx in 0..dst[0].len() {
+                    let mut acc = beta;
+                    for (src, gamma) in src.iter_mut().zip(powers_of_gamma.iter()) {
                         let src = src.next().expect("table column element");
-                        P::mul_and_accumulate_into(&mut acc_c0, &src, gamma_c0, &mut ctx);
-                        P::mul_and_accumulate_into(&mut acc_c1, &src, gamma_c1, &mut ctx);
+                        for (acc, gamma) in acc.iter_mut().zip(gamma.iter()) {
+                            P::mul_and_accumulate_into(acc, &src, gamma, &mut ctx);
+                        }
                     }
 
-                    dst_c0.write(acc_c0);
-                    dst_c1.write(acc_c1);
+                    for (dst, acc) in dst.iter_mut().zip(acc.into_iter()) {
+                        dst[idx].write(acc);
+                    }
                 }
             });
         }
     });
 
-    unsafe { aggregated_lookup_columns_c0.set_len(domain_size / P::SIZE_FACTOR) };
-    unsafe { aggregated_lookup_columns_c1.set_len(domain_size / P::SIZE_FACTOR) };
+    for el in aggregated_lookup_columns.iter_mut() {
+        unsafe { el.set_len(domain_size / P::SIZE_FACTOR) };
+    }
 
-    let mut aggregated_lookup_columns_inversed_c0 =
-        P::vec_into_base_vec(aggregated_lookup_columns_c0);
-    let mut aggregated_lookup_columns_inversed_c1 =
-        P::vec_into_base_vec(aggregated_lookup_columns_c1);
+    let mut aggregated_lookup_columns_inversed = aggregated_lookup_columns;
 
-    batch_inverse_inplace_parallel_in_extension::<F, EXT, A>(
-        &mut aggregated_lookup_columns_inversed_c0,
-        &mut aggregated_lookup_columns_inversed_c1,
+    let mut it = aggregated_lookup_columns_inversed.iter_mut();
+    batch_inverse_vectorized_inplace_parallel_in_extension::<F, P, DEGREE, EXT, A>(
+        std::array::from_fn(|_| &mut it.next().unwrap()[..]),
         worker,
     );
-    let aggregated_lookup_columns_inversed_c0 =
-        P::vec_from_base_vec(aggregated_lookup_columns_inversed_c0);
-    let aggregated_lookup_columns_inversed_c1 =
-        P::vec_from_base_vec(aggregated_lookup_columns_inversed_c1);
 
     // we follow the same aproach as above - first prepare chunks, and then work over them
     for witness_columns in
         variables_columns_for_lookup.chunks_exact(num_variable_columns_per_argument)
     {
-        let mut witness_encoding_poly_c0 =
-            Vec::with_capacity_in(domain_size / P::SIZE_FACTOR, A::default());
-        let mut witness_encoding_poly_c1 =
-            Vec::with_capacity_in(domain_size / P::SIZE_FACTOR, A::default());
+        let mut witness_encoding_poly: [_; DEGREE] = std::array::from_fn(|_| {
+            Vec::with_capacity_in(domain_size / P::SIZE_FACTOR, A::default())
+        });
+
+        let mut dst_it = witness_encoding_poly.iter_mut();
+        let dst_columns: [_; DEGREE] = std::array::from_fn(|_| {
+            &mut dst_it.next().unwrap().spare_capacity_mut()[..domain_size / P::SIZE_FACTOR]
+        });
 
         worker.scope(domain_size / P::SIZE_FACTOR, |scope, chunk_size| {
             // prepare subiterators
@@ -555,123 +523,83 @@ pub(crate) fn compute_lookup_poly_pairs_specialized<
                         .iter();
                     tmp.push(chunk);
                 }
-                assert_eq!(tmp.len(), powers_of_gamma_c0.len());
-                assert_eq!(tmp.len(), powers_of_gamma_c1.len());
+                assert_eq!(tmp.len(), powers_of_gamma.len());
                 subiterators.push(tmp);
             }
 
             // work with A poly only, compute denominator
 
-            assert_eq!(
-                witness_encoding_poly_c0.spare_capacity_mut()[..domain_size / P::SIZE_FACTOR]
-                    .chunks_mut(chunk_size)
-                    .len(),
-                subiterators.len()
-            );
-            assert_eq!(
-                witness_encoding_poly_c1.spare_capacity_mut()[..domain_size / P::SIZE_FACTOR]
-                    .chunks_mut(chunk_size)
-                    .len(),
-                subiterators.len()
-            );
-
-            for ((dst_c0, dst_c1), src) in witness_encoding_poly_c0.spare_capacity_mut()
-                [..domain_size / P::SIZE_FACTOR]
-                .chunks_mut(chunk_size)
-                .zip(
-                    witness_encoding_poly_c1.spare_capacity_mut()[..domain_size / P::SIZE_FACTOR]
-                        .chunks_mut(chunk_size),
-                )
-                .zip(subiterators.into_iter())
-            {
-                let powers_of_gamma_c0 = &powers_of_gamma_c0[..];
-                let powers_of_gamma_c1 = &powers_of_gamma_c1[..];
+            let dst_chunks = chunk_columns_mut(dst_columns, chunk_size);
+            assert_eq!(dst_chunks.len(), subiterators.len());
+
+            for (dst, src) in dst_chunks.into_iter().zip(subiterators.into_iter()) {
+                let powers_of_gamma = &powers_of_gamma[..];
                 let mut ctx = *ctx;
-                assert_eq!(src.len(), powers_of_gamma_c0.len());
-                assert_eq!(src.len(), powers_of_gamma_c1.len());
+                assert_eq!(src.len(), powers_of_gamma.len());
 
                 scope.spawn(move |_| {
                     let mut src = src;
-                    for (dst_c0, dst_c1) in dst_c0.iter_mut().zip(dst_c1.iter_mut()) {
-                        let mut acc_c0 = beta_c0;
-                        let mut acc_c1 = beta_c1;
-                        for ((src, gamma_c0), gamma_c1) in src
-                            .iter_mut()
-                            .zip(powers_of_gamma_c0.iter())
-                            .zip(powers_of_gamma_c1.iter())
-                        {
+                    let mut dst = dst;
+                    for idx in 0..dst[0].len() {
+                        let mut acc = beta;
+                        for (src, gamma) in src.iter_mut().zip(powers_of_gamma.iter()) {
                             let src = src.next().expect("witness column element");
-                            P::mul_and_accumulate_into(&mut acc_c0, &src, gamma_c0, &mut ctx);
-                            P::mul_and_accumulate_into(&mut acc_c1, &src, gamma_c1, &mut ctx);
+                            for (acc, gamma) in acc.iter_mut().zip(gamma.iter()) {
+                                P::mul_and_accumulate_into(acc, &src, gamma, &mut ctx);
+                            }
                         }
 
-                        dst_c0.write(acc_c0);
-                        dst_c1.write(acc_c1);
+                        for (dst, acc) in dst.iter_mut().zip(acc.into_iter()) {
+                            dst[idx].write(acc);
+                        }
                     }
                 });
             }
         });
 
-        unsafe { witness_encoding_poly_c0.set_len(domain_size / P::SIZE_FACTOR) };
-        unsafe { witness_encoding_poly_c1.set_len(domain_size / P::SIZE_FACTOR) };
-
-        let mut witness_encoding_poly_c0 = P::vec_into_base_vec(witness_encoding_poly_c0);
-        let mut witness_encoding_poly_c1 = P::vec_into_base_vec(witness_encoding_poly_c1);
+        for el in witness_encoding_poly.iter_mut() {
+            unsafe { el.set_len(domain_size / P::SIZE_FACTOR) };
+        }
 
-        batch_inverse_inplace_parallel_in_extension::<F, EXT, A>(
-            &mut witness_encoding_poly_c0,
-            &mut witness_encoding_poly_c1,
+        let mut it = witness_encoding_poly.iter_mut();
+        batch_inverse_vectorized_inplace_parallel_in_extension::<F, P, DEGREE, EXT, A>(
+            std::array::from_fn(|_| &mut it.next().unwrap()[..]),
             worker,
         );
-        let witness_encoding_poly_c0 = P::vec_from_base_vec(witness_encoding_poly_c0);
-        let witness_encoding_poly_c1 = P::vec_from_base_vec(witness_encoding_poly_c1);
 
         // push the results
-        let witness_encoding_poly_c0 = GenericPolynomial::from_storage(witness_encoding_poly_c0);
-        let witness_encoding_poly_c1 = GenericPolynomial::from_storage(witness_encoding_poly_c1);
+        let witness_encoding_poly = witness_encoding_poly.map(GenericPolynomial::from_storage);
 
-        subarguments_witness_encoding_polys
-            .push([witness_encoding_poly_c0, witness_encoding_poly_c1]);
+        subarguments_witness_encoding_polys.push(witness_encoding_poly);
     }
 
     // now multiplicities
     for multiplicity_column in multiplicities_columns.iter() {
         // A poly's denominator is ready, now we have simple elementwise pass
 
-        let mut multiplicities_encoding_poly_c0 = aggregated_lookup_columns_inversed_c0.clone();
-        let mut multiplicities_encoding_poly_c1 = aggregated_lookup_columns_inversed_c1.clone();
+        let mut multiplicities_encoding_poly = aggregated_lookup_columns_inversed.clone();
 
-        worker.scope(
-            multiplicities_encoding_poly_c0.len(),
-            |scope, chunk_size| {
-                for ((dst_c0, dst_c1), mults) in multiplicities_encoding_poly_c0
+        for multiplicities_encoding_poly in multiplicities_encoding_poly.iter_mut() {
+            worker.scope(multiplicities_encoding_poly.len(), |scope, chunk_size| {
+                for (dst, mults) in multiplicities_encoding_poly
                     .chunks_mut(chunk_size)
-                    .zip(multiplicities_encoding_poly_c1.chunks_mut(chunk_size))
                     .zip(multiplicity_column.storage.chunks(chunk_size))
                 {
                     let mut ctx = *ctx;
                     scope.spawn(move |_| {
-                        for ((dst_c0, dst_c1), mults) in
-                            dst_c0.iter_mut().zip(dst_c1.iter_mut()).zip(mults.iter())
-                        {
-                            dst_c0.mul_assign(mults, &mut ctx);
-                            dst_c1.mul_assign(mults, &mut ctx);
+                        for (dst, mults) in dst.iter_mut().zip(mults.iter()) {
+                            dst.mul_assign(mults, &mut ctx);
                         }
                     });
                 }
-            },
-        );
+            });
+        }
 
         // push the results
-        let multiplicities_encoding_poly_c0 =
-            GenericPolynomial::from_storage(multiplicities_encoding_poly_c0);
-        let multiplicities_encoding_poly_c1 =
-            GenericPolynomial::from_storage(multiplicities_encoding_poly_c1);
-
-        subarguments_multiplicities_encoding_polys.push([
-            multiplicities_encoding_poly_c0,
-            multiplicities_encoding_poly_c1,
-        ]);
+        let multiplicities_encoding_poly =
+            multiplicities_encoding_poly.map(GenericPolynomial::from_storage);
+
+        subarguments_multiplicities_encoding_polys.push(multiplicities_encoding_poly);
     }
 
     assert_eq!(subarguments_witness_encoding_polys.len(), num_subarguments);
@@ -679,33 +607,26 @@ pub(crate) fn compute_lookup_poly_pairs_specialized<
 
     if crate::config::DEBUG_SATISFIABLE == true {
         // {
-        let mut a_sum_c0 = F::ZERO;
-        let mut a_sum_c1 = F::ZERO;
+        let mut a_sum = [F::ZERO; DEGREE];
         for a_poly in subarguments_witness_encoding_polys.iter() {
-            for a in P::slice_into_base_slice(&a_poly[0].storage).iter() {
-                a_sum_c0.add_assign(a);
-            }
-            for a in P::slice_into_base_slice(&a_poly[1].storage).iter() {
-                a_sum_c1.add_assign(a);
+            for (a_sum, a_poly) in a_sum.iter_mut().zip(a_poly.iter()) {
+                for a in P::slice_into_base_slice(&a_poly.storage).iter() {
+                    a_sum.add_assign(a);
+                }
             }
         }
 
-        let mut b_sum_c0 = F::ZERO;
-        let mut b_sum_c1 = F::ZERO;
+        let mut b_sum = [F::ZERO; DEGREE];
         for b_poly in subarguments_multiplicities_encoding_polys.iter() {
-            for b in P::slice_into_base_slice(&b_poly[0].storage).iter() {
-                b_sum_c0.add_assign(b);
-            }
-            for b in P::slice_into_base_slice(&b_poly[1].storage).iter() {
-                b_sum_c1.add_assign(b);
+            for (b_sum, b_poly) in b_sum.iter_mut().zip(b_poly.iter()) {
+                for b in P::slice_into_base_slice(&b_poly.storage).iter() {
+                    b_sum.add_assign(b);
+                }
             }
         }
 
-        if a_sum_c0 != b_sum_c0 || a_sum_c1 != b_sum_c1 {
-            panic!(
-                "Sumcheck fails with a = [{:?}, {:?}], b = [{:?}, {:?}]",
-                a_sum_c0, a_sum_c1, b_sum_c0, b_sum_c1,
-            );
+        if a_sum != b_sum {
+            panic!("Sumcheck fails with a = {:?}, b = {:?}", a_sum, b_sum,);
         }
     }
 
@@ -951,25 +872,25 @@ pub(crate) fn compute_lookup_poly_pairs_specialized<
 
 pub(crate) fn compute_quotient_terms_for_lookup_specialized<
     F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
     A: GoodAllocator,
     B: GoodAllocator,
 >(
     witness: &WitnessStorage<F, P, A, B>,
-    second_stage: &SecondStageProductsStorage<F, P, A, B>,
+    second_stage: &SecondStageProductsStorage<F, DEGREE, P, A, B>,
     setup: &SetupStorage<F, P, A, B>,
-    beta: ExtensionField<F, 2, EXT>,
-    gamma: ExtensionField<F, 2, EXT>,
-    alphas: Vec<ExtensionField<F, 2, EXT>>,
+    beta: ExtensionField<F, DEGREE, EXT>,
+    gamma: ExtensionField<F, DEGREE, EXT>,
+    alphas: Vec<ExtensionField<F, DEGREE, EXT>>,
     table_id_column_idxes: Vec<usize>,
     column_elements_per_subargument: usize,
     num_subarguments: usize,
     num_multiplicities_polys: usize,
     variables_offset: usize,
     quotient_degree: usize,
-    dst_c0: &mut ArcGenericLdeStorage<F, P, A, B>,
-    dst_c1: &mut ArcGenericLdeStorage<F, P, A, B>,
+    dst: &mut [ArcGenericLdeStorage<F, P, A, B>; DEGREE],
     worker: &Worker,
     ctx: &mut P::Context,
 ) {
@@ -979,13 +900,9 @@ pub(crate) fn compute_quotient_terms_for_lookup_specialized<
 
     // B(x) * (gamma^0 * column_0 + ... + gamma^n * column_n + beta) == multiplicity column
 
-    let _gamma_c0 = P::constant(gamma.coeffs[0], ctx);
-    let _gamma_c1 = P::constant(gamma.coeffs[1], ctx);
-
-    let beta_c0 = P::constant(beta.coeffs[0], ctx);
-    let beta_c1 = P::constant(beta.coeffs[1], ctx);
+    let beta = beta.coeffs.map(|el| P::constant(el, ctx));
 
-    let domain_size = dst_c0.storage[0].domain_size();
+    let domain_size = dst[0].storage[0].domain_size();
 
     assert!(
         table_id_column_idxes.len() == 0 || table_id_column_idxes.len() == 1,
@@ -1000,37 +917,42 @@ pub(crate) fn compute_quotient_terms_for_lookup_specialized<
     // this is our lookup width, either counted by number of witness columns only, or if one includes setup
     let capacity = column_elements_per_subargument + ((table_id_column_idxes.len() == 1) as usize);
 
-    let mut powers_of_gamma_c0 = Vec::with_capacity_in(capacity, B::default());
-    let mut powers_of_gamma_c1 = Vec::with_capacity_in(capacity, B::default());
+    let mut powers_of_gamma = Vec::with_capacity_in(capacity, B::default());
     let mut tmp = {
         use crate::field::traits::field::Field;
 
-        ExtensionField::<F, 2, EXT>::ONE
+        ExtensionField::<F, DEGREE, EXT>::ONE
     };
-    powers_of_gamma_c0.push(P::constant(tmp.coeffs[0], ctx));
-    powers_of_gamma_c1.push(P::constant(tmp.coeffs[1], ctx));
+    powers_of_gamma.push(tmp.coeffs.map(|el| P::constant(el, ctx)));
     for _ in 1..capacity {
         crate::field::Field::mul_assign(&mut tmp, &gamma);
 
-        powers_of_gamma_c0.push(P::constant(tmp.coeffs[0], ctx));
-        powers_of_gamma_c1.push(P::constant(tmp.coeffs[1], ctx));
+        powers_of_gamma.push(tmp.coeffs.map(|el| P::constant(el, ctx)));
     }
 
     // for each term we form inputs, and then parallelize over them
 
-    assert_eq!(dst_c0.outer_len(), quotient_degree);
-    assert_eq!(dst_c1.outer_len(), quotient_degree);
-    let iterators = dst_c0.compute_chunks_for_num_workers(worker.num_cores);
+    for dst in dst.iter() {
+        assert_eq!(dst.outer_len(), quotient_degree);
+    }
+    let iterators = dst[0].compute_chunks_for_num_workers(worker.num_cores);
     // first precompute table aggregations
 
-    let aggregated_lookup_columns_c0 =
+    let first_lookup_column =
         setup.lookup_tables_columns[0].owned_subset_for_degree(quotient_degree);
-    let aggregated_lookup_columns_c1 = ArcGenericLdeStorage::<F, P, A, B>::zeroed(
-        aggregated_lookup_columns_c0.inner_len(),
-        aggregated_lookup_columns_c0.outer_len(),
-        A::default(),
-        B::default(),
-    );
+    let aggregated_lookup_columns: [_; DEGREE] = std::array::from_fn(|i| {
+        if i == 0 {
+            first_lookup_column.clone()
+        } else {
+            ArcGenericLdeStorage::<F, P, A, B>::zeroed(
+                first_lookup_column.inner_len(),
+                first_lookup_column.outer_len(),
+                A::default(),
+                B::default(),
+            )
+        }
+    });
+    drop(first_lookup_column);
 
     let mut other_lookup_columns =
         Vec::with_capacity_in(setup.lookup_tables_columns.len() - 1, B::default());
@@ -1040,65 +962,44 @@ pub(crate) fn compute_quotient_terms_for_lookup_specialized<
         .collect_into(&mut other_lookup_columns);
 
     // we access the memory exactly once
-    let dst_chunks = aggregated_lookup_columns_c0.compute_chunks_for_num_workers(worker.num_cores);
+    let dst_chunks = aggregated_lookup_columns[0].compute_chunks_for_num_workers(worker.num_cores);
     worker.scope(0, |scope, _| {
         // transpose other chunks
         for lde_iter in dst_chunks.into_iter() {
             let mut lde_iter = lde_iter;
-            let powers_of_gamma_c0 = &powers_of_gamma_c0[1..];
-            let powers_of_gamma_c1 = &powers_of_gamma_c1[1..];
-            assert_eq!(powers_of_gamma_c0.len(), other_lookup_columns.len());
-            assert_eq!(powers_of_gamma_c1.len(), other_lookup_columns.len());
+            let powers_of_gamma = &powers_of_gamma[1..];
+            assert_eq!(powers_of_gamma.len(), other_lookup_columns.len());
 
             let mut ctx = *ctx;
-            let mut aggregated_lookup_columns_c0 = aggregated_lookup_columns_c0.clone();
-            let mut aggregated_lookup_columns_c1 = aggregated_lookup_columns_c1.clone();
+            let mut aggregated_lookup_columns = aggregated_lookup_columns.clone();
 
             let other_lookup_columns = &other_lookup_columns;
             scope.spawn(move |_| {
                 for _ in 0..lde_iter.num_iterations() {
                     let (outer, inner) = lde_iter.current();
-                    let mut tmp_c0 = beta_c0;
-                    let mut tmp_c1 = beta_c1;
-
-                    for ((gamma_c0, gamma_c1), other) in powers_of_gamma_c0
-                        .iter()
-                        .zip(powers_of_gamma_c1.iter())
-                        .zip(other_lookup_columns.iter())
-                    {
-                        P::mul_and_accumulate_into(
-                            &mut tmp_c0,
-                            gamma_c0,
-                            &other.storage[outer].storage[inner],
-                            &mut ctx,
-                        );
+                    let mut tmp = beta;
 
-                        P::mul_and_accumulate_into(
-                            &mut tmp_c1,
-                            gamma_c1,
-                            &other.storage[outer].storage[inner],
-                            &mut ctx,
-                        );
+                    for (gamma, other) in powers_of_gamma.iter().zip(other_lookup_columns.iter()) {
+                        for (tmp, gamma) in tmp.iter_mut().zip(gamma.iter()) {
+                            P::mul_and_accumulate_into(
+                                tmp,
+                                gamma,
+                                &other.storage[outer].storage[inner],
+                                &mut ctx,
+                            );
+                        }
                     }
 
                     // our "base" value for `aggregated_lookup_columns` already contains a term 1 * column_0,
                     // so we just add
 
-                    unsafe {
-                        std::sync::Arc::get_mut_unchecked(
-                            &mut aggregated_lookup_columns_c0.storage[outer],
-                        )
-                        .storage[inner]
-                            .add_assign(&tmp_c0, &mut ctx);
-                    };
-
-                    unsafe {
-                        std::sync::Arc::get_mut_unchecked(
-                            &mut aggregated_lookup_columns_c1.storage[outer],
-                        )
-                        .storage[inner]
-                            .add_assign(&tmp_c1, &mut ctx);
-                    };
+                    for (dst, tmp) in aggregated_lookup_columns.iter_mut().zip(tmp.iter()) {
+                        unsafe {
+                            std::sync::Arc::get_mut_unchecked(&mut dst.storage[outer]).storage
+                                [inner]
+                                .add_assign(tmp, &mut ctx);
+                        };
+                    }
 
                     lde_iter.advance();
                 }
@@ -1120,14 +1021,12 @@ pub(crate) fn compute_quotient_terms_for_lookup_specialized<
         .zip(variables_columns_for_lookup.chunks_exact(column_elements_per_subargument))
         .enumerate()
     {
-        let alpha_c0 = P::constant(alpha.coeffs[0], ctx);
-        let alpha_c1 = P::constant(alpha.coeffs[1], ctx);
+        let alpha = alpha.coeffs.map(|el| P::constant(el, ctx));
 
         // A(x) * (gamma^0 * column_0 + ... + gamma^n * column_n + beta) == lookup_selector
-        let witness_encoding_poly_c0 =
-            &second_stage.lookup_witness_encoding_polys[idx][0].subset_for_degree(quotient_degree);
-        let witness_encoding_poly_c1 =
-            &second_stage.lookup_witness_encoding_polys[idx][1].subset_for_degree(quotient_degree);
+        let witness_encoding_poly = &second_stage.lookup_witness_encoding_polys[idx]
+            .clone()
+            .map(|el| el.subset_for_degree(quotient_degree));
 
         let mut columns = Vec::with_capacity_in(capacity, B::default());
         for wit_column in vars_chunk.iter() {
@@ -1144,65 +1043,49 @@ pub(crate) fn compute_quotient_terms_for_lookup_specialized<
             // transpose other chunks
             for lde_iter in iterators.iter().cloned() {
                 let mut lde_iter = lde_iter;
-                let powers_of_gamma_c0 = &powers_of_gamma_c0[..];
-                let powers_of_gamma_c1 = &powers_of_gamma_c1[..];
+                let powers_of_gamma = &powers_of_gamma[..];
                 let mut ctx = *ctx;
                 let columns = &columns;
-                assert_eq!(powers_of_gamma_c0.len(), columns.len());
-                assert_eq!(powers_of_gamma_c1.len(), columns.len());
+                assert_eq!(powers_of_gamma.len(), columns.len());
 
-                let mut dst_c0 = dst_c0.clone(); // we use Arc, so it's the same instance
-                let mut dst_c1 = dst_c1.clone(); // we use Arc, so it's the same instance
+                let mut dst = dst.clone(); // we use Arc, so it's the same instance
                 scope.spawn(move |_| {
                     let one = P::one(&mut ctx);
                     for _ in 0..lde_iter.num_iterations() {
                         let (outer, inner) = lde_iter.current();
-                        let mut tmp_c0 = beta_c0;
-                        let mut tmp_c1 = beta_c1;
-
-                        for ((gamma_c0, gamma_c1), other) in powers_of_gamma_c0
-                            .iter()
-                            .zip(powers_of_gamma_c1.iter())
-                            .zip(columns.iter())
-                        {
-                            P::mul_and_accumulate_into(
-                                &mut tmp_c0,
-                                gamma_c0,
-                                &other.storage[outer].storage[inner],
-                                &mut ctx,
-                            );
-
-                            P::mul_and_accumulate_into(
-                                &mut tmp_c1,
-                                gamma_c1,
-                                &other.storage[outer].storage[inner],
-                                &mut ctx,
-                            );
+                        let mut tmp = beta;
+
+                        for (gamma, other) in powers_of_gamma.iter().zip(columns.iter()) {
+                            for (tmp, gamma) in tmp.iter_mut().zip(gamma.iter()) {
+                                P::mul_and_accumulate_into(
+                                    tmp,
+                                    gamma,
+                                    &other.storage[outer].storage[inner],
+                                    &mut ctx,
+                                );
+                            }
                         }
                         // mul by A(X)
-                        mul_assign_vectorized_in_extension::<F, P, EXT>(
-                            &mut tmp_c0,
-                            &mut tmp_c1,
-                            &witness_encoding_poly_c0.storage[outer].storage[inner],
-                            &witness_encoding_poly_c1.storage[outer].storage[inner],
+                        let witness_encoding_value: [P; DEGREE] = std::array::from_fn(|i| {
+                            witness_encoding_poly[i].storage[outer].storage[inner]
+                        });
+                        mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(
+                            &mut tmp,
+                            &witness_encoding_value,
                             &mut ctx,
                         );
 
                         // subtract 1
-                        tmp_c0.sub_assign(&one, &mut ctx);
+                        tmp[0].sub_assign(&one, &mut ctx);
 
                         // mul by alpha
-                        mul_assign_vectorized_in_extension::<F, P, EXT>(
-                            &mut tmp_c0,
-                            &mut tmp_c1,
-                            &alpha_c0,
-                            &alpha_c1,
-                            &mut ctx,
+                        mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(
+                            &mut tmp, &alpha, &mut ctx,
                         );
 
                         if crate::config::DEBUG_SATISFIABLE == true {
                             if outer == 0 {
-                                if tmp_c0.is_zero() == false {
+                                if tmp[0].is_zero() == false {
                                     let mut normal_enumeration = inner.reverse_bits();
                                     normal_enumeration >>=
                                         usize::BITS - domain_size.trailing_zeros();
@@ -1215,16 +1098,13 @@ pub(crate) fn compute_quotient_terms_for_lookup_specialized<
                         }
 
                         // add into accumulator
-                        unsafe {
-                            std::sync::Arc::get_mut_unchecked(&mut dst_c0.storage[outer]).storage
-                                [inner]
-                                .add_assign(&tmp_c0, &mut ctx);
-                        };
-                        unsafe {
-                            std::sync::Arc::get_mut_unchecked(&mut dst_c1.storage[outer]).storage
-                                [inner]
-                                .add_assign(&tmp_c1, &mut ctx);
-                        };
+                        for (dst, tmp) in dst.iter_mut().zip(tmp.iter()) {
+                            unsafe {
+                                std::sync::Arc::get_mut_unchecked(&mut dst.storage[outer]).storage
+                                    [inner]
+                                    .add_assign(tmp, &mut ctx);
+                            };
+                        }
 
                         lde_iter.advance();
                     }
@@ -1243,15 +1123,11 @@ pub(crate) fn compute_quotient_terms_for_lookup_specialized<
         .iter()
         .enumerate()
     {
-        let alpha_c0 = P::constant(alpha.coeffs[0], ctx);
-        let alpha_c1 = P::constant(alpha.coeffs[1], ctx);
+        let alpha = alpha.coeffs.map(|el| P::constant(el, ctx));
         // B(x) * (gamma^0 * column_0 + ... + gamma^n * column_n + beta) == multiplicity column
-        let multiplicities_encoding_poly_c0 = &second_stage.lookup_multiplicities_encoding_polys
-            [idx][0]
-            .subset_for_degree(quotient_degree);
-        let multiplicities_encoding_poly_c1 = &second_stage.lookup_multiplicities_encoding_polys
-            [idx][1]
-            .subset_for_degree(quotient_degree);
+        let multiplicities_encoding_poly = &second_stage.lookup_multiplicities_encoding_polys[idx]
+            .clone()
+            .map(|el| el.subset_for_degree(quotient_degree));
         // columns are precomputed, so we need multiplicity
         let multiplicity =
             witness.lookup_multiplicities_polys[idx].subset_for_degree(quotient_degree);
@@ -1260,40 +1136,36 @@ pub(crate) fn compute_quotient_terms_for_lookup_specialized<
             for lde_iter in iterators.iter().cloned() {
                 let mut lde_iter = lde_iter;
                 let mut ctx = *ctx;
-                let mut dst_c0 = dst_c0.clone(); // we use Arc, so it's the same instance
-                let mut dst_c1 = dst_c1.clone(); // we use Arc, so it's the same instance
+                let mut dst = dst.clone(); // we use Arc, so it's the same instance
                 let multiplicity = &multiplicity;
-                let aggregated_lookup_columns_c0 = &aggregated_lookup_columns_c0;
-                let aggregated_lookup_columns_c1 = &aggregated_lookup_columns_c1;
+                let aggregated_lookup_columns = &aggregated_lookup_columns;
                 scope.spawn(move |_| {
                     for _ in 0..lde_iter.num_iterations() {
                         let (outer, inner) = lde_iter.current();
-                        let mut tmp_c0 = aggregated_lookup_columns_c0.storage[outer].storage[inner];
-                        let mut tmp_c1 = aggregated_lookup_columns_c1.storage[outer].storage[inner];
+                        let mut tmp: [P; DEGREE] = std::array::from_fn(|i| {
+                            aggregated_lookup_columns[i].storage[outer].storage[inner]
+                        });
                         // mul by B(X)
-                        mul_assign_vectorized_in_extension::<F, P, EXT>(
-                            &mut tmp_c0,
-                            &mut tmp_c1,
-                            &multiplicities_encoding_poly_c0.storage[outer].storage[inner],
-                            &multiplicities_encoding_poly_c1.storage[outer].storage[inner],
+                        let multiplicities_encoding_value: [P; DEGREE] = std::array::from_fn(|i| {
+                            multiplicities_encoding_poly[i].storage[outer].storage[inner]
+                        });
+                        mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(
+                            &mut tmp,
+                            &multiplicities_encoding_value,
                             &mut ctx,
                         );
 
                         // subtract multiplicity
-                        tmp_c0.sub_assign(&multiplicity.storage[outer].storage[inner], &mut ctx);
+                        tmp[0].sub_assign(&multiplicity.storage[outer].storage[inner], &mut ctx);
 
                         // mul by alpha
-                        mul_assign_vectorized_in_extension::<F, P, EXT>(
-                            &mut tmp_c0,
-                            &mut tmp_c1,
-                            &alpha_c0,
-                            &alpha_c1,
-                            &mut ctx,
+                        mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(
+                            &mut tmp, &alpha, &mut ctx,
                         );
 
                         if crate::config::DEBUG_SATISFIABLE == true {
                             if outer == 0 {
-                                if tmp_c0.is_zero() == false {
+                                if tmp[0].is_zero() == false {
                                     let mut normal_enumeration = inner.reverse_bits();
                                     normal_enumeration >>=
                                         usize::BITS - domain_size.trailing_zeros();
@@ -1306,16 +1178,13 @@ pub(crate) fn compute_quotient_terms_for_lookup_specialized<
                         }
 
                         // add into accumulator
-                        unsafe {
-                            std::sync::Arc::get_mut_unchecked(&mut dst_c0.storage[outer]).storage
-                                [inner]
-                                .add_assign(&tmp_c0, &mut ctx);
-                        };
-                        unsafe {
-                            std::sync::Arc::get_mut_unchecked(&mut dst_c1.storage[outer]).storage
-                                [inner]
-                                .add_assign(&tmp_c1, &mut ctx);
-                        };
+                        for (dst, tmp) in dst.iter_mut().zip(tmp.iter()) {
+                            unsafe {
+                                std::sync::Arc::get_mut_unchecked(&mut dst.storage[outer]).storage
+                                    [inner]
+                                    .add_assign(tmp, &mut ctx);
+                            };
+                        }
 
                         lde_iter.advance();
                     }
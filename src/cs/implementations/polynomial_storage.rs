@@ -33,6 +33,7 @@ pub struct WitnessStorage<
 #[derivative(Clone, Debug)]
 pub struct SecondStageProductsStorage<
     F: PrimeField,
+    const DEGREE: usize,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F> = F,
     A: GoodAllocator = Global,
     B: GoodAllocator = Global,
@@ -40,10 +41,10 @@ pub struct SecondStageProductsStorage<
     // We store full LDEs of the original polynomials.
     // For those we can produce adapters to properly iterate over
     // future leafs of the oracles
-    pub z_poly: [ArcGenericLdeStorage<F, P, A, B>; 2],
-    pub intermediate_polys: Vec<[ArcGenericLdeStorage<F, P, A, B>; 2], B>,
-    pub lookup_witness_encoding_polys: Vec<[ArcGenericLdeStorage<F, P, A, B>; 2], B>,
-    pub lookup_multiplicities_encoding_polys: Vec<[ArcGenericLdeStorage<F, P, A, B>; 2], B>,
+    pub z_poly: [ArcGenericLdeStorage<F, P, A, B>; DEGREE],
+    pub intermediate_polys: Vec<[ArcGenericLdeStorage<F, P, A, B>; DEGREE], B>,
+    pub lookup_witness_encoding_polys: Vec<[ArcGenericLdeStorage<F, P, A, B>; DEGREE], B>,
+    pub lookup_multiplicities_encoding_polys: Vec<[ArcGenericLdeStorage<F, P, A, B>; DEGREE], B>,
 }
 
 #[derive(Derivative, serde::Serialize, serde::Deserialize)]
@@ -699,10 +700,11 @@ impl<
 
 impl<
         F: PrimeField,
+        const DEGREE: usize,
         P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
         A: GoodAllocator,
         B: GoodAllocator,
-    > SecondStageProductsStorage<F, P, A, B>
+    > SecondStageProductsStorage<F, DEGREE, P, A, B>
 {
     pub(crate) fn flattened_source<'a>(
         &'a self,
@@ -3,6 +3,10 @@ use blake2::Digest;
 use sha3::Keccak256;
 
 use super::*;
+use crate::algebraic_props::round_function::AbsorptionModeOverwrite;
+use crate::algebraic_props::sponge::GoldilocksPoseidon2Sponge;
+use crate::field::goldilocks::GoldilocksField;
+use crate::field::U64Representable;
 
 pub trait PoWRunner: 'static + Send + Sync {
     fn run_from_field_elements<F: SmallField>(seed: Vec<F>, pow_bits: u32, worker: &Worker) -> u64 {
@@ -223,3 +227,127 @@ impl PoWRunner for Keccak256 {
         u64::from_le_bytes(le_bytes).trailing_zeros() >= pow_bits
     }
 }
+
+const POSEIDON2_NO_RESULT: u64 = u64::MAX;
+const POSEIDON2_ROUNDS_PER_INVOCAITON: usize = 1 << 12u32;
+
+// Algebraic PoW over Goldilocks, so it's cheap to verify in the recursive verifier.
+// We hash the seed and then the challenge as two 32-bit limbs as a Merkle tree leaf,
+// and require lowest `pow_bits` of the first output element to be zero
+pub struct Poseidon2Pow;
+
+impl Poseidon2Pow {
+    fn seed_into_goldilocks<F: SmallField>(seed: Vec<F>) -> Vec<GoldilocksField> {
+        assert_eq!(
+            F::CHAR,
+            GoldilocksField::CHAR,
+            "Poseidon2 PoW is only defined over Goldilocks"
+        );
+
+        seed.into_iter()
+            .map(|el| GoldilocksField::from_u64_unchecked(el.as_u64_reduced()))
+            .collect()
+    }
+
+    fn output_for_challenge(seed: &[GoldilocksField], challenge: u64) -> u64 {
+        let mut buffer = Vec::with_capacity(seed.len() + 2);
+        buffer.extend_from_slice(seed);
+        buffer.push(GoldilocksField::from_u64_unchecked(challenge as u32 as u64));
+        buffer.push(GoldilocksField::from_u64_unchecked(challenge >> 32));
+
+        let output =
+            <GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite> as crate::cs::oracle::TreeHasher<
+                GoldilocksField,
+            >>::hash_into_leaf(&buffer);
+
+        output[0].as_u64_reduced()
+    }
+}
+
+impl PoWRunner for Poseidon2Pow {
+    fn run_from_field_elements<F: SmallField>(seed: Vec<F>, pow_bits: u32, worker: &Worker) -> u64 {
+        assert!(pow_bits <= 32);
+
+        let seed = Self::seed_into_goldilocks(seed);
+
+        if pow_bits <= POSEIDON2_ROUNDS_PER_INVOCAITON.trailing_zeros() {
+            // serial case
+            log!("Do serial PoW");
+            for challenge in 0u64..(POSEIDON2_NO_RESULT - 1) {
+                if Self::output_for_challenge(&seed, challenge).trailing_zeros() >= pow_bits {
+                    return challenge;
+                }
+            }
+        }
+
+        use std::sync::atomic::AtomicU64;
+        use std::sync::atomic::Ordering;
+
+        let result = std::sync::Arc::new(AtomicU64::new(POSEIDON2_NO_RESULT));
+
+        log!("Do parallel PoW");
+
+        let pow_rounds_per_invocation = POSEIDON2_ROUNDS_PER_INVOCAITON as u64;
+        // it's good to parallelize
+        let num_workers = worker.num_cores as u64;
+        worker.scope(0, |scope, _| {
+            for worker_idx in 0..num_workers {
+                let seed = &seed;
+                let result = std::sync::Arc::clone(&result);
+                scope.spawn(move |_| {
+                    for i in
+                        0..((POSEIDON2_NO_RESULT - 1) / num_workers / pow_rounds_per_invocation)
+                    {
+                        let base = (worker_idx + i * num_workers) * pow_rounds_per_invocation;
+                        let current_flag = result.load(Ordering::Relaxed);
+                        if current_flag == POSEIDON2_NO_RESULT {
+                            for j in 0..pow_rounds_per_invocation {
+                                let challenge_u64 = base + j;
+                                if Self::output_for_challenge(seed, challenge_u64).trailing_zeros()
+                                    >= pow_bits
+                                {
+                                    let _ = result.compare_exchange(
+                                        POSEIDON2_NO_RESULT,
+                                        challenge_u64,
+                                        Ordering::Acquire,
+                                        Ordering::Relaxed,
+                                    );
+
+                                    break;
+                                }
+                            }
+                        } else {
+                            break;
+                        }
+                    }
+                })
+            }
+        });
+
+        let challenge_u64 = result.load(Ordering::SeqCst);
+
+        assert!(Self::output_for_challenge(&seed, challenge_u64).trailing_zeros() >= pow_bits);
+
+        challenge_u64
+    }
+
+    fn run_from_bytes(_seed: Vec<u8>, _pow_bits: u32, _worker: &Worker) -> u64 {
+        unimplemented!("Poseidon2 PoW is only defined over field elements")
+    }
+
+    fn verify_from_field_elements<F: SmallField>(
+        seed: Vec<F>,
+        pow_bits: u32,
+        challenge: u64,
+    ) -> bool {
+        let seed = Self::seed_into_goldilocks(seed);
+
+        Self::output_for_challenge(&seed, challenge).trailing_zeros() >= pow_bits
+    }
+
+    // there is no byte encoding of the seed, so no challenge is valid for it. Verifier
+    // must not panic on it, unlike the prover that is misconfigured in this case
+    fn verify_from_bytes(_seed: Vec<u8>, _pow_bits: u32, _challenge: u64) -> bool {
+        false
+    }
+}
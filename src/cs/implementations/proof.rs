@@ -118,7 +118,12 @@ use crate::field::FieldExtension;
 #[derive(derivative::Derivative, serde::Serialize, serde::Deserialize)]
 #[derivative(Clone(bound = ""), Debug(bound = ""), Hash(bound = ""))]
 #[serde(bound = "H::Output: serde::Serialize + serde::de::DeserializeOwned")]
-pub struct Proof<F: SmallField, H: TreeHasher<F>, EXT: FieldExtension<2, BaseField = F>> {
+pub struct Proof<
+    F: SmallField,
+    H: TreeHasher<F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+> {
     pub proof_config: ProofConfig,
 
     pub public_inputs: Vec<F>,
@@ -126,11 +131,12 @@ pub struct Proof<F: SmallField, H: TreeHasher<F>, EXT: FieldExtension<2, BaseFie
     pub witness_oracle_cap: Vec<H::Output>,
     pub stage_2_oracle_cap: Vec<H::Output>,
     pub quotient_oracle_cap: Vec<H::Output>,
-    pub final_fri_monomials: [Vec<F>; 2],
+    #[serde(with = "crate::serde_utils::BigArraySerde")]
+    pub final_fri_monomials: [Vec<F>; DEGREE],
 
-    pub values_at_z: Vec<ExtensionField<F, 2, EXT>>,
-    pub values_at_z_omega: Vec<ExtensionField<F, 2, EXT>>,
-    pub values_at_0: Vec<ExtensionField<F, 2, EXT>>,
+    pub values_at_z: Vec<ExtensionField<F, DEGREE, EXT>>,
+    pub values_at_z_omega: Vec<ExtensionField<F, DEGREE, EXT>>,
+    pub values_at_0: Vec<ExtensionField<F, DEGREE, EXT>>,
 
     pub fri_base_oracle_cap: Vec<H::Output>,
     pub fri_intermediate_oracles_caps: Vec<Vec<H::Output>>,
@@ -142,7 +148,13 @@ pub struct Proof<F: SmallField, H: TreeHasher<F>, EXT: FieldExtension<2, BaseFie
     pub _marker: std::marker::PhantomData<EXT>,
 }
 
-impl<F: SmallField, H: TreeHasher<F>, EXT: FieldExtension<2, BaseField = F>> Proof<F, H, EXT> {
+impl<
+        F: SmallField,
+        H: TreeHasher<F>,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
+    > Proof<F, H, DEGREE, EXT>
+{
     pub fn is_same_geometry(a: &Self, b: &Self) -> bool {
         if a.proof_config != b.proof_config {
             return false;
@@ -201,7 +213,7 @@ impl<F: SmallField, H: TreeHasher<F>, EXT: FieldExtension<2, BaseField = F>> Pro
 
     pub fn transmute_to_another_formal_hasher<HH: TreeHasher<F, Output = H::Output>>(
         self,
-    ) -> Proof<F, HH, EXT> {
+    ) -> Proof<F, HH, DEGREE, EXT> {
         let Proof {
             proof_config,
             public_inputs,
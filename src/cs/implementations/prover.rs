@@ -29,6 +29,10 @@ use crate::cs::implementations::proof::SingleRoundQueries;
 use crate::cs::implementations::transcript::BoolsBuffer;
 use crate::cs::traits::gate::GatePlacementStrategy;
 use crate::dag::WitnessSource;
+use crate::field::batch_inversion::{
+    batch_inverse_vectorized_inplace, batch_inverse_vectorized_inplace_in_extension,
+    chunk_columns_mut,
+};
 use crate::field::traits::field_like::mul_assign_vectorized_in_extension;
 use std::sync::Arc;
 
@@ -59,6 +63,9 @@ pub struct ProofConfig {
     pub fri_folding_schedule: Option<Vec<usize>>,
     pub security_level: usize,
     pub pow_bits: u32,
+    // opt-in salting of oracle leaves, see `ORACLE_LEAF_SALT_SIZE`
+    #[serde(default)]
+    pub salt_oracle_leaves: bool,
 }
 
 impl std::default::Default for ProofConfig {
@@ -69,6 +76,22 @@ impl std::default::Default for ProofConfig {
             fri_folding_schedule: None,
             security_level: 100,
             pow_bits: 20,
+            salt_oracle_leaves: false,
+        }
+    }
+}
+
+// If salting is enabled every leaf of witness, stage 2 and quotient oracles is extended by this number
+// of random elements, so Merkle caps and sibling hashes in paths are not a deterministic function
+// of the trace. Values opened at z and at FRI queries are the same as without salting
+pub const ORACLE_LEAF_SALT_SIZE: usize = 4;
+
+impl ProofConfig {
+    pub fn leaf_salt_size(&self) -> usize {
+        if self.salt_oracle_leaves {
+            ORACLE_LEAF_SALT_SIZE
+        } else {
+            0
         }
     }
 }
@@ -170,7 +193,8 @@ impl<
     }
 
     pub fn prove_cpu_basic<
-        EXT: FieldExtension<2, BaseField = F>,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
         TR: Transcript<F>,
         H: TreeHasher<F, Output = TR::CompatibleCap>,
         POW: PoWRunner,
@@ -184,8 +208,12 @@ impl<
         vk: &VerificationKey<F, H>,
         proof_config: ProofConfig,
         transcript_params: TR::TransciptParameters,
-    ) -> Proof<F, H, EXT> {
-        assert!(proof_config.fri_lde_factor.is_power_of_two());
+    ) -> Proof<F, H, DEGREE, EXT> {
+        assert!(
+            proof_config.fri_lde_factor.is_power_of_two(),
+            "FRI requires power of two LDE factor, but {} is given. Other factors are only supported by `FftPlan::lde`",
+            proof_config.fri_lde_factor
+        );
         assert!(proof_config.fri_lde_factor > 1);
 
         profile_fn!(prove_cpu_basic);
@@ -214,7 +242,7 @@ impl<
         );
 
         let interactive_soundness =
-            (F::CAPACITY_BITS * 2) - base_system_degree.trailing_zeros() as usize;
+            (F::CAPACITY_BITS * DEGREE) - base_system_degree.trailing_zeros() as usize;
         dbg!(interactive_soundness);
 
         let cap_size = proof_config.merkle_tree_cap_size;
@@ -361,6 +389,13 @@ impl<
                 .map(|el| el.subset_for_degree(proof_config.fri_lde_factor)),
         );
 
+        let witness_salt = random_leaf_salt::<F, P>(
+            proof_config.leaf_salt_size(),
+            source[0].inner_len(),
+            source[0].outer_len(),
+        );
+        source.extend(witness_salt.iter().cloned());
+
         log!("Witness LDE taken {:?}", now.elapsed());
 
         let witness_tree = MerkleTreeWithCap::<F, H>::construct(source, cap_size, worker);
@@ -376,11 +411,11 @@ impl<
         // here we commit to our original witness,
         // potentially including lookup related one
 
-        let beta_coeffs = transcript.get_multiple_challenges_fixed::<2>();
-        let beta = ExtensionField::<F, 2, EXT>::from_coeff_in_base(beta_coeffs);
+        let beta_coeffs = transcript.get_multiple_challenges_fixed::<DEGREE>();
+        let beta = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(beta_coeffs);
 
-        let gamma_coeffs = transcript.get_multiple_challenges_fixed::<2>();
-        let gamma = ExtensionField::<F, 2, EXT>::from_coeff_in_base(gamma_coeffs);
+        let gamma_coeffs = transcript.get_multiple_challenges_fixed::<DEGREE>();
+        let gamma = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(gamma_coeffs);
 
         let copy_permutation_chunking_degree = quotient_degree;
 
@@ -390,6 +425,7 @@ impl<
             super::copy_permutation::compute_partial_products_in_extension::<
                 F,
                 P,
+                DEGREE,
                 EXT,
                 Global,
                 Global,
@@ -418,11 +454,11 @@ impl<
             profile_section!(get_lookups);
 
             // lookup argument related parts
-            let lookup_beta = transcript.get_multiple_challenges_fixed::<2>();
-            let lookup_beta = ExtensionField::<F, 2, EXT>::from_coeff_in_base(lookup_beta);
+            let lookup_beta = transcript.get_multiple_challenges_fixed::<DEGREE>();
+            let lookup_beta = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(lookup_beta);
 
-            let lookup_gamma = transcript.get_multiple_challenges_fixed::<2>();
-            let lookup_gamma = ExtensionField::<F, 2, EXT>::from_coeff_in_base(lookup_gamma);
+            let lookup_gamma = transcript.get_multiple_challenges_fixed::<DEGREE>();
+            let lookup_gamma = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(lookup_gamma);
 
             let contributions = match self.lookup_parameters {
                 LookupParameters::NoLookup => {
@@ -496,7 +532,7 @@ impl<
 
             (contributions, lookup_beta, lookup_gamma)
         } else {
-            let zero = ExtensionField::<F, 2, EXT>::ZERO;
+            let zero = ExtensionField::<F, DEGREE, EXT>::ZERO;
 
             ((vec![], vec![]), zero, zero)
         };
@@ -565,6 +601,13 @@ impl<
                 .map(|el| el.subset_for_degree(proof_config.fri_lde_factor)),
         );
 
+        let second_stage_salt = random_leaf_salt::<F, P>(
+            proof_config.leaf_salt_size(),
+            source[0].inner_len(),
+            source[0].outer_len(),
+        );
+        source.extend(second_stage_salt.iter().cloned());
+
         let second_stage_tree = MerkleTreeWithCap::<F, H>::construct(source, cap_size, worker);
 
         // now we can commit to grand products and get new challenges
@@ -576,8 +619,8 @@ impl<
 
         let now = std::time::Instant::now();
 
-        let alpha = transcript.get_multiple_challenges_fixed::<2>();
-        let alpha = ExtensionField::<F, 2, EXT>::from_coeff_in_base(alpha);
+        let alpha = transcript.get_multiple_challenges_fixed::<DEGREE>();
+        let alpha = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(alpha);
 
         // two extra relations per lookup subargument - for A and B polys
         let num_lookup_subarguments = self.num_sublookup_arguments();
@@ -660,6 +703,7 @@ impl<
         let mut destination = GateEvaluationReducingDestination::<F, P>::new(
             inner_size,
             quotient_degree,
+            DEGREE,
             vec![], // we will reassign later on
             ctx,
         );
@@ -743,7 +787,7 @@ impl<
             let pregenerated_challenges_for_gates_over_specialized_columns: Vec<_> =
                 pregenerated_challenges_for_gates_over_specialized_columns
                     .into_iter()
-                    .map(|el| el.into_coeffs_in_base())
+                    .flat_map(|el| el.into_coeffs_in_base())
                     .collect();
             destination.challenges_powers =
                 std::sync::Arc::new(pregenerated_challenges_for_gates_over_specialized_columns);
@@ -824,7 +868,7 @@ impl<
         let pregenerated_challenges_for_gates_over_general_purpose_columns: Vec<_> =
             pregenerated_challenges_for_gates_over_general_purpose_columns
                 .into_iter()
-                .map(|el| el.into_coeffs_in_base())
+                .flat_map(|el| el.into_coeffs_in_base())
                 .collect();
         destination.challenges_powers =
             std::sync::Arc::new(pregenerated_challenges_for_gates_over_general_purpose_columns);
@@ -1106,11 +1150,12 @@ impl<
         drop(sect_5);
         profile_section!(sect_6);
 
-        let [quotient_c0s, quotient_c1s] =
+        let quotient_buffers: Vec<Vec<Vec<P>>> =
             Arc::try_unwrap(destination.quotient_buffers).expect("must be exclusively owned");
+        assert_eq!(quotient_buffers.len(), DEGREE);
 
         if crate::config::DEBUG_SATISFIABLE {
-            for (idx, el) in quotient_c0s[0].iter().enumerate() {
+            for (idx, el) in quotient_buffers[0][0].iter().enumerate() {
                 if el.is_zero() == false {
                     let mut normal_enumeration = idx.reverse_bits();
                     normal_enumeration >>= usize::BITS - domain_size.trailing_zeros();
@@ -1128,7 +1173,7 @@ impl<
         }
 
         let inner_size = domain_size / P::SIZE_FACTOR;
-        let outer_size = quotient_c0s.len();
+        let outer_size = quotient_buffers[0].len();
         let quotient_domain_size = outer_size * domain_size;
         let inverse_twiddles =
             P::precompute_inverse_twiddles_for_fft::<Global>(quotient_domain_size, worker, ctx);
@@ -1177,86 +1222,78 @@ impl<
 
             // the last part is z(x * omega) = partial_product_{n-1} * rational_function
 
-            let mut q_c0_as_lde =
-                ArcGenericLdeStorage::empty_with_capacity_in(quotient_c0s.len(), Global);
-            let mut q_c1_as_lde =
-                ArcGenericLdeStorage::empty_with_capacity_in(quotient_c0s.len(), Global);
-            for el in quotient_c0s.into_iter() {
-                q_c0_as_lde
-                    .storage
-                    .push(Arc::new(GenericPolynomial::from_storage(el)));
-            }
-            for el in quotient_c1s.into_iter() {
-                q_c1_as_lde
-                    .storage
-                    .push(Arc::new(GenericPolynomial::from_storage(el)));
-            }
+            let mut quotient_buffers = quotient_buffers.into_iter();
+            let mut q_as_lde: [ArcGenericLdeStorage<F, P, Global, Global>; DEGREE] =
+                std::array::from_fn(|_| {
+                    let buffers = quotient_buffers
+                        .next()
+                        .expect("must have all the coefficients");
+                    let mut as_lde =
+                        ArcGenericLdeStorage::empty_with_capacity_in(buffers.len(), Global);
+                    for el in buffers.into_iter() {
+                        as_lde
+                            .storage
+                            .push(Arc::new(GenericPolynomial::from_storage(el)));
+                    }
+
+                    as_lde
+                });
 
             let mut challenges_it = remaining_challenges.iter();
             let mut lookup_challenges_it = pregenerated_challenges_for_lookup.iter();
 
             let one = P::one(ctx);
             let alpha_power = challenges_it.next().expect("must have enough challenges");
-            let alpha_power_c0 = P::constant(alpha_power.coeffs[0], ctx);
-            let alpha_power_c1 = P::constant(alpha_power.coeffs[1], ctx);
-
-            let z_poly_c0 = second_stage_polys_storage.z_poly[0].subset_for_degree(quotient_degree);
-            let z_poly_c1 = second_stage_polys_storage.z_poly[1].subset_for_degree(quotient_degree);
+            let alpha_power: [P; DEGREE] = alpha_power.coeffs.map(|el| P::constant(el, ctx));
 
-            let mut dst = [q_c0_as_lde, q_c1_as_lde];
+            let z_poly: [ArcGenericLdeStorage<F, P, Global, Global>; DEGREE] =
+                std::array::from_fn(|i| {
+                    second_stage_polys_storage.z_poly[i].subset_for_degree(quotient_degree)
+                });
 
             if crate::config::DEBUG_SATISFIABLE == false {
-                let src = [
-                    z_poly_c0.clone(),
-                    z_poly_c1.clone(),
-                    unnormalized_l1_inverse,
-                ];
+                let l1_inverse = unnormalized_l1_inverse;
 
                 let op = #[inline(always)]
-                move |dst: &mut [ArcGenericLdeStorage<F, P, Global, Global>; 2],
-                               src: &[ArcGenericLdeStorage<F, P, Global, Global>; 3],
+                move |dst: &mut [ArcGenericLdeStorage<F, P, Global, Global>; DEGREE],
+                               src: &[ArcGenericLdeStorage<F, P, Global, Global>; DEGREE],
                                outer: usize,
                                inner: usize,
                                ctx: &mut P::Context| {
-                    let mut z_c0 = src[0].storage[outer].storage[inner];
+                    let l1_inv = &l1_inverse.storage[outer].storage[inner];
+                    let mut z: [P; DEGREE] =
+                        std::array::from_fn(|i| src[i].storage[outer].storage[inner]);
                     // sub 1
-                    z_c0.sub_assign(&one, ctx);
-                    let mut z_c1 = src[1].storage[outer].storage[inner];
-                    let l1_inv = &src[2].storage[outer].storage[inner];
-                    z_c0.mul_assign(l1_inv, ctx);
-                    z_c1.mul_assign(l1_inv, ctx);
+                    z[0].sub_assign(&one, ctx);
+                    for el in z.iter_mut() {
+                        el.mul_assign(l1_inv, ctx);
+                    }
                     // mul by alpha
-                    mul_assign_vectorized_in_extension::<F, P, EXT>(
-                        &mut z_c0,
-                        &mut z_c1,
-                        &alpha_power_c0,
-                        &alpha_power_c1,
+                    mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(
+                        &mut z,
+                        &alpha_power,
                         ctx,
                     );
 
-                    unsafe {
-                        Arc::get_mut_unchecked(&mut dst[0].storage[outer]).storage[inner]
-                            .add_assign(&z_c0, ctx);
-
-                        Arc::get_mut_unchecked(&mut dst[1].storage[outer]).storage[inner]
-                            .add_assign(&z_c1, ctx);
+                    for (dst, z) in dst.iter_mut().zip(z.iter()) {
+                        unsafe {
+                            Arc::get_mut_unchecked(&mut dst.storage[outer]).storage[inner]
+                                .add_assign(z, ctx);
+                        }
                     }
                 };
 
-                apply_multiop(&mut dst, &src, &op, worker, ctx)
+                apply_multiop(&mut q_as_lde, &z_poly, &op, worker, ctx)
             } else {
-                debug_assert_eq!(
-                    P::slice_into_base_slice(&z_poly_c0.storage[0].storage)[0],
-                    F::ONE
-                );
-                debug_assert_eq!(
-                    P::slice_into_base_slice(&z_poly_c1.storage[0].storage)[0],
-                    F::ZERO
-                );
+                for (idx, z_poly) in z_poly.iter().enumerate() {
+                    let expected = if idx == 0 { F::ONE } else { F::ZERO };
+                    debug_assert_eq!(
+                        P::slice_into_base_slice(&z_poly.storage[0].storage)[0],
+                        expected
+                    );
+                }
             }
 
-            let [mut q_c0_as_lde, mut q_c1_as_lde] = dst;
-
             let num_challenges = num_intermediate_partial_product_relations + 1;
             let mut alphas = Vec::with_capacity(num_challenges);
             for _ in 0..num_challenges {
@@ -1279,8 +1316,7 @@ impl<
                 gamma,
                 alphas,
                 &x_poly_lde,
-                &mut q_c0_as_lde,
-                &mut q_c1_as_lde,
+                &mut q_as_lde,
                 worker,
                 ctx,
             );
@@ -1372,8 +1408,7 @@ impl<
                         num_multiplicities_polys,
                         self.parameters.num_columns_under_copy_permutation,
                         quotient_degree,
-                        &mut q_c0_as_lde,
-                        &mut q_c1_as_lde,
+                        &mut q_as_lde,
                         worker,
                         ctx,
                     );
@@ -1382,85 +1417,54 @@ impl<
 
             assert_eq!(challenges_it.len(), 0, "must exhaust all the challenges");
 
-            let mut q_c0_as_vectors: Vec<Vec<P>> = q_c0_as_lde
-                .storage
-                .into_iter()
-                .map(|el| {
-                    Arc::try_unwrap(el)
-                        .expect("must be exclusively owned")
-                        .into_storage()
-                })
-                .collect();
-
-            let mut q_c1_as_vectors: Vec<Vec<P>> = q_c1_as_lde
-                .storage
-                .into_iter()
-                .map(|el| {
-                    Arc::try_unwrap(el)
-                        .expect("must be exclusively owned")
-                        .into_storage()
-                })
-                .collect();
-
-            if crate::config::DEBUG_SATISFIABLE == false {
-                divide_by_vanishing_for_bitreversed_coset_enumeration(
-                    &mut q_c0_as_vectors,
-                    worker,
-                    ctx,
-                );
-                divide_by_vanishing_for_bitreversed_coset_enumeration(
-                    &mut q_c1_as_vectors,
-                    worker,
-                    ctx,
-                );
-            }
+            q_as_lde.map(|q_as_lde| {
+                let mut q_as_vectors: Vec<Vec<P>> = q_as_lde
+                    .storage
+                    .into_iter()
+                    .map(|el| {
+                        Arc::try_unwrap(el)
+                            .expect("must be exclusively owned")
+                            .into_storage()
+                    })
+                    .collect();
 
-            let q_c0: Vec<Vec<F>> = q_c0_as_vectors
-                .into_iter()
-                .map(|el| P::vec_into_base_vec(el))
-                .collect();
+                if crate::config::DEBUG_SATISFIABLE == false {
+                    divide_by_vanishing_for_bitreversed_coset_enumeration(
+                        &mut q_as_vectors,
+                        worker,
+                        ctx,
+                    );
+                }
 
-            let q_c1: Vec<Vec<F>> = q_c1_as_vectors
-                .into_iter()
-                .map(|el| P::vec_into_base_vec(el))
-                .collect();
+                let q: Vec<Vec<F>> = q_as_vectors
+                    .into_iter()
+                    .map(|el| P::vec_into_base_vec(el))
+                    .collect();
 
-            let flattened_c0 = flatten_presumably_bitreversed::<_, P, _, _>(q_c0, worker);
-            let flattened_c1 = flatten_presumably_bitreversed::<_, P, _, _>(q_c1, worker);
-            let mut monomial_form_c0 = P::vec_from_base_vec(flattened_c0);
-            let mut monomial_form_c1 = P::vec_from_base_vec(flattened_c1);
+                let flattened = flatten_presumably_bitreversed::<_, P, _, _>(q, worker);
+                let mut monomial_form = P::vec_from_base_vec(flattened);
 
-            if crate::config::DEBUG_SATISFIABLE {
-                let as_slice = P::slice_into_base_slice(&monomial_form_c0);
-                for (idx, el) in as_slice.iter().step_by(quotient_degree).enumerate() {
-                    assert_eq!(*el, F::ZERO, "poly is not divisible at row {}", idx);
+                if crate::config::DEBUG_SATISFIABLE {
+                    let as_slice = P::slice_into_base_slice(&monomial_form);
+                    for (idx, el) in as_slice.iter().step_by(quotient_degree).enumerate() {
+                        assert_eq!(*el, F::ZERO, "poly is not divisible at row {}", idx);
+                    }
                 }
-            }
 
-            P::ifft_natural_to_natural(&mut monomial_form_c0, coset, &inverse_twiddles, ctx);
-            P::ifft_natural_to_natural(&mut monomial_form_c1, coset, &inverse_twiddles, ctx);
+                P::ifft_natural_to_natural(&mut monomial_form, coset, &inverse_twiddles, ctx);
 
-            if crate::config::DEBUG_SATISFIABLE == false {
-                assert!(
-                    P::slice_into_base_slice(&monomial_form_c0)
-                        .last()
-                        .unwrap()
-                        .is_zero(),
-                    "unsatisfied",
-                );
-                assert!(
-                    P::slice_into_base_slice(&monomial_form_c1)
-                        .last()
-                        .unwrap()
-                        .is_zero(),
-                    "unsatisfied",
-                );
-            }
+                if crate::config::DEBUG_SATISFIABLE == false {
+                    assert!(
+                        P::slice_into_base_slice(&monomial_form)
+                            .last()
+                            .unwrap()
+                            .is_zero(),
+                        "unsatisfied",
+                    );
+                }
 
-            [
-                GenericPolynomial::<F, MonomialForm, P, _>::from_storage(monomial_form_c0),
-                GenericPolynomial::<F, MonomialForm, P, _>::from_storage(monomial_form_c1),
-            ]
+                GenericPolynomial::<F, MonomialForm, P, _>::from_storage(monomial_form)
+            })
         };
 
         // now we can chunk every polynomial and LDE them
@@ -1471,18 +1475,23 @@ impl<
         // and memory consumption
 
         let mut quotient_chunks = vec![];
-        let [q_c0, q_c1] = quotient_monomials;
-        // we layout [c0, c1] in every chunk
-
-        for (c0, c1) in q_c0
-            .chunk_into_subpolys_of_degree::<Global>(base_system_degree)
+        // we layout [c0, c1, ...] in every chunk
+        let mut chunked_coeffs: Vec<_> = quotient_monomials
             .into_iter()
-            .zip(q_c1.chunk_into_subpolys_of_degree::<Global>(base_system_degree))
-        {
-            let c0 = c0.into_storage();
-            let c1 = c1.into_storage();
-            quotient_chunks.push(c0);
-            quotient_chunks.push(c1);
+            .map(|el| {
+                el.chunk_into_subpolys_of_degree::<Global>(base_system_degree)
+                    .into_iter()
+            })
+            .collect();
+
+        for _ in 0..quotient_degree {
+            for coeff in chunked_coeffs.iter_mut() {
+                let chunk = coeff.next().expect("must have enough chunks");
+                quotient_chunks.push(chunk.into_storage());
+            }
+        }
+        for coeff in chunked_coeffs.iter_mut() {
+            assert!(coeff.next().is_none());
         }
 
         // how we should LDE quotients and form another oracle
@@ -1505,7 +1514,13 @@ impl<
 
         log!("Quotient work and LDE taken {:?}", now.elapsed());
 
-        let source = quotient_chunks_ldes.clone();
+        let quotients_salt = random_leaf_salt::<F, P>(
+            proof_config.leaf_salt_size(),
+            quotient_chunks_ldes[0].inner_len(),
+            quotient_chunks_ldes[0].outer_len(),
+        );
+        let mut source = quotient_chunks_ldes.clone();
+        source.extend(quotients_salt.iter().cloned());
         let quotients_tree = MerkleTreeWithCap::<F, H>::construct(source, cap_size, worker);
 
         // now we can commit to grand products and get new challenges
@@ -1517,13 +1532,13 @@ impl<
 
         let now = std::time::Instant::now();
 
-        let z = transcript.get_multiple_challenges_fixed::<2>();
-        let z = ExtensionField::<F, 2, EXT>::from_coeff_in_base(z);
+        let z = transcript.get_multiple_challenges_fixed::<DEGREE>();
+        let z = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(z);
 
         // we have to evaluate all sets at the corresponding point
 
-        // let mut naive = ExtensionField::<F, 2, EXT>::ZERO;
-        // let mut current = ExtensionField::<F, 2, EXT>::ONE;
+        // let mut naive = ExtensionField::<F, DEGREE, EXT>::ZERO;
+        // let mut current = ExtensionField::<F, DEGREE, EXT>::ONE;
         // for (c0, c1) in P::slice_into_base_slice(&quotient_chunks_tmp[0]).iter()
         //     .zip(P::slice_into_base_slice(&quotient_chunks_tmp[1]).iter()) {
         //     let mut tmp = ExtensionField::from_coeff_in_base([*c0, *c1]);
@@ -1535,33 +1550,34 @@ impl<
 
         // dbg!(&naive);
 
-        let [precomputed_lagranges_c0, precomputed_lagranges_c1] =
-            precompute_for_barycentric_evaluation_in_extension::<F, EXT, P, Global>(
+        let precomputed_lagranges =
+            precompute_for_barycentric_evaluation_in_extension::<F, DEGREE, EXT, P, Global>(
                 domain_size,
                 F::multiplicative_generator(),
                 z,
                 worker,
                 ctx,
             );
+        let lagranges: [&[F]; DEGREE] =
+            std::array::from_fn(|i| P::slice_into_base_slice(&precomputed_lagranges[i]));
 
         let mut all_polys_at_zs = vec![];
 
         let evaluate_from_base = |coeffs: &[P]| {
             barycentric_evaluate_base_at_extension_for_bitreversed_parallel(
                 P::slice_into_base_slice(coeffs),
-                &P::slice_into_base_slice(&precomputed_lagranges_c0),
-                &P::slice_into_base_slice(&precomputed_lagranges_c1),
+                lagranges,
                 &worker,
             )
         };
 
-        let evaluate_from_extension = |coeffs_c0: &[P], coeffs_c1: &[P]| {
-            barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, EXT>(
-                P::slice_into_base_slice(coeffs_c0),
-                P::slice_into_base_slice(coeffs_c1),
-                &P::slice_into_base_slice(&precomputed_lagranges_c0),
-                &P::slice_into_base_slice(&precomputed_lagranges_c1),
-                &worker,
+        let evaluate_from_extension = |polys: &[ArcGenericLdeStorage<F, P, Global, Global>;
+                                            DEGREE]| {
+            let coeffs: [&[F]; DEGREE] = std::array::from_fn(|i| {
+                P::slice_into_base_slice(&polys[i].storage[0].as_ref().storage)
+            });
+            barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, DEGREE, EXT>(
+                coeffs, lagranges, &worker,
             )
         };
 
@@ -1573,7 +1589,7 @@ impl<
                 .iter()
                 .map(|el| &el.storage[0].as_ref().storage)
                 .map(|el| evaluate_from_base(el))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(el)),
         );
         all_polys_at_zs.extend(
             trace_holder
@@ -1582,7 +1598,7 @@ impl<
                 .iter()
                 .map(|el| &el.storage[0].as_ref().storage)
                 .map(|el| evaluate_from_base(el))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(el)),
         );
         // "normal" setup
         all_polys_at_zs.extend(
@@ -1592,7 +1608,7 @@ impl<
                 .iter()
                 .map(|el| &el.storage[0].as_ref().storage)
                 .map(|el| evaluate_from_base(el))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(el)),
         );
         all_polys_at_zs.extend(
             trace_holder
@@ -1601,31 +1617,18 @@ impl<
                 .iter()
                 .map(|el| &el.storage[0].as_ref().storage)
                 .map(|el| evaluate_from_base(el))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(el)),
         );
         // copy-permutation
-        all_polys_at_zs.push(ExtensionField::<F, 2, EXT>::from_coeff_in_base(
-            evaluate_from_extension(
-                &second_stage_polys_storage.z_poly[0].storage[0]
-                    .as_ref()
-                    .storage,
-                &second_stage_polys_storage.z_poly[1].storage[0]
-                    .as_ref()
-                    .storage,
-            ),
+        all_polys_at_zs.push(ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(
+            evaluate_from_extension(&second_stage_polys_storage.z_poly),
         ));
         all_polys_at_zs.extend(
             second_stage_polys_storage
                 .intermediate_polys
                 .iter()
-                .map(|[a, b]| {
-                    [
-                        &a.storage[0].as_ref().storage,
-                        &b.storage[0].as_ref().storage,
-                    ]
-                })
-                .map(|[a, b]| evaluate_from_extension(a, b))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| evaluate_from_extension(el))
+                .map(|el| ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(el)),
         );
 
         assert_eq!(
@@ -1646,34 +1649,22 @@ impl<
                 .iter()
                 .map(|el| &el.storage[0].as_ref().storage)
                 .map(|el| evaluate_from_base(el))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(el)),
         );
         // lookup witness and multiplicities
         all_polys_at_zs.extend(
             second_stage_polys_storage
                 .lookup_witness_encoding_polys
                 .iter()
-                .map(|[a, b]| {
-                    [
-                        &a.storage[0].as_ref().storage,
-                        &b.storage[0].as_ref().storage,
-                    ]
-                })
-                .map(|[a, b]| evaluate_from_extension(a, b))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| evaluate_from_extension(el))
+                .map(|el| ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(el)),
         );
         all_polys_at_zs.extend(
             second_stage_polys_storage
                 .lookup_multiplicities_encoding_polys
                 .iter()
-                .map(|[a, b]| {
-                    [
-                        &a.storage[0].as_ref().storage,
-                        &b.storage[0].as_ref().storage,
-                    ]
-                })
-                .map(|[a, b]| evaluate_from_extension(a, b))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| evaluate_from_extension(el))
+                .map(|el| ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(el)),
         );
         // lookup setup
         // tables columns
@@ -1684,21 +1675,15 @@ impl<
                 .iter()
                 .map(|el| &el.storage[0].as_ref().storage)
                 .map(|el| evaluate_from_base(el))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(el)),
         );
         // table ids are not needed as those come from constatns in general
         // quotient
         all_polys_at_zs.extend(
             quotient_chunks_ldes
-                .array_chunks::<2>()
-                .map(|[a, b]| {
-                    [
-                        &a.storage[0].as_ref().storage,
-                        &b.storage[0].as_ref().storage,
-                    ]
-                })
-                .map(|[a, b]| evaluate_from_extension(a, b))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .array_chunks::<DEGREE>()
+                .map(|el| evaluate_from_extension(el))
+                .map(|el| ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(el)),
         );
 
         let expected_lookup_polys_total = if self.lookup_parameters.lookup_is_allowed() {
@@ -1726,35 +1711,30 @@ impl<
         let mut z_omega = z;
         z_omega.mul_assign_by_base(&domain_generator_for_size::<F>(domain_size as u64));
 
-        let [precomputed_lagranges_c0, precomputed_lagranges_c1] =
-            precompute_for_barycentric_evaluation_in_extension::<F, EXT, P, Global>(
+        let precomputed_lagranges =
+            precompute_for_barycentric_evaluation_in_extension::<F, DEGREE, EXT, P, Global>(
                 domain_size,
                 F::multiplicative_generator(),
                 z_omega,
                 worker,
                 ctx,
             );
+        let lagranges: [&[F]; DEGREE] =
+            std::array::from_fn(|i| P::slice_into_base_slice(&precomputed_lagranges[i]));
 
-        let evaluate_from_extension = |coeffs_c0: &[P], coeffs_c1: &[P]| {
-            barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, EXT>(
-                P::slice_into_base_slice(coeffs_c0),
-                P::slice_into_base_slice(coeffs_c1),
-                &P::slice_into_base_slice(&precomputed_lagranges_c0),
-                &P::slice_into_base_slice(&precomputed_lagranges_c1),
-                &worker,
+        let evaluate_from_extension = |polys: &[ArcGenericLdeStorage<F, P, Global, Global>;
+                                            DEGREE]| {
+            let coeffs: [&[F]; DEGREE] = std::array::from_fn(|i| {
+                P::slice_into_base_slice(&polys[i].storage[0].as_ref().storage)
+            });
+            barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, DEGREE, EXT>(
+                coeffs, lagranges, &worker,
             )
         };
 
         let mut all_polys_at_zomegas = vec![];
-        all_polys_at_zomegas.push(ExtensionField::<F, 2, EXT>::from_coeff_in_base(
-            evaluate_from_extension(
-                &second_stage_polys_storage.z_poly[0].storage[0]
-                    .as_ref()
-                    .storage,
-                &second_stage_polys_storage.z_poly[1].storage[0]
-                    .as_ref()
-                    .storage,
-            ),
+        all_polys_at_zomegas.push(ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(
+            evaluate_from_extension(&second_stage_polys_storage.z_poly),
         ));
         assert_eq!(all_polys_at_zomegas.len(), 1);
 
@@ -1768,22 +1748,24 @@ impl<
         profile_section!(sect_8);
 
         if self.lookup_parameters != LookupParameters::NoLookup {
-            let [precomputed_lagranges_c0, precomputed_lagranges_c1] =
-                precompute_for_barycentric_evaluation_in_extension::<F, EXT, P, Global>(
+            let precomputed_lagranges =
+                precompute_for_barycentric_evaluation_in_extension::<F, DEGREE, EXT, P, Global>(
                     domain_size,
                     F::multiplicative_generator(),
-                    ExtensionField::<F, 2, EXT>::ZERO,
+                    ExtensionField::<F, DEGREE, EXT>::ZERO,
                     worker,
                     ctx,
                 );
+            let lagranges: [&[F]; DEGREE] =
+                std::array::from_fn(|i| P::slice_into_base_slice(&precomputed_lagranges[i]));
 
-            let evaluate_from_extension = |coeffs_c0: &[P], coeffs_c1: &[P]| {
-                barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, EXT>(
-                    P::slice_into_base_slice(coeffs_c0),
-                    P::slice_into_base_slice(coeffs_c1),
-                    &P::slice_into_base_slice(&precomputed_lagranges_c0),
-                    &P::slice_into_base_slice(&precomputed_lagranges_c1),
-                    &worker,
+            let evaluate_from_extension = |polys: &[ArcGenericLdeStorage<F, P, Global, Global>;
+                                                DEGREE]| {
+                let coeffs: [&[F]; DEGREE] = std::array::from_fn(|i| {
+                    P::slice_into_base_slice(&polys[i].storage[0].as_ref().storage)
+                });
+                barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, DEGREE, EXT>(
+                    coeffs, lagranges, &worker,
                 )
             };
 
@@ -1791,27 +1773,15 @@ impl<
                 second_stage_polys_storage
                     .lookup_witness_encoding_polys
                     .iter()
-                    .map(|[a, b]| {
-                        [
-                            &a.storage[0].as_ref().storage,
-                            &b.storage[0].as_ref().storage,
-                        ]
-                    })
-                    .map(|[a, b]| evaluate_from_extension(a, b))
-                    .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                    .map(|el| evaluate_from_extension(el))
+                    .map(|el| ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(el)),
             );
             all_polys_at_zero.extend(
                 second_stage_polys_storage
                     .lookup_multiplicities_encoding_polys
                     .iter()
-                    .map(|[a, b]| {
-                        [
-                            &a.storage[0].as_ref().storage,
-                            &b.storage[0].as_ref().storage,
-                        ]
-                    })
-                    .map(|[a, b]| evaluate_from_extension(a, b))
-                    .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                    .map(|el| evaluate_from_extension(el))
+                    .map(|el| ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(el)),
             );
         }
 
@@ -1845,8 +1815,7 @@ impl<
 
         // Since it will be the last step before FRI, we will use challenges from extension
 
-        let c0 = transcript.get_challenge();
-        let c1 = transcript.get_challenge();
+        let challenge: [F; DEGREE] = std::array::from_fn(|_| transcript.get_challenge());
 
         let mut total_num_challenges = 0;
         total_num_challenges += num_poly_values_at_z;
@@ -1856,18 +1825,19 @@ impl<
             total_num_challenges += subset.len();
         }
 
-        let challenges = materialize_ext_challenge_powers::<F, EXT>((c0, c1), total_num_challenges);
+        let challenges =
+            materialize_ext_challenge_powers::<F, DEGREE, EXT>(challenge, total_num_challenges);
         // we may use lower factor LDE for FRI if we want
         let lde_factor_for_fri = proof_config.fri_lde_factor;
 
-        let mut outer_c0 = Vec::with_capacity(lde_factor_for_fri);
-        let mut outer_c1 = Vec::with_capacity(lde_factor_for_fri);
-        for _ in 0..lde_factor_for_fri {
-            let buff = vec![P::zero(ctx); inner_size];
-            outer_c0.push(buff);
-            let buff = vec![P::zero(ctx); inner_size];
-            outer_c1.push(buff);
-        }
+        let mut outer: [Vec<Vec<P>>; DEGREE] = std::array::from_fn(|_| {
+            let mut outer = Vec::with_capacity(lde_factor_for_fri);
+            for _ in 0..lde_factor_for_fri {
+                outer.push(vec![P::zero(ctx); inner_size]);
+            }
+
+            outer
+        });
 
         let x_poly_lde_subset = x_poly_lde.subset_for_degree(lde_factor_for_fri);
 
@@ -1879,23 +1849,21 @@ impl<
                     .iter()
                     .map(|el| {
                         let c0 = el.subset_for_degree(lde_factor_for_fri);
+                        let mut c0 = Some(c0);
 
-                        [Some(c0), None]
+                        std::array::from_fn(|_| c0.take())
                     })
-                    .collect::<Vec<_>>()
+                    .collect::<Vec<[_; DEGREE]>>()
             };
 
         let map_extension_for_quotening =
-            move |input: &[[ArcGenericLdeStorage<F, P, Global, Global>; 2]]| {
+            move |input: &[[ArcGenericLdeStorage<F, P, Global, Global>; DEGREE]]| {
                 input
                     .iter()
-                    .map(|[a, b]| {
-                        let c0 = a.subset_for_degree(lde_factor_for_fri);
-                        let c1 = b.subset_for_degree(lde_factor_for_fri);
-
-                        [Some(c0), Some(c1)]
+                    .map(|el| {
+                        std::array::from_fn(|i| Some(el[i].subset_for_degree(lde_factor_for_fri)))
                     })
-                    .collect::<Vec<_>>()
+                    .collect::<Vec<[_; DEGREE]>>()
             };
 
         let mut sources = vec![];
@@ -1935,7 +1903,10 @@ impl<
             ));
         }
         // quotient
-        let quotinents: Vec<_> = quotient_chunks_ldes.array_chunks::<2>().cloned().collect();
+        let quotinents: Vec<_> = quotient_chunks_ldes
+            .array_chunks::<DEGREE>()
+            .cloned()
+            .collect();
         sources.extend(map_extension_for_quotening(&quotinents));
 
         let num_challenges_required = sources.len();
@@ -1947,8 +1918,7 @@ impl<
         log!("Making quotiening at Z");
 
         quotening_operation_in_extension(
-            &mut outer_c0,
-            &mut outer_c1,
+            &mut outer,
             sources,
             values_at_z,
             &x_poly_lde_subset,
@@ -1976,8 +1946,7 @@ impl<
         log!("Making quotiening at Z*omega");
 
         quotening_operation_in_extension(
-            &mut outer_c0,
-            &mut outer_c1,
+            &mut outer,
             sources,
             values_at_z_omega,
             &x_poly_lde_subset,
@@ -2011,12 +1980,11 @@ impl<
             log!("Making quotiening at 0 for lookups sumchecks");
 
             quotening_operation_in_extension(
-                &mut outer_c0,
-                &mut outer_c1,
+                &mut outer,
                 sources,
                 values_at_zero,
                 &x_poly_lde_subset,
-                ExtensionField::<F, 2, EXT>::ZERO,
+                ExtensionField::<F, DEGREE, EXT>::ZERO,
                 &challenges[challenges_offset..(challenges_offset + num_challenges_required)],
                 worker,
                 ctx,
@@ -2036,7 +2004,7 @@ impl<
                         .variables_columns[column]
                         .clone()]));
                     let expected_value =
-                        ExtensionField::<F, 2, EXT>::from_coeff_in_base([expected_value, F::ZERO]);
+                        ExtensionField::<F, DEGREE, EXT>::from_base(expected_value);
                     values.push(expected_value);
                 }
                 let num_challenges_required = sources.len();
@@ -2044,11 +2012,10 @@ impl<
 
                 log!("Making quotiening at {} for public inputs", open_at);
 
-                let open_at = ExtensionField::<F, 2, EXT>::from_coeff_in_base([open_at, F::ZERO]);
+                let open_at = ExtensionField::<F, DEGREE, EXT>::from_base(open_at);
 
                 quotening_operation_in_extension(
-                    &mut outer_c0,
-                    &mut outer_c1,
+                    &mut outer,
                     sources,
                     values,
                     &x_poly_lde_subset,
@@ -2064,25 +2031,20 @@ impl<
 
         assert_eq!(challenges_offset, challenges.len());
 
-        // for simplicity we transform c0 and c1 into ArcGenericPoly
+        // for simplicity we transform every coefficient into ArcGenericPoly
 
-        let c0_as_poly = GenericLdeStorage {
-            storage: outer_c0
-                .into_iter()
-                .map(|el| GenericPolynomial::<F, BitreversedLagrangeForm, P, _>::from_storage(el))
-                .collect(),
-        };
-
-        let c0_as_poly = ArcGenericLdeStorage::from_owned(c0_as_poly);
-
-        let c1_as_poly = GenericLdeStorage {
-            storage: outer_c1
-                .into_iter()
-                .map(|el| GenericPolynomial::<F, BitreversedLagrangeForm, P, _>::from_storage(el))
-                .collect(),
-        };
+        let c_as_poly = outer.map(|outer| {
+            let as_poly = GenericLdeStorage {
+                storage: outer
+                    .into_iter()
+                    .map(|el| {
+                        GenericPolynomial::<F, BitreversedLagrangeForm, P, _>::from_storage(el)
+                    })
+                    .collect(),
+            };
 
-        let c1_as_poly = ArcGenericLdeStorage::from_owned(c1_as_poly);
+            ArcGenericLdeStorage::from_owned(as_poly)
+        });
 
         log!("Batched FRI opening computation taken {:?}", now.elapsed());
 
@@ -2092,27 +2054,23 @@ impl<
         // FRI oracle. We may want to have 8 or 4 elements per leaf, so we can have smaller
         // number of FRI intermediate oracles
 
-        let basic_pow_bits = proof_config.pow_bits;
-
         let (
             new_pow_bits,                 // updated POW bits if needed
             num_queries,                  // num queries
             interpolation_log2s_schedule, // folding schedule
             final_expected_degree,
-        ) = compute_fri_schedule(
-            proof_config.security_level as u32,
-            cap_size,
-            basic_pow_bits,
+        ) = compute_fri_schedule_for_config(
+            &proof_config,
             lde_factor_for_fri.trailing_zeros(),
             domain_size.trailing_zeros(),
-        );
+        )
+        .expect("FRI folding schedule must be valid");
 
         dbg!(&interpolation_log2s_schedule);
         dbg!(cap_size);
 
-        let fri_data = do_fri::<F, P, EXT, TR, H, Global, Global>(
-            c0_as_poly.clone(),
-            c1_as_poly.clone(),
+        let fri_data = do_fri::<F, P, DEGREE, EXT, TR, H, Global, Global>(
+            c_as_poly.clone(),
             &mut transcript,
             interpolation_log2s_schedule.clone(),
             lde_factor_for_fri,
@@ -2121,8 +2079,9 @@ impl<
             ctx,
         );
 
-        assert_eq!(fri_data.monomial_forms[0].len(), final_expected_degree);
-        assert_eq!(fri_data.monomial_forms[1].len(), final_expected_degree);
+        for el in fri_data.monomial_forms.iter() {
+            assert_eq!(el.len(), final_expected_degree);
+        }
 
         // now we can do PoW if we want
 
@@ -2152,12 +2111,7 @@ impl<
             0
         };
 
-        assert!(
-            proof_config.fri_folding_schedule.is_none(),
-            "we do not yet support externally provided FRI schedule"
-        );
-
-        let mut proof = Proof::<F, H, EXT> {
+        let mut proof = Proof::<F, H, DEGREE, EXT> {
             proof_config,
             public_inputs: public_inputs_only_values,
             witness_oracle_cap: witness_tree_cap.clone(),
@@ -2184,7 +2138,7 @@ impl<
             max_needed: max_needed_bits,
         };
 
-        let base_fri_source = (c0_as_poly, c1_as_poly);
+        let base_fri_source = c_as_poly;
 
         let num_bits_for_in_coset_index =
             max_needed_bits - lde_factor_for_fri.trailing_zeros() as usize;
@@ -2205,7 +2159,7 @@ impl<
 
             let witness_query = OracleQuery::construct(
                 &witness_tree,
-                &trace_holder.variables,
+                &(&trace_holder.variables, &witness_salt),
                 lde_factor_for_fri,
                 coset_idx,
                 domain_size,
@@ -2215,7 +2169,7 @@ impl<
 
             let second_stage_query = OracleQuery::construct(
                 &second_stage_tree,
-                &second_stage_polys_storage,
+                &(&second_stage_polys_storage, &second_stage_salt),
                 lde_factor_for_fri,
                 coset_idx,
                 domain_size,
@@ -2225,7 +2179,7 @@ impl<
 
             let quotient_query = OracleQuery::construct(
                 &quotients_tree,
-                &quotient_chunks_ldes,
+                &(&quotient_chunks_ldes, &quotients_salt),
                 lde_factor_for_fri,
                 coset_idx,
                 domain_size,
@@ -2289,6 +2243,32 @@ impl<
     }
 }
 
+// Random columns that we append to the oracle leafs if salting is enabled. They are never part of
+// any relation, so it's not a problem that they are not low-degree
+pub(crate) fn random_leaf_salt<
+    F: SmallField,
+    P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+>(
+    num_columns: usize,
+    inner_len: usize,
+    outer_len: usize,
+) -> Vec<ArcGenericLdeStorage<F, P>> {
+    let mut rng = rand::thread_rng();
+    let mut result = Vec::with_capacity(num_columns);
+    for _ in 0..num_columns {
+        let mut salt = ArcGenericLdeStorage::<F, P>::zeroed(inner_len, outer_len, Global, Global);
+        for coset in salt.storage.iter_mut() {
+            let coset = Arc::get_mut(coset).expect("salt storage is not shared yet");
+            for el in P::slice_into_base_slice_mut(&mut coset.storage).iter_mut() {
+                *el = crate::field::rand_from_rng(&mut rng);
+            }
+        }
+        result.push(salt);
+    }
+
+    result
+}
+
 pub(crate) fn u64_from_lsb_first_bits(bits: &[bool]) -> u64 {
     let mut result = 0u64;
     for (shift, bit) in bits.iter().enumerate() {
@@ -2391,24 +2371,81 @@ pub fn compute_fri_schedule(
     )
 }
 
-pub fn materialize_ext_challenge_powers<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
-    (c0, c1): (F, F),
+// Same as `compute_fri_schedule`, but respects an explicit folding schedule from the config.
+// Every step folds by 2, 4 or 8, and the last FRI oracle must still have at least
+// `cap_size` leafs
+pub fn compute_fri_schedule_for_config(
+    proof_config: &ProofConfig,
+    rate_log_two: u32,
+    initial_degree_log_two: u32,
+) -> Result<
+    (
+        u32,        // updated POW bits if needed
+        usize,      // num queries
+        Vec<usize>, // folding schedule,
+        usize,      // final poly degree to expect
+    ),
+    &'static str,
+> {
+    let (new_pow_bits, num_queries, default_schedule, default_final_degree) = compute_fri_schedule(
+        proof_config.security_level as u32,
+        proof_config.merkle_tree_cap_size,
+        proof_config.pow_bits,
+        rate_log_two,
+        initial_degree_log_two,
+    );
+
+    let Some(schedule) = proof_config.fri_folding_schedule.as_ref() else {
+        return Ok((
+            new_pow_bits,
+            num_queries,
+            default_schedule,
+            default_final_degree,
+        ));
+    };
+
+    if schedule.is_empty() {
+        return Err("FRI folding schedule must have at least one step");
+    }
+    if schedule.iter().any(|el| *el == 0 || *el > 3) {
+        return Err("FRI folding steps must be in the range 1..=3");
+    }
+    let total_folding_log_two: usize = schedule.iter().sum();
+    if total_folding_log_two > initial_degree_log_two as usize {
+        return Err("FRI folding schedule folds below degree 1");
+    }
+    let final_degree_log_two = initial_degree_log_two as usize - total_folding_log_two;
+    let cap_size_log_two = proof_config.merkle_tree_cap_size.trailing_zeros() as usize;
+    if final_degree_log_two + (rate_log_two as usize) < cap_size_log_two {
+        return Err("FRI folding schedule makes the last oracle smaller than the cap");
+    }
+
+    Ok((
+        new_pow_bits,
+        num_queries,
+        schedule.clone(),
+        1 << final_degree_log_two,
+    ))
+}
+
+pub fn materialize_ext_challenge_powers<
+    F: SmallField,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+>(
+    challenge: [F; DEGREE],
     num_challenges: usize,
-) -> Vec<(F, F)> {
+) -> Vec<[F; DEGREE]> {
     debug_assert!(num_challenges >= 2);
     let mut result = Vec::with_capacity(num_challenges);
-    result.push((F::ONE, F::ZERO));
-    result.push((c0, c1));
-    let as_extension = ExtensionField::<F, 2, EXT> {
-        coeffs: [c0, c1],
-        _marker: std::marker::PhantomData,
-    };
+    result.push(ExtensionField::<F, DEGREE, EXT>::ONE.into_coeffs_in_base());
+    result.push(challenge);
+    let as_extension = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(challenge);
 
     let mut current = as_extension;
     for _ in 2..num_challenges {
         current.mul_assign(&as_extension);
-        let [c0, c1] = current.into_coeffs_in_base();
-        result.push((c0, c1));
+        result.push(current.into_coeffs_in_base());
     }
 
     result
@@ -2417,44 +2454,39 @@ pub fn materialize_ext_challenge_powers<F: SmallField, EXT: FieldExtension<2, Ba
 fn quotening_operation<
     F: SmallField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+    const DEGREE: usize,
     A: GoodAllocator,
     B: GoodAllocator,
 >(
-    destination_c0: &mut Vec<Vec<P, A>, B>,
-    destination_c1: &mut Vec<Vec<P, A>, B>,
+    destination: &mut [Vec<Vec<P, A>, B>; DEGREE],
     polynomial_ldes: Vec<ArcGenericLdeStorage<F, P, A, B>>,
     values_at_z: Vec<F>,
     precomputed_cosets: &ArcGenericLdeStorage<F, P, A, B>,
     at: F,
-    challenge_coefficients: &[(F, F)],
+    challenge_coefficients: &[[F; DEGREE]],
     worker: &Worker,
     ctx: &mut P::Context,
 ) {
     // we precompute challenges outside to avoid any manual extension ops here
 
-    assert_eq!(destination_c0.len(), destination_c1.len());
-    assert_eq!(destination_c0.len(), precomputed_cosets.outer_len());
+    for el in destination.iter() {
+        assert_eq!(el.len(), precomputed_cosets.outer_len());
+        assert_eq!(el[0].len(), precomputed_cosets.inner_len());
+    }
     assert_eq!(polynomial_ldes.len(), values_at_z.len());
     assert_eq!(polynomial_ldes.len(), challenge_coefficients.len());
-    assert_eq!(destination_c0[0].len(), precomputed_cosets.inner_len());
     let polynomial_ldes_ref = &polynomial_ldes;
     let values_at_z_ref = &values_at_z;
     let challenge_coefficients_ref = challenge_coefficients;
 
     // we will work over every coset independently for simplicity
-    for (coset_idx, (c0, c1)) in destination_c0
-        .iter_mut()
-        .zip(destination_c1.iter_mut())
-        .enumerate()
-    {
-        debug_assert_eq!(c0.len(), c1.len());
-        let work_size = c0.len();
+    for coset_idx in 0..precomputed_cosets.outer_len() {
+        let mut it = destination.iter_mut();
+        let dst: [&mut [P]; DEGREE] =
+            std::array::from_fn(|_| &mut it.next().unwrap()[coset_idx][..]);
+        let work_size = dst[0].len();
         worker.scope(work_size, |scope, chunk_size| {
-            for (chunk_idx, (c0, c1)) in c0
-                .chunks_mut(chunk_size)
-                .zip(c1.chunks_mut(chunk_size))
-                .enumerate()
-            {
+            for (chunk_idx, dst) in chunk_columns_mut(dst, chunk_size).into_iter().enumerate() {
                 let mut ctx = *ctx;
                 scope.spawn(move |_| {
                     let start = chunk_idx * chunk_size;
@@ -2470,16 +2502,16 @@ fn quotening_operation<
                     for el in roots_minus_z.iter_mut() {
                         el.sub_assign(&at, &mut ctx);
                     }
-                    let mut denoms = P::vec_into_base_vec(roots_minus_z);
-                    batch_inverse_inplace::<_, A>(&mut denoms);
-                    let denoms = P::vec_from_base_vec(denoms);
+                    let mut denoms = roots_minus_z;
+                    batch_inverse_vectorized_inplace::<P, A>(&mut denoms, &mut ctx);
 
-                    // buffers to compute c0 and c1 coefficients before placing them into destination
-                    let mut buffer_c0 = Vec::with_capacity_in(end - start, A::default());
-                    buffer_c0.resize(end - start, P::zero(&mut ctx));
+                    // buffers to compute coefficients before placing them into destination
+                    let mut buffers: [Vec<P, A>; DEGREE] = std::array::from_fn(|_| {
+                        let mut buffer = Vec::with_capacity_in(end - start, A::default());
+                        buffer.resize(end - start, P::zero(&mut ctx));
 
-                    let mut buffer_c1 = Vec::with_capacity_in(end - start, A::default());
-                    buffer_c1.resize(end - start, P::zero(&mut ctx));
+                        buffer
+                    });
 
                     for ((src_poly, value_at_z), challenge_coefficients) in polynomial_ldes_ref
                         .iter()
@@ -2489,50 +2521,34 @@ fn quotening_operation<
                         let src_coset = &src_poly.storage[coset_idx];
                         let src_chunk = &src_coset.storage[start..end];
 
-                        let (challenge_c0, challenge_c1) = challenge_coefficients;
-                        let challenge_c0 = P::constant(*challenge_c0, &mut ctx);
-                        let challenge_c1 = P::constant(*challenge_c1, &mut ctx);
-                        debug_assert_eq!(src_chunk.len(), buffer_c0.len());
+                        let challenge = challenge_coefficients.map(|el| P::constant(el, &mut ctx));
+                        debug_assert_eq!(src_chunk.len(), buffers[0].len());
                         let value_at_z = P::constant(*value_at_z, &mut ctx);
 
-                        for ((src, dst_c0), dst_c1) in src_chunk
-                            .iter()
-                            .zip(buffer_c0.iter_mut())
-                            .zip(buffer_c1.iter_mut())
-                        {
-                            // src is in the base field, so in fact we do two independent operations
+                        for (idx, src) in src_chunk.iter().enumerate() {
+                            // src is in the base field, so in fact we do independent operations
                             let mut tmp = *src;
                             tmp.sub_assign(&value_at_z, &mut ctx);
 
-                            let mut c0 = tmp;
-                            c0.mul_assign(&challenge_c0, &mut ctx);
-
-                            let mut c1 = tmp;
-                            c1.mul_assign(&challenge_c1, &mut ctx);
-
-                            dst_c0.add_assign(&c0, &mut ctx);
-                            dst_c1.add_assign(&c1, &mut ctx);
+                            for (dst, challenge) in buffers.iter_mut().zip(challenge.iter()) {
+                                let mut c = tmp;
+                                c.mul_assign(challenge, &mut ctx);
+                                dst[idx].add_assign(&c, &mut ctx);
+                            }
                         }
                     }
 
                     // numerators were aggregated, now "divide" once
-                    for ((dst_c0, dst_c1), denom) in buffer_c0
-                        .iter_mut()
-                        .zip(buffer_c1.iter_mut())
-                        .zip(denoms.into_iter())
-                    {
-                        dst_c0.mul_assign(&denom, &mut ctx);
-                        dst_c1.mul_assign(&denom, &mut ctx);
+                    for buffer in buffers.iter_mut() {
+                        for (dst, denom) in buffer.iter_mut().zip(denoms.iter()) {
+                            dst.mul_assign(denom, &mut ctx);
+                        }
                     }
 
-                    for (((dst_c0, dst_c1), src_c0), src_c1) in c0
-                        .iter_mut()
-                        .zip(c1.iter_mut())
-                        .zip(buffer_c0.into_iter())
-                        .zip(buffer_c1.into_iter())
-                    {
-                        dst_c0.add_assign(&src_c0, &mut ctx);
-                        dst_c1.add_assign(&src_c1, &mut ctx);
+                    for (dst, buffer) in dst.into_iter().zip(buffers.into_iter()) {
+                        for (dst, src) in dst.iter_mut().zip(buffer.into_iter()) {
+                            dst.add_assign(&src, &mut ctx);
+                        }
                     }
                 })
             }
@@ -2542,46 +2558,41 @@ fn quotening_operation<
 
 fn quotening_operation_in_extension<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
     A: GoodAllocator,
     B: GoodAllocator,
 >(
-    destination_c0: &mut Vec<Vec<P, A>, B>,
-    destination_c1: &mut Vec<Vec<P, A>, B>,
-    polynomial_ldes: Vec<[Option<ArcGenericLdeStorage<F, P, A, B>>; 2]>,
-    values_at_z: Vec<ExtensionField<F, 2, EXT>>,
+    destination: &mut [Vec<Vec<P, A>, B>; DEGREE],
+    polynomial_ldes: Vec<[Option<ArcGenericLdeStorage<F, P, A, B>>; DEGREE]>,
+    values_at_z: Vec<ExtensionField<F, DEGREE, EXT>>,
     precomputed_cosets: &ArcGenericLdeStorage<F, P, A, B>,
-    at: ExtensionField<F, 2, EXT>,
-    challenge_coefficients: &[(F, F)],
+    at: ExtensionField<F, DEGREE, EXT>,
+    challenge_coefficients: &[[F; DEGREE]],
     worker: &Worker,
     ctx: &mut P::Context,
 ) {
     // we precompute challenges outside to avoid any manual extension ops here
 
-    assert_eq!(destination_c0.len(), destination_c1.len());
-    assert_eq!(destination_c0.len(), precomputed_cosets.outer_len());
+    for el in destination.iter() {
+        assert_eq!(el.len(), precomputed_cosets.outer_len());
+        assert_eq!(el[0].len(), precomputed_cosets.inner_len());
+    }
     assert_eq!(polynomial_ldes.len(), values_at_z.len());
     assert_eq!(polynomial_ldes.len(), challenge_coefficients.len());
-    assert_eq!(destination_c0[0].len(), precomputed_cosets.inner_len());
     let polynomial_ldes_ref = &polynomial_ldes;
     let values_at_z_ref = &values_at_z;
     let challenge_coefficients_ref = challenge_coefficients;
 
     // we will work over every coset independently for simplicity
-    for (coset_idx, (c0, c1)) in destination_c0
-        .iter_mut()
-        .zip(destination_c1.iter_mut())
-        .enumerate()
-    {
-        debug_assert_eq!(c0.len(), c1.len());
-        let work_size = c0.len();
+    for coset_idx in 0..precomputed_cosets.outer_len() {
+        let mut it = destination.iter_mut();
+        let dst: [&mut [P]; DEGREE] =
+            std::array::from_fn(|_| &mut it.next().unwrap()[coset_idx][..]);
+        let work_size = dst[0].len();
         worker.scope(work_size, |scope, chunk_size| {
-            for (chunk_idx, (c0, c1)) in c0
-                .chunks_mut(chunk_size)
-                .zip(c1.chunks_mut(chunk_size))
-                .enumerate()
-            {
+            for (chunk_idx, dst) in chunk_columns_mut(dst, chunk_size).into_iter().enumerate() {
                 let mut ctx = *ctx;
                 scope.spawn(move |_| {
                     let start = chunk_idx * chunk_size;
@@ -2589,135 +2600,94 @@ fn quotening_operation_in_extension<
                     if end > work_size {
                         end = work_size;
                     }
-                    // denominator is shared between everything, and we can compute it once here
+                    let zero = P::zero(&mut ctx);
+                    // denominator is shared between everything, and we can compute it once here,
+                    // roots are in the base field, so only the first coefficient has a contribution from them
                     let roots = &precomputed_cosets.storage[coset_idx].storage[start..end];
-                    let at_c0 = P::constant(at.coeffs[0], &mut ctx);
-                    let at_c1 = P::constant(at.coeffs[1], &mut ctx);
-                    let mut roots_minus_z_c0 = roots.to_vec_in(A::default());
-                    let mut at_c1_negated = at_c1;
-                    at_c1_negated.negate(&mut ctx);
-                    let mut at_c1_negated_vec =
-                        Vec::with_capacity_in(roots_minus_z_c0.len(), A::default());
-                    at_c1_negated_vec.resize(roots_minus_z_c0.len(), at_c1_negated);
-
-                    // denominator for (x - z), but roots are in base, so only one subtraction is needed
-                    for el in roots_minus_z_c0.iter_mut() {
-                        el.sub_assign(&at_c0, &mut ctx);
-                    }
+                    let mut denoms: [Vec<P, A>; DEGREE] = std::array::from_fn(|idx| {
+                        let mut at = P::constant(at.coeffs[idx], &mut ctx);
+                        if idx == 0 {
+                            let mut roots_minus_z = roots.to_vec_in(A::default());
+                            for el in roots_minus_z.iter_mut() {
+                                el.sub_assign(&at, &mut ctx);
+                            }
 
-                    let mut denoms_c0 = P::vec_into_base_vec(roots_minus_z_c0);
-                    let mut denoms_c1 = P::vec_into_base_vec(at_c1_negated_vec);
-                    batch_inverse_inplace_in_extension::<F, EXT, A>(&mut denoms_c0, &mut denoms_c1);
-                    let denoms_c0 = P::vec_from_base_vec(denoms_c0);
-                    let denoms_c1 = P::vec_from_base_vec(denoms_c1);
+                            roots_minus_z
+                        } else {
+                            at.negate(&mut ctx);
+                            let mut at_negated = Vec::with_capacity_in(roots.len(), A::default());
+                            at_negated.resize(roots.len(), at);
 
-                    let zero = P::zero(&mut ctx);
+                            at_negated
+                        }
+                    });
+                    let mut it = denoms.iter_mut();
+                    batch_inverse_vectorized_inplace_in_extension::<F, P, DEGREE, EXT, A>(
+                        std::array::from_fn(|_| &mut it.next().unwrap()[..]),
+                        &mut ctx,
+                    );
 
-                    // buffers to compute c0 and c1 coefficients before placing them into destination
-                    let mut buffer_c0 = Vec::with_capacity_in(end - start, A::default());
-                    buffer_c0.resize(end - start, zero);
+                    // buffers to compute coefficients before placing them into destination
+                    let mut buffers: [Vec<P, A>; DEGREE] = std::array::from_fn(|_| {
+                        let mut buffer = Vec::with_capacity_in(end - start, A::default());
+                        buffer.resize(end - start, zero);
 
-                    let mut buffer_c1 = Vec::with_capacity_in(end - start, A::default());
-                    buffer_c1.resize(end - start, zero);
+                        buffer
+                    });
 
-                    for (([src_poly_c0, src_poly_c1], value_at_z), challenge_coefficients) in
-                        polynomial_ldes_ref
-                            .iter()
-                            .zip(values_at_z_ref.iter())
-                            .zip(challenge_coefficients_ref.iter())
+                    for ((src_polys, value_at_z), challenge_coefficients) in polynomial_ldes_ref
+                        .iter()
+                        .zip(values_at_z_ref.iter())
+                        .zip(challenge_coefficients_ref.iter())
                     {
-                        let src_coset_c0 = &src_poly_c0
-                            .as_ref()
-                            .expect("c0 must never be empty")
-                            .storage[coset_idx];
-                        let src_coset_c1 = src_poly_c1.as_ref().map(|el| &el.storage[coset_idx]);
-                        let src_chunk_c0 = &src_coset_c0.storage[start..end];
-                        let src_chunk_c1 = src_coset_c1.map(|el| &el.storage[start..end]);
-
-                        let (challenge_c0, challenge_c1) = challenge_coefficients;
-                        let challenge_c0 = P::constant(*challenge_c0, &mut ctx);
-                        let challenge_c1 = P::constant(*challenge_c1, &mut ctx);
-                        debug_assert_eq!(src_chunk_c0.len(), buffer_c0.len());
-                        let value_at_z_c0 = P::constant(value_at_z.coeffs[0], &mut ctx);
-                        let value_at_z_c1 = P::constant(value_at_z.coeffs[1], &mut ctx);
-                        let mut value_at_z_c1_negated = value_at_z_c1;
-                        value_at_z_c1_negated.negate(&mut ctx);
-
-                        // we branch based on what we have
-
-                        if let Some(src_chunk_c1) = src_chunk_c1 {
-                            debug_assert_eq!(src_chunk_c1.len(), buffer_c1.len());
-
-                            for (((src_c0, src_c1), dst_c0), dst_c1) in src_chunk_c0
-                                .iter()
-                                .zip(src_chunk_c1.iter())
-                                .zip(buffer_c0.iter_mut())
-                                .zip(buffer_c1.iter_mut())
-                            {
-                                // src is in the base field, so in fact we do two independent operations
-                                let mut tmp_c0 = *src_c0;
-                                tmp_c0.sub_assign(&value_at_z_c0, &mut ctx);
-
-                                let mut tmp_c1 = *src_c1;
-                                tmp_c1.sub_assign(&value_at_z_c1, &mut ctx);
-
-                                mul_assign_vectorized_in_extension::<F, P, EXT>(
-                                    &mut tmp_c0,
-                                    &mut tmp_c1,
-                                    &challenge_c0,
-                                    &challenge_c1,
-                                    &mut ctx,
-                                );
+                        assert!(src_polys[0].is_some(), "c0 must never be empty");
+                        // missing coefficients are zeroes
+                        let src_chunks: [Option<&[P]>; DEGREE] = std::array::from_fn(|i| {
+                            src_polys[i]
+                                .as_ref()
+                                .map(|el| &el.storage[coset_idx].storage[start..end])
+                        });
 
-                                dst_c0.add_assign(&tmp_c0, &mut ctx);
-                                dst_c1.add_assign(&tmp_c1, &mut ctx);
-                            }
-                        } else {
-                            for ((src, dst_c0), dst_c1) in src_chunk_c0
-                                .iter()
-                                .zip(buffer_c0.iter_mut())
-                                .zip(buffer_c1.iter_mut())
-                            {
-                                // src is in the base field, so in fact we do two independent operations
-                                let mut tmp_c0 = *src;
-                                tmp_c0.sub_assign(&value_at_z_c0, &mut ctx);
-
-                                let mut tmp_c1 = value_at_z_c1_negated;
-
-                                mul_assign_vectorized_in_extension::<F, P, EXT>(
-                                    &mut tmp_c0,
-                                    &mut tmp_c1,
-                                    &challenge_c0,
-                                    &challenge_c1,
-                                    &mut ctx,
-                                );
+                        let challenge = challenge_coefficients.map(|el| P::constant(el, &mut ctx));
+                        let value_at_z = value_at_z.coeffs.map(|el| P::constant(el, &mut ctx));
+
+                        for idx in 0..(end - start) {
+                            let mut tmp: [P; DEGREE] = std::array::from_fn(|i| {
+                                let mut tmp = match src_chunks[i] {
+                                    Some(src_chunk) => src_chunk[idx],
+                                    None => zero,
+                                };
+                                tmp.sub_assign(&value_at_z[i], &mut ctx);
+
+                                tmp
+                            });
 
-                                dst_c0.add_assign(&tmp_c0, &mut ctx);
-                                dst_c1.add_assign(&tmp_c1, &mut ctx);
+                            mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(
+                                &mut tmp, &challenge, &mut ctx,
+                            );
+
+                            for (dst, tmp) in buffers.iter_mut().zip(tmp.iter()) {
+                                dst[idx].add_assign(tmp, &mut ctx);
                             }
                         }
                     }
 
                     // numerators were aggregated, now "divide" once
-                    for (((dst_c0, dst_c1), denom_c0), denom_c1) in buffer_c0
-                        .iter_mut()
-                        .zip(buffer_c1.iter_mut())
-                        .zip(denoms_c0.into_iter())
-                        .zip(denoms_c1.into_iter())
-                    {
-                        mul_assign_vectorized_in_extension::<F, P, EXT>(
-                            dst_c0, dst_c1, &denom_c0, &denom_c1, &mut ctx,
+                    for idx in 0..(end - start) {
+                        let mut value: [P; DEGREE] = std::array::from_fn(|i| buffers[i][idx]);
+                        let denom: [P; DEGREE] = std::array::from_fn(|i| denoms[i][idx]);
+                        mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(
+                            &mut value, &denom, &mut ctx,
                         );
+                        for (dst, value) in buffers.iter_mut().zip(value.into_iter()) {
+                            dst[idx] = value;
+                        }
                     }
 
-                    for (((dst_c0, dst_c1), src_c0), src_c1) in c0
-                        .iter_mut()
-                        .zip(c1.iter_mut())
-                        .zip(buffer_c0.into_iter())
-                        .zip(buffer_c1.into_iter())
-                    {
-                        dst_c0.add_assign(&src_c0, &mut ctx);
-                        dst_c1.add_assign(&src_c1, &mut ctx);
+                    for (dst, buffer) in dst.into_iter().zip(buffers.into_iter()) {
+                        for (dst, src) in dst.iter_mut().zip(buffer.into_iter()) {
+                            dst.add_assign(&src, &mut ctx);
+                        }
                     }
                 })
             }
@@ -1,13 +1,15 @@
 use super::reference_cs::CSReferenceAssembly;
 use super::*;
 
-use crate::config::DevCSConfig;
+use crate::config::CSConfig;
 
 use crate::cs::implementations::polynomial_storage::SatisfiabilityCheckRowView;
 use crate::cs::traits::evaluator::GatePlacementType;
 use crate::cs::traits::gate::GatePlacementStrategy;
 
-impl<F: SmallField> CSReferenceAssembly<F, F, DevCSConfig> {
+// Works for any configuration that keeps setup and evaluates witness, so negative tests
+// can skip the runtime asserts and still check a malicious witness against the constraints
+impl<F: SmallField, CFG: CSConfig> CSReferenceAssembly<F, F, CFG> {
     pub fn check_if_satisfied(&mut self, worker: &Worker) -> bool {
         let (constants, selectors_placement, _) = self.create_constant_setup_polys(worker);
         let (_deg, num_constants_for_general_purpose_columns) = selectors_placement.compute_stats();
@@ -150,6 +150,18 @@ pub type GoldilocksPoisedon2Transcript = AlgebraicSpongeBasedTranscript<
     AbsorptionModeOverwrite,
 >;
 
+use crate::field::baby_bear::BabyBearField;
+use crate::implementations::poseidon2_baby_bear::Poseidon2BabyBear;
+
+pub type BabyBearPoseidon2Transcript = AlgebraicSpongeBasedTranscript<
+    BabyBearField,
+    8,
+    16,
+    8,
+    Poseidon2BabyBear,
+    AbsorptionModeOverwrite,
+>;
+
 #[derive(Derivative)]
 #[derivative(Clone, Debug)]
 pub struct Blake2sTranscript {
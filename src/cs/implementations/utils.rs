@@ -3,6 +3,10 @@ use super::*;
 use crate::cs::implementations::polynomial::LagrangeForm;
 use crate::cs::traits::GoodAllocator;
 use crate::fft::bitreverse_enumeration_inplace;
+use crate::field::batch_inversion::{
+    batch_inverse, batch_inverse_inplace_parallel, batch_inverse_inplace_parallel_in_extension,
+    batch_inverse_vectorized_inplace_parallel, chunk_columns_mut,
+};
 use crate::field::traits::field_like::mul_assign_in_extension;
 use crate::field::traits::field_like::PrimeFieldLikeVectorized;
 use crate::field::ExtensionField;
@@ -385,237 +389,6 @@ pub(crate) fn transform_monomials_to_lde<
     columns
 }
 
-pub(crate) fn batch_inverse<F: PrimeField, A: GoodAllocator>(input: &[F], into: &mut Vec<F, A>) {
-    debug_assert!(into.is_empty());
-
-    if input.is_empty() {
-        return;
-    }
-
-    // we do Montgomery batch inversion trick, and reuse a buffer
-    let one = F::ONE;
-    into.push(one);
-    let mut accumulator = input[0];
-    for el in input[1..].iter() {
-        into.push(accumulator);
-        accumulator.mul_assign(el);
-    }
-
-    // for a set of a, b, c, d we have
-    // - input = [1, a, ab, abc],
-    // - accumulator = abcd
-
-    let mut grand_inverse = accumulator
-        .inverse()
-        .expect("batch inverse must be called on sets without zeroes");
-
-    // grand_inverse = a^-1 b^-1 c^-1 d^-1
-
-    for (dst, original) in into.iter_mut().rev().zip(input.iter().rev()) {
-        dst.mul_assign(&grand_inverse); // e.g it's now d^-1
-        grand_inverse.mul_assign(original); // e.g. it's now a^-1 b^-1 c^-1
-    }
-
-    debug_assert_eq!(into.len(), input.len());
-}
-
-pub(crate) fn batch_inverse_inplace<F: PrimeField, A: GoodAllocator>(input: &mut [F]) {
-    if input.is_empty() {
-        return;
-    }
-
-    let mut into = Vec::with_capacity_in(input.len(), A::default());
-
-    // we do Montgomery batch inversion trick, and reuse a buffer
-    let one = F::ONE;
-    into.push(one);
-    let mut accumulator = input[0];
-    for el in input[1..].iter() {
-        into.push(accumulator);
-        accumulator.mul_assign(el);
-    }
-
-    // for a set of a, b, c, d we have
-    // - input = [1, a, ab, abc],
-    // - accumulator = abcd
-
-    let mut grand_inverse = accumulator
-        .inverse()
-        .expect("batch inverse must be called on sets without zeroes");
-
-    // grand_inverse = a^-1 b^-1 c^-1 d^-1
-
-    for (tmp, original) in into.into_iter().rev().zip(input.iter_mut().rev()) {
-        let mut tmp = tmp; // abc
-        tmp.mul_assign(&grand_inverse); // d^-1
-        grand_inverse.mul_assign(original); // e.g. it's now a^-1 b^-1 c^-1
-
-        *original = tmp;
-    }
-}
-
-pub(crate) fn batch_inverse_in_extension<
-    F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
-    A: GoodAllocator,
->(
-    input_c0: &[F],
-    input_c1: &[F],
-    into_c0: &mut Vec<F, A>,
-    into_c1: &mut Vec<F, A>,
-) {
-    assert!(into_c0.is_empty());
-    assert!(into_c1.is_empty());
-
-    assert_eq!(input_c0.len(), input_c1.len());
-
-    if input_c0.is_empty() {
-        return;
-    }
-
-    // we do Montgomery batch inversion trick, and reuse a buffer
-    let one = {
-        use crate::field::traits::field::Field;
-
-        ExtensionField::<F, 2, EXT>::ONE
-    };
-    into_c0.push(one.coeffs[0]);
-    into_c1.push(one.coeffs[1]);
-    let mut accumulator =
-        ExtensionField::<F, 2, EXT>::from_coeff_in_base([input_c0[0], input_c1[0]]);
-    for (el_c0, el_c1) in input_c0[1..].iter().zip(input_c1[1..].iter()) {
-        into_c0.push(accumulator.coeffs[0]);
-        into_c1.push(accumulator.coeffs[1]);
-
-        let el = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*el_c0, *el_c1]);
-        crate::field::Field::mul_assign(&mut accumulator, &el);
-    }
-
-    // for a set of a, b, c, d we have
-    // - input = [1, a, ab, abc],
-    // - accumulator = abcd
-
-    let mut grand_inverse = accumulator
-        .inverse()
-        .expect("batch inverse must be called on sets without zeroes");
-
-    // grand_inverse = a^-1 b^-1 c^-1 d^-1
-
-    for (((tmp_c0, tmp_c1), original_c0), original_c1) in into_c0
-        .iter_mut()
-        .rev()
-        .zip(into_c1.iter_mut().rev())
-        .zip(input_c0.iter().rev())
-        .zip(input_c1.iter().rev())
-    {
-        let mut tmp = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*tmp_c0, *tmp_c1]); // abc
-        crate::field::Field::mul_assign(&mut tmp, &grand_inverse); // d^-1
-
-        // write back
-        *tmp_c0 = tmp.coeffs[0];
-        *tmp_c1 = tmp.coeffs[1];
-
-        let original =
-            ExtensionField::<F, 2, EXT>::from_coeff_in_base([*original_c0, *original_c1]);
-        crate::field::Field::mul_assign(&mut grand_inverse, &original); // e.g. it's now a^-1 b^-1 c^-1
-    }
-
-    assert_eq!(input_c0.len(), into_c0.len());
-    assert_eq!(input_c1.len(), into_c1.len());
-}
-
-pub(crate) fn batch_inverse_inplace_in_extension<
-    F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
-    A: GoodAllocator,
->(
-    input_c0: &mut [F],
-    input_c1: &mut [F],
-) {
-    assert_eq!(input_c0.len(), input_c1.len());
-
-    if input_c0.is_empty() {
-        return;
-    }
-
-    let mut into = Vec::with_capacity_in(input_c0.len(), A::default());
-
-    // we do Montgomery batch inversion trick, and reuse a buffer
-    let one = {
-        use crate::field::traits::field::Field;
-
-        ExtensionField::<F, 2, EXT>::ONE
-    };
-    into.push(one);
-    let mut accumulator =
-        ExtensionField::<F, 2, EXT>::from_coeff_in_base([input_c0[0], input_c1[0]]);
-    for (el_c0, el_c1) in input_c0[1..].iter().zip(input_c1[1..].iter()) {
-        into.push(accumulator);
-        let el = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*el_c0, *el_c1]);
-        crate::field::Field::mul_assign(&mut accumulator, &el);
-    }
-
-    // for a set of a, b, c, d we have
-    // - input = [1, a, ab, abc],
-    // - accumulator = abcd
-
-    let mut grand_inverse = accumulator
-        .inverse()
-        .expect("batch inverse must be called on sets without zeroes");
-
-    // grand_inverse = a^-1 b^-1 c^-1 d^-1
-
-    for ((tmp, original_c0), original_c1) in into
-        .into_iter()
-        .rev()
-        .zip(input_c0.iter_mut().rev())
-        .zip(input_c1.iter_mut().rev())
-    {
-        let mut tmp = tmp; // abc
-        crate::field::Field::mul_assign(&mut tmp, &grand_inverse); // d^-1
-        let original =
-            ExtensionField::<F, 2, EXT>::from_coeff_in_base([*original_c0, *original_c1]);
-        crate::field::Field::mul_assign(&mut grand_inverse, &original); // e.g. it's now a^-1 b^-1 c^-1
-
-        *original_c0 = tmp.coeffs[0];
-        *original_c1 = tmp.coeffs[1];
-    }
-}
-
-pub(crate) fn batch_inverse_inplace_parallel<F: PrimeField, A: GoodAllocator>(
-    input: &mut [F],
-    worker: &Worker,
-) {
-    worker.scope(input.len(), |scope, chunk_size| {
-        for dst in input.chunks_mut(chunk_size) {
-            scope.spawn(move |_| {
-                batch_inverse_inplace::<F, A>(dst);
-            });
-        }
-    });
-}
-
-pub(crate) fn batch_inverse_inplace_parallel_in_extension<
-    F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
-    A: GoodAllocator,
->(
-    input_c0: &mut [F],
-    input_c1: &mut [F],
-    worker: &Worker,
-) {
-    worker.scope(input_c0.len(), |scope, chunk_size| {
-        for (dst_c0, dst_c1) in input_c0
-            .chunks_mut(chunk_size)
-            .zip(input_c1.chunks_mut(chunk_size))
-        {
-            scope.spawn(move |_| {
-                batch_inverse_inplace_in_extension::<F, EXT, A>(dst_c0, dst_c1);
-            });
-        }
-    });
-}
-
 pub fn make_non_residues<F: PrimeField>(num: usize, domain_size: usize) -> Vec<F> {
     assert!(domain_size.is_power_of_two());
     assert!(domain_size <= u64::MAX as usize);
@@ -861,10 +634,8 @@ pub fn precompute_for_barycentric_evaluation<
 
     unsafe { storage.set_len(n) };
 
-    let mut result = allocate_in_with_alignment_of::<F, P, A>(n, A::default());
-    batch_inverse(&storage, &mut result);
-
-    drop(storage);
+    let mut result = storage;
+    batch_inverse_inplace_parallel::<F, A>(&mut result, worker);
 
     // * omega^i * constant_factor
 
@@ -888,25 +659,29 @@ pub fn precompute_for_barycentric_evaluation<
 
 // precompute values useful for barycentric evaluation
 pub fn precompute_for_barycentric_evaluation_in_extension<
-    F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    F: SmallField,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
     A: GoodAllocator,
 >(
     n: usize,
     coset: F,
-    at: ExtensionField<F, 2, EXT>,
+    at: ExtensionField<F, DEGREE, EXT>,
     worker: &Worker,
     ctx: &mut P::Context,
-) -> [Vec<P, A>; 2] {
+) -> [Vec<P, A>; DEGREE] {
     if n == 1 {
-        let mut result_c0 = Vec::with_capacity_in(1, A::default());
-        result_c0.push(P::one(ctx));
-
-        let mut result_c1 = Vec::with_capacity_in(1, A::default());
-        result_c1.push(P::zero(ctx));
+        return std::array::from_fn(|idx| {
+            let mut result = Vec::with_capacity_in(1, A::default());
+            if idx == 0 {
+                result.push(P::one(ctx));
+            } else {
+                result.push(P::zero(ctx));
+            }
 
-        return [result_c0, result_c1];
+            result
+        });
     }
 
     // L_i(X) = (omega^i / N) / (X - omega^i) * (X^N - 1)
@@ -929,17 +704,19 @@ pub fn precompute_for_barycentric_evaluation_in_extension<
 
     // compute vector of (X - coset * omega^i)
 
-    let mut storage_c0 = allocate_in_with_alignment_of::<F, P, A>(n, A::default());
-    let mut storage_c1 = allocate_in_with_alignment_of::<F, P, A>(n, A::default());
+    let mut storage: [_; DEGREE] =
+        std::array::from_fn(|_| allocate_in_with_alignment_of::<F, P, A>(n, A::default()));
 
-    // because we only subtract c0, we can just fill it
-    storage_c1.resize(n, at.coeffs[1]);
+    // because we only subtract c0, we can just fill the other coefficients
+    for (dst, el) in storage.iter_mut().zip(at.coeffs.iter()).skip(1) {
+        dst.resize(n, *el);
+    }
 
     let omega = domain_generator_for_size::<F>(n as u64);
     let at_c0 = at.coeffs[0];
 
     worker.scope(n, |scope, chunk_size| {
-        for (chunk_idx, chunk_c0) in storage_c0.spare_capacity_mut()[..n]
+        for (chunk_idx, chunk_c0) in storage[0].spare_capacity_mut()[..n]
             .chunks_mut(chunk_size)
             .enumerate()
         {
@@ -957,36 +734,37 @@ pub fn precompute_for_barycentric_evaluation_in_extension<
         }
     });
 
-    unsafe { storage_c0.set_len(n) };
+    unsafe { storage[0].set_len(n) };
 
-    assert_eq!(storage_c0.len(), storage_c1.len());
+    for el in storage.iter() {
+        assert_eq!(el.len(), n);
+    }
 
-    let mut result_c0 = storage_c0;
-    let mut result_c1 = storage_c1;
-    batch_inverse_inplace_parallel_in_extension::<F, EXT, A>(
-        &mut result_c0,
-        &mut result_c1,
+    let mut result = storage;
+    let mut it = result.iter_mut();
+    batch_inverse_inplace_parallel_in_extension::<F, DEGREE, EXT, A>(
+        std::array::from_fn(|_| &mut it.next().unwrap()[..]),
         worker,
     );
 
     // * omega^i * constant_factor
 
-    worker.scope(result_c0.len(), |scope, chunk_size| {
-        for (chunk_idx, (chunk_c0, chunk_c1)) in result_c0
-            .chunks_mut(chunk_size)
-            .zip(result_c1.chunks_mut(chunk_size))
+    let mut it = result.iter_mut();
+    let columns: [&mut [F]; DEGREE] = std::array::from_fn(|_| &mut it.next().unwrap()[..]);
+    worker.scope(n, |scope, chunk_size| {
+        for (chunk_idx, mut chunk) in chunk_columns_mut(columns, chunk_size)
+            .into_iter()
             .enumerate()
         {
             scope.spawn(move |_| {
                 let mut current = constant_factor;
                 current.mul_assign_by_base(&omega.pow_u64((chunk_idx * chunk_size) as u64));
-                for (el_c0, el_c1) in chunk_c0.iter_mut().zip(chunk_c1.iter_mut()) {
-                    mul_assign_in_extension::<F, EXT>(
-                        el_c0,
-                        el_c1,
-                        &current.coeffs[0],
-                        &current.coeffs[1],
-                    );
+                for idx in 0..chunk[0].len() {
+                    let mut el: [F; DEGREE] = std::array::from_fn(|i| chunk[i][idx]);
+                    mul_assign_in_extension::<F, DEGREE, EXT>(&mut el, &current.coeffs);
+                    for (dst, el) in chunk.iter_mut().zip(el.into_iter()) {
+                        dst[idx] = el;
+                    }
 
                     current.mul_assign_by_base(&omega);
                 }
@@ -994,13 +772,11 @@ pub fn precompute_for_barycentric_evaluation_in_extension<
         }
     });
 
-    bitreverse_enumeration_inplace(&mut result_c0);
-    bitreverse_enumeration_inplace(&mut result_c1);
+    result.map(|mut el| {
+        bitreverse_enumeration_inplace(&mut el);
 
-    [
-        P::vec_from_base_vec(result_c0),
-        P::vec_from_base_vec(result_c1),
-    ]
+        P::vec_from_base_vec(el)
+    })
 }
 
 // No parallelism here - we parallelize externally
@@ -1065,163 +841,133 @@ pub(crate) fn barycentric_evaluate_at_for_bitreversed_parallel<F: PrimeField>(
     result
 }
 
-pub(crate) fn barycentric_evaluate_base_at_extension_for_bitreversed_parallel<F: PrimeField>(
+pub(crate) fn barycentric_evaluate_base_at_extension_for_bitreversed_parallel<
+    F: PrimeField,
+    const DEGREE: usize,
+>(
     values: &[F],
-    precomputed_lagrange_poly_evals_c0: &[F],
-    precomputed_lagrange_poly_evals_c1: &[F],
+    precomputed_lagrange_poly_evals: [&[F]; DEGREE],
     worker: &Worker,
-) -> [F; 2] {
-    debug_assert_eq!(values.len(), precomputed_lagrange_poly_evals_c0.len());
-    debug_assert_eq!(values.len(), precomputed_lagrange_poly_evals_c1.len());
+) -> [F; DEGREE] {
+    for el in precomputed_lagrange_poly_evals.iter() {
+        debug_assert_eq!(values.len(), el.len());
+    }
 
     if values.len() == 1 {
         // evaluation is just a constant
-        return [values[0], values[0]];
-    };
+        let mut result = [F::ZERO; DEGREE];
+        result[0] = values[0];
 
-    if values.len() < worker.num_cores {
-        // do single thread
-        let mut dst_c0 = F::ZERO;
-        let mut dst_c1 = F::ZERO;
-        for ((a, b_c0), b_c1) in values
-            .iter()
-            .zip(precomputed_lagrange_poly_evals_c0.iter())
-            .zip(precomputed_lagrange_poly_evals_c1.iter())
-        {
-            let mut c0 = *b_c0;
-            c0.mul_assign(a);
-            let mut c1 = *b_c1;
-            c1.mul_assign(a);
+        return result;
+    };
 
-            dst_c0.add_assign(&c0);
-            dst_c1.add_assign(&c1);
+    let evaluate_chunk = |range: std::ops::Range<usize>| {
+        let mut dst = [F::ZERO; DEGREE];
+        for idx in range {
+            for (dst, b) in dst.iter_mut().zip(precomputed_lagrange_poly_evals.iter()) {
+                let mut tmp = b[idx];
+                tmp.mul_assign(&values[idx]);
+                dst.add_assign(&tmp);
+            }
         }
 
-        return [dst_c0, dst_c1];
+        dst
+    };
+
+    if values.len() < worker.num_cores {
+        // do single thread
+        return evaluate_chunk(0..values.len());
     }
 
     let chunk_size = worker.get_chunk_size(values.len());
     let num_chunks = Worker::compute_num_chunks(values.len(), chunk_size);
 
-    let mut chunk_evals_c0 = vec![F::ZERO; num_chunks];
-    let mut chunk_evals_c1 = vec![F::ZERO; num_chunks];
+    let mut chunk_evals = vec![[F::ZERO; DEGREE]; num_chunks];
 
     worker.scope(values.len(), |scope, chunk_size| {
-        for ((((a, b_c0), b_c1), dst_c0), dst_c1) in values
-            .chunks(chunk_size)
-            .zip(precomputed_lagrange_poly_evals_c0.chunks(chunk_size))
-            .zip(precomputed_lagrange_poly_evals_c1.chunks(chunk_size))
-            .zip(chunk_evals_c0.iter_mut())
-            .zip(chunk_evals_c1.iter_mut())
-        {
+        for (chunk_idx, dst) in chunk_evals.iter_mut().enumerate() {
+            let evaluate_chunk = &evaluate_chunk;
             scope.spawn(move |_| {
-                for ((a, b_c0), b_c1) in a.iter().zip(b_c0.iter()).zip(b_c1.iter()) {
-                    let mut tmp = *a;
-                    tmp.mul_assign(b_c0);
-                    dst_c0.add_assign(&tmp);
-
-                    let mut tmp = *a;
-                    tmp.mul_assign(b_c1);
-                    dst_c1.add_assign(&tmp);
-                }
+                let start = chunk_idx * chunk_size;
+                let end = std::cmp::min(start + chunk_size, values.len());
+                *dst = evaluate_chunk(start..end);
             });
         }
     });
 
-    let mut result_c0 = chunk_evals_c0[0];
-    let mut result_c1 = chunk_evals_c1[0];
-    for el in chunk_evals_c0.into_iter().skip(1) {
-        result_c0.add_assign(&el);
-    }
-    for el in chunk_evals_c1.into_iter().skip(1) {
-        result_c1.add_assign(&el);
+    let mut result = chunk_evals[0];
+    for el in chunk_evals.into_iter().skip(1) {
+        for (dst, src) in result.iter_mut().zip(el.iter()) {
+            dst.add_assign(src);
+        }
     }
 
-    [result_c0, result_c1]
+    result
 }
 
 pub(crate) fn barycentric_evaluate_extension_at_extension_for_bitreversed_parallel<
     F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
 >(
-    values_c0: &[F],
-    values_c1: &[F],
-    precomputed_lagrange_poly_evals_c0: &[F],
-    precomputed_lagrange_poly_evals_c1: &[F],
+    values: [&[F]; DEGREE],
+    precomputed_lagrange_poly_evals: [&[F]; DEGREE],
     worker: &Worker,
-) -> [F; 2] {
-    debug_assert_eq!(values_c0.len(), precomputed_lagrange_poly_evals_c0.len());
-    debug_assert_eq!(values_c0.len(), precomputed_lagrange_poly_evals_c1.len());
-    debug_assert_eq!(values_c1.len(), precomputed_lagrange_poly_evals_c0.len());
-    debug_assert_eq!(values_c1.len(), precomputed_lagrange_poly_evals_c1.len());
+) -> [F; DEGREE] {
+    let len = values[0].len();
+    for (a, b) in values.iter().zip(precomputed_lagrange_poly_evals.iter()) {
+        debug_assert_eq!(len, a.len());
+        debug_assert_eq!(len, b.len());
+    }
 
-    if values_c0.len() == 1 {
+    if len == 1 {
         // evaluation is just a constant
-        return [values_c0[0], values_c1[0]];
+        return values.map(|el| el[0]);
     };
 
-    if values_c0.len() < worker.num_cores {
-        // do single thread
-        let mut dst_c0 = F::ZERO;
-        let mut dst_c1 = F::ZERO;
-        for (((a_c0, a_c1), b_c0), b_c1) in values_c0
-            .iter()
-            .zip(values_c1.iter())
-            .zip(precomputed_lagrange_poly_evals_c0.iter())
-            .zip(precomputed_lagrange_poly_evals_c1.iter())
-        {
-            let mut a_c0 = *a_c0;
-            let mut a_c1 = *a_c1;
-            mul_assign_in_extension::<F, EXT>(&mut a_c0, &mut a_c1, b_c0, b_c1);
-            dst_c0.add_assign(&a_c0);
-            dst_c1.add_assign(&a_c1);
+    let evaluate_chunk = |range: std::ops::Range<usize>| {
+        let mut dst = [F::ZERO; DEGREE];
+        for idx in range {
+            let mut a: [F; DEGREE] = std::array::from_fn(|i| values[i][idx]);
+            let b: [F; DEGREE] = std::array::from_fn(|i| precomputed_lagrange_poly_evals[i][idx]);
+            mul_assign_in_extension::<F, DEGREE, EXT>(&mut a, &b);
+            for (dst, src) in dst.iter_mut().zip(a.iter()) {
+                dst.add_assign(src);
+            }
         }
 
-        return [dst_c0, dst_c1];
+        dst
+    };
+
+    if len < worker.num_cores {
+        // do single thread
+        return evaluate_chunk(0..len);
     }
 
-    let chunk_size = worker.get_chunk_size(values_c0.len());
-    let num_chunks = Worker::compute_num_chunks(values_c0.len(), chunk_size);
+    let chunk_size = worker.get_chunk_size(len);
+    let num_chunks = Worker::compute_num_chunks(len, chunk_size);
 
-    let mut chunk_evals_c0 = vec![F::ZERO; num_chunks];
-    let mut chunk_evals_c1 = vec![F::ZERO; num_chunks];
+    let mut chunk_evals = vec![[F::ZERO; DEGREE]; num_chunks];
 
-    worker.scope(values_c0.len(), |scope, chunk_size| {
-        for (((((a_c0, a_c1), b_c0), b_c1), dst_c0), dst_c1) in values_c0
-            .chunks(chunk_size)
-            .zip(values_c1.chunks(chunk_size))
-            .zip(precomputed_lagrange_poly_evals_c0.chunks(chunk_size))
-            .zip(precomputed_lagrange_poly_evals_c1.chunks(chunk_size))
-            .zip(chunk_evals_c0.iter_mut())
-            .zip(chunk_evals_c1.iter_mut())
-        {
+    worker.scope(len, |scope, chunk_size| {
+        for (chunk_idx, dst) in chunk_evals.iter_mut().enumerate() {
+            let evaluate_chunk = &evaluate_chunk;
             scope.spawn(move |_| {
-                for (((a_c0, a_c1), b_c0), b_c1) in a_c0
-                    .iter()
-                    .zip(a_c1.iter())
-                    .zip(b_c0.iter())
-                    .zip(b_c1.iter())
-                {
-                    let mut a_c0 = *a_c0;
-                    let mut a_c1 = *a_c1;
-                    mul_assign_in_extension::<F, EXT>(&mut a_c0, &mut a_c1, b_c0, b_c1);
-                    dst_c0.add_assign(&a_c0);
-                    dst_c1.add_assign(&a_c1);
-                }
+                let start = chunk_idx * chunk_size;
+                let end = std::cmp::min(start + chunk_size, len);
+                *dst = evaluate_chunk(start..end);
             });
         }
     });
 
-    let mut result_c0 = chunk_evals_c0[0];
-    let mut result_c1 = chunk_evals_c1[0];
-    for el in chunk_evals_c0.into_iter().skip(1) {
-        result_c0.add_assign(&el);
-    }
-    for el in chunk_evals_c1.into_iter().skip(1) {
-        result_c1.add_assign(&el);
+    let mut result = chunk_evals[0];
+    for el in chunk_evals.into_iter().skip(1) {
+        for (dst, src) in result.iter_mut().zip(el.iter()) {
+            dst.add_assign(src);
+        }
     }
 
-    [result_c0, result_c1]
+    result
 }
 
 // signature is over P to keep our alignment at bay
@@ -1623,9 +1369,8 @@ pub(crate) fn unnormalized_l1_inverse<
         bitreverse_enumeration_inplace(&mut r);
 
         // inverse to get denominators
-        batch_inverse_inplace_parallel::<F, A>(&mut r, worker);
-
         let mut r = P::vec_from_base_vec(r);
+        batch_inverse_vectorized_inplace_parallel::<P, A>(&mut r, worker);
 
         worker.scope(r.len(), |scope, chunk_size| {
             let mut ctx = *ctx;
@@ -1726,76 +1471,6 @@ mod test {
         assert_eq!(serial, parallel);
     }
 
-    #[test]
-    fn test_batch_inverse() {
-        use crate::field::traits::field::Field;
-
-        let input = vec![
-            F::ONE,
-            F::from_u64_with_reduction(123),
-            F::from_u64_with_reduction(456),
-            F::from_u64_with_reduction(789),
-            F::from_u64_with_reduction(123),
-            F::from_u64_with_reduction(456),
-            F::from_u64_with_reduction(789),
-            F::from_u64_with_reduction(123),
-            F::from_u64_with_reduction(456),
-            F::from_u64_with_reduction(789),
-            F::TWO,
-        ];
-
-        let mut inv_dst = vec![];
-        batch_inverse(&input, &mut inv_dst);
-
-        let reference: Vec<_> = input.into_iter().map(|el| el.inverse().unwrap()).collect();
-        assert_eq!(inv_dst, reference);
-    }
-
-    #[test]
-    fn test_batch_inverse_in_extension() {
-        use crate::field::traits::field::Field;
-        use std::alloc::Global;
-
-        let input_c0 = vec![
-            F::ONE,
-            F::from_u64_with_reduction(123),
-            F::from_u64_with_reduction(456),
-            F::from_u64_with_reduction(789),
-            F::from_u64_with_reduction(123),
-            F::from_u64_with_reduction(456),
-            F::from_u64_with_reduction(789),
-            F::from_u64_with_reduction(123),
-            F::from_u64_with_reduction(456),
-            F::from_u64_with_reduction(789),
-            F::TWO,
-        ];
-
-        let mut input_c1 = input_c0.clone();
-        input_c1.reverse();
-
-        let mut inv_dst_c0 = vec![];
-        let mut inv_dst_c1 = vec![];
-        batch_inverse_in_extension::<F, GoldilocksExt2, Global>(
-            &input_c0,
-            &input_c1,
-            &mut inv_dst_c0,
-            &mut inv_dst_c1,
-        );
-
-        let mut reference_c0 = vec![];
-        let mut reference_c1 = vec![];
-
-        for (c0, c1) in input_c0.into_iter().zip(input_c1.into_iter()) {
-            let el = ExtensionField::<F, 2, GoldilocksExt2>::from_coeff_in_base([c0, c1]);
-            let [c0, c1] = el.inverse().unwrap().coeffs;
-            reference_c0.push(c0);
-            reference_c1.push(c1);
-        }
-
-        assert_eq!(inv_dst_c0, reference_c0);
-        assert_eq!(inv_dst_c1, reference_c1);
-    }
-
     #[test]
     fn test_barycentric_eval() {
         for log_size in 0..20 {
@@ -1876,7 +1551,7 @@ mod test {
             let worker = Worker::new();
 
             let [precomps_c0, precomps_c1] =
-                precompute_for_barycentric_evaluation_in_extension::<F, EXT, F, Global>(
+                precompute_for_barycentric_evaluation_in_extension::<F, 2, EXT, F, Global>(
                     size,
                     F::multiplicative_generator(),
                     z,
@@ -1905,11 +1580,9 @@ mod test {
             }
 
             let barycentric_val =
-                barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, EXT>(
-                    &forward_c0,
-                    &forward_c1,
-                    &precomps_c0,
-                    &precomps_c1,
+                barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, 2, EXT>(
+                    [&forward_c0, &forward_c1],
+                    [&precomps_c0, &precomps_c1],
                     &worker,
                 );
 
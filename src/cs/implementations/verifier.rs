@@ -352,7 +352,8 @@ pub(crate) fn compute_selector_subpath_at_z<
 
 pub struct VerifierProxy<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     GC: GateConfigurationHolder<F>,
     T: StaticToolboxHolder,
 > {
@@ -363,12 +364,12 @@ pub struct VerifierProxy<
     pub gates_configuration: GC,
     pub(crate) gate_type_ids_for_specialized_columns: Vec<TypeId>,
     pub(crate) evaluators_over_specialized_columns:
-        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT>>,
+        Vec<TypeErasedGateEvaluationVerificationFunction<F, DEGREE, EXT>>,
     pub(crate) offsets_for_specialized_evaluators: Vec<(PerChunkOffset, PerChunkOffset, usize)>,
 
     // pub(crate) gate_type_ids_for_general_purpose_columns: Vec<TypeId>,
     pub(crate) evaluators_over_general_purpose_columns:
-        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT>>,
+        Vec<TypeErasedGateEvaluationVerificationFunction<F, DEGREE, EXT>>,
     pub(crate) general_purpose_evaluators_comparison_functions:
         HashMap<TypeId, Vec<(GateBatchEvaluationComparisonFunction, usize)>>,
 
@@ -381,12 +382,13 @@ pub struct VerifierProxy<
 
 impl<
         F: SmallField,
-        EXT: FieldExtension<2, BaseField = F>,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
         GC: GateConfigurationHolder<F>,
         T: StaticToolboxHolder,
-    > VerifierProxy<F, EXT, GC, T>
+    > VerifierProxy<F, DEGREE, EXT, GC, T>
 {
-    pub fn into_verifier(self) -> Verifier<F, EXT> {
+    pub fn into_verifier(self) -> Verifier<F, DEGREE, EXT> {
         let Self {
             parameters,
             lookup_parameters,
@@ -417,7 +419,7 @@ impl<
             placement_strategies.insert(*gate_type_id, placement_strategy);
         }
 
-        Verifier::<F, EXT> {
+        Verifier::<F, DEGREE, EXT> {
             parameters,
             lookup_parameters,
             gate_type_ids_for_specialized_columns,
@@ -432,19 +434,20 @@ impl<
     }
 }
 
-pub struct Verifier<F: SmallField, EXT: FieldExtension<2, BaseField = F>> {
+pub struct Verifier<F: SmallField, const DEGREE: usize, EXT: FieldExtension<DEGREE, BaseField = F>>
+{
     // when we init we get the following from VK
     pub parameters: CSGeometry,
     pub lookup_parameters: LookupParameters,
 
     pub(crate) gate_type_ids_for_specialized_columns: Vec<TypeId>,
     pub(crate) evaluators_over_specialized_columns:
-        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT>>,
+        Vec<TypeErasedGateEvaluationVerificationFunction<F, DEGREE, EXT>>,
     pub(crate) offsets_for_specialized_evaluators: Vec<(PerChunkOffset, PerChunkOffset, usize)>,
 
     // pub(crate) gate_type_ids_for_general_purpose_columns: Vec<TypeId>,
     pub(crate) evaluators_over_general_purpose_columns:
-        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT>>,
+        Vec<TypeErasedGateEvaluationVerificationFunction<F, DEGREE, EXT>>,
 
     pub(crate) total_num_variables_for_specialized_columns: usize,
     pub(crate) total_num_witnesses_for_specialized_columns: usize,
@@ -460,7 +463,8 @@ pub struct Verifier<F: SmallField, EXT: FieldExtension<2, BaseField = F>> {
 #[derivative(Debug)]
 pub(crate) struct TypeErasedGateEvaluationVerificationFunction<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
 > {
     pub(crate) debug_name: String,
     pub(crate) evaluator_type_id: TypeId,
@@ -476,9 +480,9 @@ pub(crate) struct TypeErasedGateEvaluationVerificationFunction<
         Box<
             dyn GenericDynamicEvaluatorOverSpecializedColumns<
                     F,
-                    ExtensionField<F, 2, EXT>,
-                    VerifierPolyStorage<F, ExtensionField<F, 2, EXT>>,
-                    VerifierRelationDestination<F, ExtensionField<F, 2, EXT>>,
+                    ExtensionField<F, DEGREE, EXT>,
+                    VerifierPolyStorage<F, ExtensionField<F, DEGREE, EXT>>,
+                    VerifierRelationDestination<F, ExtensionField<F, DEGREE, EXT>>,
                 >
                 + 'static
                 + Send
@@ -490,9 +494,9 @@ pub(crate) struct TypeErasedGateEvaluationVerificationFunction<
         Box<
             dyn GenericDynamicEvaluatorOverGeneralPurposeColumns<
                     F,
-                    ExtensionField<F, 2, EXT>,
-                    VerifierPolyStorage<F, ExtensionField<F, 2, EXT>>,
-                    VerifierRelationDestination<F, ExtensionField<F, 2, EXT>>,
+                    ExtensionField<F, DEGREE, EXT>,
+                    VerifierPolyStorage<F, ExtensionField<F, DEGREE, EXT>>,
+                    VerifierRelationDestination<F, ExtensionField<F, DEGREE, EXT>>,
                 >
                 + 'static
                 + Send
@@ -501,8 +505,8 @@ pub(crate) struct TypeErasedGateEvaluationVerificationFunction<
     >,
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
-    TypeErasedGateEvaluationVerificationFunction<F, EXT>
+impl<F: SmallField, const DEGREE: usize, EXT: FieldExtension<DEGREE, BaseField = F>>
+    TypeErasedGateEvaluationVerificationFunction<F, DEGREE, EXT>
 {
     pub fn from_evaluator<E: GateConstraintEvaluator<F>>(
         evaluator: E,
@@ -572,9 +576,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
                             as Box<
                                 dyn GenericDynamicEvaluatorOverSpecializedColumns<
                                         F,
-                                        ExtensionField<F, 2, EXT>,
-                                        VerifierPolyStorage<F, ExtensionField<F, 2, EXT>>,
-                                        VerifierRelationDestination<F, ExtensionField<F, 2, EXT>>,
+                                        ExtensionField<F, DEGREE, EXT>,
+                                        VerifierPolyStorage<F, ExtensionField<F, DEGREE, EXT>>,
+                                        VerifierRelationDestination<
+                                            F,
+                                            ExtensionField<F, DEGREE, EXT>,
+                                        >,
                                     >
                                     + 'static
                                     + Send
@@ -599,9 +606,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
                             as Box<
                                 dyn GenericDynamicEvaluatorOverGeneralPurposeColumns<
                                         F,
-                                        ExtensionField<F, 2, EXT>,
-                                        VerifierPolyStorage<F, ExtensionField<F, 2, EXT>>,
-                                        VerifierRelationDestination<F, ExtensionField<F, 2, EXT>>,
+                                        ExtensionField<F, DEGREE, EXT>,
+                                        VerifierPolyStorage<F, ExtensionField<F, DEGREE, EXT>>,
+                                        VerifierRelationDestination<
+                                            F,
+                                            ExtensionField<F, DEGREE, EXT>,
+                                        >,
                                     >
                                     + 'static
                                     + Send
@@ -796,10 +806,76 @@ impl<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>>
     }
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OracleKind {
+    Witness,
+    Stage2,
+    Quotient,
+    Setup,
+    Fri(usize),
+}
+
+impl std::fmt::Display for OracleKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OracleKind::Witness => write!(f, "witness"),
+            OracleKind::Stage2 => write!(f, "stage 2"),
+            OracleKind::Quotient => write!(f, "quotient"),
+            OracleKind::Setup => write!(f, "setup"),
+            OracleKind::Fri(idx) => write!(f, "FRI number {}", idx),
+        }
+    }
+}
+
+// Reason why the proof was rejected. Verifier never panics on the proof itself, so anything that
+// doesn't have the expected shape is reported as `MalformedProof`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerificationError {
+    // verifier, verification key and proof config are not for the same circuit
+    GeometryMismatch(&'static str),
+    MalformedProof(String),
+    InvalidMerklePath { oracle: OracleKind, query: usize },
+    InvalidLookupSumcheck,
+    InvalidQuotientAtZ,
+    // folded value is not in the leaf of the next FRI oracle
+    InvalidFriFold { query: usize, step: usize },
+    InvalidFriFinalPolynomial { query: usize },
+    InvalidPow,
+}
+
+impl std::fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerificationError::GeometryMismatch(what) => write!(f, "Different {}", what),
+            VerificationError::MalformedProof(what) => write!(f, "Malformed proof: {}", what),
+            VerificationError::InvalidMerklePath { oracle, query } => {
+                write!(f, "{} query number {} is not in the tree", oracle, query)
+            }
+            VerificationError::InvalidLookupSumcheck => write!(f, "Lookup sumcheck is invalid"),
+            VerificationError::InvalidQuotientAtZ => write!(f, "Invalid quotient at Z"),
+            VerificationError::InvalidFriFold { query, step } => write!(
+                f,
+                "FRI element is not in the leaf for step {} of query number {}",
+                step, query
+            ),
+            VerificationError::InvalidFriFinalPolynomial { query } => write!(
+                f,
+                "Not equal to evaluation from monomials for query number {}",
+                query
+            ),
+            VerificationError::InvalidPow => write!(f, "PoW is invalid"),
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+impl<F: SmallField, const DEGREE: usize, EXT: FieldExtension<DEGREE, BaseField = F>>
+    Verifier<F, DEGREE, EXT>
+{
     #[inline]
     pub fn num_sublookup_arguments(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_sublookup_arguments(
+        SizeCalculator::<F, DEGREE, EXT>::num_sublookup_arguments(
             &self.parameters,
             &self.lookup_parameters,
         )
@@ -807,7 +883,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
     #[inline]
     pub fn num_multipicities_polys(&self, total_tables_len: usize, domain_size: u64) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_multipicities_polys(
+        SizeCalculator::<F, DEGREE, EXT>::num_multipicities_polys(
             &self.lookup_parameters,
             total_tables_len,
             domain_size,
@@ -815,21 +891,21 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
     }
 
     pub fn num_variable_polys(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_variable_polys(
+        SizeCalculator::<F, DEGREE, EXT>::num_variable_polys(
             &self.parameters,
             self.total_num_variables_for_specialized_columns,
         )
     }
 
     pub fn num_witness_polys(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_witness_polys(
+        SizeCalculator::<F, DEGREE, EXT>::num_witness_polys(
             &self.parameters,
             self.total_num_witnesses_for_specialized_columns,
         )
     }
 
     pub fn num_constant_polys(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_constant_polys(
+        SizeCalculator::<F, DEGREE, EXT>::num_constant_polys(
             &self.parameters,
             vk_fixed_params,
             self.total_num_constants_for_specialized_columns,
@@ -837,7 +913,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
     }
 
     pub fn quotient_degree(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::quotient_degree(vk_fixed_params)
+        SizeCalculator::<F, DEGREE, EXT>::quotient_degree(vk_fixed_params)
     }
 
     pub fn num_copy_permutation_polys(&self) -> usize {
@@ -845,11 +921,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
     }
 
     pub fn num_lookup_table_setup_polys(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_lookup_table_setup_polys(&self.lookup_parameters)
+        SizeCalculator::<F, DEGREE, EXT>::num_lookup_table_setup_polys(&self.lookup_parameters)
     }
 
     pub fn witness_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::witness_leaf_size(
+        SizeCalculator::<F, DEGREE, EXT>::witness_leaf_size(
             &self.parameters,
             &self.lookup_parameters,
             vk_fixed_params,
@@ -859,7 +935,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
     }
 
     pub fn stage_2_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::stage_2_leaf_size(
+        SizeCalculator::<F, DEGREE, EXT>::stage_2_leaf_size(
             &self.parameters,
             &self.lookup_parameters,
             vk_fixed_params,
@@ -868,11 +944,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
     }
 
     pub fn quotient_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::quotient_leaf_size(vk_fixed_params)
+        SizeCalculator::<F, DEGREE, EXT>::quotient_leaf_size(vk_fixed_params)
     }
 
     pub fn setup_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::setup_leaf_size(
+        SizeCalculator::<F, DEGREE, EXT>::setup_leaf_size(
             &self.parameters,
             &self.lookup_parameters,
             vk_fixed_params,
@@ -889,40 +965,65 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
         &self,
         transcript_params: TR::TransciptParameters,
         vk: &VerificationKey<F, H>,
-        proof: &Proof<F, H, EXT>,
+        proof: &Proof<F, H, DEGREE, EXT>,
     ) -> bool {
+        match self.try_verify::<H, TR, POW>(transcript_params, vk, proof) {
+            Ok(()) => true,
+            Err(error) => {
+                log!("{}", error);
+                false
+            }
+        }
+    }
+
+    pub fn try_verify<
+        H: TreeHasher<F>,
+        TR: Transcript<F, CompatibleCap = H::Output>,
+        POW: PoWRunner,
+    >(
+        &self,
+        transcript_params: TR::TransciptParameters,
+        vk: &VerificationKey<F, H>,
+        proof: &Proof<F, H, DEGREE, EXT>,
+    ) -> Result<(), VerificationError> {
         let mut transcript = TR::new(transcript_params);
 
         if self.parameters != vk.fixed_parameters.parameters {
-            log!("Different circuit parameters in verifier and VK");
-            return false;
+            return Err(VerificationError::GeometryMismatch(
+                "circuit parameters in verifier and VK",
+            ));
         }
 
         if self.lookup_parameters != vk.fixed_parameters.lookup_parameters {
-            log!("Different lookup parameters in verifier and VK");
-            return false;
+            return Err(VerificationError::GeometryMismatch(
+                "lookup parameters in verifier and VK",
+            ));
         }
 
         if vk.fixed_parameters.cap_size != proof.proof_config.merkle_tree_cap_size {
-            log!("Different cap sized in proof as VK");
-            return false;
+            return Err(VerificationError::GeometryMismatch(
+                "cap size in proof and VK",
+            ));
         }
 
         if vk.fixed_parameters.fri_lde_factor != proof.proof_config.fri_lde_factor {
-            log!("Different FRI LDE factor in proof as VK");
-            return false;
+            return Err(VerificationError::GeometryMismatch(
+                "FRI LDE factor in proof and VK",
+            ));
         }
 
         if vk.fixed_parameters.cap_size != vk.setup_merkle_tree_cap.len() {
-            log!("Cap is malformed");
-            return false;
+            return Err(VerificationError::GeometryMismatch(
+                "cap size and setup cap length in VK",
+            ));
         }
         transcript.witness_merkle_tree_cap(&vk.setup_merkle_tree_cap);
 
         if proof.public_inputs.len() != vk.fixed_parameters.public_inputs_locations.len() {
             // VK mismatch
-            log!("Invalid number of public inputs");
-            return false;
+            return Err(VerificationError::MalformedProof(
+                "invalid number of public inputs".to_string(),
+            ));
         }
 
         let num_public_inputs = proof.public_inputs.len();
@@ -942,40 +1043,42 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
         // commit witness
         if vk.fixed_parameters.cap_size != proof.witness_oracle_cap.len() {
-            log!("Cap is malformed");
-            return false;
+            return Err(VerificationError::MalformedProof(
+                "invalid witness oracle cap size".to_string(),
+            ));
         }
         transcript.witness_merkle_tree_cap(&proof.witness_oracle_cap);
 
         // draw challenges for stage 2
-        let beta = transcript.get_multiple_challenges_fixed::<2>();
-        let beta = ExtensionField::<F, 2, EXT>::from_coeff_in_base(beta);
-        let gamma = transcript.get_multiple_challenges_fixed::<2>();
-        let gamma = ExtensionField::<F, 2, EXT>::from_coeff_in_base(gamma);
+        let beta = transcript.get_multiple_challenges_fixed::<DEGREE>();
+        let beta = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(beta);
+        let gamma = transcript.get_multiple_challenges_fixed::<DEGREE>();
+        let gamma = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(gamma);
 
         let (lookup_beta, lookup_gamma) = if self.lookup_parameters != LookupParameters::NoLookup {
             // lookup argument related parts
-            let lookup_beta = transcript.get_multiple_challenges_fixed::<2>();
-            let lookup_beta = ExtensionField::<F, 2, EXT>::from_coeff_in_base(lookup_beta);
-            let lookup_gamma = transcript.get_multiple_challenges_fixed::<2>();
-            let lookup_gamma = ExtensionField::<F, 2, EXT>::from_coeff_in_base(lookup_gamma);
+            let lookup_beta = transcript.get_multiple_challenges_fixed::<DEGREE>();
+            let lookup_beta = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(lookup_beta);
+            let lookup_gamma = transcript.get_multiple_challenges_fixed::<DEGREE>();
+            let lookup_gamma = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(lookup_gamma);
 
             (lookup_beta, lookup_gamma)
         } else {
-            let zero = ExtensionField::<F, 2, EXT>::ZERO;
+            let zero = ExtensionField::<F, DEGREE, EXT>::ZERO;
 
             (zero, zero)
         };
 
         if vk.fixed_parameters.cap_size != proof.stage_2_oracle_cap.len() {
-            log!("Cap is malformed");
-            return false;
+            return Err(VerificationError::MalformedProof(
+                "invalid stage 2 oracle cap size".to_string(),
+            ));
         }
         transcript.witness_merkle_tree_cap(&proof.stage_2_oracle_cap);
 
         // draw challenges for quotient
-        let alpha = transcript.get_multiple_challenges_fixed::<2>();
-        let alpha = ExtensionField::<F, 2, EXT>::from_coeff_in_base(alpha);
+        let alpha = transcript.get_multiple_challenges_fixed::<DEGREE>();
+        let alpha = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(alpha);
 
         // two extra relations per lookup subargument - for A and B polys
         let num_lookup_subarguments = self.num_sublookup_arguments();
@@ -1049,15 +1152,16 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
         // commit quotient
         if vk.fixed_parameters.cap_size != proof.quotient_oracle_cap.len() {
-            log!("Cap is malformed");
-            return false;
+            return Err(VerificationError::MalformedProof(
+                "invalid quotient oracle cap size".to_string(),
+            ));
         }
         transcript.witness_merkle_tree_cap(&proof.quotient_oracle_cap);
 
         // get z
 
-        let z = transcript.get_multiple_challenges_fixed::<2>();
-        let z = ExtensionField::<F, 2, EXT>::from_coeff_in_base(z);
+        let z = transcript.get_multiple_challenges_fixed::<DEGREE>();
+        let z = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(z);
 
         // commit claimed values at z, and form our poly storage
         for set in proof.values_at_z.iter() {
@@ -1123,18 +1227,21 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             quotient_degree; // chunks of quotient poly
 
         if proof.values_at_z.len() != num_poly_values_at_z {
-            log!("Number of openings at Z is unexpected");
-            return false;
+            return Err(VerificationError::MalformedProof(
+                "unexpected number of openings at Z".to_string(),
+            ));
         }
 
         if proof.values_at_z_omega.len() != 1 {
-            log!("Number of openings at Z*omega is unexpected");
-            return false;
+            return Err(VerificationError::MalformedProof(
+                "unexpected number of openings at Z*omega".to_string(),
+            ));
         }
 
         if proof.values_at_0.len() != total_num_lookup_argument_terms {
-            log!("Number of openings at 0 is unexpected");
-            return false;
+            return Err(VerificationError::MalformedProof(
+                "unexpected number of openings at 0".to_string(),
+            ));
         }
 
         // run verifier at z
@@ -1203,7 +1310,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             let mut source_it = all_values_at_z_omega.iter();
             let copy_permutation_z_at_z_omega = *source_it.next().unwrap();
 
-            let mut t_accumulator = ExtensionField::<F, 2, EXT>::ZERO;
+            let mut t_accumulator = ExtensionField::<F, DEGREE, EXT>::ZERO;
             // precompute selectors at z
 
             let mut selectors_buffer = HashMap::new();
@@ -1240,22 +1347,17 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                 let multiplicities_encoding_polys_at_0 =
                     &all_values_at_0[num_lookup_subarguments..];
 
-                let mut witness_subsum = ExtensionField::<F, 2, EXT>::ZERO;
+                let mut witness_subsum = ExtensionField::<F, DEGREE, EXT>::ZERO;
                 for a in lookup_witness_encoding_polys_polys_at_0.iter() {
                     witness_subsum.add_assign(a);
                 }
 
-                let mut multiplicities_subsum = ExtensionField::<F, 2, EXT>::ZERO;
+                let mut multiplicities_subsum = ExtensionField::<F, DEGREE, EXT>::ZERO;
                 for b in multiplicities_encoding_polys_at_0.iter() {
                     multiplicities_subsum.add_assign(b);
                 }
                 if witness_subsum != multiplicities_subsum {
-                    log!(
-                        "Lookup sumcheck is invalid: a = {}, b = {}",
-                        witness_subsum,
-                        multiplicities_subsum
-                    );
-                    return false;
+                    return Err(VerificationError::InvalidLookupSumcheck);
                 }
 
                 // lookup argument related parts
@@ -1294,7 +1396,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                         let capacity = column_elements_per_subargument
                             + ((vk.fixed_parameters.table_ids_column_idxes.len() == 1) as usize);
                         let mut powers_of_gamma = Vec::with_capacity(capacity);
-                        let mut tmp = ExtensionField::<F, 2, EXT>::ONE;
+                        let mut tmp = ExtensionField::<F, DEGREE, EXT>::ONE;
                         powers_of_gamma.push(tmp);
                         for _ in 1..capacity {
                             tmp.mul_assign(&lookup_gamma);
@@ -1307,7 +1409,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                         for (gamma, column) in
                             powers_of_gamma.iter().zip(lookup_tables_columns.iter())
                         {
-                            ExtensionField::<F, 2, EXT>::mul_and_accumulate_into(
+                            ExtensionField::<F, DEGREE, EXT>::mul_and_accumulate_into(
                                 &mut lookup_table_columns_aggregated,
                                 gamma,
                                 column,
@@ -1349,7 +1451,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                                 .iter()
                                 .zip(witness_columns.iter().chain(table_id.iter()))
                             {
-                                ExtensionField::<F, 2, EXT>::mul_and_accumulate_into(
+                                ExtensionField::<F, DEGREE, EXT>::mul_and_accumulate_into(
                                     &mut contribution,
                                     gamma,
                                     column,
@@ -1415,7 +1517,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                         let capacity = column_elements_per_subargument
                             + ((vk.fixed_parameters.table_ids_column_idxes.len() == 1) as usize);
                         let mut powers_of_gamma = Vec::with_capacity(capacity);
-                        let mut tmp = ExtensionField::<F, 2, EXT>::ONE;
+                        let mut tmp = ExtensionField::<F, DEGREE, EXT>::ONE;
                         powers_of_gamma.push(tmp);
                         for _ in 1..capacity {
                             tmp.mul_assign(&lookup_gamma);
@@ -1428,7 +1530,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                         for (gamma, column) in
                             powers_of_gamma.iter().zip(lookup_tables_columns.iter())
                         {
-                            ExtensionField::<F, 2, EXT>::mul_and_accumulate_into(
+                            ExtensionField::<F, DEGREE, EXT>::mul_and_accumulate_into(
                                 &mut lookup_table_columns_aggregated,
                                 gamma,
                                 column,
@@ -1473,7 +1575,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                                 .iter()
                                 .zip(witness_columns.iter().chain(table_id.iter()))
                             {
-                                ExtensionField::<F, 2, EXT>::mul_and_accumulate_into(
+                                ExtensionField::<F, DEGREE, EXT>::mul_and_accumulate_into(
                                     &mut contribution,
                                     gamma,
                                     column,
@@ -1483,7 +1585,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                             // mul by A(x)
                             contribution.mul_assign(a_poly);
                             // sub numerator
-                            contribution.sub_assign(&ExtensionField::<F, 2, EXT>::ONE);
+                            contribution.sub_assign(&ExtensionField::<F, DEGREE, EXT>::ONE);
                             // mul by power of challenge
                             contribution.mul_assign(&alpha);
 
@@ -1615,8 +1717,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                     }
 
                     let mut dst = VerifierRelationDestination {
-                        accumulator: ExtensionField::<F, 2, EXT>::ZERO,
-                        selector_value: ExtensionField::<F, 2, EXT>::ONE,
+                        accumulator: ExtensionField::<F, DEGREE, EXT>::ZERO,
+                        selector_value: ExtensionField::<F, DEGREE, EXT>::ONE,
                         challenges: specialized_evaluators_challenges.clone(),
                         current_challenge_offset: challenges_offset,
                         _marker: std::marker::PhantomData,
@@ -1683,7 +1785,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                         let constant_placement_offset = path.len();
 
                         let mut dst = VerifierRelationDestination {
-                            accumulator: ExtensionField::<F, 2, EXT>::ZERO,
+                            accumulator: ExtensionField::<F, DEGREE, EXT>::ZERO,
                             selector_value: selector,
                             challenges: general_purpose_challenges.clone(),
                             current_challenge_offset: challenges_offset,
@@ -1722,14 +1824,14 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             let z_in_domain_size = z.pow_u64(vk.fixed_parameters.domain_size as u64);
 
             let mut vanishing_at_z = z_in_domain_size;
-            vanishing_at_z.sub_assign(&ExtensionField::<F, 2, EXT>::ONE);
+            vanishing_at_z.sub_assign(&ExtensionField::<F, DEGREE, EXT>::ONE);
 
             let mut challenges_it = remaining_challenges.iter();
 
             {
                 // (x^n - 1) / (x - 1),
                 let mut z_minus_one = z;
-                z_minus_one.sub_assign(&ExtensionField::<F, 2, EXT>::ONE);
+                z_minus_one.sub_assign(&ExtensionField::<F, DEGREE, EXT>::ONE);
 
                 let mut unnormalized_l1_inverse_at_z = vanishing_at_z;
                 unnormalized_l1_inverse_at_z
@@ -1738,7 +1840,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                 let alpha = *challenges_it.next().expect("challenge for z(1) == 1");
                 // (z(x) - 1) * l(1)
                 let mut contribution = copy_permutation_z_at_z;
-                contribution.sub_assign(&ExtensionField::<F, 2, EXT>::ONE);
+                contribution.sub_assign(&ExtensionField::<F, DEGREE, EXT>::ONE);
                 contribution.mul_assign(&unnormalized_l1_inverse_at_z);
                 contribution.mul_assign(&alpha);
 
@@ -1792,8 +1894,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
             assert_eq!(challenges_it.len(), 0, "must exhaust all the challenges");
 
-            let mut t_from_chunks = ExtensionField::<F, 2, EXT>::ZERO;
-            let mut pow = ExtensionField::<F, 2, EXT>::ONE;
+            let mut t_from_chunks = ExtensionField::<F, DEGREE, EXT>::ZERO;
+            let mut pow = ExtensionField::<F, DEGREE, EXT>::ONE;
             for el in quotient_chunks.into_iter() {
                 let mut tmp = el;
                 tmp.mul_assign(&pow);
@@ -1806,14 +1908,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             // assert_eq!(t_accumulator, t_from_chunks, "unsatisfied at Z",);
 
             if t_accumulator != t_from_chunks {
-                log!("Invalid quotient at Z");
-                return false;
+                return Err(VerificationError::InvalidQuotientAtZ);
             }
         }
 
         // get challenges
-        let c0 = transcript.get_challenge();
-        let c1 = transcript.get_challenge();
+        let challenge: [F; DEGREE] = std::array::from_fn(|_| transcript.get_challenge());
 
         let mut total_num_challenges = 0;
         total_num_challenges += proof.values_at_z.len();
@@ -1824,29 +1924,37 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
         }
 
         let challenges_for_fri_quotiening =
-            crate::cs::implementations::prover::materialize_ext_challenge_powers::<F, EXT>(
-                (c0, c1),
+            crate::cs::implementations::prover::materialize_ext_challenge_powers::<F, DEGREE, EXT>(
+                challenge,
                 total_num_challenges,
             );
 
+        if proof.proof_config.pow_bits as usize >= proof.proof_config.security_level
+            || proof.proof_config.security_level > u32::MAX as usize
+        {
+            return Err(VerificationError::MalformedProof(
+                "PoW bits must be less than the security level".to_string(),
+            ));
+        }
+
         let (
             new_pow_bits,                 // updated POW bits if needed
             num_queries,                  // num queries
             interpolation_log2s_schedule, // folding schedule
             final_expected_degree,
-        ) = crate::cs::implementations::prover::compute_fri_schedule(
-            proof.proof_config.security_level as u32,
-            proof.proof_config.merkle_tree_cap_size,
-            proof.proof_config.pow_bits,
+        ) = crate::cs::implementations::prover::compute_fri_schedule_for_config(
+            &proof.proof_config,
             proof.proof_config.fri_lde_factor.trailing_zeros(),
             vk.fixed_parameters.domain_size.trailing_zeros(),
-        );
+        )
+        .map_err(|what| VerificationError::MalformedProof(what.to_string()))?;
 
         let mut expected_degree = vk.fixed_parameters.domain_size;
 
         if new_pow_bits != proof.proof_config.pow_bits {
-            log!("PoW bits computation diverged");
-            return false;
+            return Err(VerificationError::MalformedProof(
+                "PoW bits are not aligned with the security level".to_string(),
+            ));
         }
 
         let mut fri_intermediate_challenges = vec![];
@@ -1854,28 +1962,24 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
         {
             // now witness base FRI oracle
             if vk.fixed_parameters.cap_size != proof.fri_base_oracle_cap.len() {
-                log!("Cap is malformed");
-                return false;
+                return Err(VerificationError::MalformedProof(
+                    "invalid FRI base oracle cap size".to_string(),
+                ));
             }
             transcript.witness_merkle_tree_cap(&proof.fri_base_oracle_cap);
 
             let reduction_degree_log_2 = interpolation_log2s_schedule[0];
-            let c0 = transcript.get_challenge();
-            let c1 = transcript.get_challenge();
+            let challenge: [F; DEGREE] = std::array::from_fn(|_| transcript.get_challenge());
 
             let mut challenge_powers = Vec::with_capacity(reduction_degree_log_2);
-            challenge_powers.push((c0, c1));
-            let as_extension = ExtensionField::<F, 2, EXT> {
-                coeffs: [c0, c1],
-                _marker: std::marker::PhantomData,
-            };
+            challenge_powers.push(challenge);
+            let as_extension = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(challenge);
 
             let mut current = as_extension;
 
             for _ in 1..reduction_degree_log_2 {
                 current.square();
-                let [c0, c1] = current.into_coeffs_in_base();
-                challenge_powers.push((c0, c1));
+                challenge_powers.push(current.into_coeffs_in_base());
             }
             expected_degree >>= reduction_degree_log_2;
 
@@ -1883,8 +1987,9 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
         }
 
         if interpolation_log2s_schedule[1..].len() != proof.fri_intermediate_oracles_caps.len() {
-            log!("Unexpected number of intermediate FRI oracles");
-            return false;
+            return Err(VerificationError::MalformedProof(
+                "unexpected number of intermediate FRI oracles".to_string(),
+            ));
         }
 
         for (interpolation_degree_log2, cap) in interpolation_log2s_schedule[1..]
@@ -1893,29 +1998,25 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
         {
             // commit new oracle
             if vk.fixed_parameters.cap_size != cap.len() {
-                log!("Cap is malformed");
-                return false;
+                return Err(VerificationError::MalformedProof(
+                    "invalid intermediate FRI oracle cap size".to_string(),
+                ));
             }
             transcript.witness_merkle_tree_cap(&cap);
 
             // get challenge
             let reduction_degree_log_2 = *interpolation_degree_log2;
-            let c0 = transcript.get_challenge();
-            let c1 = transcript.get_challenge();
+            let challenge: [F; DEGREE] = std::array::from_fn(|_| transcript.get_challenge());
 
             let mut challenge_powers = Vec::with_capacity(reduction_degree_log_2);
-            challenge_powers.push((c0, c1));
-            let as_extension = ExtensionField::<F, 2, EXT> {
-                coeffs: [c0, c1],
-                _marker: std::marker::PhantomData,
-            };
+            challenge_powers.push(challenge);
+            let as_extension = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(challenge);
 
             let mut current = as_extension;
 
             for _ in 1..reduction_degree_log_2 {
                 current.square();
-                let [c0, c1] = current.into_coeffs_in_base();
-                challenge_powers.push((c0, c1));
+                challenge_powers.push(current.into_coeffs_in_base());
             }
 
             fri_intermediate_challenges.push(challenge_powers);
@@ -1923,32 +2024,39 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
         }
 
         if final_expected_degree != expected_degree as usize {
-            log!("Expected final degree diverged");
-            return false;
+            return Err(VerificationError::MalformedProof(
+                "expected final FRI degree diverged".to_string(),
+            ));
         }
 
-        if proof.final_fri_monomials[0].len() != proof.final_fri_monomials[1].len() {
-            log!("Monomials coefficients length mismatch");
-            return false;
+        for el in proof.final_fri_monomials[1..].iter() {
+            if proof.final_fri_monomials[0].len() != el.len() {
+                return Err(VerificationError::MalformedProof(
+                    "FRI monomials coefficients length mismatch".to_string(),
+                ));
+            }
         }
 
-        if proof.final_fri_monomials[0].len() == 0 || proof.final_fri_monomials[1].len() == 0 {
-            log!("Monomials coefficients length is zero");
-            return false;
+        for el in proof.final_fri_monomials.iter() {
+            if el.len() == 0 {
+                return Err(VerificationError::MalformedProof(
+                    "FRI monomials coefficients length is zero".to_string(),
+                ));
+            }
         }
 
-        if expected_degree as usize != proof.final_fri_monomials[0].len() {
-            log!("Unexpected number of monomials in FRI");
-            return false;
-        }
-        if expected_degree as usize != proof.final_fri_monomials[1].len() {
-            log!("Unexpected number of monomials in FRI");
-            return false;
+        for el in proof.final_fri_monomials.iter() {
+            if expected_degree as usize != el.len() {
+                return Err(VerificationError::MalformedProof(
+                    "unexpected number of monomials in FRI".to_string(),
+                ));
+            }
         }
 
         // witness monomial coeffs
-        transcript.witness_field_elements(&proof.final_fri_monomials[0]);
-        transcript.witness_field_elements(&proof.final_fri_monomials[1]);
+        for el in proof.final_fri_monomials.iter() {
+            transcript.witness_field_elements(el);
+        }
 
         if new_pow_bits != 0 {
             log!("Doing PoW verification for {} bits", new_pow_bits);
@@ -1968,8 +2076,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                 pow_challenge,
             );
             if pow_is_valid == false {
-                log!("PoW is invalid");
-                return false;
+                return Err(VerificationError::InvalidPow);
             }
 
             assert!(F::CAPACITY_BITS >= 32);
@@ -2030,23 +2137,25 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
         assert_eq!(interpolation_steps[2].pow_u64(8), F::ONE);
 
         if num_queries != proof.queries_per_fri_repetition.len() {
-            log!(
+            return Err(VerificationError::MalformedProof(format!(
                 "FRI queries number is invalid: expecting {}, prover provided {}",
                 num_queries,
                 proof.queries_per_fri_repetition.len(),
-            );
+            )));
         }
 
         let base_oracle_depth = vk.fixed_parameters.base_oracles_depth();
 
-        let witness_leaf_size = self.witness_leaf_size(&vk.fixed_parameters);
+        let leaf_salt_size = proof.proof_config.leaf_salt_size();
+
+        let witness_leaf_size = self.witness_leaf_size(&vk.fixed_parameters) + leaf_salt_size;
 
-        let stage_2_leaf_size = self.stage_2_leaf_size(&vk.fixed_parameters);
-        let quotient_leaf_size = self.quotient_leaf_size(&vk.fixed_parameters);
+        let stage_2_leaf_size = self.stage_2_leaf_size(&vk.fixed_parameters) + leaf_salt_size;
+        let quotient_leaf_size = self.quotient_leaf_size(&vk.fixed_parameters) + leaf_salt_size;
 
         let setup_leaf_size = self.setup_leaf_size(&vk.fixed_parameters);
 
-        for queries in proof.queries_per_fri_repetition.iter() {
+        for (query_idx, queries) in proof.queries_per_fri_repetition.iter().enumerate() {
             let query_index_lsb_first_bits =
                 bools_buffer.get_bits(&mut transcript, max_needed_bits);
             // we consider it to be some convenient for us encoding of coset + inner index.
@@ -2069,13 +2178,17 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
             // first verify basic inclusion proofs
             if queries.witness_query.leaf_elements.len() != witness_leaf_size {
-                log!("Invalid leaf size for witness oracle");
-                return false;
+                return Err(VerificationError::MalformedProof(format!(
+                    "invalid leaf size for witness oracle at query number {}",
+                    query_idx
+                )));
             }
             let leaf_hash = H::hash_into_leaf(&queries.witness_query.leaf_elements);
             if queries.witness_query.proof.len() != base_oracle_depth {
-                log!("Invalid Merkle proof length for witness oracle");
-                return false;
+                return Err(VerificationError::MalformedProof(format!(
+                    "invalid Merkle proof length for witness oracle at query number {}",
+                    query_idx
+                )));
             }
             let is_included = MerkleTreeWithCap::<F, H, Global, Global>::verify_proof_over_cap(
                 &queries.witness_query.proof,
@@ -2085,18 +2198,24 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             );
 
             if is_included == false {
-                log!("Witness query not in tree");
-                return false;
+                return Err(VerificationError::InvalidMerklePath {
+                    oracle: OracleKind::Witness,
+                    query: query_idx,
+                });
             }
 
             if queries.stage_2_query.leaf_elements.len() != stage_2_leaf_size {
-                log!("Invalid leaf size for stage 2 oracle");
-                return false;
+                return Err(VerificationError::MalformedProof(format!(
+                    "invalid leaf size for stage 2 oracle at query number {}",
+                    query_idx
+                )));
             }
             let leaf_hash = H::hash_into_leaf(&queries.stage_2_query.leaf_elements);
             if queries.stage_2_query.proof.len() != base_oracle_depth {
-                log!("Invalid Merkle proof length for stage 2 oracle");
-                return false;
+                return Err(VerificationError::MalformedProof(format!(
+                    "invalid Merkle proof length for stage 2 oracle at query number {}",
+                    query_idx
+                )));
             }
             let is_included = MerkleTreeWithCap::<F, H, Global, Global>::verify_proof_over_cap(
                 &queries.stage_2_query.proof,
@@ -2106,18 +2225,24 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             );
 
             if is_included == false {
-                log!("Stage 2 query not in tree");
-                return false;
+                return Err(VerificationError::InvalidMerklePath {
+                    oracle: OracleKind::Stage2,
+                    query: query_idx,
+                });
             }
 
             if queries.quotient_query.leaf_elements.len() != quotient_leaf_size {
-                log!("Invalid leaf size for quotient oracle");
-                return false;
+                return Err(VerificationError::MalformedProof(format!(
+                    "invalid leaf size for quotient oracle at query number {}",
+                    query_idx
+                )));
             }
             let leaf_hash = H::hash_into_leaf(&queries.quotient_query.leaf_elements);
             if queries.quotient_query.proof.len() != base_oracle_depth {
-                log!("Invalid Merkle proof length for quotient oracle");
-                return false;
+                return Err(VerificationError::MalformedProof(format!(
+                    "invalid Merkle proof length for quotient oracle at query number {}",
+                    query_idx
+                )));
             }
             let is_included = MerkleTreeWithCap::<F, H, Global, Global>::verify_proof_over_cap(
                 &queries.quotient_query.proof,
@@ -2127,18 +2252,24 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             );
 
             if is_included == false {
-                log!("Quotient query not in tree");
-                return false;
+                return Err(VerificationError::InvalidMerklePath {
+                    oracle: OracleKind::Quotient,
+                    query: query_idx,
+                });
             }
 
             if queries.setup_query.leaf_elements.len() != setup_leaf_size {
-                log!("Invalid leaf size for setup oracle");
-                return false;
+                return Err(VerificationError::MalformedProof(format!(
+                    "invalid leaf size for setup oracle at query number {}",
+                    query_idx
+                )));
             }
             let leaf_hash = H::hash_into_leaf(&queries.setup_query.leaf_elements);
             if queries.setup_query.proof.len() != base_oracle_depth {
-                log!("Invalid Merkle proof length for setup oracle");
-                return false;
+                return Err(VerificationError::MalformedProof(format!(
+                    "invalid Merkle proof length for setup oracle at query number {}",
+                    query_idx
+                )));
             }
             let is_included = MerkleTreeWithCap::<F, H, Global, Global>::verify_proof_over_cap(
                 &queries.setup_query.proof,
@@ -2148,12 +2279,25 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             );
 
             if is_included == false {
-                log!("Setup query not in tree");
-                return false;
+                return Err(VerificationError::InvalidMerklePath {
+                    oracle: OracleKind::Setup,
+                    query: query_idx,
+                });
+            }
+
+            // salt is only needed for inclusion proofs
+            let mut queries = queries.clone();
+            for query in [
+                &mut queries.witness_query,
+                &mut queries.stage_2_query,
+                &mut queries.quotient_query,
+            ] {
+                let num_elements = query.leaf_elements.len() - leaf_salt_size;
+                query.leaf_elements.truncate(num_elements);
             }
 
             // now perform the quotiening operation
-            let mut simulated_ext_element = ExtensionField::<F, 2, EXT>::ZERO;
+            let mut simulated_ext_element = ExtensionField::<F, DEGREE, EXT>::ZERO;
 
             assert_eq!(
                 query_index_lsb_first_bits.len(),
@@ -2198,11 +2342,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             let mut challenge_offset = 0;
 
             let z_polys_offset = 0;
-            let intermediate_polys_offset = 2;
+            let intermediate_polys_offset = DEGREE;
             let lookup_witness_encoding_polys_offset =
-                intermediate_polys_offset + num_intermediate_partial_product_relations * 2;
+                intermediate_polys_offset + num_intermediate_partial_product_relations * DEGREE;
             let lookup_multiplicities_encoding_polys_offset =
-                lookup_witness_encoding_polys_offset + num_lookup_subarguments * 2;
+                lookup_witness_encoding_polys_offset + num_lookup_subarguments * DEGREE;
             let copy_permutation_polys_offset = 0;
             let constants_offset = 0 + num_copy_permutation_polys;
             let lookup_tables_values_offset = 0 + num_copy_permutation_polys + num_constant_polys;
@@ -2217,15 +2361,15 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
                 let cast_from_base = move |el: &[F]| {
                     el.iter()
-                        .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base([*el, F::ZERO]))
+                        .map(|el| ExtensionField::<F, DEGREE, EXT>::from_base(*el))
                         .collect::<Vec<_>>()
                 };
 
                 let cast_from_extension = move |el: &[F]| {
-                    assert_eq!(el.len() % 2, 0);
+                    assert_eq!(el.len() % DEGREE, 0);
 
-                    el.array_chunks::<2>()
-                        .map(|[a, b]| ExtensionField::<F, 2, EXT>::from_coeff_in_base([*a, *b]))
+                    el.array_chunks::<DEGREE>()
+                        .map(|el| ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(*el))
                         .collect::<Vec<_>>()
                 };
 
@@ -2336,7 +2480,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                         &sources,
                         &values_at_0,
                         domain_element_for_quotiening,
-                        ExtensionField::<F, 2, EXT>::ZERO,
+                        ExtensionField::<F, DEGREE, EXT>::ZERO,
                         &challenges_for_fri_quotiening
                             [challenge_offset..(challenge_offset + sources.len())],
                     );
@@ -2350,14 +2494,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                 let mut sources = Vec::with_capacity(set.len());
                 let mut values = Vec::with_capacity(set.len());
                 for (column, expected_value) in set.into_iter() {
-                    let el = ExtensionField::<F, 2, EXT>::from_coeff_in_base([
+                    let el = ExtensionField::<F, DEGREE, EXT>::from_base(
                         queries.witness_query.leaf_elements[*column],
-                        F::ZERO,
-                    ]);
+                    );
                     sources.push(el);
 
-                    let value =
-                        ExtensionField::<F, 2, EXT>::from_coeff_in_base([*expected_value, F::ZERO]);
+                    let value = ExtensionField::<F, DEGREE, EXT>::from_base(*expected_value);
                     values.push(value);
                 }
                 let num_challenges_required = sources.len();
@@ -2365,7 +2507,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
                 // log!("Making quotiening at {} for public inputs", open_at);
 
-                let open_at = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*open_at, F::ZERO]);
+                let open_at = ExtensionField::<F, DEGREE, EXT>::from_base(*open_at);
 
                 quotening_operation(
                     &mut simulated_ext_element,
@@ -2387,8 +2529,10 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             let mut coset_inverse = base_coset_inverse;
 
             if interpolation_log2s_schedule.len() != queries.fri_queries.len() {
-                log!("Invalid number of FRI intermediate oracle queries per repetition");
-                return false;
+                return Err(VerificationError::MalformedProof(format!(
+                    "invalid number of FRI oracle queries at query number {}",
+                    query_idx
+                )));
             }
 
             let mut expected_fri_query_len = base_oracle_depth;
@@ -2403,12 +2547,23 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                 let subidx_in_leaf = subidx % interpolation_degree;
                 let tree_idx = subidx >> interpolation_degree_log2;
 
-                let [c0, c1] = current_folded_value.into_coeffs_in_base();
-                if c0 != fri_query.leaf_elements[subidx_in_leaf]
-                    || c1 != fri_query.leaf_elements[interpolation_degree + subidx_in_leaf]
-                {
-                    log!("FRI element is not in the leaf for step {}", idx);
-                    return false;
+                if fri_query.leaf_elements.len() != interpolation_degree * DEGREE {
+                    // account for extension here
+                    return Err(VerificationError::MalformedProof(format!(
+                        "invalid leaf size for FRI oracle number {} at query number {}",
+                        idx, query_idx
+                    )));
+                }
+
+                let coeffs = current_folded_value.into_coeffs_in_base();
+                let is_consistent = coeffs.iter().enumerate().all(|(i, el)| {
+                    *el == fri_query.leaf_elements[i * interpolation_degree + subidx_in_leaf]
+                });
+                if is_consistent == false {
+                    return Err(VerificationError::InvalidFriFold {
+                        query: query_idx,
+                        step: idx,
+                    });
                 }
 
                 // verify query itself
@@ -2417,15 +2572,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                 } else {
                     &proof.fri_intermediate_oracles_caps[idx - 1]
                 };
-                if fri_query.leaf_elements.len() != interpolation_degree * 2 {
-                    // account for extension here
-                    log!("Invalid leaf size for FRI oracle number {}", idx);
-                    return false;
-                }
                 let leaf_hash = H::hash_into_leaf(&fri_query.leaf_elements);
                 if fri_query.proof.len() != expected_fri_query_len {
-                    log!("Invalid Merkle proof length for FRI oracle number {}", idx);
-                    return false;
+                    return Err(VerificationError::MalformedProof(format!(
+                        "invalid Merkle proof length for FRI oracle number {} at query number {}",
+                        idx, query_idx
+                    )));
                 }
                 let is_included = MerkleTreeWithCap::<F, H, Global, Global>::verify_proof_over_cap(
                     &fri_query.proof,
@@ -2434,20 +2586,19 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                     tree_idx as usize,
                 );
                 if is_included == false {
-                    log!("FRI leaf is not in the tree for step {}", idx);
-                    return false;
+                    return Err(VerificationError::InvalidMerklePath {
+                        oracle: OracleKind::Fri(idx),
+                        query: query_idx,
+                    });
                 }
 
                 // interpolate
                 let mut elements_to_interpolate = Vec::with_capacity(interpolation_degree);
-                for (c0, c1) in fri_query.leaf_elements[..interpolation_degree]
-                    .iter()
-                    .zip(fri_query.leaf_elements[interpolation_degree..].iter())
-                {
-                    let as_ext = ExtensionField::<F, 2, EXT> {
-                        coeffs: [*c0, *c1],
-                        _marker: std::marker::PhantomData,
-                    };
+                for idx in 0..interpolation_degree {
+                    let as_ext =
+                        ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(std::array::from_fn(
+                            |i| fri_query.leaf_elements[i * interpolation_degree + idx],
+                        ));
                     elements_to_interpolate.push(as_ext);
                 }
 
@@ -2457,11 +2608,9 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
                 let mut base_pow = power_chunks[idx];
 
-                for (c0, c1) in challenges.iter() {
-                    let challenge = ExtensionField::<F, 2, EXT> {
-                        coeffs: [*c0, *c1],
-                        _marker: std::marker::PhantomData,
-                    };
+                for challenge in challenges.iter() {
+                    let challenge =
+                        ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(*challenge);
                     for (i, [a, b]) in elements_to_interpolate.array_chunks::<2>().enumerate() {
                         let mut result = *a;
                         result.add_assign(b);
@@ -2496,51 +2645,49 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
             // and we should evaluate monomial form and compare
 
-            let mut result_from_monomial = ExtensionField::<F, 2, EXT>::ZERO;
+            let mut result_from_monomial = ExtensionField::<F, DEGREE, EXT>::ZERO;
             // horner rule
-            for (c0, c1) in proof.final_fri_monomials[0]
-                .iter()
-                .zip(proof.final_fri_monomials[1].iter())
-                .rev()
-            {
-                let coeff = ExtensionField::<F, 2, EXT> {
-                    coeffs: [*c0, *c1],
-                    _marker: std::marker::PhantomData,
-                };
+            for idx in (0..proof.final_fri_monomials[0].len()).rev() {
+                let coeff = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(
+                    std::array::from_fn(|i| proof.final_fri_monomials[i][idx]),
+                );
 
                 result_from_monomial.mul_assign_by_base(&domain_element_for_interpolation);
                 result_from_monomial.add_assign(&coeff);
             }
 
             if result_from_monomial != current_folded_value {
-                log!("Not equal to evaluation from monomials");
-                return false;
+                return Err(VerificationError::InvalidFriFinalPolynomial { query: query_idx });
             }
         }
 
-        true
+        Ok(())
     }
 }
 
-fn quotening_operation<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
-    dst: &mut ExtensionField<F, 2, EXT>,
-    polynomial_values: &Vec<ExtensionField<F, 2, EXT>>,
-    values_at: &Vec<ExtensionField<F, 2, EXT>>,
+fn quotening_operation<
+    F: SmallField,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+>(
+    dst: &mut ExtensionField<F, DEGREE, EXT>,
+    polynomial_values: &Vec<ExtensionField<F, DEGREE, EXT>>,
+    values_at: &Vec<ExtensionField<F, DEGREE, EXT>>,
     domain_element: F,
-    at: ExtensionField<F, 2, EXT>,
-    challenge_coefficients: &[(F, F)],
+    at: ExtensionField<F, DEGREE, EXT>,
+    challenge_coefficients: &[[F; DEGREE]],
 ) {
     // we precompute challenges outside to avoid any manual extension ops here
     assert_eq!(polynomial_values.len(), values_at.len());
     assert_eq!(polynomial_values.len(), challenge_coefficients.len());
 
-    let mut denom = ExtensionField::<F, 2, EXT>::from_coeff_in_base([domain_element, F::ZERO]);
+    let mut denom = ExtensionField::<F, DEGREE, EXT>::from_base(domain_element);
     denom.sub_assign(&at);
     denom = denom.inverse().expect("must exist");
 
-    let mut acc = ExtensionField::<F, 2, EXT>::ZERO;
+    let mut acc = ExtensionField::<F, DEGREE, EXT>::ZERO;
 
-    for ((poly_value, value_at), (c0, c1)) in polynomial_values
+    for ((poly_value, value_at), challenge) in polynomial_values
         .iter()
         .zip(values_at.iter())
         .zip(challenge_coefficients.iter())
@@ -2549,10 +2696,7 @@ fn quotening_operation<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
         let mut tmp = *poly_value;
         tmp.sub_assign(&value_at);
 
-        let mut as_ext = ExtensionField::<F, 2, EXT> {
-            coeffs: [*c0, *c1],
-            _marker: std::marker::PhantomData,
-        };
+        let mut as_ext = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(*challenge);
 
         as_ext.mul_assign(&tmp);
         acc.add_assign(&as_ext);
@@ -35,20 +35,20 @@ use crate::field::ExtensionField;
 use crate::field::FieldExtension;
 use crate::field::PrimeField;
 
-impl<F: SmallField, E: FieldExtension<2, BaseField = F>> FlattenIntoBase<F, 2>
-    for &ExtensionField<F, 2, E>
+impl<F: SmallField, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>>
+    FlattenIntoBase<F, DEGREE> for &ExtensionField<F, DEGREE, E>
 {
     #[inline(always)]
-    fn into_base(self) -> [F; 2] {
+    fn into_base(self) -> [F; DEGREE] {
         self.into_coeffs_in_base()
     }
 }
 
-impl<F: SmallField, E: FieldExtension<2, BaseField = F>> FlattenIntoBase<F, 2>
-    for ExtensionField<F, 2, E>
+impl<F: SmallField, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>>
+    FlattenIntoBase<F, DEGREE> for ExtensionField<F, DEGREE, E>
 {
     #[inline(always)]
-    fn into_base(self) -> [F; 2] {
+    fn into_base(self) -> [F; DEGREE] {
         self.into_coeffs_in_base()
     }
 }
@@ -2,6 +2,7 @@ use crate::field::goldilocks::GoldilocksField;
 use crate::field::traits::field::{Field, PrimeField};
 use crate::field::SmallField;
 
+pub mod plan;
 pub mod transpose;
 use crate::field::goldilocks::MixedGL;
 
@@ -0,0 +1,336 @@
+use super::*;
+use crate::cs::implementations::utils::precompute_twiddles_for_fft;
+use std::alloc::Global;
+
+// Radix-4 engine is the same Cooley-Tukey decimation in frequency as `serial_ct_ntt_natural_to_bitreversed`,
+// but two consecutive radix-2 stages are fused, so every element is loaded and stored once per
+// two stages. In prime fields it doesn't save multiplications, only the memory traffic
+
+pub(crate) fn serial_radix_4_ntt_natural_to_bitreversed<F: BaseField>(
+    a: &mut [F],
+    log_n: u32,
+    omegas_bit_reversed: &[F],
+) {
+    let n = a.len();
+    if n == 1 {
+        return;
+    }
+
+    debug_assert!(n <= omegas_bit_reversed.len() * 2);
+    debug_assert!(n == (1 << log_n) as usize);
+
+    let mut num_groups = 1;
+    let mut distance = n / 2;
+
+    while distance >= 2 {
+        // group k of the first stage is split into groups 2k and 2k + 1 of the second one
+        let quarter = distance / 2;
+        let mut k = 0;
+        while k < num_groups {
+            let s = omegas_bit_reversed[k];
+            let t0 = omegas_bit_reversed[2 * k];
+            let t1 = omegas_bit_reversed[2 * k + 1];
+
+            let start = k * distance * 2;
+            let mut j = start;
+            while j < start + quarter {
+                let a0 = a[j];
+                let a1 = a[j + quarter];
+                let mut a2 = a[j + distance];
+                a2.mul_assign(&s);
+                let mut a3 = a[j + distance + quarter];
+                a3.mul_assign(&s);
+
+                let mut b0 = a0;
+                b0.add_assign(&a2);
+                let mut b2 = a0;
+                b2.sub_assign(&a2);
+                let mut b1 = a1;
+                b1.add_assign(&a3);
+                b1.mul_assign(&t0);
+                let mut b3 = a1;
+                b3.sub_assign(&a3);
+                b3.mul_assign(&t1);
+
+                a[j] = b0;
+                a[j].add_assign(&b1);
+                a[j + quarter] = b0;
+                a[j + quarter].sub_assign(&b1);
+                a[j + distance] = b2;
+                a[j + distance].add_assign(&b3);
+                a[j + distance + quarter] = b2;
+                a[j + distance + quarter].sub_assign(&b3);
+
+                j += 1;
+            }
+
+            k += 1;
+        }
+
+        num_groups *= 4;
+        distance /= 4;
+    }
+
+    if distance == 1 {
+        // odd number of stages, so the last one is radix-2
+        let mut k = 0;
+        while k < num_groups {
+            let j = 2 * k;
+            let mut v = a[j + 1];
+            v.mul_assign(&omegas_bit_reversed[k]);
+
+            let mut tmp = a[j];
+            tmp.sub_assign(&v);
+
+            a[j + 1] = tmp;
+            a[j].add_assign(&v);
+
+            k += 1;
+        }
+    }
+}
+
+/// Finds a primitive root of unity of any order that divides `p - 1`, so it's not restricted
+/// to powers of two
+pub fn root_of_unity_for_order<F: SmallField>(order: u64) -> Option<F> {
+    assert!(order > 0);
+
+    let group_order = F::CHAR - 1;
+    if group_order % order != 0 {
+        return None;
+    }
+
+    Some(F::multiplicative_generator().pow_u64(group_order / order))
+}
+
+/// Shifts `g * omega^j` for `j` in `0..lde_factor`, where `omega` is a root of unity of order `domain_size * lde_factor`,
+/// so the cosets `g * omega^j * H` of the subgroup `H` of size `domain_size` partition the coset of the
+/// subgroup of size `domain_size * lde_factor`. Factor doesn't have to be a power of two, but it must divide
+/// `(p - 1) / domain_size`, otherwise `None` is returned
+pub fn lde_cosets<F: SmallField>(domain_size: usize, lde_factor: usize) -> Option<Vec<F>> {
+    assert!(domain_size.is_power_of_two());
+    assert!(lde_factor > 0);
+
+    let omega = root_of_unity_for_order::<F>((domain_size * lde_factor) as u64)?;
+    let mut current = F::multiplicative_generator();
+    let mut result = Vec::with_capacity(lde_factor);
+    for _ in 0..lde_factor {
+        result.push(current);
+        current.mul_assign(&omega);
+    }
+
+    Some(result)
+}
+
+/// Precomputed twiddles that can be reused for all power of two sizes up to `max_size`, as bitreversed
+/// twiddles for the smaller domain are a prefix of the ones for the larger domain
+#[derive(Clone, Debug)]
+pub struct FftPlan<F: SmallField, A: GoodAllocator = Global> {
+    max_size: usize,
+    forward_twiddles: Vec<F, A>,
+    inverse_twiddles: Vec<F, A>,
+}
+
+impl<F: SmallField, A: GoodAllocator> FftPlan<F, A> {
+    pub fn new(max_size: usize, worker: &Worker) -> Self {
+        assert!(max_size.is_power_of_two());
+        assert!(max_size.trailing_zeros() as usize <= F::TWO_ADICITY);
+
+        let forward_twiddles =
+            precompute_twiddles_for_fft::<F, F, A, false>(max_size, worker, &mut ());
+        let inverse_twiddles =
+            precompute_twiddles_for_fft::<F, F, A, true>(max_size, worker, &mut ());
+
+        Self {
+            max_size,
+            forward_twiddles,
+            inverse_twiddles,
+        }
+    }
+
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    fn check_size(&self, size: usize) {
+        assert!(size.is_power_of_two());
+        assert!(
+            size <= self.max_size,
+            "plan is made for sizes up to {}, but {} is requested",
+            self.max_size,
+            size
+        );
+    }
+
+    /// Evaluates monomial form on `coset * H` in bitreversed enumeration
+    pub fn fft_natural_to_bitreversed(&self, input: &mut [F], coset: F) {
+        self.check_size(input.len());
+
+        if coset != F::ONE {
+            distribute_powers(input, coset);
+        }
+
+        let log_n = input.len().trailing_zeros();
+        serial_radix_4_ntt_natural_to_bitreversed(input, log_n, &self.forward_twiddles);
+    }
+
+    /// Interpolates values on `coset * H` in natural enumeration into monomial form
+    pub fn ifft_natural_to_natural(&self, input: &mut [F], coset: F) {
+        self.check_size(input.len());
+
+        let log_n = input.len().trailing_zeros();
+        serial_radix_4_ntt_natural_to_bitreversed(input, log_n, &self.inverse_twiddles);
+        bitreverse_enumeration_inplace(input);
+
+        if coset != F::ONE {
+            let coset = coset.inverse().expect("inverse of coset must exist");
+            distribute_powers(input, coset);
+        }
+
+        if input.len() > 1 {
+            let n_inv = F::from_u64_with_reduction(input.len() as u64)
+                .inverse()
+                .unwrap();
+            for el in input.iter_mut() {
+                el.mul_assign(&n_inv);
+            }
+        }
+    }
+
+    /// Takes values on the subgroup `H` in natural enumeration, and evaluates the same polynomial on every
+    /// coset `c * H` for `c` in `cosets`. Every output is in bitreversed enumeration, same as
+    /// in the LDEs of the prover
+    pub fn lde_on_cosets(&self, values: &[F], cosets: &[F], worker: &Worker) -> Vec<Vec<F, A>> {
+        self.check_size(values.len());
+        assert!(cosets.len() > 0);
+
+        let mut monomial = Vec::with_capacity_in(values.len(), A::default());
+        monomial.extend_from_slice(values);
+        self.ifft_natural_to_natural(&mut monomial, F::ONE);
+
+        let mut result: Vec<_> = cosets.iter().map(|_| monomial.clone()).collect();
+        worker.scope(result.len(), |scope, chunk_size| {
+            for (dst, cosets) in result.chunks_mut(chunk_size).zip(cosets.chunks(chunk_size)) {
+                scope.spawn(move |_| {
+                    for (dst, coset) in dst.iter_mut().zip(cosets.iter()) {
+                        self.fft_natural_to_bitreversed(dst, *coset);
+                    }
+                });
+            }
+        });
+
+        result
+    }
+
+    /// LDE by any factor that the field allows, see `lde_cosets`. Note that it's a standalone
+    /// API: prover, FRI and verifiers work over power of two LDE factors only
+    pub fn lde(&self, values: &[F], lde_factor: usize, worker: &Worker) -> Option<Vec<Vec<F, A>>> {
+        let cosets = lde_cosets::<F>(values.len(), lde_factor)?;
+
+        Some(self.lde_on_cosets(values, &cosets, worker))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cs::implementations::utils::domain_generator_for_size;
+    use crate::field::rand_from_rng;
+    use std::collections::HashSet;
+
+    type F = GoldilocksField;
+
+    fn naive_evaluate(coeffs: &[F], at: F) -> F {
+        let mut result = F::ZERO;
+        for coeff in coeffs.iter().rev() {
+            result.mul_assign(&at);
+            result.add_assign(coeff);
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_radix_4_matches_radix_2() {
+        let worker = Worker::new();
+        let mut rng = rand::thread_rng();
+        for log_n in 0..14 {
+            let n = 1 << log_n;
+            let twiddles = precompute_twiddles_for_fft::<F, F, Global, false>(n, &worker, &mut ());
+            let original: Vec<F> = (0..n).map(|_| rand_from_rng(&mut rng)).collect();
+
+            let mut radix_2 = original.clone();
+            serial_ct_ntt_natural_to_bitreversed(&mut radix_2, log_n, &twiddles);
+            let mut radix_4 = original;
+            serial_radix_4_ntt_natural_to_bitreversed(&mut radix_4, log_n, &twiddles);
+
+            assert_eq!(radix_2, radix_4, "failed for size 2^{}", log_n);
+        }
+    }
+
+    #[test]
+    fn test_plan_reuse_and_roundtrip() {
+        let worker = Worker::new();
+        let mut rng = rand::thread_rng();
+        let plan = FftPlan::<F>::new(1 << 12, &worker);
+
+        for log_n in 0..=12 {
+            let n = 1 << log_n;
+            let coeffs: Vec<F> = (0..n).map(|_| rand_from_rng(&mut rng)).collect();
+            let coset = F::multiplicative_generator();
+
+            let mut values = coeffs.clone();
+            plan.fft_natural_to_bitreversed(&mut values, coset);
+            bitreverse_enumeration_inplace(&mut values);
+
+            let omega = domain_generator_for_size::<F>(n as u64);
+            for (i, value) in values.iter().enumerate().take(16) {
+                let mut x = omega.pow_u64(i as u64);
+                x.mul_assign(&coset);
+                assert_eq!(*value, naive_evaluate(&coeffs, x));
+            }
+
+            plan.ifft_natural_to_natural(&mut values, coset);
+            assert_eq!(values, coeffs, "failed for size 2^{}", log_n);
+        }
+    }
+
+    #[test]
+    fn test_lde_by_non_power_of_two_factors() {
+        let worker = Worker::new();
+        let mut rng = rand::thread_rng();
+        let n = 1 << 8;
+        let plan = FftPlan::<F>::new(n, &worker);
+        let coeffs: Vec<F> = (0..n).map(|_| rand_from_rng(&mut rng)).collect();
+
+        let mut values = coeffs.clone();
+        plan.fft_natural_to_bitreversed(&mut values, F::ONE);
+        bitreverse_enumeration_inplace(&mut values);
+
+        let omega = domain_generator_for_size::<F>(n as u64);
+        for lde_factor in [2, 3, 5, 6] {
+            let ldes = plan.lde(&values, lde_factor, &worker).unwrap();
+            let cosets = lde_cosets::<F>(n, lde_factor).unwrap();
+            assert_eq!(ldes.len(), lde_factor);
+
+            let mut all_points = HashSet::new();
+            for (lde, coset) in ldes.iter().zip(cosets.iter()) {
+                for (i, value) in lde.iter().enumerate() {
+                    let idx = i.reverse_bits() >> (usize::BITS - n.trailing_zeros());
+                    let mut x = omega.pow_u64(idx as u64);
+                    x.mul_assign(coset);
+                    if i < 16 {
+                        assert_eq!(*value, naive_evaluate(&coeffs, x));
+                    }
+                    all_points.insert(x);
+                }
+            }
+            // cosets are disjoint
+            assert_eq!(all_points.len(), n * lde_factor);
+        }
+
+        // 7 doesn't divide p - 1 for Goldilocks
+        assert!(lde_cosets::<F>(n, 7).is_none());
+        assert!(plan.lde(&values, 7, &worker).is_none());
+    }
+}
@@ -0,0 +1,166 @@
+use super::*;
+use derivative::*;
+
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone, Copy, Debug, Hash)]
+pub struct BabyBearExt2;
+
+impl std::fmt::Display for BabyBearExt2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BabyBearExt2")
+    }
+}
+
+impl BabyBearExt2 {
+    const NON_RESIDUE: BabyBearField = BabyBearField(11u32);
+    const GENERATOR_COEFFS: [BabyBearField; 2] = [BabyBearField(1u32), BabyBearField(5u32)];
+}
+
+impl crate::field::FieldExtension<2> for BabyBearExt2 {
+    // p^2 - 1 = (p - 1) * (p + 1), and p + 1 = 2 mod 4
+    const TWO_ADICITY: usize = BabyBearField::TWO_ADICITY + 1;
+
+    type BaseField = BabyBearField;
+
+    #[inline(always)]
+    fn non_residue() -> Self::BaseField {
+        Self::NON_RESIDUE
+    }
+
+    fn compute_norm(el: &[Self::BaseField; 2]) -> Self::BaseField {
+        // c0^2 - non_residue * c1^2
+        let mut result = el[0];
+        result.square();
+        let mut t = el[1];
+        t.square().mul_assign(&Self::NON_RESIDUE);
+        result.sub_assign(&t);
+
+        result
+    }
+
+    fn multiplicative_generator_coeffs() -> [Self::BaseField; 2] {
+        Self::GENERATOR_COEFFS
+    }
+
+    #[inline(always)]
+    fn mul_by_non_residue(el: &mut Self::BaseField) {
+        el.mul_assign(&Self::NON_RESIDUE);
+    }
+}
+
+impl crate::field::traits::field_like::PrimeFieldLikeExtension<2> for BabyBearExt2 {
+    const TWO_ADICITY: usize = BabyBearField::TWO_ADICITY + 1;
+
+    type BaseField = BabyBearField;
+
+    fn compute_norm(
+        el: &[Self::BaseField; 2],
+        _ctx: &mut <Self::BaseField as crate::field::traits::field_like::PrimeFieldLike>::Context,
+    ) -> Self::BaseField {
+        <Self as crate::field::FieldExtension<2>>::compute_norm(el)
+    }
+
+    fn multiplicative_generator_coeffs(
+        _ctx: &mut <Self::BaseField as crate::field::traits::field_like::PrimeFieldLike>::Context,
+    ) -> [Self::BaseField; 2] {
+        Self::GENERATOR_COEFFS
+    }
+
+    fn mul_by_non_residue(
+        el: &mut Self::BaseField,
+        _ctx: &mut <Self::BaseField as crate::field::traits::field_like::PrimeFieldLike>::Context,
+    ) {
+        el.mul_assign(&Self::NON_RESIDUE);
+    }
+}
+
+/// Cubic extension by `x^3 = 2`
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone, Copy, Debug, Hash)]
+pub struct BabyBearExt3;
+
+impl std::fmt::Display for BabyBearExt3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BabyBearExt3")
+    }
+}
+
+impl BabyBearExt3 {
+    const NON_RESIDUE: BabyBearField = BabyBearField(2u32);
+    const GENERATOR_COEFFS: [BabyBearField; 3] = [
+        BabyBearField(1u32),
+        BabyBearField(3u32),
+        BabyBearField(0u32),
+    ];
+}
+
+impl crate::field::FieldExtension<3> for BabyBearExt3 {
+    // p^3 - 1 = (p - 1) * (p^2 + p + 1), and second multiple is odd
+    const TWO_ADICITY: usize = BabyBearField::TWO_ADICITY;
+
+    type BaseField = BabyBearField;
+
+    #[inline(always)]
+    fn non_residue() -> Self::BaseField {
+        Self::NON_RESIDUE
+    }
+
+    fn compute_norm(el: &[Self::BaseField; 3]) -> Self::BaseField {
+        crate::field::ExtensionField::<BabyBearField, 3, Self>::from_coeff_in_base(*el).norm()
+    }
+
+    fn multiplicative_generator_coeffs() -> [Self::BaseField; 3] {
+        Self::GENERATOR_COEFFS
+    }
+
+    #[inline(always)]
+    fn mul_by_non_residue(el: &mut Self::BaseField) {
+        el.double();
+    }
+}
+
+/// Quartic extension by `x^4 = 11`
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone, Copy, Debug, Hash)]
+pub struct BabyBearExt4;
+
+impl std::fmt::Display for BabyBearExt4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BabyBearExt4")
+    }
+}
+
+impl BabyBearExt4 {
+    const NON_RESIDUE: BabyBearField = BabyBearField(11u32);
+    const GENERATOR_COEFFS: [BabyBearField; 4] = [
+        BabyBearField(5u32),
+        BabyBearField(2u32),
+        BabyBearField(0u32),
+        BabyBearField(0u32),
+    ];
+}
+
+impl crate::field::FieldExtension<4> for BabyBearExt4 {
+    // p^4 - 1 = (p - 1) * (p + 1) * (p^2 + 1), and last two are 2 mod 4
+    const TWO_ADICITY: usize = BabyBearField::TWO_ADICITY + 2;
+
+    type BaseField = BabyBearField;
+
+    #[inline(always)]
+    fn non_residue() -> Self::BaseField {
+        Self::NON_RESIDUE
+    }
+
+    fn compute_norm(el: &[Self::BaseField; 4]) -> Self::BaseField {
+        crate::field::ExtensionField::<BabyBearField, 4, Self>::from_coeff_in_base(*el).norm()
+    }
+
+    fn multiplicative_generator_coeffs() -> [Self::BaseField; 4] {
+        Self::GENERATOR_COEFFS
+    }
+
+    #[inline(always)]
+    fn mul_by_non_residue(el: &mut Self::BaseField) {
+        el.mul_assign(&Self::NON_RESIDUE);
+    }
+}
@@ -0,0 +1,593 @@
+use crate::field::{
+    Field, PrimeField, SmallField, SmallFieldRepresentable, U64RawRepresentable, U64Representable,
+};
+use std::hash::{Hash, Hasher};
+
+mod extension;
+
+pub use self::extension::{BabyBearExt2, BabyBearExt3, BabyBearExt4};
+
+/// 31-bit field with large 2-adic subgroup.
+///
+/// Its order is 15 * 2^27 + 1.
+///
+/// Values are always kept in the canonical form, so unlike Goldilocks all the `*_unchecked`
+/// constructors reduce the input
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[repr(transparent)]
+pub struct BabyBearField(pub u32);
+
+impl BabyBearField {
+    pub const MULTIPLICATIVE_GROUP_GENERATOR: Self = Self(31);
+    pub const RADIX_2_SUBGROUP_GENERATOR: Self = Self(440564289);
+    pub const ORDER_BITS: usize = 31;
+    pub const ORDER: u32 = 0x78000001;
+    pub const TWO_ADICITY: usize = 27;
+    pub const T: u32 = (Self::ORDER - 1) >> Self::TWO_ADICITY;
+
+    #[inline(always)]
+    pub const fn new(value: u32) -> Self {
+        Self(value % Self::ORDER)
+    }
+
+    #[inline(always)]
+    pub const fn to_reduced_u32(&self) -> u32 {
+        self.0
+    }
+
+    #[inline(always)]
+    pub const fn from_u64_with_reduction_impl(x: u64) -> Self {
+        Self((x % (Self::ORDER as u64)) as u32)
+    }
+
+    // Barrett reduction of the products of canonical values in u64 lanes. The quotient is
+    // estimated from the top bits with at most 2 error, that is fixed by conditional subtractions
+    #[inline(always)]
+    pub fn reduce_u64x16(x: std::simd::u64x16) -> std::simd::u32x16 {
+        use std::simd::*;
+
+        const BARRETT_CONSTANT: u64 = (1u64 << 63) / (BabyBearField::ORDER as u64);
+        let order = u64x16::splat(Self::ORDER as u64);
+        let quotient = ((x >> u64x16::splat(31)) * u64x16::splat(BARRETT_CONSTANT))
+            >> u64x16::splat(32);
+        let mut result = x - quotient * order;
+        result = result.simd_min(result - order);
+        result = result.simd_min(result - order);
+
+        result.cast::<u32>()
+    }
+
+    const fn compute_shifts() -> [Self; Self::ORDER_BITS] {
+        let mut result = [Self(0); Self::ORDER_BITS];
+        let mut i = 0;
+        while i < Self::ORDER_BITS {
+            result[i] = Self(1u32 << i);
+            i += 1;
+        }
+
+        result
+    }
+
+    #[inline(always)]
+    pub(crate) const fn add_assign_impl(&'_ mut self, other: &Self) -> &'_ mut Self {
+        // both are below 2^31, so can not overflow
+        let mut sum = self.0 + other.0;
+        if sum >= Self::ORDER {
+            sum -= Self::ORDER;
+        }
+        self.0 = sum;
+
+        self
+    }
+
+    #[inline(always)]
+    pub(crate) const fn sub_assign_impl(&'_ mut self, other: &Self) -> &'_ mut Self {
+        let (mut diff, borrow) = self.0.overflowing_sub(other.0);
+        if borrow {
+            diff = diff.wrapping_add(Self::ORDER);
+        }
+        self.0 = diff;
+
+        self
+    }
+
+    #[inline(always)]
+    pub(crate) const fn mul_assign_impl(&'_ mut self, other: &Self) -> &'_ mut Self {
+        *self = Self::from_u64_with_reduction_impl((self.0 as u64) * (other.0 as u64));
+
+        self
+    }
+}
+
+impl Default for BabyBearField {
+    fn default() -> Self {
+        Self(0u32)
+    }
+}
+
+impl PartialEq for BabyBearField {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for BabyBearField {}
+
+impl Hash for BabyBearField {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u32(self.0)
+    }
+}
+
+impl std::fmt::Display for BabyBearField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:08x}", self.0)
+    }
+}
+
+impl std::fmt::Debug for BabyBearField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:08x}", self.0)
+    }
+}
+
+impl Field for BabyBearField {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(1);
+    const TWO: Self = Self(2);
+    const MINUS_ONE: Self = Self(Self::ORDER - 1);
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline(always)]
+    fn add_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
+        self.add_assign_impl(other)
+    }
+
+    #[inline(always)]
+    fn sub_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
+        self.sub_assign_impl(other)
+    }
+
+    #[inline(always)]
+    fn negate(&mut self) -> &mut Self {
+        if self.is_zero() == false {
+            self.0 = Self::ORDER - self.0;
+        }
+
+        self
+    }
+
+    #[inline(always)]
+    fn mul_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
+        self.mul_assign_impl(other)
+    }
+
+    #[inline(always)]
+    fn square(&mut self) -> &mut Self {
+        let t = *self;
+        self.mul_assign_impl(&t)
+    }
+
+    #[inline(always)]
+    fn double(&mut self) -> &mut Self {
+        let t = *self;
+        self.add_assign_impl(&t)
+    }
+
+    #[inline(always)]
+    fn from_u64_with_reduction(value: u64) -> Self {
+        Self::from_u64_with_reduction_impl(value)
+    }
+}
+
+impl PrimeField for BabyBearField {
+    const CHAR_BITS: usize = Self::ORDER_BITS;
+    const CAPACITY_BITS: usize = Self::ORDER_BITS - 1;
+    const TWO_ADICITY: usize = Self::TWO_ADICITY;
+    const SHIFTS: &'static [Self] = &Self::compute_shifts();
+
+    #[inline(always)]
+    fn multiplicative_generator() -> Self {
+        Self::MULTIPLICATIVE_GROUP_GENERATOR
+    }
+
+    #[inline(always)]
+    fn radix_2_subgroup_generator() -> Self {
+        Self::RADIX_2_SUBGROUP_GENERATOR
+    }
+
+    #[inline(always)]
+    fn frobenius(&self, _power: usize) -> Self {
+        *self
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        Some(self.pow_u64((Self::ORDER - 2) as u64))
+    }
+
+    fn legendre(&self) -> super::LegendreSymbol {
+        // s = self^((modulus - 1) // 2)
+        let s = self.pow_u64((Self::CHAR - 1) / 2);
+        if s == Self::ZERO {
+            super::LegendreSymbol::Zero
+        } else if s == Self::ONE {
+            super::LegendreSymbol::QuadraticResidue
+        } else {
+            super::LegendreSymbol::QuadraticNonResidue
+        }
+    }
+}
+
+impl U64RawRepresentable for BabyBearField {
+    #[inline(always)]
+    fn as_raw_u64(self) -> u64 {
+        self.0 as u64
+    }
+
+    #[inline(always)]
+    fn from_raw_u64_unchecked(value: u64) -> Self {
+        Self::from_u64_with_reduction_impl(value)
+    }
+
+    #[inline(always)]
+    fn from_raw_u64_checked(value: u64) -> Option<Self> {
+        if value >= Self::ORDER as u64 {
+            None
+        } else {
+            Some(Self(value as u32))
+        }
+    }
+
+    #[inline(always)]
+    fn as_raw_u64_array<const N: usize>(input: [Self; N]) -> [u64; N] {
+        input.map(|el| el.0 as u64)
+    }
+}
+
+impl U64Representable for BabyBearField {
+    #[inline(always)]
+    fn as_u64(self) -> u64 {
+        self.0 as u64
+    }
+
+    #[inline(always)]
+    fn from_u64_unchecked(value: u64) -> Self {
+        Self::from_u64_with_reduction_impl(value)
+    }
+
+    #[inline(always)]
+    fn from_u64(value: u64) -> Option<Self> {
+        if value >= Self::ORDER as u64 {
+            None
+        } else {
+            Some(Self(value as u32))
+        }
+    }
+
+    #[inline(always)]
+    fn as_u64_array<const N: usize>(input: [Self; N]) -> [u64; N] {
+        input.map(|el| el.0 as u64)
+    }
+
+    #[inline(always)]
+    fn as_u64_reduced(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
+impl SmallFieldRepresentable for BabyBearField {
+    #[inline(always)]
+    fn from_u128_reduced(value: u128) -> Self {
+        Self((value % (Self::ORDER as u128)) as u32)
+    }
+}
+
+impl SmallField for BabyBearField {
+    const CHAR: u64 = Self::ORDER as u64;
+    // a * b + c
+    #[inline(always)]
+    fn fma(a: Self, b: Self, c: Self) -> Self {
+        Self::from_u64_with_reduction_impl((a.0 as u64) * (b.0 as u64) + (c.0 as u64))
+    }
+
+    const CAN_CAST_VECTOR_TO_U64_LE_VECTOR: bool = false;
+}
+
+crate::impl_std_ops_for_field!(BabyBearField);
+
+crate::impl_packed_16_for_small_field!(MixedBabyBear, BabyBearField, BabyBearField::reduce_u64x16);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::field::rand_from_rng;
+    use crate::field::{ExtensionField, FieldExtension};
+
+    type F = BabyBearField;
+
+    #[test]
+    fn test_generators() {
+        let multiplicative_generator = F::multiplicative_generator();
+        let pow = (F::CHAR - 1) >> F::TWO_ADICITY;
+        let pow = multiplicative_generator.pow_u64(pow);
+        assert_eq!(pow, F::radix_2_subgroup_generator());
+
+        let pow = F::radix_2_subgroup_generator().pow_u64(1u64 << (F::TWO_ADICITY - 1));
+        assert_eq!(pow, F::MINUS_ONE);
+
+        // non-residue used for extension
+        assert_eq!(
+            BabyBearExt2::non_residue().legendre(),
+            crate::field::LegendreSymbol::QuadraticNonResidue
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_against_reference() {
+        let mut rng = rand::thread_rng();
+        let order = F::CHAR as u128;
+
+        for _ in 0..1 << 16 {
+            let a: F = rand_from_rng(&mut rng);
+            let b: F = rand_from_rng(&mut rng);
+            let (a_u128, b_u128) = (a.as_u64() as u128, b.as_u64() as u128);
+
+            assert_eq!((a + b).as_u64() as u128, (a_u128 + b_u128) % order);
+            assert_eq!((a - b).as_u64() as u128, (a_u128 + order - b_u128) % order);
+            assert_eq!((a * b).as_u64() as u128, (a_u128 * b_u128) % order);
+            assert_eq!((-a).as_u64() as u128, (order - a_u128) % order);
+            assert_eq!(F::fma(a, b, a), a * b + a);
+
+            if let Some(a_inversed) = a.inverse() {
+                assert_eq!(a * a_inversed, F::ONE);
+            } else {
+                assert!(a.is_zero());
+            }
+        }
+
+        assert_eq!(F::from_u64_unchecked(u64::MAX).as_u64(), u64::MAX % F::CHAR);
+        assert_eq!(F::from_u64(F::CHAR), None);
+        for (i, shift) in F::SHIFTS.iter().enumerate() {
+            assert_eq!(*shift, F::from_u64_with_reduction(1u64 << i));
+        }
+    }
+
+    #[test]
+    fn test_extension() {
+        type Ext = ExtensionField<F, 2, BabyBearExt2>;
+
+        let mut rng = rand::thread_rng();
+        let a = Ext::from_coeff_in_base([rand_from_rng(&mut rng), rand_from_rng(&mut rng)]);
+        let b = Ext::from_coeff_in_base([rand_from_rng(&mut rng), rand_from_rng(&mut rng)]);
+
+        let mut ab = a;
+        ab.mul_assign(&b);
+        let mut ba = b;
+        ba.mul_assign(&a);
+        assert_eq!(ab, ba);
+
+        let mut product = b.inverse().unwrap();
+        product.mul_assign(&b);
+        assert_eq!(product, Ext::ONE);
+
+        // norm is multiplicative
+        let mut norm_product = BabyBearExt2::compute_norm(a.as_coeffs_in_base());
+        norm_product.mul_assign(&BabyBearExt2::compute_norm(b.as_coeffs_in_base()));
+        assert_eq!(
+            BabyBearExt2::compute_norm(ab.as_coeffs_in_base()),
+            norm_product
+        );
+
+        // generator is not in any maximal subgroup
+        let generator = Ext::multiplicative_generator();
+        let group_order = (F::CHAR as u128) * (F::CHAR as u128) - 1;
+        for factor in [2u128, 3, 5, 31, 32472031] {
+            let power = group_order / factor;
+            let power = [power as u64, (power >> 64) as u64];
+            assert_ne!(generator.pow(&power), Ext::ONE);
+        }
+    }
+
+    fn check_binomial_extension<const N: usize, E: FieldExtension<N, BaseField = F>>(
+        group_order_factors: &[u128],
+    ) where
+        ExtensionField<F, N, E>: PrimeField,
+    {
+        let mut rng = rand::thread_rng();
+        let random_element = |rng: &mut rand::rngs::ThreadRng| {
+            ExtensionField::<F, N, E>::from_coeff_in_base(std::array::from_fn(|_| {
+                rand_from_rng(rng)
+            }))
+        };
+        let a = random_element(&mut rng);
+        let b = random_element(&mut rng);
+
+        let mut ab = a;
+        ab.mul_assign(&b);
+        let mut ba = b;
+        ba.mul_assign(&a);
+        assert_eq!(ab, ba);
+
+        let mut a_squared = a;
+        a_squared.square();
+        let mut a_by_a = a;
+        a_by_a.mul_assign(&a);
+        assert_eq!(a_squared, a_by_a);
+
+        // Frobenius is p-th power
+        let a_to_p = a.pow_u64(F::CHAR);
+        assert_eq!(a.frobenius(1), a_to_p);
+        assert_eq!(a.frobenius(N), a);
+
+        let mut product = a.inverse().unwrap();
+        product.mul_assign(&a);
+        assert_eq!(product, ExtensionField::<F, N, E>::ONE);
+        assert!(ExtensionField::<F, N, E>::ZERO.inverse().is_none());
+
+        let mut norm_product = E::compute_norm(a.as_coeffs_in_base());
+        norm_product.mul_assign(&E::compute_norm(b.as_coeffs_in_base()));
+        assert_eq!(E::compute_norm(ab.as_coeffs_in_base()), norm_product);
+
+        let mut a_squared_legendre = a;
+        a_squared_legendre.square();
+        assert_eq!(
+            a_squared_legendre.legendre(),
+            crate::field::LegendreSymbol::QuadraticResidue
+        );
+
+        let generator = ExtensionField::<F, N, E>::multiplicative_generator();
+        let group_order = (F::CHAR as u128).pow(N as u32) - 1;
+        for factor in group_order_factors.iter() {
+            let power = group_order / factor;
+            let power = [power as u64, (power >> 64) as u64];
+            assert_ne!(generator.pow(&power), ExtensionField::<F, N, E>::ONE);
+        }
+    }
+
+    #[test]
+    fn test_extensions_of_higher_degree() {
+        check_binomial_extension::<3, BabyBearExt3>(&[2, 3, 5, 7, 3939008426310247]);
+        check_binomial_extension::<4, BabyBearExt4>(&[
+            2,
+            3,
+            5,
+            31,
+            97,
+            12241,
+            32472031,
+            1706804017873,
+        ]);
+    }
+
+    #[test]
+    fn test_packed_arithmetic() {
+        crate::field::traits::field_like::check_packed_arithmetic::<F, MixedBabyBear>();
+    }
+
+    #[test]
+    fn test_fft_roundtrip() {
+        use crate::field::traits::field_like::{
+            fft_natural_to_bitreversed, ifft_natural_to_natural,
+        };
+        let mut rng = rand::thread_rng();
+        const POLY_SIZE: usize = 1 << 12;
+
+        let monomials: Vec<F> = (0..POLY_SIZE).map(|_| rand_from_rng(&mut rng)).collect();
+        let mut values = monomials.clone();
+        fft_natural_to_bitreversed(&mut values, F::ONE);
+
+        // spot check against direct evaluation
+        let omega =
+            crate::cs::implementations::utils::domain_generator_for_size::<F>(POLY_SIZE as u64);
+        for idx in [0usize, 1, 17, POLY_SIZE - 1] {
+            let point = omega.pow_u64(idx as u64);
+            let mut expected = F::ZERO;
+            for coeff in monomials.iter().rev() {
+                expected.mul_assign(&point).add_assign(coeff);
+            }
+            let bitreversed_idx = idx.reverse_bits() >> (usize::BITS - POLY_SIZE.trailing_zeros());
+            assert_eq!(values[bitreversed_idx], expected);
+        }
+
+        crate::fft::bitreverse_enumeration_inplace(&mut values);
+        ifft_natural_to_natural(&mut values, F::ONE);
+        assert_eq!(values, monomials);
+    }
+
+    #[test]
+    fn test_prove_and_verify() {
+        prove_and_verify::<2, BabyBearExt2>();
+    }
+
+    #[test]
+    fn test_prove_and_verify_in_quartic_extension() {
+        prove_and_verify::<4, BabyBearExt4>();
+    }
+
+    fn prove_and_verify<const DEGREE: usize, EXT: FieldExtension<DEGREE, BaseField = F>>() {
+        use crate::algebraic_props::round_function::AbsorptionModeOverwrite;
+        use crate::algebraic_props::sponge::BabyBearPoseidon2Sponge;
+        use crate::config::DevCSConfig;
+        use crate::cs::cs_builder::*;
+        use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+        use crate::cs::cs_builder_verifier::CsVerifierBuilder;
+        use crate::cs::gates::*;
+        use crate::cs::implementations::pow::NoPow;
+        use crate::cs::implementations::prover::ProofConfig;
+        use crate::cs::implementations::transcript::BabyBearPoseidon2Transcript;
+        use crate::cs::traits::cs::ConstraintSystem;
+        use crate::cs::traits::gate::GatePlacementStrategy;
+        use crate::cs::{CSGeometry, GateConfigurationHolder, StaticToolboxHolder};
+        use crate::worker::Worker;
+
+        type TR = BabyBearPoseidon2Transcript;
+        type H = BabyBearPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+        const GEOMETRY: CSGeometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 4,
+        };
+
+        fn configure<
+            T: CsBuilderImpl<F, T>,
+            GC: GateConfigurationHolder<F>,
+            TB: StaticToolboxHolder,
+        >(
+            builder: CsBuilder<T, F, GC, TB>,
+        ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+            let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = PublicInputGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = NopGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+
+            builder
+        }
+
+        let worker = Worker::new();
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(GEOMETRY, 1 << 12, 1 << 10);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = configure(builder);
+        let mut cs = builder.build(());
+
+        // iterate x -> x^2 + x
+        let mut x = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(3));
+        for _ in 0..100 {
+            x = FmaGateInBaseFieldWithoutConstant::compute_fma(&mut cs, F::ONE, (x, x), F::ONE, x);
+        }
+        PublicInputGate::new(x).add_to_cs(&mut cs);
+
+        cs.pad_and_shrink();
+        let cs = cs.into_assembly();
+        let proof_config = ProofConfig {
+            pow_bits: 0,
+            ..ProofConfig::default()
+        };
+        let (proof, vk) = cs.prove_one_shot::<DEGREE, EXT, TR, H, NoPow>(&worker, proof_config, ());
+
+        let builder_impl = CsVerifierBuilder::<F, DEGREE, EXT>::new_from_parameters(GEOMETRY);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = configure(builder);
+        let verifier = builder.build(());
+        assert!(verifier.verify::<H, TR, NoPow>((), &vk, &proof));
+    }
+}
@@ -0,0 +1,523 @@
+// Montgomery batch inversion: for a set of a, b, c, d we compute running products
+// [1, a, ab, abc] and abcd, invert the last one, and then walk back multiplying
+// by a running inverse (abcd)^-1 -> (abc)^-1 -> ..., so N inversions cost
+// a single inversion and 3 * (N - 1) multiplications.
+//
+// All the functions below panic if any of the inputs is zero
+
+use crate::cs::traits::GoodAllocator;
+use crate::field::traits::field_like::{
+    mul_assign_vectorized_in_extension, PrimeFieldLikeVectorized,
+};
+use crate::field::{ExtensionField, FieldExtension, PrimeField, SmallField};
+use crate::worker::Worker;
+
+pub fn batch_inverse<F: PrimeField, A: GoodAllocator>(input: &[F], into: &mut Vec<F, A>) {
+    debug_assert!(into.is_empty());
+
+    if input.is_empty() {
+        return;
+    }
+
+    // we do Montgomery batch inversion trick, and reuse a buffer
+    let one = F::ONE;
+    into.push(one);
+    let mut accumulator = input[0];
+    for el in input[1..].iter() {
+        into.push(accumulator);
+        accumulator.mul_assign(el);
+    }
+
+    // for a set of a, b, c, d we have
+    // - input = [1, a, ab, abc],
+    // - accumulator = abcd
+
+    let mut grand_inverse = accumulator
+        .inverse()
+        .expect("batch inverse must be called on sets without zeroes");
+
+    // grand_inverse = a^-1 b^-1 c^-1 d^-1
+
+    for (dst, original) in into.iter_mut().rev().zip(input.iter().rev()) {
+        dst.mul_assign(&grand_inverse); // e.g it's now d^-1
+        grand_inverse.mul_assign(original); // e.g. it's now a^-1 b^-1 c^-1
+    }
+
+    debug_assert_eq!(into.len(), input.len());
+}
+
+pub fn batch_inverse_inplace<F: PrimeField, A: GoodAllocator>(input: &mut [F]) {
+    if input.is_empty() {
+        return;
+    }
+
+    let mut into = Vec::with_capacity_in(input.len(), A::default());
+
+    // we do Montgomery batch inversion trick, and reuse a buffer
+    let one = F::ONE;
+    into.push(one);
+    let mut accumulator = input[0];
+    for el in input[1..].iter() {
+        into.push(accumulator);
+        accumulator.mul_assign(el);
+    }
+
+    // for a set of a, b, c, d we have
+    // - input = [1, a, ab, abc],
+    // - accumulator = abcd
+
+    let mut grand_inverse = accumulator
+        .inverse()
+        .expect("batch inverse must be called on sets without zeroes");
+
+    // grand_inverse = a^-1 b^-1 c^-1 d^-1
+
+    for (tmp, original) in into.into_iter().rev().zip(input.iter_mut().rev()) {
+        let mut tmp = tmp; // abc
+        tmp.mul_assign(&grand_inverse); // d^-1
+        grand_inverse.mul_assign(original); // e.g. it's now a^-1 b^-1 c^-1
+
+        *original = tmp;
+    }
+}
+
+/// Every worker inverts its own chunk, so it costs one inversion per chunk
+pub fn batch_inverse_parallel<F: PrimeField, A: GoodAllocator>(
+    input: &[F],
+    worker: &Worker,
+) -> Vec<F, A> {
+    let mut result = Vec::with_capacity_in(input.len(), A::default());
+    result.extend_from_slice(input);
+    batch_inverse_inplace_parallel::<F, A>(&mut result, worker);
+
+    result
+}
+
+pub fn batch_inverse_inplace_parallel<F: PrimeField, A: GoodAllocator>(
+    input: &mut [F],
+    worker: &Worker,
+) {
+    worker.scope(input.len(), |scope, chunk_size| {
+        for dst in input.chunks_mut(chunk_size) {
+            scope.spawn(move |_| {
+                batch_inverse_inplace::<F, A>(dst);
+            });
+        }
+    });
+}
+
+// Variants below work over extension elements that are stored as separate columns of coefficients
+
+pub fn batch_inverse_in_extension<
+    F: SmallField,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+    A: GoodAllocator,
+>(
+    input: [&[F]; DEGREE],
+    mut into: [&mut Vec<F, A>; DEGREE],
+) {
+    let len = input[0].len();
+    for (src, dst) in input.iter().zip(into.iter()) {
+        assert_eq!(src.len(), len);
+        assert!(dst.is_empty());
+    }
+
+    if len == 0 {
+        return;
+    }
+
+    let get = |columns: &[&[F]; DEGREE], idx: usize| {
+        ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(std::array::from_fn(|i| {
+            columns[i][idx]
+        }))
+    };
+
+    // we do Montgomery batch inversion trick, and reuse a buffer
+    let one = {
+        use crate::field::traits::field::Field;
+
+        ExtensionField::<F, DEGREE, EXT>::ONE
+    };
+    for (dst, el) in into.iter_mut().zip(one.coeffs.iter()) {
+        dst.push(*el);
+    }
+    let mut accumulator = get(&input, 0);
+    for idx in 1..len {
+        for (dst, el) in into.iter_mut().zip(accumulator.coeffs.iter()) {
+            dst.push(*el);
+        }
+
+        let el = get(&input, idx);
+        crate::field::Field::mul_assign(&mut accumulator, &el);
+    }
+
+    // for a set of a, b, c, d we have
+    // - input = [1, a, ab, abc],
+    // - accumulator = abcd
+
+    let mut grand_inverse = accumulator
+        .inverse()
+        .expect("batch inverse must be called on sets without zeroes");
+
+    // grand_inverse = a^-1 b^-1 c^-1 d^-1
+
+    for idx in (0..len).rev() {
+        let mut tmp =
+            ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(std::array::from_fn(|i| {
+                into[i][idx]
+            })); // abc
+        crate::field::Field::mul_assign(&mut tmp, &grand_inverse); // d^-1
+
+        // write back
+        for (dst, el) in into.iter_mut().zip(tmp.coeffs.iter()) {
+            dst[idx] = *el;
+        }
+
+        let original = get(&input, idx);
+        crate::field::Field::mul_assign(&mut grand_inverse, &original); // e.g. it's now a^-1 b^-1 c^-1
+    }
+}
+
+pub fn batch_inverse_inplace_in_extension<
+    F: SmallField,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+    A: GoodAllocator,
+>(
+    mut input: [&mut [F]; DEGREE],
+) {
+    let len = input[0].len();
+    for el in input.iter() {
+        assert_eq!(el.len(), len);
+    }
+
+    if len == 0 {
+        return;
+    }
+
+    let get = |columns: &[&mut [F]; DEGREE], idx: usize| {
+        ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(std::array::from_fn(|i| {
+            columns[i][idx]
+        }))
+    };
+
+    let mut into = Vec::with_capacity_in(len, A::default());
+
+    // we do Montgomery batch inversion trick, and reuse a buffer
+    let one = {
+        use crate::field::traits::field::Field;
+
+        ExtensionField::<F, DEGREE, EXT>::ONE
+    };
+    into.push(one);
+    let mut accumulator = get(&input, 0);
+    for idx in 1..len {
+        into.push(accumulator);
+        let el = get(&input, idx);
+        crate::field::Field::mul_assign(&mut accumulator, &el);
+    }
+
+    // for a set of a, b, c, d we have
+    // - input = [1, a, ab, abc],
+    // - accumulator = abcd
+
+    let mut grand_inverse = accumulator
+        .inverse()
+        .expect("batch inverse must be called on sets without zeroes");
+
+    // grand_inverse = a^-1 b^-1 c^-1 d^-1
+
+    for (idx, tmp) in into.into_iter().enumerate().rev() {
+        let mut tmp = tmp; // abc
+        crate::field::Field::mul_assign(&mut tmp, &grand_inverse); // d^-1
+        let original = get(&input, idx);
+        crate::field::Field::mul_assign(&mut grand_inverse, &original); // e.g. it's now a^-1 b^-1 c^-1
+
+        for (dst, el) in input.iter_mut().zip(tmp.coeffs.iter()) {
+            dst[idx] = *el;
+        }
+    }
+}
+
+// Splits every column into the same chunks, so every worker gets a set of columns of the same length
+pub(crate) fn chunk_columns_mut<'a, T, const N: usize>(
+    columns: [&'a mut [T]; N],
+    chunk_size: usize,
+) -> Vec<[&'a mut [T]; N]> {
+    let num_chunks = (columns[0].len() + chunk_size - 1) / chunk_size;
+    let mut chunks = columns.map(|el| el.chunks_mut(chunk_size));
+
+    (0..num_chunks)
+        .map(|_| {
+            std::array::from_fn(|i| chunks[i].next().expect("columns must have the same length"))
+        })
+        .collect()
+}
+
+pub fn batch_inverse_inplace_parallel_in_extension<
+    F: SmallField,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+    A: GoodAllocator,
+>(
+    input: [&mut [F]; DEGREE],
+    worker: &Worker,
+) {
+    worker.scope(input[0].len(), |scope, chunk_size| {
+        for dst in chunk_columns_mut(input, chunk_size) {
+            scope.spawn(move |_| {
+                batch_inverse_inplace_in_extension::<F, DEGREE, EXT, A>(dst);
+            });
+        }
+    });
+}
+
+// Variants below work over packed elements. Every lane runs its own independent chain
+// of multiplications, that removes the data dependency between consecutive multiplications
+// of the scalar version. Inversion of the packed accumulator is lane-wise for e.g. `MixedGL`,
+// so instead we batch invert its lanes, and it's still a single inversion per chunk
+
+pub fn batch_inverse_vectorized_inplace<P: PrimeFieldLikeVectorized, A: GoodAllocator>(
+    input: &mut [P],
+    ctx: &mut P::Context,
+) where
+    P::Base: PrimeField,
+{
+    if input.is_empty() {
+        return;
+    }
+
+    let mut into = Vec::with_capacity_in(input.len(), A::default());
+
+    into.push(P::one(ctx));
+    let mut accumulator = input[0];
+    for el in input[1..].iter() {
+        into.push(accumulator);
+        accumulator.mul_assign(el, ctx);
+    }
+
+    let mut grand_inverse = accumulator;
+    batch_inverse_inplace::<P::Base, A>(P::slice_into_base_slice_mut(std::slice::from_mut(
+        &mut grand_inverse,
+    )));
+
+    for (tmp, original) in into.into_iter().rev().zip(input.iter_mut().rev()) {
+        let mut tmp = tmp;
+        tmp.mul_assign(&grand_inverse, ctx);
+        grand_inverse.mul_assign(original, ctx);
+
+        *original = tmp;
+    }
+}
+
+pub fn batch_inverse_vectorized_inplace_parallel<P: PrimeFieldLikeVectorized, A: GoodAllocator>(
+    input: &mut [P],
+    worker: &Worker,
+) where
+    P::Base: PrimeField,
+{
+    worker.scope(input.len(), |scope, chunk_size| {
+        for dst in input.chunks_mut(chunk_size) {
+            scope.spawn(move |_| {
+                batch_inverse_vectorized_inplace::<P, A>(dst, &mut ());
+            });
+        }
+    });
+}
+
+pub fn batch_inverse_vectorized_inplace_in_extension<
+    F: SmallField,
+    P: PrimeFieldLikeVectorized<Base = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+    A: GoodAllocator,
+>(
+    mut input: [&mut [P]; DEGREE],
+    ctx: &mut P::Context,
+) {
+    let len = input[0].len();
+    for el in input.iter() {
+        assert_eq!(el.len(), len);
+    }
+
+    if len == 0 {
+        return;
+    }
+
+    let mut into = Vec::with_capacity_in(len, A::default());
+
+    let zero = P::zero(ctx);
+    let mut one = [zero; DEGREE];
+    one[0] = P::one(ctx);
+    into.push(one);
+    let mut accumulator: [P; DEGREE] = std::array::from_fn(|i| input[i][0]);
+    for idx in 1..len {
+        into.push(accumulator);
+        let el: [P; DEGREE] = std::array::from_fn(|i| input[i][idx]);
+        mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(&mut accumulator, &el, ctx);
+    }
+
+    let mut grand_inverse = accumulator;
+    let mut it = grand_inverse.iter_mut();
+    batch_inverse_inplace_in_extension::<F, DEGREE, EXT, A>(std::array::from_fn(|_| {
+        P::slice_into_base_slice_mut(std::slice::from_mut(it.next().unwrap()))
+    }));
+
+    for (idx, tmp) in into.into_iter().enumerate().rev() {
+        let mut tmp = tmp;
+        mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(&mut tmp, &grand_inverse, ctx);
+        let original: [P; DEGREE] = std::array::from_fn(|i| input[i][idx]);
+        mul_assign_vectorized_in_extension::<F, P, DEGREE, EXT>(&mut grand_inverse, &original, ctx);
+
+        for (dst, el) in input.iter_mut().zip(tmp.into_iter()) {
+            dst[idx] = el;
+        }
+    }
+}
+
+pub fn batch_inverse_vectorized_inplace_parallel_in_extension<
+    F: SmallField,
+    P: PrimeFieldLikeVectorized<Base = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+    A: GoodAllocator,
+>(
+    input: [&mut [P]; DEGREE],
+    worker: &Worker,
+) {
+    worker.scope(input[0].len(), |scope, chunk_size| {
+        for dst in chunk_columns_mut(input, chunk_size) {
+            scope.spawn(move |_| {
+                batch_inverse_vectorized_inplace_in_extension::<F, P, DEGREE, EXT, A>(dst, &mut ());
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::field::goldilocks::{GoldilocksExt2, GoldilocksField, MixedGL};
+    use crate::field::rand_from_rng;
+    use crate::field::traits::field::Field;
+    use std::alloc::Global;
+
+    type F = GoldilocksField;
+
+    #[test]
+    fn test_batch_inverse() {
+        let input = vec![
+            F::ONE,
+            F::from_u64_with_reduction(123),
+            F::from_u64_with_reduction(456),
+            F::from_u64_with_reduction(789),
+            F::from_u64_with_reduction(123),
+            F::from_u64_with_reduction(456),
+            F::from_u64_with_reduction(789),
+            F::from_u64_with_reduction(123),
+            F::from_u64_with_reduction(456),
+            F::from_u64_with_reduction(789),
+            F::TWO,
+        ];
+
+        let mut inv_dst = vec![];
+        batch_inverse(&input, &mut inv_dst);
+
+        let reference: Vec<_> = input.into_iter().map(|el| el.inverse().unwrap()).collect();
+        assert_eq!(inv_dst, reference);
+    }
+
+    #[test]
+    fn test_batch_inverse_in_extension() {
+        let input_c0 = vec![
+            F::ONE,
+            F::from_u64_with_reduction(123),
+            F::from_u64_with_reduction(456),
+            F::from_u64_with_reduction(789),
+            F::from_u64_with_reduction(123),
+            F::from_u64_with_reduction(456),
+            F::from_u64_with_reduction(789),
+            F::from_u64_with_reduction(123),
+            F::from_u64_with_reduction(456),
+            F::from_u64_with_reduction(789),
+            F::TWO,
+        ];
+
+        let mut input_c1 = input_c0.clone();
+        input_c1.reverse();
+
+        let mut inv_dst_c0 = vec![];
+        let mut inv_dst_c1 = vec![];
+        batch_inverse_in_extension::<F, 2, GoldilocksExt2, Global>(
+            [&input_c0, &input_c1],
+            [&mut inv_dst_c0, &mut inv_dst_c1],
+        );
+
+        let mut reference_c0 = vec![];
+        let mut reference_c1 = vec![];
+
+        for (c0, c1) in input_c0.into_iter().zip(input_c1.into_iter()) {
+            let el = ExtensionField::<F, 2, GoldilocksExt2>::from_coeff_in_base([c0, c1]);
+            let [c0, c1] = el.inverse().unwrap().coeffs;
+            reference_c0.push(c0);
+            reference_c1.push(c1);
+        }
+
+        assert_eq!(inv_dst_c0, reference_c0);
+        assert_eq!(inv_dst_c1, reference_c1);
+    }
+
+    #[test]
+    fn test_parallel_and_vectorized_batch_inverse() {
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new();
+
+        // not a multiple of the number of threads or of the packed width
+        let size = 16 * 1000 + 16;
+        let input_c0: Vec<F> = (0..size).map(|_| rand_from_rng(&mut rng)).collect();
+        let input_c1: Vec<F> = (0..size).map(|_| rand_from_rng(&mut rng)).collect();
+
+        let mut reference = vec![];
+        batch_inverse(&input_c0, &mut reference);
+
+        let parallel = batch_inverse_parallel::<F, Global>(&input_c0, &worker);
+        assert_eq!(parallel, reference);
+
+        let mut vectorized = MixedGL::vec_from_base_vec(input_c0.clone());
+        batch_inverse_vectorized_inplace::<MixedGL, Global>(&mut vectorized, &mut ());
+        assert_eq!(MixedGL::vec_into_base_vec(vectorized), reference);
+
+        let mut vectorized = MixedGL::vec_from_base_vec(input_c0.clone());
+        batch_inverse_vectorized_inplace_parallel::<MixedGL, Global>(&mut vectorized, &worker);
+        assert_eq!(MixedGL::vec_into_base_vec(vectorized), reference);
+
+        let mut reference_c0 = vec![];
+        let mut reference_c1 = vec![];
+        batch_inverse_in_extension::<F, 2, GoldilocksExt2, Global>(
+            [&input_c0, &input_c1],
+            [&mut reference_c0, &mut reference_c1],
+        );
+
+        let mut parallel_c0 = input_c0.clone();
+        let mut parallel_c1 = input_c1.clone();
+        batch_inverse_inplace_parallel_in_extension::<F, 2, GoldilocksExt2, Global>(
+            [&mut parallel_c0, &mut parallel_c1],
+            &worker,
+        );
+        assert_eq!(parallel_c0, reference_c0);
+        assert_eq!(parallel_c1, reference_c1);
+
+        let mut vectorized_c0 = MixedGL::vec_from_base_vec(input_c0);
+        let mut vectorized_c1 = MixedGL::vec_from_base_vec(input_c1);
+        batch_inverse_vectorized_inplace_parallel_in_extension::<
+            F,
+            MixedGL,
+            2,
+            GoldilocksExt2,
+            Global,
+        >([&mut vectorized_c0, &mut vectorized_c1], &worker);
+        assert_eq!(MixedGL::vec_into_base_vec(vectorized_c0), reference_c0);
+        assert_eq!(MixedGL::vec_into_base_vec(vectorized_c1), reference_c1);
+    }
+}
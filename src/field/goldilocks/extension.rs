@@ -1,4 +1,5 @@
 use super::*;
+use crate::field::{ExtensionField, FieldExtension};
 use derivative::*;
 
 #[derive(Derivative, serde::Serialize, serde::Deserialize)]
@@ -13,6 +14,8 @@ impl std::fmt::Display for GoldilocksExt2 {
 
 impl GoldilocksExt2 {
     const NON_RESIDUE: GoldilocksField = GoldilocksField(7u64);
+    // 11 + u generates the full multiplicative group of order p^2 - 1
+    const GENERATOR_COEFFS: [GoldilocksField; 2] = [GoldilocksField(11u64), GoldilocksField(1u64)];
 }
 
 impl crate::field::FieldExtension<2> for GoldilocksExt2 {
@@ -25,12 +28,19 @@ impl crate::field::FieldExtension<2> for GoldilocksExt2 {
         Self::NON_RESIDUE
     }
 
-    fn compute_norm(_el: &[Self::BaseField; 2]) -> Self::BaseField {
-        todo!()
+    fn compute_norm(el: &[Self::BaseField; 2]) -> Self::BaseField {
+        // c0^2 - non_residue * c1^2
+        let mut result = el[0];
+        result.square();
+        let mut t = el[1];
+        t.square().mul_assign(&Self::NON_RESIDUE);
+        result.sub_assign(&t);
+
+        result
     }
 
     fn multiplicative_generator_coeffs() -> [Self::BaseField; 2] {
-        todo!()
+        Self::GENERATOR_COEFFS
     }
 
     #[inline(always)]
@@ -45,16 +55,16 @@ impl crate::field::traits::field_like::PrimeFieldLikeExtension<2> for Goldilocks
     type BaseField = GoldilocksField;
 
     fn compute_norm(
-        _el: &[Self::BaseField; 2],
+        el: &[Self::BaseField; 2],
         _ctx: &mut <Self::BaseField as crate::field::traits::field_like::PrimeFieldLike>::Context,
     ) -> Self::BaseField {
-        todo!()
+        <Self as crate::field::FieldExtension<2>>::compute_norm(el)
     }
 
     fn multiplicative_generator_coeffs(
         _ctx: &mut <Self::BaseField as crate::field::traits::field_like::PrimeFieldLike>::Context,
     ) -> [Self::BaseField; 2] {
-        todo!()
+        Self::GENERATOR_COEFFS
     }
 
     fn mul_by_non_residue(
@@ -64,3 +74,139 @@ impl crate::field::traits::field_like::PrimeFieldLikeExtension<2> for Goldilocks
         el.mul_assign(&Self::NON_RESIDUE);
     }
 }
+
+impl crate::field::SqrtField for ExtensionField<GoldilocksField, 2, GoldilocksExt2> {
+    fn sqrt(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(*self);
+        }
+
+        let [a0, a1] = self.coeffs;
+        if a1.is_zero() {
+            // either root is in the base field, or a0 = 7 * c^2 and root is c * u
+            if let Some(root) = a0.sqrt() {
+                return Some(Self::from_coeff_in_base([root, GoldilocksField::ZERO]));
+            }
+            let mut t = a0;
+            t.mul_assign(&GoldilocksExt2::NON_RESIDUE.inverse().unwrap());
+            let root = t.sqrt().expect("ratio of non-residues is a residue");
+
+            return Some(Self::from_coeff_in_base([GoldilocksField::ZERO, root]));
+        }
+
+        // (x0 + x1 * u)^2 = a0 + a1 * u gives x0^2 = (a0 +- sqrt(norm(a))) / 2 and x1 = a1 / (2 * x0),
+        // and exactly one of two candidates for x0^2 is a residue
+        let mut norm = a0;
+        norm.square();
+        let mut t = a1;
+        t.square();
+        GoldilocksExt2::mul_by_non_residue(&mut t);
+        norm.sub_assign(&t);
+        let alpha = norm.sqrt()?;
+
+        let two_inversed = GoldilocksField::TWO.inverse().unwrap();
+        let mut delta = a0;
+        delta.add_assign(&alpha).mul_assign(&two_inversed);
+        if delta.legendre() == crate::field::LegendreSymbol::QuadraticNonResidue {
+            delta = a0;
+            delta.sub_assign(&alpha).mul_assign(&two_inversed);
+        }
+
+        let x0 = delta.sqrt()?;
+        let mut x1 = x0;
+        x1.double();
+        let mut x1 = x1.inverse()?;
+        x1.mul_assign(&a1);
+
+        let root = Self::from_coeff_in_base([x0, x1]);
+
+        debug_assert!({
+            let mut tmp = root;
+            tmp.square();
+
+            tmp == *self
+        });
+
+        Some(root)
+    }
+}
+
+impl crate::field::CubeRootField for ExtensionField<GoldilocksField, 2, GoldilocksExt2> {
+    fn cube_root(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(*self);
+        }
+
+        // p^2 - 1 = 3 * M, where M is coprime with 3, so an element is a cube iff a^M = 1,
+        // and then a^(3^-1 mod M) is one of its roots
+        const M: [u64; 2] = [0xaaaaaaaa00000000, 0x55555554aaaaaaab];
+        const INV_3_MOD_M: [u64; 2] = [0x38e38e38aaaaaaab, 0x1c71c71c38e38e39];
+
+        if self.pow(&M) != Self::ONE {
+            return None;
+        }
+
+        let root = self.pow(&INV_3_MOD_M);
+
+        debug_assert!({
+            let mut tmp = root;
+            tmp.square().mul_assign(&root);
+
+            tmp == *self
+        });
+
+        Some(root)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::field::{rand_from_rng, CubeRootField, SqrtField};
+
+    type Ext = ExtensionField<GoldilocksField, 2, GoldilocksExt2>;
+
+    #[test]
+    fn test_roots_in_extension() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..1000 {
+            let a = Ext::from_coeff_in_base([rand_from_rng(&mut rng), rand_from_rng(&mut rng)]);
+
+            let mut a_squared = a;
+            a_squared.square();
+            let mut minus_a = a;
+            minus_a.negate();
+            let root = a_squared.sqrt().unwrap();
+            assert!(root == a || root == minus_a);
+
+            let mut a_cubed = a_squared;
+            a_cubed.mul_assign(&a);
+            let root = a_cubed.cube_root().unwrap();
+            let mut root_cubed = root;
+            root_cubed.square().mul_assign(&root);
+            assert_eq!(root_cubed, a_cubed);
+        }
+
+        // squares of purely "imaginary" elements and base field non-residues
+        let c = GoldilocksField::from_u64_with_reduction(12345);
+        let mut a = Ext::from_coeff_in_base([GoldilocksField::ZERO, c]);
+        a.square();
+        let root = a.sqrt().unwrap();
+        let mut root_squared = root;
+        root_squared.square();
+        assert_eq!(root_squared, a);
+
+        let non_residue =
+            Ext::from_coeff_in_base([GoldilocksExt2::NON_RESIDUE, GoldilocksField::ZERO]);
+        let root = non_residue.sqrt().unwrap();
+        let mut root_squared = root;
+        root_squared.square();
+        assert_eq!(root_squared, non_residue);
+
+        // generator of the multiplicative group is neither a square nor a cube
+        let generator = Ext::multiplicative_generator();
+        assert!(generator.sqrt().is_none());
+        assert!(generator.cube_root().is_none());
+    }
+}
@@ -42,7 +42,7 @@ pub use x86_64_asm_impl::*;
 pub use self::extension::GoldilocksExt2;
 use self::inversion::try_inverse_u64;
 
-use super::SqrtField;
+use super::{CubeRootField, SqrtField};
 
 const EPSILON: u64 = (1 << 32) - 1;
 
@@ -380,6 +380,34 @@ impl SqrtField for GoldilocksField {
     }
 }
 
+impl CubeRootField for GoldilocksField {
+    fn cube_root(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(*self);
+        }
+
+        // p - 1 = 3 * M, where M is coprime with 3, so an element is a cube iff a^M = 1,
+        // and then a^(3^-1 mod M) is one of its roots
+        const M: u64 = (GoldilocksField::ORDER - 1) / 3;
+        const INV_3_MOD_M: u64 = 0x38e38e38aaaaaaab;
+
+        if self.pow_u64(M) != GoldilocksField::ONE {
+            return None;
+        }
+
+        let root = self.pow_u64(INV_3_MOD_M);
+
+        debug_assert!({
+            let mut tmp = root;
+            tmp.square().mul_assign(&root);
+
+            tmp == *self
+        });
+
+        Some(root)
+    }
+}
+
 impl PrimeField for GoldilocksField {
     const CHAR_BITS: usize = Self::ORDER_BITS;
     const CAPACITY_BITS: usize = Self::ORDER_BITS - 1;
@@ -635,4 +663,45 @@ mod test {
         let x = GoldilocksField::TWO.sqrt();
         dbg!(&x);
     }
+
+    #[test]
+    fn test_roots() {
+        use crate::field::rand_from_rng;
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..1000 {
+            let a: GoldilocksField = rand_from_rng(&mut rng);
+
+            let mut a_squared = a;
+            a_squared.square();
+            let root = a_squared.sqrt().unwrap();
+            assert!(root == a || root == -a);
+
+            let mut a_cubed = a_squared;
+            a_cubed.mul_assign(&a);
+            let root = a_cubed.cube_root().unwrap();
+            let mut root_cubed = root;
+            root_cubed.square().mul_assign(&root);
+            assert_eq!(root_cubed, a_cubed);
+
+            // generator is neither square nor cube, so only one of (a, g * a) can be a square,
+            // and only one of (a, g * a, g^2 * a) can be a cube
+            let mut scaled = a;
+            scaled.mul_assign(&GoldilocksField::multiplicative_generator());
+            assert!(a.sqrt().is_some() ^ scaled.sqrt().is_some());
+            let mut scaled_twice = scaled;
+            scaled_twice.mul_assign(&GoldilocksField::multiplicative_generator());
+            let num_cubes = [a, scaled, scaled_twice]
+                .iter()
+                .filter(|el| el.cube_root().is_some())
+                .count();
+            assert_eq!(num_cubes, 1);
+        }
+
+        assert_eq!(GoldilocksField::ZERO.sqrt(), Some(GoldilocksField::ZERO));
+        assert_eq!(
+            GoldilocksField::ZERO.cube_root(),
+            Some(GoldilocksField::ZERO)
+        );
+    }
 }
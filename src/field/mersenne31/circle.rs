@@ -0,0 +1,224 @@
+use super::*;
+
+// Points of the circle x^2 + y^2 = 1 over Mersenne31 form a cyclic group of order p + 1 = 2^31
+// under (x0, y0) * (x1, y1) = (x0 * x1 - y0 * y1, x0 * y1 + y0 * x1), that is multiplication
+// of unit norm elements of `Mersenne31Complex`. Its subgroups and their cosets are used as
+// power of two sized evaluation domains instead of subgroups of the multiplicative group
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct CirclePoint {
+    pub x: Mersenne31Field,
+    pub y: Mersenne31Field,
+}
+
+impl CirclePoint {
+    pub const LOG_ORDER: usize = 31;
+
+    pub const IDENTITY: Self = Self {
+        x: Mersenne31Field::ONE,
+        y: Mersenne31Field::ZERO,
+    };
+
+    // generator of the full circle group
+    pub const GENERATOR: Self = Self {
+        x: Mersenne31Field(2),
+        y: Mersenne31Field(1268011823),
+    };
+
+    pub fn is_on_circle(&self) -> bool {
+        let mut lhs = self.x;
+        lhs.square();
+        let mut y_squared = self.y;
+        y_squared.square();
+        lhs.add_assign(&y_squared);
+
+        lhs == Mersenne31Field::ONE
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut x = self.x;
+        x.mul_assign(&other.x);
+        let mut t = self.y;
+        t.mul_assign(&other.y);
+        x.sub_assign(&t);
+
+        let mut y = self.x;
+        y.mul_assign(&other.y);
+        let mut t = self.y;
+        t.mul_assign(&other.x);
+        y.add_assign(&t);
+
+        Self { x, y }
+    }
+
+    // squaring map (x, y) -> (2 * x^2 - 1, 2 * x * y), that halves the size of any subgroup
+    pub fn double(&self) -> Self {
+        let mut x = self.x;
+        x.square().double().sub_assign(&Mersenne31Field::ONE);
+        let mut y = self.x;
+        y.mul_assign(&self.y).double();
+
+        Self { x, y }
+    }
+
+    pub fn repeated_double(&self, num_times: usize) -> Self {
+        let mut result = *self;
+        for _ in 0..num_times {
+            result = result.double();
+        }
+
+        result
+    }
+
+    // inverse in the group
+    pub fn conjugate(&self) -> Self {
+        let mut y = self.y;
+        y.negate();
+
+        Self { x: self.x, y }
+    }
+
+    // multiplication by the unique element of order 2, that is (-1, 0)
+    pub fn antipode(&self) -> Self {
+        let mut x = self.x;
+        x.negate();
+        let mut y = self.y;
+        y.negate();
+
+        Self { x, y }
+    }
+
+    pub fn pow(&self, mut exp: u64) -> Self {
+        let mut result = Self::IDENTITY;
+        let mut base = *self;
+        while exp != 0 {
+            if exp & 1 == 1 {
+                result = result.mul(&base);
+            }
+            base = base.double();
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    pub fn subgroup_generator(log_size: usize) -> Self {
+        assert!(log_size <= Self::LOG_ORDER);
+
+        Self::GENERATOR.repeated_double(Self::LOG_ORDER - log_size)
+    }
+}
+
+/// Coset `initial * <step>` of the circle subgroup of size `2^log_size`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CircleCoset {
+    pub initial: CirclePoint,
+    pub step: CirclePoint,
+    pub log_size: usize,
+}
+
+impl CircleCoset {
+    pub fn new(initial: CirclePoint, log_size: usize) -> Self {
+        assert!(initial.is_on_circle());
+
+        Self {
+            initial,
+            step: CirclePoint::subgroup_generator(log_size),
+            log_size,
+        }
+    }
+
+    pub fn subgroup(log_size: usize) -> Self {
+        Self::new(CirclePoint::IDENTITY, log_size)
+    }
+
+    /// Standard domain of size `2^log_size` for circle FFT: coset of the subgroup of size
+    /// `2^log_size` shifted by the generator of the subgroup of size `2^(log_size + 1)`.
+    /// It is closed under conjugation, so `y` coordinates split it into pairs with
+    /// the same `x`, and its image under the squaring map is a standard domain of half the size
+    pub fn standard(log_size: usize) -> Self {
+        assert!(log_size > 0);
+        assert!(log_size < CirclePoint::LOG_ORDER);
+
+        Self::new(CirclePoint::subgroup_generator(log_size + 1), log_size)
+    }
+
+    pub fn size(&self) -> usize {
+        1 << self.log_size
+    }
+
+    pub fn at(&self, index: usize) -> CirclePoint {
+        debug_assert!(index < self.size());
+
+        self.initial.mul(&self.step.pow(index as u64))
+    }
+
+    pub fn points(&self) -> Vec<CirclePoint> {
+        let mut result = Vec::with_capacity(self.size());
+        let mut current = self.initial;
+        for _ in 0..self.size() {
+            result.push(current);
+            current = current.mul(&self.step);
+        }
+
+        result
+    }
+
+    // image of the coset under the squaring map
+    pub fn double(&self) -> Self {
+        assert!(self.log_size > 0);
+
+        Self {
+            initial: self.initial.double(),
+            step: self.step.double(),
+            log_size: self.log_size - 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_generator_order() {
+        let generator = CirclePoint::GENERATOR;
+        assert!(generator.is_on_circle());
+
+        let element_of_order_two = generator.repeated_double(CirclePoint::LOG_ORDER - 1);
+        assert_eq!(element_of_order_two, CirclePoint::IDENTITY.antipode());
+        assert_eq!(element_of_order_two.double(), CirclePoint::IDENTITY);
+
+        let a = generator.pow(12345);
+        let b = generator.pow(67890);
+        assert_eq!(a.mul(&b), generator.pow(12345 + 67890));
+        assert_eq!(a.mul(&a.conjugate()), CirclePoint::IDENTITY);
+    }
+
+    #[test]
+    fn test_standard_domain() {
+        for log_size in 1..10 {
+            let domain = CircleCoset::standard(log_size);
+            let points = domain.points();
+            assert_eq!(points[5 % points.len()], domain.at(5 % points.len()));
+
+            let as_set: HashSet<_> = points.iter().copied().collect();
+            assert_eq!(as_set.len(), domain.size());
+            for point in points.iter() {
+                assert!(point.is_on_circle());
+                assert!(as_set.contains(&point.conjugate()));
+            }
+
+            if log_size > 1 {
+                let halved: HashSet<_> = points.iter().map(|el| el.double()).collect();
+                let expected: HashSet<_> = CircleCoset::standard(log_size - 1)
+                    .points()
+                    .into_iter()
+                    .collect();
+                assert_eq!(halved, expected);
+                assert_eq!(domain.double(), CircleCoset::standard(log_size - 1));
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use super::*;
+use crate::field::ExtensionField;
+use derivative::*;
+
+/// Quadratic extension by `i^2 = -1`, that is irreducible as `p = 3 mod 4`
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone, Copy, Debug, Hash)]
+pub struct Mersenne31Ext2;
+
+pub type Mersenne31Complex = ExtensionField<Mersenne31Field, 2, Mersenne31Ext2>;
+
+impl std::fmt::Display for Mersenne31Ext2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Mersenne31Ext2")
+    }
+}
+
+impl Mersenne31Ext2 {
+    const NON_RESIDUE: Mersenne31Field = Mersenne31Field(Mersenne31Field::ORDER - 1);
+    const GENERATOR_COEFFS: [Mersenne31Field; 2] = [Mersenne31Field(2u32), Mersenne31Field(7u32)];
+}
+
+impl crate::field::FieldExtension<2> for Mersenne31Ext2 {
+    // p^2 - 1 = (p - 1) * (p + 1) = (2^31 - 2) * 2^31
+    const TWO_ADICITY: usize = 32;
+
+    type BaseField = Mersenne31Field;
+
+    #[inline(always)]
+    fn non_residue() -> Self::BaseField {
+        Self::NON_RESIDUE
+    }
+
+    fn compute_norm(el: &[Self::BaseField; 2]) -> Self::BaseField {
+        // c0^2 + c1^2
+        let mut result = el[0];
+        result.square();
+        let mut t = el[1];
+        t.square();
+        result.add_assign(&t);
+
+        result
+    }
+
+    fn multiplicative_generator_coeffs() -> [Self::BaseField; 2] {
+        Self::GENERATOR_COEFFS
+    }
+
+    #[inline(always)]
+    fn mul_by_non_residue(el: &mut Self::BaseField) {
+        el.negate();
+    }
+}
+
+impl crate::field::traits::field_like::PrimeFieldLikeExtension<2> for Mersenne31Ext2 {
+    const TWO_ADICITY: usize = 32;
+
+    type BaseField = Mersenne31Field;
+
+    fn compute_norm(
+        el: &[Self::BaseField; 2],
+        _ctx: &mut <Self::BaseField as crate::field::traits::field_like::PrimeFieldLike>::Context,
+    ) -> Self::BaseField {
+        <Self as crate::field::FieldExtension<2>>::compute_norm(el)
+    }
+
+    fn multiplicative_generator_coeffs(
+        _ctx: &mut <Self::BaseField as crate::field::traits::field_like::PrimeFieldLike>::Context,
+    ) -> [Self::BaseField; 2] {
+        Self::GENERATOR_COEFFS
+    }
+
+    fn mul_by_non_residue(
+        el: &mut Self::BaseField,
+        _ctx: &mut <Self::BaseField as crate::field::traits::field_like::PrimeFieldLike>::Context,
+    ) {
+        el.negate();
+    }
+}
+
+/// Cubic extension by `x^3 = 5`
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone, Copy, Debug, Hash)]
+pub struct Mersenne31Ext3;
+
+impl std::fmt::Display for Mersenne31Ext3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Mersenne31Ext3")
+    }
+}
+
+impl Mersenne31Ext3 {
+    const NON_RESIDUE: Mersenne31Field = Mersenne31Field(5u32);
+    const GENERATOR_COEFFS: [Mersenne31Field; 3] = [
+        Mersenne31Field(5u32),
+        Mersenne31Field(2u32),
+        Mersenne31Field(0u32),
+    ];
+}
+
+impl crate::field::FieldExtension<3> for Mersenne31Ext3 {
+    // p^3 - 1 = (p - 1) * (p^2 + p + 1), and second multiple is odd
+    const TWO_ADICITY: usize = Mersenne31Field::TWO_ADICITY;
+
+    type BaseField = Mersenne31Field;
+
+    #[inline(always)]
+    fn non_residue() -> Self::BaseField {
+        Self::NON_RESIDUE
+    }
+
+    fn compute_norm(el: &[Self::BaseField; 3]) -> Self::BaseField {
+        ExtensionField::<Mersenne31Field, 3, Self>::from_coeff_in_base(*el).norm()
+    }
+
+    fn multiplicative_generator_coeffs() -> [Self::BaseField; 3] {
+        Self::GENERATOR_COEFFS
+    }
+
+    #[inline(always)]
+    fn mul_by_non_residue(el: &mut Self::BaseField) {
+        el.mul_assign(&Self::NON_RESIDUE);
+    }
+}
@@ -0,0 +1,429 @@
+use crate::field::{
+    Field, PrimeField, SmallField, SmallFieldRepresentable, U64RawRepresentable, U64Representable,
+};
+use std::hash::{Hash, Hasher};
+
+pub mod circle;
+mod extension;
+
+pub use self::extension::{Mersenne31Complex, Mersenne31Ext2, Mersenne31Ext3};
+
+/// Mersenne prime field of order 2^31 - 1.
+///
+/// Multiplicative group has 2-adicity of 1, so radix-2 FFTs are only possible over
+/// the circle group, see `circle` module.
+///
+/// Values are always kept in the canonical form, so all the `*_unchecked`
+/// constructors reduce the input
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[repr(transparent)]
+pub struct Mersenne31Field(pub u32);
+
+impl Mersenne31Field {
+    pub const MULTIPLICATIVE_GROUP_GENERATOR: Self = Self(7);
+    pub const RADIX_2_SUBGROUP_GENERATOR: Self = Self(Self::ORDER - 1);
+    pub const ORDER_BITS: usize = 31;
+    pub const ORDER: u32 = (1 << 31) - 1;
+    pub const TWO_ADICITY: usize = 1;
+
+    #[inline(always)]
+    pub const fn new(value: u32) -> Self {
+        Self::from_u64_with_reduction_impl(value as u64)
+    }
+
+    #[inline(always)]
+    pub const fn to_reduced_u32(&self) -> u32 {
+        self.0
+    }
+
+    #[inline(always)]
+    pub const fn from_u64_with_reduction_impl(x: u64) -> Self {
+        // 2^31 = 1, so we can fold high bits into low ones
+        let folded = (x & (Self::ORDER as u64)) + (x >> Self::ORDER_BITS);
+        let mut folded = ((folded & (Self::ORDER as u64)) + (folded >> Self::ORDER_BITS)) as u32;
+        if folded >= Self::ORDER {
+            folded -= Self::ORDER;
+        }
+
+        Self(folded)
+    }
+
+    // Same folding as in `from_u64_with_reduction_impl`, but over u64 lanes
+    #[inline(always)]
+    pub fn reduce_u64x16(x: std::simd::u64x16) -> std::simd::u32x16 {
+        use std::simd::*;
+
+        let order = u64x16::splat(Self::ORDER as u64);
+        let shift = u64x16::splat(Self::ORDER_BITS as u64);
+        let folded = (x & order) + (x >> shift);
+        let folded = (folded & order) + (folded >> shift);
+
+        folded.simd_min(folded - order).cast::<u32>()
+    }
+
+    const fn compute_shifts() -> [Self; Self::ORDER_BITS] {
+        let mut result = [Self(0); Self::ORDER_BITS];
+        let mut i = 0;
+        while i < Self::ORDER_BITS {
+            result[i] = Self(1u32 << i);
+            i += 1;
+        }
+
+        result
+    }
+
+    #[inline(always)]
+    pub(crate) const fn add_assign_impl(&'_ mut self, other: &Self) -> &'_ mut Self {
+        // both are below 2^31, so can not overflow
+        let mut sum = self.0 + other.0;
+        if sum >= Self::ORDER {
+            sum -= Self::ORDER;
+        }
+        self.0 = sum;
+
+        self
+    }
+
+    #[inline(always)]
+    pub(crate) const fn sub_assign_impl(&'_ mut self, other: &Self) -> &'_ mut Self {
+        let (mut diff, borrow) = self.0.overflowing_sub(other.0);
+        if borrow {
+            diff = diff.wrapping_add(Self::ORDER);
+        }
+        self.0 = diff;
+
+        self
+    }
+
+    #[inline(always)]
+    pub(crate) const fn mul_assign_impl(&'_ mut self, other: &Self) -> &'_ mut Self {
+        *self = Self::from_u64_with_reduction_impl((self.0 as u64) * (other.0 as u64));
+
+        self
+    }
+}
+
+impl Default for Mersenne31Field {
+    fn default() -> Self {
+        Self(0u32)
+    }
+}
+
+impl PartialEq for Mersenne31Field {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Mersenne31Field {}
+
+impl Hash for Mersenne31Field {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u32(self.0)
+    }
+}
+
+impl std::fmt::Display for Mersenne31Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:08x}", self.0)
+    }
+}
+
+impl std::fmt::Debug for Mersenne31Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:08x}", self.0)
+    }
+}
+
+impl Field for Mersenne31Field {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(1);
+    const TWO: Self = Self(2);
+    const MINUS_ONE: Self = Self(Self::ORDER - 1);
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline(always)]
+    fn add_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
+        self.add_assign_impl(other)
+    }
+
+    #[inline(always)]
+    fn sub_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
+        self.sub_assign_impl(other)
+    }
+
+    #[inline(always)]
+    fn negate(&mut self) -> &mut Self {
+        if self.is_zero() == false {
+            self.0 = Self::ORDER - self.0;
+        }
+
+        self
+    }
+
+    #[inline(always)]
+    fn mul_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
+        self.mul_assign_impl(other)
+    }
+
+    #[inline(always)]
+    fn square(&mut self) -> &mut Self {
+        let t = *self;
+        self.mul_assign_impl(&t)
+    }
+
+    #[inline(always)]
+    fn double(&mut self) -> &mut Self {
+        let t = *self;
+        self.add_assign_impl(&t)
+    }
+
+    #[inline(always)]
+    fn from_u64_with_reduction(value: u64) -> Self {
+        Self::from_u64_with_reduction_impl(value)
+    }
+}
+
+impl PrimeField for Mersenne31Field {
+    const CHAR_BITS: usize = Self::ORDER_BITS;
+    const CAPACITY_BITS: usize = Self::ORDER_BITS - 1;
+    const TWO_ADICITY: usize = Self::TWO_ADICITY;
+    const SHIFTS: &'static [Self] = &Self::compute_shifts();
+
+    #[inline(always)]
+    fn multiplicative_generator() -> Self {
+        Self::MULTIPLICATIVE_GROUP_GENERATOR
+    }
+
+    #[inline(always)]
+    fn radix_2_subgroup_generator() -> Self {
+        Self::RADIX_2_SUBGROUP_GENERATOR
+    }
+
+    #[inline(always)]
+    fn frobenius(&self, _power: usize) -> Self {
+        *self
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        Some(self.pow_u64((Self::ORDER - 2) as u64))
+    }
+
+    fn legendre(&self) -> super::LegendreSymbol {
+        // s = self^((modulus - 1) // 2)
+        let s = self.pow_u64((Self::CHAR - 1) / 2);
+        if s == Self::ZERO {
+            super::LegendreSymbol::Zero
+        } else if s == Self::ONE {
+            super::LegendreSymbol::QuadraticResidue
+        } else {
+            super::LegendreSymbol::QuadraticNonResidue
+        }
+    }
+}
+
+impl U64RawRepresentable for Mersenne31Field {
+    #[inline(always)]
+    fn as_raw_u64(self) -> u64 {
+        self.0 as u64
+    }
+
+    #[inline(always)]
+    fn from_raw_u64_unchecked(value: u64) -> Self {
+        Self::from_u64_with_reduction_impl(value)
+    }
+
+    #[inline(always)]
+    fn from_raw_u64_checked(value: u64) -> Option<Self> {
+        if value >= Self::ORDER as u64 {
+            None
+        } else {
+            Some(Self(value as u32))
+        }
+    }
+
+    #[inline(always)]
+    fn as_raw_u64_array<const N: usize>(input: [Self; N]) -> [u64; N] {
+        input.map(|el| el.0 as u64)
+    }
+}
+
+impl U64Representable for Mersenne31Field {
+    #[inline(always)]
+    fn as_u64(self) -> u64 {
+        self.0 as u64
+    }
+
+    #[inline(always)]
+    fn from_u64_unchecked(value: u64) -> Self {
+        Self::from_u64_with_reduction_impl(value)
+    }
+
+    #[inline(always)]
+    fn from_u64(value: u64) -> Option<Self> {
+        if value >= Self::ORDER as u64 {
+            None
+        } else {
+            Some(Self(value as u32))
+        }
+    }
+
+    #[inline(always)]
+    fn as_u64_array<const N: usize>(input: [Self; N]) -> [u64; N] {
+        input.map(|el| el.0 as u64)
+    }
+
+    #[inline(always)]
+    fn as_u64_reduced(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
+impl SmallFieldRepresentable for Mersenne31Field {
+    #[inline(always)]
+    fn from_u128_reduced(value: u128) -> Self {
+        Self((value % (Self::ORDER as u128)) as u32)
+    }
+}
+
+impl SmallField for Mersenne31Field {
+    const CHAR: u64 = Self::ORDER as u64;
+    // a * b + c
+    #[inline(always)]
+    fn fma(a: Self, b: Self, c: Self) -> Self {
+        Self::from_u64_with_reduction_impl((a.0 as u64) * (b.0 as u64) + (c.0 as u64))
+    }
+
+    const CAN_CAST_VECTOR_TO_U64_LE_VECTOR: bool = false;
+}
+
+crate::impl_std_ops_for_field!(Mersenne31Field);
+
+crate::impl_packed_16_for_small_field!(
+    MixedMersenne31,
+    Mersenne31Field,
+    Mersenne31Field::reduce_u64x16
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::field::rand_from_rng;
+    use crate::field::{ExtensionField, FieldExtension};
+
+    type F = Mersenne31Field;
+
+    #[test]
+    fn test_generator() {
+        let generator = F::multiplicative_generator();
+        // p - 1 = 2 * 3^2 * 7 * 11 * 31 * 151 * 331
+        for factor in [2u64, 3, 7, 11, 31, 151, 331] {
+            assert_ne!(generator.pow_u64((F::CHAR - 1) / factor), F::ONE);
+        }
+        assert_eq!(
+            F::radix_2_subgroup_generator().pow_u64(1u64 << F::TWO_ADICITY),
+            F::ONE
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_against_reference() {
+        let mut rng = rand::thread_rng();
+        let order = F::CHAR as u128;
+
+        for _ in 0..1 << 16 {
+            let a: F = rand_from_rng(&mut rng);
+            let b: F = rand_from_rng(&mut rng);
+            let (a_u128, b_u128) = (a.as_u64() as u128, b.as_u64() as u128);
+
+            assert_eq!((a + b).as_u64() as u128, (a_u128 + b_u128) % order);
+            assert_eq!((a - b).as_u64() as u128, (a_u128 + order - b_u128) % order);
+            assert_eq!((a * b).as_u64() as u128, (a_u128 * b_u128) % order);
+            assert_eq!((-a).as_u64() as u128, (order - a_u128) % order);
+            assert_eq!(F::fma(a, b, a), a * b + a);
+
+            if let Some(a_inversed) = a.inverse() {
+                assert_eq!(a * a_inversed, F::ONE);
+            } else {
+                assert!(a.is_zero());
+            }
+        }
+
+        for value in [
+            u64::MAX,
+            F::CHAR,
+            F::CHAR - 1,
+            1u64 << 62,
+            (F::CHAR << 31) - 1,
+        ] {
+            assert_eq!(F::from_u64_unchecked(value).as_u64(), value % F::CHAR);
+        }
+    }
+
+    #[test]
+    fn test_packed_arithmetic() {
+        crate::field::traits::field_like::check_packed_arithmetic::<F, MixedMersenne31>();
+    }
+
+    #[test]
+    fn test_complex_extension() {
+        type Ext = ExtensionField<F, 2, Mersenne31Ext2>;
+
+        // i^2 = -1
+        let mut i = Ext::from_coeff_in_base([F::ZERO, F::ONE]);
+        i.square();
+        assert_eq!(i, Ext::MINUS_ONE);
+
+        let mut rng = rand::thread_rng();
+        let a = Ext::from_coeff_in_base([rand_from_rng(&mut rng), rand_from_rng(&mut rng)]);
+        let mut product = a.inverse().unwrap();
+        product.mul_assign(&a);
+        assert_eq!(product, Ext::ONE);
+
+        let generator = Ext::multiplicative_generator();
+        let group_order = (F::CHAR as u128) * (F::CHAR as u128) - 1;
+        for factor in [2u128, 3, 7, 11, 31, 151, 331] {
+            let power = group_order / factor;
+            let power = [power as u64, (power >> 64) as u64];
+            assert_ne!(generator.pow(&power), Ext::ONE);
+        }
+
+        // norm of a is a * conj(a)
+        let mut conjugate = a;
+        conjugate.coeffs[1].negate();
+        conjugate.mul_assign(&a);
+        assert_eq!(
+            conjugate,
+            Ext::from_coeff_in_base([Mersenne31Ext2::compute_norm(a.as_coeffs_in_base()), F::ZERO])
+        );
+    }
+
+    #[test]
+    fn test_cubic_extension() {
+        type Ext = ExtensionField<F, 3, Mersenne31Ext3>;
+
+        let mut rng = rand::thread_rng();
+        let a = Ext::from_coeff_in_base(std::array::from_fn(|_| rand_from_rng(&mut rng)));
+        assert_eq!(a.frobenius(1), a.pow_u64(F::CHAR));
+
+        let mut product = a.inverse().unwrap();
+        product.mul_assign(&a);
+        assert_eq!(product, Ext::ONE);
+
+        let generator = Ext::multiplicative_generator();
+        let group_order = (F::CHAR as u128).pow(3) - 1;
+        for factor in [2u128, 3, 7, 11, 31, 151, 331, 529510939, 2903110321] {
+            let power = group_order / factor;
+            let power = [power as u64, (power >> 64) as u64];
+            assert_ne!(generator.pow(&power), Ext::ONE);
+        }
+    }
+}
@@ -1,4 +1,7 @@
+pub mod baby_bear;
+pub mod batch_inversion;
 pub mod goldilocks;
+pub mod mersenne31;
 pub mod traits;
 
 pub use self::traits::field::*;
@@ -147,6 +147,10 @@ pub trait SqrtField: Field {
     fn sqrt(&self) -> Option<Self>;
 }
 
+pub trait CubeRootField: Field {
+    fn cube_root(&self) -> Option<Self>;
+}
+
 // we also create a macro to add default implementations for overrides of arithmetic ops from std
 
 // Produces impl blocks for feature enabled and disabled
@@ -325,25 +329,64 @@ pub trait FieldExtension<const DEGREE: usize>:
 #[derive(Clone, Copy, Hash, serde::Serialize, serde::Deserialize)]
 // #[derive(Clone, Copy, Hash)]
 pub struct ExtensionField<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> {
-    #[serde(bound(serialize = "[F; DEGREE]: serde::Serialize"))]
-    #[serde(bound(deserialize = "[F; DEGREE]: serde::de::DeserializeOwned"))]
+    #[serde(bound = "F: serde::Serialize + serde::de::DeserializeOwned")]
+    #[serde(with = "crate::serde_utils::BigArraySerde")]
     pub coeffs: [F; DEGREE],
     pub _marker: std::marker::PhantomData<E>,
 }
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> std::cmp::PartialEq
-    for ExtensionField<F, 2, E>
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>>
+    ExtensionField<F, DEGREE, E>
+{
+    #[inline(always)]
+    pub fn mul_assign_by_base(&mut self, base: &F) {
+        for el in self.coeffs.iter_mut() {
+            el.mul_assign(base);
+        }
+    }
+    #[inline(always)]
+    pub const fn as_coeffs_in_base(&self) -> &[F; DEGREE] {
+        &self.coeffs
+    }
+    #[inline(always)]
+    pub const fn into_coeffs_in_base(self) -> [F; DEGREE] {
+        self.coeffs
+    }
+    #[inline(always)]
+    pub const fn from_coeff_in_base(coeffs: [F; DEGREE]) -> Self {
+        Self {
+            coeffs,
+            _marker: std::marker::PhantomData,
+        }
+    }
+    #[inline(always)]
+    pub fn from_base(value: F) -> Self {
+        Self::from_base_const(value)
+    }
+    const fn from_base_const(value: F) -> Self {
+        let mut coeffs = [F::ZERO; DEGREE];
+        coeffs[0] = value;
+
+        Self::from_coeff_in_base(coeffs)
+    }
+}
+
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> std::cmp::PartialEq
+    for ExtensionField<F, DEGREE, E>
 {
     #[inline(always)]
     fn eq(&self, other: &Self) -> bool {
-        self.coeffs[0].eq(&other.coeffs[0]) && self.coeffs[1].eq(&other.coeffs[1])
+        self.coeffs == other.coeffs
     }
 }
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> std::cmp::Eq for ExtensionField<F, 2, E> {}
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> std::cmp::Eq
+    for ExtensionField<F, DEGREE, E>
+{
+}
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> std::default::Default
-    for ExtensionField<F, 2, E>
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> std::default::Default
+    for ExtensionField<F, DEGREE, E>
 {
     #[inline(always)]
     fn default() -> Self {
@@ -351,125 +394,187 @@ impl<F: Field, E: FieldExtension<2, BaseField = F>> std::default::Default
     }
 }
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> std::fmt::Debug for ExtensionField<F, 2, E> {
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> std::fmt::Debug
+    for ExtensionField<F, DEGREE, E>
+{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "F2{{")?;
-        writeln!(f, "\t{},", self.coeffs[0])?;
-        writeln!(f, "\t{},", self.coeffs[1])?;
+        writeln!(f, "F{}{{", DEGREE)?;
+        for el in self.coeffs.iter() {
+            writeln!(f, "\t{},", el)?;
+        }
         write!(f, "}}")
     }
 }
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> std::fmt::Display for ExtensionField<F, 2, E> {
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> std::fmt::Display
+    for ExtensionField<F, DEGREE, E>
+{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "F2{{")?;
-        writeln!(f, "{},", self.coeffs[0])?;
-        writeln!(f, "{}", self.coeffs[1])?;
+        writeln!(f, "F{}{{", DEGREE)?;
+        for el in self.coeffs.iter() {
+            writeln!(f, "{},", el)?;
+        }
         writeln!(f, "}}")
     }
 }
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> Field for ExtensionField<F, 2, E> {
+// Extensions are binomial, i.e. F[x] / (x^DEGREE - non_residue), and require DEGREE to divide p - 1,
+// so Frobenius map is just a scaling of the coefficients. Quadratic case keeps dedicated formulas
+
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> Field
+    for ExtensionField<F, DEGREE, E>
+{
     const ZERO: Self = ExtensionField {
-        coeffs: [F::ZERO; 2],
-        _marker: std::marker::PhantomData,
-    };
-    const ONE: Self = ExtensionField {
-        coeffs: [F::ONE, F::ZERO],
-        _marker: std::marker::PhantomData,
-    };
-    const TWO: Self = ExtensionField {
-        coeffs: [F::TWO, F::ZERO],
-        _marker: std::marker::PhantomData,
-    };
-    const MINUS_ONE: Self = ExtensionField {
-        coeffs: [F::MINUS_ONE, F::ZERO],
+        coeffs: [F::ZERO; DEGREE],
         _marker: std::marker::PhantomData,
     };
+    const ONE: Self = Self::from_base_const(F::ONE);
+    const TWO: Self = Self::from_base_const(F::TWO);
+    const MINUS_ONE: Self = Self::from_base_const(F::MINUS_ONE);
     #[inline]
     fn is_zero(&self) -> bool {
-        self.coeffs[0].is_zero() && self.coeffs[1].is_zero()
+        self.coeffs.iter().all(|el| el.is_zero())
     }
     #[inline]
     fn add_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
-        self.coeffs[0].add_assign(&other.coeffs[0]);
-        self.coeffs[1].add_assign(&other.coeffs[1]);
+        for (a, b) in self.coeffs.iter_mut().zip(other.coeffs.iter()) {
+            a.add_assign(b);
+        }
 
         self
     }
     #[inline]
     fn sub_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
-        self.coeffs[0].sub_assign(&other.coeffs[0]);
-        self.coeffs[1].sub_assign(&other.coeffs[1]);
+        for (a, b) in self.coeffs.iter_mut().zip(other.coeffs.iter()) {
+            a.sub_assign(b);
+        }
 
         self
     }
     #[inline]
     fn mul_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
-        let mut v0 = self.coeffs[0];
-        v0.mul_assign(&other.coeffs[0]);
-        let mut v1 = self.coeffs[1];
-        v1.mul_assign(&other.coeffs[1]);
-
-        let t = self.coeffs[0];
-        self.coeffs[1].add_assign(&t);
-
-        let mut t0 = other.coeffs[0];
-        t0.add_assign(&other.coeffs[1]);
-        self.coeffs[1].mul_assign(&t0);
-        self.coeffs[1].sub_assign(&v0);
-        self.coeffs[1].sub_assign(&v1);
-        self.coeffs[0] = v0;
-        E::mul_by_non_residue(&mut v1);
-        self.coeffs[0].add_assign(&v1);
+        if DEGREE == 2 {
+            let mut v0 = self.coeffs[0];
+            v0.mul_assign(&other.coeffs[0]);
+            let mut v1 = self.coeffs[1];
+            v1.mul_assign(&other.coeffs[1]);
+
+            let t = self.coeffs[0];
+            self.coeffs[1].add_assign(&t);
+
+            let mut t0 = other.coeffs[0];
+            t0.add_assign(&other.coeffs[1]);
+            self.coeffs[1].mul_assign(&t0);
+            self.coeffs[1].sub_assign(&v0);
+            self.coeffs[1].sub_assign(&v1);
+            self.coeffs[0] = v0;
+            E::mul_by_non_residue(&mut v1);
+            self.coeffs[0].add_assign(&v1);
+
+            return self;
+        }
+
+        // schoolbook, and x^DEGREE = non_residue for higher terms
+        let mut low = [F::ZERO; DEGREE];
+        let mut high = [F::ZERO; DEGREE];
+        for i in 0..DEGREE {
+            for j in 0..DEGREE {
+                let mut t = self.coeffs[i];
+                t.mul_assign(&other.coeffs[j]);
+                if i + j < DEGREE {
+                    low[i + j].add_assign(&t);
+                } else {
+                    high[i + j - DEGREE].add_assign(&t);
+                }
+            }
+        }
+        for (dst, mut src) in low.iter_mut().zip(high.into_iter()) {
+            E::mul_by_non_residue(&mut src);
+            dst.add_assign(&src);
+        }
+        self.coeffs = low;
 
         self
     }
     #[inline]
     fn square(&mut self) -> &mut Self {
-        let mut v0 = self.coeffs[0];
-        v0.sub_assign(&self.coeffs[1]);
-        let mut v3 = self.coeffs[0];
-        let mut t0 = self.coeffs[1];
-        E::mul_by_non_residue(&mut t0);
-        v3.sub_assign(&t0);
-        let mut v2 = self.coeffs[0];
-        v2.mul_assign(&self.coeffs[1]);
-        v0.mul_assign(&v3);
-        v0.add_assign(&v2);
-
-        self.coeffs[1] = v2;
-        self.coeffs[1].double();
-        self.coeffs[0] = v0;
-        E::mul_by_non_residue(&mut v2);
-        self.coeffs[0].add_assign(&v2);
+        if DEGREE == 2 {
+            let mut v0 = self.coeffs[0];
+            v0.sub_assign(&self.coeffs[1]);
+            let mut v3 = self.coeffs[0];
+            let mut t0 = self.coeffs[1];
+            E::mul_by_non_residue(&mut t0);
+            v3.sub_assign(&t0);
+            let mut v2 = self.coeffs[0];
+            v2.mul_assign(&self.coeffs[1]);
+            v0.mul_assign(&v3);
+            v0.add_assign(&v2);
+
+            self.coeffs[1] = v2;
+            self.coeffs[1].double();
+            self.coeffs[0] = v0;
+            E::mul_by_non_residue(&mut v2);
+            self.coeffs[0].add_assign(&v2);
+
+            return self;
+        }
 
-        self
+        let t = *self;
+        self.mul_assign(&t)
     }
     #[inline]
     fn negate(&mut self) -> &mut Self {
-        self.coeffs[0].negate();
-        self.coeffs[1].negate();
+        for el in self.coeffs.iter_mut() {
+            el.negate();
+        }
 
         self
     }
     #[inline]
     fn double(&mut self) -> &mut Self {
-        self.coeffs[0].double();
-        self.coeffs[1].double();
+        for el in self.coeffs.iter_mut() {
+            el.double();
+        }
 
         self
     }
     #[inline]
     fn from_u64_with_reduction(value: u64) -> Self {
-        ExtensionField {
-            coeffs: [F::from_u64_with_reduction(value), F::ZERO],
-            _marker: std::marker::PhantomData,
+        Self::from_base(F::from_u64_with_reduction(value))
+    }
+}
+
+impl<
+        F: crate::field::SmallField,
+        const DEGREE: usize,
+        E: FieldExtension<DEGREE, BaseField = F>,
+    > ExtensionField<F, DEGREE, E>
+{
+    // x^p = gamma * x, where gamma = non_residue^((p - 1) / DEGREE)
+    fn frobenius_gamma() -> F {
+        assert!((F::CHAR - 1) % (DEGREE as u64) == 0);
+
+        E::non_residue().pow_u64((F::CHAR - 1) / (DEGREE as u64))
+    }
+
+    // product of all the conjugates, that is always in the base field
+    pub fn norm(&self) -> F {
+        let mut result = *self;
+        for power in 1..DEGREE {
+            result.mul_assign(&self.frobenius(power));
         }
+        debug_assert!(result.coeffs[1..].iter().all(|el| el.is_zero()));
+
+        result.coeffs[0]
     }
 }
 
-impl<F: PrimeField, E: FieldExtension<2, BaseField = F>> PrimeField for ExtensionField<F, 2, E> {
+impl<
+        F: crate::field::SmallField,
+        const DEGREE: usize,
+        E: FieldExtension<DEGREE, BaseField = F>,
+    > PrimeField for ExtensionField<F, DEGREE, E>
+{
     const CAPACITY_BITS: usize = 1;
     const CHAR_BITS: usize = F::CHAR_BITS;
     const TWO_ADICITY: usize = 0;
@@ -483,64 +588,61 @@ impl<F: PrimeField, E: FieldExtension<2, BaseField = F>> PrimeField for Extensio
         unreachable!()
     }
     fn inverse(&self) -> Option<Self> {
-        let mut v0 = self.coeffs[0];
-        v0.square();
-        let mut v1 = self.coeffs[1];
-        v1.square();
-        // v0 = v0 - beta * v1
-        let mut v1_by_nonresidue = v1;
-        E::mul_by_non_residue(&mut v1_by_nonresidue);
-        v0.sub_assign(&v1_by_nonresidue);
-        match v0.inverse() {
+        if DEGREE == 2 {
+            let mut v0 = self.coeffs[0];
+            v0.square();
+            let mut v1 = self.coeffs[1];
+            v1.square();
+            // v0 = v0 - beta * v1
+            let mut v1_by_nonresidue = v1;
+            E::mul_by_non_residue(&mut v1_by_nonresidue);
+            v0.sub_assign(&v1_by_nonresidue);
+
+            return match v0.inverse() {
+                Some(inversed) => {
+                    let mut new = *self;
+                    new.coeffs[0].mul_assign(&inversed);
+                    new.coeffs[1].mul_assign(&inversed);
+                    new.coeffs[1].negate();
+
+                    Some(new)
+                }
+                None => None,
+            };
+        }
+
+        // a^-1 = (a^p * ... * a^(p^(DEGREE - 1))) / norm(a)
+        let mut conjugates_product = Self::ONE;
+        for power in 1..DEGREE {
+            conjugates_product.mul_assign(&self.frobenius(power));
+        }
+        let mut norm = conjugates_product;
+        norm.mul_assign(self);
+        debug_assert!(norm.coeffs[1..].iter().all(|el| el.is_zero()));
+
+        match norm.coeffs[0].inverse() {
             Some(inversed) => {
-                let mut c0 = self.coeffs[0];
-                c0.mul_assign(&inversed);
-                let mut c1 = self.coeffs[1];
-                c1.mul_assign(&inversed);
-                c1.negate();
-
-                let new = Self {
-                    coeffs: [c0, c1],
-                    _marker: std::marker::PhantomData,
-                };
-
-                Some(new)
+                conjugates_product.mul_assign_by_base(&inversed);
+
+                Some(conjugates_product)
             }
             None => None,
         }
     }
-    fn frobenius(&self, _power: usize) -> Self {
-        unimplemented!()
-    }
-    fn legendre(&self) -> LegendreSymbol {
-        unimplemented!()
-    }
-}
+    fn frobenius(&self, power: usize) -> Self {
+        let gamma = Self::frobenius_gamma().pow_u64((power % DEGREE) as u64);
+        let mut result = *self;
+        let mut scale = F::ONE;
+        for el in result.coeffs.iter_mut() {
+            el.mul_assign(&scale);
+            scale.mul_assign(&gamma);
+        }
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> ExtensionField<F, 2, E> {
-    #[inline(always)]
-    pub fn mul_assign_by_base(&mut self, base: &F)
-    where
-        F: Field,
-        E: FieldExtension<2, BaseField = F>,
-    {
-        self.coeffs[0].mul_assign(base);
-        self.coeffs[1].mul_assign(base);
+        result
     }
-    #[inline(always)]
-    pub const fn as_coeffs_in_base(&self) -> &[F; 2] {
-        &self.coeffs
-    }
-    #[inline(always)]
-    pub const fn into_coeffs_in_base(self) -> [F; 2] {
-        self.coeffs
-    }
-    #[inline(always)]
-    pub const fn from_coeff_in_base(coeffs: [F; 2]) -> Self {
-        Self {
-            coeffs,
-            _marker: std::marker::PhantomData,
-        }
+    fn legendre(&self) -> LegendreSymbol {
+        // norm map is surjective, so it maps squares onto squares of the base field
+        self.norm().legendre()
     }
 }
 
@@ -288,6 +288,397 @@ impl<F: BaseField> PrimeFieldLikeVectorized for F {
     }
 }
 
+// Lane-wise arithmetic over canonical representations of 31-bit prime fields, used by
+// `impl_packed_16_for_small_field`. Both inputs must be below the order, so sums fit into u32,
+// and wrapping arithmetic allows to select the reduced value with a single minimum
+#[inline(always)]
+pub fn add_mod_u32x16(
+    a: std::simd::u32x16,
+    b: std::simd::u32x16,
+    order: std::simd::u32x16,
+) -> std::simd::u32x16 {
+    use std::simd::*;
+
+    let sum = a + b;
+    sum.simd_min(sum - order)
+}
+
+#[inline(always)]
+pub fn sub_mod_u32x16(
+    a: std::simd::u32x16,
+    b: std::simd::u32x16,
+    order: std::simd::u32x16,
+) -> std::simd::u32x16 {
+    use std::simd::*;
+
+    let diff = a - b;
+    diff.simd_min(diff + order)
+}
+
+// Packing of 16 elements of a 31-bit field over `std::simd` vectors. Additions are done in u32
+// lanes, products are computed in u64 lanes and reduced by the field specific `$reduce`, that
+// maps u64x16 with values below order^2 into canonical u32x16. Elements are stored in the same
+// order as in the base slice, so FFTs can be done over the unpacked representation
+#[macro_export]
+macro_rules! impl_packed_16_for_small_field {
+    { $packed_name:ident, $base_name:ident, $reduce:path } => {
+        #[derive(Hash, Clone, Copy)]
+        #[repr(C, align(64))]
+        pub struct $packed_name(pub [$base_name; 16]);
+
+        impl std::fmt::Debug for $packed_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{:?}", self.0)
+            }
+        }
+
+        impl std::fmt::Display for $packed_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{:?}", self.0)
+            }
+        }
+
+        impl $packed_name {
+            #[inline(always)]
+            pub fn new() -> Self {
+                Self([<$base_name as $crate::field::Field>::ZERO; 16])
+            }
+
+            #[inline(always)]
+            pub fn from_constant(value: $base_name) -> Self {
+                Self([value; 16])
+            }
+
+            #[inline(always)]
+            pub fn from_array(value: [$base_name; 16]) -> Self {
+                Self(value)
+            }
+
+            const ORDER_VECTOR: std::simd::u32x16 = std::simd::u32x16::from_array([$base_name::ORDER; 16]);
+
+            #[inline(always)]
+            fn as_simd(&self) -> std::simd::u32x16 {
+                std::simd::u32x16::from_array(self.0.map(|el| el.0))
+            }
+
+            #[inline(always)]
+            fn from_simd(value: std::simd::u32x16) -> Self {
+                Self(value.to_array().map($base_name))
+            }
+
+            #[inline(always)]
+            fn mul_simd(a: std::simd::u32x16, b: std::simd::u32x16) -> std::simd::u32x16 {
+                use std::simd::*;
+
+                let product: std::simd::u64x16 = a.cast::<u64>() * b.cast::<u64>();
+                $reduce(product)
+            }
+        }
+
+        impl Default for $packed_name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl PartialEq for $packed_name {
+            #[inline(always)]
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl Eq for $packed_name {}
+
+        impl $crate::field::traits::field_like::PrimeFieldLike for $packed_name {
+            type Base = $base_name;
+            type Context = ();
+
+            #[inline(always)]
+            fn zero(_ctx: &mut Self::Context) -> Self {
+                Self([<$base_name as $crate::field::Field>::ZERO; 16])
+            }
+            #[inline(always)]
+            fn one(_ctx: &mut Self::Context) -> Self {
+                Self([<$base_name as $crate::field::Field>::ONE; 16])
+            }
+            #[inline(always)]
+            fn minus_one(_ctx: &mut Self::Context) -> Self {
+                Self([<$base_name as $crate::field::Field>::MINUS_ONE; 16])
+            }
+
+            #[inline(always)]
+            fn add_assign(&mut self, other: &Self, _ctx: &mut Self::Context) -> &mut Self {
+                *self = Self::from_simd($crate::field::traits::field_like::add_mod_u32x16(
+                    self.as_simd(),
+                    other.as_simd(),
+                    Self::ORDER_VECTOR,
+                ));
+                self
+            }
+
+            #[inline(always)]
+            fn sub_assign(&'_ mut self, other: &Self, _ctx: &mut Self::Context) -> &mut Self {
+                *self = Self::from_simd($crate::field::traits::field_like::sub_mod_u32x16(
+                    self.as_simd(),
+                    other.as_simd(),
+                    Self::ORDER_VECTOR,
+                ));
+                self
+            }
+
+            #[inline(always)]
+            fn mul_assign(&'_ mut self, other: &Self, _ctx: &mut Self::Context) -> &mut Self {
+                *self = Self::from_simd(Self::mul_simd(self.as_simd(), other.as_simd()));
+                self
+            }
+
+            #[inline(always)]
+            fn square(&'_ mut self, _ctx: &mut Self::Context) -> &'_ mut Self {
+                let value = self.as_simd();
+                *self = Self::from_simd(Self::mul_simd(value, value));
+                self
+            }
+
+            #[inline(always)]
+            fn negate(&'_ mut self, _ctx: &mut Self::Context) -> &'_ mut Self {
+                *self = Self::from_simd($crate::field::traits::field_like::sub_mod_u32x16(
+                    std::simd::u32x16::splat(0),
+                    self.as_simd(),
+                    Self::ORDER_VECTOR,
+                ));
+                self
+            }
+
+            #[inline(always)]
+            fn double(&'_ mut self, _ctx: &mut Self::Context) -> &'_ mut Self {
+                let value = self.as_simd();
+                *self = Self::from_simd($crate::field::traits::field_like::add_mod_u32x16(
+                    value,
+                    value,
+                    Self::ORDER_VECTOR,
+                ));
+                self
+            }
+
+            #[inline(always)]
+            fn inverse(&self, _ctx: &mut Self::Context) -> Self {
+                let mut result = *self;
+                for a in result.0.iter_mut() {
+                    *a = $crate::field::PrimeField::inverse(a).expect("inverse must exist");
+                }
+
+                result
+            }
+
+            #[inline(always)]
+            fn constant(value: Self::Base, _ctx: &mut Self::Context) -> Self {
+                Self([value; 16])
+            }
+        }
+
+        impl $crate::field::traits::field_like::PrimeFieldLikeVectorized for $packed_name {
+            type Twiddles<A: $crate::cs::traits::GoodAllocator> = Vec<$base_name, A>;
+            type InverseTwiddles<A: $crate::cs::traits::GoodAllocator> = Vec<$base_name, A>;
+
+            #[inline(always)]
+            fn is_zero(&self) -> bool {
+                self.0 == [<$base_name as $crate::field::Field>::ZERO; 16]
+            }
+
+            #[inline(always)]
+            fn equals(&self, other: &Self) -> bool {
+                self.eq(&other)
+            }
+
+            #[inline(always)]
+            fn mul_all_by_base(
+                &'_ mut self,
+                other: &Self::Base,
+                _ctx: &mut Self::Context,
+            ) -> &'_ mut Self {
+                let other = std::simd::u32x16::splat(other.0);
+                *self = Self::from_simd(Self::mul_simd(self.as_simd(), other));
+                self
+            }
+
+            #[inline(always)]
+            fn slice_from_base_slice(input: &[Self::Base]) -> &[Self] {
+                if input.len() < Self::SIZE_FACTOR {
+                    panic!("too small input size to cast");
+                }
+                debug_assert!(input.len() % Self::SIZE_FACTOR == 0);
+                debug_assert!(input.as_ptr().addr() % std::mem::align_of::<Self>() == 0);
+                let result_len = input.len() / 16;
+                unsafe { std::slice::from_raw_parts(input.as_ptr() as *mut Self, result_len) }
+            }
+
+            #[inline(always)]
+            fn slice_into_base_slice(input: &[Self]) -> &[Self::Base] {
+                let result_len = input.len() * 16;
+                unsafe { std::slice::from_raw_parts(input.as_ptr() as *mut $base_name, result_len) }
+            }
+
+            #[inline(always)]
+            fn slice_into_base_slice_mut(input: &mut [Self]) -> &mut [Self::Base] {
+                let result_len = input.len() * 16;
+                unsafe {
+                    std::slice::from_raw_parts_mut(input.as_ptr() as *mut $base_name, result_len)
+                }
+            }
+
+            #[inline(always)]
+            fn vec_from_base_vec<A: $crate::cs::traits::GoodAllocator>(
+                input: Vec<Self::Base, A>,
+            ) -> Vec<Self, A> {
+                if input.len() < Self::SIZE_FACTOR {
+                    panic!("too small input size to cast");
+                }
+                let (ptr, len, capacity, allocator) = input.into_raw_parts_with_alloc();
+                debug_assert!(ptr.addr() % std::mem::align_of::<Self>() == 0);
+                debug_assert!(len % Self::SIZE_FACTOR == 0);
+                debug_assert!(capacity % Self::SIZE_FACTOR == 0);
+
+                unsafe {
+                    Vec::from_raw_parts_in(
+                        ptr as _,
+                        len / Self::SIZE_FACTOR,
+                        capacity / Self::SIZE_FACTOR,
+                        allocator,
+                    )
+                }
+            }
+
+            #[inline(always)]
+            fn vec_into_base_vec<A: $crate::cs::traits::GoodAllocator>(
+                input: Vec<Self, A>,
+            ) -> Vec<Self::Base, A> {
+                let (ptr, len, capacity, allocator) = input.into_raw_parts_with_alloc();
+
+                unsafe {
+                    Vec::from_raw_parts_in(
+                        ptr as _,
+                        len * Self::SIZE_FACTOR,
+                        capacity * Self::SIZE_FACTOR,
+                        allocator,
+                    )
+                }
+            }
+
+            #[inline(always)]
+            fn fft_natural_to_bitreversed<A: $crate::cs::traits::GoodAllocator>(
+                input: &mut [Self],
+                coset: Self::Base,
+                twiddles: &Self::Twiddles<A>,
+                _ctx: &mut Self::Context,
+            ) {
+                let input = Self::slice_into_base_slice_mut(input);
+                $crate::fft::fft_natural_to_bitreversed::<$base_name>(input, coset, twiddles);
+            }
+
+            #[inline(always)]
+            fn ifft_natural_to_natural<A: $crate::cs::traits::GoodAllocator>(
+                input: &mut [Self],
+                coset: Self::Base,
+                twiddles: &Self::InverseTwiddles<A>,
+                _ctx: &mut Self::Context,
+            ) {
+                let input = Self::slice_into_base_slice_mut(input);
+                $crate::fft::ifft_natural_to_natural::<$base_name>(input, coset, twiddles);
+            }
+
+            #[inline(always)]
+            fn precompute_forward_twiddles_for_fft<A: $crate::cs::traits::GoodAllocator>(
+                fft_size: usize,
+                worker: &$crate::worker::Worker,
+                ctx: &mut Self::Context,
+            ) -> Self::Twiddles<A> {
+                $crate::cs::implementations::utils::precompute_twiddles_for_fft::<
+                    $base_name,
+                    $base_name,
+                    A,
+                    false,
+                >(fft_size, &worker, ctx)
+            }
+
+            #[inline(always)]
+            fn precompute_inverse_twiddles_for_fft<A: $crate::cs::traits::GoodAllocator>(
+                fft_size: usize,
+                worker: &$crate::worker::Worker,
+                ctx: &mut Self::Context,
+            ) -> Self::Twiddles<A> {
+                $crate::cs::implementations::utils::precompute_twiddles_for_fft::<
+                    $base_name,
+                    $base_name,
+                    A,
+                    true,
+                >(fft_size, &worker, ctx)
+            }
+        }
+    };
+}
+
+// Checks packed arithmetic against the base field, including the values at the edges of
+// the canonical range
+#[cfg(test)]
+pub(crate) fn check_packed_arithmetic<
+    F: SmallField,
+    P: PrimeFieldLikeVectorized<Base = F, Context = ()>,
+>() {
+    let mut ctx = ();
+    let mut rng = rand::thread_rng();
+    const POLY_SIZE: usize = 1 << 10;
+
+    let mut a: Vec<F> = (0..POLY_SIZE)
+        .map(|_| crate::field::rand_from_rng(&mut rng))
+        .collect();
+    let mut b: Vec<F> = (0..POLY_SIZE)
+        .map(|_| crate::field::rand_from_rng(&mut rng))
+        .collect();
+    let edge_values = [
+        F::ZERO,
+        F::ONE,
+        F::MINUS_ONE,
+        F::from_u64_unchecked(F::CHAR - 2),
+    ];
+    for (idx, (x, y)) in edge_values
+        .iter()
+        .flat_map(|x| edge_values.iter().map(move |y| (x, y)))
+        .enumerate()
+    {
+        a[idx] = *x;
+        b[idx] = *y;
+    }
+    let constant = F::MINUS_ONE;
+
+    let mut expected = a.clone();
+    for (a, b) in expected.iter_mut().zip(b.iter()) {
+        let mut t = *a;
+        Field::sub_assign(&mut t, b);
+        Field::mul_assign(a, b);
+        Field::add_assign(a, &t);
+        Field::square(a);
+        Field::negate(a);
+        Field::double(a);
+        Field::mul_assign(a, &constant);
+    }
+
+    let mut av = P::vec_from_base_vec(crate::utils::clone_respecting_allignment::<F, P, _>(&a));
+    let bv = P::vec_from_base_vec(crate::utils::clone_respecting_allignment::<F, P, _>(&b));
+    for (a, b) in av.iter_mut().zip(bv.iter()) {
+        let mut t = *a;
+        t.sub_assign(b, &mut ctx);
+        a.mul_assign(b, &mut ctx)
+            .add_assign(&t, &mut ctx)
+            .square(&mut ctx)
+            .negate(&mut ctx)
+            .double(&mut ctx)
+            .mul_all_by_base(&constant, &mut ctx);
+    }
+
+    assert_eq!(P::vec_into_base_vec(av), expected);
+}
+
 pub(crate) fn fft_natural_to_bitreversed<F: SmallField>(input: &mut [F], coset: F) {
     debug_assert!(input.len().is_power_of_two());
     let worker = Worker::new();
@@ -353,7 +744,9 @@ impl<'a, F: PrimeField, P: PrimeFieldLikeVectorized<Base = F>> Flattener<'a, F,
     }
 }
 
-impl<F: BaseField, E: FieldExtension<2, BaseField = F>> PrimeFieldLike for ExtensionField<F, 2, E> {
+impl<F: SmallField, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> PrimeFieldLike
+    for ExtensionField<F, DEGREE, E>
+{
     type Base = F;
     type Context = ();
     #[inline(always)]
@@ -398,10 +791,7 @@ impl<F: BaseField, E: FieldExtension<2, BaseField = F>> PrimeFieldLike for Exten
     }
     #[inline(always)]
     fn constant(value: F, _ctx: &mut Self::Context) -> Self {
-        Self {
-            coeffs: [value, F::ZERO],
-            _marker: std::marker::PhantomData,
-        }
+        Self::from_base(value)
     }
     // #[inline(always)]
     // fn mul_by_base(&'_ mut self, other: &F, _ctx: &mut Self::Context) -> &'_ mut Self {
@@ -619,41 +1009,65 @@ impl<P: PrimeFieldLike, E: PrimeFieldLikeExtension<2, BaseField = P>>
 pub fn mul_assign_vectorized_in_extension<
     F: PrimeField,
     P: PrimeFieldLikeVectorized<Base = F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
 >(
-    a_c0: &mut P,
-    a_c1: &mut P,
-    b_c0: &P,
-    b_c1: &P,
-    ctx: &mut (),
+    a: &mut [P; DEGREE],
+    b: &[P; DEGREE],
+    ctx: &mut P::Context,
 ) {
-    let mut v0 = *a_c0;
-    v0.mul_assign(&b_c0, ctx);
-    let mut v1 = *a_c1;
-    v1.mul_assign(&b_c1, ctx);
-    let t = *a_c0;
-    a_c1.add_assign(&t, ctx);
-    let mut t0 = *b_c0;
-    t0.add_assign(&b_c1, ctx);
-    a_c1.mul_assign(&t0, ctx);
-    a_c1.sub_assign(&v0, ctx);
-    a_c1.sub_assign(&v1, ctx);
-    *a_c0 = v0;
-    v1.mul_all_by_base(&EXT::non_residue(), ctx);
-    a_c0.add_assign(&v1, ctx);
+    if DEGREE == 2 {
+        let mut v0 = a[0];
+        v0.mul_assign(&b[0], ctx);
+        let mut v1 = a[1];
+        v1.mul_assign(&b[1], ctx);
+        let t = a[0];
+        a[1].add_assign(&t, ctx);
+        let mut t0 = b[0];
+        t0.add_assign(&b[1], ctx);
+        a[1].mul_assign(&t0, ctx);
+        a[1].sub_assign(&v0, ctx);
+        a[1].sub_assign(&v1, ctx);
+        a[0] = v0;
+        v1.mul_all_by_base(&EXT::non_residue(), ctx);
+        a[0].add_assign(&v1, ctx);
+
+        return;
+    }
+
+    // schoolbook, and x^DEGREE = non_residue for higher terms
+    let zero = P::zero(ctx);
+    let mut low = [zero; DEGREE];
+    let mut high = [zero; DEGREE];
+    for i in 0..DEGREE {
+        for j in 0..DEGREE {
+            let mut t = a[i];
+            t.mul_assign(&b[j], ctx);
+            if i + j < DEGREE {
+                low[i + j].add_assign(&t, ctx);
+            } else {
+                high[i + j - DEGREE].add_assign(&t, ctx);
+            }
+        }
+    }
+    for (dst, mut src) in low.iter_mut().zip(high.into_iter()) {
+        src.mul_all_by_base(&EXT::non_residue(), ctx);
+        dst.add_assign(&src, ctx);
+    }
+    *a = low;
 }
 
 #[inline]
-pub fn mul_assign_in_extension<F: PrimeField, EXT: FieldExtension<2, BaseField = F>>(
-    a_c0: &mut F,
-    a_c1: &mut F,
-    b_c0: &F,
-    b_c1: &F,
+pub fn mul_assign_in_extension<
+    F: PrimeField,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
+>(
+    a: &mut [F; DEGREE],
+    b: &[F; DEGREE],
 ) {
-    let mut a = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*a_c0, *a_c1]);
-    let b = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*b_c0, *b_c1]);
-    crate::field::Field::mul_assign(&mut a, &b);
-    let [c0, c1] = a.into_coeffs_in_base();
-    *a_c0 = c0;
-    *a_c1 = c1;
+    let mut result = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(*a);
+    let b = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(*b);
+    crate::field::Field::mul_assign(&mut result, &b);
+    *a = result.into_coeffs_in_base();
 }
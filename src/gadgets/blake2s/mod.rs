@@ -1,6 +1,10 @@
 use super::*;
 use crate::cs::traits::cs::ConstraintSystem;
+use crate::gadgets::impls::length_markers::LengthMarkers;
+use crate::gadgets::traits::selectable::Selectable;
+use crate::gadgets::u32::UInt32;
 use crate::gadgets::u8::UInt8;
+use mixing_function::Word;
 use std::mem::MaybeUninit;
 
 pub const BLAKE2S_ROUNDS: usize = 10;
@@ -36,94 +40,181 @@ pub fn blake2s<F: SmallField, CS: ConstraintSystem<F>>(
     cs: &mut CS,
     input: &[UInt8<F>],
 ) -> [UInt8<F>; BLAKE2S_DIGEST_SIZE] {
-    // create an initial state
-    use crate::cs::gates::ConstantAllocatableCS;
-    use crate::gadgets::blake2s::mixing_function::Word;
+    let mut hasher = Blake2sHasher::new(cs);
+    hasher.absorb(cs, input);
 
-    assert!(input.len() <= u32::MAX as usize);
+    hasher.finalize(cs)
+}
 
-    let input_len = input.len();
+// Hashes first `length` bytes of the `input`, where `length` is only known at proving time
+// and must not exceed `input.len()`. Cost is the same as for hashing of the full `input`
+pub fn blake2s_variable_length<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    input: &[UInt8<F>],
+    length: &UInt32<F>,
+) -> [UInt8<F>; BLAKE2S_DIGEST_SIZE] {
+    use self::round_function::*;
 
-    let mut state: [_; 8] = std::array::from_fn(|idx| {
-        let word = if idx == 0 { IV_0_TWIST } else { IV[idx] };
+    let markers = LengthMarkers::new(cs, &length.into_num(), input.len());
 
-        let le_bytes = word.to_le_bytes();
-        let le_bytes = le_bytes.map(|el| cs.allocate_constant(F::from_u64_unchecked(el as u64)));
+    let mut state = initial_state(cs);
+    let zero = UInt8::<F>::allocated_constant(cs, 0u8);
 
-        let state_word = unsafe {
-            Word {
-                inner: le_bytes.map(|el| UInt8::from_variable_unchecked(el)),
+    let mut num_blocks = input.len() / BLAKE2S_BLOCK_SIZE;
+    if input.len() % BLAKE2S_BLOCK_SIZE != 0 {
+        num_blocks += 1;
+    }
+    // empty input still requires one block
+    let num_blocks = std::cmp::max(num_blocks, 1);
+    let mut result = None;
+    for block_idx in 0..num_blocks {
+        let start = block_idx * BLAKE2S_BLOCK_SIZE;
+        // block is the last one if it contains the last byte, or if input is empty
+        let range = if block_idx == 0 {
+            0..(BLAKE2S_BLOCK_SIZE + 1)
+        } else {
+            (start + 1)..(start + BLAKE2S_BLOCK_SIZE + 1)
+        };
+        let is_last_block = markers.is_end_within(cs, range);
+
+        let mut block = [zero; BLAKE2S_BLOCK_SIZE];
+        for (offset, dst) in block.iter_mut().enumerate() {
+            let idx = start + offset;
+            if idx < input.len() {
+                let byte = markers.mask_element(cs, input[idx].get_variable(), idx);
+                *dst = unsafe { UInt8::from_variable_unchecked(byte) };
             }
+        }
+
+        let full_offset = UInt32::allocated_constant(cs, (start + BLAKE2S_BLOCK_SIZE) as u32);
+        let offset = UInt32::conditionally_select(cs, is_last_block, length, &full_offset);
+        let control = Blake2sControl::VariableLength {
+            offset,
+            is_last_block,
         };
 
-        state_word
-    });
+        blake2s_round_function(cs, &mut state, &words_from_block(&block), control);
+        let digest = digest_from_state(&state);
+
+        result = match result {
+            None => Some(digest),
+            Some(previous) => Some(Selectable::conditionally_select(
+                cs,
+                is_last_block,
+                &digest,
+                &previous,
+            )),
+        };
+    }
 
-    let mut num_rounds = input.len() / BLAKE2S_BLOCK_SIZE;
-    if input.len() % BLAKE2S_BLOCK_SIZE != 0 {
-        num_rounds += 1;
+    result.expect("at least one block is always processed")
+}
+
+// Incremental version of `blake2s` for inputs of lengths known at synthesis time.
+// The last block must be compressed with a special flag, so we only compress a full block
+// once some more input follows it
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct Blake2sHasher<F: SmallField> {
+    state: [Word<F>; STATE_WIDTH_IN_U32_WORDS],
+    buffer: Vec<UInt8<F>>,
+    absorbed_length: usize,
+}
+
+impl<F: SmallField> Blake2sHasher<F> {
+    pub fn new<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        Self {
+            state: initial_state(cs),
+            buffer: Vec::with_capacity(2 * BLAKE2S_BLOCK_SIZE),
+            absorbed_length: 0,
+        }
     }
 
-    use self::round_function::*;
+    pub fn absorb<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, input: &[UInt8<F>]) {
+        use self::round_function::*;
 
-    let mut input_chunks = input.array_chunks::<BLAKE2S_BLOCK_SIZE>();
-    let mut offset = 0;
-    if num_rounds > 1 {
-        for _round in 0..(num_rounds - 1) {
-            offset += BLAKE2S_BLOCK_SIZE as u32;
-
-            let chunk = input_chunks.next().unwrap();
-            let mut block = [MaybeUninit::<Word<F>>::uninit(); 16];
-            for (dst, src) in block.iter_mut().zip(chunk.array_chunks::<4>()) {
-                let word = Word { inner: *src };
-                dst.write(word);
-            }
+        self.absorbed_length += input.len();
+        assert!(self.absorbed_length <= u32::MAX as usize);
 
-            let block = unsafe { block.map(|el| el.assume_init()) };
+        self.buffer.extend_from_slice(input);
+        let mut num_processed = 0;
+        for block in self.buffer.array_chunks::<BLAKE2S_BLOCK_SIZE>() {
+            if num_processed + BLAKE2S_BLOCK_SIZE == self.buffer.len() {
+                // may be the last one
+                break;
+            }
+            num_processed += BLAKE2S_BLOCK_SIZE;
 
             let control = Blake2sControl::FixedLength {
-                offset,
+                offset: (self.absorbed_length - self.buffer.len() + num_processed) as u32,
                 is_last_block: false,
             };
 
-            blake2s_round_function(cs, &mut state, &block, control);
+            blake2s_round_function(cs, &mut self.state, &words_from_block(block), control);
         }
+        self.buffer.drain(..num_processed);
     }
 
-    // final block
-    let last_block = if let Some(full_chunk) = input_chunks.next() {
-        assert!(input_chunks.remainder().is_empty());
+    pub fn finalize<CS: ConstraintSystem<F>>(
+        mut self,
+        cs: &mut CS,
+    ) -> [UInt8<F>; BLAKE2S_DIGEST_SIZE] {
+        use self::round_function::*;
 
-        *full_chunk
-    } else {
+        // final block
+        let len = self.buffer.len();
+        assert!(len <= BLAKE2S_BLOCK_SIZE);
         let zero = UInt8::<F>::allocated_constant(cs, 0u8);
-        let mut full_buffer = [zero; BLAKE2S_BLOCK_SIZE];
-        let remainder = input_chunks.remainder();
-        let len = remainder.len();
-        assert!(len > 0);
-        assert!(len < BLAKE2S_BLOCK_SIZE);
-        full_buffer[..len].copy_from_slice(remainder);
-
-        full_buffer
-    };
+        let mut last_block = [zero; BLAKE2S_BLOCK_SIZE];
+        last_block[..len].copy_from_slice(&self.buffer);
 
-    let mut block = [MaybeUninit::<Word<F>>::uninit(); 16];
-    for (dst, src) in block.iter_mut().zip(last_block.array_chunks::<4>()) {
-        let word = Word { inner: *src };
-        dst.write(word);
+        let control = Blake2sControl::FixedLength {
+            offset: self.absorbed_length as u32,
+            is_last_block: true,
+        };
+
+        blake2s_round_function(cs, &mut self.state, &words_from_block(&last_block), control);
+
+        digest_from_state(&self.state)
     }
+}
 
-    let block = unsafe { block.map(|el| el.assume_init()) };
+fn initial_state<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+) -> [Word<F>; STATE_WIDTH_IN_U32_WORDS] {
+    use crate::cs::gates::ConstantAllocatableCS;
 
-    let control = Blake2sControl::FixedLength {
-        offset: input_len as u32,
-        is_last_block: true,
-    };
+    std::array::from_fn(|idx| {
+        let word = if idx == 0 { IV_0_TWIST } else { IV[idx] };
 
-    blake2s_round_function(cs, &mut state, &block, control);
+        let le_bytes = word.to_le_bytes();
+        let le_bytes = le_bytes.map(|el| cs.allocate_constant(F::from_u64_unchecked(el as u64)));
 
-    // copy back
+        let state_word = unsafe {
+            Word {
+                inner: le_bytes.map(|el| UInt8::from_variable_unchecked(el)),
+            }
+        };
 
+        state_word
+    })
+}
+
+fn words_from_block<F: SmallField>(
+    block: &[UInt8<F>; BLAKE2S_BLOCK_SIZE],
+) -> [Word<F>; BLOCK_WIDTH_IN_U32_WORDS] {
+    let mut words = [MaybeUninit::<Word<F>>::uninit(); BLOCK_WIDTH_IN_U32_WORDS];
+    for (dst, src) in words.iter_mut().zip(block.array_chunks::<4>()) {
+        let word = Word { inner: *src };
+        dst.write(word);
+    }
+
+    unsafe { words.map(|el| el.assume_init()) }
+}
+
+fn digest_from_state<F: SmallField>(
+    state: &[Word<F>; STATE_WIDTH_IN_U32_WORDS],
+) -> [UInt8<F>; BLAKE2S_DIGEST_SIZE] {
     let mut result = [MaybeUninit::<UInt8<F>>::uninit(); BLAKE2S_DIGEST_SIZE];
     for (dst, src) in result.array_chunks_mut::<4>().zip(state[..8].iter()) {
         for i in 0..4 {
@@ -253,4 +344,116 @@ mod test {
         let mut owned_cs = owned_cs.into_assembly();
         owned_cs.wait_for_witness();
     }
+
+    #[test]
+    fn test_variable_length_and_incremental() {
+        use crate::cs::gates::{
+            FmaGateInBaseFieldWithoutConstant, NopGate, ReductionGate, SelectionGate, ZeroCheckGate,
+        };
+        use crate::gadgets::u32::UInt32;
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42 as u64);
+
+        let max_len = 2 * 64 + 42;
+        let input: Vec<u8> = (0..max_len).map(|_| rng.gen()).collect();
+
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 20,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        use crate::config::DevCSConfig;
+        use crate::cs::cs_builder_reference::*;
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 20, 1 << 18);
+        use crate::cs::cs_builder::new_builder;
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 5,
+                share_table_id: true,
+            },
+        );
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = U32TriAddCarryAsChunkGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        // add tables
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+
+        let table = create_byte_split_table::<F, 4>();
+        owned_cs.add_lookup_table::<ByteSplitTable<4>, 3>(table);
+
+        let table = create_byte_split_table::<F, 7>();
+        owned_cs.add_lookup_table::<ByteSplitTable<7>, 3>(table);
+
+        let table = create_byte_split_table::<F, 1>();
+        owned_cs.add_lookup_table::<ByteSplitTable<1>, 3>(table);
+
+        let cs = &mut owned_cs;
+
+        let circuit_input: Vec<_> = input
+            .iter()
+            .map(|el| UInt8::allocate_checked(cs, *el))
+            .collect();
+
+        for len in [0, 1, 42, 63, 64, 65, 2 * 64, 2 * 64 + 1, max_len] {
+            let mut hasher = blake2::Blake2s256::new();
+            hasher.update(&input[..len]);
+            let reference_output = hex::encode(hasher.finalize().as_slice());
+
+            let length = UInt32::allocate_checked(cs, len as u32);
+            let output = blake2s_variable_length(cs, &circuit_input, &length);
+            let output = hex::encode(&(output.witness_hook(&*cs))().unwrap());
+            assert_eq!(output, reference_output, "failed for length {}", len);
+
+            // same bytes in few pieces
+            let mut hasher = Blake2sHasher::new(cs);
+            let (first, second) = circuit_input[..len].split_at(len / 3);
+            hasher.absorb(cs, first);
+            hasher.absorb(cs, &[]);
+            hasher.absorb(cs, second);
+            let output = hasher.finalize(cs);
+            let output = hex::encode(&(output.witness_hook(&*cs))().unwrap());
+            assert_eq!(output, reference_output, "failed for length {}", len);
+        }
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        use crate::worker::Worker;
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
 }
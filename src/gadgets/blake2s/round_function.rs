@@ -6,6 +6,7 @@ use crate::cs::gates::ConstantAllocatableCS;
 use crate::cs::traits::cs::ConstraintSystem;
 use crate::gadgets::blake2s::mixing_function::mixing_function_g;
 use crate::gadgets::boolean::Boolean;
+use crate::gadgets::num::Num;
 use crate::gadgets::u32::UInt32;
 
 pub enum Blake2sControl<F: SmallField> {
@@ -104,7 +105,50 @@ pub fn blake2s_round_function<F: SmallField, CS: ConstraintSystem<F>>(
                 local_work_vector[14] = new_v14;
             }
         }
-        _ => unimplemented!(),
+        Blake2sControl::VariableLength {
+            offset,
+            is_last_block,
+        } => {
+            let offset = offset.decompose_into_bytes(cs);
+
+            let new_v12 = xor_many(
+                cs,
+                &local_work_vector[12].inner.map(|el| el.variable),
+                &offset.map(|el| el.variable),
+            );
+
+            let new_v12 = unsafe {
+                Word {
+                    inner: new_v12.map(|el| UInt8::<F>::from_variable_unchecked(el)),
+                }
+            };
+
+            local_work_vector[12] = new_v12;
+
+            // mask is 0xff or 0x00 depending on the flag
+            let mask_byte = Num::linear_combination(
+                cs,
+                &[(
+                    is_last_block.get_variable(),
+                    F::from_u64_unchecked(0xffu8 as u64),
+                )],
+            );
+            let mask = [mask_byte.get_variable(); 4];
+
+            let new_v14 = xor_many(
+                cs,
+                &local_work_vector[14].inner.map(|el| el.variable),
+                &mask,
+            );
+
+            let new_v14 = unsafe {
+                Word {
+                    inner: new_v14.map(|el| UInt8::<F>::from_variable_unchecked(el)),
+                }
+            };
+
+            local_work_vector[14] = new_v14;
+        }
     }
 
     for round_idx in 0..BLAKE2S_ROUNDS {
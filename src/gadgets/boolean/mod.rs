@@ -150,6 +150,18 @@ impl<F: SmallField> Selectable<F> for Boolean<F> {
     }
 }
 
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+
+impl<F: SmallField> CircuitEq<F> for Boolean<F> {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Boolean::equals(cs, a, b)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        Boolean::enforce_equal(cs, a, b)
+    }
+}
+
 pub const BOOLEAN_NEGATION_LOOKUP_TOOLING: &'static str = "Boolean negation tooling";
 
 #[derive(Derivative)]
@@ -702,3 +714,43 @@ impl<F: SmallField> CircuitVarLengthEncodable<F> for Boolean<F> {
         dst.push(self.variable);
     }
 }
+
+use crate::gadgets::traits::encodable::{
+    CircuitDecodable, CircuitEncodable, CircuitEncodingLayout,
+};
+
+impl<F: SmallField> CircuitEncodable<F, 1> for Boolean<F> {
+    #[inline(always)]
+    fn encode<CS: ConstraintSystem<F>>(&self, _cs: &mut CS) -> [Variable; 1] {
+        [self.variable]
+    }
+}
+
+impl<F: SmallField> CircuitDecodable<F, 1> for Boolean<F> {
+    #[inline(always)]
+    fn decode<CS: ConstraintSystem<F>>(cs: &mut CS, encoding: [Variable; 1]) -> Self {
+        Self::from_variable_checked(cs, encoding[0])
+    }
+}
+
+use crate::gadgets::traits::public_input::CSPublicInput;
+
+impl<F: SmallField> CSPublicInput<F, 1> for Boolean<F> {
+    #[inline(always)]
+    fn encode_witness(witness: &Self::Witness) -> [F; 1] {
+        [F::from_u64_unchecked(*witness as u64)]
+    }
+    #[inline(always)]
+    fn decode_witness(encoding: [F; 1]) -> Option<Self::Witness> {
+        match encoding[0].as_u64_reduced() {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl<F: SmallField> CircuitEncodingLayout<F> for Boolean<F> {
+    const ENCODING_LENGTH: usize = 1;
+    const PACKED_WIDTH: usize = 1;
+}
@@ -0,0 +1,356 @@
+use super::*;
+use crate::algebraic_props::round_function::{AbsorptionModeOverwrite, AlgebraicRoundFunction};
+use crate::cs::gates::{ConstantAllocatableCS, PublicInputGate};
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::field::goldilocks::GoldilocksField;
+use crate::gadgets::keccak256::keccak256;
+use crate::gadgets::num::Num;
+use crate::gadgets::recursion::recursive_tree_hasher::goldilocks_num_into_le_bytes;
+use crate::gadgets::sha256::sha256;
+use crate::gadgets::traits::encodable::CircuitVarLengthEncodable;
+use crate::gadgets::traits::round_function::CircuitRoundFunction;
+use crate::gadgets::u8::UInt8;
+
+// Commitments to the large outputs of the circuit, that are exposed as public inputs instead
+// of the outputs themselves. Input is the encoding of the `CircuitVarLengthEncodable` structure,
+// i.e. a sequence of field elements `e_0, ..., e_{n-1}`.
+//
+// Algebraic commitment with the round function that has rate `AW`:
+// - state is all zeroes, with the last element set to `n` (same as `specialize_for_len`)
+// - encoding is padded by zeroes to the non-zero multiple of `AW`
+// - every chunk of `AW` elements overwrites the first `AW` elements of the state, and then
+//   the round function is applied
+// - commitment is the first `N <= AW` elements of the state
+//
+// Byte-oriented commitment with keccak256 or sha256:
+// - every element is serialized as 8 bytes of its canonical value in big endian, i.e.
+//   as `abi.encodePacked(uint64(e_0), ..., uint64(e_{n-1}))` in Solidity
+// - digest is the hash of the concatenation
+// - digest is truncated to fit into `N` field elements: every element is a big endian integer
+//   formed from the next `F::CAPACITY_BITS / 8` bytes of the digest, starting from the first
+//   byte, and remaining bytes are dropped
+
+pub const fn digest_bytes_per_element<F: SmallField>() -> usize {
+    F::CAPACITY_BITS / 8
+}
+
+fn encode_into_nums<F: SmallField, CS: ConstraintSystem<F>, T: CircuitVarLengthEncodable<F>>(
+    cs: &mut CS,
+    value: &T,
+) -> Vec<Num<F>> {
+    let mut encoding = Vec::with_capacity(value.encoding_length());
+    value.encode_to_buffer(cs, &mut encoding);
+
+    encoding.into_iter().map(Num::from_variable).collect()
+}
+
+pub fn commit_with_round_function<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    T: CircuitVarLengthEncodable<F>,
+    R: CircuitRoundFunction<F, AW, SW, CW> + AlgebraicRoundFunction<F, AW, SW, CW>,
+    const AW: usize,
+    const SW: usize,
+    const CW: usize,
+    const N: usize,
+>(
+    cs: &mut CS,
+    value: &T,
+) -> [Num<F>; N] {
+    assert!(N > 0 && N <= AW);
+
+    let mut encoding = Vec::with_capacity(value.encoding_length());
+    value.encode_to_buffer(cs, &mut encoding);
+
+    let length = cs.allocate_constant(F::from_u64_unchecked(encoding.len() as u64));
+    let mut state = R::create_empty_state(cs);
+    R::apply_length_specialization(cs, &mut state, length);
+
+    let zero = cs.allocate_constant(F::ZERO);
+    let num_rounds = std::cmp::max((encoding.len() + AW - 1) / AW, 1);
+    let mut elements_source = encoding.into_iter();
+    for _ in 0..num_rounds {
+        let mut to_absorb = [zero; AW];
+        for (dst, src) in to_absorb.iter_mut().zip(&mut elements_source) {
+            *dst = src;
+        }
+
+        let capacity_elements = R::split_capacity_elements(&state);
+        state = R::absorb_with_replacement(cs, to_absorb, capacity_elements);
+        state = R::compute_round_function(cs, state);
+    }
+
+    <R as CircuitRoundFunction<F, AW, SW, CW>>::state_into_commitment::<N>(&state)
+        .map(Num::from_variable)
+}
+
+fn commit_bytes_with_hash<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    T: CircuitVarLengthEncodable<F>,
+    const N: usize,
+>(
+    cs: &mut CS,
+    value: &T,
+    hash_fn: impl FnOnce(&mut CS, &[UInt8<F>]) -> [UInt8<F>; 32],
+) -> [Num<F>; N] {
+    let bytes_per_element = digest_bytes_per_element::<F>();
+    assert!(N > 0 && N * bytes_per_element <= 32);
+
+    let encoding = encode_into_nums(cs, value);
+    let mut bytes = Vec::with_capacity(encoding.len() * 8);
+    for el in encoding.iter() {
+        let mut le_bytes = goldilocks_num_into_le_bytes(cs, el);
+        le_bytes.reverse();
+        bytes.extend(le_bytes);
+    }
+
+    let digest = hash_fn(cs, &bytes);
+
+    std::array::from_fn(|idx| {
+        let chunk = &digest[(idx * bytes_per_element)..((idx + 1) * bytes_per_element)];
+        let terms: Vec<_> = chunk
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(shift, byte)| (byte.get_variable(), F::SHIFTS[shift * 8]))
+            .collect();
+
+        Num::linear_combination(cs, &terms)
+    })
+}
+
+pub fn commit_with_keccak256<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    T: CircuitVarLengthEncodable<F>,
+    const N: usize,
+>(
+    cs: &mut CS,
+    value: &T,
+) -> [Num<F>; N] {
+    commit_bytes_with_hash(cs, value, |cs, bytes| keccak256(cs, bytes))
+}
+
+pub fn commit_with_sha256<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    T: CircuitVarLengthEncodable<F>,
+    const N: usize,
+>(
+    cs: &mut CS,
+    value: &T,
+) -> [Num<F>; N] {
+    commit_bytes_with_hash(cs, value, |cs, bytes| sha256(cs, bytes))
+}
+
+pub fn expose_commitment<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    commitment: &[Num<F>; N],
+) {
+    for el in commitment.iter() {
+        let gate = PublicInputGate::new(el.get_variable());
+        gate.add_to_cs(cs);
+    }
+}
+
+// Native mirrors that take the values of the encoding
+
+pub fn simulate_commitment_with_round_function<
+    F: SmallField,
+    R: AlgebraicRoundFunction<F, AW, SW, CW>,
+    const AW: usize,
+    const SW: usize,
+    const CW: usize,
+    const N: usize,
+>(
+    encoding: &[F],
+) -> [F; N] {
+    assert!(N > 0 && N <= AW);
+
+    let mut state = R::initial_state();
+    R::specialize_for_len(encoding.len() as u32, &mut state);
+
+    let num_rounds = std::cmp::max((encoding.len() + AW - 1) / AW, 1);
+    let mut elements_source = encoding.iter().copied();
+    for _ in 0..num_rounds {
+        let mut to_absorb = [F::ZERO; AW];
+        for (dst, src) in to_absorb.iter_mut().zip(&mut elements_source) {
+            *dst = src;
+        }
+
+        R::absorb_into_state::<AbsorptionModeOverwrite>(&mut state, &to_absorb);
+        R::round_function(&mut state);
+    }
+
+    R::state_into_commitment::<N>(&state)
+}
+
+fn simulate_commitment_with_digest<F: SmallField, const N: usize>(digest: &[u8]) -> [F; N] {
+    let bytes_per_element = digest_bytes_per_element::<F>();
+    assert!(N > 0 && N * bytes_per_element <= digest.len());
+
+    std::array::from_fn(|idx| {
+        let mut value = 0u64;
+        for byte in digest[(idx * bytes_per_element)..((idx + 1) * bytes_per_element)].iter() {
+            value = (value << 8) | (*byte as u64);
+        }
+
+        F::from_u64_unchecked(value)
+    })
+}
+
+pub fn commitment_preimage_bytes<F: SmallField>(encoding: &[F]) -> Vec<u8> {
+    assert_eq!(F::CHAR, GoldilocksField::CHAR);
+
+    encoding
+        .iter()
+        .flat_map(|el| el.as_u64_reduced().to_be_bytes())
+        .collect()
+}
+
+pub fn simulate_commitment_with_keccak256<F: SmallField, const N: usize>(encoding: &[F]) -> [F; N] {
+    use sha3::Digest;
+
+    let digest = sha3::Keccak256::digest(commitment_preimage_bytes(encoding));
+
+    simulate_commitment_with_digest(digest.as_slice())
+}
+
+pub fn simulate_commitment_with_sha256<F: SmallField, const N: usize>(encoding: &[F]) -> [F; N] {
+    use sha2::Digest;
+
+    let digest = sha2::Sha256::digest(commitment_preimage_bytes(encoding));
+
+    simulate_commitment_with_digest(digest.as_slice())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::*;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, LookupParameters};
+    use crate::field::{Field, U64Representable};
+    use crate::gadgets::tables::{
+        and8::{create_and8_table, And8Table},
+        byte_split::{create_byte_split_table, ByteSplitTable},
+        xor8::{create_xor8_table, Xor8Table},
+    };
+    use crate::gadgets::traits::allocatable::CSAllocatable;
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::implementations::poseidon2::Poseidon2Goldilocks;
+    use crate::worker::Worker;
+
+    type F = GoldilocksField;
+
+    #[test]
+    fn test_commitments() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 130,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 8,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 20, 1 << 16);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = builder.allow_lookup(
+            LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 5,
+                share_table_id: true,
+            },
+        );
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = Poseidon2FlattenedGate::<F, 8, 12, 4, Poseidon2Goldilocks>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder = PublicInputGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+        let table = create_and8_table();
+        owned_cs.add_lookup_table::<And8Table, 3>(table);
+        let table = create_byte_split_table::<F, 1>();
+        owned_cs.add_lookup_table::<ByteSplitTable<1>, 3>(table);
+        let table = create_byte_split_table::<F, 2>();
+        owned_cs.add_lookup_table::<ByteSplitTable<2>, 3>(table);
+        let table = create_byte_split_table::<F, 3>();
+        owned_cs.add_lookup_table::<ByteSplitTable<3>, 3>(table);
+        let table = create_byte_split_table::<F, 4>();
+        owned_cs.add_lookup_table::<ByteSplitTable<4>, 3>(table);
+        let cs = &mut owned_cs;
+
+        // non-multiple of the rate, and includes the largest canonical element
+        let values: Vec<F> = (0..19u64)
+            .map(|el| F::from_u64_unchecked(el * 0x0123_4567_89ab_cdef % F::CHAR))
+            .chain(std::iter::once(F::MINUS_ONE))
+            .collect();
+        let output: Vec<_> = values.iter().map(|el| Num::allocate(cs, *el)).collect();
+        let output: [Num<F>; 20] = output.try_into().unwrap();
+
+        let commitment =
+            commit_with_round_function::<F, _, _, Poseidon2Goldilocks, 8, 12, 4, 4>(cs, &output);
+        let expected =
+            simulate_commitment_with_round_function::<F, Poseidon2Goldilocks, 8, 12, 4, 4>(&values);
+        assert_eq!(commitment.witness_hook(&*cs)().unwrap(), expected);
+        expose_commitment(cs, &commitment);
+
+        let commitment = commit_with_keccak256::<F, _, _, 4>(cs, &output);
+        let expected = simulate_commitment_with_keccak256::<F, 4>(&values);
+        assert_eq!(commitment.witness_hook(&*cs)().unwrap(), expected);
+        expose_commitment(cs, &commitment);
+
+        // first 7 bytes of the digest as big endian integer
+        let digest = {
+            use sha3::Digest;
+            sha3::Keccak256::digest(commitment_preimage_bytes(&values))
+        };
+        let mut first_element = 0u64;
+        for byte in digest[..7].iter() {
+            first_element = (first_element << 8) | (*byte as u64);
+        }
+        assert_eq!(expected[0], F::from_u64_unchecked(first_element));
+
+        let worker = Worker::new();
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
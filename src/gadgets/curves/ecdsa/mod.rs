@@ -0,0 +1,827 @@
+use super::*;
+
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::curves::scalar_mul::multi_scalar_mul;
+use crate::gadgets::curves::sw_projective::SWProjectivePoint;
+use crate::gadgets::curves::zeroable_affine::ZeroableAffinePoint;
+use crate::gadgets::keccak256::keccak256;
+use crate::gadgets::non_native_field::implementations::*;
+use crate::gadgets::num::Num;
+use crate::gadgets::traits::selectable::Selectable;
+use crate::gadgets::u16::UInt16;
+use crate::gadgets::u160::UInt160;
+use crate::gadgets::u256::UInt256;
+use crate::gadgets::u32::UInt32;
+use crate::gadgets::u8::UInt8;
+use ethereum_types::U256;
+use pairing::ff::{Field, PrimeField};
+use pairing::GenericCurveAffine;
+use std::sync::Arc;
+
+// ECDSA over short Weierstrass curves of prime order with zero `a` coefficient (e.g. secp256k1),
+// with both base and scalar fields represented by 16 limbs of 16 bits, so conversion from and to
+// `UInt256` is just a regrouping of limbs. All the functions below never make the circuit unsatisfiable
+// for malformed inputs (zero or out of range `r` and `s`, public keys that are not on the curve, etc),
+// but instead return a `Boolean` that tells whether the signature is valid.
+
+const WINDOW_WIDTH: usize = 4;
+
+fn modulus_as_u256<T: PrimeField>() -> U256 {
+    assert!(T::NUM_BITS <= 256);
+    let mut result = U256::zero();
+    for (dst, src) in result.0.iter_mut().zip(T::char().as_ref().iter()) {
+        *dst = *src;
+    }
+
+    result
+}
+
+// Returns `0 < value < modulus`
+fn is_in_range_exclusive<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    value: &UInt256<F>,
+    modulus: &UInt256<F>,
+) -> Boolean<F> {
+    let (_, is_less_than_modulus) = value.overflowing_sub(cs, modulus);
+    let is_zero = value.is_zero(cs);
+    let is_non_zero = is_zero.negated(cs);
+
+    Boolean::multi_and(cs, &[is_less_than_modulus, is_non_zero])
+}
+
+/// Interprets 256-bit integer as a (not necessarily reduced) element of the non-native field
+pub fn uint256_into_non_native<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    T: PrimeField,
+    const N: usize,
+>(
+    cs: &mut CS,
+    value: &UInt256<F>,
+    params: &Arc<NonNativeFieldOverU16Params<T, N>>,
+) -> NonNativeFieldOverU16<F, T, N>
+where
+    [(); N + 1]:,
+{
+    assert_eq!(params.modulus_limbs, 16);
+
+    let zero_var = cs.allocate_constant(F::ZERO);
+    let mut limbs = [zero_var; N];
+    for (dst, word) in limbs.array_chunks_mut::<2>().zip(value.inner.iter()) {
+        let [b0, b1, b2, b3] = word.decompose_into_bytes(cs);
+        dst[0] = UInt16::from_le_bytes(cs, [b0, b1]).get_variable();
+        dst[1] = UInt16::from_le_bytes(cs, [b2, b3]).get_variable();
+    }
+
+    NonNativeFieldOverU16 {
+        limbs,
+        non_zero_limbs: params.modulus_limbs,
+        tracker: OverflowTracker {
+            max_moduluses: params.max_mods_in_allocation,
+        },
+        form: RepresentationForm::Normalized,
+        params: params.clone(),
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// Returns canonical (strictly less than the modulus) 256-bit integer representation of the
+/// non-native field element
+pub fn non_native_into_uint256<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    T: PrimeField,
+    const N: usize,
+>(
+    cs: &mut CS,
+    value: &mut NonNativeFieldOverU16<F, T, N>,
+) -> UInt256<F>
+where
+    [(); N + 1]:,
+{
+    assert_eq!(value.params.modulus_limbs, 16);
+
+    // normalized limbs are range checked to 16 bits, but value itself is only guaranteed to be
+    // at most the modulus
+    value.normalize(cs);
+    let shift = F::from_u64_unchecked(1u64 << 16);
+    let mut inner = [UInt32::zero(cs); 8];
+    for (dst, [low, high]) in inner.iter_mut().zip(value.limbs.array_chunks::<2>()) {
+        let word = Num::linear_combination(cs, &[(*low, F::ONE), (*high, shift)]);
+        *dst = unsafe { UInt32::from_variable_unchecked(word.get_variable()) };
+    }
+    let result = UInt256 { inner };
+
+    let modulus = UInt256::allocated_constant(cs, modulus_as_u256::<T>());
+    let (_, is_less_than_modulus) = result.overflowing_sub(cs, &modulus);
+    let boolean_true = Boolean::allocated_constant(cs, true);
+    Boolean::enforce_equal(cs, &is_less_than_modulus, &boolean_true);
+
+    result
+}
+
+// Returns `x^3 + b`
+fn curve_equation_rhs<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    C: GenericCurveAffine,
+    const N: usize,
+>(
+    cs: &mut CS,
+    x: &mut NonNativeFieldOverU16<F, C::Base, N>,
+) -> NonNativeFieldOverU16<F, C::Base, N>
+where
+    C::Base: PrimeField,
+    [(); N + 1]:,
+{
+    let params = x.params.clone();
+    let mut b = NonNativeFieldOverU16::allocated_constant(cs, C::b_coeff(), &params);
+    let mut x_squared = x.square(cs);
+    let mut x_cubed = x_squared.mul(cs, x);
+
+    x_cubed.add(cs, &mut b)
+}
+
+// Returns `y^2 == x^3 + b`
+fn is_on_curve<F: SmallField, CS: ConstraintSystem<F>, C: GenericCurveAffine, const N: usize>(
+    cs: &mut CS,
+    x: &mut NonNativeFieldOverU16<F, C::Base, N>,
+    y: &mut NonNativeFieldOverU16<F, C::Base, N>,
+) -> Boolean<F>
+where
+    C::Base: PrimeField,
+    [(); N + 1]:,
+{
+    let mut rhs = curve_equation_rhs::<F, CS, C, N>(cs, x);
+    let mut lhs = y.square(cs);
+    let mut diff = lhs.sub(cs, &mut rhs);
+    let diff = non_native_into_uint256(cs, &mut diff);
+
+    diff.is_zero(cs)
+}
+
+/// Verifies ECDSA signature `(r, s)` of the message hash by the public key given as affine coordinates.
+/// Signature is considered invalid if `r` or `s` are not in the range `[1, n)`, public key coordinates
+/// are not in the range `[0, p)` or public key is not on the curve. No malleability check on `s` is performed
+pub fn ecdsa_verify<F: SmallField, CS: ConstraintSystem<F>, C: GenericCurveAffine, const N: usize>(
+    cs: &mut CS,
+    message_hash: &UInt256<F>,
+    r: &UInt256<F>,
+    s: &UInt256<F>,
+    public_key: (&UInt256<F>, &UInt256<F>),
+    base_field_params: &Arc<NonNativeFieldOverU16Params<C::Base, N>>,
+    scalar_field_params: &Arc<NonNativeFieldOverU16Params<C::Scalar, N>>,
+) -> Boolean<F>
+where
+    C::Base: PrimeField,
+    [(); N + 1]:,
+{
+    let scalar_modulus = UInt256::allocated_constant(cs, modulus_as_u256::<C::Scalar>());
+    let base_modulus = UInt256::allocated_constant(cs, modulus_as_u256::<C::Base>());
+
+    let r_is_valid = is_in_range_exclusive(cs, r, &scalar_modulus);
+    let s_is_valid = is_in_range_exclusive(cs, s, &scalar_modulus);
+
+    // coordinates can be zero, but (0, 0) is never on the curve as b != 0
+    let (pk_x, pk_y) = public_key;
+    let (_, pk_x_is_valid) = pk_x.overflowing_sub(cs, &base_modulus);
+    let (_, pk_y_is_valid) = pk_y.overflowing_sub(cs, &base_modulus);
+    let mut x = uint256_into_non_native(cs, pk_x, base_field_params);
+    let mut y = uint256_into_non_native(cs, pk_y, base_field_params);
+    let pk_is_on_curve = is_on_curve::<F, CS, C, N>(cs, &mut x, &mut y);
+
+    let mut e = uint256_into_non_native(cs, message_hash, scalar_field_params);
+    let mut r_fe = uint256_into_non_native(cs, r, scalar_field_params);
+    let s_fe = uint256_into_non_native(cs, s, scalar_field_params);
+
+    // substitute safe value to avoid unsatisfiable inversion of zero
+    let scalar_one =
+        NonNativeFieldOverU16::allocated_constant(cs, C::Scalar::one(), scalar_field_params);
+    let mut s_fe = NonNativeFieldOverU16::conditionally_select(cs, s_is_valid, &s_fe, &scalar_one);
+    let mut s_inv = s_fe.inverse_unchecked(cs);
+    let u1 = e.mul(cs, &mut s_inv);
+    let u2 = r_fe.mul(cs, &mut s_inv);
+
+    let generator: SWProjectivePoint<F, C, NonNativeFieldOverU16<F, C::Base, N>> =
+        SWProjectivePoint::allocated_constant(cs, C::one(), base_field_params);
+    let public_key_point = SWProjectivePoint::from_xy_unchecked(cs, x, y);
+    let mut points = [generator, public_key_point];
+    let mut scalars = [u1, u2];
+    let mut result = multi_scalar_mul::<_, _, _, _, _, WINDOW_WIDTH>(cs, &mut points, &mut scalars);
+    let ((mut result_x, _), result_is_infinity) = result.convert_to_affine_or_default(cs, C::one());
+    let result_is_finite = result_is_infinity.negated(cs);
+
+    // x coordinate of the result is in the range [0, p), so it may be either `r` or `r + n`
+    let result_x = non_native_into_uint256(cs, &mut result_x);
+    let x_equals_r = UInt256::equals(cs, &result_x, r);
+    let (r_plus_n, overflow) = r.overflowing_add(cs, &scalar_modulus);
+    let no_overflow = overflow.negated(cs);
+    let x_equals_r_plus_n = UInt256::equals(cs, &result_x, &r_plus_n);
+    let x_equals_r_plus_n = Boolean::multi_and(cs, &[x_equals_r_plus_n, no_overflow]);
+    let x_matches = Boolean::multi_or(cs, &[x_equals_r, x_equals_r_plus_n]);
+
+    Boolean::multi_and(
+        cs,
+        &[
+            r_is_valid,
+            s_is_valid,
+            pk_x_is_valid,
+            pk_y_is_valid,
+            pk_is_on_curve,
+            result_is_finite,
+            x_matches,
+        ],
+    )
+}
+
+/// Recovers public key from ECDSA signature `(r, s)` of the message hash and the recovery id
+/// (`0` or `1`, that is the parity of the y coordinate of the ephemeral point). Only the recovery
+/// ids that correspond to the ephemeral point having x coordinate equal to `r` are supported, so
+/// ids `2` and `3` are treated as invalid. If signature is invalid, then returned coordinates are zeroes
+pub fn ecrecover<F: SmallField, CS: ConstraintSystem<F>, C: GenericCurveAffine, const N: usize>(
+    cs: &mut CS,
+    message_hash: &UInt256<F>,
+    r: &UInt256<F>,
+    s: &UInt256<F>,
+    recovery_id: &UInt8<F>,
+    base_field_params: &Arc<NonNativeFieldOverU16Params<C::Base, N>>,
+    scalar_field_params: &Arc<NonNativeFieldOverU16Params<C::Scalar, N>>,
+) -> (Boolean<F>, (UInt256<F>, UInt256<F>))
+where
+    C::Base: PrimeField,
+    [(); N + 1]:,
+{
+    // we reinterpret `r` as x coordinate, so it must fit
+    assert!(*scalar_field_params.modulus_u1024 < *base_field_params.modulus_u1024);
+
+    let scalar_modulus = UInt256::allocated_constant(cs, modulus_as_u256::<C::Scalar>());
+
+    let r_is_valid = is_in_range_exclusive(cs, r, &scalar_modulus);
+    let s_is_valid = is_in_range_exclusive(cs, s, &scalar_modulus);
+
+    let recovery_id_is_zero = recovery_id.is_zero(cs);
+    let one_u8 = UInt8::allocated_constant(cs, 1);
+    let recovery_id_is_one = UInt8::equals(cs, recovery_id, &one_u8);
+    let recovery_id_is_valid = Boolean::multi_or(cs, &[recovery_id_is_zero, recovery_id_is_one]);
+
+    let x = uint256_into_non_native(cs, r, base_field_params);
+    let (ephemeral_point, point_exists) =
+        ZeroableAffinePoint::<F, C, _>::decompress(cs, &x, recovery_id_is_one);
+    let ephemeral_point = ephemeral_point.to_projective(cs);
+
+    let mut e = uint256_into_non_native(cs, message_hash, scalar_field_params);
+    let r_fe = uint256_into_non_native(cs, r, scalar_field_params);
+    let mut s_fe = uint256_into_non_native(cs, s, scalar_field_params);
+
+    // substitute safe value to avoid unsatisfiable inversion of zero
+    let scalar_one =
+        NonNativeFieldOverU16::allocated_constant(cs, C::Scalar::one(), scalar_field_params);
+    let mut r_fe = NonNativeFieldOverU16::conditionally_select(cs, r_is_valid, &r_fe, &scalar_one);
+    let mut r_inv = r_fe.inverse_unchecked(cs);
+    let mut u1 = e.mul(cs, &mut r_inv);
+    let u1 = u1.negated(cs);
+    let u2 = s_fe.mul(cs, &mut r_inv);
+
+    // Q = r^-1 * (s * R - e * G)
+    let generator: SWProjectivePoint<F, C, NonNativeFieldOverU16<F, C::Base, N>> =
+        SWProjectivePoint::allocated_constant(cs, C::one(), base_field_params);
+    let mut points = [generator, ephemeral_point];
+    let mut scalars = [u1, u2];
+    let mut result = multi_scalar_mul::<_, _, _, _, _, WINDOW_WIDTH>(cs, &mut points, &mut scalars);
+    let ((mut result_x, mut result_y), result_is_infinity) =
+        result.convert_to_affine_or_default(cs, C::one());
+    let result_is_finite = result_is_infinity.negated(cs);
+
+    let is_valid = Boolean::multi_and(
+        cs,
+        &[
+            r_is_valid,
+            s_is_valid,
+            recovery_id_is_valid,
+            point_exists,
+            result_is_finite,
+        ],
+    );
+
+    let result_x = non_native_into_uint256(cs, &mut result_x);
+    let result_y = non_native_into_uint256(cs, &mut result_y);
+    let result_x = result_x.mask(cs, is_valid);
+    let result_y = result_y.mask(cs, is_valid);
+
+    (is_valid, (result_x, result_y))
+}
+
+/// Ethereum address of the public key: last 20 bytes of keccak256 of the concatenation of
+/// big-endian encodings of coordinates
+pub fn public_key_to_address<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    public_key: (&UInt256<F>, &UInt256<F>),
+) -> UInt160<F> {
+    let (x, y) = public_key;
+    let mut bytes = Vec::with_capacity(64);
+    bytes.extend(x.to_be_bytes(cs));
+    bytes.extend(y.to_be_bytes(cs));
+    let digest = keccak256(cs, &bytes);
+
+    let mut inner = [UInt32::zero(cs); 5];
+    for (dst, src) in inner.iter_mut().zip(digest[12..].array_chunks::<4>().rev()) {
+        *dst = UInt32::from_be_bytes(cs, *src);
+    }
+
+    UInt160 { inner }
+}
+
+/// Same as `ecrecover`, but returns Ethereum address of the recovered public key. Address is zero
+/// if signature is invalid
+pub fn ecrecover_address<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    C: GenericCurveAffine,
+    const N: usize,
+>(
+    cs: &mut CS,
+    message_hash: &UInt256<F>,
+    r: &UInt256<F>,
+    s: &UInt256<F>,
+    recovery_id: &UInt8<F>,
+    base_field_params: &Arc<NonNativeFieldOverU16Params<C::Base, N>>,
+    scalar_field_params: &Arc<NonNativeFieldOverU16Params<C::Scalar, N>>,
+) -> (Boolean<F>, UInt160<F>)
+where
+    C::Base: PrimeField,
+    [(); N + 1]:,
+{
+    let (is_valid, (x, y)) = ecrecover::<F, CS, C, N>(
+        cs,
+        message_hash,
+        r,
+        s,
+        recovery_id,
+        base_field_params,
+        scalar_field_params,
+    );
+    let address = public_key_to_address(cs, (&x, &y));
+    let address = address.mask(cs, is_valid);
+
+    (is_valid, address)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, StaticToolboxHolder};
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::curves::secp256k1::PointAffine as Secp256k1;
+    use crate::gadgets::tables::{
+        and8::{create_and8_table, And8Table},
+        byte_split::{create_byte_split_table, ByteSplitTable},
+        xor8::{create_xor8_table, Xor8Table},
+    };
+    use crate::gadgets::traits::allocatable::CSAllocatable;
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+    use ethereum_types::Address;
+    use pairing::bn256::{Fq, Fr, G1Affine};
+    use pairing::ff::PrimeFieldRepr;
+    use pairing::ff::{LegendreSymbol, SqrtField};
+    use pairing::GenericCurveProjective;
+    use sha3::Digest;
+
+    // BN254 G1 is also a curve of prime order with zero `a` coefficient and scalar field modulus
+    // being less than the base field one, so most of the tests are over it, as `pairing` implementation
+    // is much faster than the test one for secp256k1. Fixed secp256k1 vectors are at the end
+    type F = GoldilocksField;
+    type C = G1Affine;
+    const N: usize = 17;
+
+    fn create_test_cs() -> CSReferenceImplementation<
+        F,
+        F,
+        DevCSConfig,
+        impl GateConfigurationHolder<F>,
+        impl StaticToolboxHolder,
+    > {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 26, 1 << 22);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 8,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = DotProductGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<32>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<16>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ParallelSelectionGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        // 16-bit range checks of non-native field limbs fall back to the 8x8 table
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+
+        let table = create_and8_table();
+        owned_cs.add_lookup_table::<And8Table, 3>(table);
+
+        let table = create_byte_split_table::<F, 1>();
+        owned_cs.add_lookup_table::<ByteSplitTable<1>, 3>(table);
+        let table = create_byte_split_table::<F, 2>();
+        owned_cs.add_lookup_table::<ByteSplitTable<2>, 3>(table);
+        let table = create_byte_split_table::<F, 3>();
+        owned_cs.add_lookup_table::<ByteSplitTable<3>, 3>(table);
+        let table = create_byte_split_table::<F, 4>();
+        owned_cs.add_lookup_table::<ByteSplitTable<4>, 3>(table);
+
+        owned_cs
+    }
+
+    fn fe_to_u256<T: PrimeField>(value: &T) -> U256 {
+        let repr = value.into_repr();
+        let mut result = U256::zero();
+        for (dst, src) in result.0.iter_mut().zip(repr.as_ref().iter()) {
+            *dst = *src;
+        }
+
+        result
+    }
+
+    fn u256_to_scalar(value: U256) -> Fr {
+        let two_to_64 = Fr::from_str("18446744073709551616").unwrap();
+        let mut result = Fr::zero();
+        let mut shift = Fr::one();
+        for limb in value.0.iter() {
+            let mut tmp = Fr::from_str(&limb.to_string()).unwrap();
+            tmp.mul_assign(&shift);
+            result.add_assign(&tmp);
+            shift.mul_assign(&two_to_64);
+        }
+
+        result
+    }
+
+    fn public_key(secret_key: Fr) -> (U256, U256) {
+        let (x, y) = C::one()
+            .mul(secret_key.into_repr())
+            .into_affine()
+            .into_xy_unchecked();
+
+        (fe_to_u256(&x), fe_to_u256(&y))
+    }
+
+    fn address(public_key: (U256, U256)) -> Address {
+        let mut encoding = [0u8; 64];
+        public_key.0.to_big_endian(&mut encoding[..32]);
+        public_key.1.to_big_endian(&mut encoding[32..]);
+        let mut hasher = sha3::Keccak256::new();
+        hasher.update(&encoding);
+        let digest = hasher.finalize();
+
+        Address::from_slice(&digest[12..])
+    }
+
+    // returns (r, s, recovery id)
+    fn sign(secret_key: Fr, message_hash: U256, nonce_seed: u64) -> (U256, U256, u8) {
+        let scalar_modulus = modulus_as_u256::<Fr>();
+        let mut nonce = Fr::from_str(&nonce_seed.to_string()).unwrap();
+        loop {
+            let (x, y) = C::one()
+                .mul(nonce.into_repr())
+                .into_affine()
+                .into_xy_unchecked();
+            // we only support recovery when x coordinate of ephemeral point is less than the group order
+            if fe_to_u256(&x) < scalar_modulus {
+                let r = u256_to_scalar(fe_to_u256(&x));
+                let mut s = r;
+                s.mul_assign(&secret_key);
+                s.add_assign(&u256_to_scalar(message_hash));
+                s.mul_assign(&nonce.inverse().unwrap());
+                let recovery_id = y.into_repr().is_odd() as u8;
+
+                return (fe_to_u256(&r), fe_to_u256(&s), recovery_id);
+            }
+            nonce.add_assign(&Fr::one());
+        }
+    }
+
+    fn run_ecdsa_verify(message_hash: U256, r: U256, s: U256, public_key: (U256, U256)) -> bool {
+        run_ecdsa_verify_on_curve::<C>(message_hash, r, s, public_key)
+    }
+
+    fn run_ecdsa_verify_on_curve<C: GenericCurveAffine>(
+        message_hash: U256,
+        r: U256,
+        s: U256,
+        public_key: (U256, U256),
+    ) -> bool
+    where
+        C::Base: PrimeField,
+    {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let base_params = Arc::new(NonNativeFieldOverU16Params::<C::Base, N>::create());
+        let scalar_params = Arc::new(NonNativeFieldOverU16Params::<C::Scalar, N>::create());
+
+        let message_hash = UInt256::allocate(cs, message_hash);
+        let r = UInt256::allocate(cs, r);
+        let s = UInt256::allocate(cs, s);
+        let pk_x = UInt256::allocate(cs, public_key.0);
+        let pk_y = UInt256::allocate(cs, public_key.1);
+
+        let is_valid = ecdsa_verify::<F, _, C, N>(
+            cs,
+            &message_hash,
+            &r,
+            &s,
+            (&pk_x, &pk_y),
+            &base_params,
+            &scalar_params,
+        );
+        let is_valid = is_valid.witness_hook(&*cs)().unwrap();
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+
+        is_valid
+    }
+
+    // returns validity flag, recovered public key and address
+    fn run_ecrecover(
+        message_hash: U256,
+        r: U256,
+        s: U256,
+        recovery_id: u8,
+    ) -> (bool, (U256, U256), Address) {
+        run_ecrecover_on_curve::<C>(message_hash, r, s, recovery_id)
+    }
+
+    fn run_ecrecover_on_curve<C: GenericCurveAffine>(
+        message_hash: U256,
+        r: U256,
+        s: U256,
+        recovery_id: u8,
+    ) -> (bool, (U256, U256), Address)
+    where
+        C::Base: PrimeField,
+    {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let base_params = Arc::new(NonNativeFieldOverU16Params::<C::Base, N>::create());
+        let scalar_params = Arc::new(NonNativeFieldOverU16Params::<C::Scalar, N>::create());
+
+        let message_hash = UInt256::allocate(cs, message_hash);
+        let r = UInt256::allocate(cs, r);
+        let s = UInt256::allocate(cs, s);
+        let recovery_id = UInt8::allocate_checked(cs, recovery_id);
+
+        let (is_valid, (x, y)) = ecrecover::<F, _, C, N>(
+            cs,
+            &message_hash,
+            &r,
+            &s,
+            &recovery_id,
+            &base_params,
+            &scalar_params,
+        );
+        let address = public_key_to_address(cs, (&x, &y));
+        let address = address.mask(cs, is_valid);
+
+        let is_valid = is_valid.witness_hook(&*cs)().unwrap();
+        let x = x.witness_hook(&*cs)().unwrap();
+        let y = y.witness_hook(&*cs)().unwrap();
+        let address = address.witness_hook(&*cs)().unwrap();
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+
+        (is_valid, (x, y), address)
+    }
+
+    fn test_signature() -> (U256, (U256, U256), (U256, U256, u8)) {
+        let secret_key = Fr::from_str("1234567890987654321").unwrap();
+        let message_hash = U256::from_str_radix(
+            "deadbeef0123456789abcdef0123456789abcdef0123456789abcdef0123",
+            16,
+        )
+        .unwrap();
+        let public_key = public_key(secret_key);
+        let signature = sign(secret_key, message_hash, 42);
+
+        (message_hash, public_key, signature)
+    }
+
+    #[test]
+    fn test_ecdsa_verify_valid_signature() {
+        let (message_hash, public_key, (r, s, _)) = test_signature();
+        assert!(run_ecdsa_verify(message_hash, r, s, public_key));
+    }
+
+    #[test]
+    fn test_ecdsa_verify_invalid_signatures() {
+        let (message_hash, public_key, (r, s, _)) = test_signature();
+        let scalar_modulus = modulus_as_u256::<Fr>();
+
+        // wrong message
+        assert!(!run_ecdsa_verify(message_hash + 1, r, s, public_key));
+        // zero s
+        assert!(!run_ecdsa_verify(message_hash, r, U256::zero(), public_key));
+        // r out of range
+        assert!(!run_ecdsa_verify(
+            message_hash,
+            r + scalar_modulus,
+            s,
+            public_key
+        ));
+        // public key is not on curve
+        let (x, y) = public_key;
+        assert!(!run_ecdsa_verify(message_hash, r, s, (x, y + 1)));
+    }
+
+    #[test]
+    fn test_ecrecover() {
+        let (message_hash, public_key, (r, s, recovery_id)) = test_signature();
+
+        let (is_valid, recovered, recovered_address) =
+            run_ecrecover(message_hash, r, s, recovery_id);
+        assert!(is_valid);
+        assert_eq!(recovered, public_key);
+        assert_eq!(recovered_address, address(public_key));
+
+        // other parity of ephemeral point gives some other key
+        let (is_valid, recovered, _) = run_ecrecover(message_hash, r, s, 1 - recovery_id);
+        assert!(is_valid);
+        assert_ne!(recovered, public_key);
+
+        // unsupported recovery id
+        let (is_valid, recovered, recovered_address) = run_ecrecover(message_hash, r, s, 2);
+        assert!(!is_valid);
+        assert_eq!(recovered, (U256::zero(), U256::zero()));
+        assert_eq!(recovered_address, Address::zero());
+    }
+
+    #[test]
+    fn test_ecrecover_x_not_on_curve() {
+        let (message_hash, _, (_, s, recovery_id)) = test_signature();
+
+        let b = C::b_coeff();
+        let mut x = Fq::from_str("5").unwrap();
+        loop {
+            let mut rhs = x;
+            rhs.square();
+            rhs.mul_assign(&x);
+            rhs.add_assign(&b);
+            if matches!(rhs.legendre(), LegendreSymbol::QuadraticNonResidue) {
+                break;
+            }
+            x.add_assign(&Fq::one());
+        }
+
+        let (is_valid, recovered, _) = run_ecrecover(message_hash, fe_to_u256(&x), s, recovery_id);
+        assert!(!is_valid);
+        assert_eq!(recovered, (U256::zero(), U256::zero()));
+    }
+
+    // secret key is 1, so public key is the generator, and `r`, `s` were computed for a fixed nonce
+    fn secp256k1_signature() -> (U256, (U256, U256), (U256, U256, u8)) {
+        let from_hex = |value: &str| U256::from_str_radix(value, 16).unwrap();
+        let message_hash =
+            from_hex("47e143f8b5d93aed8de689eaf5e87eab65de9fb46448e0cbddc8c5119319272b");
+        let public_key = (
+            from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+            from_hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"),
+        );
+        let r = from_hex("968e625db4d4958bccfe2f4b3a372dd02be2e0fd73c4f9425a012f12ff6a9e67");
+        let s = from_hex("1205ac321d06f2c0c04a078eec5033e2e437329aff1b8909759c795201bad0a3");
+
+        (message_hash, public_key, (r, s, 1))
+    }
+
+    fn secp256k1_address() -> Address {
+        // well known address of the secret key 1
+        Address::from_slice(&hex::decode("7e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap())
+    }
+
+    #[test]
+    fn test_secp256k1_valid_signature() {
+        let (message_hash, public_key, (r, s, recovery_id)) = secp256k1_signature();
+        assert!(run_ecdsa_verify_on_curve::<Secp256k1>(
+            message_hash,
+            r,
+            s,
+            public_key
+        ));
+
+        let (is_valid, recovered, recovered_address) =
+            run_ecrecover_on_curve::<Secp256k1>(message_hash, r, s, recovery_id);
+        assert!(is_valid);
+        assert_eq!(recovered, public_key);
+        assert_eq!(recovered_address, secp256k1_address());
+    }
+
+    #[test]
+    fn test_secp256k1_bad_signature() {
+        let (message_hash, public_key, (r, s, recovery_id)) = secp256k1_signature();
+        let s = s + 1;
+        assert!(!run_ecdsa_verify_on_curve::<Secp256k1>(
+            message_hash,
+            r,
+            s,
+            public_key
+        ));
+
+        // any signature in range recovers some key, but not the signer's one
+        let (is_valid, recovered, recovered_address) =
+            run_ecrecover_on_curve::<Secp256k1>(message_hash, r, s, recovery_id);
+        assert!(is_valid);
+        assert_ne!(recovered, public_key);
+        assert_ne!(recovered_address, secp256k1_address());
+    }
+
+    #[test]
+    fn test_secp256k1_high_s_signature() {
+        let (message_hash, public_key, (r, s, recovery_id)) = secp256k1_signature();
+        // (r, n - s) is the same signature with the ephemeral point negated, and it's not
+        // rejected, as there is no malleability check
+        let high_s = modulus_as_u256::<<Secp256k1 as GenericCurveAffine>::Scalar>() - s;
+        assert!(run_ecdsa_verify_on_curve::<Secp256k1>(
+            message_hash,
+            r,
+            high_s,
+            public_key
+        ));
+
+        let (is_valid, recovered, recovered_address) =
+            run_ecrecover_on_curve::<Secp256k1>(message_hash, r, high_s, 1 - recovery_id);
+        assert!(is_valid);
+        assert_eq!(recovered, public_key);
+        assert_eq!(recovered_address, secp256k1_address());
+
+        // but recovery id must match
+        let (is_valid, recovered, _) =
+            run_ecrecover_on_curve::<Secp256k1>(message_hash, r, high_s, recovery_id);
+        assert!(is_valid);
+        assert_ne!(recovered, public_key);
+    }
+}
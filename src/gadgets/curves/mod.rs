@@ -1,4 +1,8 @@
 use super::*;
 
+pub mod ecdsa;
+pub mod scalar_mul;
+#[cfg(test)]
+pub(crate) mod secp256k1;
 pub mod sw_projective;
 pub mod zeroable_affine;
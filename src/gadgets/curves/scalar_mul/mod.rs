@@ -0,0 +1,543 @@
+use super::*;
+
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::curves::sw_projective::SWProjectivePoint;
+use crate::gadgets::non_native_field::traits::NonNativeField;
+use crate::gadgets::traits::selectable::binary_select;
+use pairing::ff::PrimeField;
+use pairing::{GenericCurveAffine, GenericCurveProjective};
+use std::sync::Arc;
+
+// All the routines below rely on the complete addition and doubling formulas of `SWProjectivePoint`
+// (valid for curves of prime order with zero j-invariant), so we do not need to care about exceptional
+// cases like adding a point to itself or to the point at infinity. Scalars are consumed as little-endian
+// bit decompositions of the normalized non-native scalar field elements, and are split into windows
+// of `W` bits starting from the most significant one.
+
+fn split_into_windows<F: SmallField, CS: ConstraintSystem<F>, const W: usize>(
+    cs: &mut CS,
+    bits: &[Boolean<F>],
+) -> Vec<[Boolean<F>; W]> {
+    assert!(W > 0);
+    let boolean_false = Boolean::allocated_constant(cs, false);
+    let num_windows = (bits.len() + W - 1) / W;
+    let mut windows = Vec::with_capacity(num_windows);
+    for chunk in bits.chunks(W) {
+        let mut window = [boolean_false; W];
+        window[..chunk.len()].copy_from_slice(chunk);
+        windows.push(window);
+    }
+
+    windows
+}
+
+impl<F: SmallField, C: GenericCurveAffine, NN: NonNativeField<F, C::Base>>
+    SWProjectivePoint<F, C, NN>
+where
+    C::Base: pairing::ff::PrimeField,
+{
+    /// Computes table of `[0, P, 2P, ..., (2^W - 1) * P]` for windowed multiplication
+    pub fn precompute_window_table<CS: ConstraintSystem<F>, const W: usize>(
+        &mut self,
+        cs: &mut CS,
+    ) -> Vec<Self> {
+        assert!(W > 0);
+        let params = self.x.get_params().clone();
+        let table_size = 1 << W;
+        let mut table = Vec::with_capacity(table_size);
+        table.push(Self::zero(cs, &params));
+        table.push(self.clone());
+        for idx in 2..table_size {
+            let new = if idx % 2 == 0 {
+                table[idx / 2].double(cs)
+            } else {
+                let mut point = self.clone();
+                table[idx - 1].add(cs, &mut point)
+            };
+            table.push(new);
+        }
+
+        table
+    }
+
+    /// Variable-base scalar multiplication using windows of `W` bits
+    pub fn mul_windowed<
+        CS: ConstraintSystem<F>,
+        SNN: NonNativeField<F, C::Scalar>,
+        const W: usize,
+    >(
+        &mut self,
+        cs: &mut CS,
+        scalar: &mut SNN,
+    ) -> Self {
+        let table = self.precompute_window_table::<CS, W>(cs);
+        let bits = scalar.to_le_bits(cs);
+        let windows = split_into_windows::<F, CS, W>(cs, &bits);
+
+        let mut acc: Option<Self> = None;
+        for window in windows.iter().rev() {
+            let mut selected = binary_select(cs, &table, window);
+            acc = match acc {
+                None => Some(selected),
+                Some(mut acc) => {
+                    for _ in 0..W {
+                        acc = acc.double(cs);
+                    }
+                    Some(acc.add(cs, &mut selected))
+                }
+            };
+        }
+
+        acc.expect("scalar must have at least one window")
+    }
+}
+
+/// Native precomputations for multiplication of a fixed base point, so we only need to select
+/// and add one constant point per window
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct FixedBaseMulTable<C: GenericCurveAffine, const W: usize> {
+    // window_entries[i][j] = j * 2^{W * i} * base
+    pub window_entries: Vec<Vec<C>>,
+}
+
+impl<C: GenericCurveAffine, const W: usize> FixedBaseMulTable<C, W> {
+    pub fn new(base: C) -> Self {
+        assert!(W > 0);
+        let num_bits = <C::Scalar as PrimeField>::NUM_BITS as usize;
+        let num_windows = (num_bits + W - 1) / W;
+
+        let mut window_base = base.into_projective();
+        let mut window_entries = Vec::with_capacity(num_windows);
+        for _ in 0..num_windows {
+            let mut entries = Vec::with_capacity(1 << W);
+            let mut acc = C::Projective::zero();
+            for _ in 0..(1 << W) {
+                entries.push(acc.into_affine());
+                acc.add_assign(&window_base);
+            }
+            window_entries.push(entries);
+
+            for _ in 0..W {
+                window_base.double();
+            }
+        }
+
+        Self { window_entries }
+    }
+
+    pub fn num_windows(&self) -> usize {
+        self.window_entries.len()
+    }
+}
+
+/// Multiplication of the fixed base point, that was used to create `table`, by the variable scalar
+pub fn fixed_base_mul<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    C: GenericCurveAffine,
+    NN: NonNativeField<F, C::Base>,
+    SNN: NonNativeField<F, C::Scalar>,
+    const W: usize,
+>(
+    cs: &mut CS,
+    table: &FixedBaseMulTable<C, W>,
+    scalar: &mut SNN,
+    params: &Arc<NN::Params>,
+) -> SWProjectivePoint<F, C, NN>
+where
+    C::Base: pairing::ff::PrimeField,
+{
+    let bits = scalar.to_le_bits(cs);
+    let windows = split_into_windows::<F, CS, W>(cs, &bits);
+    assert_eq!(windows.len(), table.num_windows());
+
+    let mut acc: Option<SWProjectivePoint<F, C, NN>> = None;
+    for (window, entries) in windows.iter().zip(table.window_entries.iter()) {
+        let constant_points: Vec<_> = entries
+            .iter()
+            .map(|el| SWProjectivePoint::allocated_constant(cs, *el, params))
+            .collect();
+        let mut selected = binary_select(cs, &constant_points, window);
+        acc = match acc {
+            None => Some(selected),
+            Some(mut acc) => Some(acc.add(cs, &mut selected)),
+        };
+    }
+
+    acc.expect("scalar must have at least one window")
+}
+
+/// Multi-scalar multiplication `sum_i scalars[i] * points[i]` using Straus' method: every point
+/// gets its own window table, but doublings of the accumulator are shared between all the terms
+pub fn multi_scalar_mul<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    C: GenericCurveAffine,
+    NN: NonNativeField<F, C::Base>,
+    SNN: NonNativeField<F, C::Scalar>,
+    const W: usize,
+>(
+    cs: &mut CS,
+    points: &mut [SWProjectivePoint<F, C, NN>],
+    scalars: &mut [SNN],
+) -> SWProjectivePoint<F, C, NN>
+where
+    C::Base: pairing::ff::PrimeField,
+{
+    assert_eq!(points.len(), scalars.len());
+    assert!(points.len() > 0);
+
+    let mut tables = Vec::with_capacity(points.len());
+    let mut all_windows = Vec::with_capacity(points.len());
+    for (point, scalar) in points.iter_mut().zip(scalars.iter_mut()) {
+        let table = point.precompute_window_table::<CS, W>(cs);
+        tables.push(table);
+        let bits = scalar.to_le_bits(cs);
+        let windows = split_into_windows::<F, CS, W>(cs, &bits);
+        all_windows.push(windows);
+    }
+
+    let num_windows = all_windows[0].len();
+    for windows in all_windows.iter() {
+        assert_eq!(windows.len(), num_windows);
+    }
+
+    let mut acc: Option<SWProjectivePoint<F, C, NN>> = None;
+    for window_idx in (0..num_windows).rev() {
+        if let Some(existing) = acc.as_mut() {
+            for _ in 0..W {
+                *existing = existing.double(cs);
+            }
+        }
+
+        for (table, windows) in tables.iter().zip(all_windows.iter()) {
+            let mut selected = binary_select(cs, table, &windows[window_idx]);
+            acc = match acc {
+                None => Some(selected),
+                Some(mut acc) => Some(acc.add(cs, &mut selected)),
+            };
+        }
+    }
+
+    acc.expect("scalars must have at least one window")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, StaticToolboxHolder};
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::non_native_field::implementations::*;
+    use crate::gadgets::tables::range_check_16_bits::{
+        create_range_check_16_bits_table, RangeCheck16BitsTable,
+    };
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+    use pairing::bn256::{Fq, Fr, G1Affine};
+    use pairing::ff::Field;
+
+    type F = GoldilocksField;
+    type C = G1Affine;
+    type BaseNN = NonNativeFieldOverU16<F, Fq, 17>;
+    type ScalarNN = NonNativeFieldOverU16<F, Fr, 17>;
+
+    fn create_test_cs() -> CSReferenceImplementation<
+        F,
+        F,
+        DevCSConfig,
+        impl GateConfigurationHolder<F>,
+        impl StaticToolboxHolder,
+    > {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 24, 1 << 21);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 1,
+                num_repetitions: 10,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = DotProductGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<16>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ParallelSelectionGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        let table = create_range_check_16_bits_table();
+        owned_cs.add_lookup_table::<RangeCheck16BitsTable, 1>(table);
+
+        owned_cs
+    }
+
+    fn assert_point_value<CS: ConstraintSystem<F>, C: GenericCurveAffine>(
+        cs: &mut CS,
+        point: &mut SWProjectivePoint<F, C, NonNativeFieldOverU16<F, C::Base, 17>>,
+        expected: C,
+    ) where
+        C::Base: PrimeField,
+    {
+        let ((x, y), is_infinity) = point.convert_to_affine_or_default(cs, C::one());
+        assert_eq!(
+            is_infinity.witness_hook(&*cs)().unwrap(),
+            expected.is_zero()
+        );
+        if expected.is_zero() == false {
+            let (expected_x, expected_y) = expected.into_xy_unchecked();
+            assert_eq!(x.witness_hook(&*cs)().unwrap().get(), expected_x);
+            assert_eq!(y.witness_hook(&*cs)().unwrap().get(), expected_y);
+        }
+    }
+
+    fn allocate_point<CS: ConstraintSystem<F>, C: GenericCurveAffine>(
+        cs: &mut CS,
+        value: C,
+        params: &Arc<NonNativeFieldOverU16Params<C::Base, 17>>,
+    ) -> SWProjectivePoint<F, C, NonNativeFieldOverU16<F, C::Base, 17>>
+    where
+        C::Base: PrimeField,
+    {
+        let (x, y) = value.into_xy_unchecked();
+        let x = NonNativeFieldOverU16::allocate_checked(cs, x, params);
+        let y = NonNativeFieldOverU16::allocate_checked(cs, y, params);
+
+        SWProjectivePoint::from_xy_unchecked(cs, x, y)
+    }
+
+    #[test]
+    fn test_variable_base_mul() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let base_params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+        let scalar_params = Arc::new(NonNativeFieldOverU16Params::<Fr, 17>::create());
+
+        let scalar_value = Fr::from_str("1234567890123456789012345678901234567890").unwrap();
+        let mut base_value = C::one().mul(Fr::from_str("42").unwrap().into_repr());
+        base_value.double();
+        let base_value = base_value.into_affine();
+        let expected = base_value.mul(scalar_value.into_repr()).into_affine();
+
+        let mut point = allocate_point(cs, base_value, &base_params);
+        let mut scalar = ScalarNN::allocate_checked(cs, scalar_value, &scalar_params);
+        let mut result = point.mul_windowed::<_, _, 4>(cs, &mut scalar);
+        assert_point_value(cs, &mut result, expected);
+
+        // multiplication by zero gives point at infinity
+        let mut zero_scalar = ScalarNN::allocate_checked(cs, Fr::zero(), &scalar_params);
+        let mut result = point.mul_windowed::<_, _, 4>(cs, &mut zero_scalar);
+        assert_point_value(cs, &mut result, C::zero());
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    fn test_fixed_base_mul() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let base_params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+        let scalar_params = Arc::new(NonNativeFieldOverU16Params::<Fr, 17>::create());
+
+        let table = FixedBaseMulTable::<C, 4>::new(C::one());
+
+        let mut scalar_value = Fr::zero();
+        scalar_value.sub_assign(&Fr::one());
+        let expected = C::one().mul(scalar_value.into_repr()).into_affine();
+
+        let mut scalar = ScalarNN::allocate_checked(cs, scalar_value, &scalar_params);
+        let mut result: SWProjectivePoint<F, C, BaseNN> =
+            fixed_base_mul(cs, &table, &mut scalar, &base_params);
+        assert_point_value(cs, &mut result, expected);
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    fn test_multi_scalar_mul() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let base_params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+        let scalar_params = Arc::new(NonNativeFieldOverU16Params::<Fr, 17>::create());
+
+        let scalar_values = [
+            Fr::from_str("98765432109876543210").unwrap(),
+            Fr::from_str("123").unwrap(),
+            Fr::from_str("5555555555555555555555555555555555").unwrap(),
+        ];
+        let point_values = [
+            C::one(),
+            C::one()
+                .mul(Fr::from_str("7").unwrap().into_repr())
+                .into_affine(),
+            C::one()
+                .mul(Fr::from_str("1000001").unwrap().into_repr())
+                .into_affine(),
+        ];
+
+        let mut expected = <C as GenericCurveAffine>::Projective::zero();
+        for (point, scalar) in point_values.iter().zip(scalar_values.iter()) {
+            let term = point.mul(scalar.into_repr());
+            expected.add_assign(&term);
+        }
+        let expected = expected.into_affine();
+
+        let mut points: Vec<_> = point_values
+            .iter()
+            .map(|el| allocate_point(cs, *el, &base_params))
+            .collect();
+        let mut scalars: Vec<_> = scalar_values
+            .iter()
+            .map(|el| ScalarNN::allocate_checked(cs, *el, &scalar_params))
+            .collect();
+
+        let mut result = multi_scalar_mul::<_, _, _, _, _, 3>(cs, &mut points, &mut scalars);
+        assert_point_value(cs, &mut result, expected);
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    fn test_secp256k1_scalar_mul() {
+        use crate::gadgets::curves::secp256k1::{Fq, Fr, PointAffine};
+        use ethereum_types::U256;
+
+        type SecpBaseNN = NonNativeFieldOverU16<F, Fq, 17>;
+        type SecpScalarNN = NonNativeFieldOverU16<F, Fr, 17>;
+
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let base_params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+        let scalar_params = Arc::new(NonNativeFieldOverU16Params::<Fr, 17>::create());
+
+        // 3G from the standard test vectors
+        let three_g = PointAffine::from_xy_checked(
+            Fq::from_u256(
+                U256::from_str_radix(
+                    "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+                    16,
+                )
+                .unwrap(),
+            )
+            .unwrap(),
+            Fq::from_u256(
+                U256::from_str_radix(
+                    "388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672",
+                    16,
+                )
+                .unwrap(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let mut generator = allocate_point(cs, PointAffine::one(), &base_params);
+        let mut three =
+            SecpScalarNN::allocate_checked(cs, Fr::from_str("3").unwrap(), &scalar_params);
+        let mut result = generator.mul_windowed::<_, _, 4>(cs, &mut three);
+        assert_point_value(cs, &mut result, three_g);
+
+        // variable base
+        let scalar_value = Fr::from_str("1234567890123456789012345678901234567890").unwrap();
+        let expected = three_g.mul(scalar_value).into_affine();
+        let mut point = allocate_point(cs, three_g, &base_params);
+        let mut scalar = SecpScalarNN::allocate_checked(cs, scalar_value, &scalar_params);
+        let mut result = point.mul_windowed::<_, _, 4>(cs, &mut scalar);
+        assert_point_value(cs, &mut result, expected);
+
+        // fixed base by `n - 1`, as scalar modulus is close to 2^256 it uses all the windows
+        let table = FixedBaseMulTable::<PointAffine, 4>::new(PointAffine::one());
+        let mut minus_one = Fr::zero();
+        minus_one.sub_assign(&Fr::one());
+        let mut expected = PointAffine::one();
+        expected.negate();
+        let mut scalar = SecpScalarNN::allocate_checked(cs, minus_one, &scalar_params);
+        let mut result: SWProjectivePoint<F, PointAffine, SecpBaseNN> =
+            fixed_base_mul(cs, &table, &mut scalar, &base_params);
+        assert_point_value(cs, &mut result, expected);
+
+        // G * (n - 1) + G is the point at infinity
+        let mut points = [
+            allocate_point(cs, PointAffine::one(), &base_params),
+            allocate_point(cs, PointAffine::one(), &base_params),
+        ];
+        let mut scalars = [
+            SecpScalarNN::allocate_checked(cs, minus_one, &scalar_params),
+            SecpScalarNN::allocate_checked(cs, Fr::one(), &scalar_params),
+        ];
+        let mut result = multi_scalar_mul::<_, _, _, _, _, 3>(cs, &mut points, &mut scalars);
+        assert_point_value(cs, &mut result, PointAffine::zero());
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
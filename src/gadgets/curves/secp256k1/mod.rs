@@ -0,0 +1,617 @@
+// Minimal secp256k1 implementation for tests, as there is none in `pairing`. Field elements are
+// kept in canonical form and arithmetic goes through 512-bit products and reductions, so it's slow
+// and not constant time, but simple enough to be obviously correct. Representation type is borrowed
+// from BN254, as it's just 4 little-endian 64-bit limbs
+
+use ethereum_types::{U256, U512};
+use pairing::bn256::FqRepr as Repr;
+use pairing::ff::{Field, LegendreSymbol, PrimeField, PrimeFieldDecodingError, SqrtField};
+use pairing::{GenericCurveAffine, GenericCurveProjective, GroupDecodingError};
+use rand_04::{Rand, Rng};
+use std::fmt;
+
+fn add_mod(a: U256, b: U256, modulus: U256) -> U256 {
+    let (sum, overflow) = a.overflowing_add(b);
+    if overflow || sum >= modulus {
+        sum.overflowing_sub(modulus).0
+    } else {
+        sum
+    }
+}
+
+fn sub_mod(a: U256, b: U256, modulus: U256) -> U256 {
+    if a >= b {
+        a - b
+    } else {
+        a.overflowing_sub(b).0.overflowing_add(modulus).0
+    }
+}
+
+fn mul_mod(a: U256, b: U256, modulus: U256) -> U256 {
+    let product = a.full_mul(b) % U512::from(modulus);
+
+    U256([product.0[0], product.0[1], product.0[2], product.0[3]])
+}
+
+macro_rules! secp256k1_field {
+    ($name: ident, $modulus: expr, $generator: expr, $two_adicity: expr) => {
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+        pub struct $name(U256);
+
+        impl $name {
+            fn modulus() -> U256 {
+                U256::from_str_radix($modulus, 16).unwrap()
+            }
+
+            pub fn from_u256(value: U256) -> Option<Self> {
+                if value < Self::modulus() {
+                    Some(Self(value))
+                } else {
+                    None
+                }
+            }
+
+            pub fn to_u256(&self) -> U256 {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}(0x{:x})", stringify!($name), self.0)
+            }
+        }
+
+        impl Rand for $name {
+            fn rand<R: Rng>(rng: &mut R) -> Self {
+                loop {
+                    let value = U256([rng.gen(), rng.gen(), rng.gen(), rng.gen()]);
+                    if let Some(result) = Self::from_u256(value) {
+                        return result;
+                    }
+                }
+            }
+        }
+
+        impl From<$name> for Repr {
+            fn from(value: $name) -> Self {
+                value.into_repr()
+            }
+        }
+
+        impl Field for $name {
+            fn zero() -> Self {
+                Self(U256::zero())
+            }
+
+            fn one() -> Self {
+                Self(U256::one())
+            }
+
+            fn is_zero(&self) -> bool {
+                self.0.is_zero()
+            }
+
+            fn square(&mut self) {
+                self.0 = mul_mod(self.0, self.0, Self::modulus());
+            }
+
+            fn double(&mut self) {
+                self.0 = add_mod(self.0, self.0, Self::modulus());
+            }
+
+            fn negate(&mut self) {
+                self.0 = sub_mod(U256::zero(), self.0, Self::modulus());
+            }
+
+            fn add_assign(&mut self, other: &Self) {
+                self.0 = add_mod(self.0, other.0, Self::modulus());
+            }
+
+            fn sub_assign(&mut self, other: &Self) {
+                self.0 = sub_mod(self.0, other.0, Self::modulus());
+            }
+
+            fn mul_assign(&mut self, other: &Self) {
+                self.0 = mul_mod(self.0, other.0, Self::modulus());
+            }
+
+            fn inverse(&self) -> Option<Self> {
+                if self.is_zero() {
+                    return None;
+                }
+                let exponent = Self::modulus() - U256::from(2u64);
+
+                Some(self.pow(exponent.0))
+            }
+
+            fn frobenius_map(&mut self, _power: usize) {
+                // identity for prime fields
+            }
+        }
+
+        impl PrimeField for $name {
+            type Repr = Repr;
+
+            const NUM_BITS: u32 = 256;
+            const CAPACITY: u32 = 255;
+            const S: u32 = $two_adicity;
+
+            fn from_repr(repr: Self::Repr) -> Result<Self, PrimeFieldDecodingError> {
+                Self::from_u256(U256(repr.0))
+                    .ok_or_else(|| PrimeFieldDecodingError::NotInField(format!("{}", repr)))
+            }
+
+            fn from_raw_repr(repr: Self::Repr) -> Result<Self, PrimeFieldDecodingError> {
+                Self::from_repr(repr)
+            }
+
+            fn into_repr(&self) -> Self::Repr {
+                Repr(self.0 .0)
+            }
+
+            fn into_raw_repr(&self) -> Self::Repr {
+                self.into_repr()
+            }
+
+            fn char() -> Self::Repr {
+                Repr(Self::modulus().0)
+            }
+
+            fn multiplicative_generator() -> Self {
+                Self(U256::from($generator))
+            }
+
+            fn root_of_unity() -> Self {
+                let odd_part = (Self::modulus() - U256::one()) >> $two_adicity;
+
+                Self::multiplicative_generator().pow(odd_part.0)
+            }
+        }
+
+        impl SqrtField for $name {
+            fn legendre(&self) -> LegendreSymbol {
+                let exponent = (Self::modulus() - U256::one()) >> 1;
+                let symbol = self.pow(exponent.0);
+                if symbol.is_zero() {
+                    LegendreSymbol::Zero
+                } else if symbol == Self::one() {
+                    LegendreSymbol::QuadraticResidue
+                } else {
+                    LegendreSymbol::QuadraticNonResidue
+                }
+            }
+
+            // Tonelli-Shanks
+            fn sqrt(&self) -> Option<Self> {
+                match self.legendre() {
+                    LegendreSymbol::Zero => return Some(*self),
+                    LegendreSymbol::QuadraticNonResidue => return None,
+                    LegendreSymbol::QuadraticResidue => {}
+                }
+
+                let odd_part = (Self::modulus() - U256::one()) >> $two_adicity;
+                let mut m = $two_adicity;
+                let mut c = Self::root_of_unity();
+                let mut t = self.pow(odd_part.0);
+                let mut r = self.pow(((odd_part + U256::one()) >> 1).0);
+                while t != Self::one() {
+                    let mut i = 0;
+                    let mut tmp = t;
+                    while tmp != Self::one() {
+                        tmp.square();
+                        i += 1;
+                    }
+                    let mut b = c;
+                    for _ in 0..(m - i - 1) {
+                        b.square();
+                    }
+                    m = i;
+                    c = b;
+                    c.square();
+                    t.mul_assign(&c);
+                    r.mul_assign(&b);
+                }
+
+                Some(r)
+            }
+        }
+    };
+}
+
+secp256k1_field!(
+    Fq,
+    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+    3u64,
+    1
+);
+
+secp256k1_field!(
+    Fr,
+    "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+    7u64,
+    6
+);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PointAffine {
+    x: Fq,
+    y: Fq,
+    infinity: bool,
+}
+
+impl fmt::Display for PointAffine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.infinity {
+            write!(f, "PointAffine(Infinity)")
+        } else {
+            write!(f, "PointAffine(x={}, y={})", self.x, self.y)
+        }
+    }
+}
+
+impl PointAffine {
+    fn is_on_curve(&self) -> bool {
+        if self.infinity {
+            return true;
+        }
+        let mut lhs = self.y;
+        lhs.square();
+        let mut rhs = self.x;
+        rhs.square();
+        rhs.mul_assign(&self.x);
+        rhs.add_assign(&Self::b_coeff());
+
+        lhs == rhs
+    }
+}
+
+impl GenericCurveAffine for PointAffine {
+    type Scalar = Fr;
+    type Base = Fq;
+    type Projective = PointProjective;
+
+    fn zero() -> Self {
+        Self {
+            x: Fq::zero(),
+            y: Fq::zero(),
+            infinity: true,
+        }
+    }
+
+    fn one() -> Self {
+        let x = U256::from_str_radix(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            16,
+        )
+        .unwrap();
+        let y = U256::from_str_radix(
+            "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            16,
+        )
+        .unwrap();
+
+        Self {
+            x: Fq::from_u256(x).unwrap(),
+            y: Fq::from_u256(y).unwrap(),
+            infinity: false,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.infinity
+    }
+
+    fn negate(&mut self) {
+        if !self.infinity {
+            self.y.negate();
+        }
+    }
+
+    fn mul<S: Into<<Self::Scalar as PrimeField>::Repr>>(&self, other: S) -> Self::Projective {
+        let mut result = self.into_projective();
+        result.mul_assign(other);
+
+        result
+    }
+
+    fn into_projective(&self) -> Self::Projective {
+        if self.infinity {
+            PointProjective::zero()
+        } else {
+            PointProjective {
+                x: self.x,
+                y: self.y,
+                z: Fq::one(),
+            }
+        }
+    }
+
+    fn as_xy(&self) -> (&Self::Base, &Self::Base) {
+        (&self.x, &self.y)
+    }
+
+    fn into_xy_unchecked(self) -> (Self::Base, Self::Base) {
+        (self.x, self.y)
+    }
+
+    fn from_xy_unchecked(x: Self::Base, y: Self::Base) -> Self {
+        let infinity = x.is_zero() && y.is_zero();
+
+        Self { x, y, infinity }
+    }
+
+    fn from_xy_checked(x: Self::Base, y: Self::Base) -> Result<Self, GroupDecodingError> {
+        let point = Self::from_xy_unchecked(x, y);
+        if point.is_on_curve() {
+            Ok(point)
+        } else {
+            Err(GroupDecodingError::NotOnCurve)
+        }
+    }
+
+    fn a_coeff() -> Self::Base {
+        Fq::zero()
+    }
+
+    fn b_coeff() -> Self::Base {
+        Fq::from_u256(U256::from(7u64)).unwrap()
+    }
+}
+
+// Homogeneous projective coordinates, so we can use the complete formulas
+// from https://eprint.iacr.org/2015/1060 and never care about exceptional cases
+#[derive(Clone, Copy, Debug)]
+pub struct PointProjective {
+    x: Fq,
+    y: Fq,
+    z: Fq,
+}
+
+impl PartialEq for PointProjective {
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_zero() || other.is_zero() {
+            return self.is_zero() && other.is_zero();
+        }
+
+        let mut x0 = self.x;
+        x0.mul_assign(&other.z);
+        let mut x1 = other.x;
+        x1.mul_assign(&self.z);
+        let mut y0 = self.y;
+        y0.mul_assign(&other.z);
+        let mut y1 = other.y;
+        y1.mul_assign(&self.z);
+
+        x0 == x1 && y0 == y1
+    }
+}
+
+impl Eq for PointProjective {}
+
+impl fmt::Display for PointProjective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.into_affine())
+    }
+}
+
+impl Rand for PointProjective {
+    fn rand<R: Rng>(rng: &mut R) -> Self {
+        PointAffine::one().mul(Fr::rand(rng))
+    }
+}
+
+impl GenericCurveProjective for PointProjective {
+    type Scalar = Fr;
+    type Base = Fq;
+    type Affine = PointAffine;
+
+    fn zero() -> Self {
+        Self {
+            x: Fq::zero(),
+            y: Fq::one(),
+            z: Fq::zero(),
+        }
+    }
+
+    fn one() -> Self {
+        PointAffine::one().into_projective()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    fn batch_normalization(v: &mut [Self]) {
+        for el in v.iter_mut() {
+            *el = el.into_affine().into_projective();
+        }
+    }
+
+    fn is_normalized(&self) -> bool {
+        self.is_zero() || self.z == Fq::one()
+    }
+
+    fn double(&mut self) {
+        let other = *self;
+        self.add_assign(&other);
+    }
+
+    // Algorithm 7 of https://eprint.iacr.org/2015/1060
+    fn add_assign(&mut self, other: &Self) {
+        let mut b3 = PointAffine::b_coeff();
+        b3.mul_assign(&Fq::from_u256(U256::from(3u64)).unwrap());
+
+        let mut t0 = self.x;
+        t0.mul_assign(&other.x);
+        let mut t1 = self.y;
+        t1.mul_assign(&other.y);
+        let mut t2 = self.z;
+        t2.mul_assign(&other.z);
+
+        let mut t3 = self.x;
+        t3.add_assign(&self.y);
+        let mut t4 = other.x;
+        t4.add_assign(&other.y);
+        t3.mul_assign(&t4);
+        t4 = t0;
+        t4.add_assign(&t1);
+        t3.sub_assign(&t4);
+
+        t4 = self.y;
+        t4.add_assign(&self.z);
+        let mut x3 = other.y;
+        x3.add_assign(&other.z);
+        t4.mul_assign(&x3);
+        x3 = t1;
+        x3.add_assign(&t2);
+        t4.sub_assign(&x3);
+
+        x3 = self.x;
+        x3.add_assign(&self.z);
+        let mut y3 = other.x;
+        y3.add_assign(&other.z);
+        x3.mul_assign(&y3);
+        y3 = t0;
+        y3.add_assign(&t2);
+        y3.negate();
+        y3.add_assign(&x3);
+
+        x3 = t0;
+        x3.double();
+        t0.add_assign(&x3);
+        t2.mul_assign(&b3);
+        let mut z3 = t1;
+        z3.add_assign(&t2);
+        t1.sub_assign(&t2);
+        y3.mul_assign(&b3);
+
+        x3 = t4;
+        x3.mul_assign(&y3);
+        t2 = t3;
+        t2.mul_assign(&t1);
+        x3.negate();
+        x3.add_assign(&t2);
+
+        y3.mul_assign(&t0);
+        t1.mul_assign(&z3);
+        y3.add_assign(&t1);
+
+        t0.mul_assign(&t3);
+        z3.mul_assign(&t4);
+        z3.add_assign(&t0);
+
+        self.x = x3;
+        self.y = y3;
+        self.z = z3;
+    }
+
+    fn add_assign_mixed(&mut self, other: &Self::Affine) {
+        self.add_assign(&other.into_projective());
+    }
+
+    fn negate(&mut self) {
+        self.y.negate();
+    }
+
+    fn mul_assign<S: Into<<Self::Scalar as PrimeField>::Repr>>(&mut self, other: S) {
+        let scalar: Repr = other.into();
+        let mut result = Self::zero();
+        for limb in scalar.as_ref().iter().rev() {
+            for i in (0..64).rev() {
+                result.double();
+                if (limb >> i) & 1 == 1 {
+                    result.add_assign(self);
+                }
+            }
+        }
+
+        *self = result;
+    }
+
+    fn into_affine(&self) -> Self::Affine {
+        if self.is_zero() {
+            return PointAffine::zero();
+        }
+
+        let z_inv = self.z.inverse().unwrap();
+        let mut x = self.x;
+        x.mul_assign(&z_inv);
+        let mut y = self.y;
+        y.mul_assign(&z_inv);
+
+        PointAffine {
+            x,
+            y,
+            infinity: false,
+        }
+    }
+
+    fn recommended_wnaf_for_scalar(_scalar: <Self::Scalar as PrimeField>::Repr) -> usize {
+        4
+    }
+
+    fn recommended_wnaf_for_num_scalars(_num_scalars: usize) -> usize {
+        4
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_secp256k1_arithmetic() {
+        // 2G and 3G from the standard test vectors
+        let expected = [
+            (
+                "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+                "1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
+            ),
+            (
+                "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+                "388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672",
+            ),
+        ];
+        for (k, (x, y)) in [2u64, 3].into_iter().zip(expected) {
+            let (px, py) = PointAffine::one().mul(k).into_affine().into_xy_unchecked();
+            assert_eq!(px.to_u256(), U256::from_str_radix(x, 16).unwrap());
+            assert_eq!(py.to_u256(), U256::from_str_radix(y, 16).unwrap());
+        }
+
+        // group order
+        assert!(PointAffine::one().mul(Fr::char()).is_zero());
+
+        let mut minus_one = Fr::zero();
+        minus_one.sub_assign(&Fr::one());
+        let mut minus_g = PointAffine::one();
+        minus_g.negate();
+        assert_eq!(PointAffine::one().mul(minus_one).into_affine(), minus_g);
+
+        let (x, y) = PointAffine::one().into_xy_unchecked();
+        assert!(PointAffine::from_xy_checked(x, y).is_ok());
+        assert!(PointAffine::from_xy_checked(x, x).is_err());
+
+        let scalar = Fr::from_str("1234567890").unwrap();
+        let mut product = scalar;
+        product.mul_assign(&scalar.inverse().unwrap());
+        assert_eq!(product, Fr::one());
+
+        // both fields, as only the scalar one has non-trivial two-adicity
+        let mut square = scalar;
+        square.square();
+        let mut root = square.sqrt().unwrap();
+        root.square();
+        assert_eq!(root, square);
+
+        let mut y_squared = y;
+        y_squared.square();
+        let mut root = y_squared.sqrt().unwrap();
+        root.square();
+        assert_eq!(root, y_squared);
+        assert!(matches!(
+            Fq::multiplicative_generator().legendre(),
+            LegendreSymbol::QuadraticNonResidue
+        ));
+    }
+}
@@ -1,11 +1,21 @@
 use super::*;
 
+use crate::config::*;
+use crate::cs::traits::cs::DstBuffer;
+use crate::gadgets::non_native_field::implementations::implementation_u16::FFProxyValue;
+use crate::gadgets::non_native_field::implementations::utils::fe_to_u16_words;
+use crate::gadgets::non_native_field::implementations::{
+    NonNativeFieldOverU16, NonNativeFieldOverU16Params,
+};
+use crate::gadgets::traits::castable::WitnessCastable;
+use crate::gadgets::traits::circuit_eq::CircuitEq;
 use crate::gadgets::traits::selectable::Selectable;
 use crate::{
     cs::traits::cs::ConstraintSystem,
     gadgets::{boolean::Boolean, non_native_field::traits::NonNativeField},
 };
 use pairing::GenericCurveAffine;
+use std::sync::Arc;
 
 #[derive(Derivative)]
 #[derivative(Clone, Debug)]
@@ -53,6 +63,22 @@ where
         }
     }
 
+    pub fn allocated_constant<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        value: C,
+        params: &std::sync::Arc<NN::Params>,
+    ) -> Self {
+        if value.is_zero() {
+            return Self::zero(cs, params);
+        }
+
+        let (x, y) = value.into_xy_unchecked();
+        let x = NN::allocated_constant(cs, x, params);
+        let y = NN::allocated_constant(cs, y, params);
+
+        Self::from_xy_unchecked(cs, x, y)
+    }
+
     pub fn double<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
         // this formula is only valid for curve of prime order with zero j-ivariant
         use pairing::ff::Field;
@@ -232,6 +258,119 @@ where
         new
     }
 
+    fn add_sub_impl<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        other: &mut Self,
+        is_subtraction: bool,
+    ) -> Self {
+        // this formula is only valid for curve of prime order with zero j-ivariant,
+        // but it's complete, so it's safe to use for doubling or points at infinity
+        use pairing::ff::Field;
+        let params = self.x.get_params().clone();
+        assert!(C::a_coeff().is_zero());
+
+        let mut three = C::Base::one();
+        three.double();
+        three.add_assign(&C::Base::one());
+
+        let curve_b = C::b_coeff();
+        let mut curve_b3 = curve_b;
+        curve_b3.double();
+        curve_b3.add_assign(&curve_b);
+
+        let mut three_nn = NN::allocated_constant(cs, three, &params);
+        let mut curve_b3 = NN::allocated_constant(cs, curve_b3, &params);
+
+        let x1 = &mut self.x;
+        let y1 = &mut self.y;
+        let z1 = &mut self.z;
+
+        let x2 = &mut other.x;
+        let mut y2 = if is_subtraction {
+            other.y.negated(cs)
+        } else {
+            other.y.clone()
+        };
+        let y2 = &mut y2;
+        let z2 = &mut other.z;
+
+        // t0 = x1 * x2
+        let mut t0 = x1.mul(cs, x2);
+        // t1 = y1 * y2
+        let mut t1 = y1.mul(cs, y2);
+        // t2 = z1 * z2
+        let mut t2 = z1.mul(cs, z2);
+
+        // t3 = (x1 + y1) * (x2 + y2) - t0 - t1
+        let mut a1 = x1.add(cs, y1);
+        let mut a2 = x2.add(cs, y2);
+        let mut t3 = a1.mul(cs, &mut a2);
+        let mut t3 = t3.sub(cs, &mut t0);
+        let mut t3 = t3.sub(cs, &mut t1);
+
+        // t4 = (y1 + z1) * (y2 + z2) - t1 - t2
+        let mut a3 = y1.add(cs, z1);
+        let mut a4 = y2.add(cs, z2);
+        let mut t4 = a3.mul(cs, &mut a4);
+        let mut t4 = t4.sub(cs, &mut t1);
+        let mut t4 = t4.sub(cs, &mut t2);
+
+        // y3 = (x1 + z1) * (x2 + z2) - t0 - t2
+        let mut a5 = x1.add(cs, z1);
+        let mut a6 = x2.add(cs, z2);
+        let mut y3 = a5.mul(cs, &mut a6);
+        let mut y3 = y3.sub(cs, &mut t0);
+        let mut y3 = y3.sub(cs, &mut t2);
+
+        // t2 = b3 * z1 * z2
+        let mut t2 = t2.mul(cs, &mut curve_b3);
+
+        // z3 = t1 + t2
+        let mut z3 = t1.add(cs, &mut t2);
+
+        // t1 = t1 - t2
+        let mut t1 = t1.sub(cs, &mut t2);
+
+        // x3 = t4 * b3 * y3
+        let mut y3_mul_b3 = y3.mul(cs, &mut curve_b3);
+        let mut x3 = t4.mul(cs, &mut y3_mul_b3);
+
+        // x3 = t3 * t1 - x3
+        let mut new_x3 = t3.mul(cs, &mut t1);
+        let x3 = new_x3.sub(cs, &mut x3);
+
+        // y3 = (b3 * y3) * (3 * t0)
+        let mut t0_mul_3 = t0.mul(cs, &mut three_nn);
+        let mut y3 = y3_mul_b3.mul(cs, &mut t0_mul_3);
+
+        // y3 = t1 * z3 + y3
+        let mut new_y3 = t1.mul(cs, &mut z3);
+        let y3 = new_y3.add(cs, &mut y3);
+
+        // z3 = z3 * t4 + (3 * t0) * t3
+        let mut z3 = z3.mul(cs, &mut t4);
+        let mut t0 = t0_mul_3.mul(cs, &mut t3);
+        let z3 = z3.add(cs, &mut t0);
+
+        let new = Self {
+            x: x3,
+            y: y3,
+            z: z3,
+            _marker: std::marker::PhantomData,
+        };
+
+        new
+    }
+
+    pub fn add<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        self.add_sub_impl(cs, other, false)
+    }
+
+    pub fn sub<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        self.add_sub_impl(cs, other, true)
+    }
+
     pub fn add_mixed<CS: ConstraintSystem<F>>(
         &mut self,
         cs: &mut CS,
@@ -271,6 +410,188 @@ where
 
         ((x, y), is_point_at_infty)
     }
+
+    /// Returns whether the point satisfies the curve equation in projective form
+    /// `Y^2 * Z = X^3 + b * Z^3`. Note that `(0, 0, 0)` satisfies it, but is not a valid point
+    pub fn is_on_curve<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        use pairing::ff::Field;
+        assert!(C::a_coeff().is_zero());
+        let params = self.x.get_params().clone();
+        let mut curve_b = NN::allocated_constant(cs, C::b_coeff(), &params);
+
+        let x = &mut self.x;
+        let y = &mut self.y;
+        let z = &mut self.z;
+
+        // lhs = Y^2 * Z
+        let mut y_squared = y.square(cs);
+        let mut lhs = y_squared.mul(cs, z);
+        // rhs = X^3 + b * Z^3
+        let mut x_squared = x.square(cs);
+        let mut x_cubed = x_squared.mul(cs, x);
+        let mut z_squared = z.square(cs);
+        let mut z_cubed = z_squared.mul(cs, z);
+        let mut b_mul_z_cubed = z_cubed.mul(cs, &mut curve_b);
+        let mut rhs = x_cubed.add(cs, &mut b_mul_z_cubed);
+        let equation_holds = lhs.equals(cs, &mut rhs);
+
+        // if Z == 0 then X == 0 from the equation above, so we only need to check Y
+        let y_is_zero = y.is_zero(cs);
+        let z_is_zero = z.is_zero(cs);
+        let is_degenerate = Boolean::multi_and(cs, &[y_is_zero, z_is_zero]);
+        let is_not_degenerate = is_degenerate.negated(cs);
+
+        Boolean::multi_and(cs, &[equation_holds, is_not_degenerate])
+    }
+
+    pub fn enforce_on_curve<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        let is_on_curve = self.is_on_curve(cs);
+        let boolean_true = Boolean::allocated_constant(cs, true);
+        Boolean::enforce_equal(cs, &is_on_curve, &boolean_true);
+    }
+
+    // double-and-add over the bits of the scalar field modulus, that is a public constant
+    fn mul_by_scalar_field_modulus<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        use pairing::ff::PrimeField;
+        let modulus = <C::Scalar as PrimeField>::char();
+        let words = modulus.as_ref();
+        let num_bits = <C::Scalar as PrimeField>::NUM_BITS as usize;
+
+        // top bit is always set
+        let mut acc = self.clone();
+        for bit_idx in (0..(num_bits - 1)).rev() {
+            acc = acc.double(cs);
+            if (words[bit_idx / 64] >> (bit_idx % 64)) & 1 == 1 {
+                acc = acc.add(cs, self);
+            }
+        }
+
+        acc
+    }
+
+    /// Returns whether `n * P` is the point at infinity, where `n` is the scalar field modulus.
+    /// For curves of prime order it is implied by the point being on the curve, so this check is only
+    /// useful for curves of odd order with non-trivial cofactor, where the complete formulas are still valid
+    pub fn is_in_subgroup<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        let mut multiplied = self.mul_by_scalar_field_modulus(cs);
+
+        multiplied.z.is_zero(cs)
+    }
+
+    pub fn enforce_in_subgroup<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        let is_in_subgroup = self.is_in_subgroup(cs);
+        let boolean_true = Boolean::allocated_constant(cs, true);
+        Boolean::enforce_equal(cs, &is_in_subgroup, &boolean_true);
+    }
+
+    /// Compares points in projective coordinates as `X1 * Z2 == X2 * Z1` and `Y1 * Z2 == Y2 * Z1`.
+    /// Both points are expected to be on the curve
+    pub fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &mut Self, b: &mut Self) -> Boolean<F> {
+        let mut x1_mul_z2 = a.x.mul(cs, &mut b.z);
+        let mut x2_mul_z1 = b.x.mul(cs, &mut a.z);
+        let mut y1_mul_z2 = a.y.mul(cs, &mut b.z);
+        let mut y2_mul_z1 = b.y.mul(cs, &mut a.z);
+
+        let same_x = x1_mul_z2.equals(cs, &mut x2_mul_z1);
+        let same_y = y1_mul_z2.equals(cs, &mut y2_mul_z1);
+
+        Boolean::multi_and(cs, &[same_x, same_y])
+    }
+
+    pub fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &mut Self, b: &mut Self) {
+        let are_equal = Self::equals(cs, a, b);
+        let boolean_true = Boolean::allocated_constant(cs, true);
+        Boolean::enforce_equal(cs, &are_equal, &boolean_true);
+    }
+}
+
+impl<F: SmallField, C: GenericCurveAffine, const N: usize>
+    SWProjectivePoint<F, C, NonNativeFieldOverU16<F, C::Base, N>>
+where
+    C::Base: pairing::ff::PrimeField,
+    [(); N + 1]:,
+{
+    /// Allocates a point in affine form from the witness closure, that must not return the point
+    /// at infinity. Point is not checked to be on the curve
+    pub fn allocate_from_closure_and_dependencies<
+        CS: ConstraintSystem<F>,
+        FN: FnOnce(&[F]) -> C + 'static + Send + Sync,
+    >(
+        cs: &mut CS,
+        witness_closure: FN,
+        dependencies: &[Place],
+        params: &Arc<NonNativeFieldOverU16Params<C::Base, N>>,
+    ) -> Self {
+        let x = NonNativeFieldOverU16::allocate_checked_without_value(cs, params);
+        let y = NonNativeFieldOverU16::allocate_checked_without_value(cs, params);
+
+        if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
+            let modulus_limbs = params.modulus_limbs;
+            let value_fn = move |inputs: &[F], dst: &mut DstBuffer<'_, '_, F>| {
+                let witness = (witness_closure)(inputs);
+                assert!(!witness.is_zero());
+                let (x, y) = witness.into_xy_unchecked();
+                for coordinate in [x, y].iter() {
+                    let limbs = fe_to_u16_words::<_, N>(coordinate);
+                    for el in limbs.into_iter().take(modulus_limbs) {
+                        dst.push(F::from_u64_unchecked(el as u64));
+                    }
+                }
+            };
+
+            let mut outputs = Vec::with_capacity(2 * params.modulus_limbs);
+            for coordinate in [&x, &y] {
+                outputs.extend(
+                    Place::from_variables(coordinate.limbs)
+                        .into_iter()
+                        .take(params.modulus_limbs),
+                );
+            }
+
+            cs.set_values_with_dependencies_vararg(dependencies, &outputs, value_fn);
+        }
+
+        Self::from_xy_unchecked(cs, x, y)
+    }
+
+    /// Returns the point `Q` such that `2 * Q == P`. It is unique for curves of odd order
+    pub fn halving<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let params = self.x.params.clone();
+        let ((x, y), is_point_at_infty) = self.convert_to_affine_or_default(cs, C::one());
+
+        let mut dependencies = Vec::with_capacity(2 * N);
+        dependencies.extend(Place::from_variables(x.limbs));
+        dependencies.extend(Place::from_variables(y.limbs));
+
+        let witness_fn = move |inputs: &[F]| {
+            use pairing::ff::{Field, PrimeField};
+            use pairing::GenericCurveProjective;
+
+            let x: [F; N] = inputs[..N].try_into().unwrap();
+            let y: [F; N] = inputs[N..].try_into().unwrap();
+            let x = <FFProxyValue<C::Base, N> as WitnessCastable<F, [F; N]>>::cast_from_source(x);
+            let y = <FFProxyValue<C::Base, N> as WitnessCastable<F, [F; N]>>::cast_from_source(y);
+            let point = C::from_xy_unchecked(x.get(), y.get());
+
+            // if Q = P / 2 then Q = (2^-1 mod n) * P
+            let mut two = C::Scalar::one();
+            two.double();
+            let two_inv = two.inverse().expect("group order must be odd");
+
+            point.mul(two_inv.into_repr()).into_affine()
+        };
+
+        let mut halved =
+            Self::allocate_from_closure_and_dependencies(cs, witness_fn, &dependencies, &params);
+        halved.enforce_on_curve(cs);
+        let mut doubled = halved.double(cs);
+        let mut initial = Self::from_xy_unchecked(cs, x, y);
+        Self::enforce_equal(cs, &mut doubled, &mut initial);
+
+        let zero = Self::zero(cs, &params);
+
+        Self::conditionally_select(cs, is_point_at_infty, &zero, &halved)
+    }
 }
 
 impl<F: SmallField, C: GenericCurveAffine, NN: NonNativeField<F, C::Base>> Selectable<F>
@@ -299,407 +620,235 @@ where
     }
 }
 
-// impl<'a, E: Engine, G: GenericCurveAffine, T: Extension2Params<G::Base>> ProjectivePoint<'a, E, G, T>
-// where <G as GenericCurveAffine>::Base: PrimeField
-// {
-//     pub fn get_x(&self) -> FieldElement<'a, E, G::Base> {
-//         self.x.clone()
-//     }
-
-//     pub fn get_y(&self) -> FieldElement<'a, E, G::Base> {
-//         self.y.clone()
-//     }
-
-//     pub fn get_z(&self) -> FieldElement<'a, E, G::Base> {
-//         self.z.clone()
-//     }
-
-//     pub fn from_coordinates_unchecked(
-//         x: FieldElement<'a, E, G::Base>, y: FieldElement<'a, E, G::Base>, z: FieldElement<'a, E, G::Base>,
-//         circuit_params: &'a CurveCircuitParameters<E, G, T>
-//     ) -> Self {
-//         let value = match (x.get_field_value(), y.get_field_value(), z.get_field_value()) {
-//             (Some(mut x), Some(mut y), Some(z)) => {
-//                 let res = if z.is_zero() {
-//                     G::zero()
-//                 } else {
-//                     let z_inv = z.inverse().unwrap();
-//                     x.mul_assign(&z_inv);
-//                     x.mul_assign(&z_inv);
-//                     y.mul_assign(&z_inv);
-//                     y.mul_assign(&z_inv);
-//                     y.mul_assign(&z_inv);
-//                     G::from_xy_checked(x, y).expect("should be a valid point")
-//                 };
-//                 Some(res)
-//             },
-//             _ => None,
-//         };
-
-//         Self { x, y, z, value, circuit_params }
-//     }
-
-//     #[track_caller]
-//     pub fn enforce_if_on_curve<CS: ConstraintSystem<E>>(&mut self, cs: &mut CS) -> Result<(), SynthesisError> {
-//         let a: u64 = fe_to_biguint(&G::a_coeff()).to_u64().expect("G::a_ceoff should fit into u64");
-//         let b: u64 = fe_to_biguint(&G::b_coeff()).to_u64().expect("G::a_ceoff should fit into u64");
-
-//         // Y^2 * Z = X^3 + a * X * Z^2 + b * Z^3
-//         let mut lhs = self.y.square(cs)?;
-//         lhs = lhs.mul(cs, &self.z)?;
-
-//         let x_squared = self.x.square(cs)?;
-//         let x_cubed = x_squared.mul(cs, &self.x)?;
-//         let z_squared = self.z.square(cs)?;
-//         let z_mul_b = self.z.scale(cs, b)?;
-//         let mut rhs = z_squared.mul(cs, &z_mul_b)?;
-
-//         rhs = rhs.add(cs, &x_cubed)?;
-//         if a != 0 {
-//             let x_mul_a = self.x.scale(cs, a)?;
-//             let tmp = z_squared.mul(cs, &x_mul_a)?;
-//             rhs = rhs.add(cs, &tmp)?
-//         };
-//         FieldElement::enforce_equal(cs, &mut lhs, &mut rhs)
-//     }
-
-//     #[track_caller]
-//     pub fn enforce_equal<CS>(cs: &mut CS, left: &mut Self, right: &mut Self) -> Result<(), SynthesisError>
-//     where CS: ConstraintSystem<E>
-//     {
-//         // we should check that x2 = t * x1; y2 = t * y1; z2 = t * z1 for some scalar t
-//         let mut t_wit = None;
-//         let rns_params = &left.circuit_params.base_field_rns_params;
-
-//         for (cand1, cand2) in [&left.x, &left.y, &left.z].iter().zip([&right.x, &right.y, &right.z].iter()) {
-//             if cand2.get_field_value().is_some() && !cand1.get_field_value().unwrap_or(G::Base::zero()).is_zero() {
-//                 let res = cand1.get_field_value().unwrap();
-//                 let mut res_inversed = res.inverse().unwrap();
-//                 res_inversed.mul_assign(&cand2.get_field_value().unwrap());
-//                 t_wit = Some(res_inversed);
-//                 break;
-//             };
-//         }
-//         let t = FieldElement::alloc(cs, t_wit, rns_params)?;
-
-//         for (a, b) in [&left.x, &left.y, &left.z].iter().zip([&right.x, &right.y, &right.z].iter()) {
-//             let mut lhs = FieldElement::mul(&a, cs, &t)?;
-//             let mut rhs : FieldElement<E, G::Base> = (*b).clone();
-//             FieldElement::enforce_equal(cs, &mut lhs, &mut rhs)?;
-//         }
-
-//         Ok(())
-//     }
-
-//     pub fn zero(params: &'a CurveCircuitParameters<E, G, T>) -> Self
-//     {
-//         let rns_params = &params.base_field_rns_params;
-//         let x = FieldElement::zero(rns_params);
-//         let y = FieldElement::one(rns_params);
-//         let z = FieldElement::zero(rns_params);
-//         let value = Some(G::zero());
-
-//         Self { x, y, z, value, circuit_params: params }
-//     }
-
-//     pub fn is_constant(&self) -> bool {
-//         self.x.is_constant() & self.y.is_constant() & self.z.is_constant()
-//     }
-
-//     pub fn get_value(&self) -> Option<G> {
-//         self.value.clone()
-//     }
-
-//     pub fn negate<CS: ConstraintSystem<E>>(&self, cs: &mut CS) -> Result<Self, SynthesisError> {
-//         let y_negated = self.y.negate(cs)?;
-//         let new_value = self.value.map(|el| {
-//             let mut t = el;
-//             t.negate();
-//             t
-//         });
-
-//         let new = Self {
-//             x: self.x.clone(),
-//             y: y_negated,
-//             z: self.z.clone(),
-//             value: new_value,
-//             circuit_params: self.circuit_params
-//         };
-
-//         Ok(new)
-//     }
-
-//     pub fn conditionally_negate<CS>(&self, cs: &mut CS, flag: &Boolean) -> Result<Self, SynthesisError>
-//     where CS: ConstraintSystem<E>
-//     {
-//         let y_negated = self.y.conditionally_negate(cs, flag)?;
-//         let new_value = match (self.value, flag.get_value()) {
-//             (Some(val), Some(flag)) => {
-//                 let mut t = val;
-//                 if flag { t.negate() };
-//                 Some(t)
-//             },
-//             _ => None,
-//         };
-
-//         let new = Self {
-//             x: self.x.clone(),
-//             y: y_negated,
-//             z: self.z.clone(),
-//             value: new_value,
-//             circuit_params: self.circuit_params
-//         };
-
-//         Ok(new)
-//     }
-
-//     pub unsafe fn convert_to_affine<CS>(&self, cs: &mut CS) -> Result<AffinePoint<'a, E, G, T>, SynthesisError>
-//     where CS: ConstraintSystem<E> {
-//         let x = self.x.div(cs, &self.z)?;
-//         let y = self.y.div(cs, &self.z)?;
-//         let value = self.get_value();
-
-//         Ok(AffinePoint { x, y, value, circuit_params: self.circuit_params })
-//     }
-
-//     #[track_caller]
-//     pub fn convert_to_affine_or_default<CS: ConstraintSystem<E>>(
-//         &mut self, cs: &mut CS, default: &AffinePoint<'a, E, G, T>
-//     ) -> Result<(AffinePoint<'a, E, G, T>, Boolean), SynthesisError> {
-//         let params = self.x.representation_params;
-//         let is_point_at_infty = FieldElement::is_zero(&mut self.z, cs)?;
-//         let safe_z = FieldElement::conditionally_select(
-//             cs, &is_point_at_infty, &FieldElement::one(params), &self.z
-//         )?;
-//         let x_for_safe_z = self.x.div(cs, &safe_z)?;
-//         let y_for_safe_z = self.y.div(cs, &safe_z)?;
-//         let x = FieldElement::conditionally_select(cs, &is_point_at_infty, &default.x, &x_for_safe_z)?;
-//         let y = FieldElement::conditionally_select(cs, &is_point_at_infty, &default.y, &y_for_safe_z)?;
-
-//         let value = match (is_point_at_infty.get_value(), self.get_value(), AffinePoint::get_value(&default)) {
-//             (Some(true), _, Some(val)) | (Some(false), Some(val), _) => Some(val),
-//             _ => None,
-//         };
-
-//         let new = AffinePoint { x, y, value, circuit_params: self.circuit_params };
-//         Ok((new, is_point_at_infty))
-//     }
-
-//     pub fn convert_to_affine_or_uninitialized<CS: ConstraintSystem<E>>(
-//         &mut self, cs: &mut CS
-//     ) -> Result<(AffinePoint<'a, E, G, T>, Boolean), SynthesisError> {
-//         self.convert_to_affine_or_default(cs, &AffinePoint::uninitialized(self.circuit_params))
-//     }
-
-//     #[track_caller]
-//     fn add_sub_impl<CS>(&self, cs: &mut CS, other: &Self, is_subtraction: bool) -> Result<Self, SynthesisError>
-//     where CS: ConstraintSystem<E>
-//     {
-//         // this formula is only valid for curve of prime order with zero j-ivariant
-//         let params = self.circuit_params;
-//         assert!(G::a_coeff().is_zero());
-//         assert!(params.is_prime_order_curve);
-
-//         let curve_b: u64 = fe_to_biguint(&G::b_coeff()).to_u64().expect("G::b_ceoff should fit into u64");
-//         let curve_b3 = curve_b * 3;
-
-//         let x1 = self.x.clone();
-//         let y1 = self.y.clone();
-//         let z1 = self.z.clone();
-
-//         let x2 = other.x.clone();
-//         let y2 = other.y.clone();
-//         let gate_count_start = cs.get_current_step_number();
-//         let y2 = if is_subtraction { y2.negate(cs)? } else { y2 };
-//         let gate_count_end = cs.get_current_step_number();
-//         assert_eq!(gate_count_end - gate_count_start, 0);
-//         let z2 = other.z.clone();
-
-//         // t0 = x1 * x2
-//         let t0 = x1.mul(cs, &x2)?;
-//         // t1 = y1 * y2
-//         let t1 = y1.mul(cs, &y2)?;
-//         // t2 = z1 * z2
-//         let t2 = z1.mul(cs, &z2)?;
-//         // a1 = x1 + y1
-//         let a1 = x1.add(cs, &y1)?;
-//         // a2 = x2 + y2
-//         let a2 = x2.add(cs, &y2)?;
-//         // t3 = a1 * a2 - t0 - t1
-//         let mut chain = FieldElementsChain::new();
-//         chain.add_neg_term(&t0).add_neg_term(&t1);
-//         let t3 = FieldElement::mul_with_chain(cs, &a1, &a2, chain)?;
-//         // t4 = y1 + z1
-//         let t4 = y1.add(cs, &z1)?;
-//         // x3 = y2 + z2
-//         let x3 = y2.add(cs, &z2)?;
-//         // t4 = t4 * x3 - t1 - t2
-//         let mut chain = FieldElementsChain::new();
-//         chain.add_neg_term(&t1).add_neg_term(&t2);
-//         let t4 = FieldElement::mul_with_chain(cs, &t4, &x3, chain)?;
-//         // a3 = x1 + z1
-//         let a3 = x1.add(cs, &z1)?;
-//         // a4 = x2 + z2
-//         let a4 = x2.add(cs, &z2)?;
-//         // y3 = a3 * a4 - t0 - t2
-//         let mut chain = FieldElementsChain::new();
-//         chain.add_neg_term(&t0).add_neg_term(&t2);
-//         let y3 = FieldElement::mul_with_chain(cs, &a3, &a4, chain)?;
-//         // t2 = b3 * z1 * z2
-//         let b3_mul_z1 = z1.scale(cs, curve_b3)?;
-//         let t2 = b3_mul_z1.mul(cs, &z2)?;
-//         // z3 = t1 + t2
-//         let z3 = t1.add(cs, &t2)?;
-//         // t1 = t1 - t2
-//         let t1 = t1.sub(cs, &t2)?;
-//         // x3 = t4 * b3 * y3
-//         let b3_mul_y3 = y3.scale(cs, curve_b3)?;
-//         let x3 = b3_mul_y3.mul(cs, &t4)?;
-//         // x3 = t3 * t1 - x3
-//         let mut chain = FieldElementsChain::new();
-//         chain.add_neg_term(&x3);
-//         let x3 = FieldElement::mul_with_chain(cs, &t1, &t3, chain)?;
-//         // y3 = (b3 * y3) * (3 * t0)
-//         let t0_mul_3 = t0.scale(cs, 3)?;
-//         let y3 = b3_mul_y3.mul(cs, &t0_mul_3)?;
-//         // y3 = t1 * z3  + y3
-//         let mut chain = FieldElementsChain::new();
-//         chain.add_pos_term(&y3);
-//         let y3 = FieldElement::mul_with_chain(cs, &t1, &z3, chain)?;
-//         // z3 = z3 * t4
-//         let z3 = z3.mul(cs, &t4)?;
-//         // z3 = (3 * t0) * t3 + z3
-//         let mut chain = FieldElementsChain::new();
-//         chain.add_pos_term(&z3);
-//         let z3 = FieldElement::mul_with_chain(cs, &t0_mul_3, &t3, chain)?;
-
-//         let new_value = match (self.value, other.value) {
-//             (Some(this), Some(mut other)) => {
-//                 let mut tmp = this.into_projective();
-//                 if is_subtraction {
-//                     other.negate();
-//                 }
-//                 tmp.add_assign_mixed(&other);
-
-//                 Some(tmp.into_affine())
-//             },
-//             _ => None
-//         };
-
-//         let new = Self {
-//             x: x3, y: y3, z: z3,
-//             value: new_value,
-//             circuit_params: self.circuit_params
-//         };
-//         Ok(new)
-//     }
-
-//     pub fn add<CS: ConstraintSystem<E>>(&self, cs: &mut CS, other: &Self) -> Result<Self, SynthesisError>
-//     {
-//         self.add_sub_impl(cs, other, false)
-//     }
-
-//     pub fn sub<CS: ConstraintSystem<E>>(&self, cs: &mut CS, other: &Self) -> Result<Self, SynthesisError>
-//     {
-//         self.add_sub_impl(cs, other, true)
-//     }
-
-//     #[track_caller]
-//     pub fn double<CS: ConstraintSystem<E>>(&self, cs: &mut CS) -> Result<Self, SynthesisError> {
-//         // this formula is only valid for curve of prime order with zero j-ivariant
-//         let params = self.circuit_params;
-//         assert!(G::a_coeff().is_zero());
-//         assert!(params.is_prime_order_curve);
-
-//         let curve_b: u64 = fe_to_biguint(&G::b_coeff()).to_u64().expect("G::b_ceoff should fit into u64");
-//         let curve_b3 = curve_b * 3;
-
-//         let x = self.x.clone();
-//         let y = self.y.clone();
-//         let z = self.z.clone();
-
-//         // t0 = y * y
-//         let t0 = y.square(cs)?;
-//         // t2 = b3 * z * z
-//         let b3_mul_z = z.scale(cs, curve_b3)?;
-//         let t2 = b3_mul_z.mul(cs, &z)?;
-//         // y3 = t0 + t2
-//         let y3 = t0.add(cs, &t2)?;
-//         // t1 = y * z
-//         let t1 = y.mul(cs, &z)?;
-//         // z3 = 8 * t0 * t1
-//         let t0_mul_4 = t0.scale(cs, 4)?;
-//         let t0_mul_8 = t0_mul_4.double(cs)?;
-//         let z3 = t0_mul_8.mul(cs, &t1)?;
-//         // t4 = 4 * t0 - 3 * y3
-//         let y3_mul_3 = y3.scale(cs, 3)?;
-//         let t4 = t0_mul_4.sub(cs, &y3_mul_3)?;
-//         // y3 = t4 * y3
-//         let y3 = t4.mul(cs, &y3)?;
-//         // y3 = 8 * t0 * t2  + y3
-//         let mut chain = FieldElementsChain::new();
-//         chain.add_pos_term(&y3);
-//         let y3 = FieldElement::mul_with_chain(cs, &t0_mul_8, &t2, chain)?;
-//         // t1 = x * y
-//         let t1 = x.mul(cs, &y)?;
-//         // x3 = 2 * t4 * t1
-//         let t4_mul_2 = t4.double(cs)?;
-//         let x3 = t4_mul_2.mul(cs, &t1)?;
-
-//         let new_value = self.value.clone().map(|el| {
-//             let mut tmp = el.into_projective();
-//             tmp.double();
-//             tmp.into_affine()
-//         });
-
-//         let new = Self {
-//             x: x3, y: y3, z: z3,
-//             value: new_value,
-//             circuit_params: self.circuit_params
-//         };
-//         Ok(new)
-//     }
-
-//     pub fn conditionally_select<CS: ConstraintSystem<E>>(
-//         cs: &mut CS, flag: &Boolean, first: &Self, second: &Self
-//     ) -> Result<Self, SynthesisError> {
-
-//         let first_value = first.value;
-//         let second_value = second.value;
-//         let x = FieldElement::conditionally_select(cs, flag, &first.x, &second.x)?;
-//         let y = FieldElement::conditionally_select(cs, flag, &first.y, &second.y)?;
-//         let z = FieldElement::conditionally_select(cs, flag, &first.z, &second.z)?;
-
-//         let value = match (flag.get_value(), first_value, second_value) {
-//             (Some(true), Some(p), _) => Some(p),
-//             (Some(false), _, Some(p)) => Some(p),
-//             (_, _, _) => None
-//         };
-
-//         let circuit_params = first.circuit_params;
-//         let selected = Self { x, y, z, value, circuit_params };
-//         Ok(selected)
-//     }
-
-//     pub fn halving<CS: ConstraintSystem<E>>(&mut self, cs: &mut CS) -> Result<Self, SynthesisError> {
-//         let default = AffinePoint::constant(G::one(), self.circuit_params);
-//         let (mut affine_point, is_point_at_infty) = self.convert_to_affine_or_default(cs, &default)?;
-//         let wit = affine_point.get_value().map(|x| {
-//             // if x = 2 * y and order of group is n - odd prime, then:
-//             // (n-1)/2 * x = (n-1) * y = -y
-//             let mut scalar = <G::Scalar as PrimeField>::char();
-//             scalar.div2();
-//             let mut res = x.mul(scalar).into_affine();
-//             res.negate();
-//             res
-//         });
-
-//         let halved = AffinePoint::alloc(cs, wit, self.circuit_params)?;
-//         let mut initial = halved.double(cs)?;
-//         AffinePoint::enforce_equal(cs, &mut affine_point, &mut initial)?;
-
-//         ProjectivePoint::conditionally_select(
-//             cs, &is_point_at_infty, &ProjectivePoint::zero(self.circuit_params), &ProjectivePoint::from(halved)
-//         )
-//     }
-// }
+impl<F: SmallField, C: GenericCurveAffine, NN: NonNativeField<F, C::Base>> CircuitEq<F>
+    for SWProjectivePoint<F, C, NN>
+where
+    C::Base: pairing::ff::PrimeField,
+{
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        let mut a = a.clone();
+        let mut b = b.clone();
+        SWProjectivePoint::equals(cs, &mut a, &mut b)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        let mut a = a.clone();
+        let mut b = b.clone();
+        SWProjectivePoint::enforce_equal(cs, &mut a, &mut b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, StaticToolboxHolder};
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::non_native_field::implementations::*;
+    use crate::gadgets::tables::range_check_16_bits::{
+        create_range_check_16_bits_table, RangeCheck16BitsTable,
+    };
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+    use pairing::bn256::{Fq, Fr, G1Affine};
+    use pairing::ff::{Field, PrimeField};
+    use pairing::GenericCurveProjective;
+
+    type F = GoldilocksField;
+    type C = G1Affine;
+    type BaseNN = NonNativeFieldOverU16<F, Fq, 17>;
+
+    fn create_test_cs() -> CSReferenceImplementation<
+        F,
+        F,
+        DevCSConfig,
+        impl GateConfigurationHolder<F>,
+        impl StaticToolboxHolder,
+    > {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 24, 1 << 21);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 1,
+                num_repetitions: 10,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = DotProductGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<16>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ParallelSelectionGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        let table = create_range_check_16_bits_table();
+        owned_cs.add_lookup_table::<RangeCheck16BitsTable, 1>(table);
+
+        owned_cs
+    }
+
+    fn assert_point_value<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        point: &mut SWProjectivePoint<F, C, BaseNN>,
+        expected: C,
+    ) {
+        let ((x, y), is_infinity) = point.convert_to_affine_or_default(cs, C::one());
+        assert_eq!(
+            is_infinity.witness_hook(&*cs)().unwrap(),
+            expected.is_zero()
+        );
+        if expected.is_zero() == false {
+            let (expected_x, expected_y) = expected.into_xy_unchecked();
+            assert_eq!(x.witness_hook(&*cs)().unwrap().get(), expected_x);
+            assert_eq!(y.witness_hook(&*cs)().unwrap().get(), expected_y);
+        }
+    }
+
+    fn allocate_point<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        value: C,
+        params: &Arc<NonNativeFieldOverU16Params<Fq, 17>>,
+    ) -> SWProjectivePoint<F, C, BaseNN> {
+        let (x, y) = value.into_xy_unchecked();
+        let x = BaseNN::allocate_checked(cs, x, params);
+        let y = BaseNN::allocate_checked(cs, y, params);
+
+        SWProjectivePoint::from_xy_unchecked(cs, x, y)
+    }
+
+    #[test]
+    fn test_point_checks() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+
+        let p_value = C::one()
+            .mul(Fr::from_str("12345").unwrap().into_repr())
+            .into_affine();
+        let q_value = C::one()
+            .mul(Fr::from_str("67890").unwrap().into_repr())
+            .into_affine();
+
+        let mut p = allocate_point(cs, p_value, &params);
+        let mut q = allocate_point(cs, q_value, &params);
+        let is_on_curve = p.is_on_curve(cs);
+        assert!(is_on_curve.witness_hook(&*cs)().unwrap());
+        p.enforce_on_curve(cs);
+        p.enforce_in_subgroup(cs);
+
+        let mut zero = SWProjectivePoint::<F, C, BaseNN>::zero(cs, &params);
+        let is_on_curve = zero.is_on_curve(cs);
+        assert!(is_on_curve.witness_hook(&*cs)().unwrap());
+
+        let (x, y) = p_value.into_xy_unchecked();
+        let mut y_plus_one = y;
+        y_plus_one.add_assign(&Fq::one());
+        let x = BaseNN::allocate_checked(cs, x, &params);
+        let y_plus_one = BaseNN::allocate_checked(cs, y_plus_one, &params);
+        let mut off_curve = SWProjectivePoint::<F, C, BaseNN>::from_xy_unchecked(cs, x, y_plus_one);
+        let is_on_curve = off_curve.is_on_curve(cs);
+        assert!(!is_on_curve.witness_hook(&*cs)().unwrap());
+
+        // same points with different projective representations
+        let mut p_plus_q = p.add(cs, &mut q);
+        let mut q_plus_p = q.add(cs, &mut p);
+        let mut p_plus_q_affine = {
+            let mut sum = p_value.into_projective();
+            sum.add_assign_mixed(&q_value);
+            allocate_point(cs, sum.into_affine(), &params)
+        };
+        SWProjectivePoint::enforce_equal(cs, &mut p_plus_q, &mut q_plus_p);
+        SWProjectivePoint::enforce_equal(cs, &mut p_plus_q, &mut p_plus_q_affine);
+        let are_equal = CircuitEq::equals(cs, &p_plus_q, &q_plus_p);
+        assert!(are_equal.witness_hook(&*cs)().unwrap());
+
+        let are_equal = SWProjectivePoint::equals(cs, &mut p, &mut q);
+        assert!(!are_equal.witness_hook(&*cs)().unwrap());
+        let are_equal = SWProjectivePoint::equals(cs, &mut p, &mut zero);
+        assert!(!are_equal.witness_hook(&*cs)().unwrap());
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    fn test_halving() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+
+        let doubled_value = C::one()
+            .mul(Fr::from_str("246").unwrap().into_repr())
+            .into_affine();
+        let expected = C::one()
+            .mul(Fr::from_str("123").unwrap().into_repr())
+            .into_affine();
+
+        let mut doubled = allocate_point(cs, doubled_value, &params);
+        let mut halved = doubled.halving(cs);
+        assert_point_value(cs, &mut halved, expected);
+
+        let mut zero = SWProjectivePoint::<F, C, BaseNN>::zero(cs, &params);
+        let mut halved = zero.halving(cs);
+        assert_point_value(cs, &mut halved, C::zero());
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
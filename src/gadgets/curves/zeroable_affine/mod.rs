@@ -1,7 +1,19 @@
 use std::sync::Arc;
 
+use pairing::ff::{Field, LegendreSymbol, SqrtField};
 use pairing::GenericCurveAffine;
 
+use crate::config::*;
+use crate::cs::traits::cs::DstBuffer;
+use crate::gadgets::curves::sw_projective::SWProjectivePoint;
+use crate::gadgets::non_native_field::implementations::implementation_u16::FFProxyValue;
+use crate::gadgets::non_native_field::implementations::utils::fe_to_u16_words;
+use crate::gadgets::non_native_field::implementations::NonNativeFieldOverU16;
+use crate::gadgets::num::Num;
+use crate::gadgets::traits::allocatable::CSAllocatable;
+use crate::gadgets::traits::castable::WitnessCastable;
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+use crate::gadgets::traits::selectable::Selectable;
 use crate::{
     cs::traits::cs::ConstraintSystem,
     gadgets::{boolean::Boolean, non_native_field::traits::NonNativeField},
@@ -9,6 +21,8 @@ use crate::{
 
 use super::*;
 
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
 pub struct ZeroableAffinePoint<F: SmallField, C: GenericCurveAffine, NN: NonNativeField<F, C::Base>>
 where
     C::Base: pairing::ff::PrimeField,
@@ -42,6 +56,17 @@ where
         }
     }
 
+    pub fn from_xy_unchecked<CS: ConstraintSystem<F>>(cs: &mut CS, x: NN, y: NN) -> Self {
+        let boolean_false = Boolean::allocated_constant(cs, false);
+
+        Self {
+            x,
+            y,
+            is_zero: boolean_false,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
     pub fn same_x<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Boolean<F> {
         self.x.equals(cs, &mut other.x)
     }
@@ -70,4 +95,397 @@ where
 
         todo!()
     }
+
+    /// Returns whether the point is the point at infinity or satisfies `y^2 = x^3 + a * x + b`
+    pub fn is_on_curve<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        let params = self.x.get_params().clone();
+        let mut rhs = curve_equation_rhs::<F, CS, C, NN>(cs, &mut self.x, &params);
+        let mut lhs = self.y.square(cs);
+        let equation_holds = lhs.equals(cs, &mut rhs);
+
+        Boolean::multi_or(cs, &[self.is_zero, equation_holds])
+    }
+
+    pub fn enforce_on_curve<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        let is_on_curve = self.is_on_curve(cs);
+        let boolean_true = Boolean::allocated_constant(cs, true);
+        Boolean::enforce_equal(cs, &is_on_curve, &boolean_true);
+    }
+
+    /// Points are equal if both are the point at infinity, or both are not and have the same coordinates
+    pub fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &mut Self, b: &mut Self) -> Boolean<F> {
+        let same_x = a.same_x(cs, b);
+        let same_y = a.same_y(cs, b);
+        let both_are_zero = Boolean::multi_and(cs, &[a.is_zero, b.is_zero]);
+        let a_is_not_zero = a.is_zero.negated(cs);
+        let b_is_not_zero = b.is_zero.negated(cs);
+        let same_non_zero_points =
+            Boolean::multi_and(cs, &[a_is_not_zero, b_is_not_zero, same_x, same_y]);
+
+        Boolean::multi_or(cs, &[both_are_zero, same_non_zero_points])
+    }
+
+    pub fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &mut Self, b: &mut Self) {
+        let are_equal = Self::equals(cs, a, b);
+        let boolean_true = Boolean::allocated_constant(cs, true);
+        Boolean::enforce_equal(cs, &are_equal, &boolean_true);
+    }
+
+    /// Point at infinity is mapped into `(0, 1, 0)`
+    pub fn to_projective<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+    ) -> SWProjectivePoint<F, C, NN> {
+        let params = self.x.get_params().clone();
+        let zero_nn = NN::allocated_constant(cs, C::Base::zero(), &params);
+        let one_nn = NN::allocated_constant(cs, C::Base::one(), &params);
+
+        let x = NN::conditionally_select(cs, self.is_zero, &zero_nn, &self.x);
+        let y = NN::conditionally_select(cs, self.is_zero, &one_nn, &self.y);
+        let z = NN::conditionally_select(cs, self.is_zero, &zero_nn, &one_nn);
+
+        SWProjectivePoint {
+            x,
+            y,
+            z,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+// Returns `x^3 + a * x + b`
+fn curve_equation_rhs<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    C: GenericCurveAffine,
+    NN: NonNativeField<F, C::Base>,
+>(
+    cs: &mut CS,
+    x: &mut NN,
+    params: &Arc<NN::Params>,
+) -> NN
+where
+    C::Base: pairing::ff::PrimeField,
+{
+    let mut curve_b = NN::allocated_constant(cs, C::b_coeff(), params);
+    let mut x_squared = x.square(cs);
+    let mut rhs = x_squared.mul(cs, x);
+    if !C::a_coeff().is_zero() {
+        let mut curve_a = NN::allocated_constant(cs, C::a_coeff(), params);
+        let mut a_mul_x = x.mul(cs, &mut curve_a);
+        rhs = rhs.add(cs, &mut a_mul_x);
+    }
+
+    rhs.add(cs, &mut curve_b)
+}
+
+fn find_quadratic_non_residue<T: SqrtField>() -> T {
+    let mut candidate = T::one();
+    loop {
+        candidate.add_assign(&T::one());
+        if matches!(candidate.legendre(), LegendreSymbol::QuadraticNonResidue) {
+            return candidate;
+        }
+    }
+}
+
+impl<F: SmallField, C: GenericCurveAffine, const N: usize>
+    ZeroableAffinePoint<F, C, NonNativeFieldOverU16<F, C::Base, N>>
+where
+    C::Base: pairing::ff::PrimeField,
+    [(); N + 1]:,
+{
+    /// Decompresses the point from its x coordinate and parity of the y coordinate. If there is no point
+    /// with such x coordinate on the curve, then returns the point at infinity and `false`. Parity is only
+    /// well defined for curves without points of order two
+    pub fn decompress<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        x: &NonNativeFieldOverU16<F, C::Base, N>,
+        y_is_odd: Boolean<F>,
+    ) -> (Self, Boolean<F>) {
+        let params = x.params.clone();
+        let non_residue = find_quadratic_non_residue::<C::Base>();
+
+        let mut x = x.clone();
+        let mut rhs = curve_equation_rhs::<F, CS, C, _>(cs, &mut x, &params);
+        rhs.normalize(cs);
+
+        // if rhs is not a square, then `g * rhs` is for any non-residue `g`
+        let mut root = NonNativeFieldOverU16::allocate_checked_without_value(cs, &params);
+        let exists = Boolean::allocate_without_value(cs);
+
+        if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
+            let modulus_limbs = params.modulus_limbs;
+            let value_fn = move |inputs: &[F], dst: &mut DstBuffer<'_, '_, F>| {
+                let inputs: [F; N] = inputs.try_into().unwrap();
+                let rhs =
+                    <FFProxyValue<C::Base, N> as WitnessCastable<F, [F; N]>>::cast_from_source(
+                        inputs,
+                    )
+                    .get();
+                let (root, exists) = if let Some(root) = rhs.sqrt() {
+                    (root, true)
+                } else {
+                    let mut tmp = rhs;
+                    tmp.mul_assign(&non_residue);
+                    let root = tmp.sqrt().expect("must be a quadratic residue");
+
+                    (root, false)
+                };
+
+                let limbs = fe_to_u16_words::<_, N>(&root);
+                for el in limbs.into_iter().take(modulus_limbs) {
+                    dst.push(F::from_u64_unchecked(el as u64));
+                }
+                dst.push(F::from_u64_unchecked(exists as u64));
+            };
+
+            let mut outputs = Vec::with_capacity(params.modulus_limbs + 1);
+            outputs.extend(
+                Place::from_variables(root.limbs)
+                    .into_iter()
+                    .take(params.modulus_limbs),
+            );
+            outputs.push(Place::from_variable(exists.get_variable()));
+
+            cs.set_values_with_dependencies_vararg(
+                &Place::from_variables(rhs.limbs),
+                &outputs,
+                value_fn,
+            );
+        }
+
+        // root^2 == exists ? rhs : g * rhs
+        let mut non_residue = NonNativeFieldOverU16::allocated_constant(cs, non_residue, &params);
+        let twisted_rhs = rhs.mul(cs, &mut non_residue);
+        let mut expected =
+            NonNativeFieldOverU16::conditionally_select(cs, exists, &rhs, &twisted_rhs);
+        let mut root_squared = root.square(cs);
+        let roots_match = root_squared.equals(cs, &mut expected);
+        let boolean_true = Boolean::allocated_constant(cs, true);
+        Boolean::enforce_equal(cs, &roots_match, &boolean_true);
+
+        // allocated root is at most the modulus, and can only be equal to it if rhs is zero,
+        // so parity of the lowest limb is the parity of the root
+        let root_is_odd = Num::from_variable(root.limbs[0]).spread_into_bits::<_, 16>(cs)[0];
+        let should_negate = Boolean::xor(root_is_odd, cs, y_is_odd);
+        let negated_root = root.negated(cs);
+        let y =
+            NonNativeFieldOverU16::conditionally_select(cs, should_negate, &negated_root, &root);
+
+        let is_zero = exists.negated(cs);
+        let new = Self {
+            x: x.mask(cs, exists),
+            y: y.mask(cs, exists),
+            is_zero,
+            _marker: std::marker::PhantomData,
+        };
+
+        (new, exists)
+    }
+}
+
+impl<F: SmallField, C: GenericCurveAffine, NN: NonNativeField<F, C::Base>> Selectable<F>
+    for ZeroableAffinePoint<F, C, NN>
+where
+    C::Base: pairing::ff::PrimeField,
+{
+    const SUPPORTS_PARALLEL_SELECT: bool = false;
+
+    fn conditionally_select<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        flag: Boolean<F>,
+        a: &Self,
+        b: &Self,
+    ) -> Self {
+        let x = NN::conditionally_select(cs, flag, &a.x, &b.x);
+        let y = NN::conditionally_select(cs, flag, &a.y, &b.y);
+        let is_zero = Boolean::conditionally_select(cs, flag, &a.is_zero, &b.is_zero);
+
+        Self {
+            x,
+            y,
+            is_zero,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<F: SmallField, C: GenericCurveAffine, NN: NonNativeField<F, C::Base>> CircuitEq<F>
+    for ZeroableAffinePoint<F, C, NN>
+where
+    C::Base: pairing::ff::PrimeField,
+{
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        let mut a = a.clone();
+        let mut b = b.clone();
+        ZeroableAffinePoint::equals(cs, &mut a, &mut b)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        let mut a = a.clone();
+        let mut b = b.clone();
+        ZeroableAffinePoint::enforce_equal(cs, &mut a, &mut b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, StaticToolboxHolder};
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::non_native_field::implementations::*;
+    use crate::gadgets::tables::range_check_16_bits::{
+        create_range_check_16_bits_table, RangeCheck16BitsTable,
+    };
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+    use pairing::bn256::{Fq, Fr, G1Affine};
+    use pairing::ff::PrimeField;
+    use pairing::GenericCurveProjective;
+
+    type F = GoldilocksField;
+    type C = G1Affine;
+    type BaseNN = NonNativeFieldOverU16<F, Fq, 17>;
+
+    fn create_test_cs() -> CSReferenceImplementation<
+        F,
+        F,
+        DevCSConfig,
+        impl GateConfigurationHolder<F>,
+        impl StaticToolboxHolder,
+    > {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 24, 1 << 21);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 1,
+                num_repetitions: 10,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = DotProductGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<16>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ParallelSelectionGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        let table = create_range_check_16_bits_table();
+        owned_cs.add_lookup_table::<RangeCheck16BitsTable, 1>(table);
+
+        owned_cs
+    }
+
+    #[test]
+    fn test_decompress() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+
+        let value = C::one()
+            .mul(Fr::from_str("987654321").unwrap().into_repr())
+            .into_affine();
+        let (x_value, y_value) = value.into_xy_unchecked();
+        let y_is_odd = y_value.into_repr().is_odd();
+
+        let x = BaseNN::allocate_checked(cs, x_value, &params);
+        let y = BaseNN::allocate_checked(cs, y_value, &params);
+        let mut expected = ZeroableAffinePoint::<F, C, BaseNN>::from_xy_unchecked(cs, x.clone(), y);
+
+        let parity = Boolean::allocated_constant(cs, y_is_odd);
+        let (mut decompressed, exists) =
+            ZeroableAffinePoint::<F, C, BaseNN>::decompress(cs, &x, parity);
+        assert!(exists.witness_hook(&*cs)().unwrap());
+        decompressed.enforce_on_curve(cs);
+        ZeroableAffinePoint::enforce_equal(cs, &mut decompressed, &mut expected);
+
+        // other parity gives the negated point
+        let parity = Boolean::allocated_constant(cs, !y_is_odd);
+        let (mut decompressed, exists) =
+            ZeroableAffinePoint::<F, C, BaseNN>::decompress(cs, &x, parity);
+        assert!(exists.witness_hook(&*cs)().unwrap());
+        let mut negated_y = y_value;
+        negated_y.negate();
+        assert_eq!(
+            decompressed.y.witness_hook(&*cs)().unwrap().get(),
+            negated_y
+        );
+        let are_equal = ZeroableAffinePoint::equals(cs, &mut decompressed, &mut expected);
+        assert!(!are_equal.witness_hook(&*cs)().unwrap());
+
+        // find x such that x^3 + b is not a square
+        let mut x_value = Fq::one();
+        loop {
+            let mut rhs = x_value;
+            rhs.square();
+            rhs.mul_assign(&x_value);
+            rhs.add_assign(&C::b_coeff());
+            if matches!(rhs.legendre(), LegendreSymbol::QuadraticNonResidue) {
+                break;
+            }
+            x_value.add_assign(&Fq::one());
+        }
+        let x = BaseNN::allocate_checked(cs, x_value, &params);
+        let (mut decompressed, exists) =
+            ZeroableAffinePoint::<F, C, BaseNN>::decompress(cs, &x, parity);
+        assert!(!exists.witness_hook(&*cs)().unwrap());
+        assert!(decompressed.is_zero.witness_hook(&*cs)().unwrap());
+        decompressed.enforce_on_curve(cs);
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
 }
@@ -0,0 +1,678 @@
+use super::*;
+
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::non_native_field::traits::NonNativeField;
+use crate::gadgets::tower_extension::fq12::NonNativeFq12;
+use crate::gadgets::tower_extension::fq2::NonNativeFq2;
+use crate::gadgets::traits::allocatable::CSAllocatable;
+use pairing::bn256::{Fq, Fq12, Fq2, G1Affine, G2Affine};
+use pairing::ff::Field;
+use pairing::GenericCurveAffine;
+use std::sync::Arc;
+
+// Optimal ate pairing over BN254. The G2 points are represented over the D-type sextic twist
+// y^2 = x^3 + 3 / (9 + u) that is untwisted into E(Fq12) as (x, y) -> (x * w^2, y * w^3).
+// All the points are expected in affine form and not to be the point at infinity (e.g. G1 and
+// G2 are usually verification key constants or come from a proof where points at infinity are
+// not valid anyway). Points of G2 that are not constants must be checked with `enforce_on_curve`
+// and `enforce_in_subgroup` by the caller, as the twist has small order subgroups
+
+// curve parameter `u`, so the loop of the optimal ate pairing runs over `6u + 2`
+const BN_U: u64 = 4965661367192848881;
+
+/// Point of G1 in affine coordinates
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct G1AffinePoint<F: SmallField, NN: NonNativeField<F, Fq>> {
+    pub x: NN,
+    pub y: NN,
+    pub _marker: std::marker::PhantomData<F>,
+}
+
+impl<F: SmallField, NN: NonNativeField<F, Fq>> G1AffinePoint<F, NN> {
+    pub fn from_xy_unchecked(x: NN, y: NN) -> Self {
+        Self {
+            x,
+            y,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn allocate_checked<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        witness: G1Affine,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        assert!(!witness.is_zero(), "point at infinity is not supported");
+        let (x, y) = witness.into_xy_unchecked();
+        let x = NN::allocate_checked(cs, x, params);
+        let y = NN::allocate_checked(cs, y, params);
+
+        Self::from_xy_unchecked(x, y)
+    }
+
+    pub fn negated<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let y = self.y.negated(cs);
+
+        Self::from_xy_unchecked(self.x.clone(), y)
+    }
+
+    pub fn is_on_curve<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        let params = self.x.get_params().clone();
+        let mut b = NN::allocated_constant(cs, G1Affine::b_coeff(), &params);
+        let mut rhs = self.x.square(cs).mul(cs, &mut self.x).add(cs, &mut b);
+        let mut lhs = self.y.square(cs);
+
+        lhs.equals(cs, &mut rhs)
+    }
+
+    pub fn enforce_on_curve<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        let is_on_curve = self.is_on_curve(cs);
+        let boolean_true = Boolean::allocated_constant(cs, true);
+        Boolean::enforce_equal(cs, &is_on_curve, &boolean_true);
+    }
+}
+
+/// Point of G2 in affine coordinates over the twist
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct G2AffinePoint<F: SmallField, NN: NonNativeField<F, Fq>> {
+    pub x: NonNativeFq2<F, NN>,
+    pub y: NonNativeFq2<F, NN>,
+}
+
+impl<F: SmallField, NN: NonNativeField<F, Fq>> G2AffinePoint<F, NN> {
+    pub fn from_xy_unchecked(x: NonNativeFq2<F, NN>, y: NonNativeFq2<F, NN>) -> Self {
+        Self { x, y }
+    }
+
+    pub fn allocate_checked<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        witness: G2Affine,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        assert!(!witness.is_zero(), "point at infinity is not supported");
+        let (x, y) = witness.into_xy_unchecked();
+        let x = NonNativeFq2::allocate_checked(cs, x, params);
+        let y = NonNativeFq2::allocate_checked(cs, y, params);
+
+        Self::from_xy_unchecked(x, y)
+    }
+
+    pub fn negated<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let y = self.y.negated(cs);
+
+        Self::from_xy_unchecked(self.x.clone(), y)
+    }
+
+    pub fn is_on_curve<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        let params = self.x.get_params().clone();
+        let mut b = NonNativeFq2::allocated_constant(cs, G2Affine::b_coeff(), &params);
+        let mut rhs = self.x.square(cs).mul(cs, &mut self.x).add(cs, &mut b);
+        let mut lhs = self.y.square(cs);
+
+        lhs.equals(cs, &mut rhs)
+    }
+
+    pub fn enforce_on_curve<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        let is_on_curve = self.is_on_curve(cs);
+        let boolean_true = Boolean::allocated_constant(cs, true);
+        Boolean::enforce_equal(cs, &is_on_curve, &boolean_true);
+    }
+
+    /// Enforces that the point is in the prime order subgroup. Uses the endomorphism `psi`
+    /// (untwist-Frobenius-twist), for BN curves the point is in G2 iff `psi(Q) == [6u^2]Q`
+    /// (https://eprint.iacr.org/2022/348). The point is expected to be already checked to be on
+    /// the curve
+    pub fn enforce_in_subgroup<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        let mut psi = self.frobenius_map(cs, 1);
+        let mut multiple = self.mul_by_6u_squared(cs);
+
+        let x_is_equal = psi.x.equals(cs, &mut multiple.x);
+        let y_is_equal = psi.y.equals(cs, &mut multiple.y);
+        let is_in_subgroup = Boolean::multi_and(cs, &[x_is_equal, y_is_equal]);
+        let boolean_true = Boolean::allocated_constant(cs, true);
+        Boolean::enforce_equal(cs, &is_in_subgroup, &boolean_true);
+    }
+
+    // double-and-add over the bits of `6u^2`. For a point of the prime order subgroup none of the
+    // intermediate points is +-Q, so we enforce it for every addition - otherwise a point of small
+    // order could make the slope unconstrained
+    fn mul_by_6u_squared<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let scalar = 6 * (BN_U as u128) * (BN_U as u128);
+        let num_bits = 128 - scalar.leading_zeros() as usize;
+        let boolean_false = Boolean::allocated_constant(cs, false);
+
+        let mut result = self.clone();
+        for i in (0..(num_bits - 1)).rev() {
+            let (doubled, _) = double_with_slope(cs, &mut result);
+            result = doubled;
+            if (scalar >> i) & 1 == 1 {
+                let mut x_difference = self.x.sub(cs, &mut result.x);
+                let x_are_equal = x_difference.is_zero(cs);
+                Boolean::enforce_equal(cs, &x_are_equal, &boolean_false);
+
+                let (sum, _) = add_with_slope(cs, &mut result, self);
+                result = sum;
+            }
+        }
+
+        result
+    }
+
+    // Frobenius endomorphism on the twist: untwist, raise coordinates to `p^power` and twist back
+    fn frobenius_map<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, power: usize) -> Self {
+        let (gamma_x, gamma_y) = twist_frobenius_coefficients(power);
+        let params = self.x.get_params().clone();
+        let mut gamma_x = NonNativeFq2::allocated_constant(cs, gamma_x, &params);
+        let mut gamma_y = NonNativeFq2::allocated_constant(cs, gamma_y, &params);
+
+        let mut x = self.x.frobenius_map(cs, power).mul(cs, &mut gamma_x);
+        let mut y = self.y.frobenius_map(cs, power).mul(cs, &mut gamma_y);
+        x.normalize(cs);
+        y.normalize(cs);
+
+        Self::from_xy_unchecked(x, y)
+    }
+}
+
+// coefficients to multiply x and y by after raising them to `p^power`: those are `w^2` and `w^3`
+// (used for untwisting) raised to `p^power - 1`
+fn twist_frobenius_coefficients(power: usize) -> (Fq2, Fq2) {
+    let mut w_squared = Fq12::zero();
+    w_squared.c0.c1 = Fq2::one();
+    w_squared.frobenius_map(power);
+
+    let mut w_cubed = Fq12::zero();
+    w_cubed.c1.c1 = Fq2::one();
+    w_cubed.frobenius_map(power);
+
+    (w_squared.c0.c1, w_cubed.c1.c1)
+}
+
+// non-adjacent form of `6u + 2`, least significant digit first
+fn ate_loop_naf() -> Vec<i8> {
+    let mut k = 6 * (BN_U as u128) + 2;
+    let mut naf = vec![];
+    while k != 0 {
+        if k & 1 == 1 {
+            if k & 3 == 1 {
+                naf.push(1);
+                k -= 1;
+            } else {
+                naf.push(-1);
+                k += 1;
+            }
+        } else {
+            naf.push(0);
+        }
+        k >>= 1;
+    }
+
+    naf
+}
+
+// Line function through T and Q evaluated at P is `y_P + (d0 + d1 * v) * w` with
+// d0 = -lambda * x_P and d1 = lambda * x_T - y_T
+fn line_coefficients<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    lambda: &mut NonNativeFq2<F, NN>,
+    t: &mut G2AffinePoint<F, NN>,
+    negated_px: &mut NN,
+) -> (NonNativeFq2<F, NN>, NonNativeFq2<F, NN>) {
+    let d0 = lambda.mul_by_base_field(cs, negated_px);
+    let d1 = lambda.mul(cs, &mut t.x).sub(cs, &mut t.y);
+
+    (d0, d1)
+}
+
+// computes 2T and the tangent line at T
+fn doubling_step<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    t: &mut G2AffinePoint<F, NN>,
+    negated_px: &mut NN,
+) -> (
+    G2AffinePoint<F, NN>,
+    (NonNativeFq2<F, NN>, NonNativeFq2<F, NN>),
+) {
+    let (doubled, mut lambda) = double_with_slope(cs, t);
+    let line = line_coefficients(cs, &mut lambda, t, negated_px);
+
+    (doubled, line)
+}
+
+// computes 2T and the slope of the tangent at T
+fn double_with_slope<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    t: &mut G2AffinePoint<F, NN>,
+) -> (G2AffinePoint<F, NN>, NonNativeFq2<F, NN>) {
+    // lambda = 3 * x^2 / (2 * y)
+    let mut x_squared = t.x.square(cs);
+    let mut numerator = x_squared.double(cs).add(cs, &mut x_squared);
+    let mut denominator = t.y.double(cs);
+    let mut lambda = numerator.div_unchecked(cs, &mut denominator);
+
+    // x' = lambda^2 - 2 * x
+    let mut two_x = t.x.double(cs);
+    let mut x = lambda.square(cs).sub(cs, &mut two_x);
+    // y' = lambda * (x - x') - y
+    let mut y = t.x.sub(cs, &mut x).mul(cs, &mut lambda).sub(cs, &mut t.y);
+    x.normalize(cs);
+    y.normalize(cs);
+
+    (G2AffinePoint::from_xy_unchecked(x, y), lambda)
+}
+
+// computes T + Q and the line through T and Q, T != +-Q
+fn addition_step<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    t: &mut G2AffinePoint<F, NN>,
+    q: &mut G2AffinePoint<F, NN>,
+    negated_px: &mut NN,
+) -> (
+    G2AffinePoint<F, NN>,
+    (NonNativeFq2<F, NN>, NonNativeFq2<F, NN>),
+) {
+    let (sum, mut lambda) = add_with_slope(cs, t, q);
+    let line = line_coefficients(cs, &mut lambda, t, negated_px);
+
+    (sum, line)
+}
+
+// computes T + Q and the slope of the line through T and Q, T != +-Q
+fn add_with_slope<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    t: &mut G2AffinePoint<F, NN>,
+    q: &mut G2AffinePoint<F, NN>,
+) -> (G2AffinePoint<F, NN>, NonNativeFq2<F, NN>) {
+    // lambda = (y_Q - y_T) / (x_Q - x_T)
+    let mut numerator = q.y.sub(cs, &mut t.y);
+    let mut denominator = q.x.sub(cs, &mut t.x);
+    let mut lambda = numerator.div_unchecked(cs, &mut denominator);
+
+    // x' = lambda^2 - x_T - x_Q
+    let mut x = lambda.square(cs).sub(cs, &mut t.x).sub(cs, &mut q.x);
+    // y' = lambda * (x_T - x') - y_T
+    let mut y = t.x.sub(cs, &mut x).mul(cs, &mut lambda).sub(cs, &mut t.y);
+    x.normalize(cs);
+    y.normalize(cs);
+
+    (G2AffinePoint::from_xy_unchecked(x, y), lambda)
+}
+
+/// Product of Miller loops of the optimal ate pairing for all the pairs, without the final exponentiation
+pub fn multi_miller_loop<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    pairs: &mut [(G1AffinePoint<F, NN>, G2AffinePoint<F, NN>)],
+) -> NonNativeFq12<F, NN> {
+    assert!(!pairs.is_empty());
+    let params = pairs[0].0.x.get_params().clone();
+
+    let mut negated_pxs: Vec<_> = pairs.iter_mut().map(|(p, _)| p.x.negated(cs)).collect();
+    let mut negated_qs: Vec<_> = pairs.iter_mut().map(|(_, q)| q.negated(cs)).collect();
+    let mut ts: Vec<_> = pairs.iter().map(|(_, q)| q.clone()).collect();
+
+    let mut f = NonNativeFq12::one(cs, &params);
+
+    let naf = ate_loop_naf();
+    // most significant digit is 1 and is accounted by starting from T = Q
+    for (idx, digit) in naf.iter().rev().skip(1).enumerate() {
+        if idx != 0 {
+            f = f.square(cs);
+        }
+
+        for (((p, q), negated_q), (t, negated_px)) in pairs
+            .iter_mut()
+            .zip(negated_qs.iter_mut())
+            .zip(ts.iter_mut().zip(negated_pxs.iter_mut()))
+        {
+            let (doubled, (mut d0, mut d1)) = doubling_step(cs, t, negated_px);
+            *t = doubled;
+            f = f.mul_by_034(cs, &mut p.y, &mut d0, &mut d1);
+
+            let q = match *digit {
+                1 => q,
+                -1 => negated_q,
+                _ => continue,
+            };
+            let (sum, (mut d0, mut d1)) = addition_step(cs, t, q, negated_px);
+            *t = sum;
+            f = f.mul_by_034(cs, &mut p.y, &mut d0, &mut d1);
+        }
+    }
+
+    // final steps with Q_1 = pi(Q) and Q_2 = -pi^2(Q)
+    for ((p, q), (t, negated_px)) in pairs
+        .iter_mut()
+        .zip(ts.iter_mut().zip(negated_pxs.iter_mut()))
+    {
+        let mut q1 = q.frobenius_map(cs, 1);
+        let mut q2 = q.frobenius_map(cs, 2).negated(cs);
+
+        let (sum, (mut d0, mut d1)) = addition_step(cs, t, &mut q1, negated_px);
+        *t = sum;
+        f = f.mul_by_034(cs, &mut p.y, &mut d0, &mut d1);
+
+        let (_, (mut d0, mut d1)) = addition_step(cs, t, &mut q2, negated_px);
+        f = f.mul_by_034(cs, &mut p.y, &mut d0, &mut d1);
+    }
+
+    f
+}
+
+// f^u for f in the cyclotomic subgroup
+fn pow_u<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    f: &mut NonNativeFq12<F, NN>,
+) -> NonNativeFq12<F, NN> {
+    let num_bits = 64 - BN_U.leading_zeros();
+    let mut result = f.clone();
+    for i in (0..(num_bits - 1)).rev() {
+        result = result.square(cs);
+        if (BN_U >> i) & 1 == 1 {
+            result = result.mul(cs, f);
+        }
+    }
+
+    result
+}
+
+// f^(-u) for f in the cyclotomic subgroup
+fn pow_negative_u<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    f: &mut NonNativeFq12<F, NN>,
+) -> NonNativeFq12<F, NN> {
+    pow_u(cs, f).conjugate(cs)
+}
+
+/// Raises the result of the Miller loop to the power `(p^12 - 1) / r`, up to a fixed
+/// multiplier coprime with `r` in the hard part
+pub fn final_exponentiation<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    f: &mut NonNativeFq12<F, NN>,
+) -> NonNativeFq12<F, NN> {
+    // easy part: f^((p^6 - 1) * (p^2 + 1)), the result is in the cyclotomic subgroup
+    let mut f_inverse = f.inverse_unchecked(cs);
+    let mut r = f.conjugate(cs).mul(cs, &mut f_inverse);
+    let mut r = r.frobenius_map(cs, 2).mul(cs, &mut r);
+
+    // hard part following Fuentes-Castaneda, Knapp and Rodriguez-Henriquez,
+    // "Faster hashing to G2", that computes a power of the exponent (p^4 - p^2 + 1) / r
+    let mut y0 = pow_negative_u(cs, &mut r);
+    let mut y1 = y0.square(cs);
+    let mut y2 = y1.square(cs);
+    let mut y3 = y2.mul(cs, &mut y1);
+    let mut y4 = pow_negative_u(cs, &mut y3);
+    let mut y5 = y4.square(cs);
+    let mut y6 = pow_negative_u(cs, &mut y5);
+    let mut y3 = y3.conjugate(cs);
+    let mut y6 = y6.conjugate(cs);
+    let mut y7 = y6.mul(cs, &mut y4);
+    let mut y8 = y7.mul(cs, &mut y3);
+    let mut y9 = y8.mul(cs, &mut y1);
+    let mut y10 = y8.mul(cs, &mut y4);
+    let mut y11 = y10.mul(cs, &mut r);
+    let mut y12 = y9.frobenius_map(cs, 1);
+    let mut y13 = y12.mul(cs, &mut y11);
+    let mut y8 = y8.frobenius_map(cs, 2);
+    let mut y14 = y8.mul(cs, &mut y13);
+    let mut r = r.conjugate(cs);
+    let mut y15 = r.mul(cs, &mut y9).frobenius_map(cs, 3);
+
+    y15.mul(cs, &mut y14)
+}
+
+/// Checks that `e(P_0, Q_0) * ... * e(P_n, Q_n) == 1`
+pub fn pairing_check<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    pairs: &mut [(G1AffinePoint<F, NN>, G2AffinePoint<F, NN>)],
+) -> Boolean<F> {
+    let mut f = multi_miller_loop(cs, pairs);
+    let mut result = final_exponentiation(cs, &mut f);
+
+    result.is_one(cs)
+}
+
+/// Checks that `e(A, B) == e(C, D)`
+pub fn pairing_equals<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    a: &mut G1AffinePoint<F, NN>,
+    b: &mut G2AffinePoint<F, NN>,
+    c: &mut G1AffinePoint<F, NN>,
+    d: &mut G2AffinePoint<F, NN>,
+) -> Boolean<F> {
+    // e(A, B) == e(C, D) <=> e(A, B) * e(-C, D) == 1
+    let c_negated = c.negated(cs);
+    let mut pairs = [(a.clone(), b.clone()), (c_negated, d.clone())];
+
+    pairing_check(cs, &mut pairs)
+}
+
+pub fn enforce_pairing_equality<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    NN: NonNativeField<F, Fq>,
+>(
+    cs: &mut CS,
+    a: &mut G1AffinePoint<F, NN>,
+    b: &mut G2AffinePoint<F, NN>,
+    c: &mut G1AffinePoint<F, NN>,
+    d: &mut G2AffinePoint<F, NN>,
+) {
+    let are_equal = pairing_equals(cs, a, b, c, d);
+    let boolean_true = Boolean::allocated_constant(cs, true);
+    Boolean::enforce_equal(cs, &are_equal, &boolean_true);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::*;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, StaticToolboxHolder};
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::non_native_field::implementations::*;
+    use crate::gadgets::tables::range_check_16_bits::{
+        create_range_check_16_bits_table, RangeCheck16BitsTable,
+    };
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+    use pairing::bn256::{Bn256, Fr};
+    use pairing::ff::PrimeField;
+    use pairing::{Engine, GenericCurveProjective};
+
+    type F = GoldilocksField;
+    type NN = NonNativeFieldOverU16<F, Fq, 17>;
+
+    // Same as `DevCSConfig`, but without runtime asserts, so a malicious witness
+    // gets to the satisfiability check instead of failing during synthesis
+    #[derive(Derivative)]
+    #[derivative(Clone, Copy, Debug)]
+    struct NoRuntimeAssertsCSConfig;
+
+    impl CSConfig for NoRuntimeAssertsCSConfig {
+        type WitnessConfig = DoEvaluateWitenss;
+        type DebugConfig = DontPerformRuntimeAsserts;
+        type SetupConfig = DoKeepSetup;
+        type ResolverConfig = Resolver<DontPerformRuntimeAsserts>;
+    }
+
+    fn create_test_cs<CFG: CSConfig>() -> CSReferenceImplementation<
+        F,
+        F,
+        CFG,
+        impl GateConfigurationHolder<F>,
+        impl StaticToolboxHolder,
+    > {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, CFG>::new(geometry, 1 << 26, 1 << 22);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 1,
+                num_repetitions: 10,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = DotProductGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<16>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        let table = create_range_check_16_bits_table();
+        owned_cs.add_lookup_table::<RangeCheck16BitsTable, 1>(table);
+
+        owned_cs
+    }
+
+    fn g1_mul(scalar: &str) -> G1Affine {
+        G1Affine::one()
+            .mul(Fr::from_str(scalar).unwrap().into_repr())
+            .into_affine()
+    }
+
+    fn g2_mul(scalar: &str) -> G2Affine {
+        G2Affine::one()
+            .mul(Fr::from_str(scalar).unwrap().into_repr())
+            .into_affine()
+    }
+
+    #[test]
+    fn test_ate_loop_naf() {
+        let naf = ate_loop_naf();
+        assert_eq!(naf.len(), 66);
+        assert_eq!(*naf.last().unwrap(), 1);
+
+        let mut value = 0i128;
+        for digit in naf.iter().rev() {
+            value = value * 2 + (*digit as i128);
+        }
+        assert_eq!(value, 6 * (BN_U as i128) + 2);
+        for pair in naf.windows(2) {
+            assert!(pair[0] == 0 || pair[1] == 0);
+        }
+    }
+
+    #[test]
+    fn test_pairing_equality() {
+        let mut owned_cs = create_test_cs::<DevCSConfig>();
+        let cs = &mut owned_cs;
+
+        let params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+
+        // e(a * G1, b * G2) == e(ab * G1, G2) != e(ab * G1, 2 * G2)
+        let a_value = g1_mul("1234567");
+        let b_value = g2_mul("7654321");
+        let c_value = g1_mul(&(1234567u64 * 7654321u64).to_string());
+        let d_value = G2Affine::one();
+        let wrong_d_value = g2_mul("2");
+
+        assert_eq!(
+            Bn256::pairing(a_value, b_value),
+            Bn256::pairing(c_value, d_value)
+        );
+        assert_ne!(
+            Bn256::pairing(a_value, b_value),
+            Bn256::pairing(c_value, wrong_d_value)
+        );
+
+        let mut a = G1AffinePoint::<F, NN>::allocate_checked(cs, a_value, &params);
+        let mut b = G2AffinePoint::<F, NN>::allocate_checked(cs, b_value, &params);
+        let mut c = G1AffinePoint::<F, NN>::allocate_checked(cs, c_value, &params);
+        let mut d = G2AffinePoint::<F, NN>::allocate_checked(cs, d_value, &params);
+        let mut wrong_d = G2AffinePoint::<F, NN>::allocate_checked(cs, wrong_d_value, &params);
+
+        a.enforce_on_curve(cs);
+        b.enforce_on_curve(cs);
+        c.enforce_on_curve(cs);
+        d.enforce_on_curve(cs);
+        b.enforce_in_subgroup(cs);
+        d.enforce_in_subgroup(cs);
+
+        enforce_pairing_equality(cs, &mut a, &mut b, &mut c, &mut d);
+
+        let are_equal = pairing_equals(cs, &mut a, &mut b, &mut c, &mut wrong_d);
+        assert!(!are_equal.witness_hook(&*cs)().unwrap());
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+    #[test]
+    #[should_panic(expected = "Unsatisfied")]
+    fn test_g2_point_outside_of_subgroup() {
+        let mut owned_cs = create_test_cs::<NoRuntimeAssertsCSConfig>();
+        let cs = &mut owned_cs;
+
+        let params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+
+        // a point of the twist with order that is not a multiple of r
+        let fq = |value: &str| Fq::from_str(value).unwrap();
+        let x = Fq2 {
+            c0: fq("3413513218498352040262653353725127729454431939539290118844322056224532443637"),
+            c1: fq("6077776500692565155461894309070795882353485867345896979329447163197530625403"),
+        };
+        let y = Fq2 {
+            c0: fq("18972040827057976279578674193485006817720367375218094084514427982330241426754"),
+            c1: fq("14006867913696578087287790000729026916336898124340216741382503925435473444548"),
+        };
+        let point_value = G2Affine::from_xy_unchecked(x, y);
+
+        let mut point = G2AffinePoint::<F, NN>::allocate_checked(cs, point_value, &params);
+        point.enforce_on_curve(cs);
+        point.enforce_in_subgroup(cs);
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        owned_cs.check_if_satisfied(&worker);
+    }
+}
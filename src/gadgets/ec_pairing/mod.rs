@@ -0,0 +1,3 @@
+use super::*;
+
+pub mod bn256;
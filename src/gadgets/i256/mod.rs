@@ -0,0 +1,499 @@
+use super::*;
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::cs::traits::cs::DstBuffer;
+use crate::cs::Variable;
+use crate::field::SmallField;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::i32::Int32;
+use crate::gadgets::i64::Int64;
+use crate::gadgets::impls::limbs_arithmetic::*;
+use crate::gadgets::traits::allocatable::CSAllocatable;
+use crate::gadgets::traits::allocatable::CSAllocatableExt;
+use crate::gadgets::traits::castable::Convertor;
+use crate::gadgets::traits::castable::WitnessCastable;
+use crate::gadgets::traits::witnessable::CSWitnessable;
+use crate::gadgets::traits::witnessable::WitnessHookable;
+use crate::gadgets::u256::UInt256;
+use crate::gadgets::u32::UInt32;
+use ethereum_types::U256;
+
+// Signed 256-bit integer in two's complement representation, same as EVM word for
+// signed opcodes. Witness is the same `U256` with the highest bit being the sign
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug, Hash)]
+pub struct Int256<F: SmallField> {
+    pub inner: UInt256<F>,
+}
+
+impl<F: SmallField> CSAllocatable<F> for Int256<F> {
+    type Witness = U256;
+    fn placeholder_witness() -> Self::Witness {
+        U256::zero()
+    }
+
+    #[inline(always)]
+    fn allocate_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        let inner = UInt256::allocate_without_value(cs);
+
+        Self { inner }
+    }
+
+    #[inline(always)]
+    fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
+        let inner = UInt256::allocate(cs, witness);
+
+        Self { inner }
+    }
+
+    #[inline(always)]
+    fn allocate_constant<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
+        Self::allocated_constant(cs, witness)
+    }
+}
+
+impl<F: SmallField> CSAllocatableExt<F> for Int256<F> {
+    const INTERNAL_STRUCT_LEN: usize = 8;
+
+    fn witness_from_set_of_values(values: [F; Self::INTERNAL_STRUCT_LEN]) -> Self::Witness {
+        crate::gadgets::u256::recompose_u256_as_u32x8(
+            values.map(|el| <u32 as WitnessCastable<F, F>>::cast_from_source(el)),
+        )
+    }
+
+    // we should be able to allocate without knowing values yet
+    fn create_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        Self::allocate_without_value(cs)
+    }
+
+    fn flatten_as_variables(&self) -> [Variable; Self::INTERNAL_STRUCT_LEN]
+    where
+        [(); Self::INTERNAL_STRUCT_LEN]:,
+    {
+        self.inner.inner.map(|el| el.get_variable())
+    }
+
+    fn set_internal_variables_values(witness: Self::Witness, dst: &mut DstBuffer<'_, '_, F>) {
+        UInt256::set_internal_variables_values(witness, dst);
+    }
+}
+
+impl<F: SmallField> CSWitnessable<F, 8> for Int256<F> {
+    type ConversionFunction = Convertor<F, [F; 8], U256>;
+
+    fn witness_from_set_of_values(values: [F; 8]) -> Self::Witness {
+        WitnessCastable::cast_from_source(values)
+    }
+
+    fn as_variables_set(&self) -> [Variable; 8] {
+        self.inner.inner.map(|el| el.get_variable())
+    }
+}
+
+impl<F: SmallField> WitnessHookable<F> for Int256<F> {
+    fn witness_hook<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &CS,
+    ) -> Box<dyn FnOnce() -> Option<Self::Witness>> {
+        let raw_witness = self.get_witness(cs);
+        Box::new(move || raw_witness.wait())
+    }
+}
+
+use crate::gadgets::traits::selectable::Selectable;
+
+impl<F: SmallField> Selectable<F> for Int256<F> {
+    fn conditionally_select<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        flag: Boolean<F>,
+        a: &Self,
+        b: &Self,
+    ) -> Self {
+        let inner = UInt256::conditionally_select(cs, flag, &a.inner, &b.inner);
+
+        Self { inner }
+    }
+}
+
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+
+impl<F: SmallField> CircuitEq<F> for Int256<F> {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        UInt256::equals(cs, &a.inner, &b.inner)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        <UInt256<F> as CircuitEq<F>>::enforce_equal(cs, &a.inner, &b.inner)
+    }
+}
+
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitOrd<F> for Int256<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        signed_less_than_limbs(cs, &a.inner.inner, &b.inner.inner)
+    }
+}
+
+impl<F: SmallField> Int256<F> {
+    pub fn allocated_constant<CS: ConstraintSystem<F>>(cs: &mut CS, constant: U256) -> Self {
+        let inner = UInt256::allocated_constant(cs, constant);
+
+        Self { inner }
+    }
+
+    pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        Self::allocated_constant(cs, U256::zero())
+    }
+
+    // Reinterprets the bits of unsigned integer
+    pub const fn from_unsigned(value: UInt256<F>) -> Self {
+        Self { inner: value }
+    }
+
+    // Reinterprets the bits as unsigned integer
+    pub const fn as_unsigned(&self) -> UInt256<F> {
+        self.inner
+    }
+
+    // Sign extension, same as `value as i256` would be
+    pub fn from_int32<CS: ConstraintSystem<F>>(cs: &mut CS, value: &Int32<F>) -> Self {
+        let is_negative = value.is_negative(cs);
+        let extension = sign_extension_limb(cs, is_negative);
+        let mut inner = [extension; 8];
+        inner[0] = value.inner;
+
+        Self {
+            inner: UInt256 { inner },
+        }
+    }
+
+    // Sign extension, same as `value as i256` would be
+    pub fn from_int64<CS: ConstraintSystem<F>>(cs: &mut CS, value: &Int64<F>) -> Self {
+        let is_negative = value.is_negative(cs);
+        let extension = sign_extension_limb(cs, is_negative);
+        let mut inner = [extension; 8];
+        inner[..2].copy_from_slice(&value.inner);
+
+        Self {
+            inner: UInt256 { inner },
+        }
+    }
+
+    pub fn is_negative<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Boolean<F> {
+        is_negative_limbs(cs, &self.inner.inner)
+    }
+
+    pub fn is_zero<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Boolean<F> {
+        self.inner.is_zero(cs)
+    }
+
+    // Two's complement negation, `MIN` stays unchanged
+    pub fn wrapping_neg<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Self {
+        let inner = negate_limbs(cs, &self.inner.inner);
+
+        Self {
+            inner: UInt256 { inner },
+        }
+    }
+
+    // Absolute value as unsigned integer, so it's defined for `MIN` too
+    pub fn unsigned_abs<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> UInt256<F> {
+        let is_negative = self.is_negative(cs);
+        let inner = conditionally_negate_limbs(cs, is_negative, &self.inner.inner);
+
+        UInt256 { inner }
+    }
+
+    // Returns wrapped sum and a flag if it overflows the signed range
+    pub fn overflowing_add<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        other: &Self,
+    ) -> (Self, Boolean<F>) {
+        let (inner, overflow) =
+            signed_overflowing_add_limbs(cs, &self.inner.inner, &other.inner.inner);
+
+        (
+            Self {
+                inner: UInt256 { inner },
+            },
+            overflow,
+        )
+    }
+
+    // Returns wrapped difference and a flag if it overflows the signed range
+    pub fn overflowing_sub<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        other: &Self,
+    ) -> (Self, Boolean<F>) {
+        let (inner, overflow) =
+            signed_overflowing_sub_limbs(cs, &self.inner.inner, &other.inner.inner);
+
+        (
+            Self {
+                inner: UInt256 { inner },
+            },
+            overflow,
+        )
+    }
+
+    // Returns results of `SDIV` and `SMOD` opcodes: quotient truncated towards zero and
+    // remainder having the sign of the dividend. Division by zero gives zero quotient and
+    // zero remainder, and `MIN / -1` wraps into `MIN`
+    pub fn div_rem<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> (Self, Self) {
+        let (quotient, remainder) = signed_div_rem_limbs(cs, &self.inner.inner, &other.inner.inner);
+
+        (
+            Self {
+                inner: UInt256 { inner: quotient },
+            },
+            Self {
+                inner: UInt256 { inner: remainder },
+            },
+        )
+    }
+
+    // Returns `self >> amount` filling the highest bits with the sign, `amount` must be less than 256
+    pub fn arithmetic_shr<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        assert!(amount < 256);
+        if amount == 0 {
+            return *self;
+        }
+
+        let is_negative = self.is_negative(cs);
+        let shifted = self.inner.shr(cs, amount);
+        let fill = UInt256::allocated_constant(cs, !(U256::MAX >> amount));
+        let inner = fill_sign_bits_limbs(cs, &shifted.inner, &fill.inner, is_negative);
+
+        Self {
+            inner: UInt256 { inner },
+        }
+    }
+
+    // Returns result of `SAR` opcode, so any `shift >= 256` gives 0 or -1 depending on the sign
+    pub fn arithmetic_shr_variable<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> Self {
+        let is_negative = self.is_negative(cs);
+        let (shifted, _) = self.inner.overflowing_shr(cs, shift);
+        let all_ones = UInt256::allocated_constant(cs, U256::MAX);
+        let (fill, _) = all_ones.overflowing_shr(cs, shift);
+        let fill = fill.not(cs);
+        let inner = fill_sign_bits_limbs(cs, &shifted.inner, &fill.inner, is_negative);
+
+        Self {
+            inner: UInt256 { inner },
+        }
+    }
+}
+
+use crate::gadgets::traits::encodable::CircuitVarLengthEncodable;
+
+impl<F: SmallField> CircuitVarLengthEncodable<F> for Int256<F> {
+    #[inline(always)]
+    fn encoding_length(&self) -> usize {
+        8
+    }
+    fn encode_to_buffer<CS: ConstraintSystem<F>>(&self, cs: &mut CS, dst: &mut Vec<Variable>) {
+        CircuitVarLengthEncodable::<F>::encode_to_buffer(&self.inner, cs, dst);
+    }
+}
+
+use crate::gadgets::traits::allocatable::CSPlaceholder;
+
+impl<F: SmallField> CSPlaceholder<F> for Int256<F> {
+    fn placeholder<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        Self::zero(cs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, StaticToolboxHolder};
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::tables::{
+        and8::{create_and8_table, And8Table},
+        byte_split::{create_byte_split_table, ByteSplitTable},
+        xor8::{create_xor8_table, Xor8Table},
+    };
+    use crate::worker::Worker;
+
+    type F = GoldilocksField;
+
+    fn create_test_cs() -> CSReferenceImplementation<
+        F,
+        F,
+        DevCSConfig,
+        impl GateConfigurationHolder<F>,
+        impl StaticToolboxHolder,
+    > {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 24, 1 << 20);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 8,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<32>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = U8x4FMAGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ParallelSelectionGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+
+        let table = create_and8_table();
+        owned_cs.add_lookup_table::<And8Table, 3>(table);
+
+        let table = create_byte_split_table::<F, 1>();
+        owned_cs.add_lookup_table::<ByteSplitTable<1>, 3>(table);
+        let table = create_byte_split_table::<F, 2>();
+        owned_cs.add_lookup_table::<ByteSplitTable<2>, 3>(table);
+        let table = create_byte_split_table::<F, 3>();
+        owned_cs.add_lookup_table::<ByteSplitTable<3>, 3>(table);
+        let table = create_byte_split_table::<F, 4>();
+        owned_cs.add_lookup_table::<ByteSplitTable<4>, 3>(table);
+        let table = create_byte_split_table::<F, 5>();
+        owned_cs.add_lookup_table::<ByteSplitTable<5>, 3>(table);
+        let table = create_byte_split_table::<F, 6>();
+        owned_cs.add_lookup_table::<ByteSplitTable<6>, 3>(table);
+        let table = create_byte_split_table::<F, 7>();
+        owned_cs.add_lookup_table::<ByteSplitTable<7>, 3>(table);
+
+        owned_cs
+    }
+
+    fn signed(value: i64) -> U256 {
+        if value < 0 {
+            U256::MAX - U256::from(value.unsigned_abs()) + U256::one()
+        } else {
+            U256::from(value as u64)
+        }
+    }
+
+    #[test]
+    fn test_sdiv_smod_and_sar() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let min = U256::one() << 255;
+        let minus_one = U256::MAX;
+
+        // (dividend, divisor, SDIV, SMOD)
+        let cases = [
+            (signed(7), signed(2), signed(3), signed(1)),
+            (signed(-7), signed(2), signed(-3), signed(-1)),
+            (signed(7), signed(-2), signed(-3), signed(1)),
+            (signed(-7), signed(-2), signed(3), signed(-1)),
+            (signed(-7), U256::zero(), U256::zero(), U256::zero()),
+            (min, minus_one, min, U256::zero()),
+            (min, signed(2), min >> 1 | min, U256::zero()),
+            (signed(5), min, U256::zero(), signed(5)),
+        ];
+
+        for (a, b, quotient, remainder) in cases {
+            let a = Int256::allocate(cs, a);
+            let b = Int256::allocate(cs, b);
+            let (q, r) = a.div_rem(cs, &b);
+            assert_eq!(q.witness_hook(&*cs)().unwrap(), quotient);
+            assert_eq!(r.witness_hook(&*cs)().unwrap(), remainder);
+        }
+
+        let minus_two = Int256::allocated_constant(cs, signed(-2));
+        let three = Int256::allocated_constant(cs, signed(3));
+        let is_less = Int256::less_than(cs, &minus_two, &three);
+        assert!(is_less.witness_hook(&*cs)().unwrap());
+        let is_less = Int256::less_than(cs, &three, &minus_two);
+        assert!(!is_less.witness_hook(&*cs)().unwrap());
+
+        let value = Int32::allocate(cs, -5);
+        let extended = Int256::from_int32(cs, &value);
+        assert_eq!(extended.witness_hook(&*cs)().unwrap(), signed(-5));
+        let value = Int64::allocate(cs, i64::MIN + 1);
+        let extended = Int256::from_int64(cs, &value);
+        assert_eq!(extended.witness_hook(&*cs)().unwrap(), signed(i64::MIN + 1));
+
+        // (value, shift, SAR)
+        let cases = [
+            (signed(-16), 2u32, signed(-4)),
+            (signed(-1), 255, signed(-1)),
+            (signed(-17), 1000, signed(-1)),
+            (signed(17), 256, U256::zero()),
+            (min, 255, signed(-1)),
+            (U256::MAX >> 1, 254, signed(1)),
+        ];
+
+        for (value, shift, expected) in cases {
+            let a = Int256::allocate(cs, value);
+            let shift_var = UInt32::allocated_constant(cs, shift);
+            let shifted = a.arithmetic_shr_variable(cs, &shift_var);
+            assert_eq!(shifted.witness_hook(&*cs)().unwrap(), expected);
+            if shift < 256 {
+                let shifted = a.arithmetic_shr(cs, shift);
+                assert_eq!(shifted.witness_hook(&*cs)().unwrap(), expected);
+            }
+        }
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
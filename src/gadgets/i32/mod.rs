@@ -0,0 +1,429 @@
+use super::*;
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::cs::traits::cs::DstBuffer;
+use crate::cs::Variable;
+use crate::field::SmallField;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::impls::limbs_arithmetic::*;
+use crate::gadgets::traits::allocatable::CSAllocatable;
+use crate::gadgets::traits::allocatable::CSAllocatableExt;
+use crate::gadgets::traits::castable::Convertor;
+use crate::gadgets::traits::castable::WitnessCastable;
+use crate::gadgets::traits::witnessable::CSWitnessable;
+use crate::gadgets::traits::witnessable::WitnessHookable;
+use crate::gadgets::u32::UInt32;
+
+// Signed 32-bit integer in two's complement representation
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug, Hash)]
+pub struct Int32<F: SmallField> {
+    pub inner: UInt32<F>,
+}
+
+impl<F: SmallField> CSAllocatable<F> for Int32<F> {
+    type Witness = i32;
+    fn placeholder_witness() -> Self::Witness {
+        0i32
+    }
+
+    #[inline(always)]
+    fn allocate_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        let inner = UInt32::allocate_without_value(cs);
+
+        Self { inner }
+    }
+
+    #[inline(always)]
+    fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
+        let inner = UInt32::allocate_checked(cs, witness as u32);
+
+        Self { inner }
+    }
+
+    #[inline(always)]
+    fn allocate_constant<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
+        Self::allocated_constant(cs, witness)
+    }
+}
+
+impl<F: SmallField> CSAllocatableExt<F> for Int32<F> {
+    const INTERNAL_STRUCT_LEN: usize = 1;
+
+    fn witness_from_set_of_values(values: [F; Self::INTERNAL_STRUCT_LEN]) -> Self::Witness {
+        <i32 as WitnessCastable<F, [F; 1]>>::cast_from_source(values)
+    }
+
+    // we should be able to allocate without knowing values yet
+    fn create_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        Self::allocate_without_value(cs)
+    }
+
+    fn flatten_as_variables(&self) -> [Variable; Self::INTERNAL_STRUCT_LEN]
+    where
+        [(); Self::INTERNAL_STRUCT_LEN]:,
+    {
+        [self.inner.get_variable()]
+    }
+
+    fn set_internal_variables_values(witness: Self::Witness, dst: &mut DstBuffer<'_, '_, F>) {
+        debug_assert!(F::CAPACITY_BITS >= 32);
+        dst.push(F::from_u64_unchecked((witness as u32) as u64));
+    }
+}
+
+impl<F: SmallField> CSWitnessable<F, 1> for Int32<F> {
+    type ConversionFunction = Convertor<F, [F; 1], i32>;
+
+    fn witness_from_set_of_values(values: [F; 1]) -> Self::Witness {
+        WitnessCastable::cast_from_source(values)
+    }
+
+    fn as_variables_set(&self) -> [Variable; 1] {
+        [self.inner.get_variable()]
+    }
+}
+
+impl<F: SmallField> WitnessHookable<F> for Int32<F> {
+    fn witness_hook<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &CS,
+    ) -> Box<dyn FnOnce() -> Option<Self::Witness>> {
+        let raw_witness = self.get_witness(cs);
+        Box::new(move || raw_witness.wait())
+    }
+}
+
+use crate::gadgets::traits::selectable::Selectable;
+
+impl<F: SmallField> Selectable<F> for Int32<F> {
+    fn conditionally_select<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        flag: Boolean<F>,
+        a: &Self,
+        b: &Self,
+    ) -> Self {
+        let inner = UInt32::conditionally_select(cs, flag, &a.inner, &b.inner);
+
+        Self { inner }
+    }
+}
+
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+
+impl<F: SmallField> CircuitEq<F> for Int32<F> {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        UInt32::equals(cs, &a.inner, &b.inner)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        <UInt32<F> as CircuitEq<F>>::enforce_equal(cs, &a.inner, &b.inner)
+    }
+}
+
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitOrd<F> for Int32<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        signed_less_than_limbs(cs, &[a.inner], &[b.inner])
+    }
+}
+
+impl<F: SmallField> Int32<F> {
+    pub fn allocated_constant<CS: ConstraintSystem<F>>(cs: &mut CS, constant: i32) -> Self {
+        let inner = UInt32::allocated_constant(cs, constant as u32);
+
+        Self { inner }
+    }
+
+    pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        Self::allocated_constant(cs, 0)
+    }
+
+    // Reinterprets the bits of unsigned integer, same as `value as i32`
+    pub const fn from_unsigned(value: UInt32<F>) -> Self {
+        Self { inner: value }
+    }
+
+    // Reinterprets the bits as unsigned integer, same as `self as u32`
+    pub const fn as_unsigned(&self) -> UInt32<F> {
+        self.inner
+    }
+
+    pub fn is_negative<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Boolean<F> {
+        is_negative_limbs(cs, &[self.inner])
+    }
+
+    pub fn is_zero<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Boolean<F> {
+        self.inner.is_zero(cs)
+    }
+
+    // Two's complement negation, `MIN` stays unchanged
+    pub fn wrapping_neg<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Self {
+        let [inner] = negate_limbs(cs, &[self.inner]);
+
+        Self { inner }
+    }
+
+    // Absolute value as unsigned integer, so it's defined for `MIN` too
+    pub fn unsigned_abs<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> UInt32<F> {
+        let is_negative = self.is_negative(cs);
+        let [abs] = conditionally_negate_limbs(cs, is_negative, &[self.inner]);
+
+        abs
+    }
+
+    // Returns wrapped sum and a flag if it overflows the signed range
+    pub fn overflowing_add<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        other: &Self,
+    ) -> (Self, Boolean<F>) {
+        let ([inner], overflow) = signed_overflowing_add_limbs(cs, &[self.inner], &[other.inner]);
+
+        (Self { inner }, overflow)
+    }
+
+    // Returns wrapped difference and a flag if it overflows the signed range
+    pub fn overflowing_sub<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        other: &Self,
+    ) -> (Self, Boolean<F>) {
+        let ([inner], overflow) = signed_overflowing_sub_limbs(cs, &[self.inner], &[other.inner]);
+
+        (Self { inner }, overflow)
+    }
+
+    // Returns quotient and remainder of division truncated towards zero.
+    // Follows the EVM semantics, so division by zero gives zero quotient and zero remainder,
+    // and `MIN / -1` wraps into `MIN`
+    pub fn div_rem<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> (Self, Self) {
+        let ([quotient], [remainder]) = signed_div_rem_limbs(cs, &[self.inner], &[other.inner]);
+
+        (Self { inner: quotient }, Self { inner: remainder })
+    }
+
+    // Returns `self >> amount` filling the highest bits with the sign, `amount` must be less than 32
+    pub fn arithmetic_shr<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        assert!(amount < 32);
+        if amount == 0 {
+            return *self;
+        }
+
+        let is_negative = self.is_negative(cs);
+        let shifted = self.inner.shr(cs, amount);
+        let fill = UInt32::allocated_constant(cs, !(u32::MAX >> amount));
+        let [inner] = fill_sign_bits_limbs(cs, &[shifted], &[fill], is_negative);
+
+        Self { inner }
+    }
+
+    // Returns `self >> shift` filling the highest bits with the sign,
+    // so any `shift >= 32` gives 0 or -1 depending on the sign
+    pub fn arithmetic_shr_variable<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> Self {
+        let is_negative = self.is_negative(cs);
+        let (shifted, _) = self.inner.overflowing_shr(cs, shift);
+        let all_ones = UInt32::allocated_constant(cs, u32::MAX);
+        let (fill, _) = all_ones.overflowing_shr(cs, shift);
+        let fill = fill.not(cs);
+        let [inner] = fill_sign_bits_limbs(cs, &[shifted], &[fill], is_negative);
+
+        Self { inner }
+    }
+}
+
+use crate::gadgets::traits::encodable::CircuitVarLengthEncodable;
+
+impl<F: SmallField> CircuitVarLengthEncodable<F> for Int32<F> {
+    #[inline(always)]
+    fn encoding_length(&self) -> usize {
+        1
+    }
+    fn encode_to_buffer<CS: ConstraintSystem<F>>(&self, cs: &mut CS, dst: &mut Vec<Variable>) {
+        CircuitVarLengthEncodable::<F>::encode_to_buffer(&self.inner, cs, dst);
+    }
+}
+
+use crate::gadgets::traits::allocatable::CSPlaceholder;
+
+impl<F: SmallField> CSPlaceholder<F> for Int32<F> {
+    fn placeholder<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        Self::zero(cs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, StaticToolboxHolder};
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::tables::{
+        and8::{create_and8_table, And8Table},
+        byte_split::{create_byte_split_table, ByteSplitTable},
+        xor8::{create_xor8_table, Xor8Table},
+    };
+    use crate::worker::Worker;
+
+    type F = GoldilocksField;
+
+    fn create_test_cs() -> CSReferenceImplementation<
+        F,
+        F,
+        DevCSConfig,
+        impl GateConfigurationHolder<F>,
+        impl StaticToolboxHolder,
+    > {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 20, 1 << 17);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 8,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<32>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = U8x4FMAGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ParallelSelectionGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+
+        let table = create_and8_table();
+        owned_cs.add_lookup_table::<And8Table, 3>(table);
+
+        let table = create_byte_split_table::<F, 1>();
+        owned_cs.add_lookup_table::<ByteSplitTable<1>, 3>(table);
+        let table = create_byte_split_table::<F, 2>();
+        owned_cs.add_lookup_table::<ByteSplitTable<2>, 3>(table);
+        let table = create_byte_split_table::<F, 3>();
+        owned_cs.add_lookup_table::<ByteSplitTable<3>, 3>(table);
+        let table = create_byte_split_table::<F, 4>();
+        owned_cs.add_lookup_table::<ByteSplitTable<4>, 3>(table);
+        let table = create_byte_split_table::<F, 5>();
+        owned_cs.add_lookup_table::<ByteSplitTable<5>, 3>(table);
+        let table = create_byte_split_table::<F, 6>();
+        owned_cs.add_lookup_table::<ByteSplitTable<6>, 3>(table);
+        let table = create_byte_split_table::<F, 7>();
+        owned_cs.add_lookup_table::<ByteSplitTable<7>, 3>(table);
+
+        owned_cs
+    }
+
+    #[test]
+    fn test_signed_arithmetic() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let values = [0i32, 1, -1, 7, -7, 1 << 20, i32::MAX, i32::MIN];
+        for a_value in values {
+            let a = Int32::allocate(cs, a_value);
+            let is_negative = a.is_negative(cs);
+            assert_eq!(is_negative.witness_hook(&*cs)().unwrap(), a_value < 0);
+            let abs = a.unsigned_abs(cs);
+            assert_eq!(abs.witness_hook(&*cs)().unwrap(), a_value.unsigned_abs());
+
+            for b_value in values {
+                let b = Int32::allocate(cs, b_value);
+
+                let is_less = Int32::less_than(cs, &a, &b);
+                assert_eq!(is_less.witness_hook(&*cs)().unwrap(), a_value < b_value);
+
+                let (sum, overflow) = a.overflowing_add(cs, &b);
+                let (expected_sum, expected_overflow) = a_value.overflowing_add(b_value);
+                assert_eq!(sum.witness_hook(&*cs)().unwrap(), expected_sum);
+                assert_eq!(overflow.witness_hook(&*cs)().unwrap(), expected_overflow);
+
+                let (difference, overflow) = a.overflowing_sub(cs, &b);
+                let (expected_difference, expected_overflow) = a_value.overflowing_sub(b_value);
+                assert_eq!(
+                    difference.witness_hook(&*cs)().unwrap(),
+                    expected_difference
+                );
+                assert_eq!(overflow.witness_hook(&*cs)().unwrap(), expected_overflow);
+
+                let (quotient, remainder) = a.div_rem(cs, &b);
+                let (expected_quotient, expected_remainder) = if b_value == 0 {
+                    (0, 0)
+                } else {
+                    (a_value.wrapping_div(b_value), a_value.wrapping_rem(b_value))
+                };
+                assert_eq!(quotient.witness_hook(&*cs)().unwrap(), expected_quotient);
+                assert_eq!(remainder.witness_hook(&*cs)().unwrap(), expected_remainder);
+            }
+
+            for amount in [0u32, 1, 8, 13, 31] {
+                let shifted = a.arithmetic_shr(cs, amount);
+                assert_eq!(shifted.witness_hook(&*cs)().unwrap(), a_value >> amount);
+            }
+
+            for amount in [0u32, 5, 31, 32, 100] {
+                let shift = UInt32::allocated_constant(cs, amount);
+                let shifted = a.arithmetic_shr_variable(cs, &shift);
+                let expected = a_value >> amount.min(31);
+                assert_eq!(shifted.witness_hook(&*cs)().unwrap(), expected);
+            }
+        }
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
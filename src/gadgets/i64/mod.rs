@@ -0,0 +1,296 @@
+use super::*;
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::cs::traits::cs::DstBuffer;
+use crate::cs::Variable;
+use crate::field::SmallField;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::i32::Int32;
+use crate::gadgets::impls::limbs_arithmetic::*;
+use crate::gadgets::traits::allocatable::CSAllocatable;
+use crate::gadgets::traits::allocatable::CSAllocatableExt;
+use crate::gadgets::traits::castable::Convertor;
+use crate::gadgets::traits::castable::WitnessCastable;
+use crate::gadgets::traits::witnessable::CSWitnessable;
+use crate::gadgets::traits::witnessable::WitnessHookable;
+use crate::gadgets::u32::UInt32;
+use crate::gadgets::u8::bitwise;
+use crate::gadgets::u8::UInt8;
+
+// Signed 64-bit integer in two's complement representation as two 32-bit limbs, little endian
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug, Hash)]
+pub struct Int64<F: SmallField> {
+    pub inner: [UInt32<F>; 2],
+}
+
+impl<F: SmallField> CSAllocatable<F> for Int64<F> {
+    type Witness = i64;
+    fn placeholder_witness() -> Self::Witness {
+        0i64
+    }
+
+    #[inline(always)]
+    fn allocate_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        let vars = cs.alloc_multiple_variables_without_values::<2>();
+
+        let inner = vars.map(|el| UInt32::from_variable_checked(cs, el));
+
+        Self { inner }
+    }
+
+    #[inline(always)]
+    fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
+        let inner = [witness as u32, ((witness as u64) >> 32) as u32]
+            .map(|el| UInt32::allocate_checked(cs, el));
+
+        Self { inner }
+    }
+
+    #[inline(always)]
+    fn allocate_constant<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
+        Self::allocated_constant(cs, witness)
+    }
+}
+
+impl<F: SmallField> CSAllocatableExt<F> for Int64<F> {
+    const INTERNAL_STRUCT_LEN: usize = 2;
+
+    fn witness_from_set_of_values(values: [F; Self::INTERNAL_STRUCT_LEN]) -> Self::Witness {
+        <i64 as WitnessCastable<F, [F; 2]>>::cast_from_source(values)
+    }
+
+    // we should be able to allocate without knowing values yet
+    fn create_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        Self::allocate_without_value(cs)
+    }
+
+    fn flatten_as_variables(&self) -> [Variable; Self::INTERNAL_STRUCT_LEN]
+    where
+        [(); Self::INTERNAL_STRUCT_LEN]:,
+    {
+        self.inner.map(|el| el.get_variable())
+    }
+
+    fn set_internal_variables_values(witness: Self::Witness, dst: &mut DstBuffer<'_, '_, F>) {
+        debug_assert!(F::CAPACITY_BITS >= 32);
+        dst.extend(<i64 as WitnessCastable<F, [F; 2]>>::cast_into_source(
+            witness,
+        ));
+    }
+}
+
+impl<F: SmallField> CSWitnessable<F, 2> for Int64<F> {
+    type ConversionFunction = Convertor<F, [F; 2], i64>;
+
+    fn witness_from_set_of_values(values: [F; 2]) -> Self::Witness {
+        WitnessCastable::cast_from_source(values)
+    }
+
+    fn as_variables_set(&self) -> [Variable; 2] {
+        self.inner.map(|el| el.get_variable())
+    }
+}
+
+impl<F: SmallField> WitnessHookable<F> for Int64<F> {
+    fn witness_hook<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &CS,
+    ) -> Box<dyn FnOnce() -> Option<Self::Witness>> {
+        let raw_witness = self.get_witness(cs);
+        Box::new(move || raw_witness.wait())
+    }
+}
+
+use crate::gadgets::traits::selectable::Selectable;
+
+impl<F: SmallField> Selectable<F> for Int64<F> {
+    fn conditionally_select<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        flag: Boolean<F>,
+        a: &Self,
+        b: &Self,
+    ) -> Self {
+        let inner = Selectable::conditionally_select(cs, flag, &a.inner, &b.inner);
+
+        Self { inner }
+    }
+}
+
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+
+impl<F: SmallField> CircuitEq<F> for Int64<F> {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        <[UInt32<F>; 2] as CircuitEq<F>>::equals(cs, &a.inner, &b.inner)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        <[UInt32<F>; 2] as CircuitEq<F>>::enforce_equal(cs, &a.inner, &b.inner)
+    }
+}
+
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitOrd<F> for Int64<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        signed_less_than_limbs(cs, &a.inner, &b.inner)
+    }
+}
+
+impl<F: SmallField> Int64<F> {
+    pub fn allocated_constant<CS: ConstraintSystem<F>>(cs: &mut CS, constant: i64) -> Self {
+        let inner = [constant as u32, ((constant as u64) >> 32) as u32]
+            .map(|el| UInt32::allocated_constant(cs, el));
+
+        Self { inner }
+    }
+
+    pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        Self::allocated_constant(cs, 0)
+    }
+
+    // Reinterprets the bits of unsigned 32-bit limbs, little endian
+    pub const fn from_unsigned_limbs(limbs: [UInt32<F>; 2]) -> Self {
+        Self { inner: limbs }
+    }
+
+    // Reinterprets the bits as unsigned 32-bit limbs, little endian
+    pub const fn as_unsigned_limbs(&self) -> [UInt32<F>; 2] {
+        self.inner
+    }
+
+    // Sign extension, same as `value as i64`
+    pub fn from_int32<CS: ConstraintSystem<F>>(cs: &mut CS, value: &Int32<F>) -> Self {
+        let is_negative = value.is_negative(cs);
+        let high = sign_extension_limb(cs, is_negative);
+
+        Self {
+            inner: [value.inner, high],
+        }
+    }
+
+    pub fn to_le_bytes<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> [UInt8<F>; 8] {
+        let [low, high] = self.inner.map(|el| el.decompose_into_bytes(cs));
+
+        [
+            low[0], low[1], low[2], low[3], high[0], high[1], high[2], high[3],
+        ]
+    }
+
+    pub fn from_le_bytes<CS: ConstraintSystem<F>>(cs: &mut CS, bytes: [UInt8<F>; 8]) -> Self {
+        let low = UInt32::from_le_bytes(cs, [bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let high = UInt32::from_le_bytes(cs, [bytes[4], bytes[5], bytes[6], bytes[7]]);
+
+        Self { inner: [low, high] }
+    }
+
+    pub fn is_negative<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Boolean<F> {
+        is_negative_limbs(cs, &self.inner)
+    }
+
+    pub fn is_zero<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Boolean<F> {
+        is_zero_limbs(cs, &self.inner)
+    }
+
+    // Two's complement negation, `MIN` stays unchanged
+    pub fn wrapping_neg<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Self {
+        let inner = negate_limbs(cs, &self.inner);
+
+        Self { inner }
+    }
+
+    // Absolute value as unsigned limbs, so it's defined for `MIN` too
+    pub fn unsigned_abs<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> [UInt32<F>; 2] {
+        let is_negative = self.is_negative(cs);
+
+        conditionally_negate_limbs(cs, is_negative, &self.inner)
+    }
+
+    // Returns wrapped sum and a flag if it overflows the signed range
+    pub fn overflowing_add<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        other: &Self,
+    ) -> (Self, Boolean<F>) {
+        let (inner, overflow) = signed_overflowing_add_limbs(cs, &self.inner, &other.inner);
+
+        (Self { inner }, overflow)
+    }
+
+    // Returns wrapped difference and a flag if it overflows the signed range
+    pub fn overflowing_sub<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        other: &Self,
+    ) -> (Self, Boolean<F>) {
+        let (inner, overflow) = signed_overflowing_sub_limbs(cs, &self.inner, &other.inner);
+
+        (Self { inner }, overflow)
+    }
+
+    // Returns quotient and remainder of division truncated towards zero.
+    // Follows the EVM semantics, so division by zero gives zero quotient and zero remainder,
+    // and `MIN / -1` wraps into `MIN`
+    pub fn div_rem<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> (Self, Self) {
+        let (quotient, remainder) = signed_div_rem_limbs(cs, &self.inner, &other.inner);
+
+        (Self { inner: quotient }, Self { inner: remainder })
+    }
+
+    // Returns `self >> amount` filling the highest bits with the sign, `amount` must be less than 64
+    pub fn arithmetic_shr<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        assert!(amount < 64);
+        if amount == 0 {
+            return *self;
+        }
+
+        let is_negative = self.is_negative(cs);
+        let bytes = self.to_le_bytes(cs);
+        let bytes = bitwise::shr_bytes(cs, &bytes, amount as usize, false);
+        let shifted = Self::from_le_bytes(cs, bytes);
+        let fill = Self::allocated_constant(cs, !(u64::MAX >> amount) as i64);
+        let inner = fill_sign_bits_limbs(cs, &shifted.inner, &fill.inner, is_negative);
+
+        Self { inner }
+    }
+
+    // Returns `self >> shift` filling the highest bits with the sign,
+    // so any `shift >= 64` gives 0 or -1 depending on the sign
+    pub fn arithmetic_shr_variable<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> Self {
+        let is_negative = self.is_negative(cs);
+        let bytes = self.to_le_bytes(cs);
+        let (bytes, _) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, false, false);
+        let shifted = Self::from_le_bytes(cs, bytes);
+
+        let all_ones = [UInt8::allocated_constant(cs, u8::MAX); 8];
+        let (fill, _) = bitwise::shift_bytes_by_variable(cs, &all_ones, shift, false, false);
+        let fill = bitwise::not_bytes(cs, &fill);
+        let fill = Self::from_le_bytes(cs, fill);
+        let inner = fill_sign_bits_limbs(cs, &shifted.inner, &fill.inner, is_negative);
+
+        Self { inner }
+    }
+}
+
+use crate::gadgets::traits::encodable::CircuitVarLengthEncodable;
+
+impl<F: SmallField> CircuitVarLengthEncodable<F> for Int64<F> {
+    #[inline(always)]
+    fn encoding_length(&self) -> usize {
+        2
+    }
+    fn encode_to_buffer<CS: ConstraintSystem<F>>(&self, cs: &mut CS, dst: &mut Vec<Variable>) {
+        CircuitVarLengthEncodable::<F>::encode_to_buffer(&self.inner, cs, dst);
+    }
+}
+
+use crate::gadgets::traits::allocatable::CSPlaceholder;
+
+impl<F: SmallField> CSPlaceholder<F> for Int64<F> {
+    fn placeholder<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        Self::zero(cs)
+    }
+}
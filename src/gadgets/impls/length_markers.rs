@@ -0,0 +1,80 @@
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::cs::Variable;
+use crate::field::SmallField;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::num::Num;
+
+// Markers of a runtime length over a buffer of `max_length` elements.
+// For every index in `0..=max_length` we have a flag that the index is less than the length,
+// and a flag that the index is equal to it, so exactly one of the latter is set
+pub(crate) struct LengthMarkers<F: SmallField> {
+    pub(crate) is_inside: Vec<Boolean<F>>,
+    pub(crate) is_end: Vec<Boolean<F>>,
+}
+
+impl<F: SmallField> LengthMarkers<F> {
+    // Also enforces that `length <= max_length`
+    pub(crate) fn new<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        length: &Num<F>,
+        max_length: usize,
+    ) -> Self {
+        assert!(max_length < u32::MAX as usize);
+
+        let mut is_inside = Vec::with_capacity(max_length + 1);
+        let mut is_end = Vec::with_capacity(max_length + 1);
+
+        // index is less than the length until we meet the length itself
+        let mut inside = Boolean::allocated_constant(cs, true);
+        for idx in 0..=max_length {
+            let index = Num::allocated_constant(cs, F::from_u64_unchecked(idx as u64));
+            let is_equal = Num::equals(cs, length, &index);
+            let is_not_equal = is_equal.negated(cs);
+            inside = inside.and(cs, is_not_equal);
+
+            is_inside.push(inside);
+            is_end.push(is_equal);
+        }
+
+        // if we have never met the length, then it's out of range
+        let boolean_false = Boolean::allocated_constant(cs, false);
+        Boolean::enforce_equal(cs, &inside, &boolean_false);
+
+        Self { is_inside, is_end }
+    }
+
+    // Flag that the length is within `range`, cheap because end markers are one-hot
+    pub(crate) fn is_end_within<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        range: std::ops::Range<usize>,
+    ) -> Boolean<F> {
+        let range = range.start..std::cmp::min(range.end, self.is_end.len());
+        if range.is_empty() {
+            return Boolean::allocated_constant(cs, false);
+        }
+        if range.len() == 1 {
+            return self.is_end[range.start];
+        }
+
+        let terms: Vec<(Variable, F)> = self.is_end[range]
+            .iter()
+            .map(|el| (el.get_variable(), F::ONE))
+            .collect();
+        let sum = Num::linear_combination(cs, &terms);
+
+        unsafe { Boolean::from_variable_unchecked(sum.get_variable()) }
+    }
+
+    // Element of the buffer if it's index is less than the length, and zero otherwise
+    pub(crate) fn mask_element<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        element: Variable,
+        index: usize,
+    ) -> Variable {
+        Num::from_variable(element)
+            .mask(cs, self.is_inside[index])
+            .get_variable()
+    }
+}
@@ -0,0 +1,566 @@
+use crate::config::*;
+use crate::cs::traits::cs::{ConstraintSystem, DstBuffer};
+use crate::cs::Place;
+use crate::field::SmallField;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::num::Num;
+use crate::gadgets::traits::selectable::Selectable;
+use crate::gadgets::u32::UInt32;
+use ethereum_types::U512;
+
+// Arithmetic over little-endian arrays of 32-bit limbs, shared by the wide integer gadgets.
+// Signed routines use two's complement representation
+
+pub(crate) fn is_zero_limbs<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    a: &[UInt32<F>; N],
+) -> Boolean<F> {
+    let limbs_are_zero = a.map(|el| el.is_zero(cs));
+
+    Boolean::multi_and(cs, &limbs_are_zero)
+}
+
+pub(crate) fn overflowing_add_limbs<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    a: &[UInt32<F>; N],
+    b: &[UInt32<F>; N],
+) -> ([UInt32<F>; N], Boolean<F>) {
+    let mut carry_out = Boolean::allocated_constant(cs, false);
+    let mut result = *a;
+    for ((a, b), dst) in a.iter().zip(b.iter()).zip(result.iter_mut()) {
+        let (c, carry) = (*a).overflowing_add_with_carry_in(cs, *b, carry_out);
+        *dst = c;
+        carry_out = carry;
+    }
+
+    (result, carry_out)
+}
+
+pub(crate) fn overflowing_sub_limbs<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    a: &[UInt32<F>; N],
+    b: &[UInt32<F>; N],
+) -> ([UInt32<F>; N], Boolean<F>) {
+    let mut borrow_out = Boolean::allocated_constant(cs, false);
+    let mut result = *a;
+    for ((a, b), dst) in a.iter().zip(b.iter()).zip(result.iter_mut()) {
+        let (c, borrow) = (*a).overflowing_sub_with_borrow_in(cs, *b, borrow_out);
+        *dst = c;
+        borrow_out = borrow;
+    }
+
+    (result, borrow_out)
+}
+
+// Allocates quotient and remainder of division of `dividend` by `divisor`
+// and enforces `quotient * divisor + remainder == dividend` and `remainder < divisor`.
+// Division by zero gives zero quotient and zero remainder
+pub(crate) fn div_rem_limbs<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    const N: usize,
+    const M: usize,
+>(
+    cs: &mut CS,
+    dividend: [UInt32<F>; N],
+    divisor: [UInt32<F>; M],
+) -> ([UInt32<F>; N], [UInt32<F>; M]) {
+    let (quotient, remainder) = allocate_div_rem_limbs_witness(cs, &dividend, &divisor);
+    enforce_div_rem_limbs(cs, &dividend, &divisor, &quotient, &remainder);
+
+    (quotient, remainder)
+}
+
+pub(crate) fn allocate_div_rem_limbs_witness<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    const N: usize,
+    const M: usize,
+>(
+    cs: &mut CS,
+    dividend: &[UInt32<F>; N],
+    divisor: &[UInt32<F>; M],
+) -> ([UInt32<F>; N], [UInt32<F>; M]) {
+    debug_assert!(N <= 16);
+    debug_assert!(M <= 16);
+
+    let quotient_vars = cs.alloc_multiple_variables_without_values::<N>();
+    let remainder_vars = cs.alloc_multiple_variables_without_values::<M>();
+
+    if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
+        let value_fn = move |inputs: &[F], output_buffer: &mut DstBuffer<'_, '_, F>| {
+            let mut dividend = U512::zero();
+            for (idx, el) in inputs[..N].iter().enumerate() {
+                dividend = dividend | (U512::from(el.as_u64_reduced()) << (32 * idx));
+            }
+            let mut divisor = U512::zero();
+            for (idx, el) in inputs[N..].iter().enumerate() {
+                divisor = divisor | (U512::from(el.as_u64_reduced()) << (32 * idx));
+            }
+
+            let (quotient, remainder) = if divisor.is_zero() {
+                (U512::zero(), U512::zero())
+            } else {
+                (dividend / divisor, dividend % divisor)
+            };
+
+            for idx in 0..N {
+                let limb = (quotient >> (32 * idx)).low_u64() as u32;
+                output_buffer.push(F::from_u64_unchecked(limb as u64));
+            }
+            for idx in 0..M {
+                let limb = (remainder >> (32 * idx)).low_u64() as u32;
+                output_buffer.push(F::from_u64_unchecked(limb as u64));
+            }
+        };
+
+        let mut dependencies = Vec::with_capacity(N + M);
+        dependencies.extend(Place::from_variables(dividend.map(|el| el.get_variable())));
+        dependencies.extend(Place::from_variables(divisor.map(|el| el.get_variable())));
+
+        let mut outputs = Vec::with_capacity(N + M);
+        outputs.extend(Place::from_variables(quotient_vars));
+        outputs.extend(Place::from_variables(remainder_vars));
+
+        cs.set_values_with_dependencies_vararg(&dependencies, &outputs, value_fn);
+    }
+
+    let quotient = quotient_vars.map(|el| UInt32::from_variable_checked(cs, el));
+    let remainder = remainder_vars.map(|el| UInt32::from_variable_checked(cs, el));
+
+    (quotient, remainder)
+}
+
+// Constraints of `div_rem_limbs` over already range checked quotient and remainder
+pub(crate) fn enforce_div_rem_limbs<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    const N: usize,
+    const M: usize,
+>(
+    cs: &mut CS,
+    dividend: &[UInt32<F>; N],
+    divisor: &[UInt32<F>; M],
+    quotient: &[UInt32<F>; N],
+    remainder: &[UInt32<F>; M],
+) {
+    let divisor_is_zero = is_zero_limbs(cs, divisor);
+    // for zero divisor both quotient and remainder must be zero,
+    // so we compare with zero instead of the dividend. Quotient is multiplied
+    // by zero in this case, so we also have to pin it explicitly
+    let dividend = dividend.map(|el| el.mask_negated(cs, divisor_is_zero));
+    let zero = UInt32::zero(cs);
+    for limb in quotient.iter() {
+        Num::conditionally_enforce_equal(cs, divisor_is_zero, &limb.into_num(), &zero.into_num());
+    }
+
+    // schoolbook quotient * divisor + remainder, it can not overflow N + M limbs
+    let mut accumulator = vec![zero; N + M];
+    accumulator[..M].copy_from_slice(remainder);
+    for i in 0..N {
+        let mut carry = zero;
+        for j in 0..M {
+            let res =
+                UInt32::fma_with_carry(cs, quotient[i], divisor[j], accumulator[i + j], carry);
+            (accumulator[i + j], carry) = (res[0].0, res[1].0);
+        }
+        accumulator[i + M] = carry;
+    }
+
+    for (idx, limb) in accumulator.iter().enumerate() {
+        let expected = if idx < N { dividend[idx] } else { zero };
+        Num::enforce_equal(cs, &limb.into_num(), &expected.into_num());
+    }
+
+    // remainder < divisor, unless divisor is zero
+    let (_, borrow) = overflowing_sub_limbs(cs, remainder, divisor);
+    let divisor_is_not_zero = divisor_is_zero.negated(cs);
+    Boolean::enforce_equal(cs, &borrow, &divisor_is_not_zero);
+}
+
+pub(crate) fn is_negative_limbs<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    a: &[UInt32<F>; N],
+) -> Boolean<F> {
+    // the highest bit is set if and only if the top limb is not less than 2^31
+    let boundary = UInt32::allocated_constant(cs, 1u32 << 31);
+    let (_, borrow) = a[N - 1].overflowing_sub(cs, boundary);
+
+    borrow.negated(cs)
+}
+
+pub(crate) fn negate_limbs<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    a: &[UInt32<F>; N],
+) -> [UInt32<F>; N] {
+    let zero = [UInt32::zero(cs); N];
+    let (result, _) = overflowing_sub_limbs(cs, &zero, a);
+
+    result
+}
+
+pub(crate) fn conditionally_negate_limbs<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    flag: Boolean<F>,
+    a: &[UInt32<F>; N],
+) -> [UInt32<F>; N] {
+    let negated = negate_limbs(cs, a);
+
+    UInt32::parallel_select(cs, flag, &negated, a)
+}
+
+pub(crate) fn signed_less_than_limbs<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    a: &[UInt32<F>; N],
+    b: &[UInt32<F>; N],
+) -> Boolean<F> {
+    // values of the same sign are ordered as unsigned ones, otherwise the negative one is less
+    let a_is_negative = is_negative_limbs(cs, a);
+    let b_is_negative = is_negative_limbs(cs, b);
+    let (_, unsigned_less_than) = overflowing_sub_limbs(cs, a, b);
+    let signs_differ = a_is_negative.xor(cs, b_is_negative);
+
+    Boolean::conditionally_select(cs, signs_differ, &a_is_negative, &unsigned_less_than)
+}
+
+// Signed addition with a flag of overflow of the signed range
+pub(crate) fn signed_overflowing_add_limbs<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    const N: usize,
+>(
+    cs: &mut CS,
+    a: &[UInt32<F>; N],
+    b: &[UInt32<F>; N],
+) -> ([UInt32<F>; N], Boolean<F>) {
+    let (result, _) = overflowing_add_limbs(cs, a, b);
+
+    // overflow happens if both terms have the same sign, but the result has another one
+    let a_is_negative = is_negative_limbs(cs, a);
+    let b_is_negative = is_negative_limbs(cs, b);
+    let result_is_negative = is_negative_limbs(cs, &result);
+    let signs_differ = a_is_negative.xor(cs, b_is_negative);
+    let same_signs = signs_differ.negated(cs);
+    let sign_changed = a_is_negative.xor(cs, result_is_negative);
+    let overflow = same_signs.and(cs, sign_changed);
+
+    (result, overflow)
+}
+
+// Signed subtraction with a flag of overflow of the signed range
+pub(crate) fn signed_overflowing_sub_limbs<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    const N: usize,
+>(
+    cs: &mut CS,
+    a: &[UInt32<F>; N],
+    b: &[UInt32<F>; N],
+) -> ([UInt32<F>; N], Boolean<F>) {
+    let (result, _) = overflowing_sub_limbs(cs, a, b);
+
+    // overflow happens if terms have different signs, and the result has the sign of subtrahend
+    let a_is_negative = is_negative_limbs(cs, a);
+    let b_is_negative = is_negative_limbs(cs, b);
+    let result_is_negative = is_negative_limbs(cs, &result);
+    let signs_differ = a_is_negative.xor(cs, b_is_negative);
+    let sign_changed = a_is_negative.xor(cs, result_is_negative);
+    let overflow = signs_differ.and(cs, sign_changed);
+
+    (result, overflow)
+}
+
+// Signed division that truncates towards zero, so the remainder has the sign of the dividend.
+// Follows the EVM `SDIV`/`SMOD` semantics: division by zero gives zero quotient and zero
+// remainder, and the only overflowing case of `MIN / -1` wraps into `MIN`
+pub(crate) fn signed_div_rem_limbs<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    a: &[UInt32<F>; N],
+    b: &[UInt32<F>; N],
+) -> ([UInt32<F>; N], [UInt32<F>; N]) {
+    signed_div_rem_limbs_with_witness(cs, a, b, |cs, a_abs, b_abs| {
+        allocate_div_rem_limbs_witness(cs, a_abs, b_abs)
+    })
+}
+
+// Same as above, but unsigned quotient and remainder of absolute values come from
+// the given allocator, so tests can substitute a malicious witness
+pub(crate) fn signed_div_rem_limbs_with_witness<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    const N: usize,
+>(
+    cs: &mut CS,
+    a: &[UInt32<F>; N],
+    b: &[UInt32<F>; N],
+    allocate_witness: impl FnOnce(
+        &mut CS,
+        &[UInt32<F>; N],
+        &[UInt32<F>; N],
+    ) -> ([UInt32<F>; N], [UInt32<F>; N]),
+) -> ([UInt32<F>; N], [UInt32<F>; N]) {
+    let a_is_negative = is_negative_limbs(cs, a);
+    let b_is_negative = is_negative_limbs(cs, b);
+    // absolute value of `MIN` is representable as unsigned one
+    let a_abs = conditionally_negate_limbs(cs, a_is_negative, a);
+    let b_abs = conditionally_negate_limbs(cs, b_is_negative, b);
+
+    let (quotient, remainder) = allocate_witness(cs, &a_abs, &b_abs);
+    enforce_div_rem_limbs(cs, &a_abs, &b_abs, &quotient, &remainder);
+
+    let quotient_is_negative = a_is_negative.xor(cs, b_is_negative);
+    let quotient = conditionally_negate_limbs(cs, quotient_is_negative, &quotient);
+    let remainder = conditionally_negate_limbs(cs, a_is_negative, &remainder);
+
+    (quotient, remainder)
+}
+
+// Sets bits of `fill` in `a` if `is_negative` is true, assuming that `a` and `fill` have
+// no common bits. Used to sign-extend the result of the logical shift right
+pub(crate) fn fill_sign_bits_limbs<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    a: &[UInt32<F>; N],
+    fill: &[UInt32<F>; N],
+    is_negative: Boolean<F>,
+) -> [UInt32<F>; N] {
+    let mut result = *a;
+    for ((a, fill), dst) in a.iter().zip(fill.iter()).zip(result.iter_mut()) {
+        let fill = fill.mask(cs, is_negative);
+        let sum = Num::linear_combination(
+            cs,
+            &[(a.get_variable(), F::ONE), (fill.get_variable(), F::ONE)],
+        );
+        // bits are disjoint, so the sum is a valid 32 bit integer
+        *dst = unsafe { UInt32::from_variable_unchecked(sum.get_variable()) };
+    }
+
+    result
+}
+
+// Limb of all ones for negative values and zero otherwise, used for sign extension
+pub(crate) fn sign_extension_limb<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    is_negative: Boolean<F>,
+) -> UInt32<F> {
+    let all_ones = UInt32::allocated_constant(cs, u32::MAX);
+
+    all_ones.mask(cs, is_negative)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, StaticToolboxHolder};
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::tables::{
+        and8::{create_and8_table, And8Table},
+        xor8::{create_xor8_table, Xor8Table},
+    };
+    use crate::gadgets::traits::allocatable::CSAllocatable;
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+    use derivative::*;
+
+    type F = GoldilocksField;
+
+    // Same as `DevCSConfig`, but without runtime asserts, so a malicious witness
+    // gets to the satisfiability check instead of failing during synthesis
+    #[derive(Derivative)]
+    #[derivative(Clone, Copy, Debug)]
+    struct NoRuntimeAssertsCSConfig;
+
+    impl CSConfig for NoRuntimeAssertsCSConfig {
+        type WitnessConfig = DoEvaluateWitenss;
+        type DebugConfig = DontPerformRuntimeAsserts;
+        type SetupConfig = DoKeepSetup;
+        type ResolverConfig = Resolver<DontPerformRuntimeAsserts>;
+    }
+
+    fn create_test_cs() -> CSReferenceImplementation<
+        F,
+        F,
+        NoRuntimeAssertsCSConfig,
+        impl GateConfigurationHolder<F>,
+        impl StaticToolboxHolder,
+    > {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl = CsReferenceImplementationBuilder::<F, F, NoRuntimeAssertsCSConfig>::new(
+            geometry,
+            1 << 20,
+            1 << 18,
+        );
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 8,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<32>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = U8x4FMAGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+
+        let table = create_and8_table();
+        owned_cs.add_lookup_table::<And8Table, 3>(table);
+
+        owned_cs
+    }
+
+    fn allocate_limbs<CS: ConstraintSystem<F>, const N: usize>(
+        cs: &mut CS,
+        value: u64,
+    ) -> [UInt32<F>; N] {
+        std::array::from_fn(|idx| {
+            let limb = if idx < 2 {
+                (value >> (32 * idx)) as u32
+            } else {
+                0
+            };
+            UInt32::allocate(cs, limb)
+        })
+    }
+
+    fn check_unsigned_division_by_zero(quotient_value: u64) {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let dividend = allocate_limbs::<_, 8>(cs, 0x1234_5678_9abc_def0);
+        let divisor = allocate_limbs::<_, 8>(cs, 0);
+        // the only valid witness for division by zero is zero quotient and zero remainder,
+        // so we bypass the honest witness generation to check the constraints alone
+        let quotient = allocate_limbs::<_, 8>(cs, quotient_value);
+        let remainder = allocate_limbs::<_, 8>(cs, 0);
+        enforce_div_rem_limbs(cs, &dividend, &divisor, &quotient, &remainder);
+
+        let worker = Worker::new_with_num_threads(8);
+
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    fn test_division_by_zero_with_zero_quotient() {
+        check_unsigned_division_by_zero(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unsatisfied")]
+    fn test_division_by_zero_with_malicious_quotient() {
+        check_unsigned_division_by_zero(5);
+    }
+
+    // SDIV and SMOD by zero for the widths of `Int32`, `Int64` and `Int256`
+    fn check_signed_division_by_zero<const N: usize>(
+        dividend_value: i64,
+        quotient_value: u64,
+        remainder_value: u64,
+    ) {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let mut dividend = allocate_limbs::<_, N>(cs, dividend_value as u64);
+        if dividend_value < 0 {
+            for limb in dividend[2.min(N)..].iter_mut() {
+                *limb = UInt32::allocate(cs, u32::MAX);
+            }
+        }
+        let divisor = allocate_limbs::<_, N>(cs, 0);
+        let (quotient, remainder) =
+            signed_div_rem_limbs_with_witness(cs, &dividend, &divisor, |cs, _, _| {
+                (
+                    allocate_limbs::<_, N>(cs, quotient_value),
+                    allocate_limbs::<_, N>(cs, remainder_value),
+                )
+            });
+        if quotient_value == 0 && remainder_value == 0 {
+            for limb in quotient.iter().chain(remainder.iter()) {
+                assert_eq!(limb.witness_hook(&*cs)().unwrap(), 0);
+            }
+        }
+
+        let worker = Worker::new_with_num_threads(8);
+
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    fn test_signed_division_by_zero_with_zero_quotient() {
+        check_signed_division_by_zero::<1>(7, 0, 0);
+        check_signed_division_by_zero::<2>(-7, 0, 0);
+        check_signed_division_by_zero::<8>(-7, 0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unsatisfied")]
+    fn test_sdiv_i32_by_zero_with_malicious_quotient() {
+        check_signed_division_by_zero::<1>(-7, 3, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unsatisfied")]
+    fn test_sdiv_i64_by_zero_with_malicious_quotient() {
+        check_signed_division_by_zero::<2>(7, 3, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unsatisfied")]
+    fn test_sdiv_i256_by_zero_with_malicious_quotient() {
+        check_signed_division_by_zero::<8>(-7, 3, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unsatisfied")]
+    fn test_smod_i256_by_zero_with_malicious_remainder() {
+        check_signed_division_by_zero::<8>(-7, 0, 7);
+    }
+}
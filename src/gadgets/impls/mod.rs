@@ -1,2 +1,4 @@
 pub mod lc;
+pub mod length_markers;
+pub mod limbs_arithmetic;
 pub mod limbs_decompose;
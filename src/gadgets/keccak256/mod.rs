@@ -2,7 +2,12 @@ use super::*;
 use crate::config::*;
 use crate::cs::traits::cs::ConstraintSystem;
 use crate::cs::Variable;
+use crate::gadgets::impls::length_markers::LengthMarkers;
+use crate::gadgets::num::Num;
+use crate::gadgets::traits::selectable::Selectable;
+use crate::gadgets::u32::UInt32;
 use crate::gadgets::u8::UInt8;
+use arrayvec::ArrayVec;
 use std::mem::MaybeUninit;
 
 pub mod round_function;
@@ -52,51 +57,171 @@ pub const ROUND_CONSTANTS: [u64; KECCAK256_NUM_ROUNDS] = [
 pub fn keccak256<F: SmallField, CS: ConstraintSystem<F>>(
     cs: &mut CS,
     input: &[UInt8<F>],
+) -> [UInt8<F>; KECCAK256_DIGEST_SIZE] {
+    let mut hasher = Keccak256Hasher::new(cs);
+    hasher.absorb(cs, input);
+
+    hasher.finalize(cs)
+}
+
+// Hashes first `length` bytes of the `input`, where `length` is only known at proving time
+// and must not exceed `input.len()`. Cost is the same as for hashing of the full `input`
+pub fn keccak256_variable_length<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    input: &[UInt8<F>],
+    length: &UInt32<F>,
 ) -> [UInt8<F>; KECCAK256_DIGEST_SIZE] {
     use crate::cs::gates::ConstantAllocatableCS;
 
-    assert!(input.len() <= u32::MAX as usize);
+    let markers = LengthMarkers::new(cs, &length.into_num(), input.len());
 
     let zero = cs.allocate_constant(F::ZERO);
     let mut state = [[[zero; BYTES_PER_WORD]; LANE_WIDTH]; LANE_WIDTH];
 
-    let mut padded_message = Vec::with_capacity(input.len() + KECCAK_RATE_BYTES);
-    padded_message.extend(input.iter().map(|el| el.variable));
-
-    let block_size = KECCAK_RATE_BYTES;
-    let last_block_size = input.len() % block_size;
-    let padlen = block_size - last_block_size;
-    if padlen == 1 {
-        padded_message.push(cs.allocate_constant(F::from_u64_unchecked(0x81 as u64)));
-    } else {
-        padded_message.push(cs.allocate_constant(F::from_u64_unchecked(0x01 as u64)));
-        padded_message.extend(std::iter::repeat(zero).take(padlen - 2));
-        padded_message.push(cs.allocate_constant(F::from_u64_unchecked(0x80 as u64)));
+    // padding always fits into the block that contains the end of the input
+    let num_blocks = input.len() / KECCAK_RATE_BYTES + 1;
+    let mut result = None;
+    for block_idx in 0..num_blocks {
+        let start = block_idx * KECCAK_RATE_BYTES;
+        let is_last_block = markers.is_end_within(cs, start..(start + KECCAK_RATE_BYTES));
+
+        // masked input, then 0x01 right after the end, and 0x80 at the end of the last block
+        let mut block = [zero; KECCAK_RATE_BYTES];
+        for (offset, dst) in block.iter_mut().enumerate() {
+            let idx = start + offset;
+            let mut terms = ArrayVec::<(Variable, F), 3>::new();
+            if idx < input.len() {
+                let byte = markers.mask_element(cs, input[idx].get_variable(), idx);
+                terms.push((byte, F::ONE));
+            }
+            if idx <= input.len() {
+                terms.push((markers.is_end[idx].get_variable(), F::ONE));
+            }
+            if offset == KECCAK_RATE_BYTES - 1 {
+                terms.push((
+                    is_last_block.get_variable(),
+                    F::from_u64_unchecked(0x80 as u64),
+                ));
+            }
+
+            match &terms[..] {
+                [] => {}
+                [(variable, coeff)] if *coeff == F::ONE => *dst = *variable,
+                _ => *dst = Num::linear_combination(cs, &terms).get_variable(),
+            }
+        }
+
+        absorb_block(cs, &mut state, &block);
+        let digest = squeeze_digest(&state);
+
+        result = match result {
+            None => Some(digest),
+            Some(previous) => Some(Selectable::conditionally_select(
+                cs,
+                is_last_block,
+                &digest,
+                &previous,
+            )),
+        };
+    }
+
+    result.expect("at least one block is always processed")
+}
+
+// Incremental version of `keccak256` for inputs of lengths known at synthesis time.
+// Full blocks are absorbed as soon as they are available, so the input may be collected
+// in different places of the circuit
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct Keccak256Hasher<F: SmallField> {
+    state: [[[Variable; BYTES_PER_WORD]; LANE_WIDTH]; LANE_WIDTH],
+    buffer: Vec<Variable>,
+    absorbed_length: usize,
+    _marker: std::marker::PhantomData<F>,
+}
+
+impl<F: SmallField> Keccak256Hasher<F> {
+    pub fn new<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        use crate::cs::gates::ConstantAllocatableCS;
+
+        let zero = cs.allocate_constant(F::ZERO);
+
+        Self {
+            state: [[[zero; BYTES_PER_WORD]; LANE_WIDTH]; LANE_WIDTH],
+            buffer: Vec::with_capacity(KECCAK_RATE_BYTES),
+            absorbed_length: 0,
+            _marker: std::marker::PhantomData,
+        }
     }
 
-    assert_eq!(padded_message.len() % block_size, 0);
+    pub fn absorb<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, input: &[UInt8<F>]) {
+        self.absorbed_length += input.len();
+        assert!(self.absorbed_length <= u32::MAX as usize);
 
-    use self::round_function::*;
+        self.buffer.extend(input.iter().map(|el| el.variable));
+        let num_full_bytes = self.buffer.len() - self.buffer.len() % KECCAK_RATE_BYTES;
+        for block in self.buffer[..num_full_bytes].array_chunks::<KECCAK_RATE_BYTES>() {
+            absorb_block(cs, &mut self.state, block);
+        }
+        self.buffer.drain(..num_full_bytes);
+    }
+
+    pub fn finalize<CS: ConstraintSystem<F>>(
+        mut self,
+        cs: &mut CS,
+    ) -> [UInt8<F>; KECCAK256_DIGEST_SIZE] {
+        use crate::cs::gates::ConstantAllocatableCS;
+
+        debug_assert!(self.buffer.len() < KECCAK_RATE_BYTES);
+
+        let zero = cs.allocate_constant(F::ZERO);
+        let padlen = KECCAK_RATE_BYTES - self.buffer.len();
+        if padlen == 1 {
+            self.buffer
+                .push(cs.allocate_constant(F::from_u64_unchecked(0x81 as u64)));
+        } else {
+            self.buffer
+                .push(cs.allocate_constant(F::from_u64_unchecked(0x01 as u64)));
+            self.buffer.extend(std::iter::repeat(zero).take(padlen - 2));
+            self.buffer
+                .push(cs.allocate_constant(F::from_u64_unchecked(0x80 as u64)));
+        }
 
-    for block in padded_message.array_chunks::<KECCAK_RATE_BYTES>() {
-        // absorb into state
-        for i in 0..LANE_WIDTH {
-            for j in 0..LANE_WIDTH {
-                if i + LANE_WIDTH * j < (KECCAK_RATE_BYTES / BYTES_PER_WORD) {
-                    let tmp = block
-                        .array_chunks::<BYTES_PER_WORD>()
-                        .skip(i + LANE_WIDTH * j)
-                        .next()
-                        .unwrap();
-                    use crate::gadgets::blake2s::mixing_function::xor_many;
-                    state[i][j] = xor_many(cs, &state[i][j], tmp);
-                }
+        let block: &[Variable; KECCAK_RATE_BYTES] = self.buffer[..]
+            .try_into()
+            .expect("padded buffer must be a single block");
+        absorb_block(cs, &mut self.state, block);
+
+        squeeze_digest(&self.state)
+    }
+}
+
+fn absorb_block<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    state: &mut [[[Variable; BYTES_PER_WORD]; LANE_WIDTH]; LANE_WIDTH],
+    block: &[Variable; KECCAK_RATE_BYTES],
+) {
+    use self::round_function::*;
+    use crate::gadgets::blake2s::mixing_function::xor_many;
+
+    for i in 0..LANE_WIDTH {
+        for j in 0..LANE_WIDTH {
+            if i + LANE_WIDTH * j < (KECCAK_RATE_BYTES / BYTES_PER_WORD) {
+                let tmp = block
+                    .array_chunks::<BYTES_PER_WORD>()
+                    .skip(i + LANE_WIDTH * j)
+                    .next()
+                    .unwrap();
+                state[i][j] = xor_many(cs, &state[i][j], tmp);
             }
         }
-        keccak_256_round_function(cs, &mut state);
     }
+    keccak_256_round_function(cs, state);
+}
 
-    // copy back
+fn squeeze_digest<F: SmallField>(
+    state: &[[[Variable; BYTES_PER_WORD]; LANE_WIDTH]; LANE_WIDTH],
+) -> [UInt8<F>; KECCAK256_DIGEST_SIZE] {
     let mut result = [MaybeUninit::<UInt8<F>>::uninit(); KECCAK256_DIGEST_SIZE];
     for (i, dst) in result.array_chunks_mut::<8>().enumerate() {
         for (dst, src) in dst.iter_mut().zip(state[i][0].iter()) {
@@ -243,4 +368,124 @@ mod test {
         let mut owned_cs = owned_cs.into_assembly();
         owned_cs.wait_for_witness();
     }
+
+    fn create_test_cs() -> crate::cs::implementations::reference_cs::CSReferenceImplementation<
+        F,
+        F,
+        DevCSConfig,
+        impl crate::cs::GateConfigurationHolder<F>,
+        impl crate::cs::StaticToolboxHolder,
+    > {
+        use crate::cs::gates::{NopGate, SelectionGate, ZeroCheckGate};
+
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 20,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        use crate::cs::cs_builder_reference::*;
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 20, 1 << 19);
+        use crate::cs::cs_builder::new_builder;
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 5,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        // add tables
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+
+        let table = create_and8_table();
+        owned_cs.add_lookup_table::<And8Table, 3>(table);
+
+        let table = create_byte_split_table::<F, 1>();
+        owned_cs.add_lookup_table::<ByteSplitTable<1>, 3>(table);
+        let table = create_byte_split_table::<F, 2>();
+        owned_cs.add_lookup_table::<ByteSplitTable<2>, 3>(table);
+        let table = create_byte_split_table::<F, 3>();
+        owned_cs.add_lookup_table::<ByteSplitTable<3>, 3>(table);
+        let table = create_byte_split_table::<F, 4>();
+        owned_cs.add_lookup_table::<ByteSplitTable<4>, 3>(table);
+
+        owned_cs
+    }
+
+    #[test]
+    fn test_variable_length_and_incremental() {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42 as u64);
+
+        let max_len = 2 * 136 + 42;
+        let input: Vec<u8> = (0..max_len).map(|_| rng.gen()).collect();
+
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let circuit_input: Vec<_> = input
+            .iter()
+            .map(|el| UInt8::allocate_checked(cs, *el))
+            .collect();
+
+        for len in [0, 1, 42, 135, 136, 137, 2 * 136 - 1, 2 * 136, max_len] {
+            let mut hasher = sha3::Keccak256::new();
+            hasher.update(&input[..len]);
+            let reference_output = hex::encode(hasher.finalize().as_slice());
+
+            let length = UInt32::allocate_checked(cs, len as u32);
+            let output = keccak256_variable_length(cs, &circuit_input, &length);
+            let output = hex::encode(&(output.witness_hook(&*cs))().unwrap());
+            assert_eq!(output, reference_output, "failed for length {}", len);
+
+            // same bytes in few pieces
+            let mut hasher = Keccak256Hasher::new(cs);
+            let (first, second) = circuit_input[..len].split_at(len / 3);
+            hasher.absorb(cs, first);
+            hasher.absorb(cs, &[]);
+            hasher.absorb(cs, second);
+            let output = hasher.finalize(cs);
+            let output = hex::encode(&(output.witness_hook(&*cs))().unwrap());
+            assert_eq!(output, reference_output, "failed for length {}", len);
+        }
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        use crate::worker::Worker;
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
 }
@@ -0,0 +1,271 @@
+use super::*;
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::recursion::recursive_tree_hasher::CircuitTreeHasher;
+use crate::gadgets::traits::allocatable::CSAllocatable;
+use crate::gadgets::u32::UInt32;
+
+// Merkle tree gadgets over any circuit tree hasher. Paths are given bottom-up, and index bits are
+// little endian, so the lowest bit tells whether the node is the right child at the leaf level.
+// Nodes are hashed in the same way as in the `MerkleTreeWithCap`
+
+// Decomposes the leaf index into `depth` path bits, and enforces that it's less than `2^depth`
+pub fn index_into_path_bits<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    index: &UInt32<F>,
+    depth: usize,
+) -> Vec<Boolean<F>> {
+    assert!(depth <= 32);
+
+    let bits = index.into_num().spread_into_bits::<CS, 32>(cs);
+    let boolean_false = Boolean::allocated_constant(cs, false);
+    for bit in bits[depth..].iter() {
+        Boolean::enforce_equal(cs, bit, &boolean_false);
+    }
+
+    bits[..depth].to_vec()
+}
+
+pub fn compute_root_from_path<
+    F: SmallField,
+    B: Sized + CSAllocatable<F>,
+    H: CircuitTreeHasher<F, B>,
+    CS: ConstraintSystem<F>,
+>(
+    cs: &mut CS,
+    leaf_hash: &H::CircuitOutput,
+    path: &[H::CircuitOutput],
+    index_bits: &[Boolean<F>],
+) -> H::CircuitOutput {
+    assert_eq!(path.len(), index_bits.len());
+
+    let mut current = *leaf_hash;
+    for (sibling, index_bit) in path.iter().zip(index_bits.iter()) {
+        let (left, right) = H::swap_nodes(cs, *index_bit, &current, sibling, 0);
+        current = H::hash_into_node(cs, &left, &right, 0);
+    }
+
+    current
+}
+
+// Returns whether the leaf is in the tree with the given root
+pub fn verify_inclusion<
+    F: SmallField,
+    B: Sized + CSAllocatable<F>,
+    H: CircuitTreeHasher<F, B>,
+    CS: ConstraintSystem<F>,
+>(
+    cs: &mut CS,
+    root: &H::CircuitOutput,
+    leaf_hash: &H::CircuitOutput,
+    path: &[H::CircuitOutput],
+    index_bits: &[Boolean<F>],
+) -> Boolean<F> {
+    let computed_root = compute_root_from_path::<F, B, H, CS>(cs, leaf_hash, path, index_bits);
+
+    H::compare_output(cs, &computed_root, root)
+}
+
+// Replaces the leaf at the given index. Returns whether the old leaf is in the tree with
+// the old root, and the root of the tree after the replacement. Siblings are the same for both
+// leaves, so swaps are shared between two paths
+pub fn update_leaf<
+    F: SmallField,
+    B: Sized + CSAllocatable<F>,
+    H: CircuitTreeHasher<F, B>,
+    CS: ConstraintSystem<F>,
+>(
+    cs: &mut CS,
+    old_root: &H::CircuitOutput,
+    old_leaf_hash: &H::CircuitOutput,
+    new_leaf_hash: &H::CircuitOutput,
+    path: &[H::CircuitOutput],
+    index_bits: &[Boolean<F>],
+) -> (Boolean<F>, H::CircuitOutput) {
+    assert_eq!(path.len(), index_bits.len());
+
+    let mut current_old = *old_leaf_hash;
+    let mut current_new = *new_leaf_hash;
+    for (sibling, index_bit) in path.iter().zip(index_bits.iter()) {
+        let (left, right) = H::swap_nodes(cs, *index_bit, &current_old, sibling, 0);
+        current_old = H::hash_into_node(cs, &left, &right, 0);
+        let (left, right) = H::swap_nodes(cs, *index_bit, &current_new, sibling, 0);
+        current_new = H::hash_into_node(cs, &left, &right, 0);
+    }
+
+    let is_included = H::compare_output(cs, &current_old, old_root);
+
+    (is_included, current_new)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebraic_props::round_function::AbsorptionModeOverwrite;
+    use crate::algebraic_props::sponge::GoldilocksPoseidon2Sponge;
+    use crate::cs::gates::*;
+    use crate::cs::oracle::TreeHasher;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::CSGeometry;
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::field::U64Representable;
+    use crate::gadgets::num::Num;
+    use crate::gadgets::recursion::recursive_tree_hasher::CircuitGoldilocksPoseidon2Sponge;
+    use crate::gadgets::tables::xor8::{create_xor8_table, Xor8Table};
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+
+    type F = GoldilocksField;
+    type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+    type CH = CircuitGoldilocksPoseidon2Sponge;
+
+    const DEPTH: usize = 3;
+
+    fn native_layers(leaves: &[[F; 4]]) -> Vec<Vec<[F; 4]>> {
+        let mut layers = vec![leaves.to_vec()];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| H::hash_into_node(&pair[0], &pair[1], 0))
+                .collect();
+            layers.push(next);
+        }
+
+        layers
+    }
+
+    fn native_path(layers: &[Vec<[F; 4]>], index: usize) -> Vec<[F; 4]> {
+        layers[..DEPTH]
+            .iter()
+            .enumerate()
+            .map(|(level, layer)| layer[(index >> level) ^ 1])
+            .collect()
+    }
+
+    #[test]
+    fn test_inclusion_and_update() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 80,
+            num_witness_columns: 0,
+            num_constant_columns: 8,
+            max_allowed_constraint_degree: 8,
+        };
+
+        use crate::config::DevCSConfig;
+        use crate::cs::cs_builder_reference::*;
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 20, 1 << 18);
+        use crate::cs::cs_builder::new_builder;
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 8,
+                share_table_id: true,
+            },
+        );
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+
+        let cs = &mut owned_cs;
+
+        let leaf_values: Vec<_> = (0..(1u64 << DEPTH))
+            .map(|idx| [F::from_u64_unchecked(idx), F::from_u64_unchecked(idx * idx)])
+            .collect();
+        let leaves: Vec<_> = leaf_values.iter().map(|el| H::hash_into_leaf(el)).collect();
+        let layers = native_layers(&leaves);
+        let root = layers[DEPTH][0];
+        let circuit_root = <[Num<F>; 4]>::allocate(cs, root);
+
+        for (index, values) in leaf_values.iter().enumerate() {
+            let circuit_values = values.map(|el| Num::allocate(cs, el));
+            let leaf_hash =
+                <CH as CircuitTreeHasher<F, Num<F>>>::hash_into_leaf(cs, circuit_values.iter());
+            let path: Vec<_> = native_path(&layers, index)
+                .into_iter()
+                .map(|el| <[Num<F>; 4]>::allocate(cs, el))
+                .collect();
+            let circuit_index = UInt32::allocate_checked(cs, index as u32);
+            let index_bits = index_into_path_bits(cs, &circuit_index, DEPTH);
+
+            let is_included = verify_inclusion::<F, Num<F>, CH, _>(
+                cs,
+                &circuit_root,
+                &leaf_hash,
+                &path,
+                &index_bits,
+            );
+            assert!(is_included.witness_hook(&*cs)().unwrap());
+
+            // wrong position
+            let mut wrong_bits = index_bits.clone();
+            wrong_bits[0] = wrong_bits[0].negated(cs);
+            let is_included = verify_inclusion::<F, Num<F>, CH, _>(
+                cs,
+                &circuit_root,
+                &leaf_hash,
+                &path,
+                &wrong_bits,
+            );
+            assert!(!is_included.witness_hook(&*cs)().unwrap());
+
+            // replace the leaf and compare with the tree rebuilt from scratch
+            let new_values = [
+                F::from_u64_unchecked(42),
+                F::from_u64_unchecked(index as u64),
+            ];
+            let mut new_leaves = leaves.clone();
+            new_leaves[index] = H::hash_into_leaf(&new_values);
+            let new_root = native_layers(&new_leaves)[DEPTH][0];
+
+            let circuit_new_values = new_values.map(|el| Num::allocate(cs, el));
+            let new_leaf_hash =
+                <CH as CircuitTreeHasher<F, Num<F>>>::hash_into_leaf(cs, circuit_new_values.iter());
+            let (is_included, circuit_new_root) = update_leaf::<F, Num<F>, CH, _>(
+                cs,
+                &circuit_root,
+                &leaf_hash,
+                &new_leaf_hash,
+                &path,
+                &index_bits,
+            );
+            assert!(is_included.witness_hook(&*cs)().unwrap());
+            assert_eq!(circuit_new_root.witness_hook(&*cs)().unwrap(), new_root);
+        }
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let worker = Worker::new();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
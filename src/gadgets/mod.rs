@@ -7,15 +7,24 @@ pub mod boolean;
 pub mod num;
 // pub mod poseidon;
 pub mod blake2s;
+pub mod commitment;
 pub mod curves;
+pub mod ec_pairing;
+pub mod i256;
+pub mod i32;
+pub mod i64;
 pub mod keccak256;
+pub mod merkle_tree;
+pub mod mpt;
 pub mod non_native_field;
 pub mod poseidon2;
 pub mod queue;
 pub mod recursion;
+pub mod rlp;
 pub mod round_function;
 pub mod sha256;
 pub mod tables;
+pub mod tower_extension;
 pub mod traits;
 pub mod u16;
 pub mod u160;
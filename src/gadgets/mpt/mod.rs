@@ -0,0 +1,692 @@
+use super::*;
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::gadgets::blake2s::mixing_function::split_byte_using_table;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::impls::length_markers::LengthMarkers;
+use crate::gadgets::keccak256::{keccak256, keccak256_variable_length};
+use crate::gadgets::num::Num;
+use crate::gadgets::rlp::*;
+use crate::gadgets::traits::selectable::Selectable;
+use crate::gadgets::u160::UInt160;
+use crate::gadgets::u256::UInt256;
+use crate::gadgets::u32::UInt32;
+use crate::gadgets::u8::UInt8;
+
+// Branch node with 16 hashed children and an empty value is the largest node in the trie
+pub const MPT_MAX_NODE_LENGTH: usize = 532;
+pub const MPT_KEY_NIBBLES: usize = 64;
+// RLP of [nonce, balance, storage_root, code_hash]
+pub const MAX_ACCOUNT_VALUE_LENGTH: usize = 110;
+// RLP of the 32-byte integer
+pub const MAX_STORAGE_VALUE_LENGTH: usize = 33;
+
+const HASH_REFERENCE_PREFIX: u8 = 0x80 + 32;
+const BRANCH_NODE_ITEMS: usize = 17;
+
+// Nodes on the path from the root to the leaf, padded with zeroes up to the maximum
+// length of the node and up to the maximum depth
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug)]
+pub struct MptProof<F: SmallField, const DEPTH: usize> {
+    pub nodes: [[UInt8<F>; MPT_MAX_NODE_LENGTH]; DEPTH],
+    pub node_lengths: [UInt32<F>; DEPTH],
+    pub depth: UInt32<F>,
+}
+
+impl<F: SmallField, const DEPTH: usize> MptProof<F, DEPTH> {
+    pub fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, nodes: &[Vec<u8>]) -> Self {
+        assert!(nodes.len() <= DEPTH);
+
+        let zero = UInt8::zero(cs);
+        let mut allocated_nodes = [[zero; MPT_MAX_NODE_LENGTH]; DEPTH];
+        let mut node_lengths = [UInt32::zero(cs); DEPTH];
+        for (level, (dst, dst_length)) in allocated_nodes
+            .iter_mut()
+            .zip(node_lengths.iter_mut())
+            .enumerate()
+        {
+            let mut padded = [0u8; MPT_MAX_NODE_LENGTH];
+            let length = nodes.get(level).map(|el| el.len()).unwrap_or(0);
+            assert!(length <= MPT_MAX_NODE_LENGTH);
+            if length > 0 {
+                padded[..length].copy_from_slice(&nodes[level]);
+            }
+
+            for (dst, pair) in dst.chunks_exact_mut(2).zip(padded.chunks_exact(2)) {
+                let pair = UInt8::allocate_pair(cs, [pair[0], pair[1]]);
+                dst.copy_from_slice(&pair);
+            }
+            *dst_length = UInt32::allocate_checked(cs, length as u32);
+        }
+
+        let depth = UInt32::allocate_checked(cs, nodes.len() as u32);
+
+        Self {
+            nodes: allocated_nodes,
+            node_lengths,
+            depth,
+        }
+    }
+}
+
+// Verifies the inclusion proof for the `key` in the trie with the given `root`, and returns
+// the value (zero padded) and it's length.
+// Only nodes that are referenced by their hashes are supported, so the proof will not verify
+// if any node on the path is shorter than 32 bytes and inlined into the parent. That never
+// happens in the state and storage tries where keys are hashes. Exclusion proofs are not supported
+pub fn verify_mpt_proof<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    const DEPTH: usize,
+    const MAX_VALUE_LENGTH: usize,
+>(
+    cs: &mut CS,
+    root: &[UInt8<F>; 32],
+    key: &[UInt8<F>; 32],
+    proof: &MptProof<F, DEPTH>,
+) -> ([UInt8<F>; MAX_VALUE_LENGTH], Num<F>) {
+    // level is active if it's below the depth, and the last one is exactly at depth - 1
+    let depth_markers = LengthMarkers::new(cs, &proof.depth.into_num(), DEPTH);
+    let boolean_false = Boolean::allocated_constant(cs, false);
+    Boolean::enforce_equal(cs, &depth_markers.is_end[0], &boolean_false);
+
+    let mut key_nibbles = Vec::with_capacity(MPT_KEY_NIBBLES);
+    for byte in key.iter() {
+        let (low, high) = split_byte_using_table::<F, CS, 4>(cs, byte.get_variable());
+        unsafe {
+            key_nibbles.push(UInt8::from_variable_unchecked(high));
+            key_nibbles.push(UInt8::from_variable_unchecked(low));
+        }
+    }
+
+    let nibble_constants: [Num<F>; 16] =
+        std::array::from_fn(|idx| Num::allocated_constant(cs, F::from_u64_unchecked(idx as u64)));
+    let zero_byte = UInt8::zero(cs);
+    let reference_prefix =
+        Num::allocated_constant(cs, F::from_u64_unchecked(HASH_REFERENCE_PREFIX as u64));
+
+    let mut expected_hash = *root;
+    let mut key_position = Num::zero(cs);
+    let mut last_node = [zero_byte; MPT_MAX_NODE_LENGTH];
+    let mut value_offset = Num::zero(cs);
+    let mut value_length = Num::zero(cs);
+
+    for level in 0..DEPTH {
+        let node = &proof.nodes[level];
+        let node_length = proof.node_lengths[level].into_num();
+        let is_active = depth_markers.is_inside[level];
+        let is_last = depth_markers.is_end[level + 1];
+
+        let hash = keccak256_variable_length(cs, node, &proof.node_lengths[level]);
+        for (a, b) in hash.iter().zip(expected_hash.iter()) {
+            Num::conditionally_enforce_equal(cs, is_active, &a.into_num(), &b.into_num());
+        }
+
+        // every node is a list that spans the whole node
+        let list_header = RlpItemHeader::decode(cs, &[node[0], node[1], node[2]]);
+        list_header
+            .is_list
+            .conditionally_enforce_true(cs, is_active);
+        let zero_offset = Num::zero(cs);
+        let list_end = list_header.end_offset(cs, &zero_offset);
+        Num::conditionally_enforce_equal(cs, is_active, &list_end, &node_length);
+
+        // branch has 17 items, and leaf or extension have 2
+        let (items, items_end) =
+            decode_rlp_items::<F, CS, BRANCH_NODE_ITEMS>(cs, node, &list_header.header_length);
+        let is_short_node = Num::equals(cs, &items[2].0, &node_length);
+        let is_branch = is_short_node.negated(cs);
+        let is_active_branch = is_active.and(cs, is_branch);
+        let is_active_short_node = is_active.and(cs, is_short_node);
+        Num::conditionally_enforce_equal(cs, is_active_branch, &items_end, &node_length);
+
+        let remaining_key =
+            extract_window::<F, CS, MPT_KEY_NIBBLES>(cs, &key_nibbles, &key_position);
+
+        // branch follows the child at the next nibble of the key
+        let mut child_offset_terms = Vec::with_capacity(16);
+        for ((item_offset, _), nibble) in items[..16].iter().zip(nibble_constants.iter()) {
+            let is_selected = Num::equals(cs, &remaining_key[0].into_num(), nibble);
+            let masked = item_offset.mask(cs, is_selected);
+            child_offset_terms.push((masked.get_variable(), F::ONE));
+        }
+        let branch_child_offset = Num::linear_combination(cs, &child_offset_terms);
+
+        // leaf or extension start with the compact encoding of the path:
+        // flags nibble (bit 0 for odd length, bit 1 for leaf), then padding nibble if length is even
+        let (path_offset, path_header) = items[0];
+        let path_payload_offset = path_header.payload_offset(cs, &path_offset);
+        let path = extract_window::<F, CS, 33>(cs, node, &path_payload_offset);
+        let mut path_nibbles = Vec::with_capacity(66);
+        for byte in path.iter() {
+            let (low, high) = split_byte_using_table::<F, CS, 4>(cs, byte.get_variable());
+            path_nibbles.push(Num::from_variable(high));
+            path_nibbles.push(Num::from_variable(low));
+        }
+        let flags = path_nibbles[0].spread_into_bits::<CS, 4>(cs);
+        let [is_odd, is_leaf_flag, unused_0, unused_1] = flags;
+        unused_0.conditionally_enforce_false(cs, is_active_short_node);
+        unused_1.conditionally_enforce_false(cs, is_active_short_node);
+        let is_even = is_odd.negated(cs);
+        let padding_enforced = is_active_short_node.and(cs, is_even);
+        let zero_nibble = Num::zero(cs);
+        Num::conditionally_enforce_equal(cs, padding_enforced, &path_nibbles[1], &zero_nibble);
+
+        let mut minus_two = F::TWO;
+        minus_two.negate();
+        let path_length = Num::linear_combination(
+            cs,
+            &[
+                (path_header.payload_length.get_variable(), F::TWO),
+                (is_odd.get_variable(), F::ONE),
+                (is_active_short_node.get_variable(), minus_two),
+            ],
+        );
+        let path_length = path_length.mask(cs, is_active_short_node);
+        let path_markers = LengthMarkers::new(cs, &path_length, MPT_KEY_NIBBLES);
+        for (idx, key_nibble) in remaining_key.iter().enumerate() {
+            let path_nibble = Num::conditionally_select(
+                cs,
+                is_odd,
+                &path_nibbles[1 + idx],
+                &path_nibbles[2 + idx],
+            );
+            Num::conditionally_enforce_equal(
+                cs,
+                path_markers.is_inside[idx],
+                &path_nibble,
+                &key_nibble.into_num(),
+            );
+        }
+
+        // we only go down the trie, and the path ends at the leaf
+        let is_leaf = is_active_short_node.and(cs, is_leaf_flag);
+        Boolean::enforce_equal(cs, &is_leaf, &is_last);
+
+        key_position = Num::linear_combination(
+            cs,
+            &[
+                (key_position.get_variable(), F::ONE),
+                (is_active_branch.get_variable(), F::ONE),
+                (path_length.get_variable(), F::ONE),
+            ],
+        );
+
+        // reference to the next node is the 32-byte string
+        let (second_item_offset, second_item_header) = items[1];
+        let reference_offset =
+            Num::conditionally_select(cs, is_branch, &branch_child_offset, &second_item_offset);
+        let reference = extract_window::<F, CS, 33>(cs, node, &reference_offset);
+        let not_last = is_last.negated(cs);
+        let has_child = is_active.and(cs, not_last);
+        Num::conditionally_enforce_equal(
+            cs,
+            has_child,
+            &reference[0].into_num(),
+            &reference_prefix,
+        );
+        expected_hash.copy_from_slice(&reference[1..]);
+
+        // value is the second item of the leaf
+        second_item_header
+            .is_list
+            .conditionally_enforce_false(cs, is_last);
+        let second_item_payload_offset = second_item_header.payload_offset(cs, &second_item_offset);
+        last_node = Selectable::conditionally_select(cs, is_last, node, &last_node);
+        value_offset =
+            Num::conditionally_select(cs, is_last, &second_item_payload_offset, &value_offset);
+        value_length = Num::conditionally_select(
+            cs,
+            is_last,
+            &second_item_header.payload_length,
+            &value_length,
+        );
+    }
+
+    // whole key is consumed
+    let key_length = Num::allocated_constant(cs, F::from_u64_unchecked(MPT_KEY_NIBBLES as u64));
+    Num::enforce_equal(cs, &key_position, &key_length);
+
+    let value_markers = LengthMarkers::new(cs, &value_length, MAX_VALUE_LENGTH);
+    let mut value = extract_window::<F, CS, MAX_VALUE_LENGTH>(cs, &last_node, &value_offset);
+    for (idx, byte) in value.iter_mut().enumerate() {
+        let masked = value_markers.mask_element(cs, byte.get_variable(), idx);
+        *byte = unsafe { UInt8::from_variable_unchecked(masked) };
+    }
+
+    (value, value_length)
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug)]
+pub struct AccountState<F: SmallField> {
+    pub nonce: UInt256<F>,
+    pub balance: UInt256<F>,
+    pub storage_root: [UInt8<F>; 32],
+    pub code_hash: [UInt8<F>; 32],
+}
+
+// Verifies the account proof against the state root, and decodes the account
+pub fn verify_account_proof<F: SmallField, CS: ConstraintSystem<F>, const DEPTH: usize>(
+    cs: &mut CS,
+    state_root: &[UInt8<F>; 32],
+    address: &UInt160<F>,
+    proof: &MptProof<F, DEPTH>,
+) -> AccountState<F> {
+    let address_bytes = address.to_be_bytes(cs);
+    let key = keccak256(cs, &address_bytes);
+    let (value, value_length) =
+        verify_mpt_proof::<F, CS, DEPTH, MAX_ACCOUNT_VALUE_LENGTH>(cs, state_root, &key, proof);
+
+    let list_header = RlpItemHeader::decode(cs, &[value[0], value[1], value[2]]);
+    let boolean_true = Boolean::allocated_constant(cs, true);
+    let boolean_false = Boolean::allocated_constant(cs, false);
+    Boolean::enforce_equal(cs, &list_header.is_list, &boolean_true);
+    let (items, items_end) = decode_rlp_items::<F, CS, 4>(cs, &value, &list_header.header_length);
+    Num::enforce_equal(cs, &items_end, &value_length);
+    for (_, header) in items.iter() {
+        Boolean::enforce_equal(cs, &header.is_list, &boolean_false);
+    }
+
+    let [nonce, balance] = [items[0], items[1]].map(|(offset, header)| {
+        let payload_offset = header.payload_offset(cs, &offset);
+        decode_rlp_uint256(cs, &value, &payload_offset, &header.payload_length)
+    });
+
+    let reference_prefix = UInt8::allocated_constant(cs, HASH_REFERENCE_PREFIX);
+    let [storage_root, code_hash] = [items[2].0, items[3].0].map(|offset| {
+        let reference = extract_window::<F, CS, 33>(cs, &value, &offset);
+        Num::enforce_equal(cs, &reference[0].into_num(), &reference_prefix.into_num());
+        let hash: [UInt8<F>; 32] = reference[1..].try_into().expect("length must match");
+
+        hash
+    });
+
+    AccountState {
+        nonce,
+        balance,
+        storage_root,
+        code_hash,
+    }
+}
+
+// Verifies the storage proof against the storage root of the account, and returns the value
+// of the slot. Zero values are never stored in the trie, so exclusion proofs are needed for them
+// and those are not supported
+pub fn verify_storage_proof<F: SmallField, CS: ConstraintSystem<F>, const DEPTH: usize>(
+    cs: &mut CS,
+    storage_root: &[UInt8<F>; 32],
+    slot: &UInt256<F>,
+    proof: &MptProof<F, DEPTH>,
+) -> UInt256<F> {
+    let slot_bytes = slot.to_be_bytes(cs);
+    let key = keccak256(cs, &slot_bytes);
+    let (value, value_length) =
+        verify_mpt_proof::<F, CS, DEPTH, MAX_STORAGE_VALUE_LENGTH>(cs, storage_root, &key, proof);
+
+    // value is the RLP of the integer
+    let header = RlpItemHeader::decode(cs, &[value[0], value[1], value[2]]);
+    let boolean_false = Boolean::allocated_constant(cs, false);
+    Boolean::enforce_equal(cs, &header.is_list, &boolean_false);
+    let zero_offset = Num::zero(cs);
+    let end = header.end_offset(cs, &zero_offset);
+    Num::enforce_equal(cs, &end, &value_length);
+
+    decode_rlp_uint256(cs, &value, &header.header_length, &header.payload_length)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        cs::{
+            gates::{
+                ConstantsAllocatorGate, FmaGateInBaseFieldWithoutConstant, NopGate, ReductionGate,
+                SelectionGate, ZeroCheckGate,
+            },
+            CSGeometry,
+        },
+        field::{goldilocks::GoldilocksField, U64Representable},
+        gadgets::tables::{
+            and8::{create_and8_table, And8Table},
+            byte_split::{create_byte_split_table, ByteSplitTable},
+            xor8::{create_xor8_table, Xor8Table},
+        },
+    };
+    use sha3::Digest;
+    type F = GoldilocksField;
+    use crate::config::DevCSConfig;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::gadgets::traits::allocatable::CSAllocatable;
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use ethereum_types::{Address, U256};
+
+    type Entry = ([u8; MPT_KEY_NIBBLES], Vec<u8>);
+
+    fn native_keccak(input: &[u8]) -> [u8; 32] {
+        let mut hasher = sha3::Keccak256::new();
+        hasher.update(input);
+        let mut result = [0u8; 32];
+        result.copy_from_slice(hasher.finalize().as_slice());
+
+        result
+    }
+
+    fn rlp_string(bytes: &[u8]) -> Vec<u8> {
+        let mut result = match bytes.len() {
+            1 if bytes[0] < 0x80 => vec![],
+            len if len < 56 => vec![0x80 + len as u8],
+            len => {
+                assert!(len < 256);
+                vec![0xb8, len as u8]
+            }
+        };
+        result.extend_from_slice(bytes);
+
+        result
+    }
+
+    fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+        let payload = items.concat();
+        let mut result = match payload.len() {
+            len if len < 56 => vec![0xc0 + len as u8],
+            len if len < 256 => vec![0xf8, len as u8],
+            len => vec![0xf9, (len >> 8) as u8, len as u8],
+        };
+        result.extend(payload);
+
+        result
+    }
+
+    fn rlp_uint(value: U256) -> Vec<u8> {
+        let mut bytes = [0u8; 32];
+        value.to_big_endian(&mut bytes);
+        let first_non_zero = bytes.iter().position(|el| *el != 0).unwrap_or(32);
+
+        rlp_string(&bytes[first_non_zero..])
+    }
+
+    fn compact_path(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+        let flags = if is_leaf { 2 } else { 0 } + (nibbles.len() % 2) as u8;
+        let mut all = vec![flags];
+        if nibbles.len() % 2 == 0 {
+            all.push(0);
+        }
+        all.extend_from_slice(nibbles);
+
+        all.chunks(2).map(|el| (el[0] << 4) | el[1]).collect()
+    }
+
+    fn key_nibbles(key: &[u8; 32]) -> [u8; MPT_KEY_NIBBLES] {
+        let mut result = [0u8; MPT_KEY_NIBBLES];
+        for (dst, src) in result.chunks_mut(2).zip(key.iter()) {
+            dst[0] = src >> 4;
+            dst[1] = src & 0x0f;
+        }
+
+        result
+    }
+
+    fn common_prefix_end(entries: &[Entry], depth: usize) -> usize {
+        let mut end = depth;
+        while entries.iter().all(|(key, _)| key[end] == entries[0].0[end]) {
+            end += 1;
+        }
+
+        end
+    }
+
+    // node that holds all the entries below the first `depth` nibbles of the key
+    fn build_node(entries: &[Entry], depth: usize) -> Vec<u8> {
+        if entries.len() == 1 {
+            let (key, value) = &entries[0];
+            return rlp_list(&[
+                rlp_string(&compact_path(&key[depth..], true)),
+                rlp_string(value),
+            ]);
+        }
+
+        let end = common_prefix_end(entries, depth);
+        if end > depth {
+            let child = build_node(entries, end);
+            return rlp_list(&[
+                rlp_string(&compact_path(&entries[0].0[depth..end], false)),
+                rlp_string(&native_keccak(&child)),
+            ]);
+        }
+
+        let mut items = vec![];
+        for nibble in 0..16 {
+            let children: Vec<_> = entries
+                .iter()
+                .filter(|(key, _)| key[depth] == nibble)
+                .cloned()
+                .collect();
+            if children.is_empty() {
+                items.push(rlp_string(&[]));
+            } else {
+                items.push(rlp_string(&native_keccak(&build_node(
+                    &children,
+                    depth + 1,
+                ))));
+            }
+        }
+        items.push(rlp_string(&[]));
+
+        rlp_list(&items)
+    }
+
+    fn build_proof(entries: &[Entry], key: &[u8; MPT_KEY_NIBBLES]) -> Vec<Vec<u8>> {
+        let mut proof = vec![];
+        let mut entries = entries.to_vec();
+        let mut depth = 0;
+        loop {
+            proof.push(build_node(&entries, depth));
+            if entries.len() == 1 {
+                break;
+            }
+
+            let end = common_prefix_end(&entries, depth);
+            if end > depth {
+                depth = end;
+            } else {
+                entries.retain(|(el, _)| el[depth] == key[depth]);
+                depth += 1;
+            }
+        }
+
+        proof
+    }
+
+    fn allocate_bytes<CS: ConstraintSystem<F>, const N: usize>(
+        cs: &mut CS,
+        bytes: &[u8; N],
+    ) -> [UInt8<F>; N] {
+        bytes.map(|el| UInt8::allocate_checked(cs, el))
+    }
+
+    fn create_test_cs() -> crate::cs::implementations::reference_cs::CSReferenceImplementation<
+        F,
+        F,
+        DevCSConfig,
+        impl crate::cs::GateConfigurationHolder<F>,
+        impl crate::cs::StaticToolboxHolder,
+    > {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 20,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        use crate::cs::cs_builder_reference::*;
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 22, 1 << 21);
+        use crate::cs::cs_builder::new_builder;
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 5,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+        let table = create_and8_table();
+        owned_cs.add_lookup_table::<And8Table, 3>(table);
+        let table = create_byte_split_table::<F, 1>();
+        owned_cs.add_lookup_table::<ByteSplitTable<1>, 3>(table);
+        let table = create_byte_split_table::<F, 2>();
+        owned_cs.add_lookup_table::<ByteSplitTable<2>, 3>(table);
+        let table = create_byte_split_table::<F, 3>();
+        owned_cs.add_lookup_table::<ByteSplitTable<3>, 3>(table);
+        let table = create_byte_split_table::<F, 4>();
+        owned_cs.add_lookup_table::<ByteSplitTable<4>, 3>(table);
+
+        owned_cs
+    }
+
+    #[test]
+    fn test_proof_with_extension_and_branch() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        // first two keys share 3 nibbles, so we get an extension, then branch and leaf
+        let mut keys = [[0x12u8; 32], [0x12u8; 32], [0xabu8; 32]];
+        keys[1][1] = 0x1f;
+        let values = [vec![0x01u8], vec![0x42u8; 40], vec![0x80u8; 20]];
+        let entries: Vec<Entry> = keys
+            .iter()
+            .zip(values.iter())
+            .map(|(key, value)| (key_nibbles(key), value.clone()))
+            .collect();
+        let root = native_keccak(&build_node(&entries, 0));
+        let circuit_root = allocate_bytes(cs, &root);
+
+        for (key, value) in keys.iter().zip(values.iter()) {
+            let proof = build_proof(&entries, &key_nibbles(key));
+            let circuit_proof = MptProof::<F, 5>::allocate(cs, &proof);
+            let circuit_key = allocate_bytes(cs, key);
+            let (circuit_value, circuit_length) =
+                verify_mpt_proof::<F, _, 5, 40>(cs, &circuit_root, &circuit_key, &circuit_proof);
+
+            let mut expected = [0u8; 40];
+            expected[..value.len()].copy_from_slice(value);
+            assert_eq!(circuit_value.witness_hook(&*cs)().unwrap(), expected);
+            assert_eq!(
+                circuit_length.witness_hook(&*cs)().unwrap(),
+                F::from_u64_unchecked(value.len() as u64)
+            );
+        }
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        use crate::worker::Worker;
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    fn test_account_and_storage_proofs() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let slots = [U256::from(1u64), U256::from(7u64)];
+        let slot_values = [U256::from(0x1234u64), U256::MAX];
+        let storage_entries: Vec<Entry> = slots
+            .iter()
+            .zip(slot_values.iter())
+            .map(|(slot, value)| {
+                let mut slot_bytes = [0u8; 32];
+                slot.to_big_endian(&mut slot_bytes);
+                (key_nibbles(&native_keccak(&slot_bytes)), rlp_uint(*value))
+            })
+            .collect();
+        let storage_root = native_keccak(&build_node(&storage_entries, 0));
+
+        let address = Address::from_low_u64_be(0xdeadbeef);
+        let nonce = U256::from(5u64);
+        let balance = U256::from(10u64).pow(U256::from(18u64));
+        let code_hash = native_keccak(&[]);
+        let account = rlp_list(&[
+            rlp_uint(nonce),
+            rlp_uint(balance),
+            rlp_string(&storage_root),
+            rlp_string(&code_hash),
+        ]);
+        let other_account = rlp_list(&[
+            rlp_uint(U256::zero()),
+            rlp_uint(U256::one()),
+            rlp_string(&storage_root),
+            rlp_string(&code_hash),
+        ]);
+        let account_key = key_nibbles(&native_keccak(address.as_bytes()));
+        let other_key = key_nibbles(&native_keccak(Address::from_low_u64_be(0xcafe).as_bytes()));
+        let state_entries = vec![(account_key, account), (other_key, other_account)];
+        let state_root = native_keccak(&build_node(&state_entries, 0));
+
+        let circuit_state_root = allocate_bytes(cs, &state_root);
+        let circuit_address = UInt160::allocate(cs, address);
+        let proof = build_proof(&state_entries, &account_key);
+        let circuit_proof = MptProof::<F, 3>::allocate(cs, &proof);
+        let state = verify_account_proof(cs, &circuit_state_root, &circuit_address, &circuit_proof);
+
+        assert_eq!(state.nonce.witness_hook(&*cs)().unwrap(), nonce);
+        assert_eq!(state.balance.witness_hook(&*cs)().unwrap(), balance);
+        assert_eq!(
+            state.storage_root.witness_hook(&*cs)().unwrap(),
+            storage_root
+        );
+        assert_eq!(state.code_hash.witness_hook(&*cs)().unwrap(), code_hash);
+
+        for ((slot, value), (key, _)) in slots
+            .iter()
+            .zip(slot_values.iter())
+            .zip(storage_entries.iter())
+        {
+            let circuit_slot = UInt256::allocate(cs, *slot);
+            let proof = build_proof(&storage_entries, key);
+            let circuit_proof = MptProof::<F, 3>::allocate(cs, &proof);
+            let circuit_value =
+                verify_storage_proof(cs, &state.storage_root, &circuit_slot, &circuit_proof);
+            assert_eq!(circuit_value.witness_hook(&*cs)().unwrap(), *value);
+        }
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        use crate::worker::Worker;
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
@@ -100,6 +100,10 @@ where
         todo!()
     }
 
+    fn to_le_bits<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Vec<Boolean<F>> {
+        NonNativeFieldOverU16::<F, T, N>::to_le_bits(self, cs)
+    }
+
     fn is_zero<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
         NonNativeFieldOverU16::<F, T, N>::is_zero(self, cs)
     }
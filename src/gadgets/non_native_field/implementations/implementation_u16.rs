@@ -103,6 +103,44 @@ where
         new
     }
 
+    pub fn allocate_from_closure_and_dependencies<
+        CS: ConstraintSystem<F>,
+        FN: FnOnce(&[F]) -> T + 'static + Send + Sync,
+    >(
+        cs: &mut CS,
+        witness_closure: FN,
+        dependencies: &[Place],
+        params: &Arc<NonNativeFieldOverU16Params<T, N>>,
+    ) -> Self {
+        let new = Self::allocate_checked_without_value(cs, params);
+
+        if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS == true {
+            let modulus_limbs = params.modulus_limbs;
+            let value_fn = move |inputs: &[F], dst: &mut DstBuffer<'_, '_, F>| {
+                let witness = (witness_closure)(inputs);
+                let limbs = fe_to_u16_words::<_, N>(&witness);
+                for (idx, el) in limbs.into_iter().enumerate() {
+                    if idx < modulus_limbs {
+                        dst.push(F::from_u64_unchecked(el as u64));
+                    } else {
+                        assert_eq!(el, 0);
+                    }
+                }
+            };
+
+            let mut outputs = Vec::with_capacity(params.modulus_limbs);
+            outputs.extend(
+                Place::from_variables(new.limbs)
+                    .into_iter()
+                    .take(params.modulus_limbs),
+            );
+
+            cs.set_values_with_dependencies_vararg(dependencies, &outputs, value_fn);
+        }
+
+        new
+    }
+
     pub fn enforce_reduced<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
         assert_eq!(self.form, RepresentationForm::Normalized);
         if self.tracker.max_moduluses == 1 && self.form == RepresentationForm::Normalized {
@@ -957,6 +995,24 @@ where
         Boolean::multi_and(cs, &zeroes)
     }
 
+    pub fn to_le_bits<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Vec<Boolean<F>>
+    where
+        [(); N + 1]:,
+    {
+        // normalized value is at most the modulus, so all the bits above `modulus_bits`
+        // are zeroes and we can just drop them
+        self.normalize(cs);
+        let num_bits = self.params.modulus_bits as usize;
+        let mut result = Vec::with_capacity(self.params.modulus_limbs * 16);
+        for limb in self.limbs[..self.params.modulus_limbs].iter() {
+            let bits = Num::from_variable(*limb).spread_into_bits::<_, 16>(cs);
+            result.extend(bits);
+        }
+        result.truncate(num_bits);
+
+        result
+    }
+
     #[must_use]
     pub fn mask<CS: ConstraintSystem<F>>(&self, cs: &mut CS, masking_bit: Boolean<F>) -> Self {
         let mut new = self.clone();
@@ -1132,6 +1188,21 @@ impl<F: SmallField, T: pairing::ff::PrimeField, const N: usize> Selectable<F>
     }
 }
 
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+
+impl<F: SmallField, T: pairing::ff::PrimeField, const N: usize> CircuitEq<F>
+    for NonNativeFieldOverU16<F, T, N>
+where
+    [(); N + 1]:,
+{
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        // comparison requires normalization, so we work on copies
+        let mut a = a.clone();
+        let mut b = b.clone();
+        NonNativeFieldOverU16::equals(cs, &mut a, &mut b)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -66,6 +66,9 @@ pub trait NonNativeField<F: SmallField, T: pairing::ff::PrimeField>:
 
     fn inverse_unchecked<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self;
 
+    /// Returns little-endian bit decomposition of the normalized value, `T::NUM_BITS` bits long
+    fn to_le_bits<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Vec<Boolean<F>>;
+
     fn is_zero<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F>;
     fn equals<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Boolean<F>;
 
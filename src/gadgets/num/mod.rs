@@ -12,6 +12,7 @@ use crate::cs::gates::fma_gate_without_constant::{
 use crate::cs::gates::ReductionByPowersGate;
 use crate::cs::traits::cs::ConstraintSystem;
 use crate::cs::traits::cs::DstBuffer;
+use crate::field::SqrtField;
 use crate::gadgets::impls::limbs_decompose::*;
 use crate::gadgets::traits::allocatable::CSAllocatable;
 use crate::gadgets::u8::UInt8;
@@ -185,6 +186,18 @@ impl<F: SmallField> Selectable<F> for Num<F> {
     }
 }
 
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+
+impl<F: SmallField> CircuitEq<F> for Num<F> {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Num::equals(cs, a, b)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        Num::enforce_equal(cs, a, b)
+    }
+}
+
 impl<F: SmallField> Num<F> {
     #[inline]
     pub const fn get_variable(&self) -> Variable {
@@ -1089,6 +1102,46 @@ impl<F: SmallField> Num<F> {
     }
 }
 
+impl<F: SmallField + SqrtField> Num<F> {
+    /// Returns a flag whether the value is a quadratic residue and a square root of either the value
+    /// itself if it is, or of the value multiplied by the multiplicative generator (that is a non-residue) otherwise.
+    /// Exactly one of two is a residue for nonzero value, so the flag can not be forged
+    #[must_use]
+    pub fn sqrt<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> (Boolean<F>, Self) {
+        let [root, is_square] = Self::allocate_multiple_from_closure_and_dependencies(
+            cs,
+            |inputs: &[F]| {
+                let value = inputs[0];
+                if let Some(root) = value.sqrt() {
+                    [root, F::ONE]
+                } else {
+                    let mut scaled = value;
+                    scaled.mul_assign(&F::multiplicative_generator());
+                    let root = scaled
+                        .sqrt()
+                        .expect("product of two non-residues is a residue");
+
+                    [root, F::ZERO]
+                }
+            },
+            &[Place::from_variable(self.variable)],
+        );
+        let is_square = Boolean::from_variable_checked(cs, is_square.get_variable());
+
+        let generator = Self::allocated_constant(cs, F::multiplicative_generator());
+        let scaled = self.mul(cs, &generator);
+        let expected = Self::conditionally_select(cs, is_square, self, &scaled);
+        let root_squared = root.mul(cs, &root);
+        Self::enforce_equal(cs, &root_squared, &expected);
+
+        // zero is a root of both zero and generator * zero, so we pin the flag
+        let is_zero = self.is_zero(cs);
+        is_square.conditionally_enforce_true(cs, is_zero);
+
+        (is_square, root)
+    }
+}
+
 /// Returns dot product of the variable, padding by 0 if necessary
 pub fn dot_product<F: SmallField, CS: ConstraintSystem<F>>(
     cs: &mut CS,
@@ -1211,6 +1264,41 @@ impl<F: SmallField> CircuitVarLengthEncodable<F> for Num<F> {
     }
 }
 
+use crate::gadgets::traits::encodable::{
+    CircuitDecodable, CircuitEncodable, CircuitEncodingLayout,
+};
+
+impl<F: SmallField> CircuitEncodable<F, 1> for Num<F> {
+    #[inline(always)]
+    fn encode<CS: ConstraintSystem<F>>(&self, _cs: &mut CS) -> [Variable; 1] {
+        [self.get_variable()]
+    }
+}
+
+impl<F: SmallField> CircuitDecodable<F, 1> for Num<F> {
+    #[inline(always)]
+    fn decode<CS: ConstraintSystem<F>>(_cs: &mut CS, encoding: [Variable; 1]) -> Self {
+        Self::from_variable(encoding[0])
+    }
+}
+
+use crate::gadgets::traits::public_input::CSPublicInput;
+
+impl<F: SmallField> CSPublicInput<F, 1> for Num<F> {
+    #[inline(always)]
+    fn encode_witness(witness: &Self::Witness) -> [F; 1] {
+        [*witness]
+    }
+    #[inline(always)]
+    fn decode_witness(encoding: [F; 1]) -> Option<Self::Witness> {
+        Some(encoding[0])
+    }
+}
+
+impl<F: SmallField> CircuitEncodingLayout<F> for Num<F> {
+    const ENCODING_LENGTH: usize = 1;
+}
+
 use crate::gadgets::traits::allocatable::CSAllocatableExt;
 
 impl<F: SmallField> CSAllocatableExt<F> for Num<F> {
@@ -1237,3 +1325,82 @@ impl<F: SmallField> CSAllocatableExt<F> for Num<F> {
         dst.push(witness);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cs::cs_builder::*;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::CSGeometry;
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::field::{Field, PrimeField, U64Representable};
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+
+    type F = GoldilocksField;
+
+    #[test]
+    fn test_sqrt() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 20,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 10, 1 << 8);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+        let cs = &mut owned_cs;
+
+        // 7 is the multiplicative generator, so it's a non-residue
+        for (value, expected_is_square) in [(0u64, true), (4, true), (7, false), (49, true)] {
+            let value = F::from_u64_unchecked(value);
+            let num = Num::allocate(cs, value);
+            let (is_square, root) = num.sqrt(cs);
+
+            assert_eq!(is_square.witness_hook(&*cs)().unwrap(), expected_is_square);
+            let mut root_squared = root.witness_hook(&*cs)().unwrap();
+            root_squared.square();
+            if expected_is_square {
+                assert_eq!(root_squared, value);
+            } else {
+                let mut scaled = value;
+                scaled.mul_assign(&F::multiplicative_generator());
+                assert_eq!(root_squared, scaled);
+            }
+        }
+
+        let worker = Worker::new();
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
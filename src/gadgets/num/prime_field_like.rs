@@ -148,114 +148,159 @@ where
 #[derivative(Debug(bound = ""), Hash(bound = ""))]
 pub struct NumExtAsFieldWrapper<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     CS: ConstraintSystem<F>,
 > {
-    c0: NumAsFieldWrapper<F, CS>,
-    c1: NumAsFieldWrapper<F, CS>,
+    coeffs: [NumAsFieldWrapper<F, CS>; DEGREE],
     #[derivative(Debug = "ignore", Hash = "ignore")]
     _marker: std::marker::PhantomData<EXT>,
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F>>
-    std::fmt::Display for NumExtAsFieldWrapper<F, EXT, CS>
+impl<
+        F: SmallField,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
+        CS: ConstraintSystem<F>,
+    > std::fmt::Display for NumExtAsFieldWrapper<F, DEGREE, EXT, CS>
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "NumExt as PrimeFieldLike{{")?;
-        writeln!(f, "c0: {:?},", self.c0.inner.variable)?;
-        writeln!(f, "c1: {:?},", self.c1.inner.variable)?;
+        for (idx, el) in self.coeffs.iter().enumerate() {
+            writeln!(f, "c{}: {:?},", idx, el.inner.variable)?;
+        }
         writeln!(f, "}}")
     }
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F>> Clone
-    for NumExtAsFieldWrapper<F, EXT, CS>
+impl<
+        F: SmallField,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
+        CS: ConstraintSystem<F>,
+    > Clone for NumExtAsFieldWrapper<F, DEGREE, EXT, CS>
 {
     fn clone(&self) -> Self {
         Self {
-            c0: self.c0,
-            c1: self.c1,
+            coeffs: self.coeffs,
             _marker: std::marker::PhantomData,
         }
     }
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F>> Copy
-    for NumExtAsFieldWrapper<F, EXT, CS>
+impl<
+        F: SmallField,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
+        CS: ConstraintSystem<F>,
+    > Copy for NumExtAsFieldWrapper<F, DEGREE, EXT, CS>
 {
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CSO: ConstraintSystem<F> + 'static>
-    CSAllocatable<F> for NumExtAsFieldWrapper<F, EXT, CSO>
+impl<
+        F: SmallField,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
+        CSO: ConstraintSystem<F> + 'static,
+    > CSAllocatable<F> for NumExtAsFieldWrapper<F, DEGREE, EXT, CSO>
 {
-    type Witness = ExtensionField<F, 2, EXT>;
+    type Witness = ExtensionField<F, DEGREE, EXT>;
 
     fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
-        let [c0, c1] = witness.into_coeffs_in_base();
-        let c0 = Num::allocate(cs, c0).into();
-        let c1 = Num::allocate(cs, c1).into();
+        let coeffs = witness
+            .into_coeffs_in_base()
+            .map(|el| Num::allocate(cs, el).into());
 
-        Self::from_coeffs_in_base([c0, c1])
+        Self::from_coeffs_in_base(coeffs)
     }
 
     fn allocate_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
-        let c0 = Num::allocate_without_value(cs).into();
-        let c1 = Num::allocate_without_value(cs).into();
+        let coeffs = std::array::from_fn(|_| Num::allocate_without_value(cs).into());
 
-        Self::from_coeffs_in_base([c0, c1])
+        Self::from_coeffs_in_base(coeffs)
     }
 
     fn placeholder_witness() -> Self::Witness {
-        <ExtensionField<F, 2, EXT> as crate::field::traits::field::Field>::ZERO
+        <ExtensionField<F, DEGREE, EXT> as crate::field::traits::field::Field>::ZERO
     }
 }
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CSO: ConstraintSystem<F> + 'static>
-    WitnessHookable<F> for NumExtAsFieldWrapper<F, EXT, CSO>
+impl<
+        F: SmallField,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
+        CSO: ConstraintSystem<F> + 'static,
+    > WitnessHookable<F> for NumExtAsFieldWrapper<F, DEGREE, EXT, CSO>
 {
     fn witness_hook<CS: ConstraintSystem<F>>(
         &self,
         cs: &CS,
     ) -> Box<dyn FnOnce() -> Option<Self::Witness> + 'static> {
-        let c0_hook = self.c0.witness_hook(cs);
-        let c1_hook = self.c1.witness_hook(cs);
-        Box::new(|| {
-            let c0 = c0_hook()?;
-            let c1 = c1_hook()?;
-
-            Some(ExtensionField::<F, 2, EXT>::from_coeff_in_base([c0, c1]))
+        let hooks = self.coeffs.map(|el| el.witness_hook(cs));
+        Box::new(move || {
+            let mut coeffs = [F::ZERO; DEGREE];
+            for (dst, hook) in coeffs.iter_mut().zip(hooks.into_iter()) {
+                *dst = hook()?;
+            }
+
+            Some(ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(coeffs))
         })
     }
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F> + 'static>
-    NumExtAsFieldWrapper<F, EXT, CS>
+impl<
+        F: SmallField,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
+        CS: ConstraintSystem<F> + 'static,
+    > NumExtAsFieldWrapper<F, DEGREE, EXT, CS>
 {
-    pub const fn from_coeffs_in_base(coeffs: [NumAsFieldWrapper<F, CS>; 2]) -> Self {
-        let [c0, c1] = coeffs;
-
+    pub const fn from_coeffs_in_base(coeffs: [NumAsFieldWrapper<F, CS>; DEGREE]) -> Self {
         Self {
-            c0,
-            c1,
+            coeffs,
             _marker: std::marker::PhantomData,
         }
     }
 
-    pub fn from_num_coeffs_in_base(coeffs: [Num<F>; 2]) -> Self {
-        let [c0, c1] = coeffs;
-
+    pub fn from_num_coeffs_in_base(coeffs: [Num<F>; DEGREE]) -> Self {
         Self {
-            c0: c0.into(),
-            c1: c1.into(),
+            coeffs: coeffs.map(|el| el.into()),
             _marker: std::marker::PhantomData,
         }
     }
 
-    pub const fn into_coeffs_in_base(self) -> [NumAsFieldWrapper<F, CS>; 2] {
-        [self.c0, self.c1]
+    // embeds the base field element, other coefficients are taken from the given zero
+    pub fn from_base(base: NumAsFieldWrapper<F, CS>, zero: NumAsFieldWrapper<F, CS>) -> Self {
+        let mut coeffs = [zero; DEGREE];
+        coeffs[0] = base;
+
+        Self::from_coeffs_in_base(coeffs)
+    }
+
+    pub const fn into_coeffs_in_base(self) -> [NumAsFieldWrapper<F, CS>; DEGREE] {
+        self.coeffs
+    }
+
+    pub fn into_num_coeffs_in_base(self) -> [Num<F>; DEGREE] {
+        self.coeffs.map(|el| el.into_num())
     }
 
-    pub const fn into_num_coeffs_in_base(self) -> [Num<F>; 2] {
-        [self.c0.into_num(), self.c1.into_num()]
+    // FMA gate is only available for quadratic extensions
+    #[inline(always)]
+    fn fma_gate_is_allowed(cs: &CS) -> bool {
+        DEGREE == 2 && cs.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT, DEGREE>>()
+    }
+
+    #[inline(always)]
+    fn into_quadratic_variables(&self) -> [Variable; 2] {
+        [
+            self.coeffs[0].inner.get_variable(),
+            self.coeffs[1].inner.get_variable(),
+        ]
+    }
+
+    #[inline(always)]
+    fn from_quadratic_variables(variables: [Variable; 2]) -> Self {
+        Self::from_num_coeffs_in_base(std::array::from_fn(|i| Num::from_variable(variables[i])))
     }
 
     pub fn mul_assign_by_base(&mut self, base: &NumAsFieldWrapper<F, CS>, cs: &mut CS) {
@@ -285,17 +330,19 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         //     return;
         // }
 
-        self.c0.mul_assign(base, cs);
-        self.c1.mul_assign(base, cs);
+        for el in self.coeffs.iter_mut() {
+            el.mul_assign(base, cs);
+        }
     }
 
     pub fn conditionally_select(cs: &mut CS, flag: Boolean<F>, a: &Self, b: &Self) -> Self {
-        let c0 = NumAsFieldWrapper::conditionally_select(cs, flag, &a.c0, &b.c0);
-        let c1 = NumAsFieldWrapper::conditionally_select(cs, flag, &a.c1, &b.c1);
+        let mut coeffs = a.coeffs;
+        for ((dst, a), b) in coeffs.iter_mut().zip(a.coeffs.iter()).zip(b.coeffs.iter()) {
+            *dst = NumAsFieldWrapper::conditionally_select(cs, flag, a, b);
+        }
 
         Self {
-            c0,
-            c1,
+            coeffs,
             _marker: std::marker::PhantomData,
         }
     }
@@ -306,30 +353,23 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         other: &Self,
         cs: &mut CS,
     ) {
-        if cs.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT>>() {
+        if Self::fma_gate_is_allowed(cs) {
             // we can use FMA gate
-            let a0 = other.c0.inner.get_variable();
-            let a1 = other.c1.inner.get_variable();
-
-            let c0 = dst.c0.inner.get_variable();
-            let c1 = dst.c1.inner.get_variable();
+            let [a0, a1] = other.into_quadratic_variables();
+            let [c0, c1] = dst.into_quadratic_variables();
 
             let zero_var = cs.allocate_constant(F::ZERO);
 
-            let [c0, c1] = FmaGateInExtensionWithoutConstant::<F, EXT>::compute_fma_in_extension(
-                cs,
-                ExtensionField::<F, 2, EXT>::ONE,
-                ([a0, a1], [base.inner.get_variable(), zero_var]),
-                ExtensionField::<F, 2, EXT>::ONE,
-                [c0, c1],
-            );
-
-            let c0 = Num::from_variable(c0);
-            let c1 = Num::from_variable(c1);
-
-            let result = Self::from_num_coeffs_in_base([c0, c1]);
+            let result =
+                FmaGateInExtensionWithoutConstant::<F, EXT, DEGREE>::compute_fma_in_extension(
+                    cs,
+                    ExtensionField::<F, DEGREE, EXT>::ONE,
+                    ([a0, a1], [base.inner.get_variable(), zero_var]),
+                    ExtensionField::<F, DEGREE, EXT>::ONE,
+                    [c0, c1],
+                );
 
-            *dst = result;
+            *dst = Self::from_quadratic_variables(result);
 
             return;
         } else {
@@ -342,8 +382,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
     }
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F>> PrimeFieldLike
-    for NumExtAsFieldWrapper<F, EXT, CS>
+impl<
+        F: SmallField,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
+        CS: ConstraintSystem<F>,
+    > PrimeFieldLike for NumExtAsFieldWrapper<F, DEGREE, EXT, CS>
 where
     CS: 'static,
 {
@@ -354,173 +398,215 @@ where
     fn zero(ctx: &mut Self::Context) -> Self {
         let zero = Num::allocated_constant(ctx, F::ZERO).into();
         Self {
-            c0: zero,
-            c1: zero,
+            coeffs: [zero; DEGREE],
             _marker: std::marker::PhantomData,
         }
     }
     fn one(ctx: &mut Self::Context) -> Self {
-        let zero = Num::allocated_constant(ctx, F::ZERO).into();
-        let one = Num::allocated_constant(ctx, F::ONE).into();
-        Self {
-            c0: one,
-            c1: zero,
-            _marker: std::marker::PhantomData,
-        }
+        Self::constant(F::ONE, ctx)
     }
     fn minus_one(ctx: &mut Self::Context) -> Self {
-        let zero = Num::allocated_constant(ctx, F::ZERO).into();
-        let minus_one = Num::allocated_constant(ctx, F::MINUS_ONE).into();
-        Self {
-            c0: minus_one,
-            c1: zero,
-            _marker: std::marker::PhantomData,
-        }
+        Self::constant(F::MINUS_ONE, ctx)
     }
     // Arithmetics. Expressed in mutable way. It would not matter in after inlining
     fn add_assign(&'_ mut self, other: &Self, ctx: &mut Self::Context) -> &'_ mut Self {
-        self.c0.add_assign(&other.c0, ctx);
-        self.c1.add_assign(&other.c1, ctx);
+        for (a, b) in self.coeffs.iter_mut().zip(other.coeffs.iter()) {
+            a.add_assign(b, ctx);
+        }
 
         self
     }
     fn sub_assign(&'_ mut self, other: &Self, ctx: &mut Self::Context) -> &'_ mut Self {
-        self.c0.sub_assign(&other.c0, ctx);
-        self.c1.sub_assign(&other.c1, ctx);
+        for (a, b) in self.coeffs.iter_mut().zip(other.coeffs.iter()) {
+            a.sub_assign(b, ctx);
+        }
 
         self
     }
     fn mul_assign(&'_ mut self, other: &Self, ctx: &mut Self::Context) -> &'_ mut Self {
-        if ctx.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT>>() {
+        if Self::fma_gate_is_allowed(ctx) {
             // we can use FMA
-            let a0 = self.c0.inner.get_variable();
-            let a1 = self.c1.inner.get_variable();
+            let [a0, a1] = self.into_quadratic_variables();
+            let [b0, b1] = other.into_quadratic_variables();
+
+            let result =
+                FmaGateInExtensionWithoutConstant::<F, EXT, DEGREE>::compute_fma_in_extension(
+                    ctx,
+                    ExtensionField::<F, DEGREE, EXT>::ONE,
+                    ([a0, a1], [b0, b1]),
+                    ExtensionField::<F, DEGREE, EXT>::ZERO,
+                    [a0, a1],
+                );
 
-            let b0 = other.c0.inner.get_variable();
-            let b1 = other.c1.inner.get_variable();
+            *self = Self::from_quadratic_variables(result);
 
-            let [c0, c1] = FmaGateInExtensionWithoutConstant::<F, EXT>::compute_fma_in_extension(
-                ctx,
-                ExtensionField::<F, 2, EXT>::ONE,
-                ([a0, a1], [b0, b1]),
-                ExtensionField::<F, 2, EXT>::ZERO,
-                [a0, a1],
-            );
+            return self;
+        }
+
+        let non_residue = NumAsFieldWrapper::constant(EXT::non_residue(), ctx);
 
-            let c0 = Num::from_variable(c0);
-            let c1 = Num::from_variable(c1);
+        if DEGREE == 2 {
+            let mut v0 = self.coeffs[0];
+            v0.mul_assign(&other.coeffs[0], ctx);
+            let mut v1 = self.coeffs[1];
+            v1.mul_assign(&other.coeffs[1], ctx);
 
-            let result = Self::from_num_coeffs_in_base([c0, c1]);
+            let t = self.coeffs[0];
+            self.coeffs[1].add_assign(&t, ctx);
 
-            *self = result;
+            let mut t0 = other.coeffs[0];
+            t0.add_assign(&other.coeffs[1], ctx);
+            self.coeffs[1].mul_assign(&t0, ctx);
+            self.coeffs[1].sub_assign(&v0, ctx);
+            self.coeffs[1].sub_assign(&v1, ctx);
+            self.coeffs[0] = v0;
+            v1.mul_assign(&non_residue, ctx);
+            self.coeffs[0].add_assign(&v1, ctx);
 
             return self;
         }
 
-        let mut v0 = self.c0;
-        v0.mul_assign(&other.c0, ctx);
-        let mut v1 = self.c1;
-        v1.mul_assign(&other.c1, ctx);
-
-        let t = self.c0;
-        self.c1.add_assign(&t, ctx);
-
-        let mut t0 = other.c0;
-        t0.add_assign(&other.c1, ctx);
-        self.c1.mul_assign(&t0, ctx);
-        self.c1.sub_assign(&v0, ctx);
-        self.c1.sub_assign(&v1, ctx);
-        self.c0 = v0;
-        let non_residue = NumAsFieldWrapper::constant(EXT::non_residue(), ctx);
-        v1.mul_assign(&non_residue, ctx);
-        self.c0.add_assign(&v1, ctx);
+        // schoolbook, and x^DEGREE = non_residue for higher terms
+        let zero = NumAsFieldWrapper::zero(ctx);
+        let mut low = [zero; DEGREE];
+        let mut high = [zero; DEGREE];
+        for i in 0..DEGREE {
+            for j in 0..DEGREE {
+                let dst = if i + j < DEGREE {
+                    &mut low[i + j]
+                } else {
+                    &mut high[i + j - DEGREE]
+                };
+                NumAsFieldWrapper::mul_and_accumulate_into(
+                    dst,
+                    &self.coeffs[i],
+                    &other.coeffs[j],
+                    ctx,
+                );
+            }
+        }
+        for (dst, src) in low.iter_mut().zip(high[..DEGREE - 1].iter()) {
+            NumAsFieldWrapper::mul_and_accumulate_into(dst, src, &non_residue, ctx);
+        }
+        self.coeffs = low;
 
         self
     }
     fn square(&'_ mut self, ctx: &mut Self::Context) -> &'_ mut Self {
-        if ctx.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT>>() {
+        if Self::fma_gate_is_allowed(ctx) {
             // we can use FMA
-            let c0 = self.c0.inner.get_variable();
-            let c1 = self.c1.inner.get_variable();
+            let [c0, c1] = self.into_quadratic_variables();
 
-            let [c0, c1] = FmaGateInExtensionWithoutConstant::<F, EXT>::compute_fma_in_extension(
-                ctx,
-                ExtensionField::<F, 2, EXT>::ONE,
-                ([c0, c1], [c0, c1]),
-                ExtensionField::<F, 2, EXT>::ZERO,
-                [c0, c1],
-            );
+            let result =
+                FmaGateInExtensionWithoutConstant::<F, EXT, DEGREE>::compute_fma_in_extension(
+                    ctx,
+                    ExtensionField::<F, DEGREE, EXT>::ONE,
+                    ([c0, c1], [c0, c1]),
+                    ExtensionField::<F, DEGREE, EXT>::ZERO,
+                    [c0, c1],
+                );
 
-            let c0 = Num::from_variable(c0);
-            let c1 = Num::from_variable(c1);
+            *self = Self::from_quadratic_variables(result);
 
-            let result = Self::from_num_coeffs_in_base([c0, c1]);
+            return self;
+        }
 
-            *self = result;
+        if DEGREE != 2 {
+            let this = *self;
 
-            return self;
+            return self.mul_assign(&this, ctx);
         }
 
-        let mut v0 = self.c0;
-        v0.sub_assign(&self.c1, ctx);
-        let mut v3 = self.c0;
-        let mut t0 = self.c1;
+        let mut v0 = self.coeffs[0];
+        v0.sub_assign(&self.coeffs[1], ctx);
+        let mut v3 = self.coeffs[0];
+        let mut t0 = self.coeffs[1];
         let non_residue = NumAsFieldWrapper::constant(EXT::non_residue(), ctx);
         t0.mul_assign(&non_residue, ctx);
         v3.sub_assign(&t0, ctx);
-        let mut v2 = self.c0;
-        v2.mul_assign(&self.c1, ctx);
+        let mut v2 = self.coeffs[0];
+        v2.mul_assign(&self.coeffs[1], ctx);
         v0.mul_assign(&v3, ctx);
         v0.add_assign(&v2, ctx);
 
-        self.c1 = v2;
-        self.c1.double(ctx);
-        self.c0 = v0;
+        self.coeffs[1] = v2;
+        self.coeffs[1].double(ctx);
+        self.coeffs[0] = v0;
 
         v2.mul_assign(&non_residue, ctx);
-        self.c0.add_assign(&v2, ctx);
+        self.coeffs[0].add_assign(&v2, ctx);
 
         self
     }
     fn negate(&'_ mut self, ctx: &mut Self::Context) -> &'_ mut Self {
-        self.c0.negate(ctx);
-        self.c1.negate(ctx);
+        for el in self.coeffs.iter_mut() {
+            el.negate(ctx);
+        }
 
         self
     }
     fn double(&'_ mut self, ctx: &mut Self::Context) -> &'_ mut Self {
-        self.c0.double(ctx);
-        self.c1.double(ctx);
+        for el in self.coeffs.iter_mut() {
+            el.double(ctx);
+        }
 
         self
     }
     // infallible inverse
     fn inverse(&self, ctx: &mut Self::Context) -> Self {
-        if ctx.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT>>() {
+        if Self::fma_gate_is_allowed(ctx) {
             // we can use FMA
-            let c0 = self.c0.inner.get_variable();
-            let c1 = self.c1.inner.get_variable();
+            let [c0, c1] = self.into_quadratic_variables();
             let zero_var = ctx.allocate_constant(F::ZERO);
             let one_var = ctx.allocate_constant(F::ONE);
 
-            let [c0, c1] = FmaGateInExtensionWithoutConstant::<F, EXT>::create_inversion_constraint(
-                ctx,
-                [c0, c1],
-                zero_var,
-                one_var,
-            );
-
-            let c0 = Num::from_variable(c0);
-            let c1 = Num::from_variable(c1);
+            let result =
+                FmaGateInExtensionWithoutConstant::<F, EXT, DEGREE>::create_inversion_constraint(
+                    ctx,
+                    [c0, c1],
+                    zero_var,
+                    one_var,
+                );
 
-            let result = Self::from_num_coeffs_in_base([c0, c1]);
+            return Self::from_quadratic_variables(result);
+        }
 
-            return result;
+        if DEGREE != 2 {
+            // allocate the inverse as a witness, and check that the product is one
+            let output_variables = ctx.alloc_multiple_variables_without_values::<DEGREE>();
+
+            if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
+                let value_fn = move |inputs: [F; DEGREE]| {
+                    let value = ExtensionField::<F, DEGREE, EXT>::from_coeff_in_base(inputs);
+                    let inverse = crate::field::PrimeField::inverse(&value).unwrap();
+
+                    inverse.into_coeffs_in_base()
+                };
+
+                let dependencies =
+                    Place::from_variables(self.coeffs.map(|el| el.inner.get_variable()));
+
+                ctx.set_values_with_dependencies(
+                    &dependencies,
+                    &Place::from_variables(output_variables),
+                    value_fn,
+                );
+            }
+
+            let inverse = Self::from_num_coeffs_in_base(output_variables.map(Num::from_variable));
+            let mut product = *self;
+            product.mul_assign(&inverse, ctx);
+            let one = Self::one(ctx);
+            for (a, b) in product.coeffs.iter().zip(one.coeffs.iter()) {
+                Num::enforce_equal(ctx, &a.inner, &b.inner);
+            }
+
+            return inverse;
         }
 
-        let mut v0 = self.c0;
+        let mut v0 = self.coeffs[0];
         v0.square(ctx);
-        let mut v1 = self.c1;
+        let mut v1 = self.coeffs[1];
         v1.square(ctx);
         // v0 = v0 - beta * v1
         let mut v1_by_nonresidue = v1;
@@ -528,15 +614,13 @@ where
         v1_by_nonresidue.mul_assign(&non_residue, ctx);
         v0.sub_assign(&v1_by_nonresidue, ctx);
         let inversed = v0.inverse(ctx);
-        let mut c0 = self.c0;
-        c0.mul_assign(&inversed, ctx);
-        let mut c1 = self.c1;
-        c1.mul_assign(&inversed, ctx);
-        c1.negate(ctx);
+        let mut coeffs = self.coeffs;
+        coeffs[0].mul_assign(&inversed, ctx);
+        coeffs[1].mul_assign(&inversed, ctx);
+        coeffs[1].negate(ctx);
 
         let new = Self {
-            c0,
-            c1,
+            coeffs,
             _marker: std::marker::PhantomData,
         };
 
@@ -546,37 +630,32 @@ where
     fn constant(value: Self::Base, ctx: &mut Self::Context) -> Self {
         let zero = Num::allocated_constant(ctx, F::ZERO).into();
         let constant = Num::allocated_constant(ctx, value).into();
+        let mut coeffs = [zero; DEGREE];
+        coeffs[0] = constant;
+
         Self {
-            c0: constant,
-            c1: zero,
+            coeffs,
             _marker: std::marker::PhantomData,
         }
     }
 
     fn mul_and_accumulate_into(acc: &mut Self, a: &Self, b: &Self, ctx: &mut Self::Context) {
-        if ctx.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT>>() {
+        if Self::fma_gate_is_allowed(ctx) {
             // we can use FMA
-            let a_c0 = a.c0.inner.get_variable();
-            let a_c1 = a.c1.inner.get_variable();
-            let b_c0 = b.c0.inner.get_variable();
-            let b_c1 = b.c1.inner.get_variable();
-            let acc_c0 = acc.c0.inner.get_variable();
-            let acc_c1 = acc.c1.inner.get_variable();
-
-            let [c0, c1] = FmaGateInExtensionWithoutConstant::<F, EXT>::compute_fma_in_extension(
-                ctx,
-                ExtensionField::<F, 2, EXT>::ONE,
-                ([a_c0, a_c1], [b_c0, b_c1]),
-                ExtensionField::<F, 2, EXT>::ONE,
-                [acc_c0, acc_c1],
-            );
-
-            let c0 = Num::from_variable(c0);
-            let c1 = Num::from_variable(c1);
-
-            let result = Self::from_num_coeffs_in_base([c0, c1]);
-
-            *acc = result;
+            let a_vars = a.into_quadratic_variables();
+            let b_vars = b.into_quadratic_variables();
+            let acc_vars = acc.into_quadratic_variables();
+
+            let result =
+                FmaGateInExtensionWithoutConstant::<F, EXT, DEGREE>::compute_fma_in_extension(
+                    ctx,
+                    ExtensionField::<F, DEGREE, EXT>::ONE,
+                    (a_vars, b_vars),
+                    ExtensionField::<F, DEGREE, EXT>::ONE,
+                    acc_vars,
+                );
+
+            *acc = Self::from_quadratic_variables(result);
         } else {
             // degrade to default implementation
             let mut tmp = *a;
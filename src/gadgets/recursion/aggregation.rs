@@ -0,0 +1,722 @@
+use super::*;
+use crate::algebraic_props::round_function::AbsorptionModeOverwrite;
+use crate::algebraic_props::sponge::GoldilocksPoseidon2Sponge;
+use crate::config::DevCSConfig;
+use crate::cs::cs_builder::*;
+use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+use crate::cs::gates::*;
+use crate::cs::implementations::proof::Proof;
+use crate::cs::implementations::prover::ProofConfig;
+use crate::cs::implementations::verifier::VerificationKey;
+use crate::cs::oracle::TreeHasher;
+use crate::cs::traits::circuit::{
+    CircuitBuilder, CircuitBuilderProxy, ErasedBuilderForRecursiveVerifier,
+};
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::cs::traits::gate::GatePlacementStrategy;
+use crate::cs::{CSGeometry, GateConfigurationHolder, LookupParameters, StaticToolboxHolder};
+use crate::field::goldilocks::{GoldilocksExt2, GoldilocksField};
+use crate::field::U64Representable;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::num::Num;
+use crate::gadgets::recursion::allocated_proof::AllocatedProof;
+use crate::gadgets::recursion::allocated_vk::AllocatedVerificationKey;
+use crate::gadgets::recursion::circuit_pow::RecursivePoWRunner;
+use crate::gadgets::recursion::recursive_transcript::{CircuitTranscript, RecursiveTranscript};
+use crate::gadgets::recursion::recursive_tree_hasher::{
+    CircuitGoldilocksPoseidon2Sponge, CircuitTreeHasher, RecursiveTreeHasher,
+};
+use crate::gadgets::traits::allocatable::CSAllocatable;
+use crate::gadgets::traits::witnessable::WitnessHookable;
+use crate::implementations::poseidon2::Poseidon2Goldilocks;
+use crate::worker::Worker;
+
+// Aggregation of many proofs of the same circuit into a single one. Every node circuit verifies
+// `branching_factor` proofs of the previous level and exposes a commitment to the public
+// inputs of its children as its own public input:
+// - leaf commitment is a hash of all the public inputs of the leaf proof
+// - node commitment is a hash of concatenated commitments of its children
+// Both hashes start from the domain separator, so a leaf can not pretend to be a node, whose
+// children commitments are its public inputs.
+// If number of proofs at some level is not divisible by the branching factor, then the last
+// proof is repeated
+
+type F = GoldilocksField;
+type EXT = GoldilocksExt2;
+
+pub const AGGREGATION_COMMITMENT_WIDTH: usize = 4;
+
+pub type AggregationCommitment = [F; AGGREGATION_COMMITMENT_WIDTH];
+
+pub const LEAF_COMMITMENT_DOMAIN_SEPARATOR: u64 = 0;
+pub const NODE_COMMITMENT_DOMAIN_SEPARATOR: u64 = 1;
+
+pub fn leaf_commitment(public_inputs: &[F]) -> AggregationCommitment {
+    <GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite> as TreeHasher<F>>::hash_into_leaf_owned(
+        std::iter::once(F::from_u64_unchecked(LEAF_COMMITMENT_DOMAIN_SEPARATOR))
+            .chain(public_inputs.iter().copied()),
+    )
+}
+
+pub fn node_commitment(children: &[AggregationCommitment]) -> AggregationCommitment {
+    <GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite> as TreeHasher<F>>::hash_into_leaf_owned(
+        std::iter::once(F::from_u64_unchecked(NODE_COMMITMENT_DOMAIN_SEPARATOR))
+            .chain(children.iter().flatten().copied()),
+    )
+}
+
+// Compact witness that some leaf was aggregated into the root. For every level we keep
+// commitments of all the children of the corresponding node, including our own one
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone, Debug, PartialEq, Eq)]
+pub struct AggregationInclusionWitness {
+    pub leaf_index: usize,
+    pub children_per_level: Vec<Vec<AggregationCommitment>>,
+}
+
+impl AggregationInclusionWitness {
+    // Returns `None` if the witness is not consistent with the leaf public inputs, or if it doesn't
+    // have exactly one level per node circuit, i.e. `num_node_levels` is `AggregationResult::node_vks.len()`
+    pub fn compute_root_commitment(
+        &self,
+        leaf_public_inputs: &[F],
+        num_node_levels: usize,
+    ) -> Option<AggregationCommitment> {
+        if self.children_per_level.len() != num_node_levels {
+            return None;
+        }
+
+        let mut current = leaf_commitment(leaf_public_inputs);
+        let mut index = self.leaf_index;
+        for children in self.children_per_level.iter() {
+            if children.is_empty() || children.get(index % children.len()) != Some(&current) {
+                return None;
+            }
+            index /= children.len();
+            current = node_commitment(children);
+        }
+
+        if index != 0 {
+            return None;
+        }
+
+        Some(current)
+    }
+
+    pub fn verify(
+        &self,
+        leaf_public_inputs: &[F],
+        root_public_inputs: &[F],
+        num_node_levels: usize,
+    ) -> bool {
+        match self.compute_root_commitment(leaf_public_inputs, num_node_levels) {
+            Some(root) => &root[..] == root_public_inputs,
+            None => false,
+        }
+    }
+}
+
+fn pad_to_multiple<T: Clone>(elements: &mut Vec<T>, branching_factor: usize) {
+    let last = elements
+        .last()
+        .expect("must have at least one element")
+        .clone();
+    while elements.len() % branching_factor != 0 {
+        elements.push(last.clone());
+    }
+}
+
+// Returns commitments for every level, padded to be divisible by the branching factor,
+// starting from the leafs, and the root commitment
+pub fn compute_aggregation_tree(
+    leaf_commitments: &[AggregationCommitment],
+    branching_factor: usize,
+) -> (Vec<Vec<AggregationCommitment>>, AggregationCommitment) {
+    assert!(branching_factor > 1);
+    assert!(leaf_commitments.len() > 0);
+
+    let mut levels = vec![];
+    let mut current = leaf_commitments.to_vec();
+    loop {
+        pad_to_multiple(&mut current, branching_factor);
+        let next: Vec<_> = current
+            .chunks(branching_factor)
+            .map(|el| node_commitment(el))
+            .collect();
+        levels.push(current);
+        current = next;
+        if current.len() == 1 {
+            break;
+        }
+    }
+
+    (levels, current[0])
+}
+
+pub fn compute_inclusion_witnesses(
+    levels: &[Vec<AggregationCommitment>],
+    num_leafs: usize,
+    branching_factor: usize,
+) -> Vec<AggregationInclusionWitness> {
+    (0..num_leafs)
+        .map(|leaf_index| {
+            let mut index = leaf_index;
+            let children_per_level = levels
+                .iter()
+                .map(|level| {
+                    let start = index - index % branching_factor;
+                    index /= branching_factor;
+
+                    level[start..(start + branching_factor)].to_vec()
+                })
+                .collect();
+
+            AggregationInclusionWitness {
+                leaf_index,
+                children_per_level,
+            }
+        })
+        .collect()
+}
+
+// Node circuit that is sufficient to verify proofs made with algebraic hashes
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug, Default)]
+pub struct RecursionNodeCircuitBuilder;
+
+impl CircuitBuilder<F> for RecursionNodeCircuitBuilder {
+    fn geometry() -> CSGeometry {
+        CSGeometry {
+            num_columns_under_copy_permutation: 132,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 8,
+        }
+    }
+
+    fn lookup_parameters() -> LookupParameters {
+        LookupParameters::NoLookup
+    }
+
+    fn configure_builder<
+        T: CsBuilderImpl<F, T>,
+        GC: GateConfigurationHolder<F>,
+        TB: StaticToolboxHolder,
+    >(
+        builder: CsBuilder<T, F, GC, TB>,
+    ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+        type Poseidon2Gate = Poseidon2FlattenedGate<F, 8, 12, 4, Poseidon2Goldilocks>;
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = Poseidon2Gate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder = PublicInputGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        builder
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct AggregationParameters {
+    pub branching_factor: usize,
+    pub node_proof_config: ProofConfig,
+    pub max_variables: usize,
+    pub max_trace_len: usize,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct AggregationResult<H: TreeHasher<F>> {
+    pub root_proof: Proof<F, H, 2, EXT>,
+    // verification keys of node circuits, starting from the one that verifies leaf proofs
+    pub node_vks: Vec<VerificationKey<F, H>>,
+    pub root_commitment: AggregationCommitment,
+    pub inclusion_witnesses: Vec<AggregationInclusionWitness>,
+}
+
+impl<H: TreeHasher<F>> AggregationResult<H> {
+    pub fn root_vk(&self) -> &VerificationKey<F, H> {
+        self.node_vks
+            .last()
+            .expect("at least one node level is always present")
+    }
+}
+
+// Verifies all the child proofs against the same verification key, and exposes commitment
+// to their public inputs. At the leaf level we hash full public inputs, and at the next
+// levels public inputs of children are commitments themselves
+fn synthesize_node<
+    CS: ConstraintSystem<F> + 'static,
+    RH: RecursiveTreeHasher<F, Num<F>>,
+    TR: RecursiveTranscript<
+        F,
+        CompatibleCap = <RH::NonCircuitSimulator as TreeHasher<F>>::Output,
+        CircuitReflection = CTR,
+    >,
+    CTR: CircuitTranscript<
+        F,
+        CircuitCompatibleCap = <RH as CircuitTreeHasher<F, Num<F>>>::CircuitOutput,
+        TransciptParameters = TR::TransciptParameters,
+    >,
+    POW: RecursivePoWRunner<F>,
+>(
+    cs: &mut CS,
+    child_builder: &dyn ErasedBuilderForRecursiveVerifier<F, 2, EXT, CS>,
+    child_vk: &VerificationKey<F, RH::NonCircuitSimulator>,
+    child_proofs: &[Proof<F, RH::NonCircuitSimulator, 2, EXT>],
+    is_leaf_level: bool,
+    transcript_params: TR::TransciptParameters,
+) {
+    let verifier = child_builder.create_recursive_verifier(cs);
+    let allocated_vk = AllocatedVerificationKey::<F, RH>::allocate_constant(cs, child_vk.clone());
+    let boolean_true = Boolean::allocated_constant(cs, true);
+    let leaf_domain_separator =
+        Num::allocated_constant(cs, F::from_u64_unchecked(LEAF_COMMITMENT_DOMAIN_SEPARATOR));
+    let node_domain_separator =
+        Num::allocated_constant(cs, F::from_u64_unchecked(NODE_COMMITMENT_DOMAIN_SEPARATOR));
+
+    let mut children_commitments = Vec::with_capacity(child_proofs.len());
+    for proof in child_proofs.iter() {
+        let allocated_proof = AllocatedProof::<F, RH, 2, EXT>::allocate_from_witness(
+            cs,
+            Some(proof.clone()),
+            &verifier,
+            &child_vk.fixed_parameters,
+            &proof.proof_config,
+        );
+
+        let (is_valid, public_inputs) = verifier.verify::<RH, TR, CTR, POW>(
+            cs,
+            transcript_params.clone(),
+            &allocated_proof,
+            &child_vk.fixed_parameters,
+            &proof.proof_config,
+            &allocated_vk,
+        );
+        if let Some(is_valid) = is_valid.witness_hook(&*cs)() {
+            assert!(is_valid, "child proof must be valid");
+        }
+        Boolean::enforce_equal(cs, &is_valid, &boolean_true);
+
+        if is_leaf_level {
+            let commitment =
+                <CircuitGoldilocksPoseidon2Sponge as CircuitTreeHasher<F, Num<F>>>::hash_into_leaf(
+                    cs,
+                    std::iter::once(&leaf_domain_separator).chain(public_inputs.iter()),
+                );
+            children_commitments.extend(commitment);
+        } else {
+            assert_eq!(public_inputs.len(), AGGREGATION_COMMITMENT_WIDTH);
+            children_commitments.extend(public_inputs);
+        }
+    }
+
+    let commitment =
+        <CircuitGoldilocksPoseidon2Sponge as CircuitTreeHasher<F, Num<F>>>::hash_into_leaf(
+            cs,
+            std::iter::once(&node_domain_separator).chain(children_commitments.iter()),
+        );
+    for el in commitment.into_iter() {
+        let gate = PublicInputGate::new(el.get_variable());
+        gate.add_to_cs(cs);
+    }
+}
+
+fn prove_node<
+    NB: CircuitBuilder<F> + 'static,
+    RH: RecursiveTreeHasher<F, Num<F>>,
+    TR: RecursiveTranscript<
+        F,
+        CompatibleCap = <RH::NonCircuitSimulator as TreeHasher<F>>::Output,
+        CircuitReflection = CTR,
+    >,
+    CTR: CircuitTranscript<
+        F,
+        CircuitCompatibleCap = <RH as CircuitTreeHasher<F, Num<F>>>::CircuitOutput,
+        TransciptParameters = TR::TransciptParameters,
+    >,
+    POW: RecursivePoWRunner<F>,
+    CB: CircuitBuilder<F> + 'static,
+>(
+    worker: &Worker,
+    params: &AggregationParameters,
+    child_vk: &VerificationKey<F, RH::NonCircuitSimulator>,
+    child_proofs: &[Proof<F, RH::NonCircuitSimulator, 2, EXT>],
+    is_leaf_level: bool,
+    transcript_params: TR::TransciptParameters,
+) -> (
+    Proof<F, RH::NonCircuitSimulator, 2, EXT>,
+    VerificationKey<F, RH::NonCircuitSimulator>,
+) {
+    let builder_impl = CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(
+        NB::geometry(),
+        params.max_variables,
+        params.max_trace_len,
+    );
+    let builder = new_builder::<_, F>(builder_impl);
+    let builder = NB::configure_builder(builder);
+    let mut cs = builder.build(());
+
+    let child_builder = CircuitBuilderProxy::<F, CB>::dyn_recursive_verifier_builder::<2, EXT, _>();
+    synthesize_node::<_, RH, TR, CTR, POW>(
+        &mut cs,
+        child_builder.as_ref(),
+        child_vk,
+        child_proofs,
+        is_leaf_level,
+        transcript_params.clone(),
+    );
+
+    cs.pad_and_shrink();
+    let cs = cs.into_assembly();
+
+    cs.prove_one_shot::<2, EXT, TR, RH::NonCircuitSimulator, POW>(
+        worker,
+        params.node_proof_config.clone(),
+        transcript_params,
+    )
+}
+
+// Aggregates proofs of the circuit `LB` by node circuits `NB` until a single proof is left.
+// All the leaf proofs must share the same verification key
+pub fn aggregate_proofs<
+    LB: CircuitBuilder<F> + 'static,
+    NB: CircuitBuilder<F> + 'static,
+    RH: RecursiveTreeHasher<F, Num<F>>,
+    TR: RecursiveTranscript<
+        F,
+        CompatibleCap = <RH::NonCircuitSimulator as TreeHasher<F>>::Output,
+        CircuitReflection = CTR,
+    >,
+    CTR: CircuitTranscript<
+        F,
+        CircuitCompatibleCap = <RH as CircuitTreeHasher<F, Num<F>>>::CircuitOutput,
+        TransciptParameters = TR::TransciptParameters,
+    >,
+    POW: RecursivePoWRunner<F>,
+>(
+    worker: &Worker,
+    leafs: Vec<(
+        VerificationKey<F, RH::NonCircuitSimulator>,
+        Proof<F, RH::NonCircuitSimulator, 2, EXT>,
+    )>,
+    params: &AggregationParameters,
+    transcript_params: TR::TransciptParameters,
+) -> AggregationResult<RH::NonCircuitSimulator> {
+    assert!(params.branching_factor > 1);
+    assert!(leafs.len() > 0, "nothing to aggregate");
+
+    let leaf_vk = leafs[0].0.clone();
+    for (vk, _) in leafs.iter() {
+        assert!(
+            vk == &leaf_vk,
+            "all leaf proofs must have the same verification key"
+        );
+    }
+
+    let leaf_commitments: Vec<_> = leafs
+        .iter()
+        .map(|(_, proof)| leaf_commitment(&proof.public_inputs))
+        .collect();
+    let (levels, root_commitment) =
+        compute_aggregation_tree(&leaf_commitments, params.branching_factor);
+    let inclusion_witnesses =
+        compute_inclusion_witnesses(&levels, leafs.len(), params.branching_factor);
+
+    let mut child_vk = leaf_vk;
+    let mut proofs: Vec<_> = leafs.into_iter().map(|(_, proof)| proof).collect();
+    let mut node_vks = vec![];
+
+    for (level_idx, level) in levels.iter().enumerate() {
+        pad_to_multiple(&mut proofs, params.branching_factor);
+        assert_eq!(proofs.len(), level.len());
+
+        let mut next_proofs = Vec::with_capacity(proofs.len() / params.branching_factor);
+        let mut level_vk = None;
+        for (child_proofs, children) in proofs
+            .chunks(params.branching_factor)
+            .zip(level.chunks(params.branching_factor))
+        {
+            let (proof, vk) = if level_idx == 0 {
+                prove_node::<NB, RH, TR, CTR, POW, LB>(
+                    worker,
+                    params,
+                    &child_vk,
+                    child_proofs,
+                    true,
+                    transcript_params.clone(),
+                )
+            } else {
+                prove_node::<NB, RH, TR, CTR, POW, NB>(
+                    worker,
+                    params,
+                    &child_vk,
+                    child_proofs,
+                    false,
+                    transcript_params.clone(),
+                )
+            };
+
+            assert_eq!(proof.public_inputs, node_commitment(children).to_vec());
+            if let Some(level_vk) = level_vk.as_ref() {
+                assert!(
+                    &vk == level_vk,
+                    "all nodes of the same level must have the same verification key"
+                );
+            } else {
+                level_vk = Some(vk);
+            }
+            next_proofs.push(proof);
+        }
+
+        child_vk = level_vk.expect("level is not empty");
+        node_vks.push(child_vk.clone());
+        proofs = next_proofs;
+    }
+
+    assert_eq!(proofs.len(), 1);
+
+    AggregationResult {
+        root_proof: proofs.pop().unwrap(),
+        node_vks,
+        root_commitment,
+        inclusion_witnesses,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cs::implementations::pow::NoPow;
+    use crate::cs::implementations::transcript::GoldilocksPoisedon2Transcript;
+    use crate::field::{Field, U64Representable};
+    use crate::gadgets::recursion::recursive_transcript::GoldilocksPoisedon2CircuitTranscript;
+
+    #[test]
+    fn test_inclusion_witnesses() {
+        let leafs: Vec<Vec<F>> = (0..5u64)
+            .map(|el| vec![F::from_u64_unchecked(el), F::from_u64_unchecked(el * el)])
+            .collect();
+        let leaf_commitments: Vec<_> = leafs.iter().map(|el| leaf_commitment(el)).collect();
+
+        for branching_factor in [2, 3, 8] {
+            let (levels, root) = compute_aggregation_tree(&leaf_commitments, branching_factor);
+            let witnesses = compute_inclusion_witnesses(&levels, leafs.len(), branching_factor);
+
+            let depth = levels.len();
+            for (leaf, witness) in leafs.iter().zip(witnesses.iter()) {
+                assert!(witness.verify(leaf, &root, depth));
+            }
+
+            // wrong leaf
+            assert!(!witnesses[0].verify(&leafs[1], &root, depth));
+            // wrong position
+            let mut witness = witnesses[0].clone();
+            witness.leaf_index = 1;
+            assert!(!witness.verify(&leafs[0], &root, depth));
+            // wrong depth
+            assert!(!witnesses[0].verify(&leafs[0], &root, depth + 1));
+        }
+    }
+
+    #[test]
+    fn test_node_can_not_be_claimed_as_leaf() {
+        let leafs: Vec<Vec<F>> = (0..4u64)
+            .map(|el| vec![F::from_u64_unchecked(el), F::from_u64_unchecked(el * el)])
+            .collect();
+        let leaf_commitments: Vec<_> = leafs.iter().map(|el| leaf_commitment(el)).collect();
+        let branching_factor = 2;
+        let (levels, root) = compute_aggregation_tree(&leaf_commitments, branching_factor);
+        let witnesses = compute_inclusion_witnesses(&levels, leafs.len(), branching_factor);
+        let depth = levels.len();
+        assert_eq!(depth, 2);
+
+        // claim that concatenation of the first two leaf commitments are public inputs of some leaf,
+        // and that it's aggregated right below the root
+        let children = &witnesses[0].children_per_level[0];
+        let forged_public_inputs: Vec<F> = children.iter().flatten().copied().collect();
+        let forged_witness = AggregationInclusionWitness {
+            leaf_index: 0,
+            children_per_level: witnesses[0].children_per_level[1..].to_vec(),
+        };
+
+        assert_ne!(
+            leaf_commitment(&forged_public_inputs),
+            node_commitment(children)
+        );
+        assert!(!forged_witness.verify(&forged_public_inputs, &root, depth));
+        assert!(!forged_witness.verify(&forged_public_inputs, &root, depth - 1));
+    }
+
+    struct LeafCircuitBuilder;
+
+    impl CircuitBuilder<F> for LeafCircuitBuilder {
+        fn geometry() -> CSGeometry {
+            CSGeometry {
+                num_columns_under_copy_permutation: 8,
+                num_witness_columns: 0,
+                num_constant_columns: 2,
+                max_allowed_constraint_degree: 8,
+            }
+        }
+
+        fn lookup_parameters() -> LookupParameters {
+            LookupParameters::NoLookup
+        }
+
+        fn configure_builder<
+            T: CsBuilderImpl<F, T>,
+            GC: GateConfigurationHolder<F>,
+            TB: StaticToolboxHolder,
+        >(
+            builder: CsBuilder<T, F, GC, TB>,
+        ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+            let builder = ConstantsAllocatorGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = PublicInputGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = NopGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+
+            builder
+        }
+    }
+
+    #[test]
+    #[ignore = "proves several recursive node circuits"]
+    fn test_aggregate_proofs() {
+        type TR = GoldilocksPoisedon2Transcript;
+        type CTR = GoldilocksPoisedon2CircuitTranscript;
+        type RH = CircuitGoldilocksPoseidon2Sponge;
+        type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+        let worker = Worker::new();
+
+        let mut proof_config = ProofConfig::default();
+        proof_config.fri_lde_factor = 8;
+        proof_config.security_level = 80;
+        proof_config.pow_bits = 0;
+
+        let mut leafs = vec![];
+        for leaf_idx in 0..3u64 {
+            let builder_impl = CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(
+                LeafCircuitBuilder::geometry(),
+                1 << 10,
+                1 << 8,
+            );
+            let builder = new_builder::<_, F>(builder_impl);
+            let builder = LeafCircuitBuilder::configure_builder(builder);
+            let mut cs = builder.build(());
+
+            // prove that we know x such that x^2 + x == public input
+            let x = F::from_u64_unchecked(leaf_idx + 7);
+            let x_var = cs.alloc_single_variable_from_witness(x);
+            let y = FmaGateInBaseFieldWithoutConstant::compute_fma(
+                &mut cs,
+                F::ONE,
+                (x_var, x_var),
+                F::ONE,
+                x_var,
+            );
+            PublicInputGate::new(y).add_to_cs(&mut cs);
+
+            cs.pad_and_shrink();
+            let cs = cs.into_assembly();
+            let (proof, vk) =
+                cs.prove_one_shot::<2, EXT, TR, H, NoPow>(&worker, proof_config.clone(), ());
+            leafs.push((vk, proof));
+        }
+
+        let params = AggregationParameters {
+            branching_factor: 2,
+            node_proof_config: proof_config,
+            max_variables: 1 << 24,
+            max_trace_len: 1 << 20,
+        };
+
+        let leaf_public_inputs: Vec<_> = leafs
+            .iter()
+            .map(|(_, proof)| proof.public_inputs.clone())
+            .collect();
+        let result = aggregate_proofs::<
+            LeafCircuitBuilder,
+            RecursionNodeCircuitBuilder,
+            RH,
+            TR,
+            CTR,
+            NoPow,
+        >(&worker, leafs, &params, ());
+
+        // 3 leafs are padded to 4, so we have two levels of nodes
+        assert_eq!(result.node_vks.len(), 2);
+        assert_eq!(
+            result.root_proof.public_inputs,
+            result.root_commitment.to_vec()
+        );
+
+        let verifier =
+            CircuitBuilderProxy::<F, RecursionNodeCircuitBuilder>::dyn_verifier_builder::<2, EXT>()
+                .create_verifier();
+        assert!(verifier.verify::<H, TR, NoPow>((), result.root_vk(), &result.root_proof));
+
+        for (public_inputs, witness) in leaf_public_inputs
+            .iter()
+            .zip(result.inclusion_witnesses.iter())
+        {
+            assert!(witness.verify(
+                public_inputs,
+                &result.root_proof.public_inputs,
+                result.node_vks.len()
+            ));
+        }
+        let mut wrong_public_inputs = leaf_public_inputs[0].clone();
+        wrong_public_inputs[0].add_assign(&F::ONE);
+        assert!(!result.inclusion_witnesses[0].verify(
+            &wrong_public_inputs,
+            &result.root_proof.public_inputs,
+            result.node_vks.len()
+        ));
+    }
+}
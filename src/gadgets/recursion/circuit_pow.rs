@@ -1,20 +1,195 @@
 use super::*;
-use crate::cs::implementations::pow::{NoPow, PoWRunner};
+use crate::cs::implementations::pow::{NoPow, PoWRunner, Poseidon2Pow};
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::field::goldilocks::GoldilocksField;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::num::Num;
+use crate::gadgets::recursion::recursive_tree_hasher::{
+    enforce_canonical_goldilocks_limbs, CircuitGoldilocksPoseidon2Sponge, CircuitTreeHasher,
+};
 
-pub trait CircuitPowRunner<F: SmallField> {}
+pub trait CircuitPowRunner<F: SmallField> {
+    // challenge is given in the same form as the prover adds it into the transcript,
+    // as two 32-bit limbs
+    fn verify_from_field_elements<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        seed: Vec<Num<F>>,
+        pow_bits: u32,
+        challenge_limbs: [Num<F>; 2],
+    ) -> Boolean<F>;
+}
 
 pub trait RecursivePoWRunner<F: SmallField>: PoWRunner {
     type CircuitReflection: CircuitPowRunner<F>;
 }
 
+// Little endian bits of the challenge into low and high 32-bit limbs
+pub fn pow_challenge_into_limbs<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    challenge_bits: &[Boolean<F>; 64],
+) -> [Num<F>; 2] {
+    std::array::from_fn(|limb_idx| {
+        let terms: Vec<_> = challenge_bits[(limb_idx * 32)..((limb_idx + 1) * 32)]
+            .iter()
+            .enumerate()
+            .map(|(shift, bit)| (bit.get_variable(), F::from_u64_unchecked(1u64 << shift)))
+            .collect();
+
+        Num::linear_combination(cs, &terms)
+    })
+}
+
 #[derive(Derivative)]
 #[derivative(Clone, Copy, Debug, Default(bound = ""))]
 pub struct CircuitNoPow<F: SmallField> {
     _marker: std::marker::PhantomData<F>,
 }
 
-impl<F: SmallField> CircuitPowRunner<F> for CircuitNoPow<F> {}
+impl<F: SmallField> CircuitPowRunner<F> for CircuitNoPow<F> {
+    fn verify_from_field_elements<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        _seed: Vec<Num<F>>,
+        pow_bits: u32,
+        _challenge_limbs: [Num<F>; 2],
+    ) -> Boolean<F> {
+        assert_eq!(pow_bits, 0);
+
+        Boolean::allocated_constant(cs, true)
+    }
+}
 
 impl<F: SmallField> RecursivePoWRunner<F> for NoPow {
     type CircuitReflection = CircuitNoPow<F>;
 }
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug, Default)]
+pub struct CircuitPoseidon2Pow;
+
+impl CircuitPowRunner<GoldilocksField> for CircuitPoseidon2Pow {
+    fn verify_from_field_elements<CS: ConstraintSystem<GoldilocksField>>(
+        cs: &mut CS,
+        seed: Vec<Num<GoldilocksField>>,
+        pow_bits: u32,
+        challenge_limbs: [Num<GoldilocksField>; 2],
+    ) -> Boolean<GoldilocksField> {
+        assert!(pow_bits > 0);
+        assert!(pow_bits <= 32);
+
+        let mut buffer = seed;
+        buffer.extend(challenge_limbs);
+        let output = <CircuitGoldilocksPoseidon2Sponge as CircuitTreeHasher<
+            GoldilocksField,
+            Num<GoldilocksField>,
+        >>::hash_into_leaf(cs, buffer.iter());
+
+        let bits = output[0].spread_into_bits::<CS, 64>(cs);
+        // decomposition must be canonical, otherwise `output + p` would give other lowest bits
+        let [low, high] = pow_challenge_into_limbs(cs, &bits);
+        enforce_canonical_goldilocks_limbs(cs, &low, &high);
+
+        let any_is_set = Boolean::multi_or(cs, &bits[..(pow_bits as usize)]);
+
+        any_is_set.negated(cs)
+    }
+}
+
+impl RecursivePoWRunner<GoldilocksField> for Poseidon2Pow {
+    type CircuitReflection = CircuitPoseidon2Pow;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cs::gates::*;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::CSGeometry;
+    use crate::field::U64Representable;
+    use crate::gadgets::traits::allocatable::CSAllocatable;
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+
+    type F = GoldilocksField;
+
+    #[test]
+    fn test_poseidon2_pow() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 80,
+            num_witness_columns: 0,
+            num_constant_columns: 8,
+            max_allowed_constraint_degree: 8,
+        };
+
+        use crate::config::DevCSConfig;
+        use crate::cs::cs_builder_reference::*;
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 18, 1 << 16);
+        use crate::cs::cs_builder::new_builder;
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+        let cs = &mut owned_cs;
+
+        let seed: Vec<_> = (0..4u64)
+            .map(|el| F::from_u64_unchecked(el * 1_000_003 + 7))
+            .collect();
+        let pow_bits = 6;
+        let worker = Worker::new_with_num_threads(1);
+        let challenge = Poseidon2Pow::run_from_field_elements(seed.clone(), pow_bits, &worker);
+        assert!(Poseidon2Pow::verify_from_field_elements(
+            seed.clone(),
+            pow_bits,
+            challenge
+        ));
+
+        let circuit_seed: Vec<_> = seed.iter().map(|el| Num::allocate(cs, *el)).collect();
+        // next challenge is most likely invalid, but we compare with the native result anyway
+        for challenge in [challenge, challenge + 1] {
+            let expected =
+                Poseidon2Pow::verify_from_field_elements(seed.clone(), pow_bits, challenge);
+            let bits: [_; 64] =
+                std::array::from_fn(|idx| Boolean::allocate(cs, (challenge >> idx) & 1 == 1));
+            let limbs = pow_challenge_into_limbs(cs, &bits);
+            let is_valid = CircuitPoseidon2Pow::verify_from_field_elements(
+                cs,
+                circuit_seed.clone(),
+                pow_bits,
+                limbs,
+            );
+            assert_eq!(is_valid.witness_hook(&*cs)().unwrap(), expected);
+        }
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
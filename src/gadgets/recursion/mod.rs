@@ -1,8 +1,10 @@
 use super::*;
 
+pub mod aggregation;
 pub mod allocated_proof;
 pub mod allocated_vk;
 pub mod circuit_pow;
+pub mod multi_circuit;
 pub mod recursive_transcript;
 pub mod recursive_tree_hasher;
 pub mod recursive_verifier;
@@ -0,0 +1,655 @@
+use super::*;
+use crate::algebraic_props::round_function::AbsorptionModeOverwrite;
+use crate::algebraic_props::sponge::GoldilocksPoseidon2Sponge;
+use crate::cs::implementations::proof::Proof;
+use crate::cs::implementations::prover::ProofConfig;
+use crate::cs::implementations::verifier::{VerificationKey, VerificationKeyCircuitGeometry};
+use crate::cs::oracle::TreeHasher;
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::field::goldilocks::{GoldilocksExt2, GoldilocksField};
+use crate::field::{Field, U64Representable};
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::merkle_tree::verify_inclusion;
+use crate::gadgets::num::Num;
+use crate::gadgets::recursion::aggregation::{AggregationCommitment, AGGREGATION_COMMITMENT_WIDTH};
+use crate::gadgets::recursion::allocated_proof::AllocatedProof;
+use crate::gadgets::recursion::allocated_vk::AllocatedVerificationKey;
+use crate::gadgets::recursion::circuit_pow::RecursivePoWRunner;
+use crate::gadgets::recursion::recursive_transcript::{CircuitTranscript, RecursiveTranscript};
+use crate::gadgets::recursion::recursive_tree_hasher::{
+    CircuitGoldilocksPoseidon2Sponge, CircuitTreeHasher, RecursiveTreeHasher,
+};
+use crate::gadgets::recursion::recursive_verifier::RecursiveVerifier;
+use crate::gadgets::traits::allocatable::CSAllocatable;
+use crate::gadgets::traits::encodable::CircuitVarLengthEncodable;
+use crate::gadgets::traits::selectable::Selectable;
+
+// Verification of one of several allowed circuit types by the same node circuit. The set of
+// allowed verification keys is committed as a Merkle root of their hashes, and the node takes
+// the circuit index, the verification key and the Merkle path as a witness.
+// The verifier work depends on the fixed parameters of the circuit (gates and selectors,
+// column counts, domain size, public input locations, etc.), so the node circuit verifies
+// every allowed circuit type by its own recursive verifier: the selected type gets the real
+// proof, and all the other types get their padding proofs. Results are then selected by the
+// circuit index, so the cost of the node is the sum of costs of verifying every allowed type.
+// Position of the verification key in the tree binds it to the fixed parameters of the type
+
+type F = GoldilocksField;
+type EXT = GoldilocksExt2;
+type VkTreeHasher = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+// Hash of the setup cap, that is a leaf of the allowed verification keys tree
+pub fn verification_key_hash<H: TreeHasher<F>>(vk: &VerificationKey<F, H>) -> AggregationCommitment
+where
+    H::Output: AsRef<[F]>,
+{
+    <VkTreeHasher as TreeHasher<F>>::hash_into_leaf(
+        vk.setup_merkle_tree_cap
+            .iter()
+            .flat_map(|el| el.as_ref().iter()),
+    )
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Debug, PartialEq, Eq)]
+pub struct AllowedVerificationKeysTree {
+    // leaf hashes, padded by zeroes to the power of two
+    pub leafs: Vec<AggregationCommitment>,
+    pub num_allowed_circuits: usize,
+}
+
+impl AllowedVerificationKeysTree {
+    pub fn new<H: TreeHasher<F>>(vks: &[VerificationKey<F, H>]) -> Self
+    where
+        H::Output: AsRef<[F]>,
+    {
+        assert!(vks.len() > 0);
+
+        let num_allowed_circuits = vks.len();
+        let mut leafs: Vec<_> = vks.iter().map(|el| verification_key_hash(el)).collect();
+        leafs.resize(
+            num_allowed_circuits.next_power_of_two(),
+            [F::ZERO; AGGREGATION_COMMITMENT_WIDTH],
+        );
+
+        Self {
+            leafs,
+            num_allowed_circuits,
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.leafs.len().trailing_zeros() as usize
+    }
+
+    fn layers(&self) -> Vec<Vec<AggregationCommitment>> {
+        let mut layers = vec![self.leafs.clone()];
+        while layers.last().unwrap().len() > 1 {
+            let next: Vec<_> = layers
+                .last()
+                .unwrap()
+                .chunks_exact(2)
+                .map(|pair| <VkTreeHasher as TreeHasher<F>>::hash_into_node(&pair[0], &pair[1], 0))
+                .collect();
+            layers.push(next);
+        }
+
+        layers
+    }
+
+    pub fn root(&self) -> AggregationCommitment {
+        self.layers().pop().unwrap()[0]
+    }
+
+    // Bottom-up path as expected by the `verify_inclusion` gadget
+    pub fn path(&self, circuit_index: usize) -> Vec<AggregationCommitment> {
+        assert!(circuit_index < self.num_allowed_circuits);
+
+        let layers = self.layers();
+        let mut index = circuit_index;
+        let mut path = Vec::with_capacity(self.depth());
+        for layer in layers[..layers.len() - 1].iter() {
+            path.push(layer[index ^ 1]);
+            index >>= 1;
+        }
+
+        path
+    }
+}
+
+// One of the allowed circuit types, in the same order as in the allowed verification keys tree.
+// Padding proof is any valid proof of this type, it's verified in place of the real one
+// if another type is selected
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct AllowedCircuitType<H: TreeHasher<F>> {
+    pub vk: VerificationKey<F, H>,
+    pub padding_proof: Proof<F, H, 2, EXT>,
+}
+
+impl<H: TreeHasher<F>> AllowedCircuitType<H> {
+    pub fn fixed_parameters(&self) -> &VerificationKeyCircuitGeometry {
+        &self.vk.fixed_parameters
+    }
+
+    pub fn proof_config(&self) -> &ProofConfig {
+        &self.padding_proof.proof_config
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct AllowedCircuitProofWitness<H: TreeHasher<F>> {
+    pub circuit_index: usize,
+    pub vk: VerificationKey<F, H>,
+    pub vk_path: Vec<AggregationCommitment>,
+    pub proof: Proof<F, H, 2, EXT>,
+}
+
+// Verifies the proof of the circuit selected by the witnessed index, against the verification
+// key that is proven to be in the allowed set. `verifiers` are created for the allowed circuit
+// types and are in the same order. Returns validity flag of the proof together with inclusion
+// of its verification key, the circuit index and the public inputs, padded by zeroes to the
+// largest number of public inputs among the allowed types
+pub fn verify_one_of_allowed_circuits<
+    CS: ConstraintSystem<F> + 'static,
+    RH: RecursiveTreeHasher<F, Num<F>>,
+    TR: RecursiveTranscript<
+        F,
+        CompatibleCap = <RH::NonCircuitSimulator as TreeHasher<F>>::Output,
+        CircuitReflection = CTR,
+    >,
+    CTR: CircuitTranscript<
+        F,
+        CircuitCompatibleCap = <RH as CircuitTreeHasher<F, Num<F>>>::CircuitOutput,
+        TransciptParameters = TR::TransciptParameters,
+    >,
+    POW: RecursivePoWRunner<F>,
+>(
+    cs: &mut CS,
+    verifiers: &[RecursiveVerifier<F, 2, EXT, CS>],
+    allowed_circuit_types: &[AllowedCircuitType<RH::NonCircuitSimulator>],
+    transcript_params: TR::TransciptParameters,
+    allowed_vks_root: &[Num<F>; AGGREGATION_COMMITMENT_WIDTH],
+    allowed_vks_tree_depth: usize,
+    witness: AllowedCircuitProofWitness<RH::NonCircuitSimulator>,
+) -> (Boolean<F>, Num<F>, Vec<Num<F>>) {
+    let AllowedCircuitProofWitness {
+        circuit_index,
+        vk,
+        vk_path,
+        proof,
+    } = witness;
+    assert!(allowed_circuit_types.len() > 0);
+    assert_eq!(verifiers.len(), allowed_circuit_types.len());
+    assert!(allowed_circuit_types.len() <= 1 << allowed_vks_tree_depth);
+    assert!(circuit_index < allowed_circuit_types.len());
+    let selected_type = &allowed_circuit_types[circuit_index];
+    assert_eq!(&vk.fixed_parameters, selected_type.fixed_parameters());
+    assert_eq!(&proof.proof_config, selected_type.proof_config());
+    assert_eq!(vk_path.len(), allowed_vks_tree_depth);
+    assert!(allowed_vks_tree_depth <= 32);
+
+    let selected_type_idx = circuit_index;
+    let circuit_index = Num::allocate(cs, F::from_u64_unchecked(circuit_index as u64));
+    let index_bits = circuit_index.spread_into_bits::<CS, 32>(cs);
+    let boolean_false = Boolean::allocated_constant(cs, false);
+    for bit in index_bits[allowed_vks_tree_depth..].iter() {
+        Boolean::enforce_equal(cs, bit, &boolean_false);
+    }
+
+    let max_num_public_inputs = allowed_circuit_types
+        .iter()
+        .map(|el| el.fixed_parameters().num_public_inputs())
+        .max()
+        .unwrap();
+    let zero_num = Num::zero(cs);
+    let mut is_valid = boolean_false;
+    let mut vk_hash = [zero_num; AGGREGATION_COMMITMENT_WIDTH];
+    let mut public_inputs = vec![zero_num; max_num_public_inputs];
+
+    let mut selected_vk_and_proof = Some((vk, proof));
+    for (type_idx, (verifier, circuit_type)) in verifiers
+        .iter()
+        .zip(allowed_circuit_types.iter())
+        .enumerate()
+    {
+        let (vk, proof) = if type_idx == selected_type_idx {
+            selected_vk_and_proof.take().unwrap()
+        } else {
+            (circuit_type.vk.clone(), circuit_type.padding_proof.clone())
+        };
+        let fixed_parameters = circuit_type.fixed_parameters();
+        let proof_config = circuit_type.proof_config();
+
+        let allocated_vk = AllocatedVerificationKey::<F, RH>::allocate(cs, vk);
+        let mut encoding = Vec::with_capacity(allocated_vk.encoding_length());
+        allocated_vk.encode_to_buffer(cs, &mut encoding);
+        let encoding: Vec<_> = encoding.into_iter().map(Num::from_variable).collect();
+        let type_vk_hash =
+            <CircuitGoldilocksPoseidon2Sponge as CircuitTreeHasher<F, Num<F>>>::hash_into_leaf(
+                cs,
+                encoding.iter(),
+            );
+
+        let allocated_proof = AllocatedProof::<F, RH, 2, EXT>::allocate_from_witness(
+            cs,
+            Some(proof),
+            verifier,
+            fixed_parameters,
+            proof_config,
+        );
+        let (type_is_valid, type_public_inputs) = verifier.verify::<RH, TR, CTR, POW>(
+            cs,
+            transcript_params.clone(),
+            &allocated_proof,
+            fixed_parameters,
+            proof_config,
+            &allocated_vk,
+        );
+
+        let type_idx = Num::allocated_constant(cs, F::from_u64_unchecked(type_idx as u64));
+        let is_selected = Num::equals(cs, &circuit_index, &type_idx);
+        is_valid = Boolean::conditionally_select(cs, is_selected, &type_is_valid, &is_valid);
+        vk_hash = Selectable::conditionally_select(cs, is_selected, &type_vk_hash, &vk_hash);
+        let type_public_inputs = type_public_inputs
+            .into_iter()
+            .chain(std::iter::repeat(zero_num));
+        for (dst, src) in public_inputs.iter_mut().zip(type_public_inputs) {
+            *dst = Num::conditionally_select(cs, is_selected, &src, dst);
+        }
+    }
+
+    let vk_path: Vec<_> = vk_path
+        .into_iter()
+        .map(|el| <[Num<F>; AGGREGATION_COMMITMENT_WIDTH]>::allocate(cs, el))
+        .collect();
+    let is_allowed = verify_inclusion::<F, Num<F>, CircuitGoldilocksPoseidon2Sponge, CS>(
+        cs,
+        allowed_vks_root,
+        &vk_hash,
+        &vk_path,
+        &index_bits[..allowed_vks_tree_depth],
+    );
+
+    let is_valid = Boolean::multi_and(cs, &[is_valid, is_allowed]);
+
+    (is_valid, circuit_index, public_inputs)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::*;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::pow::NoPow;
+    use crate::cs::implementations::transcript::GoldilocksPoisedon2Transcript;
+    use crate::cs::traits::circuit::{CircuitBuilder, CircuitBuilderProxy};
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, LookupParameters, StaticToolboxHolder};
+    use crate::gadgets::recursion::aggregation::RecursionNodeCircuitBuilder;
+    use crate::gadgets::recursion::recursive_transcript::GoldilocksPoisedon2CircuitTranscript;
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+
+    type TR = GoldilocksPoisedon2Transcript;
+    type CTR = GoldilocksPoisedon2CircuitTranscript;
+    type RH = CircuitGoldilocksPoseidon2Sponge;
+    type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+    // Two circuit types with different geometries: the narrow one evaluates a polynomial of
+    // lower degree, and the wide one evaluates a longer one over more columns, also uses the
+    // reduction gate and has two public inputs
+    struct NarrowCircuitBuilder;
+
+    impl CircuitBuilder<F> for NarrowCircuitBuilder {
+        fn geometry() -> CSGeometry {
+            CSGeometry {
+                num_columns_under_copy_permutation: 8,
+                num_witness_columns: 0,
+                num_constant_columns: 2,
+                max_allowed_constraint_degree: 8,
+            }
+        }
+
+        fn lookup_parameters() -> LookupParameters {
+            LookupParameters::NoLookup
+        }
+
+        fn configure_builder<
+            T: CsBuilderImpl<F, T>,
+            GC: GateConfigurationHolder<F>,
+            TB: StaticToolboxHolder,
+        >(
+            builder: CsBuilder<T, F, GC, TB>,
+        ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+            let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = PublicInputGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = NopGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+
+            builder
+        }
+    }
+
+    struct WideCircuitBuilder;
+
+    impl CircuitBuilder<F> for WideCircuitBuilder {
+        fn geometry() -> CSGeometry {
+            CSGeometry {
+                num_columns_under_copy_permutation: 16,
+                num_witness_columns: 0,
+                num_constant_columns: 4,
+                max_allowed_constraint_degree: 8,
+            }
+        }
+
+        fn lookup_parameters() -> LookupParameters {
+            LookupParameters::NoLookup
+        }
+
+        fn configure_builder<
+            T: CsBuilderImpl<F, T>,
+            GC: GateConfigurationHolder<F>,
+            TB: StaticToolboxHolder,
+        >(
+            builder: CsBuilder<T, F, GC, TB>,
+        ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+            let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = ReductionGate::<F, 4>::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = PublicInputGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = NopGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+
+            builder
+        }
+    }
+
+    // Circuits of the same type differ by the polynomial that is evaluated, so only setups
+    // are different
+    fn prove_narrow_circuit(
+        worker: &Worker,
+        proof_config: ProofConfig,
+        polynomial_idx: u64,
+    ) -> (Proof<F, H, 2, EXT>, VerificationKey<F, H>) {
+        let builder_impl = CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(
+            NarrowCircuitBuilder::geometry(),
+            1 << 10,
+            1 << 8,
+        );
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = NarrowCircuitBuilder::configure_builder(builder);
+        let mut cs = builder.build(());
+
+        // long enough for FRI to make at least one folding step with the default config
+        let x = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(5));
+        let mut y = x;
+        for _ in 0..128 {
+            y = FmaGateInBaseFieldWithoutConstant::compute_fma(
+                &mut cs,
+                F::from_u64_unchecked(polynomial_idx + 1),
+                (y, x),
+                F::ONE,
+                x,
+            );
+        }
+        PublicInputGate::new(y).add_to_cs(&mut cs);
+
+        cs.pad_and_shrink();
+        let cs = cs.into_assembly();
+
+        cs.prove_one_shot::<2, EXT, TR, H, NoPow>(worker, proof_config, ())
+    }
+
+    fn prove_wide_circuit(
+        worker: &Worker,
+        proof_config: ProofConfig,
+        polynomial_idx: u64,
+    ) -> (Proof<F, H, 2, EXT>, VerificationKey<F, H>) {
+        let builder_impl = CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(
+            WideCircuitBuilder::geometry(),
+            1 << 12,
+            1 << 10,
+        );
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = WideCircuitBuilder::configure_builder(builder);
+        let mut cs = builder.build(());
+
+        let x = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(7));
+        let mut evaluations = vec![x];
+        for _ in 0..600 {
+            let y = FmaGateInBaseFieldWithoutConstant::compute_fma(
+                &mut cs,
+                F::from_u64_unchecked(polynomial_idx + 1),
+                (*evaluations.last().unwrap(), x),
+                F::ONE,
+                x,
+            );
+            evaluations.push(y);
+        }
+        let last: [_; 4] = evaluations[(evaluations.len() - 4)..].try_into().unwrap();
+        let sum = ReductionGate::<F, 4>::reduce_terms(&mut cs, [F::ONE; 4], last);
+        PublicInputGate::new(*evaluations.last().unwrap()).add_to_cs(&mut cs);
+        PublicInputGate::new(sum).add_to_cs(&mut cs);
+
+        cs.pad_and_shrink();
+        let cs = cs.into_assembly();
+
+        cs.prove_one_shot::<2, EXT, TR, H, NoPow>(worker, proof_config, ())
+    }
+
+    fn verify_in_node(
+        worker: &Worker,
+        allowed_circuit_types: &[AllowedCircuitType<H>],
+        root: AggregationCommitment,
+        depth: usize,
+        witness: AllowedCircuitProofWitness<H>,
+    ) -> (bool, u64, Vec<F>) {
+        let builder_impl = CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(
+            RecursionNodeCircuitBuilder::geometry(),
+            1 << 23,
+            1 << 19,
+        );
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = RecursionNodeCircuitBuilder::configure_builder(builder);
+        let mut cs = builder.build(());
+
+        let verifiers =
+            vec![
+                CircuitBuilderProxy::<F, NarrowCircuitBuilder>::dyn_recursive_verifier_builder::<
+                    2,
+                    EXT,
+                    _,
+                >()
+                .create_recursive_verifier(&mut cs),
+                CircuitBuilderProxy::<F, WideCircuitBuilder>::dyn_recursive_verifier_builder::<
+                    2,
+                    EXT,
+                    _,
+                >()
+                .create_recursive_verifier(&mut cs),
+            ];
+        let root = <[Num<F>; AGGREGATION_COMMITMENT_WIDTH]>::allocate_constant(&mut cs, root);
+
+        let (is_valid, circuit_index, public_inputs) =
+            verify_one_of_allowed_circuits::<_, RH, TR, CTR, NoPow>(
+                &mut cs,
+                &verifiers,
+                allowed_circuit_types,
+                (),
+                &root,
+                depth,
+                witness,
+            );
+
+        let is_valid = is_valid.witness_hook(&cs)().unwrap();
+        let circuit_index = circuit_index.witness_hook(&cs)().unwrap().as_u64_reduced();
+        let public_inputs: Vec<_> = public_inputs
+            .iter()
+            .map(|el| el.witness_hook(&cs)().unwrap())
+            .collect();
+
+        cs.pad_and_shrink();
+        let mut cs = cs.into_assembly();
+        assert!(cs.check_if_satisfied(worker));
+
+        (is_valid, circuit_index, public_inputs)
+    }
+
+    #[test]
+    fn test_allowed_vks_tree() {
+        let worker = Worker::new_with_num_threads(4);
+        let mut proof_config = ProofConfig::default();
+        proof_config.pow_bits = 0;
+
+        let vks = vec![
+            prove_narrow_circuit(&worker, proof_config.clone(), 0).1,
+            prove_narrow_circuit(&worker, proof_config.clone(), 1).1,
+            prove_wide_circuit(&worker, proof_config.clone(), 0).1,
+        ];
+        assert_ne!(vks[0], vks[1]);
+        assert_ne!(vks[0].fixed_parameters, vks[2].fixed_parameters);
+
+        let tree = AllowedVerificationKeysTree::new(&vks);
+        assert_eq!(tree.depth(), 2);
+        assert_eq!(tree.leafs.len(), 4);
+
+        let root = tree.root();
+        for (idx, vk) in vks.iter().enumerate() {
+            let mut current = verification_key_hash(vk);
+            let mut index = idx;
+            for sibling in tree.path(idx).iter() {
+                let (left, right) = if index & 1 == 0 {
+                    (&current, sibling)
+                } else {
+                    (sibling, &current)
+                };
+                current = <VkTreeHasher as TreeHasher<F>>::hash_into_node(left, right, 0);
+                index >>= 1;
+            }
+            assert_eq!(current, root);
+        }
+    }
+
+    #[test]
+    #[ignore = "proves several base circuits and synthesizes node circuits"]
+    fn test_verify_one_of_allowed_circuits() {
+        let worker = Worker::new();
+
+        let mut proof_config = ProofConfig::default();
+        proof_config.fri_lde_factor = 8;
+        proof_config.security_level = 80;
+        proof_config.pow_bits = 0;
+
+        let circuits = vec![
+            prove_narrow_circuit(&worker, proof_config.clone(), 0),
+            prove_wide_circuit(&worker, proof_config.clone(), 0),
+        ];
+        let fixed_parameters: Vec<_> = circuits
+            .iter()
+            .map(|(_, vk)| vk.fixed_parameters.clone())
+            .collect();
+        assert_ne!(
+            fixed_parameters[0].parameters,
+            fixed_parameters[1].parameters
+        );
+        assert_ne!(
+            fixed_parameters[0].domain_size,
+            fixed_parameters[1].domain_size
+        );
+        assert_ne!(
+            fixed_parameters[0].public_inputs_locations.len(),
+            fixed_parameters[1].public_inputs_locations.len()
+        );
+
+        let allowed_circuit_types: Vec<_> = circuits
+            .iter()
+            .map(|(proof, vk)| AllowedCircuitType {
+                vk: vk.clone(),
+                padding_proof: proof.clone(),
+            })
+            .collect();
+        let vks: Vec<_> = circuits.iter().map(|(_, vk)| vk.clone()).collect();
+        let tree = AllowedVerificationKeysTree::new(&vks);
+
+        // proofs of other circuits of the same types, padding proofs are used for the other type
+        let proofs = vec![
+            prove_narrow_circuit(&worker, proof_config.clone(), 3),
+            prove_wide_circuit(&worker, proof_config.clone(), 3),
+        ];
+        for (idx, (proof, vk)) in circuits.iter().enumerate() {
+            let witness = AllowedCircuitProofWitness {
+                circuit_index: idx,
+                vk: vk.clone(),
+                vk_path: tree.path(idx),
+                proof: proof.clone(),
+            };
+            let (is_valid, circuit_index, public_inputs) = verify_in_node(
+                &worker,
+                &allowed_circuit_types,
+                tree.root(),
+                tree.depth(),
+                witness,
+            );
+            assert!(is_valid);
+            assert_eq!(circuit_index, idx as u64);
+            let mut expected_public_inputs = proof.public_inputs.clone();
+            expected_public_inputs.resize(2, F::ZERO);
+            assert_eq!(public_inputs, expected_public_inputs);
+
+            // proof is valid, but its verification key is not in the allowed set
+            let (proof, vk) = proofs[idx].clone();
+            let witness = AllowedCircuitProofWitness {
+                circuit_index: idx,
+                vk,
+                vk_path: tree.path(idx),
+                proof,
+            };
+            let (is_valid, _, _) = verify_in_node(
+                &worker,
+                &allowed_circuit_types,
+                tree.root(),
+                tree.depth(),
+                witness,
+            );
+            assert!(!is_valid);
+        }
+
+        // verification key is allowed, but the proof is of another circuit of the same type
+        let witness = AllowedCircuitProofWitness {
+            circuit_index: 1,
+            vk: circuits[1].1.clone(),
+            vk_path: tree.path(1),
+            proof: proofs[1].0.clone(),
+        };
+        let (is_valid, _, _) = verify_in_node(
+            &worker,
+            &allowed_circuit_types,
+            tree.root(),
+            tree.depth(),
+            witness,
+        );
+        assert!(!is_valid);
+    }
+}
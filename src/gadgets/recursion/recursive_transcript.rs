@@ -243,3 +243,102 @@ pub type GoldilocksPoisedon2CircuitTranscript =
 impl RecursiveTranscript<GoldilocksField> for GoldilocksPoisedon2Transcript {
     type CircuitReflection = GoldilocksPoisedon2CircuitTranscript;
 }
+
+use crate::cs::implementations::transcript::{Blake2sTranscript, Keccak256Transcript};
+use crate::gadgets::blake2s::Blake2sHasher;
+use crate::gadgets::keccak256::Keccak256Hasher;
+use crate::gadgets::recursion::recursive_tree_hasher::{
+    goldilocks_num_into_le_bytes, CircuitByteHasher,
+};
+use crate::gadgets::u8::UInt8;
+
+// Reflection of byte oriented transcripts, e.g. `Blake2sTranscript`. Hasher is never reset,
+// and instead every output is fed back into it, so we have to carry the full state
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct CircuitByteOrientedTranscript<F: SmallField, H: CircuitByteHasher<F>> {
+    inner: H,
+    buffer: Vec<UInt8<F>>,
+    available_challenge_bytes: Vec<UInt8<F>>,
+}
+
+impl<F: SmallField, H: CircuitByteHasher<F>> CircuitByteOrientedTranscript<F, H> {
+    fn reseed<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        let fresh_hasher = H::new(cs);
+        let output = std::mem::replace(&mut self.inner, fresh_hasher).finalize(cs);
+        self.inner.absorb(cs, &output);
+        self.available_challenge_bytes.extend(output);
+    }
+}
+
+impl<F: SmallField, H: CircuitByteHasher<F>> CircuitTranscript<F>
+    for CircuitByteOrientedTranscript<F, H>
+{
+    type CircuitCompatibleCap = [UInt8<F>; 32];
+    type TransciptParameters = ();
+
+    const IS_ALGEBRAIC: bool = false;
+
+    fn new<CS: ConstraintSystem<F>>(cs: &mut CS, _params: Self::TransciptParameters) -> Self {
+        Self {
+            inner: H::new(cs),
+            buffer: Vec::with_capacity(64),
+            available_challenge_bytes: Vec::with_capacity(32),
+        }
+    }
+    fn witness_field_elements<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        field_els: &[Num<F>],
+    ) {
+        for el in field_els.iter() {
+            let bytes = goldilocks_num_into_le_bytes(cs, el);
+            self.buffer.extend(bytes);
+        }
+    }
+    fn witness_merkle_tree_cap<CS: ConstraintSystem<F>>(
+        &mut self,
+        _cs: &mut CS,
+        cap: &Vec<Self::CircuitCompatibleCap>,
+    ) {
+        for el in cap.iter() {
+            self.buffer.extend_from_slice(&el[..]);
+        }
+    }
+    fn get_challenge<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Num<F> {
+        if self.buffer.is_empty() == false {
+            let to_absorb = std::mem::take(&mut self.buffer);
+            self.inner.absorb(cs, &to_absorb);
+            self.available_challenge_bytes.clear();
+
+            self.reseed(cs);
+        }
+
+        if self.available_challenge_bytes.is_empty() {
+            self.reseed(cs);
+        }
+
+        assert!(self.available_challenge_bytes.len() % 8 == 0);
+        // same as `from_u64_with_reduction` of little endian u64, as linear
+        // combination is computed modulo the characteristic anyway
+        let terms: Vec<_> = self
+            .available_challenge_bytes
+            .drain(..8)
+            .enumerate()
+            .map(|(shift, byte)| (byte.get_variable(), F::SHIFTS[shift * 8]))
+            .collect();
+
+        Num::linear_combination(cs, &terms)
+    }
+}
+
+pub type CircuitBlake2sTranscript<F> = CircuitByteOrientedTranscript<F, Blake2sHasher<F>>;
+pub type CircuitKeccak256Transcript<F> = CircuitByteOrientedTranscript<F, Keccak256Hasher<F>>;
+
+impl RecursiveTranscript<GoldilocksField> for Blake2sTranscript {
+    type CircuitReflection = CircuitBlake2sTranscript<GoldilocksField>;
+}
+
+impl RecursiveTranscript<GoldilocksField> for Keccak256Transcript {
+    type CircuitReflection = CircuitKeccak256Transcript<GoldilocksField>;
+}
@@ -11,6 +11,7 @@ use crate::gadgets::traits::allocatable::CSAllocatable;
 use crate::gadgets::traits::encodable::CircuitVarLengthEncodable;
 use crate::gadgets::traits::round_function::CircuitRoundFunction;
 use crate::implementations::poseidon2::Poseidon2Goldilocks;
+use derivative::*;
 
 pub trait CircuitTreeHasher<F: SmallField, B: Sized + CSAllocatable<F>>:
     'static + Clone + Send + Sync
@@ -21,8 +22,6 @@ pub trait CircuitTreeHasher<F: SmallField, B: Sized + CSAllocatable<F>>:
         + Copy
         + Sync
         + Send
-        + PartialEq
-        + Eq
         + std::fmt::Debug
         + CSAllocatable<F>
         + CircuitVarLengthEncodable<F>;
@@ -231,7 +230,7 @@ impl<
         cap_bits: &[Boolean<F>],
         cap: &[Self::CircuitOutput],
     ) -> Self::CircuitOutput {
-        use crate::gadgets::recursion::recursive_verifier::binary_select;
+        use crate::gadgets::traits::selectable::binary_select;
 
         binary_select(cs, cap, cap_bits)
     }
@@ -254,3 +253,221 @@ impl RecursiveTreeHasher<GoldilocksField, Num<GoldilocksField>>
 {
     type NonCircuitSimulator = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
 }
+
+use crate::gadgets::blake2s::Blake2sHasher;
+use crate::gadgets::keccak256::Keccak256Hasher;
+use crate::gadgets::traits::selectable::Selectable;
+use crate::gadgets::u8::UInt8;
+
+// Incremental byte oriented hash function, that is used to reflect non-algebraic
+// transcripts and tree hashers
+pub trait CircuitByteHasher<F: SmallField>:
+    'static + Clone + Send + Sync + std::fmt::Debug
+{
+    fn new<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self;
+    fn absorb<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, input: &[UInt8<F>]);
+    fn finalize<CS: ConstraintSystem<F>>(self, cs: &mut CS) -> [UInt8<F>; 32];
+}
+
+impl<F: SmallField> CircuitByteHasher<F> for Blake2sHasher<F> {
+    fn new<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        Blake2sHasher::new(cs)
+    }
+    fn absorb<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, input: &[UInt8<F>]) {
+        Blake2sHasher::absorb(self, cs, input)
+    }
+    fn finalize<CS: ConstraintSystem<F>>(self, cs: &mut CS) -> [UInt8<F>; 32] {
+        Blake2sHasher::finalize(self, cs)
+    }
+}
+
+impl<F: SmallField> CircuitByteHasher<F> for Keccak256Hasher<F> {
+    fn new<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        Keccak256Hasher::new(cs)
+    }
+    fn absorb<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, input: &[UInt8<F>]) {
+        Keccak256Hasher::absorb(self, cs, input)
+    }
+    fn finalize<CS: ConstraintSystem<F>>(self, cs: &mut CS) -> [UInt8<F>; 32] {
+        Keccak256Hasher::finalize(self, cs)
+    }
+}
+
+// Same encoding as `as_u64_reduced().to_le_bytes()` out of circuit. Decomposition alone
+// only proves that bytes encode the element modulo the characteristic, so we also
+// require the 64-bit value to be canonical
+pub fn goldilocks_num_into_le_bytes<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    value: &Num<F>,
+) -> [UInt8<F>; 8] {
+    assert_eq!(F::CHAR, GoldilocksField::CHAR);
+
+    let bytes = value.constraint_bit_length_as_bytes(cs, 64);
+    let bytes: [UInt8<F>; 8] = bytes.into_inner().expect("must decompose into 8 bytes");
+
+    let [low, high]: [Num<F>; 2] = std::array::from_fn(|idx| {
+        let terms: Vec<_> = bytes[(idx * 4)..((idx + 1) * 4)]
+            .iter()
+            .enumerate()
+            .map(|(shift, byte)| (byte.get_variable(), F::SHIFTS[shift * 8]))
+            .collect();
+
+        Num::linear_combination(cs, &terms)
+    });
+
+    enforce_canonical_goldilocks_limbs(cs, &low, &high);
+
+    bytes
+}
+
+// Given low and high 32-bit limbs of a 64-bit decomposition, enforces that the decomposed value
+// is less than the Goldilocks characteristic: p = 2^64 - 2^32 + 1, so if highest 32 bits
+// are all set then lowest must be zero
+pub fn enforce_canonical_goldilocks_limbs<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    low: &Num<F>,
+    high: &Num<F>,
+) {
+    assert_eq!(F::CHAR, GoldilocksField::CHAR);
+
+    let max_u32 = Num::allocated_constant(cs, F::from_u64_unchecked(u32::MAX as u64));
+    let high_is_max = Num::equals(cs, high, &max_u32);
+    let low_is_zero = low.is_zero(cs);
+    low_is_zero.conditionally_enforce_true(cs, high_is_max);
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct CircuitByteOrientedTreeHasher<F: SmallField, H: CircuitByteHasher<F>> {
+    buffer: Vec<UInt8<F>>,
+    _marker: std::marker::PhantomData<H>,
+}
+
+impl<F: SmallField, H: CircuitByteHasher<F>> CircuitTreeHasher<F, Num<F>>
+    for CircuitByteOrientedTreeHasher<F, H>
+{
+    type CircuitOutput = [UInt8<F>; 32];
+
+    fn new<CS: ConstraintSystem<F>>(_cs: &mut CS) -> Self {
+        Self {
+            buffer: Vec::new(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+    fn placeholder_output<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self::CircuitOutput {
+        let zero = UInt8::allocated_constant(cs, 0u8);
+
+        [zero; 32]
+    }
+    fn accumulate_into_leaf<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, value: &Num<F>) {
+        let bytes = goldilocks_num_into_le_bytes(cs, value);
+        self.buffer.extend(bytes);
+    }
+    fn finalize_into_leaf_hash_and_reset<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+    ) -> Self::CircuitOutput {
+        let input = std::mem::take(&mut self.buffer);
+        let mut hasher = H::new(cs);
+        hasher.absorb(cs, &input);
+
+        hasher.finalize(cs)
+    }
+    fn hash_into_leaf<'a, S: IntoIterator<Item = &'a Num<F>>, CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        source: S,
+    ) -> Self::CircuitOutput
+    where
+        Num<F>: 'a,
+    {
+        let mut hasher = <Self as CircuitTreeHasher<F, Num<F>>>::new(cs);
+
+        for el in source.into_iter() {
+            <Self as CircuitTreeHasher<F, Num<F>>>::accumulate_into_leaf(&mut hasher, cs, el);
+        }
+
+        <Self as CircuitTreeHasher<F, Num<F>>>::finalize_into_leaf_hash_and_reset(&mut hasher, cs)
+    }
+    fn hash_into_leaf_owned<S: IntoIterator<Item = Num<F>>, CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        source: S,
+    ) -> Self::CircuitOutput {
+        let mut hasher = <Self as CircuitTreeHasher<F, Num<F>>>::new(cs);
+
+        for el in source.into_iter() {
+            <Self as CircuitTreeHasher<F, Num<F>>>::accumulate_into_leaf(&mut hasher, cs, &el);
+        }
+
+        <Self as CircuitTreeHasher<F, Num<F>>>::finalize_into_leaf_hash_and_reset(&mut hasher, cs)
+    }
+    fn swap_nodes<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        should_swap: Boolean<F>,
+        left: &Self::CircuitOutput,
+        right: &Self::CircuitOutput,
+        _depth: usize,
+    ) -> (Self::CircuitOutput, Self::CircuitOutput) {
+        let new_left = Selectable::conditionally_select(cs, should_swap, right, left);
+        let new_right = Selectable::conditionally_select(cs, should_swap, left, right);
+
+        (new_left, new_right)
+    }
+    fn hash_into_node<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        left: &Self::CircuitOutput,
+        right: &Self::CircuitOutput,
+        _depth: usize,
+    ) -> Self::CircuitOutput {
+        let mut hasher = H::new(cs);
+        hasher.absorb(cs, &left[..]);
+        hasher.absorb(cs, &right[..]);
+
+        hasher.finalize(cs)
+    }
+    fn select_cap_node<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        cap_bits: &[Boolean<F>],
+        cap: &[Self::CircuitOutput],
+    ) -> Self::CircuitOutput {
+        use crate::gadgets::traits::selectable::binary_select;
+
+        binary_select(cs, cap, cap_bits)
+    }
+    fn compare_output<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        a: &Self::CircuitOutput,
+        b: &Self::CircuitOutput,
+    ) -> Boolean<F> {
+        // bytes are range checked, so we can compare them packed by 4
+        let equalities: [_; 8] = std::array::from_fn(|idx| {
+            let [a, b] = [a, b].map(|el| {
+                let terms: Vec<_> = el[(idx * 4)..((idx + 1) * 4)]
+                    .iter()
+                    .enumerate()
+                    .map(|(shift, byte)| (byte.get_variable(), F::SHIFTS[shift * 8]))
+                    .collect();
+
+                Num::linear_combination(cs, &terms)
+            });
+
+            Num::equals(cs, &a, &b)
+        });
+
+        Boolean::multi_and(cs, &equalities)
+    }
+}
+
+pub type CircuitBlake2sTreeHasher<F> = CircuitByteOrientedTreeHasher<F, Blake2sHasher<F>>;
+pub type CircuitKeccak256TreeHasher<F> = CircuitByteOrientedTreeHasher<F, Keccak256Hasher<F>>;
+
+impl RecursiveTreeHasher<GoldilocksField, Num<GoldilocksField>>
+    for CircuitBlake2sTreeHasher<GoldilocksField>
+{
+    type NonCircuitSimulator = blake2::Blake2s256;
+}
+
+impl RecursiveTreeHasher<GoldilocksField, Num<GoldilocksField>>
+    for CircuitKeccak256TreeHasher<GoldilocksField>
+{
+    type NonCircuitSimulator = sha3::Keccak256;
+}
@@ -8,7 +8,10 @@ use crate::{
         traits::{cs::ConstraintSystem, evaluator::PerChunkOffset},
     },
     field::{FieldExtension, SmallField},
-    gadgets::{boolean::Boolean, traits::selectable::Selectable},
+    gadgets::{
+        boolean::Boolean,
+        traits::selectable::binary_select,
+    },
 };
 
 use crate::cs::{
@@ -30,22 +33,25 @@ use crate::gadgets::recursion::recursive_tree_hasher::*;
 use crate::gadgets::recursion::recursive_verifier_builder::TypeErasedGateEvaluationRecursiveVerificationFunction;
 use std::alloc::Global;
 
-use crate::gadgets::recursion::circuit_pow::RecursivePoWRunner;
+use crate::gadgets::recursion::circuit_pow::{
+    pow_challenge_into_limbs, CircuitPowRunner, RecursivePoWRunner,
+};
 
 fn materialize_powers_serial<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     CS: ConstraintSystem<F> + 'static,
 >(
     cs: &mut CS,
-    base: NumExtAsFieldWrapper<F, EXT, CS>,
+    base: NumExtAsFieldWrapper<F, DEGREE, EXT, CS>,
     size: usize,
-) -> Vec<NumExtAsFieldWrapper<F, EXT, CS>> {
+) -> Vec<NumExtAsFieldWrapper<F, DEGREE, EXT, CS>> {
     if size == 0 {
         return Vec::new();
     }
     let mut storage = Vec::with_capacity(size);
-    let mut current = NumExtAsFieldWrapper::<F, EXT, CS>::one(cs);
+    let mut current = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::one(cs);
     storage.push(current);
     for idx in 1..size {
         if idx == 1 {
@@ -61,7 +67,8 @@ fn materialize_powers_serial<
 
 pub struct RecursiveVerifierProxy<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     CS: ConstraintSystem<F> + 'static,
     GC: GateConfigurationHolder<F>,
     T: StaticToolboxHolder,
@@ -73,11 +80,11 @@ pub struct RecursiveVerifierProxy<
     pub gates_configuration: GC,
     pub(crate) gate_type_ids_for_specialized_columns: Vec<TypeId>,
     pub(crate) evaluators_over_specialized_columns:
-        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>>,
+        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, DEGREE, EXT, CS>>,
     pub(crate) offsets_for_specialized_evaluators: Vec<(PerChunkOffset, PerChunkOffset, usize)>,
 
     pub(crate) evaluators_over_general_purpose_columns:
-        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>>,
+        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, DEGREE, EXT, CS>>,
 
     pub(crate) total_num_variables_for_specialized_columns: usize,
     pub(crate) total_num_witnesses_for_specialized_columns: usize,
@@ -88,13 +95,14 @@ pub struct RecursiveVerifierProxy<
 
 impl<
         F: SmallField,
-        EXT: FieldExtension<2, BaseField = F>,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
         CS: ConstraintSystem<F> + 'static,
         GC: GateConfigurationHolder<F>,
         T: StaticToolboxHolder,
-    > RecursiveVerifierProxy<F, EXT, CS, GC, T>
+    > RecursiveVerifierProxy<F, DEGREE, EXT, CS, GC, T>
 {
-    pub fn into_verifier(self) -> RecursiveVerifier<F, EXT, CS> {
+    pub fn into_verifier(self) -> RecursiveVerifier<F, DEGREE, EXT, CS> {
         let Self {
             parameters,
             lookup_parameters,
@@ -125,7 +133,7 @@ impl<
             placement_strategies.insert(*gate_type_id, placement_strategy);
         }
 
-        RecursiveVerifier::<F, EXT, CS> {
+        RecursiveVerifier::<F, DEGREE, EXT, CS> {
             parameters,
             lookup_parameters,
             gate_type_ids_for_specialized_columns,
@@ -142,7 +150,8 @@ impl<
 
 pub struct RecursiveVerifier<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     CS: ConstraintSystem<F> + 'static,
 > {
     // when we init we get the following from VK
@@ -151,11 +160,11 @@ pub struct RecursiveVerifier<
 
     pub(crate) gate_type_ids_for_specialized_columns: Vec<TypeId>,
     pub(crate) evaluators_over_specialized_columns:
-        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>>,
+        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, DEGREE, EXT, CS>>,
     pub(crate) offsets_for_specialized_evaluators: Vec<(PerChunkOffset, PerChunkOffset, usize)>,
 
     pub(crate) evaluators_over_general_purpose_columns:
-        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>>,
+        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, DEGREE, EXT, CS>>,
 
     pub(crate) total_num_variables_for_specialized_columns: usize,
     pub(crate) total_num_witnesses_for_specialized_columns: usize,
@@ -164,12 +173,16 @@ pub struct RecursiveVerifier<
     pub(crate) placement_strategies: HashMap<TypeId, GatePlacementStrategy>,
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F> + 'static>
-    RecursiveVerifier<F, EXT, CS>
+impl<
+        F: SmallField,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
+        CS: ConstraintSystem<F> + 'static,
+    > RecursiveVerifier<F, DEGREE, EXT, CS>
 {
     #[inline]
     fn num_sublookup_arguments(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_sublookup_arguments(
+        SizeCalculator::<F, DEGREE, EXT>::num_sublookup_arguments(
             &self.parameters,
             &self.lookup_parameters,
         )
@@ -177,7 +190,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
     #[inline]
     pub fn num_multipicities_polys(&self, total_tables_len: usize, domain_size: u64) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_multipicities_polys(
+        SizeCalculator::<F, DEGREE, EXT>::num_multipicities_polys(
             &self.lookup_parameters,
             total_tables_len,
             domain_size,
@@ -185,21 +198,21 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
     }
 
     pub fn num_variable_polys(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_variable_polys(
+        SizeCalculator::<F, DEGREE, EXT>::num_variable_polys(
             &self.parameters,
             self.total_num_variables_for_specialized_columns,
         )
     }
 
     pub fn num_witness_polys(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_witness_polys(
+        SizeCalculator::<F, DEGREE, EXT>::num_witness_polys(
             &self.parameters,
             self.total_num_witnesses_for_specialized_columns,
         )
     }
 
     pub fn num_constant_polys(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_constant_polys(
+        SizeCalculator::<F, DEGREE, EXT>::num_constant_polys(
             &self.parameters,
             vk_fixed_params,
             self.total_num_constants_for_specialized_columns,
@@ -207,7 +220,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
     }
 
     pub fn quotient_degree(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::quotient_degree(vk_fixed_params)
+        SizeCalculator::<F, DEGREE, EXT>::quotient_degree(vk_fixed_params)
     }
 
     pub fn num_copy_permutation_polys(&self) -> usize {
@@ -215,11 +228,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
     }
 
     pub fn num_lookup_table_setup_polys(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_lookup_table_setup_polys(&self.lookup_parameters)
+        SizeCalculator::<F, DEGREE, EXT>::num_lookup_table_setup_polys(&self.lookup_parameters)
     }
 
     pub fn witness_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::witness_leaf_size(
+        SizeCalculator::<F, DEGREE, EXT>::witness_leaf_size(
             &self.parameters,
             &self.lookup_parameters,
             vk_fixed_params,
@@ -229,7 +242,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
     }
 
     pub fn stage_2_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::stage_2_leaf_size(
+        SizeCalculator::<F, DEGREE, EXT>::stage_2_leaf_size(
             &self.parameters,
             &self.lookup_parameters,
             vk_fixed_params,
@@ -238,11 +251,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
     }
 
     pub fn quotient_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::quotient_leaf_size(vk_fixed_params)
+        SizeCalculator::<F, DEGREE, EXT>::quotient_leaf_size(vk_fixed_params)
     }
 
     pub fn setup_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::setup_leaf_size(
+        SizeCalculator::<F, DEGREE, EXT>::setup_leaf_size(
             &self.parameters,
             &self.lookup_parameters,
             vk_fixed_params,
@@ -261,13 +274,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             _num_queries,                 // num queries
             interpolation_log2s_schedule, // folding schedule
             _final_expected_degree,
-        ) = crate::cs::implementations::prover::compute_fri_schedule(
-            proof_config.security_level as u32,
-            proof_config.merkle_tree_cap_size,
-            proof_config.pow_bits,
+        ) = crate::cs::implementations::prover::compute_fri_schedule_for_config(
+            proof_config,
             fixed_parameters.fri_lde_factor.trailing_zeros(),
             fixed_parameters.domain_size.trailing_zeros(),
-        );
+        )
+        .expect("FRI folding schedule must be valid");
 
         interpolation_log2s_schedule
     }
@@ -282,13 +294,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             _num_queries,                 // num queries
             interpolation_log2s_schedule, // folding schedule
             final_expected_degree,
-        ) = crate::cs::implementations::prover::compute_fri_schedule(
-            proof_config.security_level as u32,
-            proof_config.merkle_tree_cap_size,
-            proof_config.pow_bits,
+        ) = crate::cs::implementations::prover::compute_fri_schedule_for_config(
+            proof_config,
             fixed_parameters.fri_lde_factor.trailing_zeros(),
             fixed_parameters.domain_size.trailing_zeros(),
-        );
+        )
+        .expect("FRI folding schedule must be valid");
 
         let mut expected_degree = fixed_parameters.domain_size;
 
@@ -311,13 +322,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             num_queries,                   // num queries
             _interpolation_log2s_schedule, // folding schedule
             _final_expected_degree,
-        ) = crate::cs::implementations::prover::compute_fri_schedule(
-            proof_config.security_level as u32,
-            proof_config.merkle_tree_cap_size,
-            proof_config.pow_bits,
+        ) = crate::cs::implementations::prover::compute_fri_schedule_for_config(
+            proof_config,
             fixed_parameters.fri_lde_factor.trailing_zeros(),
             fixed_parameters.domain_size.trailing_zeros(),
-        );
+        )
+        .expect("FRI folding schedule must be valid");
 
         num_queries
     }
@@ -396,14 +406,13 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         &self,
         cs: &mut CS,
         transcript_params: <TR as Transcript<F>>::TransciptParameters,
-        proof: &AllocatedProof<F, H, EXT>,
+        proof: &AllocatedProof<F, H, DEGREE, EXT>,
         fixed_parameters: &VerificationKeyCircuitGeometry,
         proof_config: &ProofConfig,
         vk: &AllocatedVerificationKey<F, H>,
     ) -> (Boolean<F>, Vec<Num<F>>) {
         assert_eq!(self.parameters, fixed_parameters.parameters);
         assert_eq!(self.lookup_parameters, fixed_parameters.lookup_parameters);
-        assert!(proof_config.fri_folding_schedule.is_none());
         assert_eq!(fixed_parameters.cap_size, proof_config.merkle_tree_cap_size);
         assert_eq!(fixed_parameters.fri_lde_factor, proof_config.fri_lde_factor,);
         assert_eq!(fixed_parameters.cap_size, vk.setup_merkle_tree_cap.len());
@@ -414,8 +423,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
         let zero_base = NumAsFieldWrapper::<F, CS>::zero(cs);
 
-        let zero_ext = NumExtAsFieldWrapper::<F, EXT, CS>::zero(cs);
-        let one_ext = NumExtAsFieldWrapper::<F, EXT, CS>::one(cs);
+        let zero_ext = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::zero(cs);
+        let one_ext = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::one(cs);
 
         let multiplicative_generator =
             NumAsFieldWrapper::constant(F::multiplicative_generator(), cs);
@@ -457,20 +466,20 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
         // draw challenges for stage 2
 
-        let beta = transcript.get_multiple_challenges_fixed::<_, 2>(cs);
-        let beta = NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(beta);
+        let beta = transcript.get_multiple_challenges_fixed::<_, DEGREE>(cs);
+        let beta = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(beta);
 
-        let gamma = transcript.get_multiple_challenges_fixed::<_, 2>(cs);
-        let gamma = NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(gamma);
+        let gamma = transcript.get_multiple_challenges_fixed::<_, DEGREE>(cs);
+        let gamma = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(gamma);
 
         let (lookup_beta, lookup_gamma) = if self.lookup_parameters != LookupParameters::NoLookup {
             // lookup argument related parts
-            let lookup_beta = transcript.get_multiple_challenges_fixed::<_, 2>(cs);
+            let lookup_beta = transcript.get_multiple_challenges_fixed::<_, DEGREE>(cs);
             let lookup_beta =
-                NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(lookup_beta);
-            let lookup_gamma = transcript.get_multiple_challenges_fixed::<_, 2>(cs);
+                NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(lookup_beta);
+            let lookup_gamma = transcript.get_multiple_challenges_fixed::<_, DEGREE>(cs);
             let lookup_gamma =
-                NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(lookup_gamma);
+                NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(lookup_gamma);
 
             (lookup_beta, lookup_gamma)
         } else {
@@ -481,8 +490,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         transcript.witness_merkle_tree_cap(cs, &proof.stage_2_oracle_cap);
 
         // draw challenges for quotient
-        let alpha = transcript.get_multiple_challenges_fixed::<_, 2>(cs);
-        let alpha = NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(alpha);
+        let alpha = transcript.get_multiple_challenges_fixed::<_, DEGREE>(cs);
+        let alpha = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(alpha);
 
         // two extra relations per lookup subargument - for A and B polys
         let num_lookup_subarguments = self.num_sublookup_arguments();
@@ -560,23 +569,23 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         transcript.witness_merkle_tree_cap(cs, &proof.quotient_oracle_cap);
 
         // get z
-        let z = transcript.get_multiple_challenges_fixed::<_, 2>(cs);
-        let z = NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(z);
+        let z = transcript.get_multiple_challenges_fixed::<_, DEGREE>(cs);
+        let z = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(z);
 
         let all_values_at_z: Vec<_> = proof
             .values_at_z
             .iter()
-            .map(|el| NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(*el))
+            .map(|el| NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(*el))
             .collect();
         let all_values_at_z_omega: Vec<_> = proof
             .values_at_z_omega
             .iter()
-            .map(|el| NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(*el))
+            .map(|el| NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(*el))
             .collect();
         let all_values_at_0: Vec<_> = proof
             .values_at_0
             .iter()
-            .map(|el| NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(*el))
+            .map(|el| NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(*el))
             .collect();
 
         // commit claimed values at z, and form our poly storage
@@ -687,7 +696,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             let mut source_it = all_values_at_z_omega.iter();
             let copy_permutation_z_at_z_omega = *source_it.next().unwrap();
 
-            let mut t_accumulator = NumExtAsFieldWrapper::<F, EXT, CS>::zero(cs);
+            let mut t_accumulator = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::zero(cs);
             // precompute selectors at z
 
             let mut selectors_buffer = HashMap::new();
@@ -723,12 +732,13 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 let multiplicities_encoding_polys_at_0 =
                     &all_values_at_0[num_lookup_subarguments..];
 
-                let mut witness_subsum = NumExtAsFieldWrapper::<F, EXT, CS>::zero(cs);
+                let mut witness_subsum = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::zero(cs);
                 for a in lookup_witness_encoding_polys_polys_at_0.iter() {
                     witness_subsum.add_assign(a, cs);
                 }
 
-                let mut multiplicities_subsum = NumExtAsFieldWrapper::<F, EXT, CS>::zero(cs);
+                let mut multiplicities_subsum =
+                    NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::zero(cs);
                 for b in multiplicities_encoding_polys_at_0.iter() {
                     multiplicities_subsum.add_assign(b, cs);
                 }
@@ -736,11 +746,10 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 let witness_subsum = witness_subsum.into_num_coeffs_in_base();
                 let multiplicities_subsum = multiplicities_subsum.into_num_coeffs_in_base();
 
-                let c0_is_valid = Num::equals(cs, &witness_subsum[0], &multiplicities_subsum[0]);
-                let c1_is_valid = Num::equals(cs, &witness_subsum[1], &multiplicities_subsum[1]);
-
-                validity_flags.push(c0_is_valid);
-                validity_flags.push(c1_is_valid);
+                for (a, b) in witness_subsum.iter().zip(multiplicities_subsum.iter()) {
+                    let is_valid = Num::equals(cs, a, b);
+                    validity_flags.push(is_valid);
+                }
 
                 // lookup argument related parts
                 match self.lookup_parameters {
@@ -777,7 +786,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                         let capacity = column_elements_per_subargument
                             + ((fixed_parameters.table_ids_column_idxes.len() == 1) as usize);
                         let mut powers_of_gamma = Vec::with_capacity(capacity);
-                        let mut tmp = NumExtAsFieldWrapper::<F, EXT, CS>::one(cs);
+                        let mut tmp = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::one(cs);
                         powers_of_gamma.push(tmp);
                         for _idx in 1..capacity {
                             if _idx == 1 {
@@ -795,7 +804,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                         for (gamma, column) in
                             powers_of_gamma.iter().zip(lookup_tables_columns.iter())
                         {
-                            NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                            NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_and_accumulate_into(
                                 &mut lookup_table_columns_aggregated,
                                 gamma,
                                 column,
@@ -838,7 +847,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                                 .iter()
                                 .zip(witness_columns.iter().chain(table_id.iter()))
                             {
-                                NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                                NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_and_accumulate_into(
                                     &mut contribution,
                                     gamma,
                                     column,
@@ -852,7 +861,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                             contribution.sub_assign(&selector, cs);
 
                             // mul by power of challenge and accumulate
-                            NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                            NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_and_accumulate_into(
                                 &mut t_accumulator,
                                 &alpha,
                                 &contribution,
@@ -882,7 +891,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                             contribution.sub_assign(multiplicities_poly, cs);
 
                             // mul by power of challenge and accumulate
-                            NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                            NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_and_accumulate_into(
                                 &mut t_accumulator,
                                 &alpha,
                                 &contribution,
@@ -918,7 +927,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                         let capacity = column_elements_per_subargument
                             + ((fixed_parameters.table_ids_column_idxes.len() == 1) as usize);
                         let mut powers_of_gamma = Vec::with_capacity(capacity);
-                        let mut tmp = NumExtAsFieldWrapper::<F, EXT, CS>::one(cs);
+                        let mut tmp = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::one(cs);
                         powers_of_gamma.push(tmp);
                         for _idx in 1..capacity {
                             if _idx == 1 {
@@ -936,7 +945,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                         for (gamma, column) in
                             powers_of_gamma.iter().zip(lookup_tables_columns.iter())
                         {
-                            NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                            NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_and_accumulate_into(
                                 &mut lookup_table_columns_aggregated,
                                 gamma,
                                 column,
@@ -982,7 +991,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                                 .iter()
                                 .zip(witness_columns.iter().chain(table_id.iter()))
                             {
-                                NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                                NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_and_accumulate_into(
                                     &mut contribution,
                                     gamma,
                                     column,
@@ -996,7 +1005,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                             contribution.sub_assign(&one_ext, cs);
 
                             // mul by power of challenge and accumulate
-                            NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                            NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_and_accumulate_into(
                                 &mut t_accumulator,
                                 &alpha,
                                 &contribution,
@@ -1026,7 +1035,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                             contribution.sub_assign(multiplicities_poly, cs);
 
                             // mul by power of challenge and accumulate
-                            NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                            NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_and_accumulate_into(
                                 &mut t_accumulator,
                                 &alpha,
                                 &contribution,
@@ -1266,7 +1275,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 contribution.mul_assign(&unnormalized_l1_inverse_at_z, cs);
 
                 // mul by power of challenge and accumulate
-                NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_and_accumulate_into(
                     &mut t_accumulator,
                     &alpha,
                     &contribution,
@@ -1319,7 +1328,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 contribution.sub_assign(&rhs, cs);
 
                 // mul by power of challenge and accumulate
-                NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_and_accumulate_into(
                     &mut t_accumulator,
                     &alpha,
                     &contribution,
@@ -1335,7 +1344,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             let mut t_from_chunks = zero_ext;
             let mut pow = one_ext;
             for el in quotient_chunks.into_iter() {
-                NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_and_accumulate_into(
                     &mut t_from_chunks,
                     &el,
                     &pow,
@@ -1354,16 +1363,14 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             let t_accumulator = t_accumulator.into_num_coeffs_in_base();
             let t_from_chunks = t_from_chunks.into_num_coeffs_in_base();
 
-            let c0_is_valid = Num::equals(cs, &t_accumulator[0], &t_from_chunks[0]);
-            let c1_is_valid = Num::equals(cs, &t_accumulator[1], &t_from_chunks[1]);
-
-            validity_flags.push(c0_is_valid);
-            validity_flags.push(c1_is_valid);
+            for (a, b) in t_accumulator.iter().zip(t_from_chunks.iter()) {
+                let is_valid = Num::equals(cs, a, b);
+                validity_flags.push(is_valid);
+            }
         }
 
         // get challenges
-        let c0 = transcript.get_challenge(cs);
-        let c1 = transcript.get_challenge(cs);
+        let challenge: [Num<F>; DEGREE] = std::array::from_fn(|_| transcript.get_challenge(cs));
 
         let expected_lookup_polys_total = if self.lookup_parameters.lookup_is_allowed() {
             num_lookup_subarguments + // lookup witness encoding polys
@@ -1393,22 +1400,22 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         assert_eq!(all_values_at_z_omega.len(), 1);
         assert_eq!(all_values_at_0.len(), total_num_lookup_argument_terms);
 
-        let challenge = NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([c0, c1]);
+        let challenge =
+            NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(challenge);
         let challenges_for_fri_quotiening =
-            materialize_powers_serial::<F, EXT, CS>(cs, challenge, total_num_challenges);
+            materialize_powers_serial::<F, DEGREE, EXT, CS>(cs, challenge, total_num_challenges);
 
         let (
             new_pow_bits,                 // updated POW bits if needed
             num_queries,                  // num queries
             interpolation_log2s_schedule, // folding schedule
             final_expected_degree,
-        ) = crate::cs::implementations::prover::compute_fri_schedule(
-            proof_config.security_level as u32,
-            proof_config.merkle_tree_cap_size,
-            proof_config.pow_bits,
+        ) = crate::cs::implementations::prover::compute_fri_schedule_for_config(
+            proof_config,
             fixed_parameters.fri_lde_factor.trailing_zeros(),
             fixed_parameters.domain_size.trailing_zeros(),
-        );
+        )
+        .expect("FRI folding schedule must be valid");
 
         let mut expected_degree = fixed_parameters.domain_size;
 
@@ -1423,12 +1430,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
             let reduction_degree_log_2 = interpolation_log2s_schedule[0];
 
-            let c0 = transcript.get_challenge(cs);
-            let c1 = transcript.get_challenge(cs);
+            let challenge: [Num<F>; DEGREE] = std::array::from_fn(|_| transcript.get_challenge(cs));
 
             let mut challenge_powers = Vec::with_capacity(reduction_degree_log_2);
             let as_extension =
-                NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([c0, c1]);
+                NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(challenge);
             challenge_powers.push(as_extension);
 
             let mut current = as_extension;
@@ -1457,12 +1463,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
             // get challenge
             let reduction_degree_log_2 = *interpolation_degree_log2;
-            let c0 = transcript.get_challenge(cs);
-            let c1 = transcript.get_challenge(cs);
+            let challenge: [Num<F>; DEGREE] = std::array::from_fn(|_| transcript.get_challenge(cs));
 
             let mut challenge_powers = Vec::with_capacity(reduction_degree_log_2);
             let as_extension =
-                NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([c0, c1]);
+                NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(challenge);
             challenge_powers.push(as_extension);
 
             let mut current = as_extension;
@@ -1477,17 +1482,15 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         }
 
         assert_eq!(final_expected_degree, expected_degree as usize);
-        assert_eq!(
-            proof.final_fri_monomials[0].len(),
-            proof.final_fri_monomials[1].len()
-        );
-        assert_eq!(expected_degree as usize, proof.final_fri_monomials[0].len());
-        assert_eq!(expected_degree as usize, proof.final_fri_monomials[1].len());
+        for el in proof.final_fri_monomials.iter() {
+            assert_eq!(expected_degree as usize, el.len());
+        }
         assert!(proof.final_fri_monomials[0].len() > 0);
 
         // witness monomial coeffs
-        transcript.witness_field_elements(cs, &proof.final_fri_monomials[0]);
-        transcript.witness_field_elements(cs, &proof.final_fri_monomials[1]);
+        for el in proof.final_fri_monomials.iter() {
+            transcript.witness_field_elements(cs, el);
+        }
 
         if new_pow_bits != 0 {
             log!("Doing PoW verification for {} bits", new_pow_bits);
@@ -1498,9 +1501,18 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             if num_challenges % F::CHAR_BITS != 0 {
                 num_challenges += 1;
             }
-            let _challenges: Vec<_> = transcript.get_multiple_challenges(cs, num_challenges);
+            let challenges: Vec<_> = transcript.get_multiple_challenges(cs, num_challenges);
 
-            todo!()
+            let challenge_limbs = pow_challenge_into_limbs(cs, &proof.pow_challenge);
+            let pow_is_valid = POW::CircuitReflection::verify_from_field_elements(
+                cs,
+                challenges,
+                new_pow_bits,
+                challenge_limbs,
+            );
+            validity_flags.push(pow_is_valid);
+
+            transcript.witness_field_elements(cs, &challenge_limbs);
         }
 
         let max_needed_bits = (fixed_parameters.domain_size
@@ -1568,10 +1580,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
         let base_oracle_depth = fixed_parameters.base_oracles_depth();
 
-        let witness_leaf_size = self.witness_leaf_size(fixed_parameters);
+        let leaf_salt_size = proof_config.leaf_salt_size();
 
-        let stage_2_leaf_size = self.stage_2_leaf_size(fixed_parameters);
-        let quotient_leaf_size = self.quotient_leaf_size(fixed_parameters);
+        let witness_leaf_size = self.witness_leaf_size(fixed_parameters) + leaf_salt_size;
+
+        let stage_2_leaf_size = self.stage_2_leaf_size(fixed_parameters) + leaf_salt_size;
+        let quotient_leaf_size = self.quotient_leaf_size(fixed_parameters) + leaf_salt_size;
 
         let setup_leaf_size = self.setup_leaf_size(fixed_parameters);
 
@@ -1655,6 +1669,17 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             );
             validity_flags.push(is_included);
 
+            // salt is only needed for inclusion proofs
+            let mut queries = queries.clone();
+            for query in [
+                &mut queries.witness_query,
+                &mut queries.stage_2_query,
+                &mut queries.quotient_query,
+            ] {
+                let num_elements = query.leaf_elements.len() - leaf_salt_size;
+                query.leaf_elements.truncate(num_elements);
+            }
+
             // now perform the quotiening operation
             let mut simulated_ext_element = zero_ext;
 
@@ -1691,11 +1716,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             let mut challenge_offset = 0;
 
             let z_polys_offset = 0;
-            let intermediate_polys_offset = 2;
+            let intermediate_polys_offset = DEGREE;
             let lookup_witness_encoding_polys_offset =
-                intermediate_polys_offset + num_intermediate_partial_product_relations * 2;
+                intermediate_polys_offset + num_intermediate_partial_product_relations * DEGREE;
             let lookup_multiplicities_encoding_polys_offset =
-                lookup_witness_encoding_polys_offset + num_lookup_subarguments * 2;
+                lookup_witness_encoding_polys_offset + num_lookup_subarguments * DEGREE;
             let copy_permutation_polys_offset = 0;
             let constants_offset = 0 + num_copy_permutation_polys;
             let lookup_tables_values_offset = 0 + num_copy_permutation_polys + num_constant_polys;
@@ -1711,19 +1736,20 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 let cast_from_base = move |el: &[Num<F>]| {
                     el.iter()
                         .map(|el| {
-                            NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([
-                                *el, zero_num,
-                            ])
+                            NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_base(
+                                (*el).into(),
+                                zero_num.into(),
+                            )
                         })
                         .collect::<Vec<_>>()
                 };
 
                 let cast_from_extension = move |el: &[Num<F>]| {
-                    assert_eq!(el.len() % 2, 0);
+                    assert_eq!(el.len() % DEGREE, 0);
 
-                    el.array_chunks::<2>()
-                        .map(|[c0, c1]| {
-                            NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([*c0, *c1])
+                    el.array_chunks::<DEGREE>()
+                        .map(|el| {
+                            NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(*el)
                         })
                         .collect::<Vec<_>>()
                 };
@@ -1854,13 +1880,13 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 for (column, expected_value) in set.into_iter() {
                     let c0 = queries.witness_query.leaf_elements[*column];
                     let el =
-                        NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([c0, zero_num]);
+                        NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_base(c0.into(), zero_base);
                     sources.push(el);
 
-                    let value = NumExtAsFieldWrapper::<F, EXT, CS>::from_coeffs_in_base([
+                    let value = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_base(
                         *expected_value,
                         zero_base,
-                    ]);
+                    );
                     values.push(value);
                 }
                 let num_challenges_required = sources.len();
@@ -1870,7 +1896,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
                 let open_at = NumAsFieldWrapper::constant(*open_at, cs);
                 let open_at =
-                    NumExtAsFieldWrapper::<F, EXT, CS>::from_coeffs_in_base([open_at, zero_base]);
+                    NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_base(open_at, zero_base);
 
                 quotening_operation(
                     cs,
@@ -1904,26 +1930,18 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 let subidx_in_leaf = &subidx[..*interpolation_degree_log2];
                 let tree_idx = &subidx[*interpolation_degree_log2..];
 
-                assert_eq!(fri_query.leaf_elements.len(), interpolation_degree * 2);
-
-                let [c0, c1] = current_folded_value.into_num_coeffs_in_base();
-
-                let c0_from_leaf = binary_select(
-                    cs,
-                    &fri_query.leaf_elements[..interpolation_degree],
-                    subidx_in_leaf,
-                );
-                let c1_from_leaf = binary_select(
-                    cs,
-                    &fri_query.leaf_elements[interpolation_degree..],
-                    subidx_in_leaf,
-                );
+                assert_eq!(fri_query.leaf_elements.len(), interpolation_degree * DEGREE);
 
-                let c0_is_valid = Num::equals(cs, &c0, &c0_from_leaf);
-                let c1_is_valid = Num::equals(cs, &c1, &c1_from_leaf);
+                let coeffs = current_folded_value.into_num_coeffs_in_base();
 
-                validity_flags.push(c0_is_valid);
-                validity_flags.push(c1_is_valid);
+                for (c, leaf_elements) in coeffs
+                    .iter()
+                    .zip(fri_query.leaf_elements.chunks_exact(interpolation_degree))
+                {
+                    let c_from_leaf = binary_select(cs, leaf_elements, subidx_in_leaf);
+                    let is_valid = Num::equals(cs, c, &c_from_leaf);
+                    validity_flags.push(is_valid);
+                }
 
                 // verify query itself
                 let cap = if idx == 0 {
@@ -1947,12 +1965,13 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
                 // interpolate
                 let mut elements_to_interpolate = Vec::with_capacity(interpolation_degree);
-                for (c0, c1) in fri_query.leaf_elements[..interpolation_degree]
-                    .iter()
-                    .zip(fri_query.leaf_elements[interpolation_degree..].iter())
-                {
+                for idx in 0..interpolation_degree {
                     let as_ext =
-                        NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([*c0, *c1]);
+                        NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(
+                            std::array::from_fn(|i| {
+                                fri_query.leaf_elements[i * interpolation_degree + idx]
+                            }),
+                        );
                     elements_to_interpolate.push(as_ext);
                 }
 
@@ -1976,7 +1995,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                         let coset_inverse = NumAsFieldWrapper::constant(coset_inverse, cs);
                         pow.mul_assign(&coset_inverse, cs);
 
-                        NumExtAsFieldWrapper::<F, EXT, CS>::mul_by_base_and_accumulate_into(
+                        NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_by_base_and_accumulate_into(
                             &mut result,
                             &pow,
                             &diff,
@@ -2008,17 +2027,15 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
             let mut result_from_monomial = zero_ext;
             // horner rule
-            for (c0, c1) in proof.final_fri_monomials[0]
-                .iter()
-                .zip(proof.final_fri_monomials[1].iter())
-                .rev()
-            {
-                let coeff = NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([*c0, *c1]);
+            for idx in (0..proof.final_fri_monomials[0].len()).rev() {
+                let coeff = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_num_coeffs_in_base(
+                    std::array::from_fn(|i| proof.final_fri_monomials[i][idx]),
+                );
 
                 // result_from_monomial = result_from_monomial * z + coeff
 
                 let mut tmp = coeff;
-                NumExtAsFieldWrapper::<F, EXT, CS>::mul_by_base_and_accumulate_into(
+                NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_by_base_and_accumulate_into(
                     &mut tmp,
                     &domain_element_for_interpolation,
                     &result_from_monomial,
@@ -2034,11 +2051,10 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             let result_from_monomial = result_from_monomial.into_num_coeffs_in_base();
             let current_folded_value = current_folded_value.into_num_coeffs_in_base();
 
-            let c0_is_valid = Num::equals(cs, &result_from_monomial[0], &current_folded_value[0]);
-            let c1_is_valid = Num::equals(cs, &result_from_monomial[1], &current_folded_value[1]);
-
-            validity_flags.push(c0_is_valid);
-            validity_flags.push(c1_is_valid);
+            for (a, b) in result_from_monomial.iter().zip(current_folded_value.iter()) {
+                let is_valid = Num::equals(cs, a, b);
+                validity_flags.push(is_valid);
+            }
         }
 
         let is_valid = Boolean::multi_and(cs, &validity_flags);
@@ -2049,16 +2065,17 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
 fn quotening_operation<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     CS: ConstraintSystem<F> + 'static,
 >(
     cs: &mut CS,
-    dst: &mut NumExtAsFieldWrapper<F, EXT, CS>,
-    polynomial_values: &Vec<NumExtAsFieldWrapper<F, EXT, CS>>,
-    values_at: &Vec<NumExtAsFieldWrapper<F, EXT, CS>>,
+    dst: &mut NumExtAsFieldWrapper<F, DEGREE, EXT, CS>,
+    polynomial_values: &Vec<NumExtAsFieldWrapper<F, DEGREE, EXT, CS>>,
+    values_at: &Vec<NumExtAsFieldWrapper<F, DEGREE, EXT, CS>>,
     domain_element: NumAsFieldWrapper<F, CS>,
-    at: NumExtAsFieldWrapper<F, EXT, CS>,
-    challenges: &[NumExtAsFieldWrapper<F, EXT, CS>],
+    at: NumExtAsFieldWrapper<F, DEGREE, EXT, CS>,
+    challenges: &[NumExtAsFieldWrapper<F, DEGREE, EXT, CS>],
 ) {
     // we precompute challenges outside to avoid any manual extension ops here
     assert_eq!(polynomial_values.len(), values_at.len());
@@ -2067,11 +2084,11 @@ fn quotening_operation<
     let zero_base = NumAsFieldWrapper::zero(cs);
 
     let mut denom =
-        NumExtAsFieldWrapper::<F, EXT, CS>::from_coeffs_in_base([domain_element, zero_base]);
+        NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::from_base(domain_element, zero_base);
     denom.sub_assign(&at, cs);
     denom = denom.inverse(cs);
 
-    let mut acc = NumExtAsFieldWrapper::<F, EXT, CS>::zero(cs);
+    let mut acc = NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::zero(cs);
 
     for ((poly_value, value_at), challenge) in polynomial_values
         .iter()
@@ -2082,14 +2099,16 @@ fn quotening_operation<
         let mut tmp = *poly_value;
         tmp.sub_assign(&value_at, cs);
 
-        NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(&mut acc, &tmp, &challenge, cs);
+        NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_and_accumulate_into(
+            &mut acc, &tmp, &challenge, cs,
+        );
 
         // let mut as_ext = *challenge;
         // as_ext.mul_assign(&tmp, cs);
         // acc.add_assign(&as_ext, cs);
     }
 
-    NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(dst, &acc, &denom, cs);
+    NumExtAsFieldWrapper::<F, DEGREE, EXT, CS>::mul_and_accumulate_into(dst, &acc, &denom, cs);
 
     // acc.mul_assign(&denom, cs);
     // dst.add_assign(&acc, cs);
@@ -2159,38 +2178,6 @@ pub fn verify_proof_over_cap<
     H::compare_output(cs, &current, &selected_cap_el)
 }
 
-pub(crate) fn binary_select<F: SmallField, T: Selectable<F>, CS: ConstraintSystem<F>>(
-    cs: &mut CS,
-    elements: &[T],
-    bits: &[Boolean<F>],
-) -> T {
-    assert_eq!(elements.len(), 1 << bits.len());
-    assert!(bits.len() > 0);
-
-    let mut input_space = Vec::with_capacity(elements.len() / 2);
-    let mut dst_space = Vec::with_capacity(elements.len() / 2);
-
-    for (idx, bit) in bits.iter().enumerate() {
-        let src = if idx == 0 { elements } else { &input_space };
-
-        debug_assert_eq!(elements.len() % 2, 0);
-        dst_space.clear();
-
-        for src in src.array_chunks::<2>() {
-            let [a, b] = src;
-            // NOTE order here
-            let selected = T::conditionally_select(cs, *bit, b, a);
-            dst_space.push(selected);
-        }
-
-        std::mem::swap(&mut dst_space, &mut input_space);
-    }
-
-    assert_eq!(input_space.len(), 1);
-
-    input_space.pop().unwrap()
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -2205,6 +2192,7 @@ mod test {
     use crate::cs::implementations::pow::NoPow;
     use crate::cs::implementations::transcript::*;
     use crate::field::goldilocks::{GoldilocksExt2, GoldilocksField};
+    use crate::field::{Field, U64Representable};
     use crate::gadgets::recursion::recursive_verifier_builder::CsRecursiveVerifierBuilder;
     use crate::gadgets::traits::witnessable::WitnessHookable;
     use crate::implementations::poseidon2::Poseidon2Goldilocks;
@@ -2284,10 +2272,10 @@ mod test {
         use crate::cs::implementations::verifier::VerificationKey;
 
         let vk: VerificationKey<F, H> = serde_json::from_reader(&mut vk_file).unwrap();
-        let proof: Proof<F, H, EXT> = serde_json::from_reader(&mut proof_file).unwrap();
+        let proof: Proof<F, H, 2, EXT> = serde_json::from_reader(&mut proof_file).unwrap();
 
         let builder_impl =
-            CsVerifierBuilder::<F, EXT>::new_from_parameters(vk.fixed_parameters.parameters);
+            CsVerifierBuilder::<F, 2, EXT>::new_from_parameters(vk.fixed_parameters.parameters);
         let builder = new_builder::<_, GoldilocksField>(builder_impl);
         // copy parameters from actual circuit
         let builder = builder.allow_lookup(
@@ -2366,7 +2354,7 @@ mod test {
         let is_valid_proof = verifier.verify::<H, TR, NoPow>((), &vk, &proof);
         assert!(is_valid_proof);
 
-        let builder_impl = CsRecursiveVerifierBuilder::<'_, F, EXT, _>::new_from_parameters(
+        let builder_impl = CsRecursiveVerifierBuilder::<'_, F, 2, EXT, _>::new_from_parameters(
             &mut cs,
             vk.fixed_parameters.parameters,
         );
@@ -2448,7 +2436,7 @@ mod test {
         use crate::gadgets::traits::allocatable::CSAllocatable;
 
         let allocated_vk = AllocatedVerificationKey::<F, RH>::allocate(&mut cs, vk.clone());
-        let allocated_proof = AllocatedProof::<F, RH, EXT>::allocate_from_witness(
+        let allocated_proof = AllocatedProof::<F, RH, 2, EXT>::allocate_from_witness(
             &mut cs,
             Some(proof.clone()),
             &verifier,
@@ -2472,4 +2460,360 @@ mod test {
 
         dbg!(cs.next_available_row());
     }
+
+    mod helpers {
+        use super::*;
+        use crate::cs::cs_builder::*;
+        use crate::cs::gates::u32_tri_add_carry_as_chunk::U32TriAddCarryAsChunkGate;
+        use crate::cs::implementations::proof::Proof;
+        use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+        use crate::cs::implementations::verifier::VerificationKey;
+        use crate::cs::oracle::TreeHasher;
+        use crate::cs::{GateConfigurationHolder, StaticToolboxHolder};
+        use crate::gadgets::tables::*;
+        use crate::gadgets::traits::allocatable::CSAllocatable;
+        use crate::worker::Worker;
+
+        pub(super) type F = GoldilocksField;
+        pub(super) type EXT = GoldilocksExt2;
+
+        pub(super) const INNER_GEOMETRY: CSGeometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 8,
+        };
+
+        pub(super) fn configure_inner<
+            T: CsBuilderImpl<F, T>,
+            GC: GateConfigurationHolder<F>,
+            TB: StaticToolboxHolder,
+        >(
+            builder: CsBuilder<T, F, GC, TB>,
+        ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+            let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = ZeroCheckGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+                false,
+            );
+            let builder = NopGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+
+            builder
+        }
+
+        // small circuit with domain size of at least 2^6
+        pub(super) fn prove_inner<
+            TR: Transcript<F, TransciptParameters = ()>,
+            H: TreeHasher<F, Output = TR::CompatibleCap>,
+        >(
+            worker: &Worker,
+            proof_config: ProofConfig,
+        ) -> (Proof<F, H, 2, EXT>, VerificationKey<F, H>) {
+            let builder_impl = CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(
+                INNER_GEOMETRY,
+                512,
+                128,
+            );
+            let builder = new_builder::<_, F>(builder_impl);
+            let builder = configure_inner(builder);
+            let mut cs = builder.build(());
+
+            let mut previous = None;
+            for _ in 0..36 {
+                let a = previous.take().unwrap_or_else(|| {
+                    cs.alloc_single_variable_from_witness(F::from_u64_unchecked(1))
+                });
+                let b = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(2));
+                let c = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(3));
+
+                let d = FmaGateInBaseFieldWithoutConstant::compute_fma(
+                    &mut cs,
+                    F::TWO,
+                    (a, b),
+                    F::MINUS_ONE,
+                    c,
+                );
+
+                previous = Some(ZeroCheckGate::check_if_zero(&mut cs, d));
+            }
+
+            cs.pad_and_shrink();
+            let cs = cs.into_assembly();
+
+            let (proof, vk) = cs.prove_one_shot::<2, EXT, TR, H, NoPow>(worker, proof_config, ());
+            assert!(vk.fixed_parameters.domain_size >= 1 << 6);
+
+            let builder_impl = CsVerifierBuilder::<F, 2, EXT>::new_from_parameters(INNER_GEOMETRY);
+            let builder = new_builder::<_, F>(builder_impl);
+            let builder = configure_inner(builder);
+            let verifier = builder.build(());
+            assert!(verifier.verify::<H, TR, NoPow>((), &vk, &proof));
+
+            (proof, vk)
+        }
+
+        // Outer circuit supports both algebraic and byte oriented hashes
+        // Lookup tables for the outer circuit. Only Blake2s and Keccak256 gadgets use lookups,
+        // and every added table must be used, otherwise it can not be padded
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        pub(super) enum OuterTables {
+            None,
+            Blake2s,
+            Keccak256,
+        }
+
+        pub(super) fn verify_inner_recursively<
+            RH: RecursiveTreeHasher<F, Num<F>>,
+            TR: RecursiveTranscript<
+                F,
+                CompatibleCap = <RH::NonCircuitSimulator as TreeHasher<F>>::Output,
+                CircuitReflection = CTR,
+                TransciptParameters = (),
+            >,
+            CTR: CircuitTranscript<
+                F,
+                CircuitCompatibleCap = <RH as CircuitTreeHasher<F, Num<F>>>::CircuitOutput,
+                TransciptParameters = (),
+            >,
+        >(
+            worker: &Worker,
+            proof: Proof<F, RH::NonCircuitSimulator, 2, EXT>,
+            vk: VerificationKey<F, RH::NonCircuitSimulator>,
+            tables: OuterTables,
+        ) {
+            let geometry = CSGeometry {
+                num_columns_under_copy_permutation: 132,
+                num_witness_columns: 0,
+                num_constant_columns: 4,
+                max_allowed_constraint_degree: 8,
+            };
+
+            let builder_impl = CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(
+                geometry,
+                1 << 22,
+                1 << 18,
+            );
+            let builder = new_builder::<_, F>(builder_impl);
+
+            if tables != OuterTables::None {
+                let builder = builder.allow_lookup(
+                    LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                        width: 3,
+                        num_repetitions: 5,
+                        share_table_id: true,
+                    },
+                );
+                let builder = configure_outer(builder);
+                let mut cs = builder.build(());
+
+                let table = create_xor8_table();
+                cs.add_lookup_table::<Xor8Table, 3>(table);
+                let table = create_byte_split_table::<F, 1>();
+                cs.add_lookup_table::<ByteSplitTable<1>, 3>(table);
+                let table = create_byte_split_table::<F, 4>();
+                cs.add_lookup_table::<ByteSplitTable<4>, 3>(table);
+                if tables == OuterTables::Blake2s {
+                    let table = create_byte_split_table::<F, 7>();
+                    cs.add_lookup_table::<ByteSplitTable<7>, 3>(table);
+                } else {
+                    let table = create_and8_table();
+                    cs.add_lookup_table::<And8Table, 3>(table);
+                    let table = create_byte_split_table::<F, 2>();
+                    cs.add_lookup_table::<ByteSplitTable<2>, 3>(table);
+                    let table = create_byte_split_table::<F, 3>();
+                    cs.add_lookup_table::<ByteSplitTable<3>, 3>(table);
+                }
+
+                verify_in_cs::<RH, TR, CTR, _, _>(worker, cs, proof, vk);
+            } else {
+                let builder = configure_outer(builder);
+                let cs = builder.build(());
+
+                verify_in_cs::<RH, TR, CTR, _, _>(worker, cs, proof, vk);
+            }
+        }
+
+        fn configure_outer<
+            T: CsBuilderImpl<F, T>,
+            GC: GateConfigurationHolder<F>,
+            TB: StaticToolboxHolder,
+        >(
+            builder: CsBuilder<T, F, GC, TB>,
+        ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+            type Poseidon2Gate =
+                Poseidon2FlattenedGate<GoldilocksField, 8, 12, 4, Poseidon2Goldilocks>;
+
+            let builder = ConstantsAllocatorGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = BooleanConstraintGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = Poseidon2Gate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = U32TriAddCarryAsChunkGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = SelectionGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = ReductionGate::<F, 4>::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = ZeroCheckGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+                false,
+            );
+            let builder = NopGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+
+            builder
+        }
+
+        fn verify_in_cs<
+            RH: RecursiveTreeHasher<F, Num<F>>,
+            TR: RecursiveTranscript<
+                F,
+                CompatibleCap = <RH::NonCircuitSimulator as TreeHasher<F>>::Output,
+                CircuitReflection = CTR,
+                TransciptParameters = (),
+            >,
+            CTR: CircuitTranscript<
+                F,
+                CircuitCompatibleCap = <RH as CircuitTreeHasher<F, Num<F>>>::CircuitOutput,
+                TransciptParameters = (),
+            >,
+            GC: GateConfigurationHolder<F>,
+            TB: StaticToolboxHolder,
+        >(
+            worker: &Worker,
+            mut cs: CSReferenceImplementation<F, F, DevCSConfig, GC, TB>,
+            proof: Proof<F, RH::NonCircuitSimulator, 2, EXT>,
+            vk: VerificationKey<F, RH::NonCircuitSimulator>,
+        ) {
+            let builder_impl = CsRecursiveVerifierBuilder::<'_, F, 2, EXT, _>::new_from_parameters(
+                &mut cs,
+                INNER_GEOMETRY,
+            );
+            let builder = new_builder::<_, F>(builder_impl);
+            let builder = configure_inner(builder);
+            let recursive_verifier = builder.build(());
+
+            let allocated_vk = AllocatedVerificationKey::<F, RH>::allocate(&mut cs, vk.clone());
+            let allocated_proof = AllocatedProof::<F, RH, 2, EXT>::allocate_from_witness(
+                &mut cs,
+                Some(proof.clone()),
+                &recursive_verifier,
+                &vk.fixed_parameters,
+                &proof.proof_config,
+            );
+
+            let (is_valid, _) = recursive_verifier.verify::<RH, TR, CTR, NoPow>(
+                &mut cs,
+                (),
+                &allocated_proof,
+                &vk.fixed_parameters,
+                &proof.proof_config,
+                &allocated_vk,
+            );
+
+            assert!(is_valid.witness_hook(&cs)().unwrap());
+
+            cs.pad_and_shrink();
+            let mut cs = cs.into_assembly();
+            assert!(cs.check_if_satisfied(worker));
+        }
+    }
+
+    #[test]
+    fn test_recursive_verification_with_custom_fri_schedules() {
+        use self::helpers::*;
+        use crate::worker::Worker;
+
+        type TR = GoldilocksPoisedon2Transcript;
+        type CTR = GoldilocksPoisedon2CircuitTranscript;
+        type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+        type RH = CircuitGoldilocksPoseidon2Sponge;
+
+        let worker = Worker::new_with_num_threads(4);
+
+        // cap size 16 with LDE factor 8 requires final degree to be at least 2,
+        // so all the schedules below are valid for domain size 2^6 and above
+        for schedule in [vec![3, 2], vec![2, 2, 1], vec![1, 3, 1], vec![2, 1, 1, 1]] {
+            let mut proof_config = ProofConfig::default();
+            proof_config.fri_lde_factor = 8;
+            proof_config.security_level = 80;
+            proof_config.pow_bits = 0;
+            proof_config.fri_folding_schedule = Some(schedule.clone());
+
+            let (proof, vk) = prove_inner::<TR, H>(&worker, proof_config);
+            assert_eq!(
+                proof.fri_intermediate_oracles_caps.len(),
+                schedule.len() - 1
+            );
+
+            // proof can not be verified against some other schedule
+            let builder_impl = CsVerifierBuilder::<F, 2, EXT>::new_from_parameters(INNER_GEOMETRY);
+            let builder = new_builder::<_, F>(builder_impl);
+            let builder = configure_inner(builder);
+            let verifier = builder.build(());
+            let mut malformed_proof = proof.clone();
+            let num_foldings = schedule.iter().sum();
+            malformed_proof.proof_config.fri_folding_schedule = Some(vec![1; num_foldings]);
+            assert!(!verifier.verify::<H, TR, NoPow>((), &vk, &malformed_proof));
+
+            verify_inner_recursively::<RH, TR, CTR>(&worker, proof, vk, OuterTables::None);
+        }
+    }
+
+    #[test]
+    fn test_recursive_verification_of_byte_oriented_proofs() {
+        use self::helpers::*;
+        use crate::worker::Worker;
+
+        let worker = Worker::new_with_num_threads(4);
+
+        // few queries, as every Merkle path in the circuit costs a lot of hashes
+        let mut proof_config = ProofConfig::default();
+        proof_config.fri_lde_factor = 8;
+        proof_config.security_level = 18;
+        proof_config.pow_bits = 0;
+
+        let (proof, vk) =
+            prove_inner::<Blake2sTranscript, blake2::Blake2s256>(&worker, proof_config.clone());
+        verify_inner_recursively::<
+            CircuitBlake2sTreeHasher<F>,
+            Blake2sTranscript,
+            CircuitBlake2sTranscript<F>,
+        >(&worker, proof, vk, OuterTables::Blake2s);
+
+        let (proof, vk) =
+            prove_inner::<Keccak256Transcript, sha3::Keccak256>(&worker, proof_config);
+        verify_inner_recursively::<
+            CircuitKeccak256TreeHasher<F>,
+            Keccak256Transcript,
+            CircuitKeccak256Transcript<F>,
+        >(&worker, proof, vk, OuterTables::Keccak256);
+    }
 }
@@ -31,7 +31,8 @@ use std::{any::TypeId, collections::HashMap};
 #[derivative(Debug)]
 pub(crate) struct TypeErasedGateEvaluationRecursiveVerificationFunction<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     CS: ConstraintSystem<F> + 'static,
 > {
     pub(crate) debug_name: String,
@@ -48,9 +49,9 @@ pub(crate) struct TypeErasedGateEvaluationRecursiveVerificationFunction<
         Box<
             dyn GenericDynamicEvaluatorOverSpecializedColumns<
                     F,
-                    NumExtAsFieldWrapper<F, EXT, CS>,
-                    VerifierPolyStorage<F, NumExtAsFieldWrapper<F, EXT, CS>>,
-                    VerifierRelationDestination<F, NumExtAsFieldWrapper<F, EXT, CS>>,
+                    NumExtAsFieldWrapper<F, DEGREE, EXT, CS>,
+                    VerifierPolyStorage<F, NumExtAsFieldWrapper<F, DEGREE, EXT, CS>>,
+                    VerifierRelationDestination<F, NumExtAsFieldWrapper<F, DEGREE, EXT, CS>>,
                 >
                 + 'static
                 + Send
@@ -62,9 +63,9 @@ pub(crate) struct TypeErasedGateEvaluationRecursiveVerificationFunction<
         Box<
             dyn GenericDynamicEvaluatorOverGeneralPurposeColumns<
                     F,
-                    NumExtAsFieldWrapper<F, EXT, CS>,
-                    VerifierPolyStorage<F, NumExtAsFieldWrapper<F, EXT, CS>>,
-                    VerifierRelationDestination<F, NumExtAsFieldWrapper<F, EXT, CS>>,
+                    NumExtAsFieldWrapper<F, DEGREE, EXT, CS>,
+                    VerifierPolyStorage<F, NumExtAsFieldWrapper<F, DEGREE, EXT, CS>>,
+                    VerifierRelationDestination<F, NumExtAsFieldWrapper<F, DEGREE, EXT, CS>>,
                 >
                 + 'static
                 + Send
@@ -73,8 +74,12 @@ pub(crate) struct TypeErasedGateEvaluationRecursiveVerificationFunction<
     >,
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F> + 'static>
-    TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>
+impl<
+        F: SmallField,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
+        CS: ConstraintSystem<F> + 'static,
+    > TypeErasedGateEvaluationRecursiveVerificationFunction<F, DEGREE, EXT, CS>
 {
     pub fn from_evaluator<E: GateConstraintEvaluator<F>>(
         cs: &mut CS,
@@ -145,11 +150,14 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                             as Box<
                                 dyn GenericDynamicEvaluatorOverSpecializedColumns<
                                         F,
-                                        NumExtAsFieldWrapper<F, EXT, CS>,
-                                        VerifierPolyStorage<F, NumExtAsFieldWrapper<F, EXT, CS>>,
+                                        NumExtAsFieldWrapper<F, DEGREE, EXT, CS>,
+                                        VerifierPolyStorage<
+                                            F,
+                                            NumExtAsFieldWrapper<F, DEGREE, EXT, CS>,
+                                        >,
                                         VerifierRelationDestination<
                                             F,
-                                            NumExtAsFieldWrapper<F, EXT, CS>,
+                                            NumExtAsFieldWrapper<F, DEGREE, EXT, CS>,
                                         >,
                                     >
                                     + 'static
@@ -175,11 +183,14 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                             as Box<
                                 dyn GenericDynamicEvaluatorOverGeneralPurposeColumns<
                                         F,
-                                        NumExtAsFieldWrapper<F, EXT, CS>,
-                                        VerifierPolyStorage<F, NumExtAsFieldWrapper<F, EXT, CS>>,
+                                        NumExtAsFieldWrapper<F, DEGREE, EXT, CS>,
+                                        VerifierPolyStorage<
+                                            F,
+                                            NumExtAsFieldWrapper<F, DEGREE, EXT, CS>,
+                                        >,
                                         VerifierRelationDestination<
                                             F,
-                                            NumExtAsFieldWrapper<F, EXT, CS>,
+                                            NumExtAsFieldWrapper<F, DEGREE, EXT, CS>,
                                         >,
                                     >
                                     + 'static
@@ -226,7 +237,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 pub struct CsRecursiveVerifierBuilder<
     'a,
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const DEGREE: usize,
+    EXT: FieldExtension<DEGREE, BaseField = F>,
     CS: ConstraintSystem<F> + 'static,
 > {
     pub(crate) cs: &'a mut CS,
@@ -236,11 +248,11 @@ pub struct CsRecursiveVerifierBuilder<
 
     pub(crate) gate_type_ids_for_specialized_columns: Vec<TypeId>,
     pub(crate) evaluators_over_specialized_columns:
-        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>>,
+        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, DEGREE, EXT, CS>>,
     pub(crate) offsets_for_specialized_evaluators: Vec<(PerChunkOffset, PerChunkOffset, usize)>,
 
     pub(crate) evaluators_over_general_purpose_columns:
-        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>>,
+        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, DEGREE, EXT, CS>>,
     pub(crate) general_purpose_evaluators_comparison_functions:
         HashMap<TypeId, Vec<(GateBatchEvaluationComparisonFunction, usize)>>,
 
@@ -252,9 +264,10 @@ pub struct CsRecursiveVerifierBuilder<
 impl<
         'a,
         F: SmallField,
-        EXT: FieldExtension<2, BaseField = F>,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
         CS: ConstraintSystem<F> + 'static,
-    > CsRecursiveVerifierBuilder<'a, F, EXT, CS>
+    > CsRecursiveVerifierBuilder<'a, F, DEGREE, EXT, CS>
 {
     pub fn new_from_parameters(cs: &'a mut CS, parameters: CSGeometry) -> Self {
         Self {
@@ -282,13 +295,14 @@ use super::recursive_verifier::*;
 impl<
         'a,
         F: SmallField,
-        EXT: FieldExtension<2, BaseField = F>,
+        const DEGREE: usize,
+        EXT: FieldExtension<DEGREE, BaseField = F>,
         CS: ConstraintSystem<F> + 'static,
-    > CsBuilderImpl<F, CsRecursiveVerifierBuilder<'a, F, EXT, CS>>
-    for CsRecursiveVerifierBuilder<'a, F, EXT, CS>
+    > CsBuilderImpl<F, CsRecursiveVerifierBuilder<'a, F, DEGREE, EXT, CS>>
+    for CsRecursiveVerifierBuilder<'a, F, DEGREE, EXT, CS>
 {
     type Final<GC: GateConfigurationHolder<F>, TB: StaticToolboxHolder> =
-        RecursiveVerifier<F, EXT, CS>;
+        RecursiveVerifier<F, DEGREE, EXT, CS>;
 
     type BuildParams<'b> = ();
 
@@ -633,9 +647,9 @@ impl<
         builder: CsBuilder<Self, F, GC, TB>,
         _params: Self::BuildParams<'_>,
     ) -> Self::Final<GC, TB> {
-        let this: CsRecursiveVerifierBuilder<F, EXT, CS> = builder.implementation;
+        let this: CsRecursiveVerifierBuilder<F, DEGREE, EXT, CS> = builder.implementation;
 
-        let new = RecursiveVerifierProxy::<F, EXT, CS, GC, TB> {
+        let new = RecursiveVerifierProxy::<F, DEGREE, EXT, CS, GC, TB> {
             parameters: this.parameters,
             lookup_parameters: this.lookup_parameters,
 
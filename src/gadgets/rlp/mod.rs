@@ -0,0 +1,356 @@
+use super::*;
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::impls::length_markers::LengthMarkers;
+use crate::gadgets::num::Num;
+use crate::gadgets::traits::selectable::Selectable;
+use crate::gadgets::u256::UInt256;
+use crate::gadgets::u8::UInt8;
+
+// All offsets into the encoded data are decomposed into this many bits,
+// so we can only address buffers shorter than 64 KiB
+pub const RLP_OFFSET_BITS: usize = 16;
+
+// Header of the RLP item. Long form is only supported with 1 or 2 bytes of length,
+// that is enough for anything that fits into the addressable buffer
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug)]
+pub struct RlpItemHeader<F: SmallField> {
+    pub is_list: Boolean<F>,
+    pub header_length: Num<F>,
+    pub payload_length: Num<F>,
+}
+
+impl<F: SmallField> RlpItemHeader<F> {
+    // Decodes the header from the first 3 bytes of the item, unused bytes are ignored.
+    // Canonicity of the encoding (e.g. absence of leading zeroes in the length) is not checked
+    pub fn decode<CS: ConstraintSystem<F>>(cs: &mut CS, prefix: &[UInt8<F>; 3]) -> Self {
+        let bits = prefix[0].into_num().spread_into_bits::<CS, 8>(cs);
+
+        // [0x00, 0x7f] is a byte itself, [0x80, 0xbf] is a string and [0xc0, 0xff] is a list
+        let is_single_byte = bits[7].negated(cs);
+        let is_list = bits[7].and(cs, bits[6]);
+        // long form has lowest 6 bits of the prefix in [56, 63]
+        let is_long_form = Boolean::multi_and(cs, &[bits[7], bits[5], bits[4], bits[3]]);
+        let is_short_form = is_long_form.xor(cs, bits[7]);
+        let is_short_list = is_short_form.and(cs, bits[6]);
+
+        let is_unsupported_length = bits[1].or(cs, bits[2]);
+        is_unsupported_length.conditionally_enforce_false(cs, is_long_form);
+        let is_two_bytes_length = is_long_form.and(cs, bits[0]);
+
+        let two_bytes_length = Num::linear_combination(
+            cs,
+            &[
+                (prefix[1].get_variable(), F::from_u64_unchecked(1u64 << 8)),
+                (prefix[2].get_variable(), F::ONE),
+            ],
+        );
+        let long_form_length = Num::conditionally_select(
+            cs,
+            is_two_bytes_length,
+            &two_bytes_length,
+            &prefix[1].into_num(),
+        );
+        let long_form_length = long_form_length.mask(cs, is_long_form);
+        let short_form_prefix = prefix[0].into_num().mask(cs, is_short_form);
+
+        let header_length = Num::linear_combination(
+            cs,
+            &[
+                (bits[7].get_variable(), F::ONE),
+                (is_long_form.get_variable(), F::ONE),
+                (is_two_bytes_length.get_variable(), F::ONE),
+            ],
+        );
+
+        let mut minus_string_offset = F::from_u64_unchecked(0x80);
+        minus_string_offset.negate();
+        let mut minus_list_extra_offset = F::from_u64_unchecked(0x40);
+        minus_list_extra_offset.negate();
+
+        let payload_length = Num::linear_combination(
+            cs,
+            &[
+                (is_single_byte.get_variable(), F::ONE),
+                (short_form_prefix.get_variable(), F::ONE),
+                (is_short_form.get_variable(), minus_string_offset),
+                (is_short_list.get_variable(), minus_list_extra_offset),
+                (long_form_length.get_variable(), F::ONE),
+            ],
+        );
+
+        Self {
+            is_list,
+            header_length,
+            payload_length,
+        }
+    }
+
+    pub fn payload_offset<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        item_offset: &Num<F>,
+    ) -> Num<F> {
+        item_offset.add(cs, &self.header_length)
+    }
+
+    pub fn end_offset<CS: ConstraintSystem<F>>(&self, cs: &mut CS, item_offset: &Num<F>) -> Num<F> {
+        Num::linear_combination(
+            cs,
+            &[
+                (item_offset.get_variable(), F::ONE),
+                (self.header_length.get_variable(), F::ONE),
+                (self.payload_length.get_variable(), F::ONE),
+            ],
+        )
+    }
+}
+
+// Returns `N` bytes starting from the `offset` known only at proving time,
+// bytes past the end of the buffer are zeroes. Enforces that `offset < 2^RLP_OFFSET_BITS`
+pub fn extract_window<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    bytes: &[UInt8<F>],
+    offset: &Num<F>,
+) -> [UInt8<F>; N] {
+    let zero = UInt8::zero(cs);
+    let bits = offset.spread_into_bits::<CS, RLP_OFFSET_BITS>(cs);
+
+    // barrel shifter from the highest bit, so after every layer we only need
+    // to keep the elements that are reachable by the remaining lower bits
+    let mut current = bytes.to_vec();
+    let mut shifted_out = vec![];
+    for (bit_idx, bit) in bits.iter().enumerate().rev() {
+        let shift = 1usize << bit_idx;
+        if shift >= current.len() {
+            shifted_out.push(*bit);
+            continue;
+        }
+
+        let new_len = std::cmp::min(current.len(), N + shift - 1);
+        let mut next = Vec::with_capacity(new_len);
+        for idx in 0..new_len {
+            let shifted = current.get(idx + shift).copied().unwrap_or(zero);
+            let selected = UInt8::conditionally_select(cs, *bit, &shifted, &current[idx]);
+            next.push(selected);
+        }
+        current = next;
+    }
+
+    let is_shifted_out = match &shifted_out[..] {
+        [] => None,
+        [single] => Some(*single),
+        all => Some(Boolean::multi_or(cs, all)),
+    };
+
+    let mut result = [zero; N];
+    for (dst, src) in result.iter_mut().zip(current.iter()) {
+        *dst = match is_shifted_out {
+            Some(flag) => src.mask_negated(cs, flag),
+            None => *src,
+        };
+    }
+
+    result
+}
+
+// Decodes headers of `N` consecutive items starting from `offset`. Returns offsets of the items
+// together with their headers, and the offset right after the last one
+pub fn decode_rlp_items<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    bytes: &[UInt8<F>],
+    offset: &Num<F>,
+) -> ([(Num<F>, RlpItemHeader<F>); N], Num<F>) {
+    let mut items = Vec::with_capacity(N);
+    let mut offset = *offset;
+    for _ in 0..N {
+        let prefix = extract_window::<F, CS, 3>(cs, bytes, &offset);
+        let header = RlpItemHeader::decode(cs, &prefix);
+        let next_offset = header.end_offset(cs, &offset);
+        items.push((offset, header));
+        offset = next_offset;
+    }
+
+    (items.try_into().unwrap(), offset)
+}
+
+// Decodes the big endian integer of `length` bytes at `offset`. Enforces that `length <= 32`
+pub fn decode_rlp_uint256<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    bytes: &[UInt8<F>],
+    offset: &Num<F>,
+    length: &Num<F>,
+) -> UInt256<F> {
+    let markers = LengthMarkers::new(cs, length, 32);
+    let window = extract_window::<F, CS, 32>(cs, bytes, offset);
+
+    // drop everything past the length and reverse, so the integer is little endian
+    // and placed at the end of the buffer
+    let mut reversed = window;
+    for (idx, byte) in window.iter().enumerate() {
+        let masked = markers.mask_element(cs, byte.get_variable(), idx);
+        reversed[31 - idx] = unsafe { UInt8::from_variable_unchecked(masked) };
+    }
+
+    let full_length = Num::allocated_constant(cs, F::from_u64_unchecked(32));
+    let shift = full_length.sub(cs, length);
+    let le_bytes = extract_window::<F, CS, 32>(cs, &reversed, &shift);
+
+    UInt256::from_le_bytes(cs, le_bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        cs::{
+            gates::{
+                ConstantsAllocatorGate, FmaGateInBaseFieldWithoutConstant, NopGate, ReductionGate,
+                SelectionGate, ZeroCheckGate,
+            },
+            CSGeometry,
+        },
+        field::{goldilocks::GoldilocksField, U64Representable},
+        gadgets::tables::xor8::{create_xor8_table, Xor8Table},
+    };
+    type F = GoldilocksField;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use ethereum_types::U256;
+
+    #[test]
+    fn test_rlp_decoding() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 20,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        use crate::config::DevCSConfig;
+        use crate::cs::cs_builder_reference::*;
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 18, 1 << 17);
+        use crate::cs::cs_builder::new_builder;
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 5,
+                share_table_id: true,
+            },
+        );
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+
+        let cs = &mut owned_cs;
+
+        // list of: single byte, short string, empty string, long string, nested short list
+        let long_string = vec![0x42u8; 300];
+        let mut payload = vec![0x05, 0x83, 0x01, 0x00, 0x01, 0x80, 0xb9, 0x01, 0x2c];
+        payload.extend_from_slice(&long_string);
+        payload.extend([0xc2, 0x01, 0x02]);
+        let mut encoding = vec![0xf9, (payload.len() >> 8) as u8, payload.len() as u8];
+        encoding.extend(payload);
+
+        let bytes: Vec<_> = encoding
+            .iter()
+            .map(|el| UInt8::allocate_checked(cs, *el))
+            .collect();
+
+        let zero = Num::zero(cs);
+        let prefix = extract_window::<F, _, 3>(cs, &bytes, &zero);
+        let list_header = RlpItemHeader::decode(cs, &prefix);
+        assert_eq!(list_header.is_list.witness_hook(&*cs)().unwrap(), true);
+        assert_eq!(
+            list_header.header_length.witness_hook(&*cs)().unwrap(),
+            F::from_u64_unchecked(3)
+        );
+        assert_eq!(
+            list_header.payload_length.witness_hook(&*cs)().unwrap(),
+            F::from_u64_unchecked(encoding.len() as u64 - 3)
+        );
+
+        let (items, end) = decode_rlp_items::<F, _, 5>(cs, &bytes, &list_header.header_length);
+        assert_eq!(
+            end.witness_hook(&*cs)().unwrap(),
+            F::from_u64_unchecked(encoding.len() as u64)
+        );
+
+        let expected = [
+            (3, false, 0, 1),
+            (4, false, 1, 3),
+            (8, false, 1, 0),
+            (9, false, 3, 300),
+            (312, true, 1, 2),
+        ];
+        for ((offset, header), (offset_ref, is_list_ref, header_ref, payload_ref)) in
+            items.iter().zip(expected.iter())
+        {
+            assert_eq!(
+                offset.witness_hook(&*cs)().unwrap(),
+                F::from_u64_unchecked(*offset_ref)
+            );
+            assert_eq!(header.is_list.witness_hook(&*cs)().unwrap(), *is_list_ref);
+            assert_eq!(
+                header.header_length.witness_hook(&*cs)().unwrap(),
+                F::from_u64_unchecked(*header_ref)
+            );
+            assert_eq!(
+                header.payload_length.witness_hook(&*cs)().unwrap(),
+                F::from_u64_unchecked(*payload_ref)
+            );
+        }
+
+        // integers
+        for (idx, expected) in [(0, 5u64), (1, 0x010001), (2, 0)] {
+            let (offset, header) = items[idx];
+            let payload_offset = header.payload_offset(cs, &offset);
+            let value = decode_rlp_uint256(cs, &bytes, &payload_offset, &header.payload_length);
+            assert_eq!(value.witness_hook(&*cs)().unwrap(), U256::from(expected));
+        }
+
+        // window past the end of the buffer is zero padded
+        let offset = Num::allocated_constant(cs, F::from_u64_unchecked(encoding.len() as u64 - 2));
+        let window = extract_window::<F, _, 4>(cs, &bytes, &offset);
+        assert_eq!(
+            window.witness_hook(&*cs)().unwrap(),
+            [0x01, 0x02, 0x00, 0x00]
+        );
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        use crate::worker::Worker;
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
@@ -4,8 +4,12 @@ use crate::cs::gates::{
 };
 use crate::cs::traits::cs::ConstraintSystem;
 use crate::cs::Variable;
+use crate::gadgets::impls::length_markers::LengthMarkers;
+use crate::gadgets::num::Num;
+use crate::gadgets::traits::selectable::Selectable;
 use crate::gadgets::u32::UInt32;
 use crate::gadgets::u8::UInt8;
+use arrayvec::ArrayVec;
 
 pub mod round_function;
 
@@ -36,50 +40,184 @@ pub fn sha256<F: SmallField, CS: ConstraintSystem<F>>(
     cs: &mut CS,
     input: &[UInt8<F>],
 ) -> [UInt8<F>; SHA256_DIGEST_SIZE] {
-    // pad first
-    let last_block_size = input.len() % SHA256_BLOCK_SIZE;
-    let num_zeroes_to_add = if last_block_size <= (64 - 1 - 8) {
-        64 - 1 - 8 - last_block_size
-    } else {
-        128 - 1 - 8 - last_block_size
-    };
+    let mut hasher = Sha256Hasher::new(cs);
+    hasher.absorb(cs, input);
+
+    hasher.finalize(cs)
+}
+
+// Hashes first `length` bytes of the `input`, where `length` is only known at proving time
+// and must not exceed `input.len()`. Cost is the same as for hashing of the full `input`
+pub fn sha256_variable_length<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    input: &[UInt8<F>],
+    length: &UInt32<F>,
+) -> [UInt8<F>; SHA256_DIGEST_SIZE] {
+    let markers = LengthMarkers::new(cs, &length.into_num(), input.len());
+
+    // length in bits is less than 2^35, so it's safe to decompose as 64 bit one
+    let bit_length =
+        Num::linear_combination(cs, &[(length.get_variable(), F::from_u64_unchecked(8u64))]);
+    let bit_length_le = bit_length.constraint_bit_length_as_bytes(cs, 64);
 
-    let mut full_message = Vec::with_capacity(input.len() + 1 + 8 + num_zeroes_to_add);
-    full_message.extend_from_slice(input);
-    full_message.push(UInt8::allocated_constant(cs, 0x80));
-    if num_zeroes_to_add > 0 {
-        let zero = UInt8::allocated_constant(cs, 0x00);
-        full_message.extend(std::iter::repeat(zero).take(num_zeroes_to_add));
+    let zero = UInt8::zero(cs);
+    let mut state = INITIAL_STATE.map(|el| cs.allocate_constant(F::from_u64_unchecked(el as u64)));
+
+    // the last block is the one where the length fits after the end of the input
+    let num_blocks = (input.len() + 8) / SHA256_BLOCK_SIZE + 1;
+    let mut result = None;
+    for block_idx in 0..num_blocks {
+        let start = block_idx * SHA256_BLOCK_SIZE;
+        let is_last_block =
+            markers.is_end_within(cs, start.saturating_sub(8)..(start + SHA256_BLOCK_SIZE - 8));
+
+        // masked input, then 0x80 right after the end, and big endian bit length
+        // at the end of the last block
+        let mut block = [zero; SHA256_BLOCK_SIZE];
+        for (offset, dst) in block.iter_mut().enumerate() {
+            let idx = start + offset;
+            let mut terms = ArrayVec::<(Variable, F), 3>::new();
+            if idx < input.len() {
+                let byte = markers.mask_element(cs, input[idx].get_variable(), idx);
+                terms.push((byte, F::ONE));
+            }
+            if idx <= input.len() {
+                terms.push((
+                    markers.is_end[idx].get_variable(),
+                    F::from_u64_unchecked(0x80u64),
+                ));
+            }
+            if offset >= SHA256_BLOCK_SIZE - 8 {
+                let length_byte = bit_length_le[SHA256_BLOCK_SIZE - 1 - offset];
+                let length_byte = length_byte.into_num().mask(cs, is_last_block);
+                terms.push((length_byte.get_variable(), F::ONE));
+            }
+
+            match &terms[..] {
+                [] => {}
+                [(variable, coeff)] if *coeff == F::ONE => {
+                    *dst = unsafe { UInt8::from_variable_unchecked(*variable) }
+                }
+                _ => {
+                    let byte = Num::linear_combination(cs, &terms);
+                    *dst = unsafe { UInt8::from_variable_unchecked(byte.get_variable()) };
+                }
+            }
+        }
+
+        let final_4bit_chunks =
+            process_block(cs, &mut state, &block, true).expect("must create decompositions");
+        let digest = digest_from_4bit_chunks(cs, &final_4bit_chunks);
+
+        result = match result {
+            None => Some(digest),
+            Some(previous) => Some(Selectable::conditionally_select(
+                cs,
+                is_last_block,
+                &digest,
+                &previous,
+            )),
+        };
     }
-    let bit_length_be = (input.len() as u64 * 8u64).to_be_bytes();
-    for el in bit_length_be {
-        let el = UInt8::allocated_constant(cs, el);
-        full_message.push(el);
+
+    result.expect("at least one block is always processed")
+}
+
+// Incremental version of `sha256` for inputs of lengths known at synthesis time.
+// Full blocks are processed as soon as they are available, so the input may be collected
+// in different places of the circuit
+pub struct Sha256Hasher<F: SmallField> {
+    state: [Variable; 8],
+    buffer: Vec<UInt8<F>>,
+    absorbed_length: usize,
+}
+
+impl<F: SmallField> Sha256Hasher<F> {
+    pub fn new<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
+        let state = INITIAL_STATE.map(|el| cs.allocate_constant(F::from_u64_unchecked(el as u64)));
+
+        Self {
+            state,
+            buffer: Vec::with_capacity(SHA256_BLOCK_SIZE),
+            absorbed_length: 0,
+        }
     }
-    assert_eq!(full_message.len() % SHA256_BLOCK_SIZE, 0);
-    let num_rounds = full_message.len() / SHA256_BLOCK_SIZE;
 
-    let mut final_4bit_chunks = None;
+    pub fn absorb<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, input: &[UInt8<F>]) {
+        self.absorbed_length += input.len();
 
-    let mut state = INITIAL_STATE.map(|el| cs.allocate_constant(F::from_u64_unchecked(el as u64)));
+        self.buffer.extend_from_slice(input);
+        let num_full_bytes = self.buffer.len() - self.buffer.len() % SHA256_BLOCK_SIZE;
+        // padding always follows, so none of these blocks is the last one
+        for block in self.buffer[..num_full_bytes].array_chunks::<SHA256_BLOCK_SIZE>() {
+            let _ = process_block(cs, &mut self.state, block, false);
+        }
+        self.buffer.drain(..num_full_bytes);
+    }
 
-    for (round, input_bytes) in full_message.array_chunks::<SHA256_BLOCK_SIZE>().enumerate() {
-        let last_round = round == num_rounds - 1;
+    pub fn finalize<CS: ConstraintSystem<F>>(
+        mut self,
+        cs: &mut CS,
+    ) -> [UInt8<F>; SHA256_DIGEST_SIZE] {
+        // pad first
+        let last_block_size = self.buffer.len();
+        debug_assert!(last_block_size < SHA256_BLOCK_SIZE);
+        let num_zeroes_to_add = if last_block_size <= (64 - 1 - 8) {
+            64 - 1 - 8 - last_block_size
+        } else {
+            128 - 1 - 8 - last_block_size
+        };
 
-        let mut message_block = [Variable::placeholder(); 16];
-        for (dst, src) in message_block
-            .iter_mut()
-            .zip(input_bytes.array_chunks::<4>())
-        {
-            *dst = UInt32::from_be_bytes(cs, *src).variable;
+        self.buffer.push(UInt8::allocated_constant(cs, 0x80));
+        if num_zeroes_to_add > 0 {
+            let zero = UInt8::allocated_constant(cs, 0x00);
+            self.buffer
+                .extend(std::iter::repeat(zero).take(num_zeroes_to_add));
+        }
+        let bit_length_be = (self.absorbed_length as u64 * 8u64).to_be_bytes();
+        for el in bit_length_be {
+            let el = UInt8::allocated_constant(cs, el);
+            self.buffer.push(el);
         }
-        assert_no_placeholder_variables(&message_block);
+        assert_eq!(self.buffer.len() % SHA256_BLOCK_SIZE, 0);
+        let num_rounds = self.buffer.len() / SHA256_BLOCK_SIZE;
 
-        final_4bit_chunks =
-            self::round_function::round_function(cs, &mut state, &message_block, last_round);
+        let mut final_4bit_chunks = None;
+
+        for (round, input_bytes) in self.buffer.array_chunks::<SHA256_BLOCK_SIZE>().enumerate() {
+            let last_round = round == num_rounds - 1;
+
+            final_4bit_chunks = process_block(cs, &mut self.state, input_bytes, last_round);
+        }
+
+        let final_4bit_chunks = final_4bit_chunks.expect("must create decompositions");
+
+        digest_from_4bit_chunks(cs, &final_4bit_chunks)
+    }
+}
+
+fn process_block<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    state: &mut [Variable; 8],
+    input_bytes: &[UInt8<F>; SHA256_BLOCK_SIZE],
+    range_check_final_state: bool,
+) -> Option<[Variable; 64]> {
+    let mut message_block = [Variable::placeholder(); 16];
+    for (dst, src) in message_block
+        .iter_mut()
+        .zip(input_bytes.array_chunks::<4>())
+    {
+        *dst = UInt32::from_be_bytes(cs, *src).variable;
     }
+    assert_no_placeholder_variables(&message_block);
 
-    let final_4bit_chunks = final_4bit_chunks.expect("must create decompositions");
+    self::round_function::round_function(cs, state, &message_block, range_check_final_state)
+}
+
+fn digest_from_4bit_chunks<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    final_4bit_chunks: &[Variable; 64],
+) -> [UInt8<F>; SHA256_DIGEST_SIZE] {
     let mut output = [Variable::placeholder(); SHA256_DIGEST_SIZE];
     let shift_4 = F::from_u64_unchecked(1u64 << 4);
     let one = cs.allocate_constant(F::ONE);
@@ -248,6 +386,115 @@ mod test {
         assert!(owned_cs.check_if_satisfied(&worker));
     }
 
+    #[test]
+    fn test_variable_length_and_incremental() {
+        use crate::cs::gates::{SelectionGate, ZeroCheckGate};
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42 as u64);
+
+        let max_len = 2 * 64 + 42;
+        let input: Vec<u8> = (0..max_len).map(|_| rng.gen()).collect();
+
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 20,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        use crate::config::DevCSConfig;
+        use crate::cs::cs_builder_reference::*;
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 22, 1 << 20);
+        use crate::cs::cs_builder::new_builder;
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 4,
+                num_repetitions: 5,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        // add tables
+        let table = create_tri_xor_table();
+        owned_cs.add_lookup_table::<TriXor4Table, 4>(table);
+
+        let table = create_ch4_table();
+        owned_cs.add_lookup_table::<Ch4Table, 4>(table);
+
+        let table = create_maj4_table();
+        owned_cs.add_lookup_table::<Maj4Table, 4>(table);
+
+        let table = create_4bit_chunk_split_table::<F, 1>();
+        owned_cs.add_lookup_table::<Split4BitChunkTable<1>, 4>(table);
+
+        let table = create_4bit_chunk_split_table::<F, 2>();
+        owned_cs.add_lookup_table::<Split4BitChunkTable<2>, 4>(table);
+
+        let cs = &mut owned_cs;
+
+        let circuit_input: Vec<_> = input
+            .iter()
+            .map(|el| UInt8::allocate_checked(cs, *el))
+            .collect();
+
+        for len in [0, 1, 55, 56, 63, 64, 64 + 55, 64 + 56, 2 * 64, max_len] {
+            let mut hasher = sha2::Sha256::new();
+            hasher.update(&input[..len]);
+            let reference_output = hex::encode(hasher.finalize().as_slice());
+
+            let length = UInt32::allocate_checked(cs, len as u32);
+            let output = sha256_variable_length(cs, &circuit_input, &length);
+            let output = hex::encode(&(output.witness_hook(&*cs))().unwrap());
+            assert_eq!(output, reference_output, "failed for length {}", len);
+
+            // same bytes in few pieces
+            let mut hasher = Sha256Hasher::new(cs);
+            let (first, second) = circuit_input[..len].split_at(len / 3);
+            hasher.absorb(cs, first);
+            hasher.absorb(cs, &[]);
+            hasher.absorb(cs, second);
+            let output = hasher.finalize(cs);
+            let output = hex::encode(&(output.witness_hook(&*cs))().unwrap());
+            assert_eq!(output, reference_output, "failed for length {}", len);
+        }
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        use crate::worker::Worker;
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
     type P = crate::field::goldilocks::MixedGL;
 
     // Notes on benches:
@@ -466,7 +713,7 @@ mod test {
 
         let now = std::time::Instant::now();
 
-        let proof = owned_cs.prove_cpu_basic::<GoldilocksExt2, TR, T, NoPow>(
+        let proof = owned_cs.prove_cpu_basic::<2, GoldilocksExt2, TR, T, NoPow>(
             &worker,
             witness_set,
             &base_setup,
@@ -479,7 +726,7 @@ mod test {
 
         log!("Proving is done, taken {:?}", now.elapsed());
 
-        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder_impl = CsVerifierBuilder::<F, 2, GoldilocksExt2>::new_from_parameters(geometry);
         let builder = new_builder::<_, F>(builder_impl);
 
         let builder = configure(builder);
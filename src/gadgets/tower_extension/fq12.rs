@@ -0,0 +1,193 @@
+use super::fq2::NonNativeFq2;
+use super::fq6::NonNativeFq6;
+use super::*;
+
+/// Element `c0 + c1 * w` of `Fq12 = Fq6[w] / (w^2 - v)`
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct NonNativeFq12<F: SmallField, NN: NonNativeField<F, Fq>> {
+    pub c0: NonNativeFq6<F, NN>,
+    pub c1: NonNativeFq6<F, NN>,
+}
+
+// coefficient `gamma` such that `w^(p^power) = gamma * w`
+fn frobenius_coefficient(power: usize) -> Fq2 {
+    let mut w = Fq12::zero();
+    w.c1.c0 = Fq2::one();
+    w.frobenius_map(power);
+
+    w.c1.c0
+}
+
+impl<F: SmallField, NN: NonNativeField<F, Fq>> NonNativeFq12<F, NN> {
+    pub fn new(c0: NonNativeFq6<F, NN>, c1: NonNativeFq6<F, NN>) -> Self {
+        Self { c0, c1 }
+    }
+
+    pub fn get_params(&self) -> &Arc<NN::Params> {
+        self.c0.get_params()
+    }
+
+    pub fn allocated_constant<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        value: Fq12,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        let c0 = NonNativeFq6::allocated_constant(cs, value.c0, params);
+        let c1 = NonNativeFq6::allocated_constant(cs, value.c1, params);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn allocate_checked<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        witness: Fq12,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        let c0 = NonNativeFq6::allocate_checked(cs, witness.c0, params);
+        let c1 = NonNativeFq6::allocate_checked(cs, witness.c1, params);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS, params: &Arc<NN::Params>) -> Self {
+        Self::allocated_constant(cs, Fq12::zero(), params)
+    }
+
+    pub fn one<CS: ConstraintSystem<F>>(cs: &mut CS, params: &Arc<NN::Params>) -> Self {
+        Self::allocated_constant(cs, Fq12::one(), params)
+    }
+
+    pub fn normalize<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        self.c0.normalize(cs);
+        self.c1.normalize(cs);
+    }
+
+    pub fn add<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let c0 = self.c0.add(cs, &mut other.c0);
+        let c1 = self.c1.add(cs, &mut other.c1);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn sub<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let c0 = self.c0.sub(cs, &mut other.c0);
+        let c1 = self.c1.sub(cs, &mut other.c1);
+
+        Self::new(c0, c1)
+    }
+
+    /// Conjugation `c0 - c1 * w`, that is also an inverse for elements of the cyclotomic subgroup
+    pub fn conjugate<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let c1 = self.c1.negated(cs);
+
+        Self::new(self.c0.clone(), c1)
+    }
+
+    pub fn mul<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let mut a_a = self.c0.mul(cs, &mut other.c0);
+        let mut b_b = self.c1.mul(cs, &mut other.c1);
+
+        // c1 = (a0 + a1) * (b0 + b1) - a_a - b_b
+        let mut a = self.c0.add(cs, &mut self.c1);
+        let mut b = other.c0.add(cs, &mut other.c1);
+        let c1 = a.mul(cs, &mut b).sub(cs, &mut a_a).sub(cs, &mut b_b);
+
+        // c0 = a_a + b_b * v
+        let c0 = b_b.mul_by_nonresidue(cs).add(cs, &mut a_a);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn square<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        // (a0 + a1 * w)^2 = ((a0 + a1) * (a0 + a1 * v) - a0 * a1 - a0 * a1 * v) + 2 * a0 * a1 * w
+        let mut ab = self.c0.mul(cs, &mut self.c1);
+        let mut ab_by_nonresidue = ab.mul_by_nonresidue(cs);
+
+        let mut a = self.c0.add(cs, &mut self.c1);
+        let mut b = self.c1.mul_by_nonresidue(cs).add(cs, &mut self.c0);
+        let c0 = a
+            .mul(cs, &mut b)
+            .sub(cs, &mut ab)
+            .sub(cs, &mut ab_by_nonresidue);
+        let c1 = ab.double(cs);
+
+        Self::new(c0, c1)
+    }
+
+    /// Multiplication by sparse element `c0 + (d0 + d1 * v) * w` with `c0` in the base field,
+    /// that is a shape of the line functions in the Miller loop over the D-type twist
+    pub fn mul_by_034<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        c0: &mut NN,
+        d0: &mut NonNativeFq2<F, NN>,
+        d1: &mut NonNativeFq2<F, NN>,
+    ) -> Self {
+        let mut a_a = self.c0.mul_by_base_field(cs, c0);
+        let mut b_b = self.c1.mul_by_01(cs, d0, d1);
+
+        // c1 = (a0 + a1) * ((c0 + d0) + d1 * v) - a_a - b_b
+        let mut a = self.c0.add(cs, &mut self.c1);
+        let mut c0_plus_d0 = NonNativeFq2::new(d0.c0.add(cs, c0), d0.c1.clone());
+        let new_c1 = a
+            .mul_by_01(cs, &mut c0_plus_d0, d1)
+            .sub(cs, &mut a_a)
+            .sub(cs, &mut b_b);
+
+        // c0 = a_a + b_b * v
+        let new_c0 = b_b.mul_by_nonresidue(cs).add(cs, &mut a_a);
+
+        Self::new(new_c0, new_c1)
+    }
+
+    /// Inverse of non-zero element, unsatisfiable otherwise
+    pub fn inverse_unchecked<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        // 1 / (a0 + a1 * w) = (a0 - a1 * w) / (a0^2 - a1^2 * v)
+        let mut a1_squared_by_nonresidue = self.c1.square(cs).mul_by_nonresidue(cs);
+        let mut t = self.c0.square(cs).sub(cs, &mut a1_squared_by_nonresidue);
+        let mut t_inverse = t.inverse_unchecked(cs);
+
+        let c0 = self.c0.mul(cs, &mut t_inverse);
+        let c1 = self.c1.mul(cs, &mut t_inverse).negated(cs);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn frobenius_map<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, power: usize) -> Self {
+        let gamma = frobenius_coefficient(power % 12);
+        let params = self.get_params().clone();
+        let mut gamma = NonNativeFq2::allocated_constant(cs, gamma, &params);
+
+        let c0 = self.c0.frobenius_map(cs, power);
+        let c1 = self.c1.frobenius_map(cs, power).mul_by_fq2(cs, &mut gamma);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn is_one<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        let params = self.get_params().clone();
+        let mut one = Self::one(cs, &params);
+
+        self.equals(cs, &mut one)
+    }
+
+    pub fn equals<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Boolean<F> {
+        let c0_is_equal = self.c0.equals(cs, &mut other.c0);
+        let c1_is_equal = self.c1.equals(cs, &mut other.c1);
+
+        Boolean::multi_and(cs, &[c0_is_equal, c1_is_equal])
+    }
+
+    pub fn conditionally_select<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        flag: Boolean<F>,
+        a: &Self,
+        b: &Self,
+    ) -> Self {
+        let c0 = NonNativeFq6::conditionally_select(cs, flag, &a.c0, &b.c0);
+        let c1 = NonNativeFq6::conditionally_select(cs, flag, &a.c1, &b.c1);
+
+        Self::new(c0, c1)
+    }
+}
@@ -0,0 +1,192 @@
+use super::*;
+
+/// Element `c0 + c1 * u` of `Fq2 = Fq[u] / (u^2 + 1)`
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct NonNativeFq2<F: SmallField, NN: NonNativeField<F, Fq>> {
+    pub c0: NN,
+    pub c1: NN,
+    pub _marker: std::marker::PhantomData<F>,
+}
+
+impl<F: SmallField, NN: NonNativeField<F, Fq>> NonNativeFq2<F, NN> {
+    pub fn new(c0: NN, c1: NN) -> Self {
+        Self {
+            c0,
+            c1,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn get_params(&self) -> &Arc<NN::Params> {
+        self.c0.get_params()
+    }
+
+    pub fn allocated_constant<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        value: Fq2,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        let c0 = NN::allocated_constant(cs, value.c0, params);
+        let c1 = NN::allocated_constant(cs, value.c1, params);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn allocate_checked<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        witness: Fq2,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        let c0 = NN::allocate_checked(cs, witness.c0, params);
+        let c1 = NN::allocate_checked(cs, witness.c1, params);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS, params: &Arc<NN::Params>) -> Self {
+        Self::allocated_constant(cs, Fq2::zero(), params)
+    }
+
+    pub fn one<CS: ConstraintSystem<F>>(cs: &mut CS, params: &Arc<NN::Params>) -> Self {
+        Self::allocated_constant(cs, Fq2::one(), params)
+    }
+
+    pub fn normalize<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        self.c0.normalize(cs);
+        self.c1.normalize(cs);
+    }
+
+    pub fn add<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let c0 = self.c0.add(cs, &mut other.c0);
+        let c1 = self.c1.add(cs, &mut other.c1);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn double<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let c0 = self.c0.double(cs);
+        let c1 = self.c1.double(cs);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn sub<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let c0 = self.c0.sub(cs, &mut other.c0);
+        let c1 = self.c1.sub(cs, &mut other.c1);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn negated<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let c0 = self.c0.negated(cs);
+        let c1 = self.c1.negated(cs);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn conjugate<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let c1 = self.c1.negated(cs);
+
+        Self::new(self.c0.clone(), c1)
+    }
+
+    pub fn mul<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        // Karatsuba: (a0 + a1 * u) * (b0 + b1 * u) = (a0 * b0 - a1 * b1) + ((a0 + a1) * (b0 + b1) - a0 * b0 - a1 * b1) * u
+        let mut v0 = self.c0.mul(cs, &mut other.c0);
+        let mut v1 = self.c1.mul(cs, &mut other.c1);
+
+        let mut a = self.c0.add(cs, &mut self.c1);
+        let mut b = other.c0.add(cs, &mut other.c1);
+        let c1 = a.mul(cs, &mut b).sub(cs, &mut v0).sub(cs, &mut v1);
+        let c0 = v0.sub(cs, &mut v1);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn square<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        // (a0 + a1 * u)^2 = (a0 + a1) * (a0 - a1) + 2 * a0 * a1 * u
+        let mut sum = self.c0.add(cs, &mut self.c1);
+        let mut diff = self.c0.sub(cs, &mut self.c1);
+        let c0 = sum.mul(cs, &mut diff);
+        let c1 = self.c0.mul(cs, &mut self.c1).double(cs);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn mul_by_base_field<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        other: &mut NN,
+    ) -> Self {
+        let c0 = self.c0.mul(cs, other);
+        let c1 = self.c1.mul(cs, other);
+
+        Self::new(c0, c1)
+    }
+
+    /// Multiplication by the cubic non-residue `9 + u` used to define Fq6
+    pub fn mul_by_nonresidue<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        // (a0 + a1 * u) * (9 + u) = (9 * a0 - a1) + (a0 + 9 * a1) * u
+        let mut nine_c0 = mul_by_nine(cs, &mut self.c0);
+        let mut nine_c1 = mul_by_nine(cs, &mut self.c1);
+        let c0 = nine_c0.sub(cs, &mut self.c1);
+        let c1 = nine_c1.add(cs, &mut self.c0);
+
+        Self::new(c0, c1)
+    }
+
+    /// Inverse of non-zero element, unsatisfiable otherwise
+    pub fn inverse_unchecked<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        // 1 / (a0 + a1 * u) = (a0 - a1 * u) / (a0^2 + a1^2)
+        let mut c1_squared = self.c1.square(cs);
+        let mut norm = self.c0.square(cs).add(cs, &mut c1_squared);
+        let c0 = self.c0.div_unchecked(cs, &mut norm);
+        let c1 = self.c1.negated(cs).div_unchecked(cs, &mut norm);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn div_unchecked<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        other: &mut Self,
+    ) -> Self {
+        let mut inverse = other.inverse_unchecked(cs);
+        self.mul(cs, &mut inverse)
+    }
+
+    pub fn frobenius_map<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, power: usize) -> Self {
+        if power % 2 == 0 {
+            self.clone()
+        } else {
+            self.conjugate(cs)
+        }
+    }
+
+    pub fn is_zero<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        let c0_is_zero = self.c0.is_zero(cs);
+        let c1_is_zero = self.c1.is_zero(cs);
+
+        Boolean::multi_and(cs, &[c0_is_zero, c1_is_zero])
+    }
+
+    pub fn equals<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Boolean<F> {
+        let c0_is_equal = self.c0.equals(cs, &mut other.c0);
+        let c1_is_equal = self.c1.equals(cs, &mut other.c1);
+
+        Boolean::multi_and(cs, &[c0_is_equal, c1_is_equal])
+    }
+
+    pub fn conditionally_select<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        flag: Boolean<F>,
+        a: &Self,
+        b: &Self,
+    ) -> Self {
+        let c0 = NN::conditionally_select(cs, flag, &a.c0, &b.c0);
+        let c1 = NN::conditionally_select(cs, flag, &a.c1, &b.c1);
+
+        Self::new(c0, c1)
+    }
+}
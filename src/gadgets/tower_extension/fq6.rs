@@ -0,0 +1,283 @@
+use super::fq2::NonNativeFq2;
+use super::*;
+
+/// Element `c0 + c1 * v + c2 * v^2` of `Fq6 = Fq2[v] / (v^3 - (9 + u))`
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct NonNativeFq6<F: SmallField, NN: NonNativeField<F, Fq>> {
+    pub c0: NonNativeFq2<F, NN>,
+    pub c1: NonNativeFq2<F, NN>,
+    pub c2: NonNativeFq2<F, NN>,
+}
+
+// coefficients `gamma_1`, `gamma_2` such that `v^(p^power) = gamma_1 * v` and `(v^2)^(p^power) = gamma_2 * v^2`
+fn frobenius_coefficients(power: usize) -> (Fq2, Fq2) {
+    let mut v = Fq6::zero();
+    v.c1 = Fq2::one();
+    v.frobenius_map(power);
+
+    let mut v_squared = Fq6::zero();
+    v_squared.c2 = Fq2::one();
+    v_squared.frobenius_map(power);
+
+    (v.c1, v_squared.c2)
+}
+
+impl<F: SmallField, NN: NonNativeField<F, Fq>> NonNativeFq6<F, NN> {
+    pub fn new(c0: NonNativeFq2<F, NN>, c1: NonNativeFq2<F, NN>, c2: NonNativeFq2<F, NN>) -> Self {
+        Self { c0, c1, c2 }
+    }
+
+    pub fn get_params(&self) -> &Arc<NN::Params> {
+        self.c0.get_params()
+    }
+
+    pub fn allocated_constant<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        value: Fq6,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        let c0 = NonNativeFq2::allocated_constant(cs, value.c0, params);
+        let c1 = NonNativeFq2::allocated_constant(cs, value.c1, params);
+        let c2 = NonNativeFq2::allocated_constant(cs, value.c2, params);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn allocate_checked<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        witness: Fq6,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        let c0 = NonNativeFq2::allocate_checked(cs, witness.c0, params);
+        let c1 = NonNativeFq2::allocate_checked(cs, witness.c1, params);
+        let c2 = NonNativeFq2::allocate_checked(cs, witness.c2, params);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS, params: &Arc<NN::Params>) -> Self {
+        Self::allocated_constant(cs, Fq6::zero(), params)
+    }
+
+    pub fn one<CS: ConstraintSystem<F>>(cs: &mut CS, params: &Arc<NN::Params>) -> Self {
+        Self::allocated_constant(cs, Fq6::one(), params)
+    }
+
+    pub fn normalize<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        self.c0.normalize(cs);
+        self.c1.normalize(cs);
+        self.c2.normalize(cs);
+    }
+
+    pub fn add<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let c0 = self.c0.add(cs, &mut other.c0);
+        let c1 = self.c1.add(cs, &mut other.c1);
+        let c2 = self.c2.add(cs, &mut other.c2);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn double<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let c0 = self.c0.double(cs);
+        let c1 = self.c1.double(cs);
+        let c2 = self.c2.double(cs);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn sub<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let c0 = self.c0.sub(cs, &mut other.c0);
+        let c1 = self.c1.sub(cs, &mut other.c1);
+        let c2 = self.c2.sub(cs, &mut other.c2);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn negated<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let c0 = self.c0.negated(cs);
+        let c1 = self.c1.negated(cs);
+        let c2 = self.c2.negated(cs);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn mul<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let mut a_a = self.c0.mul(cs, &mut other.c0);
+        let mut b_b = self.c1.mul(cs, &mut other.c1);
+        let mut c_c = self.c2.mul(cs, &mut other.c2);
+
+        // c0 = a_a + ((a1 + a2) * (b1 + b2) - b_b - c_c) * xi
+        let mut a = self.c1.add(cs, &mut self.c2);
+        let mut b = other.c1.add(cs, &mut other.c2);
+        let mut t1 = a
+            .mul(cs, &mut b)
+            .sub(cs, &mut b_b)
+            .sub(cs, &mut c_c)
+            .mul_by_nonresidue(cs);
+        let mut c0 = t1.add(cs, &mut a_a);
+
+        // c1 = (a0 + a1) * (b0 + b1) - a_a - b_b + c_c * xi
+        let mut a = self.c0.add(cs, &mut self.c1);
+        let mut b = other.c0.add(cs, &mut other.c1);
+        let mut c_c_by_nonresidue = c_c.mul_by_nonresidue(cs);
+        let mut c1 = a
+            .mul(cs, &mut b)
+            .sub(cs, &mut a_a)
+            .sub(cs, &mut b_b)
+            .add(cs, &mut c_c_by_nonresidue);
+
+        // c2 = (a0 + a2) * (b0 + b2) - a_a + b_b - c_c
+        let mut a = self.c0.add(cs, &mut self.c2);
+        let mut b = other.c0.add(cs, &mut other.c2);
+        let mut c2 = a
+            .mul(cs, &mut b)
+            .sub(cs, &mut a_a)
+            .add(cs, &mut b_b)
+            .sub(cs, &mut c_c);
+
+        c0.normalize(cs);
+        c1.normalize(cs);
+        c2.normalize(cs);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn square<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let mut other = self.clone();
+        self.mul(cs, &mut other)
+    }
+
+    /// Multiplication by `v`
+    pub fn mul_by_nonresidue<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        // (c0 + c1 * v + c2 * v^2) * v = c2 * xi + c0 * v + c1 * v^2
+        let c0 = self.c2.mul_by_nonresidue(cs);
+
+        Self::new(c0, self.c0.clone(), self.c1.clone())
+    }
+
+    pub fn mul_by_fq2<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        other: &mut NonNativeFq2<F, NN>,
+    ) -> Self {
+        let c0 = self.c0.mul(cs, other);
+        let c1 = self.c1.mul(cs, other);
+        let c2 = self.c2.mul(cs, other);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn mul_by_base_field<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        other: &mut NN,
+    ) -> Self {
+        let c0 = self.c0.mul_by_base_field(cs, other);
+        let c1 = self.c1.mul_by_base_field(cs, other);
+        let c2 = self.c2.mul_by_base_field(cs, other);
+
+        Self::new(c0, c1, c2)
+    }
+
+    /// Multiplication by sparse element `b0 + b1 * v`
+    pub fn mul_by_01<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        b0: &mut NonNativeFq2<F, NN>,
+        b1: &mut NonNativeFq2<F, NN>,
+    ) -> Self {
+        let mut a_a = self.c0.mul(cs, b0);
+        let mut b_b = self.c1.mul(cs, b1);
+
+        // c0 = a_a + a2 * b1 * xi
+        let mut c0 = self.c2.mul(cs, b1).mul_by_nonresidue(cs).add(cs, &mut a_a);
+
+        // c1 = (a0 + a1) * (b0 + b1) - a_a - b_b
+        let mut a = self.c0.add(cs, &mut self.c1);
+        let mut b = b0.add(cs, b1);
+        let mut c1 = a.mul(cs, &mut b).sub(cs, &mut a_a).sub(cs, &mut b_b);
+
+        // c2 = a2 * b0 + b_b
+        let mut c2 = self.c2.mul(cs, b0).add(cs, &mut b_b);
+
+        c0.normalize(cs);
+        c1.normalize(cs);
+        c2.normalize(cs);
+
+        Self::new(c0, c1, c2)
+    }
+
+    /// Inverse of non-zero element, unsatisfiable otherwise
+    pub fn inverse_unchecked<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        // t0 = c0^2 - c1 * c2 * xi
+        let mut c1_c2 = self.c1.mul(cs, &mut self.c2).mul_by_nonresidue(cs);
+        let mut t0 = self.c0.square(cs).sub(cs, &mut c1_c2);
+
+        // t1 = c2^2 * xi - c0 * c1
+        let mut c0_c1 = self.c0.mul(cs, &mut self.c1);
+        let mut t1 = self.c2.square(cs).mul_by_nonresidue(cs).sub(cs, &mut c0_c1);
+
+        // t2 = c1^2 - c0 * c2
+        let mut c0_c2 = self.c0.mul(cs, &mut self.c2);
+        let mut t2 = self.c1.square(cs).sub(cs, &mut c0_c2);
+
+        // d = c0 * t0 + (c2 * t1 + c1 * t2) * xi
+        let mut c1_t2 = self.c1.mul(cs, &mut t2);
+        let mut tmp = self
+            .c2
+            .mul(cs, &mut t1)
+            .add(cs, &mut c1_t2)
+            .mul_by_nonresidue(cs);
+        let mut d = self.c0.mul(cs, &mut t0).add(cs, &mut tmp);
+        let mut d_inverse = d.inverse_unchecked(cs);
+
+        let c0 = t0.mul(cs, &mut d_inverse);
+        let c1 = t1.mul(cs, &mut d_inverse);
+        let c2 = t2.mul(cs, &mut d_inverse);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn frobenius_map<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, power: usize) -> Self {
+        let (gamma_1, gamma_2) = frobenius_coefficients(power % 6);
+        let params = self.get_params().clone();
+        let mut gamma_1 = NonNativeFq2::allocated_constant(cs, gamma_1, &params);
+        let mut gamma_2 = NonNativeFq2::allocated_constant(cs, gamma_2, &params);
+
+        let c0 = self.c0.frobenius_map(cs, power);
+        let c1 = self.c1.frobenius_map(cs, power).mul(cs, &mut gamma_1);
+        let c2 = self.c2.frobenius_map(cs, power).mul(cs, &mut gamma_2);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn is_zero<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        let c0_is_zero = self.c0.is_zero(cs);
+        let c1_is_zero = self.c1.is_zero(cs);
+        let c2_is_zero = self.c2.is_zero(cs);
+
+        Boolean::multi_and(cs, &[c0_is_zero, c1_is_zero, c2_is_zero])
+    }
+
+    pub fn equals<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Boolean<F> {
+        let c0_is_equal = self.c0.equals(cs, &mut other.c0);
+        let c1_is_equal = self.c1.equals(cs, &mut other.c1);
+        let c2_is_equal = self.c2.equals(cs, &mut other.c2);
+
+        Boolean::multi_and(cs, &[c0_is_equal, c1_is_equal, c2_is_equal])
+    }
+
+    pub fn conditionally_select<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        flag: Boolean<F>,
+        a: &Self,
+        b: &Self,
+    ) -> Self {
+        let c0 = NonNativeFq2::conditionally_select(cs, flag, &a.c0, &b.c0);
+        let c1 = NonNativeFq2::conditionally_select(cs, flag, &a.c1, &b.c1);
+        let c2 = NonNativeFq2::conditionally_select(cs, flag, &a.c2, &b.c2);
+
+        Self::new(c0, c1, c2)
+    }
+}
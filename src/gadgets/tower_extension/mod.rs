@@ -0,0 +1,291 @@
+use super::*;
+
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::non_native_field::traits::NonNativeField;
+use pairing::bn256::{Fq, Fq12, Fq2, Fq6};
+use pairing::ff::Field;
+use std::sync::Arc;
+
+// Extension tower over the BN254 base field, as used by the pairing:
+// Fq2 = Fq[u] / (u^2 + 1)
+// Fq6 = Fq2[v] / (v^3 - (9 + u))
+// Fq12 = Fq6[w] / (w^2 - v)
+// It follows the representation of `pairing::bn256`, so witnesses can be directly
+// taken from (and compared to) the native implementation.
+//
+// Convention on reduction: every multiplicative operation on Fq6 and Fq12 returns
+// normalized coordinates, so additive combinations of their outputs always fit
+// into the overflow budget of the underlying non-native field before the next multiplication
+
+pub mod fq12;
+pub mod fq2;
+pub mod fq6;
+
+// 9 * a via additions only, as it's much cheaper than a non-native multiplication by constant
+fn mul_by_nine<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    a: &mut NN,
+) -> NN {
+    let mut doubled = a.double(cs);
+    let mut quadrupled = doubled.double(cs);
+    let mut times_eight = quadrupled.double(cs);
+
+    times_eight.add(cs, a)
+}
+
+#[cfg(test)]
+mod test {
+    use super::fq12::NonNativeFq12;
+    use super::fq2::NonNativeFq2;
+    use super::fq6::NonNativeFq6;
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, StaticToolboxHolder};
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::non_native_field::implementations::*;
+    use crate::gadgets::tables::range_check_16_bits::{
+        create_range_check_16_bits_table, RangeCheck16BitsTable,
+    };
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+    use pairing::ff::PrimeField;
+
+    type F = GoldilocksField;
+    type NN = NonNativeFieldOverU16<F, Fq, 17>;
+
+    fn create_test_cs() -> CSReferenceImplementation<
+        F,
+        F,
+        DevCSConfig,
+        impl GateConfigurationHolder<F>,
+        impl StaticToolboxHolder,
+    > {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 24, 1 << 21);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 1,
+                num_repetitions: 10,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = DotProductGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<16>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        let table = create_range_check_16_bits_table();
+        owned_cs.add_lookup_table::<RangeCheck16BitsTable, 1>(table);
+
+        owned_cs
+    }
+
+    // simple deterministic sequence of "random looking" elements
+    struct ElementsSource(Fq);
+
+    impl ElementsSource {
+        fn new() -> Self {
+            Self(Fq::from_str("1234567891011121314151617181920").unwrap())
+        }
+
+        fn fq(&mut self) -> Fq {
+            self.0.square();
+            self.0.add_assign(&Fq::from_str("7").unwrap());
+
+            self.0
+        }
+
+        fn fq2(&mut self) -> Fq2 {
+            Fq2 {
+                c0: self.fq(),
+                c1: self.fq(),
+            }
+        }
+
+        fn fq6(&mut self) -> Fq6 {
+            Fq6 {
+                c0: self.fq2(),
+                c1: self.fq2(),
+                c2: self.fq2(),
+            }
+        }
+
+        fn fq12(&mut self) -> Fq12 {
+            Fq12 {
+                c0: self.fq6(),
+                c1: self.fq6(),
+            }
+        }
+    }
+
+    fn fq2_value<CS: ConstraintSystem<F>>(cs: &CS, el: &NonNativeFq2<F, NN>) -> Fq2 {
+        Fq2 {
+            c0: el.c0.witness_hook(cs)().unwrap().get(),
+            c1: el.c1.witness_hook(cs)().unwrap().get(),
+        }
+    }
+
+    fn fq6_value<CS: ConstraintSystem<F>>(cs: &CS, el: &NonNativeFq6<F, NN>) -> Fq6 {
+        Fq6 {
+            c0: fq2_value(&*cs, &el.c0),
+            c1: fq2_value(&*cs, &el.c1),
+            c2: fq2_value(&*cs, &el.c2),
+        }
+    }
+
+    fn fq12_value<CS: ConstraintSystem<F>>(cs: &CS, el: &NonNativeFq12<F, NN>) -> Fq12 {
+        Fq12 {
+            c0: fq6_value(&*cs, &el.c0),
+            c1: fq6_value(&*cs, &el.c1),
+        }
+    }
+
+    #[test]
+    fn test_tower_arithmetic() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+        let mut source = ElementsSource::new();
+
+        // Fq2
+        let a_value = source.fq2();
+        let b_value = source.fq2();
+        let mut a = NonNativeFq2::<F, NN>::allocate_checked(cs, a_value, &params);
+        let mut b = NonNativeFq2::<F, NN>::allocate_checked(cs, b_value, &params);
+
+        let mut expected = a_value;
+        expected.mul_assign(&b_value);
+        let product = a.mul(cs, &mut b);
+        assert_eq!(fq2_value(&*cs, &product), expected);
+
+        let mut expected = a_value;
+        expected.mul_by_nonresidue();
+        let product = a.mul_by_nonresidue(cs);
+        assert_eq!(fq2_value(&*cs, &product), expected);
+
+        let expected = a_value.inverse().unwrap();
+        let inverse = a.inverse_unchecked(cs);
+        assert_eq!(fq2_value(&*cs, &inverse), expected);
+
+        // Fq6
+        let a_value = source.fq6();
+        let b_value = source.fq6();
+        let mut a = NonNativeFq6::<F, NN>::allocate_checked(cs, a_value, &params);
+        let mut b = NonNativeFq6::<F, NN>::allocate_checked(cs, b_value, &params);
+
+        let mut expected = a_value;
+        expected.mul_assign(&b_value);
+        let product = a.mul(cs, &mut b);
+        assert_eq!(fq6_value(&*cs, &product), expected);
+
+        let expected = a_value.inverse().unwrap();
+        let inverse = a.inverse_unchecked(cs);
+        assert_eq!(fq6_value(&*cs, &inverse), expected);
+
+        // Fq12
+        let a_value = source.fq12();
+        let b_value = source.fq12();
+        let mut a = NonNativeFq12::<F, NN>::allocate_checked(cs, a_value, &params);
+        let mut b = NonNativeFq12::<F, NN>::allocate_checked(cs, b_value, &params);
+
+        let mut expected = a_value;
+        expected.mul_assign(&b_value);
+        let product = a.mul(cs, &mut b);
+        assert_eq!(fq12_value(&*cs, &product), expected);
+
+        let mut expected = a_value;
+        expected.square();
+        let square = a.square(cs);
+        assert_eq!(fq12_value(&*cs, &square), expected);
+
+        let expected = a_value.inverse().unwrap();
+        let inverse = a.inverse_unchecked(cs);
+        assert_eq!(fq12_value(&*cs, &inverse), expected);
+
+        for power in 1..4 {
+            let mut expected = a_value;
+            expected.frobenius_map(power);
+            let frobenius = a.frobenius_map(cs, power);
+            assert_eq!(fq12_value(&*cs, &frobenius), expected);
+        }
+
+        // sparse multiplication by the line function
+        let s_value = source.fq();
+        let d0_value = source.fq2();
+        let d1_value = source.fq2();
+        let mut sparse_value = Fq12::zero();
+        sparse_value.c0.c0.c0 = s_value;
+        sparse_value.c1.c0 = d0_value;
+        sparse_value.c1.c1 = d1_value;
+
+        let mut s = NN::allocate_checked(cs, s_value, &params);
+        let mut d0 = NonNativeFq2::<F, NN>::allocate_checked(cs, d0_value, &params);
+        let mut d1 = NonNativeFq2::<F, NN>::allocate_checked(cs, d1_value, &params);
+
+        let mut expected = a_value;
+        expected.mul_assign(&sparse_value);
+        let product = a.mul_by_034(cs, &mut s, &mut d0, &mut d1);
+        assert_eq!(fq12_value(&*cs, &product), expected);
+
+        let mut one = NonNativeFq12::<F, NN>::one(cs, &params);
+        let mut inverse = inverse;
+        let mut product = a.mul(cs, &mut inverse);
+        let is_one = product.equals(cs, &mut one);
+        assert!(is_one.witness_hook(&*cs)().unwrap());
+        let is_one = a.equals(cs, &mut one);
+        assert!(!is_one.witness_hook(&*cs)().unwrap());
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
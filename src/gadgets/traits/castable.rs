@@ -265,6 +265,36 @@ impl<F: SmallField> WitnessCastable<F, [F; 2]> for u32 {
     }
 }
 
+// signed integers are represented in two's complement by 32 bit limbs
+
+impl<F: SmallField> WitnessCastable<F, [F; 1]> for i32 {
+    #[inline]
+    fn cast_from_source(witness: [F; 1]) -> Self {
+        <u32 as WitnessCastable<F, F>>::cast_from_source(witness[0]) as Self
+    }
+    #[inline]
+    fn cast_into_source(self) -> [F; 1] {
+        [F::from_u64_unchecked((self as u32) as u64)]
+    }
+}
+
+impl<F: SmallField> WitnessCastable<F, [F; 2]> for i64 {
+    #[inline]
+    fn cast_from_source(witness: [F; 2]) -> Self {
+        let low = <u32 as WitnessCastable<F, F>>::cast_from_source(witness[0]) as u64;
+        let high = <u32 as WitnessCastable<F, F>>::cast_from_source(witness[1]) as u64;
+
+        (low | (high << 32)) as Self
+    }
+    #[inline]
+    fn cast_into_source(self) -> [F; 2] {
+        [
+            F::from_u64_unchecked((self as u32) as u64),
+            F::from_u64_unchecked(((self >> 32) as u32) as u64),
+        ]
+    }
+}
+
 // higher width types will de defined later on
 
 // arrays
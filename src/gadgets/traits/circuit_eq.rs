@@ -0,0 +1,42 @@
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::field::SmallField;
+use crate::gadgets::boolean::Boolean;
+
+pub trait CircuitEq<F: SmallField>: Sized {
+    /// Returns a `Boolean` that is `true` if `a` and `b` represent the same value
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F>;
+
+    /// Enforces that `a` and `b` represent the same value
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        // default implementation is not the cheapest one, but works for any type
+        let are_equal = Self::equals(cs, a, b);
+        let boolean_true = Boolean::allocated_constant(cs, true);
+        Boolean::enforce_equal(cs, &are_equal, &boolean_true);
+    }
+}
+
+impl<F: SmallField> CircuitEq<F> for () {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, _a: &Self, _b: &Self) -> Boolean<F> {
+        Boolean::allocated_constant(cs, true)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(_cs: &mut CS, _a: &Self, _b: &Self) {}
+}
+
+impl<F: SmallField, T: CircuitEq<F>, const N: usize> CircuitEq<F> for [T; N] {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        if N == 0 {
+            return Boolean::allocated_constant(cs, true);
+        }
+
+        let equalities: [_; N] = std::array::from_fn(|idx| T::equals(cs, &a[idx], &b[idx]));
+
+        Boolean::multi_and(cs, &equalities)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        for (a, b) in a.iter().zip(b.iter()) {
+            T::enforce_equal(cs, a, b);
+        }
+    }
+}
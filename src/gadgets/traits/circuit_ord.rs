@@ -0,0 +1,23 @@
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::field::SmallField;
+use crate::gadgets::boolean::Boolean;
+
+pub trait CircuitOrd<F: SmallField>: Sized {
+    /// Returns a `Boolean` that is `true` if `a < b`
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F>;
+
+    /// Returns a `Boolean` that is `true` if `a > b`
+    fn greater_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Self::less_than(cs, b, a)
+    }
+
+    /// Returns a `Boolean` that is `true` if `a <= b`
+    fn less_or_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Self::less_than(cs, b, a).negated(cs)
+    }
+
+    /// Returns a `Boolean` that is `true` if `a >= b`
+    fn greater_or_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Self::less_than(cs, a, b).negated(cs)
+    }
+}
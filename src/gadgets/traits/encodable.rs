@@ -1,6 +1,9 @@
-use crate::cs::traits::cs::ConstraintSystem;
-use crate::cs::Variable;
+use crate::config::CSConfig;
+use crate::config::CSWitnessEvaluationConfig;
+use crate::cs::traits::cs::{ConstraintSystem, DstBuffer};
+use crate::cs::{Place, Variable};
 use crate::field::SmallField;
+use crate::gadgets::num::Num;
 use crate::gadgets::traits::allocatable::{CSAllocatable, CSAllocatableExt};
 
 pub trait CircuitEncodable<F: SmallField, const N: usize>:
@@ -14,6 +17,243 @@ pub trait CircuitEncodableExt<F: SmallField, const N: usize>:
 {
 }
 
+pub trait CircuitDecodable<F: SmallField, const N: usize>: CircuitEncodable<F, N> {
+    // should enforce all the invariants of the type, e.g. range checks
+    fn decode<CS: ConstraintSystem<F>>(cs: &mut CS, encoding: [Variable; N]) -> Self;
+}
+
+// Members that are narrower than the field element are packed together by the `CSEncodable`
+// derive into the elements of at most this width
+pub const ENCODING_PACKING_BITS: usize = 56;
+
+// Shape of the encoding used by the `CSEncodable` and `CSPublicInput` derives. Members with
+// non-zero `PACKED_WIDTH` are encoded as a single range checked element of that width,
+// and can share the element with other such members
+pub trait CircuitEncodingLayout<F: SmallField> {
+    const ENCODING_LENGTH: usize;
+    const PACKED_WIDTH: usize = 0;
+}
+
+// Offset of the member in the derived encoding, where layout is given as (packed width, encoding
+// length) of every member. Packed members are placed into the latest packed element while it
+// has enough bits left, even if full members were placed after it, with the first member in the
+// lowest bits. Offset of the member past the end is the total encoding length
+pub const fn derived_encoding_offset(layout: &[(usize, usize)], member_idx: usize) -> usize {
+    assert!(member_idx <= layout.len());
+
+    let mut offset = 0;
+    // offset of the latest packed element and bits used in it
+    let mut open_slot: Option<(usize, usize)> = None;
+    let mut idx = 0;
+    loop {
+        let (width, length) = if idx < layout.len() {
+            layout[idx]
+        } else {
+            (0, 0)
+        };
+        let member_offset = if width == 0 {
+            offset += length;
+            offset - length
+        } else {
+            assert!(length == 1);
+            assert!(width <= ENCODING_PACKING_BITS);
+            match open_slot {
+                Some((slot_offset, used_bits)) if used_bits + width <= ENCODING_PACKING_BITS => {
+                    open_slot = Some((slot_offset, used_bits + width));
+                    slot_offset
+                }
+                _ => {
+                    open_slot = Some((offset, width));
+                    offset += 1;
+                    offset - 1
+                }
+            }
+        };
+        if idx == member_idx {
+            return member_offset;
+        }
+        idx += 1;
+    }
+}
+
+pub const fn derived_encoding_length(layout: &[(usize, usize)]) -> usize {
+    derived_encoding_offset(layout, layout.len())
+}
+
+// Appends the encoding of the member to the concatenated encodings consumed by `encode_members`
+pub fn encode_member<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    T: CircuitEncodable<F, N>,
+    const N: usize,
+>(
+    cs: &mut CS,
+    member: &T,
+    dst: &mut Vec<Variable>,
+) {
+    dst.extend(member.encode(cs));
+}
+
+// Takes the member from the front of the concatenated encodings produced by `decode_members`
+pub fn decode_member<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    T: CircuitDecodable<F, N>,
+    const N: usize,
+>(
+    cs: &mut CS,
+    src: &mut &[Variable],
+) -> T {
+    let (encoding, rest) = src.split_at(N);
+    *src = rest;
+
+    T::decode(cs, encoding.try_into().unwrap())
+}
+
+// Derived encoding from the concatenated encodings of the members
+pub fn encode_members<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    layout: &[(usize, usize)],
+    members: &[Variable],
+) -> [Variable; N] {
+    assert_eq!(derived_encoding_length(layout), N);
+
+    let mut encoding = [Variable::placeholder(); N];
+    let mut packed_members = vec![vec![]; N];
+    let mut members = members;
+    for (idx, (width, length)) in layout.iter().enumerate() {
+        let (member, rest) = members.split_at(*length);
+        members = rest;
+        let offset = derived_encoding_offset(layout, idx);
+        if *width == 0 {
+            encoding[offset..(offset + length)].copy_from_slice(member);
+        } else {
+            packed_members[offset].push((member[0], *width));
+        }
+    }
+    assert!(members.is_empty());
+
+    for (dst, packed_members) in encoding.iter_mut().zip(packed_members.into_iter()) {
+        if packed_members.len() > 0 {
+            *dst = pack_into_variable(cs, &packed_members);
+        }
+    }
+
+    encoding
+}
+
+// Inverse of `encode_members`. Packed members are NOT range checked, and must be checked
+// when decoded
+pub fn decode_members<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    layout: &[(usize, usize)],
+    encoding: &[Variable],
+) -> Vec<Variable> {
+    assert_eq!(derived_encoding_length(layout), encoding.len());
+
+    let mut widths = vec![vec![]; encoding.len()];
+    for (idx, (width, _)) in layout.iter().enumerate() {
+        if *width > 0 {
+            let offset = derived_encoding_offset(layout, idx);
+            widths[offset].push(*width);
+        }
+    }
+    let mut unpacked: Vec<_> = widths
+        .iter()
+        .zip(encoding.iter())
+        .map(|(widths, packed)| {
+            if widths.len() > 0 {
+                unpack_from_variable(cs, *packed, widths).into_iter()
+            } else {
+                vec![].into_iter()
+            }
+        })
+        .collect();
+
+    let mut members = Vec::with_capacity(encoding.len());
+    for (idx, (width, length)) in layout.iter().enumerate() {
+        let offset = derived_encoding_offset(layout, idx);
+        if *width == 0 {
+            members.extend_from_slice(&encoding[offset..(offset + length)]);
+        } else {
+            members.push(unpacked[offset].next().unwrap());
+        }
+    }
+
+    members
+}
+
+// Packs range checked members given as (variable, width in bits) into a single variable,
+// with the first member in the lowest bits
+pub fn pack_into_variable<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    members: &[(Variable, usize)],
+) -> Variable {
+    assert!(members.len() > 0);
+    let total_width: usize = members.iter().map(|el| el.1).sum();
+    assert!(total_width <= ENCODING_PACKING_BITS);
+    assert!(ENCODING_PACKING_BITS <= F::CAPACITY_BITS);
+
+    if members.len() == 1 {
+        return members[0].0;
+    }
+
+    let mut shift = 0;
+    let mut terms = Vec::with_capacity(members.len());
+    for (variable, width) in members.iter() {
+        terms.push((*variable, F::from_u64_unchecked(1u64 << shift)));
+        shift += *width;
+    }
+
+    Num::linear_combination(cs, &terms).get_variable()
+}
+
+// Inverse of `pack_into_variable`. Returned variables are NOT range checked, and caller
+// must check every member for the decomposition to be unique
+pub fn unpack_from_variable<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    packed: Variable,
+    widths: &[usize],
+) -> Vec<Variable> {
+    assert!(widths.len() > 0);
+    let total_width: usize = widths.iter().sum();
+    assert!(total_width <= ENCODING_PACKING_BITS);
+    assert!(ENCODING_PACKING_BITS <= F::CAPACITY_BITS);
+
+    if widths.len() == 1 {
+        return vec![packed];
+    }
+
+    let members: Vec<_> = (0..widths.len())
+        .map(|_| cs.alloc_variable_without_value())
+        .collect();
+
+    if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
+        let widths = widths.to_vec();
+        let value_fn = move |inputs: &[F], buffer: &mut DstBuffer<'_, '_, F>| {
+            let mut packed = inputs[0].as_u64_reduced();
+            for width in widths.into_iter() {
+                buffer.push(F::from_u64_unchecked(packed & ((1u64 << width) - 1)));
+                packed >>= width;
+            }
+        };
+
+        let outputs: Vec<_> = members.iter().map(|el| Place::from_variable(*el)).collect();
+        cs.set_values_with_dependencies_vararg(&[packed.into()], &outputs, value_fn);
+    }
+
+    let mut shift = 0;
+    let mut terms = Vec::with_capacity(members.len() + 1);
+    for (variable, width) in members.iter().zip(widths.iter()) {
+        terms.push((*variable, F::from_u64_unchecked(1u64 << shift)));
+        shift += *width;
+    }
+    terms.push((packed, F::MINUS_ONE));
+    Num::enforce_zero_for_linear_combination(cs, &terms);
+
+    members
+}
+
 pub trait CircuitVarLengthEncodable<F: SmallField>:
     'static + Send + Sync + CSAllocatable<F>
 {
@@ -59,3 +299,132 @@ impl<F: SmallField> CircuitVarLengthEncodable<F> for () {
         // do nothing
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::CSGeometry;
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::field::U64Representable;
+    use crate::gadgets::boolean::Boolean;
+    use crate::gadgets::tables::xor8::{create_xor8_table, Xor8Table};
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::gadgets::u16::UInt16;
+    use crate::gadgets::u256::UInt256;
+    use crate::gadgets::u32::UInt32;
+    use crate::gadgets::u8::UInt8;
+    use crate::serde_utils::BigArraySerde;
+    use crate::worker::Worker;
+    use cs_derive::*;
+    use derivative::*;
+    use ethereum_types::U256;
+
+    type F = GoldilocksField;
+
+    #[derive(Derivative, CSAllocatable, CSEncodable, WitnessHookable)]
+    #[derivative(Clone, Copy, Debug)]
+    struct TestInnerElement<F: SmallField> {
+        value: UInt32<F>,
+        is_set: Boolean<F>,
+    }
+
+    #[derive(Derivative, CSAllocatable, CSEncodable, WitnessHookable)]
+    #[derivative(Clone, Copy, Debug)]
+    struct TestQueueElement<F: SmallField> {
+        flag: Boolean<F>,
+        value: UInt32<F>,
+        bytes: [UInt8<F>; 7],
+        short: UInt16<F>,
+        inner: TestInnerElement<F>,
+        other_flag: Boolean<F>,
+        wide: UInt256<F>,
+    }
+
+    #[test]
+    fn test_derived_encoding() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 16, 1 << 12);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 8,
+                share_table_id: true,
+            },
+        );
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+        let cs = &mut owned_cs;
+
+        let witness = TestQueueElementWitness {
+            flag: true,
+            value: 0xdeadbeef,
+            bytes: [1, 2, 3, 4, 5, 6, 0xff],
+            short: 0x1234,
+            inner: TestInnerElementWitness {
+                value: 42,
+                is_set: true,
+            },
+            other_flag: false,
+            wide: U256::from_dec_str("123456789012345678901234567890").unwrap(),
+        };
+        let element = TestQueueElement::<F>::allocate(cs, witness.clone());
+
+        // flag and first 6 bytes, value, last byte with short and other flag, inner and wide
+        let encoding: [Variable; 13] = element.encode(cs);
+        let values: Vec<_> = encoding
+            .iter()
+            .map(|el| Num::from_variable(*el).witness_hook(cs)().unwrap())
+            .collect();
+
+        let mut expected_first = 1u64;
+        for (idx, byte) in witness.bytes[..6].iter().enumerate() {
+            expected_first += (*byte as u64) << (1 + 8 * idx);
+        }
+        assert_eq!(values[0], F::from_u64_unchecked(expected_first));
+        assert_eq!(values[1], F::from_u64_unchecked(0xdeadbeef));
+        assert_eq!(values[2], F::from_u64_unchecked(0xff + (0x1234 << 8)));
+        assert_eq!(values[3], F::from_u64_unchecked(42));
+        assert_eq!(values[4], F::from_u64_unchecked(1));
+
+        let decoded = TestQueueElement::<F>::decode(cs, encoding);
+        assert_eq!(decoded.witness_hook(cs)().unwrap(), witness);
+
+        let worker = Worker::new();
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
@@ -3,8 +3,11 @@ use super::*;
 pub mod allocatable;
 pub mod auxiliary;
 pub mod castable;
+pub mod circuit_eq;
+pub mod circuit_ord;
 pub mod configuration;
 pub mod encodable;
+pub mod public_input;
 pub mod round_function;
 pub mod selectable;
 pub mod witnessable;
@@ -0,0 +1,343 @@
+use crate::cs::gates::PublicInputGate;
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::field::SmallField;
+use crate::gadgets::traits::allocatable::CSAllocatable;
+use crate::gadgets::traits::encodable::{
+    derived_encoding_length, derived_encoding_offset, CircuitEncodable, ENCODING_PACKING_BITS,
+};
+use std::marker::PhantomData;
+
+// Typed public inputs. In-circuit values are placed into the public input gates in the order
+// of the `CircuitEncodable` encoding, and native encoding of the witness must produce the same
+// values, so that the struct can be decoded from the `Proof::public_inputs`.
+// Public inputs come from the untrusted proof, so decoding returns `None` on malformed encoding
+// instead of panicking
+pub trait CSPublicInput<F: SmallField, const N: usize>: CircuitEncodable<F, N> {
+    fn encode_witness(witness: &Self::Witness) -> [F; N];
+    fn decode_witness(encoding: [F; N]) -> Option<Self::Witness>;
+
+    fn declare_public_input<CS: ConstraintSystem<F>>(&self, cs: &mut CS) {
+        for variable in self.encode(cs).into_iter() {
+            let gate = PublicInputGate::new(variable);
+            gate.add_to_cs(cs);
+        }
+    }
+
+    // Decodes the value placed by `declare_public_input` at the given offset
+    fn decode_public_inputs(public_inputs: &[F], offset: usize) -> Option<Self::Witness> {
+        let end = offset.checked_add(N)?;
+        let encoding: [F; N] = public_inputs.get(offset..end)?.try_into().ok()?;
+
+        Self::decode_witness(encoding)
+    }
+}
+
+// Native counterpart of `pack_into_variable`
+pub fn pack_witness_values<F: SmallField>(members: &[(u64, usize)]) -> F {
+    let total_width: usize = members.iter().map(|el| el.1).sum();
+    assert!(total_width <= ENCODING_PACKING_BITS);
+
+    let mut result = 0u64;
+    let mut shift = 0;
+    for (value, width) in members.iter() {
+        debug_assert!(*width == 64 || *value < (1u64 << *width));
+        result |= *value << shift;
+        shift += *width;
+    }
+
+    F::from_u64_unchecked(result)
+}
+
+// Native counterpart of `unpack_from_variable`. Returns `None` if value doesn't fit into the widths
+pub fn unpack_witness_values<F: SmallField>(packed: F, widths: &[usize]) -> Option<Vec<u64>> {
+    let total_width: usize = widths.iter().sum();
+    assert!(total_width <= ENCODING_PACKING_BITS);
+
+    let mut packed = packed.as_u64_reduced();
+    let mut result = Vec::with_capacity(widths.len());
+    for width in widths.iter() {
+        result.push(packed & ((1u64 << *width) - 1));
+        packed >>= *width;
+    }
+    if packed != 0 {
+        return None;
+    }
+
+    Some(result)
+}
+
+// Native counterpart of `encode_member`. Member type is only used to select the encoding
+pub fn encode_member_witness<F: SmallField, T: CSPublicInput<F, N>, const N: usize>(
+    _member: PhantomData<T>,
+    witness: &<T as CSAllocatable<F>>::Witness,
+    dst: &mut Vec<F>,
+) {
+    dst.extend(T::encode_witness(witness));
+}
+
+// Native counterpart of `decode_member`
+pub fn decode_member_witness<F: SmallField, T: CSPublicInput<F, N>, const N: usize>(
+    _member: PhantomData<T>,
+    src: &mut &[F],
+) -> Option<<T as CSAllocatable<F>>::Witness> {
+    let (encoding, rest) = src.split_at(N);
+    *src = rest;
+
+    T::decode_witness(encoding.try_into().unwrap())
+}
+
+// Native counterpart of `encode_members`
+pub fn encode_member_witnesses<F: SmallField, const N: usize>(
+    layout: &[(usize, usize)],
+    members: &[F],
+) -> [F; N] {
+    assert_eq!(derived_encoding_length(layout), N);
+
+    let mut encoding = [F::ZERO; N];
+    let mut packed_members = vec![vec![]; N];
+    let mut members = members;
+    for (idx, (width, length)) in layout.iter().enumerate() {
+        let (member, rest) = members.split_at(*length);
+        members = rest;
+        let offset = derived_encoding_offset(layout, idx);
+        if *width == 0 {
+            encoding[offset..(offset + length)].copy_from_slice(member);
+        } else {
+            packed_members[offset].push((member[0].as_u64_reduced(), *width));
+        }
+    }
+    assert!(members.is_empty());
+
+    for (dst, packed_members) in encoding.iter_mut().zip(packed_members.into_iter()) {
+        if packed_members.len() > 0 {
+            *dst = pack_witness_values(&packed_members);
+        }
+    }
+
+    encoding
+}
+
+// Native counterpart of `decode_members`. Returns `None` if packed element has bits above
+// the total width of its members
+pub fn decode_member_witnesses<F: SmallField>(
+    layout: &[(usize, usize)],
+    encoding: &[F],
+) -> Option<Vec<F>> {
+    assert_eq!(derived_encoding_length(layout), encoding.len());
+
+    let mut widths = vec![vec![]; encoding.len()];
+    for (idx, (width, _)) in layout.iter().enumerate() {
+        if *width > 0 {
+            widths[derived_encoding_offset(layout, idx)].push(*width);
+        }
+    }
+    let mut unpacked = Vec::with_capacity(encoding.len());
+    for (widths, packed) in widths.iter().zip(encoding.iter()) {
+        if widths.len() > 0 {
+            unpacked.push(unpack_witness_values::<F>(*packed, widths)?.into_iter());
+        } else {
+            unpacked.push(vec![].into_iter());
+        }
+    }
+
+    let mut members = Vec::with_capacity(encoding.len());
+    for (idx, (width, length)) in layout.iter().enumerate() {
+        let offset = derived_encoding_offset(layout, idx);
+        if *width == 0 {
+            members.extend_from_slice(&encoding[offset..(offset + length)]);
+        } else {
+            members.push(F::from_u64_unchecked(unpacked[offset].next().unwrap()));
+        }
+    }
+
+    Some(members)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebraic_props::round_function::AbsorptionModeOverwrite;
+    use crate::algebraic_props::sponge::GoldilocksPoseidon2Sponge;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::*;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::cs_builder_verifier::CsVerifierBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::pow::NoPow;
+    use crate::cs::implementations::prover::ProofConfig;
+    use crate::cs::implementations::transcript::GoldilocksPoisedon2Transcript;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::Variable;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, LookupParameters, StaticToolboxHolder};
+    use crate::field::goldilocks::{GoldilocksExt2, GoldilocksField};
+    use crate::field::U64Representable;
+    use crate::gadgets::boolean::Boolean;
+    use crate::gadgets::num::Num;
+    use crate::gadgets::tables::xor8::{create_xor8_table, Xor8Table};
+    use crate::gadgets::traits::allocatable::CSAllocatable;
+    use crate::gadgets::traits::encodable::{
+        decode_member, decode_members, encode_member, encode_members, CircuitDecodable,
+        CircuitEncodingLayout,
+    };
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::gadgets::u256::UInt256;
+    use crate::gadgets::u8::UInt8;
+    use crate::serde_utils::BigArraySerde;
+    use crate::worker::Worker;
+    use cs_derive::*;
+    use derivative::*;
+    use ethereum_types::U256;
+
+    type F = GoldilocksField;
+    type EXT = GoldilocksExt2;
+    type TR = GoldilocksPoisedon2Transcript;
+    type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+    #[derive(Derivative, CSAllocatable, CSEncodable, CSPublicInput, WitnessHookable)]
+    #[derivative(Clone, Copy, Debug)]
+    struct TestBlockOutput<F: SmallField> {
+        is_successful: Boolean<F>,
+        state_root: UInt256<F>,
+        version: [UInt8<F>; 3],
+        is_final: Boolean<F>,
+    }
+
+    const GEOMETRY: CSGeometry = CSGeometry {
+        num_columns_under_copy_permutation: 60,
+        num_witness_columns: 0,
+        num_constant_columns: 4,
+        max_allowed_constraint_degree: 4,
+    };
+
+    fn configure<
+        T: CsBuilderImpl<F, T>,
+        GC: GateConfigurationHolder<F>,
+        TB: StaticToolboxHolder,
+    >(
+        builder: CsBuilder<T, F, GC, TB>,
+    ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+        let builder = builder.allow_lookup(
+            LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 8,
+                share_table_id: true,
+            },
+        );
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = PublicInputGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        builder
+    }
+
+    #[test]
+    fn test_typed_public_input() {
+        let worker = Worker::new();
+
+        let witness = TestBlockOutputWitness {
+            is_successful: true,
+            state_root: U256::from_dec_str("987654321098765432109876543210").unwrap(),
+            version: [1, 0, 0xff],
+            is_final: false,
+        };
+
+        // flags and version are packed into a single element
+        let encoding = TestBlockOutput::<F>::encode_witness(&witness);
+        assert_eq!(encoding.len(), 9);
+        assert_eq!(
+            encoding[0],
+            F::from_u64_unchecked(1 + (1 << 1) + (0xff << 17))
+        );
+        assert_eq!(
+            TestBlockOutput::<F>::decode_witness(encoding),
+            Some(witness.clone())
+        );
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(GEOMETRY, 1 << 16, 1 << 12);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = configure(builder);
+        let mut cs = builder.build(());
+        let table = create_xor8_table();
+        cs.add_lookup_table::<Xor8Table, 3>(table);
+
+        let output = TestBlockOutput::<F>::allocate(&mut cs, witness.clone());
+        let circuit_encoding: Vec<_> = output
+            .encode(&mut cs)
+            .into_iter()
+            .map(|el| Num::from_variable(el).witness_hook(&cs)().unwrap())
+            .collect();
+        assert_eq!(circuit_encoding, encoding.to_vec());
+        output.declare_public_input(&mut cs);
+
+        cs.pad_and_shrink();
+        let cs = cs.into_assembly();
+        let (proof, vk) =
+            cs.prove_one_shot::<2, EXT, TR, H, NoPow>(&worker, ProofConfig::default(), ());
+
+        let builder_impl = CsVerifierBuilder::<F, 2, EXT>::new_from_parameters(GEOMETRY);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = configure(builder);
+        let verifier = builder.build(());
+        assert!(verifier.verify::<H, TR, NoPow>((), &vk, &proof));
+
+        assert_eq!(
+            TestBlockOutput::<F>::decode_public_inputs(&proof.public_inputs, 0),
+            Some(witness)
+        );
+    }
+
+    #[test]
+    fn test_malformed_public_inputs() {
+        let witness = TestBlockOutputWitness {
+            is_successful: true,
+            state_root: U256::from(42u64),
+            version: [1, 2, 3],
+            is_final: true,
+        };
+        let encoding = TestBlockOutput::<F>::encode_witness(&witness);
+
+        // not enough elements
+        assert!(TestBlockOutput::<F>::decode_public_inputs(&encoding[..8], 0).is_none());
+        assert!(TestBlockOutput::<F>::decode_public_inputs(&encoding, 1).is_none());
+        assert!(TestBlockOutput::<F>::decode_public_inputs(&encoding, usize::MAX).is_none());
+
+        // packed element has bits above the total width of its members
+        let mut malformed = encoding;
+        malformed[0] = F::from_u64_unchecked(1 << 40);
+        assert!(TestBlockOutput::<F>::decode_witness(malformed).is_none());
+
+        // limb of UInt256 doesn't fit into 32 bits
+        let mut malformed = encoding;
+        malformed[1] = F::from_u64_unchecked(1 << 32);
+        assert!(TestBlockOutput::<F>::decode_witness(malformed).is_none());
+
+        assert!(
+            <UInt8<F> as CSPublicInput<F, 1>>::decode_witness([F::from_u64_unchecked(256)])
+                .is_none()
+        );
+        assert!(
+            <Boolean<F> as CSPublicInput<F, 1>>::decode_witness([F::from_u64_unchecked(2)])
+                .is_none()
+        );
+    }
+}
@@ -101,6 +101,39 @@ impl<F: SmallField, T: Selectable<F>, const N: usize> Selectable<F> for [T; N] {
     }
 }
 
+// Selects element by the little-endian index bits, so `elements` must have `2^bits.len()` entries
+pub fn binary_select<F: SmallField, T: Selectable<F>, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    elements: &[T],
+    bits: &[Boolean<F>],
+) -> T {
+    assert_eq!(elements.len(), 1 << bits.len());
+    assert!(bits.len() > 0);
+
+    let mut input_space = Vec::with_capacity(elements.len() / 2);
+    let mut dst_space = Vec::with_capacity(elements.len() / 2);
+
+    for (idx, bit) in bits.iter().enumerate() {
+        let src = if idx == 0 { elements } else { &input_space };
+
+        debug_assert_eq!(elements.len() % 2, 0);
+        dst_space.clear();
+
+        for src in src.array_chunks::<2>() {
+            let [a, b] = src;
+            // NOTE order here
+            let selected = T::conditionally_select(cs, *bit, b, a);
+            dst_space.push(selected);
+        }
+
+        std::mem::swap(&mut dst_space, &mut input_space);
+    }
+
+    assert_eq!(input_space.len(), 1);
+
+    input_space.pop().unwrap()
+}
+
 // we can select by boolean masks
 pub trait MultiSelectable<F: SmallField>: Selectable<F> {}
 
@@ -185,6 +185,18 @@ impl<F: SmallField> Selectable<F> for UInt160<F> {
     }
 }
 
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+
+impl<F: SmallField> CircuitEq<F> for UInt160<F> {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        UInt160::equals(cs, a, b)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        <[UInt32<F>; 5] as CircuitEq<F>>::enforce_equal(cs, &a.inner, &b.inner)
+    }
+}
+
 use crate::gadgets::traits::castable::Convertor;
 use crate::gadgets::traits::castable::WitnessCastable;
 use crate::gadgets::traits::witnessable::CSWitnessable;
@@ -247,6 +259,46 @@ impl<F: SmallField> CircuitVarLengthEncodable<F> for UInt160<F> {
     }
 }
 
+use crate::gadgets::traits::encodable::{
+    CircuitDecodable, CircuitEncodable, CircuitEncodingLayout,
+};
+
+impl<F: SmallField> CircuitEncodable<F, 5> for UInt160<F> {
+    #[inline(always)]
+    fn encode<CS: ConstraintSystem<F>>(&self, _cs: &mut CS) -> [Variable; 5] {
+        self.inner.map(|el| el.get_variable())
+    }
+}
+
+impl<F: SmallField> CircuitDecodable<F, 5> for UInt160<F> {
+    #[inline(always)]
+    fn decode<CS: ConstraintSystem<F>>(cs: &mut CS, encoding: [Variable; 5]) -> Self {
+        Self {
+            inner: encoding.map(|el| UInt32::from_variable_checked(cs, el)),
+        }
+    }
+}
+
+use crate::gadgets::traits::public_input::CSPublicInput;
+
+impl<F: SmallField> CSPublicInput<F, 5> for UInt160<F> {
+    fn encode_witness(witness: &Self::Witness) -> [F; 5] {
+        decompose_address_as_u32x5(*witness).map(|el| F::from_u64_unchecked(el as u64))
+    }
+    fn decode_witness(encoding: [F; 5]) -> Option<Self::Witness> {
+        let mut limbs = [0u32; 5];
+        for (dst, el) in limbs.iter_mut().zip(encoding.into_iter()) {
+            *dst = <UInt32<F> as CSPublicInput<F, 1>>::decode_witness([el])?;
+        }
+
+        Some(recompose_address_from_u32x5(limbs))
+    }
+}
+
+impl<F: SmallField> CircuitEncodingLayout<F> for UInt160<F> {
+    const ENCODING_LENGTH: usize = 5;
+}
+
 use crate::gadgets::traits::allocatable::CSPlaceholder;
 
 impl<F: SmallField> CSPlaceholder<F> for UInt160<F> {
@@ -4,6 +4,8 @@ use crate::cs::traits::cs::DstBuffer;
 use crate::field::SmallField;
 use crate::gadgets::blake2s::mixing_function::merge_byte_using_table;
 use crate::gadgets::boolean::Boolean;
+use crate::gadgets::impls::limbs_arithmetic::div_rem_limbs;
+use crate::gadgets::num::Num;
 use crate::gadgets::tables::ByteSplitTable;
 use crate::gadgets::traits::allocatable::CSAllocatable;
 use crate::gadgets::traits::allocatable::CSAllocatableExt;
@@ -11,6 +13,7 @@ use crate::gadgets::traits::witnessable::CSWitnessable;
 use crate::gadgets::traits::witnessable::WitnessHookable;
 use crate::gadgets::u32::UInt32;
 use crate::gadgets::u512::UInt512;
+use crate::gadgets::u8::bitwise;
 use crate::gadgets::u8::UInt8;
 use ethereum_types::U256;
 
@@ -115,6 +118,26 @@ impl<F: SmallField> Selectable<F> for UInt256<F> {
     }
 }
 
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+
+impl<F: SmallField> CircuitEq<F> for UInt256<F> {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        UInt256::equals(cs, a, b)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        <[UInt32<F>; 8] as CircuitEq<F>>::enforce_equal(cs, &a.inner, &b.inner)
+    }
+}
+
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitOrd<F> for UInt256<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        a.less_than(cs, b)
+    }
+}
+
 impl<F: SmallField> UInt256<F> {
     pub fn allocated_constant<CS: ConstraintSystem<F>>(cs: &mut CS, constant: U256) -> Self {
         debug_assert!(F::CAPACITY_BITS >= 32);
@@ -337,6 +360,275 @@ impl<F: SmallField> UInt256<F> {
         });
         Self::from_le_bytes(cs, bytes)
     }
+
+    // Returns `true` if `self < other`
+    pub fn less_than<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Boolean<F> {
+        let (_, borrow) = self.overflowing_sub(cs, other);
+
+        borrow
+    }
+
+    // Returns `true` if `self > other`
+    pub fn greater_than<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Boolean<F> {
+        other.less_than(cs, self)
+    }
+
+    // Returns `true` if `self <= other`
+    pub fn less_or_equal<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Boolean<F> {
+        self.greater_than(cs, other).negated(cs)
+    }
+
+    // Returns `true` if `self >= other`
+    pub fn greater_or_equal<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        other: &Self,
+    ) -> Boolean<F> {
+        self.less_than(cs, other).negated(cs)
+    }
+
+    // Returns quotient and remainder of division of `self` by `other`. Follows the EVM
+    // semantics, so division by zero gives zero quotient and zero remainder
+    pub fn div_rem<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> (Self, Self) {
+        let (quotient, remainder) = div_rem_limbs(cs, self.inner, other.inner);
+
+        (Self { inner: quotient }, Self { inner: remainder })
+    }
+
+    // Returns `(self + other) mod modulus` without intermediate overflow,
+    // and zero if modulus is zero
+    pub fn add_mod<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        other: &Self,
+        modulus: &Self,
+    ) -> Self {
+        let (sum, carry) = self.overflowing_add(cs, other);
+        let mut dividend = [sum.inner[0]; 9];
+        dividend[..8].copy_from_slice(&sum.inner);
+        // boolean is a valid 32 bit integer
+        dividend[8] = unsafe { UInt32::from_variable_unchecked(carry.get_variable()) };
+
+        let (_, remainder) = div_rem_limbs(cs, dividend, modulus.inner);
+
+        Self { inner: remainder }
+    }
+
+    // Returns `(self * other) mod modulus` without intermediate overflow,
+    // and zero if modulus is zero
+    pub fn mul_mod<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        other: &Self,
+        modulus: &Self,
+    ) -> Self {
+        let product = self.widening_mul(cs, other, 8, 8);
+        let (_, remainder) = div_rem_limbs(cs, product.inner, modulus.inner);
+
+        Self { inner: remainder }
+    }
+
+    // Returns `self << shift` and a flag if `shift >= 256`, in which case the result is zero
+    pub fn overflowing_shl<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> (Self, Boolean<F>) {
+        let (power_of_two, overflow) = Self::power_of_two_for_shift(cs, shift);
+        let result = self.widening_mul(cs, &power_of_two, 8, 8).to_low();
+        let result = result.mask_negated(cs, overflow);
+
+        (result, overflow)
+    }
+
+    // Returns `self >> shift` and a flag if `shift >= 256`, in which case the result is zero
+    pub fn overflowing_shr<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> (Self, Boolean<F>) {
+        let (power_of_two, overflow) = Self::power_of_two_for_shift(cs, shift);
+        let (result, _) = self.div_rem(cs, &power_of_two);
+        let result = result.mask_negated(cs, overflow);
+
+        (result, overflow)
+    }
+
+    // Returns `2^(shift mod 256)` and a flag if `shift >= 256`
+    fn power_of_two_for_shift<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> (Self, Boolean<F>) {
+        let bytes = shift.decompose_into_bytes(cs);
+        let low_byte = Num::from_variable(bytes[0].get_variable());
+        let fits_into_byte = Num::equals(cs, &low_byte, &shift.into_num());
+        let overflow = fits_into_byte.negated(cs);
+
+        let bits = low_byte.spread_into_bits::<CS, 8>(cs);
+
+        // lowest 5 bits give a power of two within the limb
+        let one = Num::allocated_constant(cs, F::ONE);
+        let mut in_limb = one;
+        for (idx, bit) in bits[..5].iter().enumerate() {
+            let multiplier = Num::allocated_constant(cs, F::from_u64_unchecked(1u64 << (1 << idx)));
+            let multiplier = Num::conditionally_select(cs, *bit, &multiplier, &one);
+            in_limb = in_limb.mul(cs, &multiplier);
+        }
+        // it's at most 2^31
+        let in_limb = unsafe { UInt32::from_variable_unchecked(in_limb.get_variable()) };
+
+        // and highest 3 bits give an index of the limb
+        let limb_index = Num::linear_combination(
+            cs,
+            &[
+                (bits[5].get_variable(), F::ONE),
+                (bits[6].get_variable(), F::TWO),
+                (bits[7].get_variable(), F::from_u64_unchecked(4)),
+            ],
+        );
+        let inner = std::array::from_fn(|idx| {
+            let index = Num::allocated_constant(cs, F::from_u64_unchecked(idx as u64));
+            let is_selected = Num::equals(cs, &limb_index, &index);
+
+            in_limb.mask(cs, is_selected)
+        });
+
+        (Self { inner }, overflow)
+    }
+
+    pub fn xor<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Self {
+        let a = self.to_le_bytes(cs);
+        let b = other.to_le_bytes(cs);
+        let bytes = bitwise::xor_bytes(cs, &a, &b);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    pub fn and<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Self {
+        let a = self.to_le_bytes(cs);
+        let b = other.to_le_bytes(cs);
+        let bytes = bitwise::and_bytes(cs, &a, &b);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    pub fn or<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Self {
+        let a = self.to_le_bytes(cs);
+        let b = other.to_le_bytes(cs);
+        let bytes = bitwise::or_bytes(cs, &a, &b);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    pub fn not<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Self {
+        let bytes = self.to_le_bytes(cs);
+        let bytes = bitwise::not_bytes(cs, &bytes);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Returns `self << amount`, `amount` must be less than 256
+    pub fn shl<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        assert!(amount < 256);
+        if amount % 32 == 0 {
+            // whole limbs are just moved
+            let offset = (amount / 32) as usize;
+            let zero = UInt32::zero(cs);
+            let inner = std::array::from_fn(|idx| {
+                idx.checked_sub(offset)
+                    .map(|src| self.inner[src])
+                    .unwrap_or(zero)
+            });
+
+            return Self { inner };
+        }
+
+        let bytes = self.to_le_bytes(cs);
+        let bytes = bitwise::shl_bytes(cs, &bytes, amount as usize, false);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Returns `self >> amount`, `amount` must be less than 256
+    pub fn shr<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        assert!(amount < 256);
+        if amount % 32 == 0 {
+            // whole limbs are just moved
+            let offset = (amount / 32) as usize;
+            let zero = UInt32::zero(cs);
+            let inner = std::array::from_fn(|idx| {
+                Some(idx + offset)
+                    .filter(|src| *src < 8)
+                    .map(|src| self.inner[src])
+                    .unwrap_or(zero)
+            });
+
+            return Self { inner };
+        }
+
+        let bytes = self.to_le_bytes(cs);
+        let bytes = bitwise::shr_bytes(cs, &bytes, amount as usize, false);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Rotates left by `amount` modulo 256
+    pub fn rotate_left<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        let amount = amount % 256;
+        if amount % 32 == 0 {
+            // whole limbs are just moved
+            let offset = (amount / 32) as usize;
+            let inner = std::array::from_fn(|idx| self.inner[(idx + 8 - offset) % 8]);
+
+            return Self { inner };
+        }
+
+        let bytes = self.to_le_bytes(cs);
+        let bytes = bitwise::shl_bytes(cs, &bytes, amount as usize, true);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Rotates right by `amount` modulo 256
+    pub fn rotate_right<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        let amount = amount % 256;
+        if amount % 32 == 0 {
+            // whole limbs are just moved
+            let offset = (amount / 32) as usize;
+            let inner = std::array::from_fn(|idx| self.inner[(idx + offset) % 8]);
+
+            return Self { inner };
+        }
+
+        let bytes = self.to_le_bytes(cs);
+        let bytes = bitwise::shr_bytes(cs, &bytes, amount as usize, true);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Rotates left by `shift` modulo 256
+    pub fn rotate_left_variable<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> Self {
+        let bytes = self.to_le_bytes(cs);
+        let (bytes, _) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, true, true);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Rotates right by `shift` modulo 256
+    pub fn rotate_right_variable<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> Self {
+        let bytes = self.to_le_bytes(cs);
+        let (bytes, _) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, false, true);
+
+        Self::from_le_bytes(cs, bytes)
+    }
 }
 
 use crate::cs::Variable;
@@ -401,6 +693,49 @@ impl<F: SmallField> CircuitVarLengthEncodable<F> for UInt256<F> {
     }
 }
 
+use crate::gadgets::traits::encodable::{
+    CircuitDecodable, CircuitEncodable, CircuitEncodingLayout,
+};
+
+impl<F: SmallField> CircuitEncodable<F, 8> for UInt256<F> {
+    #[inline(always)]
+    fn encode<CS: ConstraintSystem<F>>(&self, _cs: &mut CS) -> [Variable; 8] {
+        self.inner.map(|el| el.get_variable())
+    }
+}
+
+impl<F: SmallField> CircuitDecodable<F, 8> for UInt256<F> {
+    #[inline(always)]
+    fn decode<CS: ConstraintSystem<F>>(cs: &mut CS, encoding: [Variable; 8]) -> Self {
+        Self {
+            inner: encoding.map(|el| UInt32::from_variable_checked(cs, el)),
+        }
+    }
+}
+
+use crate::gadgets::traits::public_input::CSPublicInput;
+
+// Public input layout must be the same as `CircuitEncodable` one, and that is 8 limbs of 32 bits that
+// we also use for queues and commitments. Packing into 5 elements of 56 bits would require splitting
+// every limb into bytes in circuit, that is more expensive than 3 extra public inputs
+impl<F: SmallField> CSPublicInput<F, 8> for UInt256<F> {
+    fn encode_witness(witness: &Self::Witness) -> [F; 8] {
+        decompose_u256_as_u32x8(*witness).map(|el| F::from_u64_unchecked(el as u64))
+    }
+    fn decode_witness(encoding: [F; 8]) -> Option<Self::Witness> {
+        let mut limbs = [0u32; 8];
+        for (dst, el) in limbs.iter_mut().zip(encoding.into_iter()) {
+            *dst = <UInt32<F> as CSPublicInput<F, 1>>::decode_witness([el])?;
+        }
+
+        Some(recompose_u256_as_u32x8(limbs))
+    }
+}
+
+impl<F: SmallField> CircuitEncodingLayout<F> for UInt256<F> {
+    const ENCODING_LENGTH: usize = 8;
+}
+
 use crate::gadgets::traits::allocatable::CSPlaceholder;
 
 impl<F: SmallField> CSPlaceholder<F> for UInt256<F> {
@@ -408,3 +743,200 @@ impl<F: SmallField> CSPlaceholder<F> for UInt256<F> {
         Self::zero(cs)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, StaticToolboxHolder};
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::tables::{
+        and8::{create_and8_table, And8Table},
+        xor8::{create_xor8_table, Xor8Table},
+    };
+    use crate::worker::Worker;
+    use ethereum_types::U512;
+
+    type F = GoldilocksField;
+
+    fn create_test_cs() -> CSReferenceImplementation<
+        F,
+        F,
+        DevCSConfig,
+        impl GateConfigurationHolder<F>,
+        impl StaticToolboxHolder,
+    > {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 24, 1 << 20);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 8,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<32>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = U8x4FMAGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+
+        let table = create_and8_table();
+        owned_cs.add_lookup_table::<And8Table, 3>(table);
+
+        owned_cs
+    }
+
+    fn test_values() -> Vec<U256> {
+        vec![
+            U256::zero(),
+            U256::one(),
+            U256::from(7u64),
+            U256::from(0x1234_5678_9abc_def0u64),
+            U256::from_dec_str(
+                "21888242871839275222246405745257275088696311157297823662689037894645226208583",
+            )
+            .unwrap(),
+            U256::from_dec_str("340282366920938463463374607431768211457").unwrap(),
+            U256::MAX,
+        ]
+    }
+
+    #[test]
+    fn test_div_rem_and_modular_arithmetic() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let values = test_values();
+        for a_value in values.iter().copied() {
+            for b_value in values.iter().copied() {
+                let a = UInt256::allocate(cs, a_value);
+                let b = UInt256::allocate(cs, b_value);
+
+                let (quotient, remainder) = a.div_rem(cs, &b);
+                let (expected_quotient, expected_remainder) = if b_value.is_zero() {
+                    (U256::zero(), U256::zero())
+                } else {
+                    a_value.div_mod(b_value)
+                };
+                assert_eq!(quotient.witness_hook(&*cs)().unwrap(), expected_quotient);
+                assert_eq!(remainder.witness_hook(&*cs)().unwrap(), expected_remainder);
+
+                let is_less = a.less_than(cs, &b);
+                assert_eq!(is_less.witness_hook(&*cs)().unwrap(), a_value < b_value);
+                let is_greater_or_equal = a.greater_or_equal(cs, &b);
+                assert_eq!(
+                    is_greater_or_equal.witness_hook(&*cs)().unwrap(),
+                    a_value >= b_value
+                );
+
+                for modulus_value in values.iter().copied() {
+                    let modulus = UInt256::allocate(cs, modulus_value);
+                    let (expected_sum, expected_product) = if modulus_value.is_zero() {
+                        (U256::zero(), U256::zero())
+                    } else {
+                        let modulus_value = U512::from(modulus_value);
+                        let sum = (U512::from(a_value) + U512::from(b_value)) % modulus_value;
+                        let product = a_value.full_mul(b_value) % modulus_value;
+
+                        (
+                            U256::try_from(sum).unwrap(),
+                            U256::try_from(product).unwrap(),
+                        )
+                    };
+
+                    let sum = a.add_mod(cs, &b, &modulus);
+                    assert_eq!(sum.witness_hook(&*cs)().unwrap(), expected_sum);
+                    let product = a.mul_mod(cs, &b, &modulus);
+                    assert_eq!(product.witness_hook(&*cs)().unwrap(), expected_product);
+                }
+            }
+        }
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    fn test_variable_shifts() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        for value in test_values() {
+            let a = UInt256::allocate(cs, value);
+            for shift_value in [0u32, 1, 31, 32, 77, 255, 256, 300, 1 << 20] {
+                let shift = UInt32::allocate_checked(cs, shift_value);
+                let (expected_shl, expected_shr) = if shift_value >= 256 {
+                    (U256::zero(), U256::zero())
+                } else {
+                    (value << shift_value as usize, value >> shift_value as usize)
+                };
+
+                let (shl, overflow) = a.overflowing_shl(cs, &shift);
+                assert_eq!(shl.witness_hook(&*cs)().unwrap(), expected_shl);
+                assert_eq!(overflow.witness_hook(&*cs)().unwrap(), shift_value >= 256);
+
+                let (shr, overflow) = a.overflowing_shr(cs, &shift);
+                assert_eq!(shr.witness_hook(&*cs)().unwrap(), expected_shr);
+                assert_eq!(overflow.witness_hook(&*cs)().unwrap(), shift_value >= 256);
+            }
+        }
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
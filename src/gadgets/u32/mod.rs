@@ -184,6 +184,32 @@ impl<F: SmallField> Selectable<F> for UInt32<F> {
     }
 }
 
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+
+impl<F: SmallField> CircuitEq<F> for UInt32<F> {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        UInt32::equals(cs, a, b)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        Num::enforce_equal(
+            cs,
+            &Num::from_variable(a.get_variable()),
+            &Num::from_variable(b.get_variable()),
+        )
+    }
+}
+
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitOrd<F> for UInt32<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        let (_, borrow) = a.overflowing_sub(cs, *b);
+
+        borrow
+    }
+}
+
 impl<F: SmallField> UInt32<F> {
     #[inline]
     pub const fn get_variable(&self) -> Variable {
@@ -838,6 +864,121 @@ impl<F: SmallField> UInt32<F> {
         });
         Self::from_le_bytes(cs, bytes)
     }
+
+    pub fn xor<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Self {
+        let a = self.decompose_into_bytes(cs);
+        let b = other.decompose_into_bytes(cs);
+        let bytes = bitwise::xor_bytes(cs, &a, &b);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    pub fn and<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Self {
+        let a = self.decompose_into_bytes(cs);
+        let b = other.decompose_into_bytes(cs);
+        let bytes = bitwise::and_bytes(cs, &a, &b);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    pub fn or<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Self {
+        let a = self.decompose_into_bytes(cs);
+        let b = other.decompose_into_bytes(cs);
+        let bytes = bitwise::or_bytes(cs, &a, &b);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    pub fn not<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Self {
+        let bytes = self.decompose_into_bytes(cs);
+        let bytes = bitwise::not_bytes(cs, &bytes);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Returns `self << amount`, `amount` must be less than 32
+    pub fn shl<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        assert!(amount < 32);
+        let bytes = self.decompose_into_bytes(cs);
+        let bytes = bitwise::shl_bytes(cs, &bytes, amount as usize, false);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Returns `self >> amount`, `amount` must be less than 32
+    pub fn shr<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        assert!(amount < 32);
+        let bytes = self.decompose_into_bytes(cs);
+        let bytes = bitwise::shr_bytes(cs, &bytes, amount as usize, false);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Rotates left by `amount` modulo 32
+    pub fn rotate_left<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        let amount = amount % 32;
+        let bytes = self.decompose_into_bytes(cs);
+        let bytes = bitwise::shl_bytes(cs, &bytes, amount as usize, true);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Rotates right by `amount` modulo 32
+    pub fn rotate_right<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        let amount = amount % 32;
+        let bytes = self.decompose_into_bytes(cs);
+        let bytes = bitwise::shr_bytes(cs, &bytes, amount as usize, true);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Returns `self << shift` and a flag if `shift >= 32`, in which case the result is zero
+    pub fn overflowing_shl<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> (Self, Boolean<F>) {
+        let bytes = self.decompose_into_bytes(cs);
+        let (bytes, overflow) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, true, false);
+
+        (Self::from_le_bytes(cs, bytes), overflow)
+    }
+
+    // Returns `self >> shift` and a flag if `shift >= 32`, in which case the result is zero
+    pub fn overflowing_shr<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> (Self, Boolean<F>) {
+        let bytes = self.decompose_into_bytes(cs);
+        let (bytes, overflow) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, false, false);
+
+        (Self::from_le_bytes(cs, bytes), overflow)
+    }
+
+    // Rotates left by `shift` modulo 32
+    pub fn rotate_left_variable<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> Self {
+        let bytes = self.decompose_into_bytes(cs);
+        let (bytes, _) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, true, true);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Rotates right by `shift` modulo 32
+    pub fn rotate_right_variable<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> Self {
+        let bytes = self.decompose_into_bytes(cs);
+        let (bytes, _) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, false, true);
+
+        Self::from_le_bytes(cs, bytes)
+    }
 }
 
 use crate::gadgets::traits::encodable::CircuitVarLengthEncodable;
@@ -852,6 +993,46 @@ impl<F: SmallField> CircuitVarLengthEncodable<F> for UInt32<F> {
     }
 }
 
+use crate::gadgets::traits::encodable::{
+    CircuitDecodable, CircuitEncodable, CircuitEncodingLayout,
+};
+
+impl<F: SmallField> CircuitEncodable<F, 1> for UInt32<F> {
+    #[inline(always)]
+    fn encode<CS: ConstraintSystem<F>>(&self, _cs: &mut CS) -> [Variable; 1] {
+        [self.variable]
+    }
+}
+
+impl<F: SmallField> CircuitDecodable<F, 1> for UInt32<F> {
+    #[inline(always)]
+    fn decode<CS: ConstraintSystem<F>>(cs: &mut CS, encoding: [Variable; 1]) -> Self {
+        Self::from_variable_checked(cs, encoding[0])
+    }
+}
+
+use crate::gadgets::traits::public_input::CSPublicInput;
+
+impl<F: SmallField> CSPublicInput<F, 1> for UInt32<F> {
+    #[inline(always)]
+    fn encode_witness(witness: &Self::Witness) -> [F; 1] {
+        [F::from_u64_unchecked(*witness as u64)]
+    }
+    #[inline(always)]
+    fn decode_witness(encoding: [F; 1]) -> Option<Self::Witness> {
+        let value = encoding[0].as_u64_reduced();
+        if value >= 1 << 32 {
+            return None;
+        }
+
+        Some(value as u32)
+    }
+}
+
+impl<F: SmallField> CircuitEncodingLayout<F> for UInt32<F> {
+    const ENCODING_LENGTH: usize = 1;
+}
+
 use crate::gadgets::traits::allocatable::CSPlaceholder;
 
 impl<F: SmallField> CSPlaceholder<F> for UInt32<F> {
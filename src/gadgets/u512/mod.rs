@@ -9,6 +9,7 @@ use crate::gadgets::traits::witnessable::CSWitnessable;
 use crate::gadgets::traits::witnessable::WitnessHookable;
 use crate::gadgets::u256::UInt256;
 use crate::gadgets::u32::UInt32;
+use crate::gadgets::u8::bitwise;
 use crate::gadgets::u8::UInt8;
 use ethereum_types::U256;
 
@@ -138,6 +139,28 @@ impl<F: SmallField> Selectable<F> for UInt512<F> {
     }
 }
 
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+
+impl<F: SmallField> CircuitEq<F> for UInt512<F> {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        UInt512::equals(cs, a, b)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        <[UInt32<F>; 16] as CircuitEq<F>>::enforce_equal(cs, &a.inner, &b.inner)
+    }
+}
+
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitOrd<F> for UInt512<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        let (_, borrow) = a.overflowing_sub(cs, b);
+
+        borrow
+    }
+}
+
 impl<F: SmallField> UInt512<F> {
     pub fn allocated_constant<CS: ConstraintSystem<F>>(
         cs: &mut CS,
@@ -327,6 +350,164 @@ impl<F: SmallField> UInt512<F> {
             ],
         }
     }
+
+    pub fn xor<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Self {
+        let a = self.to_le_bytes(cs);
+        let b = other.to_le_bytes(cs);
+        let bytes = bitwise::xor_bytes(cs, &a, &b);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    pub fn and<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Self {
+        let a = self.to_le_bytes(cs);
+        let b = other.to_le_bytes(cs);
+        let bytes = bitwise::and_bytes(cs, &a, &b);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    pub fn or<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Self {
+        let a = self.to_le_bytes(cs);
+        let b = other.to_le_bytes(cs);
+        let bytes = bitwise::or_bytes(cs, &a, &b);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    pub fn not<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Self {
+        let bytes = self.to_le_bytes(cs);
+        let bytes = bitwise::not_bytes(cs, &bytes);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Returns `self << amount`, `amount` must be less than 512
+    pub fn shl<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        assert!(amount < 512);
+        if amount % 32 == 0 {
+            // whole limbs are just moved
+            let offset = (amount / 32) as usize;
+            let zero = UInt32::zero(cs);
+            let inner = std::array::from_fn(|idx| {
+                idx.checked_sub(offset)
+                    .map(|src| self.inner[src])
+                    .unwrap_or(zero)
+            });
+
+            return Self { inner };
+        }
+
+        let bytes = self.to_le_bytes(cs);
+        let bytes = bitwise::shl_bytes(cs, &bytes, amount as usize, false);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Returns `self >> amount`, `amount` must be less than 512
+    pub fn shr<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        assert!(amount < 512);
+        if amount % 32 == 0 {
+            // whole limbs are just moved
+            let offset = (amount / 32) as usize;
+            let zero = UInt32::zero(cs);
+            let inner = std::array::from_fn(|idx| {
+                Some(idx + offset)
+                    .filter(|src| *src < 16)
+                    .map(|src| self.inner[src])
+                    .unwrap_or(zero)
+            });
+
+            return Self { inner };
+        }
+
+        let bytes = self.to_le_bytes(cs);
+        let bytes = bitwise::shr_bytes(cs, &bytes, amount as usize, false);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Rotates left by `amount` modulo 512
+    pub fn rotate_left<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        let amount = amount % 512;
+        if amount % 32 == 0 {
+            // whole limbs are just moved
+            let offset = (amount / 32) as usize;
+            let inner = std::array::from_fn(|idx| self.inner[(idx + 16 - offset) % 16]);
+
+            return Self { inner };
+        }
+
+        let bytes = self.to_le_bytes(cs);
+        let bytes = bitwise::shl_bytes(cs, &bytes, amount as usize, true);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Rotates right by `amount` modulo 512
+    pub fn rotate_right<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        let amount = amount % 512;
+        if amount % 32 == 0 {
+            // whole limbs are just moved
+            let offset = (amount / 32) as usize;
+            let inner = std::array::from_fn(|idx| self.inner[(idx + offset) % 16]);
+
+            return Self { inner };
+        }
+
+        let bytes = self.to_le_bytes(cs);
+        let bytes = bitwise::shr_bytes(cs, &bytes, amount as usize, true);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Returns `self << shift` and a flag if `shift >= 512`, in which case the result is zero
+    pub fn overflowing_shl<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> (Self, Boolean<F>) {
+        let bytes = self.to_le_bytes(cs);
+        let (bytes, overflow) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, true, false);
+
+        (Self::from_le_bytes(cs, bytes), overflow)
+    }
+
+    // Returns `self >> shift` and a flag if `shift >= 512`, in which case the result is zero
+    pub fn overflowing_shr<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> (Self, Boolean<F>) {
+        let bytes = self.to_le_bytes(cs);
+        let (bytes, overflow) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, false, false);
+
+        (Self::from_le_bytes(cs, bytes), overflow)
+    }
+
+    // Rotates left by `shift` modulo 512
+    pub fn rotate_left_variable<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> Self {
+        let bytes = self.to_le_bytes(cs);
+        let (bytes, _) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, true, true);
+
+        Self::from_le_bytes(cs, bytes)
+    }
+
+    // Rotates right by `shift` modulo 512
+    pub fn rotate_right_variable<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> Self {
+        let bytes = self.to_le_bytes(cs);
+        let (bytes, _) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, false, true);
+
+        Self::from_le_bytes(cs, bytes)
+    }
 }
 
 use crate::cs::Variable;
@@ -0,0 +1,592 @@
+use super::*;
+use crate::config::*;
+use crate::gadgets::blake2s::mixing_function::{merge_byte_using_table, split_byte_using_table};
+use crate::gadgets::tables::and8::And8Table;
+use crate::gadgets::traits::selectable::Selectable;
+use crate::gadgets::u32::UInt32;
+
+// Bitwise operations and shifts over little-endian arrays of bytes, shared by all the unsigned
+// integer gadgets. Logical operations are driven by the `Xor8Table` and `And8Table`
+// (or by the packed `BinopTable` if those are not present), and shifts by the `ByteSplitTable`s
+// for split points 1..=4.
+// Shifts and rotations by multiples of 8 are just a rearrangement of bytes and are free.
+
+// returns [xor, or, and] of two bytes using the packed table
+fn binop_via_packed_table<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    table_id: u32,
+    a: Variable,
+    b: Variable,
+) -> [Variable; 3] {
+    let [packed] = cs.perform_lookup::<2, 1>(table_id, &[a, b]);
+    let results = cs.alloc_multiple_variables_without_values::<3>();
+
+    if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
+        let value_fn = move |input: [F; 1]| {
+            let packed = input[0].as_u64_reduced();
+
+            [
+                F::from_u64_unchecked(packed >> 32),
+                F::from_u64_unchecked((packed >> 16) & 0xff),
+                F::from_u64_unchecked(packed & 0xff),
+            ]
+        };
+
+        cs.set_values_with_dependencies(
+            &[packed.into()],
+            &Place::from_variables(results),
+            value_fn,
+        );
+    }
+
+    range_check_u8_pair(cs, &[results[0], results[2]]);
+    range_check_u8(cs, results[1]);
+
+    let [xor, or, and] = results;
+    Num::enforce_zero_for_linear_combination(
+        cs,
+        &[
+            (xor, F::from_u64_unchecked(1u64 << 32)),
+            (or, F::from_u64_unchecked(1u64 << 16)),
+            (and, F::ONE),
+            (packed, F::MINUS_ONE),
+        ],
+    );
+
+    results
+}
+
+pub(crate) fn xor_bytes<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    a: &[UInt8<F>; N],
+    b: &[UInt8<F>; N],
+) -> [UInt8<F>; N] {
+    let mut result = *a;
+    if let Some(table_id) = cs.get_table_id_for_marker::<Xor8Table>() {
+        for ((a, b), dst) in a.iter().zip(b.iter()).zip(result.iter_mut()) {
+            let [xor] = cs.perform_lookup::<2, 1>(table_id, &[a.variable, b.variable]);
+            *dst = unsafe { UInt8::from_variable_unchecked(xor) };
+        }
+    } else {
+        let table_id = cs
+            .get_table_id_for_marker::<BinopTable>()
+            .expect("table must exist");
+        for ((a, b), dst) in a.iter().zip(b.iter()).zip(result.iter_mut()) {
+            let [xor, _, _] = binop_via_packed_table(cs, table_id, a.variable, b.variable);
+            *dst = unsafe { UInt8::from_variable_unchecked(xor) };
+        }
+    }
+
+    result
+}
+
+pub(crate) fn and_bytes<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    a: &[UInt8<F>; N],
+    b: &[UInt8<F>; N],
+) -> [UInt8<F>; N] {
+    let mut result = *a;
+    if let Some(table_id) = cs.get_table_id_for_marker::<And8Table>() {
+        for ((a, b), dst) in a.iter().zip(b.iter()).zip(result.iter_mut()) {
+            let [and] = cs.perform_lookup::<2, 1>(table_id, &[a.variable, b.variable]);
+            *dst = unsafe { UInt8::from_variable_unchecked(and) };
+        }
+    } else {
+        let table_id = cs
+            .get_table_id_for_marker::<BinopTable>()
+            .expect("table must exist");
+        for ((a, b), dst) in a.iter().zip(b.iter()).zip(result.iter_mut()) {
+            let [_, _, and] = binop_via_packed_table(cs, table_id, a.variable, b.variable);
+            *dst = unsafe { UInt8::from_variable_unchecked(and) };
+        }
+    }
+
+    result
+}
+
+pub(crate) fn or_bytes<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    a: &[UInt8<F>; N],
+    b: &[UInt8<F>; N],
+) -> [UInt8<F>; N] {
+    if let Some(table_id) = cs.get_table_id_for_marker::<BinopTable>() {
+        let mut result = *a;
+        for ((a, b), dst) in a.iter().zip(b.iter()).zip(result.iter_mut()) {
+            let [_, or, _] = binop_via_packed_table(cs, table_id, a.variable, b.variable);
+            *dst = unsafe { UInt8::from_variable_unchecked(or) };
+        }
+
+        return result;
+    }
+
+    // a | b = (a ^ b) + (a & b), and the sum is a byte
+    let xor = xor_bytes(cs, a, b);
+    let and = and_bytes(cs, a, b);
+    let mut result = *a;
+    for ((xor, and), dst) in xor.iter().zip(and.iter()).zip(result.iter_mut()) {
+        let or = Num::linear_combination(cs, &[(xor.variable, F::ONE), (and.variable, F::ONE)]);
+        *dst = unsafe { UInt8::from_variable_unchecked(or.variable) };
+    }
+
+    result
+}
+
+pub(crate) fn not_bytes<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    a: &[UInt8<F>; N],
+) -> [UInt8<F>; N] {
+    // !a = 255 - a for a byte, so no lookups are needed
+    let max = cs.allocate_constant(F::from_u64_unchecked(u8::MAX as u64));
+    a.map(|el| {
+        let not = Num::linear_combination(cs, &[(max, F::ONE), (el.variable, F::MINUS_ONE)]);
+        unsafe { UInt8::from_variable_unchecked(not.variable) }
+    })
+}
+
+// returns (low, high) parts of every byte
+fn split_bytes_at<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    bytes: &[UInt8<F>; N],
+    split_at: usize,
+) -> [(Variable, Variable); N] {
+    bytes.map(|el| match split_at {
+        1 => split_byte_using_table::<F, CS, 1>(cs, el.variable),
+        2 => split_byte_using_table::<F, CS, 2>(cs, el.variable),
+        3 => split_byte_using_table::<F, CS, 3>(cs, el.variable),
+        4 => split_byte_using_table::<F, CS, 4>(cs, el.variable),
+        5..=7 => split_byte_at_high_point(cs, el.variable, split_at),
+        _ => unreachable!(),
+    })
+}
+
+// Only the split tables for 1..=4 are usually present, so for split points above 4 we witness
+// the parts, prove the decomposition with FMA and range check the parts by merging them
+// in swapped order via the table for `8 - split_at`
+fn split_byte_at_high_point<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    input: Variable,
+    split_at: usize,
+) -> (Variable, Variable) {
+    debug_assert!(split_at > 4);
+    debug_assert!(split_at < 8);
+
+    let [low, high] = cs.alloc_multiple_variables_without_values::<2>();
+
+    if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
+        let value_fn = move |input: [F; 1]| {
+            let input = <u8 as WitnessCastable<F, F>>::cast_from_source(input[0]);
+            let low = input & ((1u8 << split_at) - 1);
+            let high = input >> split_at;
+
+            [
+                F::from_u64_unchecked(low as u64),
+                F::from_u64_unchecked(high as u64),
+            ]
+        };
+
+        let outputs = Place::from_variables([low, high]);
+        cs.set_values_with_dependencies(&[input.into()], &outputs, value_fn);
+    }
+
+    // input = high * 2^split_at + low
+    let one = cs.allocate_constant(F::ONE);
+    let gate = FmaGateInBaseFieldWithoutConstant {
+        params: FmaGateInBaseWithoutConstantParams {
+            coeff_for_quadtaric_part: F::from_u64_unchecked(1u64 << split_at),
+            linear_term_coeff: F::ONE,
+        },
+        quadratic_part: (one, high),
+        linear_part: low,
+        rhs_part: input,
+    };
+    gate.add_to_cs(cs);
+
+    // high < 2^(8 - split_at) and low < 2^split_at
+    let _ = match 8 - split_at {
+        1 => merge_byte_using_table::<F, CS, 1>(cs, high, low),
+        2 => merge_byte_using_table::<F, CS, 2>(cs, high, low),
+        3 => merge_byte_using_table::<F, CS, 3>(cs, high, low),
+        _ => unreachable!(),
+    };
+
+    (low, high)
+}
+
+// high * 2^shift + low for parts of the byte that don't overlap, absent parts are zeroes
+fn merge_parts<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    high: Option<Variable>,
+    low: Option<Variable>,
+    shift: usize,
+) -> UInt8<F> {
+    let variable = match (high, low) {
+        (Some(high), Some(low)) => {
+            Num::linear_combination(
+                cs,
+                &[(high, F::from_u64_unchecked(1u64 << shift)), (low, F::ONE)],
+            )
+            .variable
+        }
+        (Some(high), None) => {
+            let zero = cs.allocate_constant(F::ZERO);
+            Num::linear_combination(
+                cs,
+                &[(high, F::from_u64_unchecked(1u64 << shift)), (zero, F::ONE)],
+            )
+            .variable
+        }
+        (None, Some(low)) => low,
+        (None, None) => cs.allocate_constant(F::ZERO),
+    };
+
+    unsafe { UInt8::from_variable_unchecked(variable) }
+}
+
+// Shifts (or rotates) little-endian bytes towards the most significant ones by a constant amount
+pub(crate) fn shl_bytes<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    bytes: &[UInt8<F>; N],
+    shift: usize,
+    rotate: bool,
+) -> [UInt8<F>; N] {
+    debug_assert!(shift < 8 * N);
+
+    let byte_shift = shift / 8;
+    let bit_shift = shift % 8;
+    let source = |idx: usize, offset: usize| -> Option<usize> {
+        if rotate {
+            Some((idx + N - offset % N) % N)
+        } else {
+            idx.checked_sub(offset)
+        }
+    };
+
+    if bit_shift == 0 {
+        let zero = UInt8::zero(cs);
+        return std::array::from_fn(|idx| {
+            source(idx, byte_shift)
+                .map(|src| bytes[src])
+                .unwrap_or(zero)
+        });
+    }
+
+    // every byte is split into `8 - bit_shift` low bits that move higher within the same byte,
+    // and `bit_shift` high bits that move into the next byte
+    let parts = split_bytes_at(cs, bytes, 8 - bit_shift);
+
+    std::array::from_fn(|idx| {
+        let high = source(idx, byte_shift).map(|src| parts[src].0);
+        let low = source(idx, byte_shift + 1).map(|src| parts[src].1);
+
+        merge_parts(cs, high, low, bit_shift)
+    })
+}
+
+// Shifts (or rotates) little-endian bytes towards the least significant ones by a constant amount
+pub(crate) fn shr_bytes<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    bytes: &[UInt8<F>; N],
+    shift: usize,
+    rotate: bool,
+) -> [UInt8<F>; N] {
+    debug_assert!(shift < 8 * N);
+
+    let byte_shift = shift / 8;
+    let bit_shift = shift % 8;
+    let source = |idx: usize, offset: usize| -> Option<usize> {
+        if rotate {
+            Some((idx + offset) % N)
+        } else {
+            Some(idx + offset).filter(|src| *src < N)
+        }
+    };
+
+    if bit_shift == 0 {
+        let zero = UInt8::zero(cs);
+        return std::array::from_fn(|idx| {
+            source(idx, byte_shift)
+                .map(|src| bytes[src])
+                .unwrap_or(zero)
+        });
+    }
+
+    // every byte is split into `bit_shift` low bits that move into the previous byte,
+    // and `8 - bit_shift` high bits that move lower within the same byte
+    let parts = split_bytes_at(cs, bytes, bit_shift);
+
+    std::array::from_fn(|idx| {
+        let high = source(idx, byte_shift + 1).map(|src| parts[src].0);
+        let low = source(idx, byte_shift).map(|src| parts[src].1);
+
+        merge_parts(cs, high, low, 8 - bit_shift)
+    })
+}
+
+// Barrel shifter over the bits of the variable shift amount. Returns a flag whether the amount
+// is not less than the bit width, in which case shifts give zero, and rotations use the amount
+// modulo the bit width
+pub(crate) fn shift_bytes_by_variable<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    bytes: &[UInt8<F>; N],
+    shift: &UInt32<F>,
+    left: bool,
+    rotate: bool,
+) -> ([UInt8<F>; N], Boolean<F>) {
+    debug_assert!(N.is_power_of_two());
+
+    let log_width = (8 * N).trailing_zeros() as usize;
+    let bits = shift.into_num().spread_into_bits::<CS, 32>(cs);
+    let overflow = Boolean::multi_or(cs, &bits[log_width..]);
+
+    let mut result = *bytes;
+    for (idx, bit) in bits[..log_width].iter().enumerate() {
+        let shifted = if left {
+            shl_bytes(cs, &result, 1 << idx, rotate)
+        } else {
+            shr_bytes(cs, &result, 1 << idx, rotate)
+        };
+        result = UInt8::parallel_select(cs, *bit, &shifted, &result);
+    }
+
+    if !rotate {
+        result = result.map(|el| el.mask_negated(cs, overflow));
+    }
+
+    (result, overflow)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, StaticToolboxHolder};
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::tables::{
+        and8::create_and8_table,
+        byte_split::{create_byte_split_table, ByteSplitTable},
+        xor8::create_xor8_table,
+    };
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::gadgets::u256::UInt256;
+    use crate::worker::Worker;
+    use ethereum_types::U256;
+
+    type F = GoldilocksField;
+
+    fn create_test_cs() -> CSReferenceImplementation<
+        F,
+        F,
+        DevCSConfig,
+        impl GateConfigurationHolder<F>,
+        impl StaticToolboxHolder,
+    > {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 20, 1 << 17);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 8,
+                share_table_id: true,
+            },
+        );
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ParallelSelectionGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(());
+
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+
+        let table = create_and8_table();
+        owned_cs.add_lookup_table::<And8Table, 3>(table);
+
+        let table = create_byte_split_table::<F, 1>();
+        owned_cs.add_lookup_table::<ByteSplitTable<1>, 3>(table);
+        let table = create_byte_split_table::<F, 2>();
+        owned_cs.add_lookup_table::<ByteSplitTable<2>, 3>(table);
+        let table = create_byte_split_table::<F, 3>();
+        owned_cs.add_lookup_table::<ByteSplitTable<3>, 3>(table);
+        let table = create_byte_split_table::<F, 4>();
+        owned_cs.add_lookup_table::<ByteSplitTable<4>, 3>(table);
+
+        owned_cs
+    }
+
+    #[test]
+    fn test_bitwise_u32() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let a_value = 0x9abc_def1u32;
+        let b_value = 0x1357_9bdfu32;
+        let a = UInt32::allocate_checked(cs, a_value);
+        let b = UInt32::allocate_checked(cs, b_value);
+
+        let xor = a.xor(cs, &b);
+        assert_eq!(xor.witness_hook(&*cs)().unwrap(), a_value ^ b_value);
+        let and = a.and(cs, &b);
+        assert_eq!(and.witness_hook(&*cs)().unwrap(), a_value & b_value);
+        let or = a.or(cs, &b);
+        assert_eq!(or.witness_hook(&*cs)().unwrap(), a_value | b_value);
+        let not = a.not(cs);
+        assert_eq!(not.witness_hook(&*cs)().unwrap(), !a_value);
+
+        for amount in [0u32, 1, 7, 8, 13, 24, 31] {
+            let shl = a.shl(cs, amount);
+            assert_eq!(shl.witness_hook(&*cs)().unwrap(), a_value << amount);
+            let shr = a.shr(cs, amount);
+            assert_eq!(shr.witness_hook(&*cs)().unwrap(), a_value >> amount);
+            let rotl = a.rotate_left(cs, amount);
+            assert_eq!(
+                rotl.witness_hook(&*cs)().unwrap(),
+                a_value.rotate_left(amount)
+            );
+            let rotr = a.rotate_right(cs, amount);
+            assert_eq!(
+                rotr.witness_hook(&*cs)().unwrap(),
+                a_value.rotate_right(amount)
+            );
+        }
+
+        for amount in [0u32, 5, 16, 31, 32, 45, 1 << 20] {
+            let shift = UInt32::allocate_checked(cs, amount);
+            let expected_overflow = amount >= 32;
+
+            let (shl, overflow) = a.overflowing_shl(cs, &shift);
+            assert_eq!(
+                shl.witness_hook(&*cs)().unwrap(),
+                a_value.checked_shl(amount).unwrap_or(0)
+            );
+            assert_eq!(overflow.witness_hook(&*cs)().unwrap(), expected_overflow);
+
+            let (shr, overflow) = a.overflowing_shr(cs, &shift);
+            assert_eq!(
+                shr.witness_hook(&*cs)().unwrap(),
+                a_value.checked_shr(amount).unwrap_or(0)
+            );
+            assert_eq!(overflow.witness_hook(&*cs)().unwrap(), expected_overflow);
+
+            let rotl = a.rotate_left_variable(cs, &shift);
+            assert_eq!(
+                rotl.witness_hook(&*cs)().unwrap(),
+                a_value.rotate_left(amount)
+            );
+            let rotr = a.rotate_right_variable(cs, &shift);
+            assert_eq!(
+                rotr.witness_hook(&*cs)().unwrap(),
+                a_value.rotate_right(amount)
+            );
+        }
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    fn test_bitwise_u8_and_u256() {
+        let mut owned_cs = create_test_cs();
+        let cs = &mut owned_cs;
+
+        let a_value = 0b1011_0110u8;
+        let b_value = 0b0110_0011u8;
+        let a = UInt8::allocate_checked(cs, a_value);
+        let b = UInt8::allocate_checked(cs, b_value);
+
+        let or = a.or(cs, &b);
+        assert_eq!(or.witness_hook(&*cs)().unwrap(), a_value | b_value);
+        for amount in 0u32..8 {
+            let rotl = a.rotate_left(cs, amount);
+            assert_eq!(
+                rotl.witness_hook(&*cs)().unwrap(),
+                a_value.rotate_left(amount)
+            );
+            let shr = a.shr(cs, amount);
+            assert_eq!(shr.witness_hook(&*cs)().unwrap(), a_value >> amount);
+        }
+
+        let a_value = U256::from_dec_str(
+            "21888242871839275222246405745257275088696311157297823662689037894645226208583",
+        )
+        .unwrap();
+        let b_value = U256::from(0x1234_5678_9abc_def0u64) << 100;
+        let a = UInt256::allocate(cs, a_value);
+        let b = UInt256::allocate(cs, b_value);
+
+        let xor = a.xor(cs, &b);
+        assert_eq!(xor.witness_hook(&*cs)().unwrap(), a_value ^ b_value);
+        let and = a.and(cs, &b);
+        assert_eq!(and.witness_hook(&*cs)().unwrap(), a_value & b_value);
+        let not = a.not(cs);
+        assert_eq!(not.witness_hook(&*cs)().unwrap(), !a_value);
+
+        for amount in [0u32, 3, 32, 100, 255] {
+            let shl = a.shl(cs, amount);
+            assert_eq!(
+                shl.witness_hook(&*cs)().unwrap(),
+                a_value << amount as usize
+            );
+            let shr = a.shr(cs, amount);
+            assert_eq!(
+                shr.witness_hook(&*cs)().unwrap(),
+                a_value >> amount as usize
+            );
+            let rotl = a.rotate_left(cs, amount);
+            let expected = if amount == 0 {
+                a_value
+            } else {
+                (a_value << amount as usize) | (a_value >> (256 - amount as usize))
+            };
+            assert_eq!(rotl.witness_hook(&*cs)().unwrap(), expected);
+        }
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
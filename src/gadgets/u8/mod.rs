@@ -18,8 +18,11 @@ use crate::gadgets::tables::ByteSplitTable;
 use crate::gadgets::traits::allocatable::CSAllocatable;
 use crate::gadgets::traits::allocatable::CSAllocatableExt;
 use crate::gadgets::traits::castable::WitnessCastable;
+use crate::gadgets::u32::UInt32;
 use crate::{cs::Variable, field::SmallField};
 
+pub(crate) mod bitwise;
+
 #[inline(always)]
 pub fn get_8_by_8_range_check_table<F: SmallField, CS: ConstraintSystem<F>>(
     cs: &CS,
@@ -291,6 +294,32 @@ impl<F: SmallField> Selectable<F> for UInt8<F> {
     }
 }
 
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+
+impl<F: SmallField> CircuitEq<F> for UInt8<F> {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        UInt8::equals(cs, a, b)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        Num::enforce_equal(
+            cs,
+            &Num::from_variable(a.get_variable()),
+            &Num::from_variable(b.get_variable()),
+        )
+    }
+}
+
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitOrd<F> for UInt8<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        let (_, borrow) = a.overflowing_sub(cs, b);
+
+        borrow
+    }
+}
+
 impl<F: SmallField> UInt8<F> {
     #[inline]
     pub const fn get_variable(&self) -> Variable {
@@ -517,6 +546,121 @@ impl<F: SmallField> UInt8<F> {
             _marker: std::marker::PhantomData,
         }
     }
+
+    pub fn xor<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Self {
+        let a = [*self];
+        let b = [*other];
+        let bytes = bitwise::xor_bytes(cs, &a, &b);
+
+        bytes[0]
+    }
+
+    pub fn and<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Self {
+        let a = [*self];
+        let b = [*other];
+        let bytes = bitwise::and_bytes(cs, &a, &b);
+
+        bytes[0]
+    }
+
+    pub fn or<CS: ConstraintSystem<F>>(&self, cs: &mut CS, other: &Self) -> Self {
+        let a = [*self];
+        let b = [*other];
+        let bytes = bitwise::or_bytes(cs, &a, &b);
+
+        bytes[0]
+    }
+
+    pub fn not<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Self {
+        let bytes = [*self];
+        let bytes = bitwise::not_bytes(cs, &bytes);
+
+        bytes[0]
+    }
+
+    // Returns `self << amount`, `amount` must be less than 8
+    pub fn shl<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        assert!(amount < 8);
+        let bytes = [*self];
+        let bytes = bitwise::shl_bytes(cs, &bytes, amount as usize, false);
+
+        bytes[0]
+    }
+
+    // Returns `self >> amount`, `amount` must be less than 8
+    pub fn shr<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        assert!(amount < 8);
+        let bytes = [*self];
+        let bytes = bitwise::shr_bytes(cs, &bytes, amount as usize, false);
+
+        bytes[0]
+    }
+
+    // Rotates left by `amount` modulo 8
+    pub fn rotate_left<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        let amount = amount % 8;
+        let bytes = [*self];
+        let bytes = bitwise::shl_bytes(cs, &bytes, amount as usize, true);
+
+        bytes[0]
+    }
+
+    // Rotates right by `amount` modulo 8
+    pub fn rotate_right<CS: ConstraintSystem<F>>(&self, cs: &mut CS, amount: u32) -> Self {
+        let amount = amount % 8;
+        let bytes = [*self];
+        let bytes = bitwise::shr_bytes(cs, &bytes, amount as usize, true);
+
+        bytes[0]
+    }
+
+    // Returns `self << shift` and a flag if `shift >= 8`, in which case the result is zero
+    pub fn overflowing_shl<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> (Self, Boolean<F>) {
+        let bytes = [*self];
+        let (bytes, overflow) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, true, false);
+
+        (bytes[0], overflow)
+    }
+
+    // Returns `self >> shift` and a flag if `shift >= 8`, in which case the result is zero
+    pub fn overflowing_shr<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> (Self, Boolean<F>) {
+        let bytes = [*self];
+        let (bytes, overflow) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, false, false);
+
+        (bytes[0], overflow)
+    }
+
+    // Rotates left by `shift` modulo 8
+    pub fn rotate_left_variable<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> Self {
+        let bytes = [*self];
+        let (bytes, _) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, true, true);
+
+        bytes[0]
+    }
+
+    // Rotates right by `shift` modulo 8
+    pub fn rotate_right_variable<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        shift: &UInt32<F>,
+    ) -> Self {
+        let bytes = [*self];
+        let (bytes, _) = bitwise::shift_bytes_by_variable(cs, &bytes, shift, false, true);
+
+        bytes[0]
+    }
 }
 
 use crate::gadgets::traits::encodable::CircuitVarLengthEncodable;
@@ -531,6 +675,47 @@ impl<F: SmallField> CircuitVarLengthEncodable<F> for UInt8<F> {
     }
 }
 
+use crate::gadgets::traits::encodable::{
+    CircuitDecodable, CircuitEncodable, CircuitEncodingLayout,
+};
+
+impl<F: SmallField> CircuitEncodable<F, 1> for UInt8<F> {
+    #[inline(always)]
+    fn encode<CS: ConstraintSystem<F>>(&self, _cs: &mut CS) -> [Variable; 1] {
+        [self.variable]
+    }
+}
+
+impl<F: SmallField> CircuitDecodable<F, 1> for UInt8<F> {
+    #[inline(always)]
+    fn decode<CS: ConstraintSystem<F>>(cs: &mut CS, encoding: [Variable; 1]) -> Self {
+        Self::from_variable_checked(cs, encoding[0])
+    }
+}
+
+use crate::gadgets::traits::public_input::CSPublicInput;
+
+impl<F: SmallField> CSPublicInput<F, 1> for UInt8<F> {
+    #[inline(always)]
+    fn encode_witness(witness: &Self::Witness) -> [F; 1] {
+        [F::from_u64_unchecked(*witness as u64)]
+    }
+    #[inline(always)]
+    fn decode_witness(encoding: [F; 1]) -> Option<Self::Witness> {
+        let value = encoding[0].as_u64_reduced();
+        if value >= 1 << 8 {
+            return None;
+        }
+
+        Some(value as u8)
+    }
+}
+
+impl<F: SmallField> CircuitEncodingLayout<F> for UInt8<F> {
+    const ENCODING_LENGTH: usize = 1;
+    const PACKED_WIDTH: usize = 8;
+}
+
 use crate::gadgets::traits::allocatable::CSPlaceholder;
 
 impl<F: SmallField> CSPlaceholder<F> for UInt8<F> {
@@ -1,4 +1,5 @@
 pub mod poseidon2;
+pub mod poseidon2_baby_bear;
 pub mod poseidon_goldilocks_naive;
 pub mod poseidon_goldilocks_params;
 pub mod suggested_mds;